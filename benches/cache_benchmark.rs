@@ -1,6 +1,21 @@
 use criterion::{black_box, criterion_group, criterion_main, Criterion};
 use lru_cache::Cache;
-use lru_cache::cache::traits::CacheStorage;
+use lru_cache::cache::clock::MockClock;
+use lru_cache::cache::clock_cache::ClockCache;
+use lru_cache::cache::concurrent::ConcurrentCache;
+use lru_cache::cache::fifo::FifoCache;
+use lru_cache::cache::random::RandomCache;
+use lru_cache::cache::sampled::SampledLruCache;
+use lru_cache::cache::sharded::ShardedCache;
+use lru_cache::cache::testing::{hit_rate, zipf_trace};
+use lru_cache::cache::traits::{CacheStorage, PersistentStorage};
+use lru_cache::storage::backend::FileBackend;
+use lru_cache::storage::file::FileStorage;
+use std::fmt;
+use std::num::NonZeroUsize;
+use std::str::FromStr;
+use std::thread;
+use std::time::Duration;
 
 fn cache_operations(c: &mut Criterion) {
     c.bench_function("put_operation", |b| {
@@ -38,5 +53,283 @@ fn cache_operations(c: &mut Criterion) {
     });
 }
 
-criterion_group!(benches, cache_operations);
+fn clock_vs_lru(c: &mut Criterion) {
+    const CAPACITY: u64 = 100_000;
+    let mut group = c.benchmark_group("get_100k");
+
+    let mut lru = Cache::new(CAPACITY as usize);
+    let mut clock = ClockCache::new(CAPACITY as usize);
+    for i in 0..CAPACITY {
+        lru.put(i, i);
+        clock.put(i, i);
+    }
+
+    group.bench_function("lru", |b| {
+        let mut i = 0;
+        b.iter(|| {
+            black_box(lru.get(&black_box(i)));
+            i = (i + 7919) % CAPACITY;
+        })
+    });
+    group.bench_function("clock", |b| {
+        let mut i = 0;
+        b.iter(|| {
+            black_box(clock.get(&black_box(i)));
+            i = (i + 7919) % CAPACITY;
+        })
+    });
+    group.finish();
+}
+
+fn replacement_policies(c: &mut Criterion) {
+    const CAPACITY: usize = 1_000;
+    let trace = zipf_trace(10_000, 100_000, 1.0, 42);
+    let mut group = c.benchmark_group("zipf_100k");
+
+    group.bench_function("lru", |b| b.iter(|| hit_rate(&mut Cache::new(CAPACITY), black_box(&trace))));
+    group.bench_function("fifo", |b| b.iter(|| hit_rate(&mut FifoCache::new(CAPACITY), black_box(&trace))));
+    group.bench_function("random", |b| {
+        b.iter(|| hit_rate(&mut RandomCache::with_seed(CAPACITY, 42), black_box(&trace)))
+    });
+    group.finish();
+}
+
+/// LRU exact contre LRU échantillonné sur un million d'entrées : lectures
+/// réparties sur tout le cache, puis insertions de nouvelles clés qui
+/// évincent à chaque fois
+fn sampled_vs_exact_lru(c: &mut Criterion) {
+    const CAPACITY: u64 = 1_000_000;
+    let mut exact = Cache::new(CAPACITY as usize);
+    let mut sampled = SampledLruCache::with_seed(CAPACITY as usize, 42);
+    for i in 0..CAPACITY {
+        exact.put(i, i);
+        sampled.put(i, i);
+    }
+
+    let mut group = c.benchmark_group("get_1m");
+    group.bench_function("lru", |b| {
+        let mut i = 0;
+        b.iter(|| {
+            black_box(exact.get(&black_box(i)));
+            i = (i + 7919) % CAPACITY;
+        })
+    });
+    group.bench_function("sampled", |b| {
+        let mut i = 0;
+        b.iter(|| {
+            black_box(sampled.get(&black_box(i)));
+            i = (i + 7919) % CAPACITY;
+        })
+    });
+    group.finish();
+
+    let mut group = c.benchmark_group("put_1m");
+    group.bench_function("lru", |b| {
+        let mut i = CAPACITY;
+        b.iter(|| {
+            exact.put(black_box(i), i);
+            i += 1;
+        })
+    });
+    group.bench_function("sampled", |b| {
+        let mut i = CAPACITY;
+        b.iter(|| {
+            sampled.put(black_box(i), i);
+            i += 1;
+        })
+    });
+    group.finish();
+}
+
+/// Fait exécuter `operation` par `THREADS` threads sur les clés d'une trace
+/// zipfienne, chaque thread en parcourant une portion différente
+fn contend(trace: &[u64], operation: impl Fn(usize, u64) + Sync) {
+    const THREADS: usize = 8;
+    let chunk = trace.len() / THREADS;
+    thread::scope(|scope| {
+        for part in trace.chunks(chunk) {
+            let operation = &operation;
+            scope.spawn(move || {
+                for (i, &key) in part.iter().enumerate() {
+                    operation(i, key);
+                }
+            });
+        }
+    });
+}
+
+fn sharded_vs_single_lock(c: &mut Criterion) {
+    const CAPACITY: usize = 1_000;
+    let trace = zipf_trace(10_000, 80_000, 1.0, 42);
+    let mut group = c.benchmark_group("contended_80k");
+
+    group.bench_function("single_lock", |b| {
+        let cache = ConcurrentCache::new(CAPACITY);
+        b.iter(|| {
+            contend(black_box(&trace), |i, key| {
+                if i % 4 == 0 {
+                    cache.put(key, key);
+                } else {
+                    black_box(cache.get(&key));
+                }
+            })
+        })
+    });
+    group.bench_function("sharded", |b| {
+        let cache = ShardedCache::new(CAPACITY);
+        b.iter(|| {
+            contend(black_box(&trace), |i, key| {
+                if i % 4 == 0 {
+                    cache.put(key, key);
+                } else {
+                    black_box(cache.get(&key));
+                }
+            })
+        })
+    });
+    group.finish();
+}
+
+/// Compare la sauvegarde en flux de `save_to_file` au passage par
+/// [`FileBackend`], qui copie d'abord chaque entrée en `String` : l'écart
+/// de temps reflète le coût de ces allocations
+fn persistence(c: &mut Criterion) {
+    const ENTRIES: u64 = 100_000;
+    let path = std::env::temp_dir().join(format!("lru_cache_bench_{}.txt", std::process::id()));
+    let mut cache = Cache::new(ENTRIES as usize);
+    for i in 0..ENTRIES {
+        cache.put(i, format!("value_{}", i));
+    }
+    let mut group = c.benchmark_group("persistence_100k");
+    group.sample_size(10);
+
+    group.bench_function("save_streaming", |b| b.iter(|| cache.save_to_file(&path).unwrap()));
+    group.bench_function("save_backend", |b| b.iter(|| cache.save_to(&mut FileBackend::new(&path)).unwrap()));
+    group.bench_function("load_streaming", |b| {
+        b.iter(|| Cache::<u64, String>::load_from_file(&path, NonZeroUsize::new(ENTRIES as usize).unwrap()).unwrap())
+    });
+    group.bench_function("load_backend", |b| {
+        b.iter(|| Cache::<u64, String>::load_from(&mut FileBackend::new(&path), NonZeroUsize::new(ENTRIES as usize).unwrap()).unwrap())
+    });
+    group.finish();
+    let _ = std::fs::remove_file(&path);
+}
+
+/// Valeur dont la conversion depuis le texte coûte : une liste de 32
+/// nombres séparés par des virgules
+struct Samples(Vec<u32>);
+
+impl fmt::Display for Samples {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let text: Vec<String> = self.0.iter().map(u32::to_string).collect();
+        f.write_str(&text.join(","))
+    }
+}
+
+impl FromStr for Samples {
+    type Err = std::num::ParseIntError;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        text.split(',').map(str::parse).collect::<Result<_, _>>().map(Samples)
+    }
+}
+
+/// Compare les chargements et préchargements séquentiels à leurs variantes
+/// sur le pool de rayon, pour 100 000 entrées dont la valeur coûte à
+/// convertir ou à calculer
+fn parallel_warmup(c: &mut Criterion) {
+    const ENTRIES: u64 = 100_000;
+    let path = std::env::temp_dir().join(format!("lru_cache_bench_parallel_{}.txt", std::process::id()));
+    let data: Vec<(u64, Samples)> = (0..ENTRIES)
+        .map(|i| (i, Samples((0..32).map(|j| (i as u32).wrapping_mul(2_654_435_761).wrapping_add(j)).collect())))
+        .collect();
+    FileStorage::save(&path, ENTRIES as usize, &data).unwrap();
+    let compute = |key: &u64| (0..256).fold(*key, |hash, _| hash.wrapping_mul(6_364_136_223_846_793_005).rotate_left(17));
+    let mut group = c.benchmark_group("parallel_warmup_100k");
+    group.sample_size(10);
+
+    group.bench_function("load", |b| b.iter(|| FileStorage::load::<u64, Samples>(&path).unwrap()));
+    group.bench_function("load_parallel", |b| b.iter(|| FileStorage::load_parallel::<u64, Samples>(&path).unwrap()));
+    group.bench_function("warm", |b| {
+        b.iter(|| {
+            let mut cache = Cache::new(ENTRIES as usize);
+            cache.warm((0..ENTRIES).map(|key| (key, compute(&key))));
+            cache
+        })
+    });
+    group.bench_function("warm_par", |b| {
+        b.iter(|| {
+            let mut cache = Cache::new(ENTRIES as usize);
+            cache.warm_par(0..ENTRIES, compute);
+            cache
+        })
+    });
+    group.finish();
+    let _ = std::fs::remove_file(&path);
+}
+
+/// Compare `get`, qui hache toute la clé, à `get_with_hash` avec un hash
+/// calculé en amont, sur des clés de 256 octets dont les 8 premiers sont
+/// distincts
+fn precomputed_hash(c: &mut Criterion) {
+    const CAPACITY: usize = 10_000;
+    let keys: Vec<String> = (0..CAPACITY).map(|i| format!("{:08}{}", i, "x".repeat(248))).collect();
+    let digest = |key: &String| u64::from_le_bytes(key.as_bytes()[..8].try_into().unwrap());
+    let mut hashed = Cache::new(CAPACITY);
+    let mut plain = Cache::new(CAPACITY);
+    for key in &keys {
+        hashed.put_with_hash(digest(key), key.clone(), ());
+        plain.put(key.clone(), ());
+    }
+    let hashes: Vec<u64> = keys.iter().map(digest).collect();
+    let mut group = c.benchmark_group("long_keys_10k");
+
+    group.bench_function("get", |b| {
+        let mut i = 0;
+        b.iter(|| {
+            black_box(plain.get(black_box(&keys[i])));
+            i = (i + 7919) % CAPACITY;
+        })
+    });
+    group.bench_function("get_with_hash", |b| {
+        let mut i = 0;
+        b.iter(|| {
+            let key = &keys[i];
+            black_box(hashed.get_with_hash(black_box(hashes[i]), |candidate| candidate == key));
+            i = (i + 7919) % CAPACITY;
+        })
+    });
+    group.finish();
+}
+
+/// Coût de `purge_expired` sur 100 000 entrées vivantes dont les échéances
+/// sont réparties sur plusieurs heures, l'horloge avançant d'une
+/// milliseconde entre deux purges : seules les entrées dues sont examinées
+fn ttl_purge(c: &mut Criterion) {
+    const ENTRIES: u64 = 100_000;
+    let clock = MockClock::new();
+    let mut cache = Cache::with_clock(ENTRIES as usize, clock.clone());
+    for i in 0..ENTRIES {
+        cache.put_with_ttl(i, i, Duration::from_millis(60_000 + i * 7919 % 36_000_000));
+    }
+    c.bench_function("purge_expired_100k", |b| {
+        b.iter(|| {
+            clock.advance(Duration::from_millis(1));
+            black_box(cache.purge_expired())
+        })
+    });
+}
+
+criterion_group!(
+    benches,
+    cache_operations,
+    clock_vs_lru,
+    replacement_policies,
+    sampled_vs_exact_lru,
+    sharded_vs_single_lock,
+    persistence,
+    parallel_warmup,
+    precomputed_hash,
+    ttl_purge
+);
 criterion_main!(benches);
\ No newline at end of file
@@ -0,0 +1,107 @@
+/* Interface C de lru_cache (feature `ffi`), voir src/ffi.rs */
+
+#ifndef LRU_CACHE_H
+#define LRU_CACHE_H
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+/**
+ * Résultat des fonctions qui ne retournent pas de pointeur
+ */
+typedef enum LruCacheStatus {
+  /**
+   * L'opération a réussi
+   */
+  LruCacheStatus_Ok = 0,
+  /**
+   * Un pointeur requis est nul
+   */
+  LruCacheStatus_NullPointer = 1,
+  /**
+   * Une chaîne n'est pas de l'UTF-8 valide
+   */
+  LruCacheStatus_InvalidUtf8 = 2,
+  /**
+   * La clé est absente du cache
+   */
+  LruCacheStatus_NotFound = 3,
+  /**
+   * Le fichier n'a pas pu être lu ou écrit
+   */
+  LruCacheStatus_Io = 4,
+  /**
+   * Une panique a été interceptée ; le cache reste utilisable mais son
+   * contenu peut être incomplet
+   */
+  LruCacheStatus_Panic = 5,
+} LruCacheStatus;
+
+/**
+ * Cache de chaînes manipulé par pointeur depuis le C
+ */
+typedef struct LruCache LruCache;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+/**
+ * Crée un cache vide de capacité `capacity`
+ */
+struct LruCache *lru_cache_new(size_t capacity);
+
+/**
+ * Libère un cache créé par `lru_cache_new` ou `lru_cache_load` ; un
+ * pointeur nul est ignoré
+ */
+void lru_cache_free(struct LruCache *cache);
+
+/**
+ * Insère ou remplace la valeur de `key`, en évinçant l'entrée la moins
+ * récemment utilisée si le cache est plein
+ */
+enum LruCacheStatus lru_cache_put(struct LruCache *cache, const char *key, const char *value);
+
+/**
+ * Retourne une copie de la valeur de `key` et la marque comme la plus
+ * récemment utilisée, ou NULL ; la copie se libère par
+ * `lru_cache_string_free`
+ */
+char *lru_cache_get(struct LruCache *cache, const char *key);
+
+/**
+ * Libère une chaîne retournée par `lru_cache_get` ; un pointeur nul est
+ * ignoré
+ */
+void lru_cache_string_free(char *s);
+
+/**
+ * Retire `key` du cache
+ */
+enum LruCacheStatus lru_cache_remove(struct LruCache *cache, const char *key);
+
+/**
+ * Retourne le nombre d'entrées du cache, 0 pour un pointeur nul
+ */
+size_t lru_cache_len(const struct LruCache *cache);
+
+/**
+ * Sauvegarde le cache dans le fichier `path`
+ */
+enum LruCacheStatus lru_cache_save(const struct LruCache *cache, const char *path);
+
+/**
+ * Charge un cache de capacité `capacity` depuis le fichier `path`, ou
+ * retourne NULL
+ */
+struct LruCache *lru_cache_load(const char *path, size_t capacity);
+
+#ifdef __cplusplus
+} // extern "C"
+#endif // __cplusplus
+
+#endif /* LRU_CACHE_H */
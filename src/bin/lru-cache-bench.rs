@@ -0,0 +1,225 @@
+//! Rejoue une trace d'accès (une clé par ligne) contre une ou plusieurs
+//! configurations de cache et affiche les succès, échecs, évictions, le taux
+//! de succès et une estimation de la mémoire maximale occupée
+//!
+//! ```text
+//! lru-cache-bench <trace> --capacity <n> [--policy <nom>] [--ttl <lignes>]
+//! lru-cache-bench <trace> --compare capacity=1000,2000,4000 [--policy <nom>] [--ttl <lignes>]
+//! ```
+//!
+//! Le TTL est exprimé en lignes de trace : chaque accès fait avancer une
+//! horloge simulée d'une milliseconde. Il n'est accepté qu'avec la
+//! politique `lru`.
+
+use lru_cache::Cache;
+use lru_cache::cache::arc_policy::ArcCache;
+use lru_cache::cache::clock::Clock;
+use lru_cache::cache::clock_cache::ClockCache;
+use lru_cache::cache::fifo::FifoCache;
+use lru_cache::cache::lfu::LfuCache;
+use lru_cache::cache::policy::{Mru, TwoChoice};
+use lru_cache::cache::slru::SlruCache;
+use lru_cache::cache::testing::{read_trace, run_trace, TraceStats};
+use lru_cache::cache::tiny_lfu::TinyLfuCache;
+use lru_cache::cache::traits::{CacheLen, CacheStorage};
+use lru_cache::cache::ttl::TtlMode;
+use lru_cache::cache::two_q::TwoQCache;
+use std::mem::size_of;
+use std::process::ExitCode;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+const USAGE: &str = "usage : lru-cache-bench <trace> (--capacity <n> | --compare capacity=<n>,<n>,...) \
+[--policy lru|mru|two-choice|fifo|lfu|slru|two-q|arc|clock|tiny-lfu] [--ttl <lignes>]";
+
+/// Surcoût estimé par entrée (nœud de liste, index, métadonnées), en octets
+const ENTRY_OVERHEAD: usize = 64;
+
+/// Horloge simulée avançant d'une milliseconde par accès de la trace
+#[derive(Debug, Clone)]
+struct TraceClock {
+    start: Instant,
+    ticks: Arc<AtomicU64>,
+}
+
+impl Clock for TraceClock {
+    fn now(&self) -> Instant {
+        self.start + Duration::from_millis(self.ticks.load(Ordering::Relaxed))
+    }
+}
+
+/// Cache dont chaque lecture fait avancer l'horloge de la trace
+struct Ticking<C> {
+    cache: C,
+    ticks: Arc<AtomicU64>,
+}
+
+impl<C: CacheStorage<String, ()>> CacheStorage<String, ()> for Ticking<C> {
+    fn get(&mut self, key: &String) -> Option<&()> {
+        self.ticks.fetch_add(1, Ordering::Relaxed);
+        self.cache.get(key)
+    }
+
+    fn put(&mut self, key: String, value: ()) {
+        self.cache.put(key, value);
+    }
+}
+
+impl<C: CacheLen> CacheLen for Ticking<C> {
+    fn len(&self) -> usize {
+        self.cache.len()
+    }
+}
+
+#[derive(Debug)]
+struct Options {
+    trace: String,
+    capacities: Vec<usize>,
+    compare: bool,
+    policy: String,
+    ttl: Option<u64>,
+}
+
+fn parse_args(mut args: impl Iterator<Item = String>) -> Result<Options, String> {
+    let mut trace = None;
+    let mut capacity = None;
+    let mut compare = None;
+    let mut policy = String::from("lru");
+    let mut ttl = None;
+    while let Some(arg) = args.next() {
+        let mut value = |name: &str| args.next().ok_or_else(|| format!("valeur manquante pour {}", name));
+        match arg.as_str() {
+            "--capacity" => capacity = Some(parse_number(&value("--capacity")?)?),
+            "--compare" => {
+                let spec = value("--compare")?;
+                let list = spec
+                    .strip_prefix("capacity=")
+                    .ok_or_else(|| format!("--compare attend capacity=<n>,... : {:?}", spec))?;
+                compare = Some(list.split(',').map(parse_number).collect::<Result<Vec<_>, _>>()?);
+            }
+            "--policy" => policy = value("--policy")?,
+            "--ttl" => ttl = Some(parse_number(&value("--ttl")?)? as u64),
+            _ if arg.starts_with("--") => return Err(format!("option inconnue : {}", arg)),
+            _ if trace.is_none() => trace = Some(arg),
+            _ => return Err(format!("argument inattendu : {}", arg)),
+        }
+    }
+    let trace = trace.ok_or("fichier de trace manquant")?;
+    let (capacities, compare) = match (capacity, compare) {
+        (Some(_), Some(_)) => return Err(String::from("--capacity et --compare sont exclusifs")),
+        (Some(capacity), None) => (vec![capacity], false),
+        (None, Some(capacities)) => (capacities, true),
+        (None, None) => return Err(String::from("--capacity ou --compare requis")),
+    };
+    Ok(Options {
+        trace,
+        capacities,
+        compare,
+        policy,
+        ttl,
+    })
+}
+
+fn parse_number(s: &str) -> Result<usize, String> {
+    s.trim().parse().map_err(|_| format!("nombre invalide : {:?}", s))
+}
+
+fn replay<C: CacheStorage<String, ()> + CacheLen>(cache: C, ticks: Arc<AtomicU64>, trace: &[String]) -> TraceStats {
+    run_trace(&mut Ticking { cache, ticks }, trace)
+}
+
+fn run(options: &Options, capacity: usize, trace: &[String]) -> Result<TraceStats, String> {
+    let ticks = Arc::new(AtomicU64::new(0));
+    if let Some(ttl) = options.ttl {
+        if options.policy != "lru" {
+            return Err(format!("--ttl n'est pas disponible avec la politique {}", options.policy));
+        }
+        let clock = TraceClock {
+            start: Instant::now(),
+            ticks: Arc::clone(&ticks),
+        };
+        let cache = Cache::with_ttl_and_clock(capacity, Duration::from_millis(ttl), TtlMode::AfterWrite, clock);
+        return Ok(replay(cache, ticks, trace));
+    }
+    let stats = match options.policy.as_str() {
+        "lru" => replay(Cache::new(capacity), ticks, trace),
+        "mru" => replay(Cache::with_eviction_policy(capacity, Mru), ticks, trace),
+        "two-choice" => replay(Cache::with_eviction_policy(capacity, TwoChoice::new(0)), ticks, trace),
+        "fifo" => replay(FifoCache::new(capacity), ticks, trace),
+        "lfu" => replay(LfuCache::new(capacity), ticks, trace),
+        "slru" => {
+            let protected = capacity * 4 / 5;
+            replay(SlruCache::new(capacity - protected, protected), ticks, trace)
+        }
+        "two-q" => replay(TwoQCache::new(capacity), ticks, trace),
+        "arc" => replay(ArcCache::new(capacity), ticks, trace),
+        "clock" => replay(ClockCache::new(capacity), ticks, trace),
+        "tiny-lfu" => replay(TinyLfuCache::new(capacity), ticks, trace),
+        other => return Err(format!("politique inconnue : {}", other)),
+    };
+    Ok(stats)
+}
+
+/// Estime la mémoire maximale : entrées au pic × (clé moyenne + surcoût)
+fn peak_bytes(stats: &TraceStats, trace: &[String]) -> usize {
+    if trace.is_empty() {
+        return 0;
+    }
+    let mean_key = trace.iter().map(String::len).sum::<usize>().div_ceil(trace.len());
+    stats.peak_len * (size_of::<String>() + mean_key + ENTRY_OVERHEAD)
+}
+
+fn main() -> ExitCode {
+    let options = match parse_args(std::env::args().skip(1)) {
+        Ok(options) => options,
+        Err(message) => {
+            eprintln!("erreur : {}\n{}", message, USAGE);
+            return ExitCode::from(2);
+        }
+    };
+    let trace = match read_trace(&options.trace) {
+        Ok(trace) => trace,
+        Err(e) => {
+            eprintln!("erreur : lecture de {} : {}", options.trace, e);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    if options.compare {
+        println!("policy={} accesses={}", options.policy, trace.len());
+        println!(
+            "{:>10} {:>10} {:>10} {:>10} {:>9} {:>12}",
+            "capacity", "hits", "misses", "evictions", "hit_rate", "peak_bytes"
+        );
+    }
+    for &capacity in &options.capacities {
+        let stats = match run(&options, capacity, &trace) {
+            Ok(stats) => stats,
+            Err(message) => {
+                eprintln!("erreur : {}", message);
+                return ExitCode::from(2);
+            }
+        };
+        let bytes = peak_bytes(&stats, &trace);
+        if options.compare {
+            println!(
+                "{:>10} {:>10} {:>10} {:>10} {:>9.4} {:>12}",
+                capacity,
+                stats.hits,
+                stats.misses,
+                stats.evictions,
+                stats.hit_rate(),
+                bytes
+            );
+        } else {
+            println!("policy={} capacity={} accesses={}", options.policy, capacity, trace.len());
+            println!("hits={}", stats.hits);
+            println!("misses={}", stats.misses);
+            println!("evictions={}", stats.evictions);
+            println!("hit_rate={:.4}", stats.hit_rate());
+            println!("peak_bytes={}", bytes);
+        }
+    }
+    ExitCode::SUCCESS
+}
@@ -0,0 +1,234 @@
+//! Implémentations triviales des traits de cache
+//!
+//! [`NullCache`] ne garde rien : il sert de cache désactivé derrière un
+//! `Box<dyn DynCache<K, V>>`. [`UnboundedMapCache`] (feature `std`) garde
+//! tout, sans jamais évincer : il sert de référence dans les tests du code
+//! écrit contre les traits.
+
+use super::stats::CacheStats;
+use super::traits::{CacheClear, CacheIterate, CacheLen, CacheRemove, CacheStorage, GetOptions};
+use core::marker::PhantomData;
+#[cfg(feature = "std")]
+use std::collections::{hash_map, HashMap};
+#[cfg(feature = "std")]
+use std::hash::Hash;
+
+/// Cache qui ne garde aucune entrée : `put` ne fait rien et chaque lecture
+/// échoue
+///
+/// Les statistiques comptent toutes les lectures comme des échecs, ce qui
+/// permet de mesurer la charge qu'un cache désactivé laisse passer.
+///
+/// # Examples
+/// ```
+/// use lru_cache::cache::adapters::NullCache;
+/// use lru_cache::cache::traits::{CacheLen, CacheStorage};
+///
+/// let mut cache = NullCache::new();
+/// cache.put("key", 1);
+/// assert_eq!(cache.get(&"key"), None);
+/// assert_eq!(cache.len(), 0);
+/// assert_eq!(cache.stats().misses, 1);
+/// ```
+pub struct NullCache<K, V> {
+    stats: CacheStats,
+    marker: PhantomData<fn(K, V)>,
+}
+
+impl<K, V> NullCache<K, V> {
+    /// Crée un cache désactivé
+    pub fn new() -> Self {
+        NullCache {
+            stats: CacheStats::default(),
+            marker: PhantomData,
+        }
+    }
+
+    /// Retourne les compteurs : seules les lectures manquées sont non nulles
+    pub fn stats(&self) -> CacheStats {
+        self.stats
+    }
+
+    /// Remet les compteurs à zéro
+    pub fn reset_stats(&mut self) {
+        self.stats = CacheStats::default();
+    }
+}
+
+impl<K, V> Default for NullCache<K, V> {
+    fn default() -> Self {
+        NullCache::new()
+    }
+}
+
+impl<K, V> core::fmt::Debug for NullCache<K, V> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("NullCache").field("stats", &self.stats).finish()
+    }
+}
+
+impl<K, V> CacheStorage<K, V> for NullCache<K, V> {
+    fn get(&mut self, key: &K) -> Option<&V> {
+        self.get_with(key, GetOptions::default())
+    }
+
+    fn put(&mut self, _key: K, _value: V) {}
+
+    fn get_with(&mut self, _key: &K, options: GetOptions) -> Option<&V> {
+        if options.record_stats {
+            self.stats.misses += 1;
+        }
+        None
+    }
+}
+
+impl<K, V> CacheRemove<K, V> for NullCache<K, V> {
+    fn remove(&mut self, _key: &K) -> Option<V> {
+        None
+    }
+}
+
+impl<K, V> CacheLen for NullCache<K, V> {
+    fn len(&self) -> usize {
+        0
+    }
+}
+
+impl<K, V> CacheClear for NullCache<K, V> {
+    fn clear(&mut self) {}
+}
+
+impl<K, V> CacheIterate<K, V> for NullCache<K, V> {
+    type Iter<'a> = core::iter::Empty<(&'a K, &'a V)> where Self: 'a, K: 'a, V: 'a;
+
+    fn iter(&self) -> Self::Iter<'_> {
+        core::iter::empty()
+    }
+}
+
+/// Cache sans capacité, adossé à une `HashMap`, qui n'évince jamais
+///
+/// Chaque entrée insérée reste jusqu'à `remove` ou `clear` : la mémoire
+/// occupée n'est pas bornée. Les statistiques comptent les lectures, les
+/// insertions et les remplacements ; `evictions` et `expirations` restent
+/// nulles.
+///
+/// # Examples
+/// ```
+/// use lru_cache::cache::adapters::UnboundedMapCache;
+/// use lru_cache::cache::traits::{CacheLen, CacheStorage};
+///
+/// let mut cache = UnboundedMapCache::new();
+/// for key in 0..1000 {
+///     cache.put(key, key * 2);
+/// }
+/// assert_eq!(cache.len(), 1000);
+/// assert_eq!(cache.get(&0), Some(&0));
+/// ```
+#[cfg(feature = "std")]
+#[derive(Debug)]
+pub struct UnboundedMapCache<K, V> {
+    entries: HashMap<K, V>,
+    stats: CacheStats,
+}
+
+#[cfg(feature = "std")]
+impl<K, V> UnboundedMapCache<K, V> {
+    /// Crée un cache vide
+    pub fn new() -> Self {
+        UnboundedMapCache {
+            entries: HashMap::new(),
+            stats: CacheStats::default(),
+        }
+    }
+
+    /// Retourne les compteurs d'activité
+    pub fn stats(&self) -> CacheStats {
+        self.stats
+    }
+
+    /// Remet les compteurs à zéro
+    pub fn reset_stats(&mut self) {
+        self.stats = CacheStats::default();
+    }
+}
+
+#[cfg(feature = "std")]
+impl<K, V> Default for UnboundedMapCache<K, V> {
+    fn default() -> Self {
+        UnboundedMapCache::new()
+    }
+}
+
+#[cfg(feature = "std")]
+impl<K: Eq + Hash, V> CacheStorage<K, V> for UnboundedMapCache<K, V> {
+    fn get(&mut self, key: &K) -> Option<&V> {
+        self.get_with(key, GetOptions::default())
+    }
+
+    fn put(&mut self, key: K, value: V) {
+        if self.entries.insert(key, value).is_some() {
+            self.stats.updates += 1;
+        } else {
+            self.stats.insertions += 1;
+        }
+    }
+
+    /// Aucun ordre d'éviction n'est tenu : seul `record_stats` a un effet
+    fn get_with(&mut self, key: &K, options: GetOptions) -> Option<&V> {
+        let value = self.entries.get(key);
+        if options.record_stats {
+            match value {
+                Some(_) => self.stats.hits += 1,
+                None => self.stats.misses += 1,
+            }
+        }
+        value
+    }
+}
+
+#[cfg(feature = "std")]
+impl<K: Eq + Hash, V> CacheRemove<K, V> for UnboundedMapCache<K, V> {
+    fn remove(&mut self, key: &K) -> Option<V> {
+        self.entries.remove(key)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<K, V> CacheLen for UnboundedMapCache<K, V> {
+    fn len(&self) -> usize {
+        self.entries.len()
+    }
+}
+
+#[cfg(feature = "std")]
+impl<K, V> CacheClear for UnboundedMapCache<K, V> {
+    fn clear(&mut self) {
+        self.entries.clear();
+    }
+}
+
+/// Itérateur sur les entrées d'un [`UnboundedMapCache`], dans un ordre
+/// quelconque
+#[cfg(feature = "std")]
+pub struct Iter<'a, K, V>(hash_map::Iter<'a, K, V>);
+
+#[cfg(feature = "std")]
+impl<'a, K, V> Iterator for Iter<'a, K, V> {
+    type Item = (&'a K, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next()
+    }
+}
+
+#[cfg(feature = "std")]
+impl<K, V> CacheIterate<K, V> for UnboundedMapCache<K, V> {
+    type Iter<'a> = Iter<'a, K, V> where Self: 'a, K: 'a, V: 'a;
+
+    /// Parcourt les entrées dans un ordre quelconque : aucune n'est jamais
+    /// évincée
+    fn iter(&self) -> Self::Iter<'_> {
+        Iter(self.entries.iter())
+    }
+}
@@ -0,0 +1,232 @@
+use super::list::{self, NodeList};
+use super::traits::{CacheIterate, CacheLen, CacheRemove, CacheStorage, GetOptions};
+use core::hash::Hash;
+use core::iter::Chain;
+
+/// Cache ARC (Adaptive Replacement Cache, Megiddo et Modha, 2003)
+///
+/// Les entrées vues une seule fois récemment sont dans `T1`, celles vues au
+/// moins deux fois dans `T2`, toutes deux gérées en LRU. Les clés évincées
+/// de `T1` et `T2` sont mémorisées (sans valeur) dans les listes fantômes
+/// `B1` et `B2`. La cible `p` de taille de `T1` s'adapte : réinsérer une clé
+/// de `B1` montre que `T1` était trop petite et augmente `p` ; réinsérer une
+/// clé de `B2` la diminue.
+///
+/// Le cache contient au plus `capacity` entrées et chaque liste fantôme au
+/// plus `capacity` clés. Comme `get` ne reçoit pas de valeur, une clé
+/// fantôme n'est réintégrée (et `p` adapté) que lors d'un `put`.
+///
+/// # Examples
+/// ```
+/// use lru_cache::cache::arc_policy::ArcCache;
+/// use lru_cache::cache::traits::CacheStorage;
+///
+/// let mut cache = ArcCache::new(2);
+/// cache.put("A", 1);
+/// cache.get(&"A");
+/// cache.put("B", 2);
+/// cache.put("C", 3);
+/// assert_eq!(cache.b1_len(), 1);
+/// cache.put("B", 2);
+/// assert_eq!(cache.p(), 1);
+/// ```
+#[derive(Debug)]
+pub struct ArcCache<K, V> {
+    capacity: usize,
+    p: usize,
+    t1: NodeList<K, V, ()>,
+    t2: NodeList<K, V, ()>,
+    b1: NodeList<K, (), ()>,
+    b2: NodeList<K, (), ()>,
+}
+
+impl<K: Eq + Hash, V> ArcCache<K, V> {
+    /// Crée un nouveau cache avec la capacité spécifiée
+    pub fn new(capacity: usize) -> Self {
+        ArcCache {
+            capacity,
+            p: 0,
+            t1: NodeList::new(),
+            t2: NodeList::new(),
+            b1: NodeList::new(),
+            b2: NodeList::new(),
+        }
+    }
+
+    /// Retourne la capacité du cache
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Retourne la taille visée pour `T1`, entre 0 et la capacité
+    pub fn p(&self) -> usize {
+        self.p
+    }
+
+    /// Retourne le nombre d'entrées vues une fois (`T1`)
+    pub fn t1_len(&self) -> usize {
+        self.t1.len()
+    }
+
+    /// Retourne le nombre d'entrées vues plusieurs fois (`T2`)
+    pub fn t2_len(&self) -> usize {
+        self.t2.len()
+    }
+
+    /// Retourne le nombre de clés fantômes évincées de `T1` (`B1`)
+    pub fn b1_len(&self) -> usize {
+        self.b1.len()
+    }
+
+    /// Retourne le nombre de clés fantômes évincées de `T2` (`B2`)
+    pub fn b2_len(&self) -> usize {
+        self.b2.len()
+    }
+
+    fn resident_len(&self) -> usize {
+        self.t1.len() + self.t2.len()
+    }
+
+    /// Déplace l'entrée `index` de `T1` en tête de `T2`
+    fn promote(&mut self, index: usize) -> usize {
+        let node = self.t1.remove(index);
+        self.t2.push(node.key, node.value, ())
+    }
+
+    /// Procédure REPLACE du papier : libère une place en envoyant l'entrée
+    /// LRU de `T1` ou de `T2` dans la liste fantôme correspondante
+    fn replace(&mut self, in_b2: bool) {
+        if self.resident_len() < self.capacity {
+            return;
+        }
+        let t1_len = self.t1.len();
+        if t1_len >= 1 && ((in_b2 && t1_len == self.p) || t1_len > self.p) {
+            if let Some(lru) = self.t1.head() {
+                let node = self.t1.remove(lru);
+                self.b1.push(node.key, (), ());
+            }
+        } else if let Some(lru) = self.t2.head() {
+            let node = self.t2.remove(lru);
+            self.b2.push(node.key, (), ());
+        } else if let Some(lru) = self.t1.head() {
+            let node = self.t1.remove(lru);
+            self.b1.push(node.key, (), ());
+        }
+    }
+
+    fn drop_oldest_ghost(list: &mut NodeList<K, (), ()>) {
+        if let Some(oldest) = list.head() {
+            list.remove(oldest);
+        }
+    }
+}
+
+impl<K: Eq + Hash, V> CacheStorage<K, V> for ArcCache<K, V> {
+    fn get(&mut self, key: &K) -> Option<&V> {
+        if let Some(index) = self.t2.find(key) {
+            self.t2.move_to_back(index);
+            return Some(&self.t2.node(index).value);
+        }
+        let index = self.t1.find(key)?;
+        let index = self.promote(index);
+        Some(&self.t2.node(index).value)
+    }
+
+    fn get_with(&mut self, key: &K, options: GetOptions) -> Option<&V> {
+        if options.promote {
+            return self.get(key);
+        }
+        let (list, index) = match self.t2.find(key) {
+            Some(index) => (&self.t2, index),
+            None => (&self.t1, self.t1.find(key)?),
+        };
+        Some(&list.node(index).value)
+    }
+
+    fn put(&mut self, key: K, value: V) {
+        if let Some(index) = self.t2.find(&key) {
+            self.t2.node_mut(index).value = value;
+            self.t2.move_to_back(index);
+            return;
+        }
+        if let Some(index) = self.t1.find(&key) {
+            let index = self.promote(index);
+            self.t2.node_mut(index).value = value;
+            return;
+        }
+        if self.capacity == 0 {
+            return;
+        }
+
+        if let Some(ghost) = self.b1.find(&key) {
+            let delta = (self.b2.len() / self.b1.len()).max(1);
+            self.p = (self.p + delta).min(self.capacity);
+            self.b1.remove(ghost);
+            self.replace(false);
+            self.t2.push(key, value, ());
+            return;
+        }
+        if let Some(ghost) = self.b2.find(&key) {
+            let delta = (self.b1.len() / self.b2.len()).max(1);
+            self.p = self.p.saturating_sub(delta);
+            self.b2.remove(ghost);
+            self.replace(true);
+            self.t2.push(key, value, ());
+            return;
+        }
+
+        if self.t1.len() + self.b1.len() >= self.capacity {
+            if self.t1.len() < self.capacity {
+                Self::drop_oldest_ghost(&mut self.b1);
+                self.replace(false);
+            } else if let Some(lru) = self.t1.head() {
+                self.t1.remove(lru);
+            }
+        } else {
+            let total = self.resident_len() + self.b1.len() + self.b2.len();
+            if total >= self.capacity {
+                if total >= 2 * self.capacity {
+                    Self::drop_oldest_ghost(&mut self.b2);
+                }
+                self.replace(false);
+            }
+        }
+        self.t1.push(key, value, ());
+    }
+}
+
+impl<K: Eq + Hash, V> CacheRemove<K, V> for ArcCache<K, V> {
+    fn remove(&mut self, key: &K) -> Option<V> {
+        if let Some(index) = self.t2.find(key) {
+            return Some(self.t2.remove(index).value);
+        }
+        let index = self.t1.find(key)?;
+        Some(self.t1.remove(index).value)
+    }
+}
+
+impl<K, V> CacheLen for ArcCache<K, V> {
+    fn len(&self) -> usize {
+        self.t1.len() + self.t2.len()
+    }
+}
+
+/// Itérateur sur les entrées d'un [`ArcCache`] : `T1` puis `T2`, chacune de
+/// la moins à la plus récemment utilisée
+pub struct Iter<'a, K, V>(Chain<list::Iter<'a, K, V, ()>, list::Iter<'a, K, V, ()>>);
+
+impl<'a, K, V> Iterator for Iter<'a, K, V> {
+    type Item = (&'a K, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next()
+    }
+}
+
+impl<K, V> CacheIterate<K, V> for ArcCache<K, V> {
+    type Iter<'a> = Iter<'a, K, V> where Self: 'a, K: 'a, V: 'a;
+
+    fn iter(&self) -> Self::Iter<'_> {
+        Iter(self.t1.iter().chain(self.t2.iter()))
+    }
+}
@@ -0,0 +1,313 @@
+//! Construction d'un cache option par option
+
+use super::clock::Clock;
+use super::error::CacheError;
+use super::listener::{CacheEventListener, RemovalCause};
+use super::lru::Cache;
+#[allow(deprecated)]
+use super::policy::Policy;
+use super::policy::{EvictionPolicy, Lru};
+use super::ttl::{TtlMode, DEFAULT_TTL_GRANULARITY};
+use super::weigher::Weigher;
+use alloc::boxed::Box;
+use core::fmt;
+use core::num::NonZeroUsize;
+use core::hash::Hash;
+use alloc::sync::Arc;
+use core::time::Duration;
+
+/// Fonction appelée pour chaque entrée qui quitte le cache
+pub(super) type EvictionListener<K, V> = Box<dyn FnMut(K, V, RemovalCause) + Send>;
+
+/// Configure un [`Cache`] en combinant ses options, puis le crée avec
+/// [`CacheBuilder::build`]
+///
+/// Chaque option non définie garde la valeur de [`Cache::new`] : pas
+/// d'expiration, ni de pondération, ni d'écouteur, horloge du système,
+/// politique LRU. Seule la capacité doit être fixée.
+///
+/// # Examples
+/// ```
+/// use lru_cache::cache::builder::CacheBuilder;
+/// use lru_cache::cache::traits::CacheStorage;
+/// use std::num::NonZeroUsize;
+/// use std::time::Duration;
+///
+/// let mut cache = CacheBuilder::new()
+///     .capacity(NonZeroUsize::new(100).unwrap())
+///     .weigher(|_: &&str, value: &String| value.len())
+///     .time_to_live(Duration::from_secs(60))
+///     .build()
+///     .unwrap();
+/// cache.put("key", String::from("value"));
+/// assert_eq!(cache.total_weight(), 5);
+/// ```
+pub struct CacheBuilder<K, V> {
+    pub(super) capacity: Option<NonZeroUsize>,
+    pub(super) low_water: Option<NonZeroUsize>,
+    pub(super) ttl: Option<(Duration, TtlMode)>,
+    pub(super) max_idle: Option<Duration>,
+    pub(super) ttl_granularity: Duration,
+    pub(super) ttl_jitter: Option<(f64, u64)>,
+    pub(super) refresh_ahead: Option<f64>,
+    #[allow(deprecated)]
+    pub(super) policy: Policy,
+    pub(super) clock: Option<Arc<dyn Clock>>,
+    pub(super) weigher: Option<Box<dyn Weigher<K, V>>>,
+    pub(super) eviction_listener: Option<EvictionListener<K, V>>,
+    pub(super) listener: Option<Box<dyn CacheEventListener<K> + Send>>,
+    pub(super) metadata: bool,
+    pub(super) preallocate: bool,
+}
+
+impl<K, V> CacheBuilder<K, V> {
+    /// Crée une configuration par défaut, sans capacité
+    #[allow(deprecated)]
+    pub fn new() -> Self {
+        CacheBuilder {
+            capacity: None,
+            low_water: None,
+            ttl: None,
+            max_idle: None,
+            ttl_granularity: DEFAULT_TTL_GRANULARITY,
+            ttl_jitter: None,
+            refresh_ahead: None,
+            policy: Policy::Lru,
+            clock: None,
+            weigher: None,
+            eviction_listener: None,
+            listener: None,
+            metadata: false,
+            preallocate: false,
+        }
+    }
+
+    /// Fixe la capacité : nombre d'entrées, ou poids total maximal avec
+    /// [`CacheBuilder::weigher`]
+    pub fn capacity(mut self, capacity: NonZeroUsize) -> Self {
+        self.capacity = Some(capacity);
+        self
+    }
+
+    /// Évince par lots : une insertion qui ferait dépasser `high` évince,
+    /// dans l'ordre d'éviction, jusqu'à ce qu'il reste `low` entrées (ou un
+    /// poids total de `low` avec [`CacheBuilder::weigher`]), nouvelle
+    /// entrée comprise
+    ///
+    /// `high` devient la capacité, comme avec [`CacheBuilder::capacity`],
+    /// qui le remplace si elle est appelée ensuite. Par défaut, les deux
+    /// seuils valent la capacité : chaque insertion dans un cache plein
+    /// évince une entrée. Voir [`Cache::resize`] pour les changements de
+    /// capacité.
+    ///
+    /// # Examples
+    /// ```
+    /// use lru_cache::cache::builder::CacheBuilder;
+    /// use lru_cache::cache::traits::{CacheLen, CacheStorage};
+    /// use std::num::NonZeroUsize;
+    ///
+    /// let mut cache = CacheBuilder::new()
+    ///     .watermarks(NonZeroUsize::new(4).unwrap(), NonZeroUsize::new(2).unwrap())
+    ///     .build()
+    ///     .unwrap();
+    /// for i in 0..4 {
+    ///     cache.put(i, i);
+    /// }
+    /// assert_eq!(cache.len(), 4);
+    /// cache.put(4, 4);
+    /// assert_eq!(cache.len(), 2);
+    /// ```
+    pub fn watermarks(mut self, high: NonZeroUsize, low: NonZeroUsize) -> Self {
+        self.capacity = Some(high);
+        self.low_water = Some(low);
+        self
+    }
+
+    /// Fait expirer chaque entrée `ttl` après son insertion
+    /// ([`TtlMode::AfterWrite`])
+    pub fn time_to_live(mut self, ttl: Duration) -> Self {
+        self.ttl = Some((ttl, TtlMode::AfterWrite));
+        self
+    }
+
+    /// Fait expirer chaque entrée `ttl` après sa dernière lecture
+    /// ([`TtlMode::AfterAccess`]) ; remplace [`CacheBuilder::time_to_live`]
+    pub fn time_to_idle(mut self, ttl: Duration) -> Self {
+        self.ttl = Some((ttl, TtlMode::AfterAccess));
+        self
+    }
+
+    /// Fait expirer chaque entrée qui n'a pas été lue depuis `max_idle`,
+    /// en plus de sa durée de vie éventuelle (voir [`Cache::with_max_idle`])
+    pub fn max_idle(mut self, max_idle: Duration) -> Self {
+        self.max_idle = Some(max_idle);
+        self
+    }
+
+    /// Fixe la durée d'un tick de la roue temporelle des échéances (voir
+    /// [`Cache::set_ttl_granularity`])
+    pub fn ttl_granularity(mut self, granularity: Duration) -> Self {
+        self.ttl_granularity = granularity;
+        self
+    }
+
+    /// Disperse les durées de vie d'au plus `fraction` avec des tirages
+    /// issus de la graine `seed` (voir [`Cache::set_ttl_jitter`])
+    pub fn ttl_jitter(mut self, fraction: f64, seed: u64) -> Self {
+        self.ttl_jitter = Some((fraction, seed));
+        self
+    }
+
+    /// Recharge d'avance les entrées lues dans la dernière `fraction` de
+    /// leur durée de vie (voir [`Cache::set_refresh_ahead`])
+    pub fn refresh_ahead(mut self, fraction: f64) -> Self {
+        self.refresh_ahead = Some(fraction);
+        self
+    }
+
+    /// Choisit la politique d'éviction intégrée (voir [`Cache::with_policy`])
+    #[deprecated(note = "utiliser `CacheBuilder::build_with_eviction_policy` avec `policy::Mru` ou `policy::TwoChoice`")]
+    #[allow(deprecated)]
+    pub fn policy(mut self, policy: Policy) -> Self {
+        self.policy = policy;
+        self
+    }
+
+    /// Mesure les échéances avec `clock` (voir [`Cache::with_clock`])
+    pub fn clock<C: Clock + 'static>(mut self, clock: C) -> Self {
+        self.clock = Some(Arc::new(clock));
+        self
+    }
+
+    /// Pondère les entrées avec `weigher` (voir [`Cache::with_weigher`])
+    pub fn weigher<W: Weigher<K, V> + 'static>(mut self, weigher: W) -> Self {
+        self.weigher = Some(Box::new(weigher));
+        self
+    }
+
+    /// Appelle `listener` pour chaque entrée qui quitte le cache (voir
+    /// [`Cache::set_eviction_listener`])
+    pub fn eviction_listener(mut self, listener: impl FnMut(K, V, RemovalCause) + Send + 'static) -> Self {
+        self.eviction_listener = Some(Box::new(listener));
+        self
+    }
+
+    /// Signale les événements du cache à `listener` (voir
+    /// [`Cache::set_listener`])
+    pub fn listener<L: CacheEventListener<K> + Send + 'static>(mut self, listener: L) -> Self {
+        self.listener = Some(Box::new(listener));
+        self
+    }
+
+    /// Relève les dates des entrées (voir [`Cache::track_metadata`])
+    pub fn track_metadata(mut self) -> Self {
+        self.metadata = true;
+        self
+    }
+
+    /// Réserve dès la construction la place de `capacity` entrées (voir
+    /// [`Cache::with_preallocated`])
+    pub fn preallocate(mut self) -> Self {
+        self.preallocate = true;
+        self
+    }
+}
+
+impl<K: Eq + Hash, V> CacheBuilder<K, V> {
+    /// Crée le cache configuré
+    ///
+    /// # Errors
+    ///
+    /// Retourne [`CacheError::ZeroCapacity`] si la capacité n'a pas été
+    /// fixée, avec ou sans pondération : le cache refuserait toute entrée.
+    /// Retourne [`CacheError::InvalidWatermarks`] si le seuil bas de
+    /// [`CacheBuilder::watermarks`] dépasse la capacité, et
+    /// [`CacheError::ZeroGranularity`] pour une
+    /// [`CacheBuilder::ttl_granularity`] nulle, [`CacheError::InvalidJitter`]
+    /// pour une [`CacheBuilder::ttl_jitter`] hors de `0..=1` et
+    /// [`CacheError::InvalidRefreshAhead`] pour un
+    /// [`CacheBuilder::refresh_ahead`] hors de `0..=1`.
+    pub fn build(self) -> Result<Cache<K, V>, CacheError> {
+        let capacity = self.validate()?;
+        Ok(Cache::from_builder(self, capacity, Lru))
+    }
+
+    /// Crée le cache configuré, dont l'entrée évincée est choisie par
+    /// `policy` (voir [`Cache::with_eviction_policy`])
+    ///
+    /// # Errors
+    ///
+    /// Les erreurs de [`CacheBuilder::build`], et
+    /// [`CacheError::ConflictingPolicies`] si une politique intégrée autre
+    /// que `Lru` a aussi été choisie par [`CacheBuilder::policy`].
+    ///
+    /// # Examples
+    /// ```
+    /// use lru_cache::cache::builder::CacheBuilder;
+    /// use lru_cache::cache::policy::Mru;
+    /// use lru_cache::cache::traits::CacheStorage;
+    /// use std::num::NonZeroUsize;
+    ///
+    /// let mut cache = CacheBuilder::new()
+    ///     .capacity(NonZeroUsize::new(2).unwrap())
+    ///     .build_with_eviction_policy(Mru)
+    ///     .unwrap();
+    /// cache.put("A", 1);
+    /// cache.put("B", 2);
+    /// cache.put("C", 3);
+    /// assert_eq!(cache.get(&"B"), None);
+    /// ```
+    #[allow(deprecated)]
+    pub fn build_with_eviction_policy<P: EvictionPolicy<K>>(self, policy: P) -> Result<Cache<K, V, P>, CacheError> {
+        let capacity = self.validate()?;
+        if self.policy != Policy::Lru {
+            return Err(CacheError::ConflictingPolicies);
+        }
+        Ok(Cache::from_builder(self, capacity, policy))
+    }
+
+    /// Vérifie la configuration et retourne la capacité fixée
+    fn validate(&self) -> Result<NonZeroUsize, CacheError> {
+        let capacity = self.capacity.ok_or(CacheError::ZeroCapacity)?;
+        if self.ttl_granularity.is_zero() {
+            return Err(CacheError::ZeroGranularity);
+        }
+        if self.ttl_jitter.is_some_and(|(fraction, _)| !(0.0..=1.0).contains(&fraction)) {
+            return Err(CacheError::InvalidJitter);
+        }
+        if self.refresh_ahead.is_some_and(|fraction| !(0.0..=1.0).contains(&fraction)) {
+            return Err(CacheError::InvalidRefreshAhead);
+        }
+        if let Some(low) = self.low_water.filter(|&low| low > capacity) {
+            return Err(CacheError::InvalidWatermarks {
+                high: capacity.get(),
+                low: low.get(),
+            });
+        }
+        Ok(capacity)
+    }
+}
+
+impl<K, V> Default for CacheBuilder<K, V> {
+    fn default() -> Self {
+        CacheBuilder::new()
+    }
+}
+
+impl<K, V> fmt::Debug for CacheBuilder<K, V> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CacheBuilder")
+            .field("capacity", &self.capacity)
+            .field("low_water", &self.low_water)
+            .field("ttl", &self.ttl)
+            .field("max_idle", &self.max_idle)
+            .field("ttl_granularity", &self.ttl_granularity)
+            .field("ttl_jitter", &self.ttl_jitter)
+            .field("refresh_ahead", &self.refresh_ahead)
+            .field("policy", &self.policy)
+            .field("clock", &self.clock)
+            .field("weighted", &self.weigher.is_some())
+            .field("metadata", &self.metadata)
+            .finish_non_exhaustive()
+    }
+}
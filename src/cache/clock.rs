@@ -0,0 +1,208 @@
+//! Source de temps utilisée pour les décisions d'expiration
+//!
+//! Toutes les échéances du cache sont calculées à partir d'un [`Clock`]. Par
+//! défaut il s'agit de [`SystemClock`] ; les tests peuvent utiliser
+//! `MockClock` (feature `test-util`) pour faire avancer le temps sans
+//! attendre.
+//!
+//! Une horloge personnalisée n'est pas tenue d'être monotone : le cache
+//! n'effectue aucune soustraction d'instants susceptible de paniquer et
+//! borne la durée de vie restante d'une entrée par son TTL quand le temps
+//! recule.
+//!
+//! Les horodatages calendaires (noms des instantanés de
+//! `storage::rolling`) passent par `Clock::system_time`.
+//!
+//! Sans la feature `std`, il n'y a pas d'horloge du système : [`Instant`]
+//! est une durée écoulée depuis une origine propre à l'horloge, et le cache
+//! utilise par défaut `StoppedClock`, sous laquelle aucune entrée
+//! n'expire. Il faut fournir une horloge (minuterie matérielle, compteur de
+//! ticks) à [`Cache::with_clock`](super::lru::Cache::with_clock) pour que
+//! les durées de vie s'appliquent.
+
+use core::fmt::Debug;
+#[cfg(not(feature = "std"))]
+use core::time::Duration;
+#[cfg(feature = "std")]
+pub use std::time::Instant;
+#[cfg(feature = "std")]
+use std::time::SystemTime;
+
+/// Fournit l'instant courant
+pub trait Clock: Debug + Send + Sync {
+    /// Retourne l'instant courant
+    fn now(&self) -> Instant;
+
+    /// Retourne la date et l'heure courantes
+    #[cfg(feature = "std")]
+    fn system_time(&self) -> SystemTime {
+        SystemTime::now()
+    }
+}
+
+/// Horloge monotone du système (`Instant::now`)
+#[cfg(feature = "std")]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+#[cfg(feature = "std")]
+impl Clock for SystemClock {
+    fn now(&self) -> Instant {
+        Instant::now()
+    }
+}
+
+/// Horloge d'un cache créé sans [`Cache::with_clock`](super::lru::Cache::with_clock)
+#[cfg(feature = "std")]
+pub(crate) type DefaultClock = SystemClock;
+#[cfg(not(feature = "std"))]
+pub(crate) type DefaultClock = StoppedClock;
+
+/// Instant mesuré par une [`Clock`] sans la bibliothèque standard : durée
+/// écoulée depuis l'origine de l'horloge
+#[cfg(not(feature = "std"))]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Instant(Duration);
+
+#[cfg(not(feature = "std"))]
+impl Instant {
+    /// Crée l'instant situé `elapsed` après l'origine de l'horloge
+    pub const fn from_elapsed(elapsed: Duration) -> Self {
+        Instant(elapsed)
+    }
+
+    /// Retourne la durée écoulée depuis l'origine de l'horloge
+    pub const fn elapsed_since_origin(self) -> Duration {
+        self.0
+    }
+
+    /// Retourne l'instant situé `duration` plus tard, ou `None` en cas de
+    /// dépassement
+    pub fn checked_add(self, duration: Duration) -> Option<Instant> {
+        self.0.checked_add(duration).map(Instant)
+    }
+
+    /// Retourne l'instant situé `duration` plus tôt, ou `None` s'il
+    /// précède l'origine
+    pub fn checked_sub(self, duration: Duration) -> Option<Instant> {
+        self.0.checked_sub(duration).map(Instant)
+    }
+
+    /// Retourne la durée écoulée depuis `earlier`, ou zéro si `earlier` est
+    /// postérieur
+    pub fn saturating_duration_since(self, earlier: Instant) -> Duration {
+        self.0.saturating_sub(earlier.0)
+    }
+
+    /// Retourne la durée écoulée depuis `earlier`, ou `None` si `earlier`
+    /// est postérieur
+    pub fn checked_duration_since(self, earlier: Instant) -> Option<Duration> {
+        self.0.checked_sub(earlier.0)
+    }
+}
+
+#[cfg(not(feature = "std"))]
+impl core::ops::Add<Duration> for Instant {
+    type Output = Instant;
+
+    /// # Panics
+    ///
+    /// Panique en cas de dépassement, comme `std::time::Instant`
+    fn add(self, duration: Duration) -> Instant {
+        self.checked_add(duration).expect("dépassement lors de l'ajout d'une durée à un instant")
+    }
+}
+
+/// Horloge par défaut sans la bibliothèque standard : le temps ne s'écoule
+/// pas, les entrées n'expirent donc jamais
+#[cfg(not(feature = "std"))]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct StoppedClock;
+
+#[cfg(not(feature = "std"))]
+impl Clock for StoppedClock {
+    fn now(&self) -> Instant {
+        Instant::default()
+    }
+}
+
+#[cfg(feature = "test-util")]
+pub use mock::MockClock;
+
+#[cfg(feature = "test-util")]
+mod mock {
+    use super::Clock;
+    use std::sync::{Arc, Mutex};
+    use std::time::{Duration, Instant, SystemTime};
+
+    /// Marge permettant de faire reculer l'horloge sous l'instant de création
+    const REWIND_MARGIN: Duration = Duration::from_secs(365 * 24 * 3600);
+
+    /// Horloge manipulable pour les tests
+    ///
+    /// Les clones partagent le même temps : on peut donner un clone au cache et
+    /// garder l'original pour le faire avancer. La date courante
+    /// ([`Clock::system_time`]) part du 1er janvier 1970 et suit `advance` et
+    /// `rewind`.
+    ///
+    /// # Examples
+    /// ```
+    /// use lru_cache::Cache;
+    /// use lru_cache::cache::clock::MockClock;
+    /// use std::time::Duration;
+    ///
+    /// let clock = MockClock::new();
+    /// let mut cache = Cache::with_clock(2, clock.clone());
+    /// cache.put_with_ttl("key", 1, Duration::from_secs(10));
+    /// clock.advance(Duration::from_secs(10));
+    /// assert_eq!(cache.peek(&"key"), None);
+    /// ```
+    #[derive(Debug, Clone)]
+    pub struct MockClock {
+        now: Arc<Mutex<(Instant, SystemTime)>>,
+    }
+
+    impl MockClock {
+        /// Crée une horloge arrêtée
+        ///
+        /// L'horloge peut reculer d'au moins un an par rapport à son instant
+        /// de départ.
+        pub fn new() -> Self {
+            MockClock {
+                now: Arc::new(Mutex::new((Instant::now() + REWIND_MARGIN, SystemTime::UNIX_EPOCH))),
+            }
+        }
+
+        /// Fait avancer l'horloge de `duration`
+        pub fn advance(&self, duration: Duration) {
+            let mut now = self.now.lock().unwrap_or_else(|e| e.into_inner());
+            now.0 += duration;
+            now.1 += duration;
+        }
+
+        /// Fait reculer l'horloge de `duration`, pour simuler une horloge non
+        /// monotone
+        pub fn rewind(&self, duration: Duration) {
+            let mut now = self.now.lock().unwrap_or_else(|e| e.into_inner());
+            if let (Some(earlier), Some(date)) = (now.0.checked_sub(duration), now.1.checked_sub(duration)) {
+                *now = (earlier, date);
+            }
+        }
+    }
+
+    impl Default for MockClock {
+        fn default() -> Self {
+            MockClock::new()
+        }
+    }
+
+    impl Clock for MockClock {
+        fn now(&self) -> Instant {
+            self.now.lock().unwrap_or_else(|e| e.into_inner()).0
+        }
+
+        fn system_time(&self) -> SystemTime {
+            self.now.lock().unwrap_or_else(|e| e.into_inner()).1
+        }
+    }
+}
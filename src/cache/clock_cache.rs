@@ -0,0 +1,218 @@
+use super::traits::{CacheIterate, CacheLen, CacheRemove, CacheStorage, GetOptions};
+use std::collections::hash_map::RandomState;
+use std::collections::HashMap;
+use std::hash::{BuildHasher, Hash};
+
+#[derive(Debug)]
+struct Entry<K, V> {
+    key: K,
+    value: V,
+    hash: u64,
+    referenced: bool,
+    bucket_next: Option<usize>,
+}
+
+/// Cache CLOCK (seconde chance), approximation de LRU sans réordonnancement
+///
+/// Les entrées occupent un anneau d'emplacements alloué une fois pour toutes
+/// à la construction. Un `get` se contente de positionner le bit de
+/// référence de l'entrée. Pour libérer une place, une aiguille parcourt
+/// l'anneau : chaque entrée référencée perd son bit et obtient une seconde
+/// chance, la première entrée non référencée est évincée. Une nouvelle
+/// entrée n'est pas référencée ; un `put` sur une clé existante la marque
+/// comme référencée. Les clés n'ont pas besoin d'implémenter `Clone`.
+///
+/// # Examples
+/// ```
+/// use lru_cache::cache::clock_cache::ClockCache;
+/// use lru_cache::cache::traits::CacheStorage;
+///
+/// let mut cache = ClockCache::new(2);
+/// cache.put("A", 1);
+/// cache.put("B", 2);
+/// cache.get(&"A");
+/// cache.put("C", 3);
+/// assert_eq!(cache.get(&"B"), None);
+/// assert_eq!(cache.get(&"A"), Some(&1));
+/// ```
+#[derive(Debug)]
+pub struct ClockCache<K, V> {
+    slots: Vec<Option<Entry<K, V>>>,
+    free: Vec<usize>,
+    map: HashMap<u64, usize>,
+    hasher: RandomState,
+    hand: usize,
+}
+
+impl<K: Eq + Hash, V> ClockCache<K, V> {
+    /// Crée un cache dont l'anneau de `capacity` emplacements est alloué
+    /// immédiatement
+    pub fn new(capacity: usize) -> Self {
+        ClockCache {
+            slots: (0..capacity).map(|_| None).collect(),
+            free: (0..capacity).rev().collect(),
+            map: HashMap::with_capacity(capacity),
+            hasher: RandomState::new(),
+            hand: 0,
+        }
+    }
+
+    /// Retourne la capacité du cache
+    pub fn capacity(&self) -> usize {
+        self.slots.len()
+    }
+
+    /// Indique si l'entrée associée à `key` a été référencée depuis le
+    /// dernier passage de l'aiguille
+    pub fn is_referenced(&self, key: &K) -> Option<bool> {
+        self.find(key).map(|index| self.entry(index).referenced)
+    }
+
+    fn entry(&self, index: usize) -> &Entry<K, V> {
+        self.slots[index].as_ref().expect("emplacement vide dans l'anneau")
+    }
+
+    fn entry_mut(&mut self, index: usize) -> &mut Entry<K, V> {
+        self.slots[index].as_mut().expect("emplacement vide dans l'anneau")
+    }
+
+    fn find(&self, key: &K) -> Option<usize> {
+        let mut current = self.map.get(&self.hasher.hash_one(key)).copied();
+        while let Some(index) = current {
+            let entry = self.entry(index);
+            if entry.key == *key {
+                return Some(index);
+            }
+            current = entry.bucket_next;
+        }
+        None
+    }
+
+    /// Fait tourner l'aiguille jusqu'à une entrée non référencée et retourne
+    /// son emplacement, libéré
+    fn sweep(&mut self) -> usize {
+        loop {
+            let index = self.hand;
+            self.hand = (self.hand + 1) % self.slots.len();
+            let entry = self.entry_mut(index);
+            if entry.referenced {
+                entry.referenced = false;
+            } else {
+                self.take(index);
+                return index;
+            }
+        }
+    }
+
+    fn take(&mut self, index: usize) -> Entry<K, V> {
+        let (hash, bucket_next) = {
+            let entry = self.entry(index);
+            (entry.hash, entry.bucket_next)
+        };
+        if self.map.get(&hash) == Some(&index) {
+            match bucket_next {
+                Some(next) => self.map.insert(hash, next),
+                None => self.map.remove(&hash),
+            };
+        } else {
+            let mut current = self.map.get(&hash).copied();
+            while let Some(previous) = current {
+                let entry = self.entry_mut(previous);
+                if entry.bucket_next == Some(index) {
+                    entry.bucket_next = bucket_next;
+                    break;
+                }
+                current = entry.bucket_next;
+            }
+        }
+        self.slots[index].take().expect("emplacement vide dans l'anneau")
+    }
+}
+
+impl<K: Eq + Hash, V> CacheStorage<K, V> for ClockCache<K, V> {
+    fn get(&mut self, key: &K) -> Option<&V> {
+        self.get_with(key, GetOptions::default())
+    }
+
+    fn get_with(&mut self, key: &K, options: GetOptions) -> Option<&V> {
+        let index = self.find(key)?;
+        let entry = self.entry_mut(index);
+        if options.promote {
+            entry.referenced = true;
+        }
+        Some(&entry.value)
+    }
+
+    fn put(&mut self, key: K, value: V) {
+        if let Some(index) = self.find(&key) {
+            let entry = self.entry_mut(index);
+            entry.value = value;
+            entry.referenced = true;
+            return;
+        }
+        if self.slots.is_empty() {
+            return;
+        }
+        let index = match self.free.pop() {
+            Some(index) => index,
+            None => self.sweep(),
+        };
+        let hash = self.hasher.hash_one(&key);
+        self.slots[index] = Some(Entry {
+            key,
+            value,
+            hash,
+            referenced: false,
+            bucket_next: self.map.insert(hash, index),
+        });
+    }
+}
+
+impl<K: Eq + Hash, V> CacheRemove<K, V> for ClockCache<K, V> {
+    fn remove(&mut self, key: &K) -> Option<V> {
+        let index = self.find(key)?;
+        self.free.push(index);
+        Some(self.take(index).value)
+    }
+}
+
+impl<K, V> CacheLen for ClockCache<K, V> {
+    fn len(&self) -> usize {
+        self.slots.len() - self.free.len()
+    }
+}
+
+/// Itérateur sur les entrées d'un [`ClockCache`], dans l'ordre de passage
+/// de l'aiguille
+pub struct Iter<'a, K, V> {
+    slots: &'a [Option<Entry<K, V>>],
+    hand: usize,
+    visited: usize,
+}
+
+impl<'a, K, V> Iterator for Iter<'a, K, V> {
+    type Item = (&'a K, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.visited < self.slots.len() {
+            let index = (self.hand + self.visited) % self.slots.len();
+            self.visited += 1;
+            if let Some(entry) = &self.slots[index] {
+                return Some((&entry.key, &entry.value));
+            }
+        }
+        None
+    }
+}
+
+impl<K, V> CacheIterate<K, V> for ClockCache<K, V> {
+    type Iter<'a> = Iter<'a, K, V> where Self: 'a, K: 'a, V: 'a;
+
+    fn iter(&self) -> Self::Iter<'_> {
+        Iter {
+            slots: &self.slots,
+            hand: self.hand,
+            visited: 0,
+        }
+    }
+}
@@ -0,0 +1,543 @@
+//! Cache partageable entre threads
+
+use super::flight::{Flights, Role};
+use super::lru::Cache;
+use super::traits::{CacheIterate, CacheLen, CacheRemove, CacheStorage, GetOptions};
+use crate::storage::file::save_entries;
+use std::fmt::Display;
+#[cfg(feature = "async")]
+use std::future::Future;
+use std::hash::Hash;
+use std::io;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{Arc, Condvar, Mutex, MutexGuard, PoisonError};
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+
+/// [`Cache`] protégé par un verrou unique, utilisable depuis plusieurs threads
+///
+/// Les lectures retournent une copie de la valeur puisque la référence ne peut
+/// pas survivre au verrou. Pour partager le cache, on le place dans un `Arc`.
+///
+/// # Examples
+/// ```
+/// use lru_cache::cache::concurrent::ConcurrentCache;
+/// use std::sync::Arc;
+/// use std::thread;
+///
+/// let cache = Arc::new(ConcurrentCache::new(10));
+/// let writer = Arc::clone(&cache);
+/// thread::spawn(move || writer.put("key", 1)).join().unwrap();
+/// assert_eq!(cache.get(&"key"), Some(1));
+/// ```
+#[derive(Debug)]
+pub struct ConcurrentCache<K, V> {
+    inner: Arc<Mutex<Cache<K, V>>>,
+    flights: Flights<K, V>,
+    reaper: Option<Reaper>,
+    flusher: Option<Flusher>,
+}
+
+impl<K: Eq + Hash, V> ConcurrentCache<K, V> {
+    /// Crée un cache partagé avec la capacité spécifiée
+    ///
+    /// # Panics
+    ///
+    /// Panique si `capacity` vaut 0, comme [`Cache::new`].
+    pub fn new(capacity: usize) -> Self {
+        ConcurrentCache::from_cache(Cache::new(capacity))
+    }
+
+    /// Rend partageable un cache existant, avec sa configuration
+    pub fn from_cache(cache: Cache<K, V>) -> Self {
+        ConcurrentCache {
+            inner: Arc::new(Mutex::new(cache)),
+            flights: Flights::default(),
+            reaper: None,
+            flusher: None,
+        }
+    }
+
+    /// Verrouille le cache pour accéder à toute son API
+    ///
+    /// Un verrou empoisonné par la panique d'un autre thread est récupéré :
+    /// les opérations du cache laissent toujours sa structure cohérente.
+    ///
+    /// Avec [`ConcurrentCache::with_persistence`], les modifications faites
+    /// par ce verrou ne comptent pas dans `max_dirty_ops` ; elles sont
+    /// sauvegardées au prochain intervalle.
+    pub fn lock(&self) -> MutexGuard<'_, Cache<K, V>> {
+        if let Some(flusher) = &self.flusher {
+            flusher.persistence.touched.store(true, Ordering::Relaxed);
+        }
+        self.locked()
+    }
+
+    /// Verrouille le cache sans le marquer comme modifié
+    fn locked(&self) -> MutexGuard<'_, Cache<K, V>> {
+        self.inner.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Compte `ops` modifications pour la sauvegarde automatique
+    fn mutated(&self, ops: usize) {
+        if let Some(flusher) = &self.flusher {
+            flusher.persistence.mutated(ops);
+        }
+    }
+
+    /// Récupère une copie de la valeur et marque l'entrée comme utilisée
+    pub fn get(&self, key: &K) -> Option<V>
+    where
+        V: Clone,
+    {
+        self.locked().get(key).cloned()
+    }
+
+    /// Comme [`ConcurrentCache::get`], en choisissant les effets de la
+    /// lecture (voir [`CacheStorage::get_with`])
+    pub fn get_with(&self, key: &K, options: GetOptions) -> Option<V>
+    where
+        V: Clone,
+    {
+        self.locked().get_with(key, options).cloned()
+    }
+
+    /// Insère une valeur dans le cache
+    pub fn put(&self, key: K, value: V) {
+        self.locked().put(key, value);
+        self.mutated(1);
+    }
+
+    /// Supprime une entrée et retourne sa valeur
+    pub fn remove(&self, key: &K) -> Option<V> {
+        let removed = self.locked().remove(key);
+        if removed.is_some() {
+            self.mutated(1);
+        }
+        removed
+    }
+
+    /// Retourne le nombre d'entrées, expirées ou non
+    pub fn len(&self) -> usize {
+        self.locked().len()
+    }
+
+    /// Indique si le cache est vide
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Retire les entrées expirées et retourne leur nombre
+    pub fn purge_expired(&self) -> usize {
+        let purged = self.locked().purge_expired();
+        if purged > 0 {
+            self.mutated(purged);
+        }
+        purged
+    }
+}
+
+impl<K: Eq + Hash + Clone, V: Clone> ConcurrentCache<K, V> {
+    /// Récupère une copie de la valeur associée à `key`, ou la charge avec
+    /// `load` et l'insère si la clé est absente
+    ///
+    /// Le verrou du cache n'est pas gardé pendant le chargement. Les appels
+    /// concurrents qui manquent la même clé sont regroupés : seul le premier
+    /// exécute `load`, les autres attendent et reçoivent une copie de son
+    /// résultat. Si ce chargement panique, la panique remonte chez son
+    /// appelant et les autres recommencent, l'un d'eux devenant le nouveau
+    /// chargeur. Si la clé a été insérée par `put` pendant le chargement, la
+    /// valeur déjà présente l'emporte et celle qui vient d'être chargée est
+    /// abandonnée.
+    ///
+    /// # Examples
+    /// ```
+    /// use lru_cache::cache::concurrent::ConcurrentCache;
+    ///
+    /// let cache = ConcurrentCache::new(2);
+    /// assert_eq!(cache.get_or_insert_with("key", || 1), 1);
+    /// assert_eq!(cache.get_or_insert_with("key", || 2), 1);
+    /// ```
+    pub fn get_or_insert_with(&self, key: K, load: impl FnOnce() -> V) -> V {
+        let mut load = Some(load);
+        loop {
+            if let Some(value) = self.get(&key) {
+                return value;
+            }
+            match self.flights.join(&key) {
+                Role::Waiter(flight) => {
+                    if let Some(value) = flight.wait() {
+                        return value;
+                    }
+                }
+                Role::Leader(leader) => {
+                    // Le chargeur précédent a pu insérer la clé depuis la lecture
+                    if let Some(value) = self.present(&key) {
+                        leader.finish(value.clone());
+                        return value;
+                    }
+                    let load = load.take().expect("un appelant ne charge qu'une fois");
+                    let value = self.insert_loaded(key, load());
+                    leader.finish(value.clone());
+                    return value;
+                }
+            }
+        }
+    }
+
+    /// Comme [`ConcurrentCache::get_or_insert_with`], en attendant la
+    /// future `load` sans bloquer le thread (feature `async`)
+    ///
+    /// `load` n'est attendue que si l'appel devient le chargeur de la clé.
+    /// Si la tâche qui charge est abandonnée pendant l'attente, les autres
+    /// appels recommencent comme après une panique.
+    #[cfg(feature = "async")]
+    pub async fn get_or_insert_with_async<F: Future<Output = V>>(&self, key: K, load: F) -> V {
+        let mut load = Some(load);
+        loop {
+            if let Some(value) = self.get(&key) {
+                return value;
+            }
+            match self.flights.join(&key) {
+                Role::Waiter(flight) => {
+                    if let Some(value) = flight.wait_async().await {
+                        return value;
+                    }
+                }
+                Role::Leader(leader) => {
+                    // Le chargeur précédent a pu insérer la clé depuis la lecture
+                    if let Some(value) = self.present(&key) {
+                        leader.finish(value.clone());
+                        return value;
+                    }
+                    let load = load.take().expect("un appelant ne charge qu'une fois");
+                    let value = self.insert_loaded(key, load.await);
+                    leader.finish(value.clone());
+                    return value;
+                }
+            }
+        }
+    }
+
+    /// Options des consultations qui suivent une lecture manquée, qui ne
+    /// comptent pas dans [`Cache::stats`](super::lru::Cache::stats)
+    const RECHECK: GetOptions = GetOptions {
+        promote: true,
+        record_stats: false,
+    };
+
+    /// Retourne une copie de la valeur de `key` si elle est présente, sans
+    /// compter de nouvelle lecture
+    fn present(&self, key: &K) -> Option<V> {
+        self.locked().get_with(key, Self::RECHECK).cloned()
+    }
+
+    /// Insère `loaded` sauf si la clé est déjà présente, et retourne la
+    /// valeur qui reste dans le cache
+    fn insert_loaded(&self, key: K, loaded: V) -> V {
+        let mut cache = self.locked();
+        if let Some(value) = cache.get_with(&key, Self::RECHECK) {
+            return value.clone();
+        }
+        cache.put(key, loaded.clone());
+        drop(cache);
+        self.mutated(1);
+        loaded
+    }
+}
+
+impl<K, V> ConcurrentCache<K, V>
+where
+    K: Eq + Hash + Send + 'static,
+    V: Send + 'static,
+{
+    /// Démarre un thread qui appelle `purge_expired` toutes les `interval`
+    ///
+    /// Sans ce thread, l'expiration est paresseuse et un cache qui ne reçoit
+    /// plus de trafic conserve ses entrées expirées. Le thread est arrêté et
+    /// attendu par [`ConcurrentCache::stop_expiration_thread`] ou à la
+    /// destruction du cache. Démarrer un nouveau thread arrête le précédent.
+    pub fn spawn_expiration_thread(&mut self, interval: Duration) {
+        self.stop_expiration_thread();
+        let cache = Arc::clone(&self.inner);
+        self.reaper = Some(Reaper::spawn(interval, move || {
+            cache.lock().unwrap_or_else(PoisonError::into_inner).purge_expired();
+        }));
+    }
+}
+
+impl<K, V> ConcurrentCache<K, V>
+where
+    K: Eq + Hash + Clone + Display + Send + 'static,
+    V: Clone + Display + Send + 'static,
+{
+    /// Sauvegarde automatiquement le cache dans `path` selon `policy`
+    ///
+    /// Un thread sauvegarde le cache toutes les `policy.interval` s'il a été
+    /// modifié depuis la dernière sauvegarde, et sans attendre dès que
+    /// `policy.max_dirty_ops` modifications se sont accumulées. Le verrou du
+    /// cache n'est gardé que le temps de copier les entrées ; l'écriture se
+    /// fait ensuite, de façon atomique comme
+    /// [`save_to_file`](super::traits::PersistentStorage::save_to_file), dans
+    /// le même format. Une sauvegarde en échec est retentée à l'intervalle
+    /// suivant et signalée par [`ConcurrentCache::last_flush_error`]. La
+    /// destruction du cache arrête le thread après une dernière sauvegarde.
+    ///
+    /// Le fichier existant n'est pas chargé : pour repartir d'une sauvegarde,
+    /// on la charge avec `load_from_file` puis on la passe à
+    /// [`ConcurrentCache::from_cache`]. Une nouvelle configuration remplace
+    /// la précédente après une dernière sauvegarde dans l'ancien fichier.
+    ///
+    /// # Panics
+    ///
+    /// Panique si `policy.interval` est nul.
+    ///
+    /// # Examples
+    /// ```
+    /// use lru_cache::cache::concurrent::{ConcurrentCache, FlushPolicy};
+    /// use std::time::Duration;
+    ///
+    /// let path = std::env::temp_dir().join(format!("flush_doc_{}.txt", std::process::id()));
+    /// let policy = FlushPolicy { interval: Duration::from_secs(30), max_dirty_ops: 1000 };
+    /// let cache = ConcurrentCache::new(10).with_persistence(&path, policy);
+    /// cache.put("key", 1);
+    /// drop(cache);
+    /// assert!(std::fs::read_to_string(&path).unwrap().contains("key;1"));
+    /// std::fs::remove_file(&path).unwrap();
+    /// ```
+    pub fn with_persistence(mut self, path: impl Into<PathBuf>, policy: FlushPolicy) -> Self {
+        assert!(!policy.interval.is_zero(), "l'intervalle de sauvegarde doit être non nul");
+        self.stop_persistence();
+        let persistence = Arc::new(Persistence {
+            path: path.into(),
+            max_dirty_ops: policy.max_dirty_ops,
+            dirty: AtomicUsize::new(0),
+            touched: AtomicBool::new(false),
+            last_error: Mutex::new(None),
+            signal: (Mutex::new(FlushSignal::default()), Condvar::new()),
+        });
+        let cache = Arc::clone(&self.inner);
+        let shared = Arc::clone(&persistence);
+        let handle = thread::spawn(move || {
+            let (signal, wakeup) = &shared.signal;
+            let mut guard = signal.lock().unwrap_or_else(PoisonError::into_inner);
+            loop {
+                let (next, _) = wakeup
+                    .wait_timeout_while(guard, policy.interval, |signal| !signal.stop && !signal.pending)
+                    .unwrap_or_else(PoisonError::into_inner);
+                guard = next;
+                guard.pending = false;
+                let stop = guard.stop;
+                drop(guard);
+                if shared.needs_flush() {
+                    // L'erreur est gardée pour last_flush_error et l'essai suivant
+                    let _ = flush(&cache, &shared);
+                }
+                if stop {
+                    return;
+                }
+                guard = signal.lock().unwrap_or_else(PoisonError::into_inner);
+            }
+        });
+        self.flusher = Some(Flusher {
+            persistence,
+            handle: Some(handle),
+        });
+        self
+    }
+
+    /// Sauvegarde le cache immédiatement dans le fichier de
+    /// [`ConcurrentCache::with_persistence`]
+    ///
+    /// Sans effet si la sauvegarde automatique n'est pas configurée.
+    ///
+    /// # Errors
+    ///
+    /// Retourne l'erreur d'écriture, également gardée par
+    /// [`ConcurrentCache::last_flush_error`].
+    pub fn flush_now(&self) -> io::Result<()> {
+        match &self.flusher {
+            Some(flusher) => flush(&self.inner, &flusher.persistence),
+            None => Ok(()),
+        }
+    }
+}
+
+impl<K, V> ConcurrentCache<K, V> {
+    /// Retourne l'erreur de la dernière sauvegarde automatique, ou `None`
+    /// si elle a réussi
+    ///
+    /// L'erreur retournée est une copie de même genre et de même message que
+    /// l'originale ; elle est effacée par la prochaine sauvegarde réussie.
+    pub fn last_flush_error(&self) -> Option<io::Error> {
+        let flusher = self.flusher.as_ref()?;
+        let last_error = flusher.persistence.last_error.lock().unwrap_or_else(PoisonError::into_inner);
+        last_error.as_ref().map(|(kind, message)| io::Error::new(*kind, message.clone()))
+    }
+
+    /// Arrête le thread de sauvegarde après une dernière sauvegarde
+    fn stop_persistence(&mut self) {
+        if let Some(flusher) = self.flusher.take() {
+            flusher.stop();
+        }
+    }
+
+    /// Arrête le thread d'expiration s'il existe et attend sa fin
+    pub fn stop_expiration_thread(&mut self) {
+        if let Some(reaper) = self.reaper.take() {
+            reaper.stop();
+        }
+    }
+}
+
+impl<K, V> Drop for ConcurrentCache<K, V> {
+    fn drop(&mut self) {
+        self.stop_expiration_thread();
+        self.stop_persistence();
+    }
+}
+
+/// Déclencheurs de la sauvegarde automatique d'un [`ConcurrentCache`]
+///
+/// La sauvegarde a lieu au premier des deux déclencheurs atteint.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FlushPolicy {
+    /// Délai entre deux sauvegardes d'un cache modifié
+    pub interval: Duration,
+    /// Nombre de modifications qui déclenche une sauvegarde avant la fin de
+    /// l'intervalle ; 0 désactive ce déclencheur
+    pub max_dirty_ops: usize,
+}
+
+/// Toutes les 30 secondes ou toutes les 1000 modifications
+impl Default for FlushPolicy {
+    fn default() -> Self {
+        FlushPolicy {
+            interval: Duration::from_secs(30),
+            max_dirty_ops: 1000,
+        }
+    }
+}
+
+/// État partagé entre un [`ConcurrentCache`] et son thread de sauvegarde
+#[derive(Debug)]
+struct Persistence {
+    path: PathBuf,
+    max_dirty_ops: usize,
+    /// Modifications comptées depuis la dernière copie des entrées
+    dirty: AtomicUsize,
+    /// Vrai si le cache a été verrouillé par `lock` depuis la dernière copie
+    touched: AtomicBool,
+    /// Genre et message de la dernière erreur ; ce verrou est aussi gardé
+    /// pendant toute une sauvegarde, pour qu'une copie plus ancienne ne
+    /// remplace jamais une plus récente
+    last_error: Mutex<Option<(io::ErrorKind, String)>>,
+    signal: (Mutex<FlushSignal>, Condvar),
+}
+
+#[derive(Debug, Default)]
+struct FlushSignal {
+    stop: bool,
+    /// `max_dirty_ops` a été atteint
+    pending: bool,
+}
+
+impl Persistence {
+    fn mutated(&self, ops: usize) {
+        let dirty = self.dirty.fetch_add(ops, Ordering::Relaxed) + ops;
+        if self.max_dirty_ops > 0 && dirty >= self.max_dirty_ops {
+            let (signal, wakeup) = &self.signal;
+            signal.lock().unwrap_or_else(PoisonError::into_inner).pending = true;
+            wakeup.notify_all();
+        }
+    }
+
+    fn needs_flush(&self) -> bool {
+        self.dirty.load(Ordering::Relaxed) > 0
+            || self.touched.load(Ordering::Relaxed)
+            || self.last_error.lock().unwrap_or_else(PoisonError::into_inner).is_some()
+    }
+}
+
+/// Copie les entrées sous le verrou du cache puis les écrit hors du verrou
+fn flush<K, V>(cache: &Mutex<Cache<K, V>>, persistence: &Persistence) -> io::Result<()>
+where
+    K: Eq + Hash + Clone + Display,
+    V: Clone + Display,
+{
+    let mut last_error = persistence.last_error.lock().unwrap_or_else(PoisonError::into_inner);
+    let (capacity, entries, options) = {
+        let cache = cache.lock().unwrap_or_else(PoisonError::into_inner);
+        persistence.dirty.store(0, Ordering::Relaxed);
+        persistence.touched.store(false, Ordering::Relaxed);
+        let entries: Vec<(K, V)> = cache.iter().map(|(key, value)| (key.clone(), value.clone())).collect();
+        (cache.capacity(), entries, cache.save_options())
+    };
+    let result = save_entries(&persistence.path, capacity, entries, options).map(|_| ());
+    *last_error = result.as_ref().err().map(|error| (error.kind(), error.to_string()));
+    result
+}
+
+/// Thread de sauvegarde automatique
+#[derive(Debug)]
+struct Flusher {
+    persistence: Arc<Persistence>,
+    handle: Option<JoinHandle<()>>,
+}
+
+impl Flusher {
+    fn stop(mut self) {
+        let (signal, wakeup) = &self.persistence.signal;
+        signal.lock().unwrap_or_else(PoisonError::into_inner).stop = true;
+        wakeup.notify_all();
+        if let Some(handle) = self.handle.take() {
+            // Une panique de la sauvegarde a déjà été signalée par le thread
+            let _ = handle.join();
+        }
+    }
+}
+
+/// Thread périodique arrêtable
+#[derive(Debug)]
+struct Reaper {
+    stop: Arc<(Mutex<bool>, Condvar)>,
+    handle: Option<JoinHandle<()>>,
+}
+
+impl Reaper {
+    fn spawn<F: FnMut() + Send + 'static>(interval: Duration, mut task: F) -> Self {
+        let stop = Arc::new((Mutex::new(false), Condvar::new()));
+        let signal = Arc::clone(&stop);
+        let handle = thread::spawn(move || {
+            let (stopped, wakeup) = &*signal;
+            let mut guard = stopped.lock().unwrap_or_else(PoisonError::into_inner);
+            loop {
+                let (next, _) = wakeup
+                    .wait_timeout_while(guard, interval, |stopped| !*stopped)
+                    .unwrap_or_else(PoisonError::into_inner);
+                guard = next;
+                if *guard {
+                    return;
+                }
+                drop(guard);
+                task();
+                guard = stopped.lock().unwrap_or_else(PoisonError::into_inner);
+            }
+        });
+        Reaper {
+            stop,
+            handle: Some(handle),
+        }
+    }
+
+    fn stop(mut self) {
+        let (stopped, wakeup) = &*self.stop;
+        *stopped.lock().unwrap_or_else(PoisonError::into_inner) = true;
+        wakeup.notify_all();
+        if let Some(handle) = self.handle.take() {
+            // Une panique de la tâche a déjà été signalée par le thread
+            let _ = handle.join();
+        }
+    }
+}
@@ -0,0 +1,124 @@
+//! Recherche d'une clé par une valeur équivalente d'un autre type
+//!
+//! Les méthodes `*_by` de [`Cache`](super::lru::Cache) (comme
+//! [`Cache::get_by`](super::lru::Cache::get_by)) acceptent toute requête
+//! `Q: Equivalent<K>` : la requête est hachée et comparée aux clés sans être
+//! convertie en `K`. Tout type que `K` emprunte (`&str` pour une clé
+//! `String`) convient. [`CaseInsensitive`] fournit en plus des clés texte
+//! insensibles à la casse, interrogées sans allouer de copie en minuscules.
+
+use alloc::string::String;
+use core::borrow::Borrow;
+use core::fmt;
+use core::hash::{Hash, Hasher};
+use core::str::FromStr;
+
+/// Requête qui désigne les clés `K` auxquelles elle est équivalente
+///
+/// Une requête et la clé équivalente doivent donner le même hash : c'est le
+/// contrat de [`Borrow`], qui fournit une implémentation pour tout type
+/// emprunté par `K`.
+pub trait Equivalent<K: ?Sized>: Hash {
+    /// Indique si `key` est désignée par cette requête
+    fn equivalent(&self, key: &K) -> bool;
+}
+
+impl<Q, K> Equivalent<K> for Q
+where
+    Q: Hash + Eq + ?Sized,
+    K: Borrow<Q> + ?Sized,
+{
+    fn equivalent(&self, key: &K) -> bool {
+        self == key.borrow()
+    }
+}
+
+/// Texte comparé et haché sans tenir compte de la casse ASCII
+///
+/// Seules les lettres ASCII sont repliées, ce qui convient aux noms d'en-têtes
+/// HTTP et autres identifiants de protocole ; `"É"` et `"é"` restent
+/// distincts. Une clé `CaseInsensitive<String>` est cherchée par une requête
+/// `CaseInsensitive<&str>`, sans allocation. La clé garde l'orthographe de sa
+/// première insertion : un `put` de la même clé avec une autre casse met à
+/// jour la valeur de l'entrée existante.
+///
+/// # Examples
+/// ```
+/// use lru_cache::Cache;
+/// use lru_cache::cache::equivalent::CaseInsensitive;
+/// use lru_cache::cache::traits::{CacheIterate, CacheStorage};
+///
+/// let mut headers = Cache::new(8);
+/// headers.put(CaseInsensitive(String::from("Content-Type")), "text/html");
+/// headers.put(CaseInsensitive(String::from("content-type")), "text/plain");
+///
+/// assert_eq!(headers.get_by(&CaseInsensitive("CONTENT-TYPE")), Some(&"text/plain"));
+/// let (key, _) = headers.iter().next().unwrap();
+/// assert_eq!(key.0, "Content-Type");
+/// ```
+#[derive(Clone, Copy, Default)]
+pub struct CaseInsensitive<S>(pub S);
+
+impl<S> CaseInsensitive<S> {
+    /// Retourne le texte enveloppé
+    pub fn into_inner(self) -> S {
+        self.0
+    }
+}
+
+impl<S: AsRef<str>> CaseInsensitive<S> {
+    /// Retourne le texte avec sa casse d'origine
+    pub fn as_str(&self) -> &str {
+        self.0.as_ref()
+    }
+}
+
+impl<S: AsRef<str>> Hash for CaseInsensitive<S> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        for byte in self.as_str().bytes() {
+            state.write_u8(byte.to_ascii_lowercase());
+        }
+        // Sépare les textes successifs d'une clé composée, comme `str`
+        state.write_u8(0xff);
+    }
+}
+
+impl<S: AsRef<str>, T: AsRef<str>> PartialEq<CaseInsensitive<T>> for CaseInsensitive<S> {
+    fn eq(&self, other: &CaseInsensitive<T>) -> bool {
+        self.as_str().eq_ignore_ascii_case(other.as_str())
+    }
+}
+
+impl<S: AsRef<str>> Eq for CaseInsensitive<S> {}
+
+impl Equivalent<CaseInsensitive<String>> for CaseInsensitive<&str> {
+    fn equivalent(&self, key: &CaseInsensitive<String>) -> bool {
+        self == key
+    }
+}
+
+impl<S: AsRef<str>> fmt::Debug for CaseInsensitive<S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self.as_str(), f)
+    }
+}
+
+impl<S: AsRef<str>> fmt::Display for CaseInsensitive<S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl<S: FromStr> FromStr for CaseInsensitive<S> {
+    type Err = S::Err;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        text.parse().map(CaseInsensitive)
+    }
+}
+
+impl From<&str> for CaseInsensitive<String> {
+    fn from(text: &str) -> Self {
+        CaseInsensitive(String::from(text))
+    }
+}
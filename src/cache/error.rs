@@ -0,0 +1,129 @@
+//! Erreurs de construction et de cohérence d'un cache
+
+use core::fmt;
+
+/// Paramètre refusé à la construction d'un cache
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum CacheError {
+    /// La capacité vaut 0 : le cache ne pourrait rien garder
+    ZeroCapacity,
+    /// Le seuil bas d'éviction dépasse le seuil haut (la capacité)
+    InvalidWatermarks { high: usize, low: usize },
+    /// La granularité des échéances est nulle
+    ZeroGranularity,
+    /// La dispersion des durées de vie n'est pas comprise entre 0 et 1
+    InvalidJitter,
+    /// La fraction de rechargement anticipé n'est pas comprise entre 0 et 1
+    InvalidRefreshAhead,
+    /// La période de vieillissement des compteurs LFU est nulle
+    ZeroDecayPeriod,
+    /// Une politique intégrée autre que `Lru` est combinée à une politique
+    /// d'éviction fournie au cache
+    ConflictingPolicies,
+}
+
+impl fmt::Display for CacheError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CacheError::ZeroCapacity => write!(f, "la capacité d'un cache doit être au moins 1"),
+            CacheError::InvalidWatermarks { high, low } => write!(
+                f,
+                "le seuil bas d'éviction ({}) dépasse le seuil haut ({})",
+                low, high
+            ),
+            CacheError::ZeroGranularity => write!(f, "la granularité des échéances doit être non nulle"),
+            CacheError::InvalidJitter => {
+                write!(f, "la dispersion des durées de vie doit être comprise entre 0 et 1")
+            }
+            CacheError::InvalidRefreshAhead => {
+                write!(f, "la fraction de rechargement anticipé doit être comprise entre 0 et 1")
+            }
+            CacheError::ZeroDecayPeriod => write!(f, "la période de vieillissement doit être non nulle"),
+            CacheError::ConflictingPolicies => {
+                write!(f, "une politique intégrée ne peut pas remplacer une politique d'éviction fournie")
+            }
+        }
+    }
+}
+
+impl core::error::Error for CacheError {}
+
+/// Écriture refusée par
+/// [`Cache::compare_and_put`](crate::Cache::compare_and_put)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CasError<V> {
+    /// Valeur qui n'a pas été écrite
+    pub value: V,
+    /// Version actuelle de l'entrée, 0 si la clé est absente
+    pub current_version: u64,
+}
+
+impl<V> fmt::Display for CasError<V> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "écriture refusée, version actuelle {}", self.current_version)
+    }
+}
+
+impl<V: fmt::Debug> core::error::Error for CasError<V> {}
+
+/// Incohérence interne détectée par
+/// [`Cache::check_invariants`](crate::Cache::check_invariants)
+///
+/// Les positions comptent les entrées depuis la tête de l'ordre d'éviction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum InvariantError {
+    /// Le chaînage de l'ordre d'éviction est rompu à la position `position`
+    /// (lien précédent incohérent, emplacement vide, boucle ou queue erronée)
+    BrokenOrder { position: usize },
+    /// L'ordre d'éviction et la table d'index ne comptent pas autant
+    /// d'entrées que le cache
+    LengthMismatch { ordered: usize, indexed: usize, len: usize },
+    /// La clé à la position `position` est introuvable par la table d'index
+    MissingFromIndex { position: usize },
+    /// La clé à la position `position` est aussi stockée dans une autre
+    /// entrée
+    DuplicateKey { position: usize },
+    /// La table d'index désigne l'emplacement `slot`, vide ou d'un autre hash
+    StaleIndex { slot: usize },
+    /// Le cache dépasse sa capacité alors que des entrées non épinglées
+    /// pourraient être évincées
+    OverCapacity { size: usize, capacity: usize },
+    /// Un compteur tenu par le cache ne correspond pas aux entrées
+    CounterMismatch { counter: &'static str, stored: usize, actual: usize },
+}
+
+impl fmt::Display for InvariantError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InvariantError::BrokenOrder { position } => {
+                write!(f, "ordre d'éviction rompu à la position {}", position)
+            }
+            InvariantError::LengthMismatch { ordered, indexed, len } => write!(
+                f,
+                "{} entrées dans l'ordre d'éviction, {} dans l'index, {} attendues",
+                ordered, indexed, len
+            ),
+            InvariantError::MissingFromIndex { position } => {
+                write!(f, "la clé à la position {} est absente de l'index", position)
+            }
+            InvariantError::DuplicateKey { position } => {
+                write!(f, "la clé à la position {} est stockée deux fois", position)
+            }
+            InvariantError::StaleIndex { slot } => {
+                write!(f, "l'index désigne l'emplacement {} qui ne correspond pas", slot)
+            }
+            InvariantError::OverCapacity { size, capacity } => {
+                write!(f, "taille {} au-delà de la capacité {}", size, capacity)
+            }
+            InvariantError::CounterMismatch { counter, stored, actual } => write!(
+                f,
+                "compteur {} à {} au lieu de {}",
+                counter, stored, actual
+            ),
+        }
+    }
+}
+
+impl core::error::Error for InvariantError {}
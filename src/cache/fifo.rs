@@ -0,0 +1,124 @@
+use super::list::{self, NodeList};
+use super::traits::{CacheIterate, CacheLen, CacheRemove, CacheStorage, PersistentStorage};
+use std::fmt::Display;
+use std::hash::Hash;
+use std::num::NonZeroUsize;
+use std::path::Path;
+use std::str::FromStr;
+
+/// Cache FIFO (First In, First Out) qui évince toujours la plus ancienne
+/// insertion
+///
+/// Les lectures ne modifient jamais l'ordre d'éviction, ce qui rend le
+/// contenu du cache indépendant des accès. Un `put` sur une clé existante
+/// remplace la valeur sans déplacer l'entrée : l'ancienneté est celle de la
+/// première insertion de la clé, pour qu'une mise à jour ne prolonge pas sa
+/// présence. Les clés n'ont pas besoin d'implémenter `Clone`.
+///
+/// # Examples
+/// ```
+/// use lru_cache::cache::fifo::FifoCache;
+/// use lru_cache::cache::traits::CacheStorage;
+///
+/// let mut cache = FifoCache::new(2);
+/// cache.put("A", 1);
+/// cache.put("B", 2);
+/// cache.get(&"A");
+/// cache.put("C", 3);
+/// assert_eq!(cache.get(&"A"), None);
+/// assert_eq!(cache.get(&"B"), Some(&2));
+/// ```
+#[derive(Debug)]
+pub struct FifoCache<K, V> {
+    capacity: usize,
+    entries: NodeList<K, V, ()>,
+}
+
+impl<K: Eq + Hash, V> FifoCache<K, V> {
+    /// Crée un nouveau cache avec la capacité spécifiée
+    ///
+    /// Un cache de capacité 0 ne garde aucune entrée.
+    pub fn new(capacity: usize) -> Self {
+        FifoCache {
+            capacity,
+            entries: NodeList::new(),
+        }
+    }
+
+    /// Retourne la capacité du cache
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+}
+
+impl<K: Eq + Hash, V> CacheStorage<K, V> for FifoCache<K, V> {
+    fn get(&mut self, key: &K) -> Option<&V> {
+        let index = self.entries.find(key)?;
+        Some(&self.entries.node(index).value)
+    }
+
+    fn put(&mut self, key: K, value: V) {
+        if let Some(index) = self.entries.find(&key) {
+            self.entries.node_mut(index).value = value;
+            return;
+        }
+        if self.capacity == 0 {
+            return;
+        }
+        if self.entries.len() >= self.capacity {
+            if let Some(oldest) = self.entries.head() {
+                self.entries.remove(oldest);
+            }
+        }
+        self.entries.push(key, value, ());
+    }
+}
+
+impl<K: Eq + Hash, V> CacheRemove<K, V> for FifoCache<K, V> {
+    fn remove(&mut self, key: &K) -> Option<V> {
+        let index = self.entries.find(key)?;
+        Some(self.entries.remove(index).value)
+    }
+}
+
+impl<K, V> CacheLen for FifoCache<K, V> {
+    fn len(&self) -> usize {
+        self.entries.len()
+    }
+}
+
+/// Itérateur sur les entrées d'un [`FifoCache`], de la plus ancienne à la
+/// plus récente
+pub struct Iter<'a, K, V>(list::Iter<'a, K, V, ()>);
+
+impl<'a, K, V> Iterator for Iter<'a, K, V> {
+    type Item = (&'a K, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next()
+    }
+}
+
+impl<K, V> CacheIterate<K, V> for FifoCache<K, V> {
+    type Iter<'a> = Iter<'a, K, V> where Self: 'a, K: 'a, V: 'a;
+
+    fn iter(&self) -> Self::Iter<'_> {
+        Iter(self.entries.iter())
+    }
+}
+
+impl<K: Eq + Hash + Display + FromStr, V: Display + FromStr> PersistentStorage<K, V> for FifoCache<K, V> {
+    fn save_to_file(&self, path: impl AsRef<Path>) -> std::io::Result<()> {
+        let data: Vec<_> = self.iter().collect();
+        crate::storage::file::FileStorage::save(path, self.capacity, &data)
+    }
+
+    fn load_from_file(path: impl AsRef<Path>, capacity: NonZeroUsize) -> std::io::Result<Self> {
+        let (_, data) = crate::storage::file::FileStorage::load(path)?;
+        let mut cache = FifoCache::new(capacity.get());
+        for (key, value) in data {
+            cache.put(key, value);
+        }
+        Ok(cache)
+    }
+}
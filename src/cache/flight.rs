@@ -0,0 +1,173 @@
+//! Regroupement des chargements concurrents d'une même clé
+//!
+//! Le premier appelant qui manque une clé devient le meneur d'un vol : il
+//! exécute le chargement pendant que les suivants attendent son résultat,
+//! bloqués sur une `Condvar` ou suspendus avec leur `Waker`. Si le meneur
+//! panique ou que sa tâche est abandonnée, le vol échoue et réveille les
+//! attentes, qui recommencent depuis la consultation du cache.
+
+use std::collections::HashMap;
+#[cfg(feature = "async")]
+use std::future::Future;
+use std::hash::Hash;
+#[cfg(feature = "async")]
+use std::pin::Pin;
+use std::sync::{Arc, Condvar, Mutex, MutexGuard, PoisonError};
+#[cfg(feature = "async")]
+use std::task::{Context, Poll};
+use std::task::Waker;
+
+#[derive(Debug)]
+enum State<V> {
+    Loading,
+    Done(V),
+    Failed,
+}
+
+/// Chargement en cours d'une clé
+#[derive(Debug)]
+pub(crate) struct Flight<V> {
+    state: Mutex<(State<V>, Vec<Waker>)>,
+    ready: Condvar,
+}
+
+impl<V: Clone> Flight<V> {
+    fn new() -> Self {
+        Flight {
+            state: Mutex::new((State::Loading, Vec::new())),
+            ready: Condvar::new(),
+        }
+    }
+
+    fn lock(&self) -> MutexGuard<'_, (State<V>, Vec<Waker>)> {
+        self.state.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn complete(&self, state: State<V>) {
+        let wakers = {
+            let mut guard = self.lock();
+            guard.0 = state;
+            std::mem::take(&mut guard.1)
+        };
+        self.ready.notify_all();
+        for waker in wakers {
+            waker.wake();
+        }
+    }
+
+    /// Bloque jusqu'à la fin du vol et retourne la valeur chargée, ou `None`
+    /// si le meneur a échoué
+    pub(crate) fn wait(&self) -> Option<V> {
+        let mut guard = self.lock();
+        while let State::Loading = guard.0 {
+            guard = self.ready.wait(guard).unwrap_or_else(PoisonError::into_inner);
+        }
+        match &guard.0 {
+            State::Done(value) => Some(value.clone()),
+            _ => None,
+        }
+    }
+
+    /// Comme [`Flight::wait`], sans bloquer le thread
+    #[cfg(feature = "async")]
+    pub(crate) fn wait_async(&self) -> Wait<'_, V> {
+        Wait(self)
+    }
+}
+
+/// Attente asynchrone de la fin d'un vol
+#[cfg(feature = "async")]
+pub(crate) struct Wait<'a, V>(&'a Flight<V>);
+
+#[cfg(feature = "async")]
+impl<V: Clone> Future for Wait<'_, V> {
+    type Output = Option<V>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<V>> {
+        let mut guard = self.0.lock();
+        match &guard.0 {
+            State::Loading => {
+                if !guard.1.iter().any(|waker| waker.will_wake(cx.waker())) {
+                    guard.1.push(cx.waker().clone());
+                }
+                Poll::Pending
+            }
+            State::Done(value) => Poll::Ready(Some(value.clone())),
+            State::Failed => Poll::Ready(None),
+        }
+    }
+}
+
+/// Vols en cours, par clé
+#[derive(Debug)]
+pub(crate) struct Flights<K, V> {
+    map: Mutex<HashMap<K, Arc<Flight<V>>>>,
+}
+
+impl<K, V> Default for Flights<K, V> {
+    fn default() -> Self {
+        Flights {
+            map: Mutex::new(HashMap::new()),
+        }
+    }
+}
+
+/// Rôle d'un appelant qui manque une clé
+pub(crate) enum Role<'a, K: Eq + Hash, V: Clone> {
+    /// Premier appelant : il doit charger la valeur
+    Leader(Leader<'a, K, V>),
+    /// Un chargement est déjà en cours
+    Waiter(Arc<Flight<V>>),
+}
+
+impl<K: Eq + Hash + Clone, V: Clone> Flights<K, V> {
+    fn lock(&self) -> MutexGuard<'_, HashMap<K, Arc<Flight<V>>>> {
+        self.map.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Rejoint le vol en cours pour `key`, ou en démarre un
+    pub(crate) fn join(&self, key: &K) -> Role<'_, K, V> {
+        let mut map = self.lock();
+        if let Some(flight) = map.get(key) {
+            return Role::Waiter(Arc::clone(flight));
+        }
+        let flight = Arc::new(Flight::new());
+        map.insert(key.clone(), Arc::clone(&flight));
+        Role::Leader(Leader {
+            flights: self,
+            key: Some(key.clone()),
+            flight,
+        })
+    }
+}
+
+/// Meneur d'un vol ; le vol échoue s'il est abandonné sans
+/// [`Leader::finish`], y compris lors d'une panique
+pub(crate) struct Leader<'a, K: Eq + Hash, V: Clone> {
+    flights: &'a Flights<K, V>,
+    key: Option<K>,
+    flight: Arc<Flight<V>>,
+}
+
+impl<K: Eq + Hash, V: Clone> Leader<'_, K, V> {
+    /// Termine le vol en transmettant `value` aux appelants qui l'attendent
+    ///
+    /// La valeur doit déjà être dans le cache : le vol est retiré avant
+    /// d'être terminé, un nouvel appelant trouve donc la clé dans le cache.
+    pub(crate) fn finish(mut self, value: V) {
+        self.land(State::Done(value));
+    }
+
+    fn land(&mut self, state: State<V>) {
+        if let Some(key) = self.key.take() {
+            self.flights.map.lock().unwrap_or_else(PoisonError::into_inner).remove(&key);
+            self.flight.complete(state);
+        }
+    }
+}
+
+impl<K: Eq + Hash, V: Clone> Drop for Leader<'_, K, V> {
+    fn drop(&mut self) {
+        self.land(State::Failed);
+    }
+}
@@ -0,0 +1,115 @@
+//! Vue en lecture seule d'un cache, partageable entre threads
+
+use super::clock::Clock;
+use super::lru::{Cache, Entries, Iter};
+use super::policy::{EvictionPolicy, Lru};
+use super::traits::CacheLen;
+use std::fmt;
+use std::hash::Hash;
+use std::sync::{Arc, Mutex, PoisonError};
+
+/// Cache figé par [`Cache::freeze`] : seules les lectures sans effet sont
+/// possibles, ce que le système de types garantit
+///
+/// Aucune méthode ne modifie l'ordre d'utilisation, les statistiques ou les
+/// entrées : [`FrozenCache::get`] lit comme [`Cache::peek`], et une entrée
+/// expirée est ignorée par les lectures mais pas retirée. `FrozenCache` est
+/// `Sync` dès que les clés et les valeurs le sont, et se partage donc entre
+/// threads derrière un `Arc`. [`FrozenCache::thaw`] rend le cache, avec son
+/// ordre, sa configuration et ses statistiques d'avant le gel.
+///
+/// # Examples
+/// ```
+/// use lru_cache::Cache;
+/// use lru_cache::cache::traits::CacheStorage;
+/// use std::sync::Arc;
+/// use std::thread;
+///
+/// let mut cache = Cache::new(2);
+/// cache.put("A", 1);
+/// cache.put("B", 2);
+///
+/// let frozen = Arc::new(cache.freeze());
+/// let reader = Arc::clone(&frozen);
+/// thread::spawn(move || assert_eq!(reader.get(&"A"), Some(&1))).join().unwrap();
+///
+/// let mut cache = Arc::try_unwrap(frozen).unwrap().thaw();
+/// cache.put("C", 3);
+/// assert_eq!(cache.get(&"A"), None);
+/// ```
+pub struct FrozenCache<K, V, P = Lru> {
+    entries: Entries<K, V>,
+    clock: Arc<dyn Clock>,
+    /// Le reste du cache, qui n'est pas `Sync` (écouteurs, lectures
+    /// partagées) ; le verrou n'est jamais pris avant `thaw`
+    cache: Mutex<Cache<K, V, P>>,
+}
+
+impl<K, V, P> FrozenCache<K, V, P> {
+    pub(super) fn new(entries: Entries<K, V>, clock: Arc<dyn Clock>, cache: Cache<K, V, P>) -> Self {
+        FrozenCache {
+            entries,
+            clock,
+            cache: Mutex::new(cache),
+        }
+    }
+
+    /// Retourne le nombre d'entrées, expirées comprises
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Indique si le cache ne contient aucune entrée
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Parcourt les entrées de la prochaine évincée à la plus récente, comme
+    /// [`Cache::iter`](super::traits::CacheIterate::iter)
+    pub fn iter(&self) -> Iter<'_, K, V> {
+        Iter(self.entries.iter())
+    }
+
+    /// Retourne l'entrée la moins récemment utilisée, en tête de l'ordre
+    /// d'éviction, même si elle est épinglée ou expirée
+    pub fn peek_lru(&self) -> Option<(&K, &V)> {
+        self.iter().next()
+    }
+}
+
+impl<K: Eq + Hash, V, P> FrozenCache<K, V, P> {
+    /// Récupère la valeur associée à `key` sans modifier l'ordre ni les
+    /// statistiques
+    pub fn get(&self, key: &K) -> Option<&V> {
+        let node = self.entries.node(self.entries.find(key)?);
+        if node.meta.is_expired(self.clock.now()) {
+            return None;
+        }
+        node.value.as_ref()
+    }
+
+    /// Indique si la clé est présente et non expirée
+    pub fn contains_key(&self, key: &K) -> bool {
+        self.get(key).is_some()
+    }
+}
+
+impl<K: Eq + Hash, V, P: EvictionPolicy<K>> FrozenCache<K, V, P> {
+    /// Rend le cache modifiable, dans l'état où il a été figé
+    pub fn thaw(self) -> Cache<K, V, P> {
+        let cache = self.cache.into_inner().unwrap_or_else(PoisonError::into_inner);
+        cache.thaw(self.entries)
+    }
+}
+
+impl<K, V, P> CacheLen for FrozenCache<K, V, P> {
+    fn len(&self) -> usize {
+        self.entries.len()
+    }
+}
+
+impl<K: fmt::Debug, V: fmt::Debug, P> fmt::Debug for FrozenCache<K, V, P> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
@@ -0,0 +1,168 @@
+//! Cache unique d'un processus, déclaré dans une `static`
+//!
+//! [`GlobalCache`] se construit en `const` et crée son
+//! [`ConcurrentCache`] au premier appel, ce qui permet de mémoïser une
+//! fonction sans faire passer de poignée de cache à travers le programme.
+
+use super::concurrent::ConcurrentCache;
+use super::stats::CacheStats;
+use std::fmt;
+use std::hash::Hash;
+use std::sync::OnceLock;
+
+/// Construction du cache au premier appel
+enum Init<K, V> {
+    Capacity(usize),
+    With(fn() -> ConcurrentCache<K, V>),
+}
+
+/// [`ConcurrentCache`] partagé par tout le processus, créé au premier appel
+///
+/// Le cache est créé par un [`OnceLock`] : si plusieurs threads l'utilisent
+/// pour la première fois en même temps, un seul le crée et les autres
+/// attendent, puis tous partagent le même cache. La capacité est donc
+/// commune à tous les threads. Les méthodes qui ne sont pas reprises ici
+/// (durées de vie, persistance...) sont accessibles par
+/// [`GlobalCache::inner`].
+///
+/// Entre deux tests qui partagent un cache global, [`GlobalCache::clear`] et
+/// [`GlobalCache::reset_stats`] remettent le cache dans son état initial.
+///
+/// # Examples
+/// ```
+/// use lru_cache::cache::global::GlobalCache;
+///
+/// static SQUARES: GlobalCache<u64, u64> = GlobalCache::new(1_000);
+///
+/// fn square(n: u64) -> u64 {
+///     SQUARES.get_or_insert_with(n, || n * n)
+/// }
+///
+/// assert_eq!(square(12), 144);
+/// assert_eq!(SQUARES.get(&12), Some(144));
+/// assert_eq!(SQUARES.stats().hits, 1);
+/// ```
+pub struct GlobalCache<K, V> {
+    init: Init<K, V>,
+    cache: OnceLock<ConcurrentCache<K, V>>,
+}
+
+impl<K, V> GlobalCache<K, V> {
+    /// Déclare un cache de capacité `capacity`, créé au premier appel
+    ///
+    /// # Panics
+    ///
+    /// Panique si `capacity` vaut 0 ; dans l'initialisation d'une
+    /// `static`, l'erreur est signalée à la compilation.
+    pub const fn new(capacity: usize) -> Self {
+        assert!(capacity > 0, "la capacité d'un cache doit être au moins 1");
+        GlobalCache {
+            init: Init::Capacity(capacity),
+            cache: OnceLock::new(),
+        }
+    }
+
+    /// Déclare un cache créé au premier appel par `init`, pour choisir sa
+    /// configuration (durée de vie, pondération...)
+    ///
+    /// # Examples
+    /// ```
+    /// use lru_cache::Cache;
+    /// use lru_cache::cache::concurrent::ConcurrentCache;
+    /// use lru_cache::cache::global::GlobalCache;
+    /// use lru_cache::cache::ttl::TtlMode;
+    /// use std::time::Duration;
+    ///
+    /// static SESSIONS: GlobalCache<String, u32> = GlobalCache::with_init(|| {
+    ///     ConcurrentCache::from_cache(Cache::with_ttl(100, Duration::from_secs(60), TtlMode::AfterWrite))
+    /// });
+    ///
+    /// SESSIONS.put(String::from("alice"), 7);
+    /// assert_eq!(SESSIONS.capacity(), 100);
+    /// ```
+    pub const fn with_init(init: fn() -> ConcurrentCache<K, V>) -> Self {
+        GlobalCache {
+            init: Init::With(init),
+            cache: OnceLock::new(),
+        }
+    }
+
+    /// Indique si le cache a déjà été créé
+    pub fn is_initialized(&self) -> bool {
+        self.cache.get().is_some()
+    }
+}
+
+impl<K: Eq + Hash, V> GlobalCache<K, V> {
+    /// Retourne le cache, en le créant au premier appel
+    pub fn inner(&self) -> &ConcurrentCache<K, V> {
+        self.cache.get_or_init(|| match self.init {
+            Init::Capacity(capacity) => ConcurrentCache::new(capacity),
+            Init::With(init) => init(),
+        })
+    }
+
+    /// Récupère une copie de la valeur et marque l'entrée comme utilisée
+    pub fn get(&self, key: &K) -> Option<V>
+    where
+        V: Clone,
+    {
+        self.inner().get(key)
+    }
+
+    /// Insère une valeur dans le cache
+    pub fn put(&self, key: K, value: V) {
+        self.inner().put(key, value);
+    }
+
+    /// Supprime une entrée et retourne sa valeur
+    pub fn remove(&self, key: &K) -> Option<V> {
+        self.inner().remove(key)
+    }
+
+    /// Récupère une copie de la valeur associée à `key`, ou la charge avec
+    /// `load` (voir [`ConcurrentCache::get_or_insert_with`])
+    pub fn get_or_insert_with(&self, key: K, load: impl FnOnce() -> V) -> V
+    where
+        K: Clone,
+        V: Clone,
+    {
+        self.inner().get_or_insert_with(key, load)
+    }
+
+    /// Retourne le nombre d'entrées, expirées ou non
+    pub fn len(&self) -> usize {
+        self.inner().len()
+    }
+
+    /// Indique si le cache est vide
+    pub fn is_empty(&self) -> bool {
+        self.inner().is_empty()
+    }
+
+    /// Retourne la capacité du cache
+    pub fn capacity(&self) -> usize {
+        self.inner().lock().capacity()
+    }
+
+    /// Supprime toutes les entrées ; les statistiques sont conservées
+    pub fn clear(&self) {
+        self.inner().lock().clear();
+    }
+
+    /// Retourne les compteurs d'activité du cache
+    pub fn stats(&self) -> CacheStats {
+        self.inner().lock().stats()
+    }
+
+    /// Remet les compteurs d'activité à zéro
+    pub fn reset_stats(&self) {
+        self.inner().lock().reset_stats();
+    }
+}
+
+impl<K: fmt::Debug, V: fmt::Debug> fmt::Debug for GlobalCache<K, V> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("GlobalCache").field("cache", &self.cache.get()).finish()
+    }
+}
@@ -0,0 +1,99 @@
+//! Emplacements rangés par nombre d'accès croissant
+//!
+//! Les emplacements d'un même nombre d'accès forment un segment contigu de
+//! la liste, du premier arrivé à ce nombre au dernier. La fin de chaque
+//! segment est gardée par nombre d'accès : changer le compteur d'un
+//! emplacement le déplace en O(log m), m étant le nombre de compteurs
+//! distincts, et le moins utilisé est en tête de liste.
+
+use alloc::collections::BTreeMap;
+use alloc::vec::Vec;
+
+/// Compteur et voisins d'un emplacement rangé
+#[derive(Debug, Clone, Copy)]
+struct Link {
+    hits: u64,
+    prev: Option<usize>,
+    next: Option<usize>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub(crate) struct HitOrder {
+    links: Vec<Option<Link>>,
+    head: Option<usize>,
+    /// Dernier emplacement de chaque nombre d'accès
+    tails: BTreeMap<u64, usize>,
+}
+
+impl HitOrder {
+    pub(crate) fn new() -> Self {
+        HitOrder::default()
+    }
+
+    /// Nombre d'accès de l'emplacement `index`, 0 s'il n'est pas rangé
+    pub(crate) fn hits(&self, index: usize) -> u64 {
+        self.link(index).map_or(0, |link| link.hits)
+    }
+
+    /// Range l'emplacement `index` avec `hits` accès, en dernier de ce
+    /// nombre, ou le déplace
+    pub(crate) fn set(&mut self, index: usize, hits: u64) {
+        self.remove(index);
+        let prev = self.tails.range(..=hits).next_back().map(|(_, &tail)| tail);
+        let next = match prev {
+            Some(prev) => self.link(prev).and_then(|link| link.next),
+            None => self.head,
+        };
+        match prev {
+            Some(prev) => self.link_mut(prev).next = Some(index),
+            None => self.head = Some(index),
+        }
+        if let Some(next) = next {
+            self.link_mut(next).prev = Some(index);
+        }
+        if self.links.len() <= index {
+            self.links.resize(index + 1, None);
+        }
+        self.links[index] = Some(Link { hits, prev, next });
+        self.tails.insert(hits, index);
+    }
+
+    /// Compte un accès de l'emplacement `index`
+    pub(crate) fn increment(&mut self, index: usize) {
+        let hits = self.hits(index);
+        self.set(index, hits.saturating_add(1));
+    }
+
+    /// Retire l'emplacement `index`, s'il est rangé
+    pub(crate) fn remove(&mut self, index: usize) {
+        let Some(Link { hits, prev, next }) = self.links.get_mut(index).and_then(Option::take) else {
+            return;
+        };
+        match prev {
+            Some(prev) => self.link_mut(prev).next = next,
+            None => self.head = next,
+        }
+        if let Some(next) = next {
+            self.link_mut(next).prev = prev;
+        }
+        if self.tails.get(&hits) == Some(&index) {
+            match prev.filter(|&prev| self.hits(prev) == hits) {
+                Some(prev) => self.tails.insert(hits, prev),
+                None => self.tails.remove(&hits),
+            };
+        }
+    }
+
+    /// Emplacements du moins utilisé au plus utilisé
+    pub(crate) fn iter(&self) -> impl Iterator<Item = usize> + '_ {
+        core::iter::successors(self.head, |&index| self.link(index).and_then(|link| link.next))
+    }
+
+    fn link(&self, index: usize) -> Option<&Link> {
+        self.links.get(index).and_then(Option::as_ref)
+    }
+
+    fn link_mut(&mut self, index: usize) -> &mut Link {
+        self.links[index].as_mut().expect("un voisin rangé")
+    }
+}
@@ -0,0 +1,384 @@
+use super::clock::{Clock, Instant, SystemClock};
+use super::error::CacheError;
+use super::list::NodeList;
+use super::traits::{CacheIterate, CacheLen, CacheRemove, CacheStorage, GetOptions, PersistentStorage};
+use std::collections::{btree_map, BTreeMap};
+use std::fmt::{self, Display};
+use std::hash::Hash;
+use std::marker::PhantomData;
+use std::num::{NonZeroU64, NonZeroUsize};
+use std::path::Path;
+use std::str::FromStr;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Compteur d'utilisation d'une entrée et tick de son dernier accès, qui
+/// forment sa place dans l'ordre d'éviction
+#[derive(Debug)]
+struct Usage {
+    count: u64,
+    tick: u64,
+}
+
+/// Cache LFU (Least Frequently Used) qui évince l'entrée la moins utilisée
+///
+/// Chaque entrée possède un compteur d'utilisation, incrémenté par `get` et
+/// par un `put` sur une clé existante. Lorsque le cache est plein, l'entrée
+/// au compteur le plus faible est évincée ; entre entrées de même compteur,
+/// la moins récemment utilisée part en premier.
+///
+/// Sans vieillissement, une clé très demandée par le passé garde son
+/// compteur pour toujours et n'est jamais évincée. [`LfuCache::builder`]
+/// permet de diviser tous les compteurs par deux à intervalle régulier (voir
+/// [`LfuBuilder::decay_every_ops`] et [`LfuBuilder::decay_interval`]) : une
+/// clé délaissée perd sa fréquence et finit par être évincée.
+///
+/// # Examples
+/// ```
+/// use lru_cache::cache::lfu::LfuCache;
+/// use lru_cache::cache::traits::CacheStorage;
+///
+/// let mut cache = LfuCache::new(2);
+/// cache.put("hot", 1);
+/// cache.get(&"hot");
+/// cache.put("cold", 2);
+/// cache.put("new", 3);
+/// assert_eq!(cache.get(&"cold"), None);
+/// assert_eq!(cache.get(&"hot"), Some(&1));
+/// ```
+#[derive(Debug)]
+pub struct LfuCache<K, V> {
+    capacity: usize,
+    entries: NodeList<K, V, Usage>,
+    /// Emplacements de `entries` rangés par compteur puis par tick : chaque
+    /// clé n'est stockée qu'une fois, dans `entries`
+    order: BTreeMap<(u64, u64), usize>,
+    tick: u64,
+    decay: Option<Decay>,
+}
+
+/// Vieillissement des compteurs : moitié tous les `ops` appels ou toutes les
+/// `interval` écoulées
+#[derive(Debug)]
+struct Decay {
+    ops: Option<NonZeroU64>,
+    interval: Option<Duration>,
+    clock: Arc<dyn Clock>,
+    /// Appels depuis la dernière division
+    pending_ops: u64,
+    /// Début de la période en cours
+    since: Instant,
+}
+
+impl<K: Eq + Hash, V> LfuCache<K, V> {
+    /// Crée un nouveau cache avec la capacité spécifiée
+    ///
+    /// Un cache de capacité 0 ne garde aucune entrée.
+    pub fn new(capacity: usize) -> Self {
+        LfuCache {
+            capacity,
+            entries: NodeList::new(),
+            order: BTreeMap::new(),
+            tick: 0,
+            decay: None,
+        }
+    }
+
+    /// Configure un cache LFU dont les compteurs vieillissent
+    ///
+    /// # Examples
+    /// ```
+    /// use lru_cache::cache::lfu::LfuCache;
+    /// use lru_cache::cache::traits::CacheStorage;
+    /// use std::num::{NonZeroU64, NonZeroUsize};
+    ///
+    /// let mut cache = LfuCache::builder()
+    ///     .capacity(NonZeroUsize::new(2).unwrap())
+    ///     .decay_every_ops(NonZeroU64::new(4).unwrap())
+    ///     .build()
+    ///     .unwrap();
+    /// cache.put("key", 1);
+    /// cache.get(&"key");
+    /// cache.get(&"key");
+    /// assert_eq!(cache.frequency(&"key"), Some(3));
+    /// cache.get(&"key");
+    /// assert_eq!(cache.frequency(&"key"), Some(2));
+    /// ```
+    pub fn builder() -> LfuBuilder<K, V> {
+        LfuBuilder::new()
+    }
+
+    /// Retourne la capacité du cache
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Retourne le compteur d'utilisation d'une clé, après les divisions
+    /// par deux déjà appliquées
+    ///
+    /// Le vieillissement n'est appliqué que par `get` et `put` : le compteur
+    /// retourné ne tient pas compte du temps écoulé depuis le dernier appel.
+    pub fn frequency(&self, key: &K) -> Option<u64> {
+        self.entries.find(key).map(|index| self.entries.node(index).meta.count)
+    }
+
+    fn next_tick(&mut self) -> u64 {
+        self.tick += 1;
+        self.tick
+    }
+
+    /// Compte un appel et divise les compteurs par deux pour chaque période
+    /// révolue
+    fn age(&mut self) {
+        let Some(decay) = &mut self.decay else {
+            return;
+        };
+        let mut halvings = 0u32;
+        if let Some(ops) = decay.ops {
+            decay.pending_ops += 1;
+            if decay.pending_ops >= ops.get() {
+                decay.pending_ops = 0;
+                halvings += 1;
+            }
+        }
+        if let Some(interval) = decay.interval {
+            let now = decay.clock.now();
+            // Une horloge qui recule ne fait que repousser la période suivante
+            let elapsed = now.checked_duration_since(decay.since).unwrap_or_default();
+            let periods = elapsed.as_nanos() / interval.as_nanos();
+            if periods > 0 {
+                let periods = u32::try_from(periods).unwrap_or(u32::MAX);
+                halvings = halvings.saturating_add(periods);
+                // La fraction de période déjà écoulée compte pour la suivante
+                decay.since = interval
+                    .checked_mul(periods)
+                    .and_then(|elapsed| decay.since.checked_add(elapsed))
+                    .unwrap_or(now);
+            }
+        }
+        if halvings > 0 {
+            self.halve(halvings);
+        }
+    }
+
+    /// Divise chaque compteur par `2^halvings` et reconstruit l'ordre
+    /// d'éviction
+    fn halve(&mut self, halvings: u32) {
+        let order = std::mem::take(&mut self.order);
+        for index in order.into_values() {
+            let usage = &mut self.entries.node_mut(index).meta;
+            usage.count = usage.count.checked_shr(halvings).unwrap_or(0);
+            self.order.insert((usage.count, usage.tick), index);
+        }
+    }
+
+    fn bump(&mut self, key: &K) -> Option<&mut V> {
+        let tick = self.next_tick();
+        let index = self.entries.find(key)?;
+        let node = self.entries.node_mut(index);
+        self.order.remove(&(node.meta.count, node.meta.tick));
+        node.meta.count = node.meta.count.saturating_add(1);
+        node.meta.tick = tick;
+        self.order.insert((node.meta.count, node.meta.tick), index);
+        Some(&mut node.value)
+    }
+}
+
+impl<K: Eq + Hash, V> CacheStorage<K, V> for LfuCache<K, V> {
+    fn get(&mut self, key: &K) -> Option<&V> {
+        self.age();
+        self.bump(key).map(|value| &*value)
+    }
+
+    /// Le compteur d'utilisation sert à la fois de statistique et d'ordre
+    /// d'éviction : il n'est incrémenté que si `promote` est demandé.
+    fn get_with(&mut self, key: &K, options: GetOptions) -> Option<&V> {
+        if options.promote {
+            return self.get(key);
+        }
+        self.entries.find(key).map(|index| &self.entries.node(index).value)
+    }
+
+    fn put(&mut self, key: K, value: V) {
+        self.age();
+        if let Some(slot) = self.bump(&key) {
+            *slot = value;
+            return;
+        }
+        if self.capacity == 0 {
+            return;
+        }
+        if self.entries.len() >= self.capacity {
+            if let Some((_, victim)) = self.order.pop_first() {
+                self.entries.remove(victim);
+            }
+        }
+        let tick = self.next_tick();
+        let index = self.entries.push(key, value, Usage { count: 1, tick });
+        self.order.insert((1, tick), index);
+    }
+}
+
+impl<K: Eq + Hash, V> CacheRemove<K, V> for LfuCache<K, V> {
+    fn remove(&mut self, key: &K) -> Option<V> {
+        let index = self.entries.find(key)?;
+        let node = self.entries.remove(index);
+        self.order.remove(&(node.meta.count, node.meta.tick));
+        Some(node.value)
+    }
+}
+
+impl<K, V> CacheLen for LfuCache<K, V> {
+    fn len(&self) -> usize {
+        self.entries.len()
+    }
+}
+
+/// Itérateur sur les entrées d'un [`LfuCache`], de la prochaine évincée à la
+/// plus utilisée
+pub struct Iter<'a, K, V> {
+    order: btree_map::Values<'a, (u64, u64), usize>,
+    entries: &'a NodeList<K, V, Usage>,
+}
+
+impl<'a, K, V> Iterator for Iter<'a, K, V> {
+    type Item = (&'a K, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let node = self.entries.node(*self.order.next()?);
+        Some((&node.key, &node.value))
+    }
+}
+
+impl<K, V> CacheIterate<K, V> for LfuCache<K, V> {
+    type Iter<'a> = Iter<'a, K, V> where Self: 'a, K: 'a, V: 'a;
+
+    fn iter(&self) -> Self::Iter<'_> {
+        Iter {
+            order: self.order.values(),
+            entries: &self.entries,
+        }
+    }
+}
+
+/// Les compteurs ne sont pas persistés : les entrées sont sauvegardées dans
+/// l'ordre d'éviction et repartent d'un compteur à 1 au chargement, en gardant
+/// cet ordre.
+impl<K: Eq + Hash + Display + FromStr, V: Display + FromStr> PersistentStorage<K, V> for LfuCache<K, V> {
+    fn save_to_file(&self, path: impl AsRef<Path>) -> std::io::Result<()> {
+        let data: Vec<_> = self.iter().collect();
+        crate::storage::file::FileStorage::save(path, self.capacity, &data)
+    }
+
+    fn load_from_file(path: impl AsRef<Path>, capacity: NonZeroUsize) -> std::io::Result<Self> {
+        let (_, data) = crate::storage::file::FileStorage::load(path)?;
+        let mut cache = LfuCache::new(capacity.get());
+        for (key, value) in data {
+            cache.put(key, value);
+        }
+        Ok(cache)
+    }
+}
+
+/// Configure un [`LfuCache`] et le vieillissement de ses compteurs, puis le
+/// crée avec [`LfuBuilder::build`]
+///
+/// Les deux déclencheurs de vieillissement peuvent être combinés ; sans
+/// aucun des deux, le cache se comporte comme [`LfuCache::new`]. Une entrée
+/// dont le compteur tombe à 0 passe avant toute entrée nouvellement insérée
+/// dans l'ordre d'éviction.
+pub struct LfuBuilder<K, V> {
+    capacity: Option<NonZeroUsize>,
+    ops: Option<NonZeroU64>,
+    interval: Option<Duration>,
+    clock: Option<Arc<dyn Clock>>,
+    marker: PhantomData<fn(K, V)>,
+}
+
+impl<K, V> LfuBuilder<K, V> {
+    /// Crée une configuration sans capacité ni vieillissement
+    pub fn new() -> Self {
+        LfuBuilder {
+            capacity: None,
+            ops: None,
+            interval: None,
+            clock: None,
+            marker: PhantomData,
+        }
+    }
+
+    /// Fixe le nombre maximal d'entrées
+    pub fn capacity(mut self, capacity: NonZeroUsize) -> Self {
+        self.capacity = Some(capacity);
+        self
+    }
+
+    /// Divise tous les compteurs par deux tous les `ops` appels à `get` ou
+    /// `put`, succès ou non
+    pub fn decay_every_ops(mut self, ops: NonZeroU64) -> Self {
+        self.ops = Some(ops);
+        self
+    }
+
+    /// Divise tous les compteurs par deux pour chaque `interval` écoulé
+    ///
+    /// Le temps est lu au début de chaque `get` ou `put` : après une longue
+    /// inactivité, les compteurs sont divisés autant de fois que de périodes
+    /// révolues.
+    pub fn decay_interval(mut self, interval: Duration) -> Self {
+        self.interval = Some(interval);
+        self
+    }
+
+    /// Mesure les périodes de [`LfuBuilder::decay_interval`] avec `clock`
+    /// plutôt qu'avec l'horloge du système
+    pub fn clock<C: Clock + 'static>(mut self, clock: C) -> Self {
+        self.clock = Some(Arc::new(clock));
+        self
+    }
+}
+
+impl<K: Eq + Hash, V> LfuBuilder<K, V> {
+    /// Crée le cache configuré
+    ///
+    /// # Errors
+    ///
+    /// Retourne [`CacheError::ZeroCapacity`] si la capacité n'a pas été
+    /// fixée et [`CacheError::ZeroDecayPeriod`] pour un
+    /// [`LfuBuilder::decay_interval`] nul.
+    pub fn build(self) -> Result<LfuCache<K, V>, CacheError> {
+        let capacity = self.capacity.ok_or(CacheError::ZeroCapacity)?;
+        if self.interval.is_some_and(|interval| interval.is_zero()) {
+            return Err(CacheError::ZeroDecayPeriod);
+        }
+        let mut cache = LfuCache::new(capacity.get());
+        if self.ops.is_some() || self.interval.is_some() {
+            let clock = self.clock.unwrap_or_else(|| Arc::new(SystemClock));
+            cache.decay = Some(Decay {
+                ops: self.ops,
+                interval: self.interval,
+                since: clock.now(),
+                clock,
+                pending_ops: 0,
+            });
+        }
+        Ok(cache)
+    }
+}
+
+impl<K, V> Default for LfuBuilder<K, V> {
+    fn default() -> Self {
+        LfuBuilder::new()
+    }
+}
+
+impl<K, V> fmt::Debug for LfuBuilder<K, V> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("LfuBuilder")
+            .field("capacity", &self.capacity)
+            .field("ops", &self.ops)
+            .field("interval", &self.interval)
+            .field("clock", &self.clock)
+            .finish()
+    }
+}
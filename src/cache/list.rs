@@ -0,0 +1,622 @@
+//! Liste ordonnée indexée par clé, partagée par les caches
+//!
+//! Les nœuds vivent dans un slab et sont chaînés dans l'ordre d'éviction :
+//! la tête est la prochaine entrée évincée, la queue la dernière insérée ou
+//! promue. La table d'index associe le hash d'une clé au premier nœud d'une
+//! chaîne de collisions, la clé n'est donc stockée qu'une fois. Chaque nœud
+//! porte des métadonnées `M` propres au cache qui l'utilise.
+//!
+//! Sans la feature `std`, la table d'index est un `BTreeMap` (les clés de la
+//! table sont des hash, `K` n'a donc pas besoin d'être `Ord`) et les clés
+//! sont hachées par SipHash à clé fixe, faute de source d'aléa.
+
+use super::error::InvariantError;
+use alloc::vec::Vec;
+use core::hash::{BuildHasher, Hash};
+#[cfg(not(feature = "std"))]
+use alloc::collections::BTreeMap as HashMap;
+#[cfg(feature = "std")]
+use std::collections::hash_map::RandomState;
+#[cfg(feature = "std")]
+use std::collections::HashMap;
+
+#[cfg(not(feature = "std"))]
+#[allow(deprecated)]
+type RandomState = core::hash::BuildHasherDefault<core::hash::SipHasher>;
+
+#[derive(Debug)]
+pub(crate) struct Node<K, V, M> {
+    pub(crate) key: K,
+    pub(crate) value: V,
+    pub(crate) meta: M,
+    hash: u64,
+    /// `hash` a été donné par l'appelant de `push_hashed` et peut différer
+    /// de `hash_of(&key)`
+    foreign: bool,
+    prev: Option<usize>,
+    next: Option<usize>,
+    bucket_next: Option<usize>,
+}
+
+#[derive(Debug)]
+struct Slot<K, V, M> {
+    generation: u64,
+    node: Option<Node<K, V, M>>,
+}
+
+#[derive(Debug)]
+pub(crate) struct NodeList<K, V, M> {
+    map: HashMap<u64, usize>,
+    hasher: RandomState,
+    slots: Vec<Slot<K, V, M>>,
+    free: Vec<usize>,
+    head: Option<usize>,
+    tail: Option<usize>,
+    generation: u64,
+    /// Nombre de nœuds `foreign`
+    foreign: usize,
+}
+
+impl<K, V, M> NodeList<K, V, M> {
+    pub(crate) fn new() -> Self {
+        NodeList {
+            map: HashMap::new(),
+            hasher: RandomState::default(),
+            slots: Vec::new(),
+            free: Vec::new(),
+            head: None,
+            tail: None,
+            generation: 0,
+            foreign: 0,
+        }
+    }
+
+    pub(crate) fn len(&self) -> usize {
+        self.slots.len() - self.free.len()
+    }
+
+    /// Nœuds dans l'ordre de leurs emplacements, qui n'est pas l'ordre
+    /// d'éviction
+    pub(crate) fn nodes(&self) -> impl Iterator<Item = &Node<K, V, M>> {
+        self.slots.iter().filter_map(|slot| slot.node.as_ref())
+    }
+
+    /// Remplace chaque valeur par `f(&key, value)` en gardant les
+    /// emplacements, l'ordre d'éviction et la table d'index
+    ///
+    /// `f` est appelée dans l'ordre des emplacements, comme par `nodes`.
+    pub(crate) fn map_values<W>(self, mut f: impl FnMut(&K, V) -> W) -> NodeList<K, W, M> {
+        let slots = self
+            .slots
+            .into_iter()
+            .map(|slot| Slot {
+                generation: slot.generation,
+                node: slot.node.map(|node| Node {
+                    value: f(&node.key, node.value),
+                    key: node.key,
+                    meta: node.meta,
+                    hash: node.hash,
+                    foreign: node.foreign,
+                    prev: node.prev,
+                    next: node.next,
+                    bucket_next: node.bucket_next,
+                }),
+            })
+            .collect();
+        NodeList {
+            map: self.map,
+            hasher: self.hasher,
+            slots,
+            free: self.free,
+            head: self.head,
+            tail: self.tail,
+            generation: self.generation,
+            foreign: self.foreign,
+        }
+    }
+
+    pub(crate) fn head(&self) -> Option<usize> {
+        self.head
+    }
+
+    pub(crate) fn tail(&self) -> Option<usize> {
+        self.tail
+    }
+
+    pub(crate) fn next(&self, index: usize) -> Option<usize> {
+        self.node(index).next
+    }
+
+    pub(crate) fn prev(&self, index: usize) -> Option<usize> {
+        self.node(index).prev
+    }
+
+    pub(crate) fn node(&self, index: usize) -> &Node<K, V, M> {
+        self.slots[index].node.as_ref().expect("emplacement vide dans la liste")
+    }
+
+    pub(crate) fn node_mut(&mut self, index: usize) -> &mut Node<K, V, M> {
+        self.slots[index].node.as_mut().expect("emplacement vide dans la liste")
+    }
+
+    /// Génération courante de l'emplacement `index`
+    pub(crate) fn generation(&self, index: usize) -> u64 {
+        self.slots[index].generation
+    }
+
+    /// Indique si l'emplacement `index` est occupé avec la génération donnée
+    pub(crate) fn is_live(&self, index: usize, generation: u64) -> bool {
+        self.slots
+            .get(index)
+            .is_some_and(|slot| slot.generation == generation && slot.node.is_some())
+    }
+
+    /// Indique si l'emplacement `index` est occupé
+    pub(crate) fn is_occupied(&self, index: usize) -> bool {
+        self.slots.get(index).is_some_and(|slot| slot.node.is_some())
+    }
+
+    /// Attribue une nouvelle génération à l'emplacement `index`
+    pub(crate) fn renew(&mut self, index: usize) {
+        self.slots[index].generation = self.next_generation();
+    }
+
+    pub(crate) fn reserve(&mut self, additional: usize) {
+        #[cfg(feature = "std")]
+        self.map.reserve(additional);
+        self.slots.reserve(additional.saturating_sub(self.free.len()));
+    }
+
+    /// Compacte les emplacements libres puis libère la mémoire inutilisée ;
+    /// les nœuds déplacés reçoivent une nouvelle génération
+    pub(crate) fn shrink_to_fit(&mut self) {
+        self.compact();
+        #[cfg(feature = "std")]
+        self.map.shrink_to_fit();
+        self.slots.shrink_to_fit();
+        self.free.shrink_to_fit();
+    }
+
+    pub(crate) fn allocated_capacity(&self) -> usize {
+        #[cfg(feature = "std")]
+        let map = self.map.capacity();
+        #[cfg(not(feature = "std"))]
+        let map = 0;
+        map.max(self.slots.capacity())
+    }
+
+    pub(crate) fn move_to_back(&mut self, index: usize) {
+        if self.tail != Some(index) {
+            self.unlink(index);
+            self.push_back(index);
+        }
+    }
+
+    /// Replace `node`, retiré de l'emplacement `index` par `remove`, dans cet
+    /// emplacement avec la génération `generation`, juste après `prev` dans
+    /// l'ordre d'éviction (en tête si `None`)
+    pub(crate) fn restore(&mut self, index: usize, generation: u64, prev: Option<usize>, mut node: Node<K, V, M>) {
+        if let Some(position) = self.free.iter().rposition(|&free| free == index) {
+            self.free.remove(position);
+        }
+        node.bucket_next = self.map.get(&node.hash).copied();
+        self.map.insert(node.hash, index);
+        self.foreign += usize::from(node.foreign);
+        self.slots[index] = Slot {
+            generation,
+            node: Some(node),
+        };
+        self.link_after(index, prev);
+    }
+
+    /// Déplace le nœud `index` juste après `prev` dans l'ordre d'éviction
+    /// (en tête si `None`)
+    pub(crate) fn move_after(&mut self, index: usize, prev: Option<usize>) {
+        if prev != Some(index) {
+            self.unlink(index);
+            self.link_after(index, prev);
+        }
+    }
+
+    /// Rend à l'emplacement `index` une génération qu'il a déjà eue
+    pub(crate) fn set_generation(&mut self, index: usize, generation: u64) {
+        self.slots[index].generation = generation;
+    }
+
+    pub(crate) fn remove(&mut self, index: usize) -> Node<K, V, M> {
+        self.unlink(index);
+        self.unlink_bucket(index);
+        self.free.push(index);
+        let node = self.slots[index].node.take().expect("emplacement vide dans la liste");
+        self.foreign -= usize::from(node.foreign);
+        node
+    }
+
+    pub(crate) fn iter(&self) -> Iter<'_, K, V, M> {
+        Iter {
+            slots: &self.slots,
+            current: self.head,
+        }
+    }
+
+    /// Parcours de la tête vers la queue, avec un accès mutable aux valeurs
+    ///
+    /// Chaque emplacement reçoit d'abord son rang dans la liste ; les
+    /// références mutables sont ensuite prises en une seule passe sur le
+    /// slab et rangées à leur rang, ce qui évite d'emprunter deux fois un
+    /// même nœud en suivant le chaînage.
+    pub(crate) fn iter_mut(&mut self) -> impl Iterator<Item = (&K, &mut V)> + '_ {
+        let mut ranks = Vec::new();
+        ranks.resize(self.slots.len(), usize::MAX);
+        let mut current = self.head;
+        let mut len = 0;
+        while let Some(index) = current {
+            ranks[index] = len;
+            len += 1;
+            current = self.node(index).next;
+        }
+        let mut ordered: Vec<Option<(&K, &mut V)>> = Vec::with_capacity(len);
+        ordered.resize_with(len, || None);
+        for (slot, rank) in self.slots.iter_mut().zip(ranks) {
+            if let Some(node) = slot.node.as_mut() {
+                ordered[rank] = Some((&node.key, &mut node.value));
+            }
+        }
+        ordered.into_iter().flatten()
+    }
+
+    /// Parcours, dans les deux sens, des clés des nœuds pour lesquels `keep`
+    /// est vrai
+    pub(crate) fn keys_where(&self, keep: fn(&M) -> bool) -> Keys<'_, K, V, M> {
+        Keys {
+            slots: &self.slots,
+            front: self.head,
+            back: self.tail,
+            keep,
+        }
+    }
+
+    fn next_generation(&mut self) -> u64 {
+        self.generation += 1;
+        self.generation
+    }
+
+    fn compact(&mut self) {
+        loop {
+            while matches!(self.slots.last(), Some(slot) if slot.node.is_none()) {
+                self.slots.pop();
+            }
+            let len = self.slots.len();
+            self.free.retain(|&index| index < len);
+            let Some(hole) = self.free.pop() else {
+                return;
+            };
+            self.relocate(len - 1, hole);
+        }
+    }
+
+    fn relocate(&mut self, from: usize, to: usize) {
+        let (hash, prev, next) = {
+            let node = self.node(from);
+            (node.hash, node.prev, node.next)
+        };
+        match prev {
+            Some(prev) => self.node_mut(prev).next = Some(to),
+            None => self.head = Some(to),
+        }
+        match next {
+            Some(next) => self.node_mut(next).prev = Some(to),
+            None => self.tail = Some(to),
+        }
+        if self.map.get(&hash) == Some(&from) {
+            self.map.insert(hash, to);
+        } else {
+            let mut current = self.map.get(&hash).copied();
+            while let Some(previous) = current {
+                let node = self.node_mut(previous);
+                if node.bucket_next == Some(from) {
+                    node.bucket_next = Some(to);
+                    break;
+                }
+                current = node.bucket_next;
+            }
+        }
+        let generation = self.next_generation();
+        let node = self.slots[from].node.take();
+        self.slots[to] = Slot { generation, node };
+    }
+
+    fn unlink(&mut self, index: usize) {
+        let (prev, next) = {
+            let node = self.node(index);
+            (node.prev, node.next)
+        };
+        match prev {
+            Some(prev) => self.node_mut(prev).next = next,
+            None => self.head = next,
+        }
+        match next {
+            Some(next) => self.node_mut(next).prev = prev,
+            None => self.tail = prev,
+        }
+    }
+
+    fn push_back(&mut self, index: usize) {
+        let tail = self.tail;
+        {
+            let node = self.node_mut(index);
+            node.prev = tail;
+            node.next = None;
+        }
+        match tail {
+            Some(tail) => self.node_mut(tail).next = Some(index),
+            None => self.head = Some(index),
+        }
+        self.tail = Some(index);
+    }
+
+    fn link_after(&mut self, index: usize, prev: Option<usize>) {
+        let next = match prev {
+            Some(prev) => self.node(prev).next,
+            None => self.head,
+        };
+        {
+            let node = self.node_mut(index);
+            node.prev = prev;
+            node.next = next;
+        }
+        match prev {
+            Some(prev) => self.node_mut(prev).next = Some(index),
+            None => self.head = Some(index),
+        }
+        match next {
+            Some(next) => self.node_mut(next).prev = Some(index),
+            None => self.tail = Some(index),
+        }
+    }
+
+    fn unlink_bucket(&mut self, index: usize) {
+        let (hash, bucket_next) = {
+            let node = self.node(index);
+            (node.hash, node.bucket_next)
+        };
+        if self.map.get(&hash) == Some(&index) {
+            match bucket_next {
+                Some(next) => self.map.insert(hash, next),
+                None => self.map.remove(&hash),
+            };
+            return;
+        }
+        let mut current = self.map.get(&hash).copied();
+        while let Some(previous) = current {
+            let node = self.node_mut(previous);
+            if node.bucket_next == Some(index) {
+                node.bucket_next = bucket_next;
+                return;
+            }
+            current = node.bucket_next;
+        }
+    }
+
+    /// Cherche dans la chaîne de `hash` le nœud dont la clé vérifie `eq`,
+    /// sans hacher aucune clé
+    pub(crate) fn find_hashed(&self, hash: u64, eq: impl Fn(&K) -> bool) -> Option<usize> {
+        let mut current = self.map.get(&hash).copied();
+        while let Some(index) = current {
+            let node = self.node(index);
+            if eq(&node.key) {
+                return Some(index);
+            }
+            current = node.bucket_next;
+        }
+        None
+    }
+
+    /// Comme `find_hashed`, en parcourant aussi les nœuds indexés par `push`
+    /// si la chaîne de `hash` ne contient pas la clé
+    ///
+    /// Une clé ajoutée par `push` n'est indexée sous `hash` que si c'est son
+    /// hash : ce parcours, sans hachage, évite de l'ajouter une seconde fois
+    /// sous un autre hash.
+    pub(crate) fn find_raw(&self, hash: u64, eq: impl Fn(&K) -> bool) -> Option<usize> {
+        self.find_hashed(hash, &eq).or_else(|| self.scan(false, eq))
+    }
+
+    /// Premier nœud dont l'indicateur `foreign` vaut `foreign` et dont la
+    /// clé vérifie `eq`, s'il existe de tels nœuds
+    fn scan(&self, foreign: bool, eq: impl Fn(&K) -> bool) -> Option<usize> {
+        let count = if foreign { self.foreign } else { self.len() - self.foreign };
+        if count == 0 {
+            return None;
+        }
+        self.slots
+            .iter()
+            .position(|slot| slot.node.as_ref().is_some_and(|node| node.foreign == foreign && eq(&node.key)))
+    }
+
+    /// Ajoute un nœud indexé sous `hash`, donné par l'appelant, en queue de
+    /// liste et retourne son emplacement
+    ///
+    /// Le nœud reste trouvé par `find` même si `hash` n'est pas celui de sa
+    /// clé, au prix d'un parcours des nœuds ajoutés ainsi.
+    pub(crate) fn push_hashed(&mut self, hash: u64, key: K, value: V, meta: M) -> usize {
+        self.foreign += 1;
+        self.link_new(hash, true, key, value, meta)
+    }
+
+    fn link_new(&mut self, hash: u64, foreign: bool, key: K, value: V, meta: M) -> usize {
+        let node = Node {
+            key,
+            value,
+            meta,
+            hash,
+            foreign,
+            prev: None,
+            next: None,
+            bucket_next: self.map.get(&hash).copied(),
+        };
+        let slot = Slot {
+            generation: self.next_generation(),
+            node: Some(node),
+        };
+        let index = match self.free.pop() {
+            Some(index) => {
+                self.slots[index] = slot;
+                index
+            }
+            None => {
+                self.slots.push(slot);
+                self.slots.len() - 1
+            }
+        };
+        self.push_back(index);
+        self.map.insert(hash, index);
+        index
+    }
+}
+
+impl<K: Eq + Hash, V, M> NodeList<K, V, M> {
+    /// Hash sous lequel `key` est indexée par `find` et `push`, ou sous
+    /// lequel une requête équivalente doit être cherchée
+    pub(crate) fn hash_of<Q: Hash + ?Sized>(&self, key: &Q) -> u64 {
+        self.hasher.hash_one(key)
+    }
+
+    pub(crate) fn find(&self, key: &K) -> Option<usize> {
+        self.find_by(key, |candidate| candidate == key)
+    }
+
+    /// Cherche le nœud dont la clé vérifie `eq` sous le hash de `query`,
+    /// puis parmi les nœuds ajoutés par `push_hashed`
+    pub(crate) fn find_by<Q: Hash + ?Sized>(&self, query: &Q, eq: impl Fn(&K) -> bool) -> Option<usize> {
+        self.find_hashed(self.hash_of(query), &eq).or_else(|| self.scan(true, eq))
+    }
+
+    /// Ajoute un nœud en queue de liste et retourne son emplacement
+    pub(crate) fn push(&mut self, key: K, value: V, meta: M) -> usize {
+        self.link_new(self.hash_of(&key), false, key, value, meta)
+    }
+
+    /// Vérifie le chaînage de l'ordre d'éviction et sa cohérence avec la
+    /// table d'index, dont les chaînes de collisions sont vérifiées en
+    /// premier pour que `find` ne suive que des emplacements occupés
+    pub(crate) fn check(&self) -> Result<(), InvariantError> {
+        let len = self.len();
+        let mut indexed = 0;
+        for (&hash, &first) in &self.map {
+            let mut current = Some(first);
+            while let Some(index) = current {
+                let node = self.slots.get(index).and_then(|slot| slot.node.as_ref());
+                let node = node.filter(|node| node.hash == hash && indexed < len);
+                let node = node.ok_or(InvariantError::StaleIndex { slot: index })?;
+                indexed += 1;
+                current = node.bucket_next;
+            }
+        }
+
+        let mut ordered = 0;
+        let mut foreign = 0;
+        let mut previous = None;
+        let mut current = self.head;
+        while let Some(index) = current {
+            let broken = InvariantError::BrokenOrder { position: ordered };
+            let node = self.slots.get(index).and_then(|slot| slot.node.as_ref()).ok_or(broken)?;
+            if node.prev != previous || ordered == len {
+                return Err(broken);
+            }
+            match self.find_hashed(node.hash, |key| *key == node.key) {
+                None => return Err(InvariantError::MissingFromIndex { position: ordered }),
+                Some(found) if found != index => {
+                    return Err(InvariantError::DuplicateKey { position: ordered })
+                }
+                Some(_) => {}
+            }
+            foreign += usize::from(node.foreign);
+            ordered += 1;
+            previous = current;
+            current = node.next;
+        }
+        if self.tail != previous {
+            return Err(InvariantError::BrokenOrder { position: ordered });
+        }
+        if ordered != len || indexed != len {
+            return Err(InvariantError::LengthMismatch { ordered, indexed, len });
+        }
+        if foreign != self.foreign {
+            return Err(InvariantError::CounterMismatch {
+                counter: "foreign",
+                stored: self.foreign,
+                actual: foreign,
+            });
+        }
+        Ok(())
+    }
+}
+
+/// Parcours de la tête vers la queue
+pub(crate) struct Iter<'a, K, V, M> {
+    slots: &'a [Slot<K, V, M>],
+    current: Option<usize>,
+}
+
+impl<'a, K, V, M> Iterator for Iter<'a, K, V, M> {
+    type Item = (&'a K, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let node = self.slots[self.current?].node.as_ref()?;
+        self.current = node.next;
+        Some((&node.key, &node.value))
+    }
+}
+
+/// Parcours des clés depuis les deux extrémités
+pub(crate) struct Keys<'a, K, V, M> {
+    slots: &'a [Slot<K, V, M>],
+    front: Option<usize>,
+    back: Option<usize>,
+    keep: fn(&M) -> bool,
+}
+
+impl<'a, K, V, M> Keys<'a, K, V, M> {
+    fn node(&self, index: usize) -> &'a Node<K, V, M> {
+        self.slots[index].node.as_ref().expect("emplacement vide dans la liste")
+    }
+}
+
+impl<'a, K, V, M> Iterator for Keys<'a, K, V, M> {
+    type Item = &'a K;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let index = self.front?;
+            let node = self.node(index);
+            if self.back == Some(index) {
+                self.front = None;
+                self.back = None;
+            } else {
+                self.front = node.next;
+            }
+            if (self.keep)(&node.meta) {
+                return Some(&node.key);
+            }
+        }
+    }
+}
+
+impl<K, V, M> DoubleEndedIterator for Keys<'_, K, V, M> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        loop {
+            let index = self.back?;
+            let node = self.node(index);
+            if self.front == Some(index) {
+                self.front = None;
+                self.back = None;
+            } else {
+                self.back = node.prev;
+            }
+            if (self.keep)(&node.meta) {
+                return Some(&node.key);
+            }
+        }
+    }
+}
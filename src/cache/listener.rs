@@ -0,0 +1,60 @@
+//! Notification des entrées qui quittent le cache et des autres événements
+
+use super::clock::Instant;
+
+/// Raison pour laquelle une entrée a quitté le cache
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RemovalCause {
+    /// Évincée pour faire de la place
+    Capacity,
+    /// Expirée
+    Expired,
+    /// Supprimée explicitement
+    Explicit,
+    /// Valeur remplacée par une nouvelle valeur pour la même clé
+    Replaced,
+    /// Supprimée par un vidage du cache
+    Cleared,
+}
+
+/// Retrait d'une entrée conservé par l'historique des évictions (voir
+/// [`Cache::enable_eviction_history`](super::lru::Cache::enable_eviction_history))
+///
+/// La valeur retirée n'est pas gardée, seulement une copie de la clé.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EvictionRecord<K> {
+    pub key: K,
+    pub cause: RemovalCause,
+    /// Instant du retrait selon l'horloge du cache
+    pub at: Instant,
+}
+
+/// Observateur des événements d'un [`Cache`](super::lru::Cache), appelé avec
+/// la clé concernée
+///
+/// Toutes les méthodes ne font rien par défaut : une implémentation ne
+/// redéfinit que celles qui l'intéressent. Les lectures sont signalées dans
+/// les mêmes cas que ceux comptés par
+/// [`Cache::stats`](super::lru::Cache::stats) ;
+/// [`CacheStats`](super::stats::CacheStats) est d'ailleurs elle-même un
+/// observateur.
+pub trait CacheEventListener<K> {
+    /// Lecture qui a trouvé une entrée vivante
+    fn on_hit(&mut self, _key: &K) {}
+
+    /// Lecture qui n'a rien trouvé, entrée expirée comprise
+    fn on_miss(&mut self, _key: &K) {}
+
+    /// Insertion d'une clé absente
+    fn on_insert(&mut self, _key: &K) {}
+
+    /// Remplacement de la valeur d'une clé présente
+    fn on_update(&mut self, _key: &K) {}
+
+    /// Éviction d'une entrée pour faire de la place, ou par `evict_n` et
+    /// `truncate`
+    fn on_evict(&mut self, _key: &K) {}
+
+    /// Retrait d'une entrée expirée
+    fn on_expire(&mut self, _key: &K) {}
+}
@@ -0,0 +1,325 @@
+//! Cache en lecture directe, qui charge lui-même les valeurs absentes
+
+use super::lru::{Cache, Lookup, StaleEntry};
+use alloc::vec::Vec;
+use super::traits::CacheStorage;
+use core::fmt;
+use core::hash::Hash;
+use core::ops::{Deref, DerefMut};
+
+/// [`Cache`] qui appelle une fonction de chargement pour chaque clé absente
+///
+/// [`LoadingCache::get`] retourne la valeur en cache ou, en cas d'échec,
+/// appelle le chargeur, insère la valeur obtenue (en évinçant si besoin) et
+/// la retourne. Une erreur du chargeur est retournée telle quelle et rien
+/// n'est mis en cache pour la clé : la lecture suivante réessaie.
+/// [`LoadingCache::get_optional`] accepte un chargeur qui peut répondre
+/// qu'une clé n'existe pas : la réponse est gardée comme entrée négative
+/// ([`Cache::put_negative`]) et le chargeur n'est plus appelé pour cette
+/// clé tant qu'elle est en cache.
+/// [`LoadingCache::get_with_stale_while_revalidate`] sert une valeur
+/// expirée sans attendre et la recharge plus tard. Avec
+/// [`Cache::set_refresh_ahead`], une valeur proche de son expiration est de
+/// même servie puis rechargée, avant qu'une lecture ne la trouve expirée.
+/// [`LoadingCache::get_if_cached`] lit sans charger. Le cache s'utilise
+/// aussi directement à travers `Deref`/`DerefMut`, par exemple pour le
+/// préremplir ou invalider une entrée.
+///
+/// Les lectures sont comptées dans [`Cache::stats`] comme celles du cache :
+/// un chargement compte pour un échec, suivi d'une insertion s'il réussit.
+///
+/// # Examples
+/// ```
+/// use lru_cache::cache::loading::LoadingCache;
+///
+/// let mut cache = LoadingCache::new(2, |key: &u32| -> Result<String, String> {
+///     Ok(format!("valeur {}", key))
+/// });
+/// assert_eq!(cache.get(&1).unwrap(), "valeur 1");
+/// assert_eq!(cache.get_if_cached(&1), Some(&String::from("valeur 1")));
+/// assert_eq!(cache.get_if_cached(&2), None);
+/// ```
+pub struct LoadingCache<K, V, F> {
+    cache: Cache<K, V>,
+    loader: F,
+    /// Clés servies expirées ou proches de l'expiration, à recharger
+    stale: Vec<K>,
+}
+
+impl<K: Eq + Hash, V, F> LoadingCache<K, V, F> {
+    /// Crée un cache vide de capacité `capacity` qui charge les valeurs
+    /// absentes avec `loader`
+    ///
+    /// # Panics
+    ///
+    /// Panique si `capacity` vaut 0, comme [`Cache::new`].
+    pub fn new(capacity: usize, loader: F) -> Self {
+        LoadingCache::from_cache(Cache::new(capacity), loader)
+    }
+
+    /// Ajoute le chargeur `loader` à un cache existant, qui garde ses
+    /// entrées et sa configuration
+    pub fn from_cache(cache: Cache<K, V>, loader: F) -> Self {
+        LoadingCache {
+            cache,
+            loader,
+            stale: Vec::new(),
+        }
+    }
+
+    /// Retourne le cache, sans le chargeur
+    pub fn into_inner(self) -> Cache<K, V> {
+        self.cache
+    }
+
+    /// Récupère la valeur associée à `key` si elle est en cache, sans
+    /// appeler le chargeur
+    ///
+    /// La lecture est celle de [`CacheStorage::get`] : elle promeut
+    /// l'entrée et compte dans les statistiques.
+    pub fn get_if_cached(&mut self, key: &K) -> Option<&V> {
+        self.cache.get(key)
+    }
+
+    /// Récupère la valeur associée à `key`, en la chargeant si elle est
+    /// absente ou expirée
+    ///
+    /// Avec [`Cache::set_refresh_ahead`], une valeur dans sa fenêtre de
+    /// rechargement anticipé est retournée sans attendre et sa clé est
+    /// marquée : elle est rechargée une fois, au début de l'appel suivant
+    /// de cette méthode ou par [`LoadingCache::refresh_stale`], ce qui
+    /// remplace la valeur et repart d'une durée de vie entière. Si ce
+    /// rechargement échoue, l'ancienne valeur reste jusqu'à son expiration
+    /// et la clé est marquée de nouveau à sa prochaine lecture.
+    ///
+    /// # Errors
+    ///
+    /// Retourne l'erreur du chargeur ; la clé reste alors absente du cache.
+    /// Une erreur d'un rechargement différé est ignorée.
+    ///
+    /// # Examples
+    /// ```
+    /// use lru_cache::cache::builder::CacheBuilder;
+    /// use lru_cache::cache::clock::MockClock;
+    /// use lru_cache::cache::loading::LoadingCache;
+    /// use std::num::NonZeroUsize;
+    /// use std::time::Duration;
+    ///
+    /// let clock = MockClock::new();
+    /// let cache = CacheBuilder::new()
+    ///     .capacity(NonZeroUsize::new(2).unwrap())
+    ///     .time_to_live(Duration::from_secs(10))
+    ///     .refresh_ahead(0.2)
+    ///     .clock(clock.clone())
+    ///     .build()
+    ///     .unwrap();
+    /// let mut version = 0;
+    /// let mut cache = LoadingCache::from_cache(cache, move |_: &&str| -> Result<u32, String> {
+    ///     version += 1;
+    ///     Ok(version)
+    /// });
+    /// assert_eq!(cache.get(&"A"), Ok(&1));
+    /// clock.advance(Duration::from_secs(9));
+    /// assert_eq!(cache.get(&"A"), Ok(&1));
+    /// assert_eq!(cache.get(&"A"), Ok(&2));
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panique si la valeur chargée est refusée par le cache, comme
+    /// [`Cache::get_or_insert_with`] : valeur plus lourde que la capacité
+    /// d'un cache pondéré.
+    pub fn get<E>(&mut self, key: &K) -> Result<&V, E>
+    where
+        K: Clone,
+        F: FnMut(&K) -> Result<V, E>,
+    {
+        if !self.stale.is_empty() {
+            let _ = self.refresh_stale();
+        }
+        if self.cache.refresh_due(key) {
+            self.mark_stale(key);
+        }
+        if self.cache.get(key).is_none() {
+            let value = (self.loader)(key)?;
+            self.cache.put(key.clone(), value);
+        }
+        Ok(self.cache.peek(key).expect("valeur chargée refusée par le cache"))
+    }
+
+    /// Récupère la valeur associée à `key`, en la chargeant si le cache ne
+    /// sait rien de la clé, avec un chargeur qui retourne `Ok(None)` pour
+    /// une clé qui n'existe pas
+    ///
+    /// Une réponse `Ok(None)` est gardée comme entrée négative, avec la
+    /// durée de vie par défaut du cache. Une entrée négative en cache, de
+    /// cette méthode ou de [`Cache::put_negative`], retourne `Ok(None)` sans
+    /// appeler le chargeur.
+    ///
+    /// # Errors
+    ///
+    /// Retourne l'erreur du chargeur ; le cache ne garde alors rien pour la
+    /// clé.
+    ///
+    /// # Panics
+    ///
+    /// Panique si la valeur chargée est refusée par le cache, comme
+    /// [`LoadingCache::get`].
+    ///
+    /// # Examples
+    /// ```
+    /// use lru_cache::cache::loading::LoadingCache;
+    /// use std::cell::Cell;
+    ///
+    /// let calls = Cell::new(0);
+    /// let mut cache = LoadingCache::new(2, |key: &u32| -> Result<Option<u32>, String> {
+    ///     calls.set(calls.get() + 1);
+    ///     Ok((key % 2 == 0).then_some(key * 10))
+    /// });
+    /// assert_eq!(cache.get_optional(&2), Ok(Some(&20)));
+    /// assert_eq!(cache.get_optional(&3), Ok(None));
+    /// assert_eq!(cache.get_optional(&3), Ok(None));
+    /// assert_eq!(calls.get(), 2);
+    /// ```
+    pub fn get_optional<E>(&mut self, key: &K) -> Result<Option<&V>, E>
+    where
+        K: Clone,
+        F: FnMut(&K) -> Result<Option<V>, E>,
+    {
+        match self.cache.lookup(key) {
+            Lookup::Hit(_) => {}
+            Lookup::NegativeHit => return Ok(None),
+            Lookup::Miss => match (self.loader)(key)? {
+                Some(value) => self.cache.put(key.clone(), value),
+                None => {
+                    self.cache.put_negative(key.clone(), None);
+                    return Ok(None);
+                }
+            },
+        }
+        Ok(Some(self.cache.peek(key).expect("valeur chargée refusée par le cache")))
+    }
+}
+
+impl<K: Eq + Hash + Clone, V, F> LoadingCache<K, V, F> {
+    /// Récupère la valeur associée à `key` ; une valeur expirée est
+    /// retournée telle quelle et la clé est marquée pour être rechargée
+    ///
+    /// Une clé absente est chargée tout de suite, comme par
+    /// [`LoadingCache::get`], et retournée dans [`StaleEntry::Fresh`]. Une
+    /// valeur expirée est lue comme par [`Cache::get_stale`] sans appeler
+    /// le chargeur : le rechargement a lieu au début de l'appel suivant de
+    /// cette méthode, ou par [`LoadingCache::refresh_stale`]. Une erreur de
+    /// ce rechargement différé est ignorée et la valeur expirée reste en
+    /// cache, marquée de nouveau à sa prochaine lecture.
+    ///
+    /// # Errors
+    ///
+    /// Retourne l'erreur du chargeur pour une clé absente.
+    ///
+    /// # Panics
+    ///
+    /// Panique si la valeur chargée est refusée par le cache, comme
+    /// [`LoadingCache::get`].
+    ///
+    /// # Examples
+    /// ```
+    /// use lru_cache::Cache;
+    /// use lru_cache::cache::clock::MockClock;
+    /// use lru_cache::cache::loading::LoadingCache;
+    /// use lru_cache::cache::ttl::TtlMode;
+    /// use std::time::Duration;
+    ///
+    /// let clock = MockClock::new();
+    /// let cache = Cache::with_ttl_and_clock(2, Duration::from_secs(10), TtlMode::AfterWrite, clock.clone());
+    /// let mut version = 0;
+    /// let mut cache = LoadingCache::from_cache(cache, move |_: &&str| -> Result<u32, String> {
+    ///     version += 1;
+    ///     Ok(version)
+    /// });
+    /// assert_eq!(cache.get_with_stale_while_revalidate(&"A").unwrap().into_value(), &1);
+    /// clock.advance(Duration::from_secs(10));
+    /// assert!(cache.get_with_stale_while_revalidate(&"A").unwrap().is_stale());
+    /// assert_eq!(cache.refresh_stale(), Ok(1));
+    /// assert_eq!(cache.get_if_cached(&"A"), Some(&2));
+    /// ```
+    pub fn get_with_stale_while_revalidate<E>(&mut self, key: &K) -> Result<StaleEntry<&V>, E>
+    where
+        F: FnMut(&K) -> Result<V, E>,
+    {
+        let _ = self.refresh_stale();
+        if self.cache.holds(key) {
+            let due = self.cache.refresh_due(key);
+            let entry = self.cache.get_stale(key).expect("valeur en cache");
+            if (entry.is_stale() || due) && !self.stale.contains(key) {
+                self.stale.push(key.clone());
+            }
+            return Ok(entry);
+        }
+        self.get(key).map(StaleEntry::Fresh)
+    }
+
+    /// Recharge les clés servies expirées par
+    /// [`LoadingCache::get_with_stale_while_revalidate`], ou proches de
+    /// l'expiration (voir [`Cache::set_refresh_ahead`]), et retourne le
+    /// nombre de valeurs remplacées
+    ///
+    /// Une clé retirée du cache entre-temps n'est pas rechargée.
+    ///
+    /// # Errors
+    ///
+    /// Retourne la première erreur du chargeur, après avoir essayé toutes
+    /// les clés ; les valeurs en échec restent expirées en cache.
+    pub fn refresh_stale<E>(&mut self) -> Result<usize, E>
+    where
+        F: FnMut(&K) -> Result<V, E>,
+    {
+        let mut refreshed = 0;
+        let mut first_error = None;
+        for key in core::mem::take(&mut self.stale) {
+            if !self.cache.holds(&key) {
+                continue;
+            }
+            match (self.loader)(&key) {
+                Ok(value) => {
+                    self.cache.put(key, value);
+                    refreshed += 1;
+                }
+                Err(error) => {
+                    first_error.get_or_insert(error);
+                }
+            }
+        }
+        match first_error {
+            Some(error) => Err(error),
+            None => Ok(refreshed),
+        }
+    }
+
+    /// Marque `key` pour le prochain rechargement, une seule fois
+    fn mark_stale(&mut self, key: &K) {
+        if !self.stale.contains(key) {
+            self.stale.push(key.clone());
+        }
+    }
+}
+
+impl<K, V, F> Deref for LoadingCache<K, V, F> {
+    type Target = Cache<K, V>;
+
+    fn deref(&self) -> &Cache<K, V> {
+        &self.cache
+    }
+}
+
+impl<K, V, F> DerefMut for LoadingCache<K, V, F> {
+    fn deref_mut(&mut self) -> &mut Cache<K, V> {
+        &mut self.cache
+    }
+}
+
+impl<K: fmt::Debug, V: fmt::Debug, F> fmt::Debug for LoadingCache<K, V, F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("LoadingCache").field("cache", &self.cache).finish_non_exhaustive()
+    }
+}
@@ -1,86 +1,5357 @@
-use super::traits::{CacheStorage, PersistentStorage};
-use std::collections::HashMap;
-use std::fmt::Display;
-use std::hash::Hash;
-use std::str::FromStr;
+use super::traits::{CacheClear, CacheIterate, CacheLen, CacheRemove, CacheStorage, GetOptions};
+#[cfg(feature = "std")]
+use super::traits::{BinaryPersistentStorage, PersistentStorage};
+use super::builder::{CacheBuilder, EvictionListener};
+use super::clock::{Clock, Instant};
+use super::clock::DefaultClock;
+use super::equivalent::Equivalent;
+use super::error::{CacheError, CasError, InvariantError};
+#[cfg(feature = "std")]
+use super::frozen::FrozenCache;
+use super::list::{self, NodeList};
+use super::metrics::{self, Snapshot};
+use super::listener::{CacheEventListener, EvictionRecord, RemovalCause};
+use super::mem_size::{MemSize, ENTRY_OVERHEAD};
+use super::policy::{Candidates, EvictionPolicy, Lru};
+#[allow(deprecated)]
+use super::policy::Policy;
+use super::rng::XorShift64;
+use super::scoped::ScopedCache;
+use super::transaction::Transaction;
+use super::snapshot::{CacheSnapshot, SnapshotEntry};
+use super::stats::CacheStats;
+use super::testing::{stable_hash, OperationLog};
+use super::ttl::{TtlMode, DEFAULT_TTL_GRANULARITY};
+use super::weigher::Weigher;
+use super::hit_order::HitOrder;
+use super::wheel::{Position, TimingWheel};
+#[cfg(feature = "std")]
+use crate::storage::binary::{BinaryError, BinaryStorage, FromBytes, ToBytes};
+#[cfg(feature = "serde")]
+use crate::storage::bincode::BincodeStorage;
+#[cfg(feature = "serde")]
+use crate::storage::json::{JsonError, JsonStorage};
+#[cfg(feature = "std")]
+use crate::storage::codec::ValueCodec;
+#[cfg(feature = "std")]
+use crate::storage::csv;
+#[cfg(feature = "std")]
+use crate::storage::error::StorageError;
+#[cfg(feature = "std")]
+use crate::storage::backend::StorageBackend;
+#[cfg(feature = "std")]
+use crate::storage::file::{save_entries, write_entries, FileStorage, Record, SaveOptions, VerifyOptions, VerifyReport};
+#[cfg(feature = "std")]
+use crate::storage::format::INCREMENTAL_VERSION;
+#[cfg(feature = "std")]
+use crate::storage::lock::FileLock;
+use alloc::boxed::Box;
+use alloc::collections::VecDeque;
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::sync::Arc;
+use alloc::vec::Vec;
+use core::cell::{Cell, RefCell};
+use core::fmt::{self, Display};
+use core::hash::Hash;
+use core::num::NonZeroUsize;
+#[cfg(feature = "std")]
+use core::str::FromStr;
+use core::sync::atomic::Ordering;
+#[cfg(feature = "std")]
+use core::sync::atomic::AtomicU64;
+use core::time::Duration;
+#[cfg(feature = "std")]
+use std::collections::HashSet;
+#[cfg(feature = "std")]
+use std::path::{Path, PathBuf};
+#[cfg(feature = "std")]
+use std::time::SystemTime;
+
+/// Identifiant opaque et copiable d'une entrée du cache
+///
+/// Un `EntryId` encode l'indice interne de l'emplacement de l'entrée ainsi
+/// qu'une génération. Dès que l'entrée quitte le cache (éviction, suppression)
+/// ou que sa valeur est remplacée, l'identifiant devient invalide : les
+/// méthodes `*_by_id` retournent alors `None` au lieu de toucher un
+/// emplacement réutilisé par une autre clé. Les générations ne sont jamais
+/// réattribuées, un identifiant invalide ne redevient donc jamais valide.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct EntryId {
+    index: usize,
+    generation: u64,
+}
+
+/// Ordre dans lequel les entrées sont écrites par
+/// [`Cache::save_to_file_ordered`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Order {
+    /// De la moins récemment utilisée à la plus récente, comme `iter`
+    #[default]
+    Recency,
+    /// De la première insérée à la dernière, comme
+    /// [`Cache::iter_insertion_order`]
+    Insertion,
+}
+
+/// Résultat de [`Cache::lookup`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Lookup<T> {
+    /// La clé est en cache avec cette valeur
+    Hit(T),
+    /// La clé est connue comme absente (entrée négative)
+    NegativeHit,
+    /// Le cache ne sait rien de la clé
+    Miss,
+}
+
+/// Résultat de [`Cache::get_stale`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StaleEntry<T> {
+    /// La valeur n'a pas expiré
+    Fresh(T),
+    /// La valeur a expiré depuis `expired_for`, mais est encore en cache
+    Stale { value: T, expired_for: Duration },
+}
+
+impl<T> StaleEntry<T> {
+    /// Retourne la valeur, fraîche ou non
+    pub fn into_value(self) -> T {
+        match self {
+            StaleEntry::Fresh(value) | StaleEntry::Stale { value, .. } => value,
+        }
+    }
+
+    /// Indique si la valeur a expiré
+    pub fn is_stale(&self) -> bool {
+        matches!(self, StaleEntry::Stale { .. })
+    }
+}
+
+/// Fonction de [`MergeStrategy::Combine`]
+type Combiner<'a, K, V> = Box<dyn FnMut(&K, V, V) -> V + 'a>;
+
+/// Règle des conflits de [`Cache::merge`], pour une clé présente dans les
+/// deux caches
+pub enum MergeStrategy<'a, K, V> {
+    /// Garder l'entrée de ce cache
+    PreferSelf,
+    /// Prendre la valeur de l'autre cache
+    PreferOther,
+    /// Calculer la valeur à partir de la clé, de la valeur de ce cache et
+    /// de celle de l'autre, dans cet ordre
+    Combine(Combiner<'a, K, V>),
+}
+
+impl<'a, K, V> MergeStrategy<'a, K, V> {
+    /// Construit [`MergeStrategy::Combine`] sans avoir à mettre `combine`
+    /// dans une boîte
+    pub fn combine(combine: impl FnMut(&K, V, V) -> V + 'a) -> Self {
+        MergeStrategy::Combine(Box::new(combine))
+    }
+}
+
+impl<K, V> fmt::Debug for MergeStrategy<'_, K, V> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MergeStrategy::PreferSelf => f.write_str("PreferSelf"),
+            MergeStrategy::PreferOther => f.write_str("PreferOther"),
+            MergeStrategy::Combine(_) => f.write_str("Combine(..)"),
+        }
+    }
+}
+
+/// Dates d'une entrée, relevées sur l'horloge du cache par
+/// [`Cache::metadata`] et [`Cache::iter_metadata`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct EntryMetadata {
+    /// Insertion de la clé ; remplacer sa valeur ne la change pas
+    pub created_at: Instant,
+    /// Dernière lecture comptée, ou l'insertion si l'entrée n'a jamais été
+    /// lue
+    pub last_accessed_at: Instant,
+    /// Nombre de lectures comptées depuis l'insertion
+    pub access_count: u64,
+}
+
+/// Dates suivies par [`Cache::track_metadata`]
+#[derive(Debug, Clone, Copy)]
+struct Times {
+    created_at: Instant,
+    accessed_at: Instant,
+}
+
+/// Entrées d'un cache ; une valeur `None` est une entrée négative (voir
+/// [`Cache::put_negative`])
+pub(super) type Entries<K, V> = NodeList<K, Option<V>, Meta>;
+
+type Node<K, V> = list::Node<K, Option<V>, Meta>;
+
+#[derive(Debug)]
+pub(super) struct Meta {
+    ttl: Option<Duration>,
+    expires_at: Option<Instant>,
+    /// Échéance d'inactivité de [`Cache::with_max_idle`], repoussée à
+    /// chaque lecture
+    idle_at: Option<Instant>,
+    /// Dans une boîte pour qu'un cache sans suivi ne paie qu'un pointeur
+    times: Option<Box<Times>>,
+    hits: u64,
+    /// Rang d'insertion de la clé, gardé quand sa valeur est remplacée
+    inserted: u64,
+    weight: usize,
+    /// Nombre d'écritures de la valeur depuis l'insertion de la clé
+    version: u64,
+    pinned: bool,
+    /// Rang de la dernière lecture partagée pas encore appliquée, 0 si aucune
+    ///
+    /// Atomique plutôt que `Cell` pour que la liste des entrées puisse être
+    /// partagée entre threads par un `FrozenCache` ; un seul thread y
+    /// accède tant qu'elle appartient au cache.
+    shared_read: ReadRank,
+}
+
+/// Rang d'une lecture partagée ; sans `std` il n'y a pas de
+/// `FrozenCache`, une `Cell` suffit et évite d'exiger des atomiques 64 bits
+#[cfg(feature = "std")]
+type ReadRank = AtomicU64;
+
+#[cfg(not(feature = "std"))]
+#[derive(Debug)]
+struct ReadRank(Cell<u64>);
+
+#[cfg(not(feature = "std"))]
+impl ReadRank {
+    fn new(rank: u64) -> Self {
+        ReadRank(Cell::new(rank))
+    }
+
+    fn load(&self, _: Ordering) -> u64 {
+        self.0.get()
+    }
+
+    fn store(&self, rank: u64, _: Ordering) {
+        self.0.set(rank);
+    }
+
+    fn swap(&self, rank: u64, _: Ordering) -> u64 {
+        self.0.replace(rank)
+    }
+}
+
+impl Meta {
+    /// Copie gardée par une transaction pour rétablir les métadonnées
+    fn snapshot(&self) -> Meta {
+        Meta {
+            ttl: self.ttl,
+            expires_at: self.expires_at,
+            idle_at: self.idle_at,
+            times: self.times.clone(),
+            hits: self.hits,
+            inserted: self.inserted,
+            weight: self.weight,
+            version: self.version,
+            pinned: self.pinned,
+            shared_read: ReadRank::new(self.shared_read.load(Ordering::Relaxed)),
+        }
+    }
+
+    /// Vrai dès que l'une des deux échéances est atteinte
+    pub(super) fn is_expired(&self, now: Instant) -> bool {
+        [self.expires_at, self.idle_at].into_iter().flatten().any(|deadline| now >= deadline)
+    }
+
+    fn has_deadline(&self) -> bool {
+        self.expires_at.is_some() || self.idle_at.is_some()
+    }
+
+    /// Première des deux échéances
+    fn deadline(&self) -> Option<Instant> {
+        [self.expires_at, self.idle_at].into_iter().flatten().min()
+    }
+
+    /// Temps écoulé depuis la première échéance dépassée, ou `None` si
+    /// l'entrée n'a pas expiré
+    fn expired_for(&self, now: Instant) -> Option<Duration> {
+        [self.expires_at, self.idle_at]
+            .into_iter()
+            .flatten()
+            .filter(|&deadline| now >= deadline)
+            .min()
+            .map(|deadline| now.saturating_duration_since(deadline))
+    }
+}
+
+/// Lectures faites par `get_shared`, appliquées à l'ordre d'utilisation lors
+/// de la prochaine opération mutable
+#[derive(Debug, Default)]
+struct SharedReads {
+    pending: RefCell<Vec<usize>>,
+    rank: Cell<u64>,
+    hits: Cell<u64>,
+    misses: Cell<u64>,
+}
+
+/// Calcul du poids des entrées d'un cache pondéré
+struct Weighing<K, V> {
+    weigher: Box<dyn Weigher<K, V>>,
+    total: usize,
+}
+
+/// Dispersion des durées de vie à l'insertion (voir
+/// [`Cache::set_ttl_jitter`])
+#[derive(Debug, Clone)]
+struct Jitter {
+    fraction: f64,
+    rng: XorShift64,
+}
+
+impl<K, V> fmt::Debug for Weighing<K, V> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Weighing").field("total", &self.total).finish_non_exhaustive()
+    }
+}
+
+/// Empreinte d'un fichier : il est considéré comme modifié dès que sa
+/// taille ou sa date de modification change
+#[cfg(feature = "std")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct FileStamp {
+    path: PathBuf,
+    len: u64,
+    modified: Option<SystemTime>,
+}
+
+#[cfg(feature = "std")]
+impl FileStamp {
+    fn read(path: &Path) -> std::io::Result<Self> {
+        let metadata = std::fs::metadata(path)?;
+        Ok(FileStamp {
+            path: path.to_path_buf(),
+            len: metadata.len(),
+            modified: metadata.modified().ok(),
+        })
+    }
+}
+
+/// Clés modifiées depuis la dernière sauvegarde, suivies à partir de
+/// [`Cache::track_changes`]
+#[cfg(feature = "std")]
+struct Changes<K> {
+    keys: HashSet<K>,
+    /// Copie une clé marquée : seul le suivi exige `K: Clone`
+    clone: fn(&K) -> K,
+}
+
+#[cfg(feature = "std")]
+impl<K> fmt::Debug for Changes<K> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Changes").field("keys", &self.keys.len()).finish_non_exhaustive()
+    }
+}
+
+#[cfg(feature = "std")]
+impl<K: Eq + Hash> Changes<K> {
+    fn mark(&mut self, key: &K) {
+        if !self.keys.contains(key) {
+            self.keys.insert((self.clone)(key));
+        }
+    }
+}
+
+/// Derniers retraits d'entrées, gardés à partir de
+/// [`Cache::enable_eviction_history`]
+struct EvictionHistory<K> {
+    records: VecDeque<EvictionRecord<K>>,
+    limit: usize,
+    /// Copie la clé retirée : seul l'historique exige `K: Clone`
+    clone: fn(&K) -> K,
+}
+
+impl<K> fmt::Debug for EvictionHistory<K> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("EvictionHistory")
+            .field("records", &self.records.len())
+            .field("limit", &self.limit)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<K> EvictionHistory<K> {
+    /// Ajoute un retrait en oubliant le plus ancien si l'historique est plein
+    fn push(&mut self, key: &K, cause: RemovalCause, at: Instant) {
+        if self.records.len() == self.limit {
+            self.records.pop_front();
+        }
+        self.records.push_back(EvictionRecord {
+            key: (self.clone)(key),
+            cause,
+            at,
+        });
+    }
+}
+
+/// Clés évincées pour faire de la place, de la plus ancienne à la plus
+/// récente, gardées à partir de [`Cache::with_ghost_capacity`]
+struct Ghosts<K> {
+    keys: NodeList<K, (), ()>,
+    capacity: usize,
+    /// Copie la clé évincée : seule la liste fantôme exige `K: Clone`
+    clone: fn(&K) -> K,
+}
+
+impl<K> fmt::Debug for Ghosts<K> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Ghosts")
+            .field("len", &self.keys.len())
+            .field("capacity", &self.capacity)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<K: Eq + Hash> Ghosts<K> {
+    /// Ajoute `key` comme la clé évincée la plus récente, en oubliant la plus
+    /// ancienne si la liste est pleine
+    fn push(&mut self, key: &K) {
+        if let Some(index) = self.keys.find(key) {
+            self.keys.move_to_back(index);
+            return;
+        }
+        if self.keys.len() == self.capacity {
+            if let Some(oldest) = self.keys.head() {
+                self.keys.remove(oldest);
+            }
+        }
+        self.keys.push((self.clone)(key), (), ());
+    }
+
+    /// Retire `key` de la liste et indique s'il y était
+    fn take(&mut self, key: &K) -> bool {
+        match self.keys.find(key) {
+            Some(index) => {
+                self.keys.remove(index);
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+/// Modification faite pendant une [`Transaction`], dans ce qu'il faut pour
+/// la défaire
+enum Undo<K, V> {
+    /// Nœud ajouté à l'emplacement `index`
+    Attached { index: usize },
+    /// Nœud retiré de l'emplacement `index`, où il suivait `prev` et
+    /// occupait `position` dans la roue ; il n'est passé à l'écouteur qu'à la
+    /// validation
+    Detached {
+        index: usize,
+        generation: u64,
+        prev: Option<usize>,
+        position: Option<Position>,
+        node: Node<K, V>,
+        cause: RemovalCause,
+    },
+    /// Valeur remplacée de l'emplacement `index`
+    Replaced {
+        index: usize,
+        generation: u64,
+        key: K,
+        value: Option<V>,
+    },
+    /// Métadonnées de l'emplacement `index` avant leur modification
+    Meta { index: usize, meta: Meta },
+    /// Nœud `index` déplacé alors qu'il suivait `prev`
+    Moved { index: usize, prev: Option<usize> },
+}
+
+/// Journal d'une transaction en cours et état du cache à son début
+struct UndoLog<K, V> {
+    undo: Vec<Undo<K, V>>,
+    stats: CacheStats,
+    insertions: u64,
+    rng: XorShift64,
+    jitter: Option<Jitter>,
+}
+
+impl<K, V> fmt::Debug for UndoLog<K, V> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("UndoLog").field("undo", &self.undo.len()).finish_non_exhaustive()
+    }
+}
+
+/// Fonction appelée pour chaque entrée qui quitte le cache
+struct Listener<K, V>(EvictionListener<K, V>);
+
+impl<K, V> fmt::Debug for Listener<K, V> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("Listener")
+    }
+}
+
+/// Compteurs d'activité et observateur des événements du cache
+struct Events<K> {
+    stats: CacheStats,
+    listener: Option<Box<dyn CacheEventListener<K> + Send>>,
+}
+
+impl<K> fmt::Debug for Events<K> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Events").field("stats", &self.stats).finish_non_exhaustive()
+    }
+}
+
+impl<K: Hash> CacheEventListener<K> for Events<K> {
+    fn on_hit(&mut self, key: &K) {
+        CacheEventListener::<K>::on_hit(&mut self.stats, key);
+        trace_event!(trace, key_hash = %stable_hash(key), "cache hit");
+        if let Some(listener) = self.listener.as_mut() {
+            listener.on_hit(key);
+        }
+    }
+
+    fn on_miss(&mut self, key: &K) {
+        CacheEventListener::<K>::on_miss(&mut self.stats, key);
+        trace_event!(trace, key_hash = %stable_hash(key), "cache miss");
+        if let Some(listener) = self.listener.as_mut() {
+            listener.on_miss(key);
+        }
+    }
+
+    fn on_insert(&mut self, key: &K) {
+        CacheEventListener::<K>::on_insert(&mut self.stats, key);
+        trace_event!(trace, key_hash = %stable_hash(key), "cache insert");
+        if let Some(listener) = self.listener.as_mut() {
+            listener.on_insert(key);
+        }
+    }
+
+    fn on_update(&mut self, key: &K) {
+        CacheEventListener::<K>::on_update(&mut self.stats, key);
+        trace_event!(trace, key_hash = %stable_hash(key), "cache update");
+        if let Some(listener) = self.listener.as_mut() {
+            listener.on_update(key);
+        }
+    }
+
+    fn on_evict(&mut self, key: &K) {
+        CacheEventListener::<K>::on_evict(&mut self.stats, key);
+        if let Some(listener) = self.listener.as_mut() {
+            listener.on_evict(key);
+        }
+    }
+
+    fn on_expire(&mut self, key: &K) {
+        CacheEventListener::<K>::on_expire(&mut self.stats, key);
+        if let Some(listener) = self.listener.as_mut() {
+            listener.on_expire(key);
+        }
+    }
+}
 
 /// Cache LRU (Least Recently Used) qui stocke les éléments les plus récemment utilisés
-/// 
+///
+/// Les entrées sont conservées dans un slab de nœuds chaînés dans l'ordre
+/// d'utilisation : la tête de la liste est l'entrée la moins récemment
+/// utilisée, la queue la plus récente. Chaque clé n'est stockée qu'une seule
+/// fois, dans son nœud : la table d'index associe le hash de la clé au premier
+/// nœud d'une chaîne de collisions. Les clés n'ont donc pas besoin d'implémenter
+/// `Clone`.
+///
+/// L'entrée évincée est choisie par la politique `P` (voir
+/// [`EvictionPolicy`]), LRU par défaut.
+///
 /// # Examples
 /// ```
 /// use lru_cache::Cache;
 /// use lru_cache::cache::traits::CacheStorage;
-/// 
+///
 /// let mut cache = Cache::new(3);
 /// cache.put("key1", 42);
 /// assert_eq!(cache.get(&"key1"), Some(&42));
 /// ```
 #[derive(Debug)]
-pub struct Cache<K, V> {
-    capacity: usize,
-    storage: HashMap<K, V>,
-    order: Vec<K>,
+pub struct Cache<K, V, P = Lru> {
+    capacity: NonZeroUsize,
+    /// Seuil bas de l'éviction par lots ; `None` pour la capacité
+    low_water: Option<NonZeroUsize>,
+    /// Rang attribué à la dernière clé insérée
+    insertions: u64,
+    entries: Entries<K, V>,
+    ttl: Option<(Duration, TtlMode)>,
+    max_idle: Option<Duration>,
+    /// Nombre d'entrées avec une échéance (durée de vie ou inactivité)
+    expiring: usize,
+    /// Entrées avec une échéance rangées par échéance, créée à la première
+    /// échéance pour que `purge_expired` ne parcoure que les entrées dues
+    wheel: Option<Box<TimingWheel>>,
+    ttl_granularity: Duration,
+    jitter: Option<Jitter>,
+    /// Fraction de la durée de vie en deçà de laquelle une entrée est à
+    /// recharger d'avance (voir [`Cache::set_refresh_ahead`])
+    refresh_ahead: Option<f64>,
+    history: Option<EvictionHistory<K>>,
+    ghosts: Option<Ghosts<K>>,
+    transaction: Option<UndoLog<K, V>>,
+    pinned: usize,
+    log: Option<OperationLog<K>>,
+    /// Politique intégrée dépréciée, qui remplace `eviction` si elle n'est
+    /// pas `Lru`
+    #[allow(deprecated)]
+    policy: Policy,
+    /// Entrées rangées par nombre de lectures, pour `Policy::TwoChoice`
+    hit_order: Option<HitOrder>,
+    rng: XorShift64,
+    clock: Arc<dyn Clock>,
+    weighing: Option<Weighing<K, V>>,
+    listener: Option<Listener<K, V>>,
+    events: Events<K>,
+    shared: SharedReads,
+    #[cfg(feature = "std")]
+    changes: Option<Changes<K>>,
+    /// Fichier texte du dernier chargement ou de la dernière sauvegarde,
+    /// pour [`Cache::reload_if_changed`]
+    #[cfg(feature = "std")]
+    file: RefCell<Option<FileStamp>>,
+    metadata: bool,
+    eviction: P,
 }
 
-impl<K: Clone + Eq + Hash, V> Cache<K, V> {
+impl<K: Eq + Hash, V> Cache<K, V> {
     /// Crée un nouveau cache avec la capacité spécifiée
+    ///
+    /// Aucune mémoire n'est réservée à l'avance : les structures internes
+    /// grandissent au fil des insertions. Voir [`Cache::with_preallocated`]
+    /// pour réserver toute la capacité dès la construction.
+    ///
+    /// Voir [`Cache::with_capacity`], qui exprime dans son type que la
+    /// capacité n'est pas nulle.
+    ///
+    /// # Panics
+    ///
+    /// Panique si `capacity` vaut 0 ; [`Cache::try_new`] retourne une
+    /// erreur à la place. Il en va de même pour tous les constructeurs qui
+    /// prennent une capacité `usize`.
     pub fn new(capacity: usize) -> Self {
+        Cache::with_capacity(non_zero(capacity))
+    }
+
+    /// Crée un nouveau cache avec la capacité spécifiée, qui ne peut pas
+    /// être nulle
+    ///
+    /// # Examples
+    /// ```
+    /// use lru_cache::Cache;
+    /// use lru_cache::cache::traits::CacheStorage;
+    /// use std::num::NonZeroUsize;
+    ///
+    /// let mut cache = Cache::with_capacity(NonZeroUsize::new(1).unwrap());
+    /// cache.put("A", 1);
+    /// cache.put("B", 2);
+    /// assert_eq!(cache.get(&"A"), None);
+    /// ```
+    pub fn with_capacity(capacity: NonZeroUsize) -> Self {
+        Cache::from_policy(capacity, Lru)
+    }
+
+    /// Crée un nouveau cache avec la capacité spécifiée, qui doit être au
+    /// moins 1
+    ///
+    /// # Examples
+    /// ```
+    /// use lru_cache::Cache;
+    /// use lru_cache::cache::error::CacheError;
+    ///
+    /// assert!(Cache::<&str, i32>::try_new(2).is_ok());
+    /// assert_eq!(Cache::<&str, i32>::try_new(0).unwrap_err(), CacheError::ZeroCapacity);
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Retourne [`CacheError::ZeroCapacity`] si `capacity` vaut 0.
+    pub fn try_new(capacity: usize) -> Result<Self, CacheError> {
+        NonZeroUsize::new(capacity).map(Cache::with_capacity).ok_or(CacheError::ZeroCapacity)
+    }
+
+    /// Crée un cache sans limite de capacité : `put` n'évince jamais
+    ///
+    /// L'ordre d'utilisation reste tenu, les entrées se retirent à la main
+    /// ([`Cache::evict_n`], [`Cache::truncate`]) ou en fixant une limite
+    /// avec [`Cache::resize`]. [`Cache::capacity`] vaut `usize::MAX`, valeur
+    /// que les sauvegardes enregistrent et relisent telle quelle.
+    ///
+    /// # Examples
+    /// ```
+    /// use lru_cache::Cache;
+    /// use lru_cache::cache::traits::CacheStorage;
+    ///
+    /// let mut cache = Cache::unbounded();
+    /// for i in 0..1000 {
+    ///     cache.put(i, i);
+    /// }
+    /// assert!(!cache.is_full());
+    /// assert_eq!(cache.evict_n(2), vec![(0, 0), (1, 1)]);
+    /// ```
+    pub fn unbounded() -> Self {
+        Cache::with_capacity(NonZeroUsize::MAX)
+    }
+
+    /// Reconstruit le cache copié par [`Cache::snapshot`], avec la
+    /// configuration par défaut
+    ///
+    /// Voir [`Cache::restore_into`].
+    pub fn restore(snapshot: CacheSnapshot<K, V>) -> Self {
+        let mut cache = Cache::with_capacity(snapshot.capacity);
+        cache.restore_into(snapshot);
+        cache
+    }
+
+    /// Crée un cache de capacité `capacity` rempli avec les entrées de `map`
+    ///
+    /// L'ordre d'une `HashMap` n'étant pas reproductible, les entrées sont
+    /// insérées par clé croissante : la plus petite clé est la moins
+    /// récemment utilisée. Si `map` contient plus de `capacity` entrées,
+    /// seules les plus grandes clés sont gardées.
+    ///
+    /// # Panics
+    ///
+    /// Panique si `capacity` vaut 0, comme [`Cache::new`].
+    ///
+    /// # Examples
+    /// ```
+    /// use lru_cache::Cache;
+    /// use lru_cache::cache::traits::CacheIterate;
+    /// use std::collections::HashMap;
+    ///
+    /// let map = HashMap::from([(3, "C"), (1, "A"), (2, "B")]);
+    /// let cache = Cache::from_map(2, map);
+    /// assert_eq!(cache.iter().map(|(key, _)| *key).collect::<Vec<_>>(), [2, 3]);
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn from_map(capacity: usize, map: std::collections::HashMap<K, V>) -> Self
+    where
+        K: Ord,
+    {
+        let mut entries: Vec<_> = map.into_iter().collect();
+        entries.sort_unstable_by(|(left, _), (right, _)| left.cmp(right));
+        let mut cache = Cache::new(capacity);
+        cache.put_many(entries);
+        cache
+    }
+
+    /// Crée un cache dont la capacité est un poids total maximal
+    ///
+    /// Le poids de chaque entrée est donné par `weigher` lors de son
+    /// insertion. Une insertion évince autant d'entrées que nécessaire, dans
+    /// l'ordre de la politique d'éviction, pour que le poids total reste au
+    /// plus `max_total_weight`. Une entrée plus lourde que `max_total_weight`
+    /// à elle seule est refusée : elle n'est pas insérée, aucune entrée n'est
+    /// évincée et l'ancienne valeur de la clé, s'il y en a une, est
+    /// supprimée. [`Cache::capacity`] retourne alors `max_total_weight`.
+    ///
+    /// # Examples
+    /// ```
+    /// use lru_cache::Cache;
+    /// use lru_cache::cache::traits::{CacheLen, CacheStorage};
+    ///
+    /// let mut cache = Cache::with_weigher(10, |_: &&str, value: &String| value.len());
+    /// cache.put("a", String::from("1234"));
+    /// cache.put("b", String::from("1234"));
+    /// cache.put("c", String::from("12345678"));
+    /// assert_eq!(cache.len(), 1);
+    /// assert_eq!(cache.total_weight(), 8);
+    /// ```
+    pub fn with_weigher<W: Weigher<K, V> + 'static>(max_total_weight: usize, weigher: W) -> Self {
+        let mut cache = Cache::new(max_total_weight);
+        cache.weighing = Some(Weighing {
+            weigher: Box::new(weigher),
+            total: 0,
+        });
+        cache
+    }
+
+    /// Crée un cache qui appelle `listener` pour chaque entrée qui le quitte
+    ///
+    /// Voir [`Cache::set_eviction_listener`].
+    pub fn with_eviction_listener(capacity: usize, listener: impl FnMut(K, V, RemovalCause) + Send + 'static) -> Self {
+        let mut cache = Cache::new(capacity);
+        cache.set_eviction_listener(listener);
+        cache
+    }
+
+    /// Crée un cache qui signale ses événements à `listener`
+    ///
+    /// Voir [`Cache::set_listener`].
+    pub fn with_listener<L: CacheEventListener<K> + Send + 'static>(capacity: usize, listener: L) -> Self {
+        let mut cache = Cache::new(capacity);
+        cache.set_listener(listener);
+        cache
+    }
+
+    /// Crée un cache dont les décisions d'expiration utilisent `clock`
+    pub fn with_clock<C: Clock + 'static>(capacity: usize, clock: C) -> Self {
+        let mut cache = Cache::new(capacity);
+        cache.clock = Arc::new(clock);
+        cache
+    }
+
+    /// Crée un cache qui relève les dates de ses entrées
+    ///
+    /// Voir [`Cache::track_metadata`].
+    pub fn with_metadata(capacity: usize) -> Self {
+        let mut cache = Cache::new(capacity);
+        cache.track_metadata();
+        cache
+    }
+
+    /// Crée un cache avec une durée de vie par défaut (voir
+    /// [`Cache::with_ttl`]) mesurée par `clock`
+    pub fn with_ttl_and_clock<C: Clock + 'static>(capacity: usize, ttl: Duration, mode: TtlMode, clock: C) -> Self {
+        let mut cache = Cache::with_ttl(capacity, ttl, mode);
+        cache.clock = Arc::new(clock);
+        cache
+    }
+
+    /// Crée un cache utilisant la politique d'éviction intégrée `policy`
+    ///
+    /// # Examples
+    /// ```
+    /// # #![allow(deprecated)]
+    /// use lru_cache::Cache;
+    /// use lru_cache::cache::policy::Policy;
+    /// use lru_cache::cache::traits::CacheStorage;
+    ///
+    /// let mut cache = Cache::with_policy(2, Policy::TwoChoice { seed: 42 });
+    /// cache.put("A", 1);
+    /// cache.put("B", 2);
+    /// cache.get(&"A");
+    /// cache.get(&"A");
+    /// cache.get(&"B");
+    /// cache.put("C", 3);
+    /// assert_eq!(cache.get(&"B"), None);
+    /// ```
+    #[deprecated(note = "utiliser `Cache::with_eviction_policy` avec `policy::Mru` ou `policy::TwoChoice`")]
+    #[allow(deprecated)]
+    pub fn with_policy(capacity: usize, policy: Policy) -> Self {
+        let mut cache = Cache::new(capacity);
+        cache.set_builtin_policy(policy);
+        cache
+    }
+
+    /// Crée un cache dont toutes les entrées expirent après la durée `ttl`
+    ///
+    /// Avec [`TtlMode::AfterWrite`] l'échéance est fixée à l'insertion ; avec
+    /// [`TtlMode::AfterAccess`] elle est repoussée à chaque `get`. Dans les deux
+    /// modes, un `put` sur une clé existante remet l'échéance à zéro. Lorsque le
+    /// cache est plein, une entrée expirée est évincée de préférence à l'entrée
+    /// la moins récemment utilisée, la plus anciennement échue d'abord ; elle
+    /// est trouvée dans la roue des échéances sans parcourir le cache.
+    ///
+    /// # Examples
+    /// ```
+    /// use lru_cache::Cache;
+    /// use lru_cache::cache::traits::CacheStorage;
+    /// use lru_cache::cache::ttl::TtlMode;
+    /// use core::time::Duration;
+    ///
+    /// let mut cache = Cache::with_ttl(2, Duration::from_secs(60), TtlMode::AfterAccess);
+    /// cache.put("key", 1);
+    /// assert_eq!(cache.get(&"key"), Some(&1));
+    /// ```
+    pub fn with_ttl(capacity: usize, ttl: Duration, mode: TtlMode) -> Self {
+        let mut cache = Cache::new(capacity);
+        cache.ttl = Some((ttl, mode));
+        cache
+    }
+
+    /// Crée un cache dont les entrées expirent quand elles n'ont pas été
+    /// lues depuis la durée `max_idle`
+    ///
+    /// L'échéance d'inactivité est fixée par `put` et repoussée par chaque
+    /// lecture qui marque l'entrée comme utilisée (`get`, `get_shared`
+    /// une fois appliquée...), jamais par `peek`. Elle s'ajoute à la durée
+    /// de vie ([`Cache::with_ttl`], [`Cache::put_with_ttl`]) sans la
+    /// remplacer : une entrée expire à la première des deux échéances
+    /// atteinte, si bien qu'une entrée lue sans cesse expire tout de même
+    /// au terme de sa durée de vie. Comme pour la durée de vie, une entrée
+    /// inactive est considérée comme absente et évincée de préférence.
+    ///
+    /// # Examples
+    /// ```
+    /// use lru_cache::Cache;
+    /// use lru_cache::cache::traits::CacheStorage;
+    /// use core::time::Duration;
+    ///
+    /// let mut cache = Cache::with_max_idle(2, Duration::from_secs(300));
+    /// cache.put("key", 1);
+    /// assert_eq!(cache.get(&"key"), Some(&1));
+    /// ```
+    pub fn with_max_idle(capacity: usize, max_idle: Duration) -> Self {
+        let mut cache = Cache::new(capacity);
+        cache.max_idle = Some(max_idle);
+        cache
+    }
+
+    /// Retourne une configuration à compléter, voir [`CacheBuilder`]
+    pub fn builder() -> CacheBuilder<K, V> {
+        CacheBuilder::new()
+    }
+
+    /// Crée un cache qui se souvient, sans leur valeur, des `ghost_capacity`
+    /// dernières clés évincées pour faire de la place
+    ///
+    /// Une lecture manquée d'une clé de cette liste fantôme, qu'un cache plus
+    /// grand d'au plus `ghost_capacity` entrées aurait trouvée, est comptée
+    /// dans [`CacheStats::ghost_hits`] et retire la clé de la liste ; une
+    /// insertion l'en retire aussi. Les clés sont copiées, d'où `K: Clone`.
+    /// Les lectures sans clé ([`Cache::get_with_hash`]) et les lectures
+    /// partagées ([`Cache::get_shared`]) ne consultent pas la liste.
+    ///
+    /// # Examples
+    /// ```
+    /// use lru_cache::Cache;
+    /// use lru_cache::cache::traits::CacheStorage;
+    ///
+    /// let mut cache = Cache::with_ghost_capacity(1, 4);
+    /// cache.put("a", 1);
+    /// cache.put("b", 2);
+    /// assert_eq!(cache.ghost_len(), 1);
+    /// assert_eq!(cache.get(&"a"), None);
+    /// assert_eq!(cache.stats().ghost_hits, 1);
+    /// assert_eq!(cache.ghost_len(), 0);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panique si `capacity` vaut 0, comme [`Cache::new`].
+    pub fn with_ghost_capacity(capacity: usize, ghost_capacity: usize) -> Self
+    where
+        K: Clone,
+    {
+        let mut cache = Cache::new(capacity);
+        cache.ghosts = (ghost_capacity > 0).then(|| Ghosts {
+            keys: NodeList::new(),
+            capacity: ghost_capacity,
+            clone: K::clone,
+        });
+        cache
+    }
+
+    /// Ouvre une transaction : les écritures, suppressions et lectures faites
+    /// par le [`Transaction`] retourné sont validées par
+    /// [`Transaction::commit`], ou défaites par [`Transaction::rollback`] et
+    /// quand il est abandonné sans être validé
+    ///
+    /// Défaire une transaction rétablit l'état exact du cache à son
+    /// ouverture : entrées évincées ou supprimées, valeurs remplacées,
+    /// ordre d'utilisation, échéances et compteurs de [`Cache::stats`]. Les
+    /// entrées retirées restent dans le journal de la transaction et ne sont
+    /// passées à l'écouteur de [`Cache::set_eviction_listener`] qu'à la
+    /// validation ; l'observateur de [`Cache::set_listener`], l'historique
+    /// des évictions, la liste fantôme, le journal des opérations et le
+    /// suivi des modifications voient en revanche les opérations au moment
+    /// où elles ont lieu. Le [`Transaction`] emprunte le cache : les
+    /// transactions imbriquées sont refusées à la compilation.
+    ///
+    /// # Examples
+    /// ```
+    /// use lru_cache::Cache;
+    /// use lru_cache::cache::traits::{CacheIterate, CacheStorage};
+    ///
+    /// let mut cache = Cache::new(2);
+    /// cache.put("a", 1);
+    /// cache.put("b", 2);
+    ///
+    /// let mut transaction = cache.transaction();
+    /// transaction.put("c", 3);
+    /// transaction.put("b", 20);
+    /// transaction.rollback();
+    /// assert_eq!(cache.iter().collect::<Vec<_>>(), vec![(&"a", &1), (&"b", &2)]);
+    ///
+    /// let mut transaction = cache.transaction();
+    /// transaction.put("c", 3);
+    /// transaction.commit();
+    /// assert_eq!(cache.peek(&"a"), None);
+    /// ```
+    pub fn transaction(&mut self) -> Transaction<'_, K, V> {
+        Transaction::new(self)
+    }
+
+    /// Crée un nouveau cache en réservant immédiatement la place pour
+    /// `capacity` entrées
+    pub fn with_preallocated(capacity: usize) -> Self {
+        let mut cache = Cache::new(capacity);
+        cache.reserve(capacity);
+        cache
+    }
+
+}
+
+impl<K: Eq + Hash, V, P: EvictionPolicy<K>> Cache<K, V, P> {
+    /// Crée un cache dont l'entrée évincée est choisie par `policy`
+    ///
+    /// # Examples
+    /// ```
+    /// use lru_cache::Cache;
+    /// use lru_cache::cache::policy::Fifo;
+    /// use lru_cache::cache::traits::CacheStorage;
+    ///
+    /// let mut cache = Cache::with_eviction_policy(2, Fifo::new());
+    /// cache.put("A", 1);
+    /// cache.put("B", 2);
+    /// cache.get(&"A");
+    /// cache.put("C", 3);
+    /// assert_eq!(cache.get(&"A"), None);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panique si `capacity` vaut 0, comme [`Cache::new`].
+    pub fn with_eviction_policy(capacity: usize, policy: P) -> Self {
+        Cache::from_policy(non_zero(capacity), policy)
+    }
+
+    /// Crée le cache décrit par `builder`, avec la capacité qui y a été
+    /// fixée et la politique `eviction`
+    pub(super) fn from_builder(builder: CacheBuilder<K, V>, capacity: NonZeroUsize, eviction: P) -> Self {
+        let mut cache = Cache::from_policy(capacity, eviction);
+        cache.set_builtin_policy(builder.policy);
+        cache.ttl = builder.ttl;
+        cache.max_idle = builder.max_idle;
+        cache.low_water = builder.low_water;
+        if let Some(clock) = builder.clock {
+            cache.clock = clock;
+        }
+        cache.weighing = builder.weigher.map(|weigher| Weighing { weigher, total: 0 });
+        cache.listener = builder.eviction_listener.map(Listener);
+        cache.events.listener = builder.listener;
+        cache.ttl_granularity = builder.ttl_granularity;
+        if let Some((fraction, seed)) = builder.ttl_jitter {
+            cache.set_ttl_jitter(fraction, seed);
+        }
+        if let Some(fraction) = builder.refresh_ahead {
+            cache.set_refresh_ahead(fraction);
+        }
+        if builder.metadata {
+            cache.track_metadata();
+        }
+        if builder.preallocate {
+            cache.reserve(capacity.get());
+        }
+        cache
+    }
+
+    /// Remplace le choix de `eviction` par la politique intégrée
+    /// dépréciée `policy`, sauf si c'est `Policy::Lru`
+    #[allow(deprecated)]
+    fn set_builtin_policy(&mut self, policy: Policy) {
+        self.hit_order = None;
+        if let Policy::TwoChoice { seed } = policy {
+            self.rng = XorShift64::new(seed);
+            let mut order = HitOrder::new();
+            for index in core::iter::successors(self.entries.head(), |&index| self.entries.next(index)) {
+                order.set(index, self.node(index).meta.hits);
+            }
+            self.hit_order = Some(order);
+        }
+        self.policy = policy;
+    }
+
+    #[allow(deprecated)]
+    fn from_policy(capacity: NonZeroUsize, policy: P) -> Self {
         Cache {
             capacity,
-            storage: HashMap::with_capacity(capacity),
-            order: Vec::with_capacity(capacity),
+            low_water: None,
+            insertions: 0,
+            entries: NodeList::new(),
+            ttl: None,
+            max_idle: None,
+            expiring: 0,
+            wheel: None,
+            ttl_granularity: DEFAULT_TTL_GRANULARITY,
+            jitter: None,
+            refresh_ahead: None,
+            history: None,
+            ghosts: None,
+            transaction: None,
+            pinned: 0,
+            log: None,
+            policy: Policy::Lru,
+            hit_order: None,
+            rng: XorShift64::new(0),
+            clock: Arc::new(DefaultClock::default()),
+            weighing: None,
+            listener: None,
+            events: Events {
+                stats: CacheStats::default(),
+                listener: None,
+            },
+            shared: SharedReads::default(),
+            #[cfg(feature = "std")]
+            changes: None,
+            #[cfg(feature = "std")]
+            file: RefCell::new(None),
+            metadata: false,
+            eviction: policy,
         }
     }
 
-    fn update_order(&mut self, key: &K) {
-        if let Some(pos) = self.order.iter().position(|k| k == key) {
-            self.order.remove(pos);
-            self.order.push(key.clone());
+    /// Retourne le poids total des entrées présentes, ou leur nombre si le
+    /// cache n'est pas pondéré
+    pub fn total_weight(&self) -> usize {
+        match &self.weighing {
+            Some(weighing) => weighing.total,
+            None => self.len(),
         }
     }
-}
 
-impl<K: Clone + Eq + Hash, V> CacheStorage<K, V> for Cache<K, V> {
-    fn get(&mut self, key: &K) -> Option<&V> {
-        if self.storage.contains_key(key) {
-            self.update_order(key);
-            self.storage.get(key)
-        } else {
-            None
+    /// Appelle `listener` avec la clé, la valeur et la cause de chaque
+    /// entrée qui quitte le cache, en remplaçant l'écouteur précédent
+    ///
+    /// L'écouteur est appelé de façon synchrone, une fois l'entrée retirée :
+    /// lors d'une éviction ([`RemovalCause::Capacity`]), quand une entrée
+    /// expirée est rencontrée ou purgée ([`RemovalCause::Expired`]), par
+    /// [`Cache::invalidate`] ([`RemovalCause::Explicit`]), par
+    /// [`Cache::clear`] ([`RemovalCause::Cleared`]) et pour l'ancienne
+    /// valeur d'une clé remplacée par `put` ([`RemovalCause::Replaced`]).
+    /// `remove` et `remove_by_id` rendent l'entrée à l'appelant et ne
+    /// l'appellent pas, sauf pour une entrée expirée qu'ils rencontrent.
+    ///
+    /// L'écouteur ne reçoit pas le cache et ne doit pas y accéder par un
+    /// autre chemin : il s'exécute pendant une opération en cours, si bien
+    /// qu'un `Rc<RefCell<_>>` paniquerait et qu'un
+    /// [`ConcurrentCache`](super::concurrent::ConcurrentCache) resterait
+    /// bloqué sur son propre verrou.
+    ///
+    /// # Examples
+    /// ```
+    /// use lru_cache::Cache;
+    /// use lru_cache::cache::listener::RemovalCause;
+    /// use lru_cache::cache::traits::CacheStorage;
+    /// use std::sync::mpsc;
+    ///
+    /// let (sender, evicted) = mpsc::channel();
+    /// let mut cache = Cache::new(1);
+    /// cache.set_eviction_listener(move |key, value, cause| sender.send((key, value, cause)).unwrap());
+    /// cache.put("A", 1);
+    /// cache.put("B", 2);
+    /// assert_eq!(evicted.try_recv(), Ok(("A", 1, RemovalCause::Capacity)));
+    /// ```
+    pub fn set_eviction_listener(&mut self, listener: impl FnMut(K, V, RemovalCause) + Send + 'static) {
+        self.listener = Some(Listener(Box::new(listener)));
+    }
+
+    /// Signale les événements du cache à `listener`, en remplaçant
+    /// l'observateur précédent
+    ///
+    /// L'observateur est appelé de façon synchrone avec la clé concernée,
+    /// dans l'ordre des événements : une insertion qui évince une entrée
+    /// signale l'éviction avant l'insertion. Les lectures sont signalées dans
+    /// les cas comptés par [`Cache::stats`], qui ne dépend pas de cet
+    /// observateur. Les suppressions explicites et les vidages ne sont
+    /// signalés qu'à l'écouteur de [`Cache::set_eviction_listener`].
+    ///
+    /// # Examples
+    /// ```
+    /// use lru_cache::Cache;
+    /// use lru_cache::cache::listener::CacheEventListener;
+    /// use lru_cache::cache::traits::CacheStorage;
+    /// use std::sync::mpsc;
+    ///
+    /// struct Misses(mpsc::Sender<&'static str>);
+    ///
+    /// impl CacheEventListener<&'static str> for Misses {
+    ///     fn on_miss(&mut self, key: &&'static str) {
+    ///         self.0.send(key).unwrap();
+    ///     }
+    /// }
+    ///
+    /// let (sender, misses) = mpsc::channel();
+    /// let mut cache = Cache::<_, i32>::with_listener(2, Misses(sender));
+    /// cache.get(&"A");
+    /// assert_eq!(misses.try_recv(), Ok("A"));
+    /// ```
+    pub fn set_listener<L: CacheEventListener<K> + Send + 'static>(&mut self, listener: L) {
+        self.events.listener = Some(Box::new(listener));
+    }
+
+    /// Commence à relever la date d'insertion et de dernière lecture de
+    /// chaque entrée, sur l'horloge du cache
+    ///
+    /// Les dates sont lues avec [`Cache::metadata`] et
+    /// [`Cache::iter_metadata`]. Une lecture est relevée quand elle est
+    /// comptée dans [`Cache::stats`] (`get`, `get_id`, `get_by_id`...),
+    /// jamais par `peek` ; une lecture de [`Cache::get_shared`] est datée du
+    /// moment où elle est appliquée. Les entrées déjà présentes sont datées
+    /// de l'appel. Le suivi coûte une allocation par entrée.
+    ///
+    /// # Examples
+    /// ```
+    /// use lru_cache::Cache;
+    /// use lru_cache::cache::traits::CacheStorage;
+    ///
+    /// let mut cache = Cache::with_metadata(2);
+    /// cache.put("a", 1);
+    /// cache.get(&"a");
+    /// let metadata = cache.metadata(&"a").unwrap();
+    /// assert_eq!(metadata.access_count, 1);
+    /// assert!(metadata.last_accessed_at >= metadata.created_at);
+    /// ```
+    pub fn track_metadata(&mut self) {
+        if self.metadata {
+            return;
+        }
+        self.metadata = true;
+        let times = self.times_now();
+        let mut next = self.entries.head();
+        while let Some(index) = next {
+            next = self.entries.next(index);
+            self.node_mut(index).meta.times = Some(Box::new(times));
         }
     }
 
-    fn put(&mut self, key: K, value: V) {
-        if self.storage.contains_key(&key) {
-            self.storage.insert(key.clone(), value);
-            self.update_order(&key);
+    /// Retourne les dates de l'entrée associée à `key`, sans la marquer
+    /// comme utilisée
+    ///
+    /// Retourne `None` si la clé est absente ou expirée, ou si
+    /// [`Cache::track_metadata`] n'a pas été appelée.
+    pub fn metadata(&self, key: &K) -> Option<EntryMetadata> {
+        let index = self.find(key)?;
+        if self.is_expired(index, self.clock.now()) {
+            return None;
+        }
+        entry_metadata(&self.node(index).meta)
+    }
+
+    /// Parcourt les dates des entrées, de la prochaine évincée à la plus
+    /// récente, entrées expirées comprises comme pour `iter`
+    ///
+    /// Vide si [`Cache::track_metadata`] n'a pas été appelée.
+    pub fn iter_metadata(&self) -> impl Iterator<Item = (&K, EntryMetadata)> + '_ {
+        core::iter::successors(self.entries.head(), |&index| self.entries.next(index)).filter_map(|index| {
+            let node = self.node(index);
+            Some((&node.key, entry_metadata(&node.meta)?))
+        })
+    }
+
+    /// Compte les entrées selon le temps écoulé depuis leur dernière lecture
+    ///
+    /// `buckets` donne des bornes croissantes ; le résultat a une case de
+    /// plus : la case `i` compte les entrées inactives depuis au moins
+    /// `buckets[i - 1]` et moins de `buckets[i]`, la dernière celles
+    /// inactives depuis au moins la dernière borne. Les entrées expirées ou
+    /// négatives sont ignorées et l'ordre d'utilisation n'est pas modifié.
+    /// Toutes les cases valent 0 si [`Cache::track_metadata`] n'a pas été
+    /// appelée.
+    ///
+    /// # Panics
+    ///
+    /// Panique si `buckets` n'est pas trié par ordre croissant.
+    ///
+    /// # Examples
+    /// ```
+    /// use lru_cache::Cache;
+    /// use lru_cache::cache::clock::MockClock;
+    /// use lru_cache::cache::traits::CacheStorage;
+    /// use std::time::Duration;
+    ///
+    /// let clock = MockClock::new();
+    /// let mut cache = Cache::with_clock(3, clock.clone());
+    /// cache.track_metadata();
+    /// cache.put("old", 1);
+    /// clock.advance(Duration::from_secs(120));
+    /// cache.put("new", 2);
+    /// let buckets = [Duration::from_secs(60)];
+    /// assert_eq!(cache.age_histogram(&buckets), vec![1, 1]);
+    /// ```
+    pub fn age_histogram(&self, buckets: &[Duration]) -> Vec<usize> {
+        assert!(
+            buckets.windows(2).all(|pair| pair[0] <= pair[1]),
+            "les bornes de l'histogramme doivent être croissantes"
+        );
+        let mut counts = alloc::vec![0; buckets.len() + 1];
+        for idle in self.idle_times() {
+            counts[buckets.partition_point(|&bound| bound <= idle)] += 1;
+        }
+        counts
+    }
+
+    /// Retourne le temps d'inactivité au centile `p` (de 0 à 100) des
+    /// entrées, par la méthode du rang le plus proche
+    ///
+    /// `percentile_idle(90.0)` est la plus petite durée telle qu'au moins
+    /// 90 % des entrées ont été lues depuis ; `0.0` donne l'entrée la plus
+    /// récemment lue, `100.0` la plus ancienne. Les entrées sont celles
+    /// comptées par [`Cache::age_histogram`]. Retourne `None` si aucune
+    /// n'est comptée, notamment si [`Cache::track_metadata`] n'a pas été
+    /// appelée.
+    ///
+    /// # Panics
+    ///
+    /// Panique si `p` n'est pas compris entre 0 et 100.
+    pub fn percentile_idle(&self, p: f64) -> Option<Duration> {
+        assert!((0.0..=100.0).contains(&p), "centile hors de 0..=100 : {}", p);
+        let mut idle: Vec<_> = self.idle_times().collect();
+        if idle.is_empty() {
+            return None;
+        }
+        idle.sort_unstable();
+        // `f64::ceil` n'existe pas sans std
+        let exact = p / 100.0 * idle.len() as f64;
+        let rank = exact as usize + usize::from((exact as usize as f64) < exact);
+        Some(idle[rank.clamp(1, idle.len()) - 1])
+    }
+
+    /// Temps écoulé depuis la dernière lecture des entrées non expirées et
+    /// non négatives qui ont des dates
+    fn idle_times(&self) -> impl Iterator<Item = Duration> + '_ {
+        let now = self.clock.now();
+        core::iter::successors(self.entries.head(), |&index| self.entries.next(index)).filter_map(move |index| {
+            let node = self.node(index);
+            if node.value.is_none() || self.is_expired(index, now) {
+                return None;
+            }
+            let times = node.meta.times.as_deref()?;
+            Some(now.saturating_duration_since(times.accessed_at))
+        })
+    }
+
+    /// Retourne la position de `key` dans l'ordre d'utilisation : 0 pour
+    /// l'entrée la moins récemment utilisée, `len() - 1` pour la plus
+    /// récente, ou `None` si la clé est absente ou expirée
+    ///
+    /// La position est celle de `iter`, qui compte aussi les entrées
+    /// expirées pas encore retirées. C'est le rang d'éviction avec la
+    /// politique par défaut tant qu'aucune entrée n'est épinglée ; les
+    /// lectures de [`Cache::get_shared`] pas encore appliquées n'y
+    /// figurent pas. L'ordre n'est pas modifié. Coût en O(n) : la liste est
+    /// parcourue depuis l'entrée jusqu'à sa tête.
+    ///
+    /// # Examples
+    /// ```
+    /// use lru_cache::Cache;
+    /// use lru_cache::cache::traits::CacheStorage;
+    ///
+    /// let mut cache = Cache::new(3);
+    /// cache.put_many([("a", 1), ("b", 2), ("c", 3)]);
+    /// cache.get(&"a");
+    /// assert_eq!(cache.recency_rank(&"b"), Some(0));
+    /// assert_eq!(cache.recency_rank(&"a"), Some(2));
+    /// assert_eq!(cache.nth_lru(1), Some((&"c", &3)));
+    /// ```
+    pub fn recency_rank(&self, key: &K) -> Option<usize> {
+        let index = self.find(key)?;
+        if self.is_expired(index, self.clock.now()) {
+            return None;
+        }
+        Some(core::iter::successors(self.entries.prev(index), |&index| self.entries.prev(index)).count())
+    }
+
+    /// Retourne l'entrée au rang `n` de l'ordre d'utilisation, 0 étant la
+    /// moins récemment utilisée, sans modifier l'ordre
+    ///
+    /// Inverse de [`Cache::recency_rank`] ; comme `iter`, les entrées
+    /// expirées pas encore retirées comptent. Coût en O(min(n, len - n)) :
+    /// la liste est parcourue depuis l'extrémité la plus proche.
+    pub fn nth_lru(&self, n: usize) -> Option<(&K, &V)> {
+        let len = self.len();
+        if n >= len {
+            return None;
+        }
+        let index = if n < len / 2 {
+            core::iter::successors(self.entries.head(), |&index| self.entries.next(index)).nth(n)
         } else {
-            if self.storage.len() >= self.capacity {
-                if let Some(lru_key) = self.order.first().cloned() {
-                    self.storage.remove(&lru_key);
-                    self.order.remove(0);
-                }
+            core::iter::successors(self.entries.tail(), |&index| self.entries.prev(index)).nth(len - 1 - n)
+        }?;
+        let node = self.node(index);
+        Some((&node.key, node.value.as_ref()?))
+    }
+
+    /// Parcourt les entrées de la première insérée à la plus récemment
+    /// insérée, quel que soit l'ordre des lectures
+    ///
+    /// Remplacer la valeur d'une clé présente ne change pas son rang ; une
+    /// clé supprimée ou évincée puis réinsérée passe en dernier. Les
+    /// entrées sont celles de `iter`, expirées comprises. Coût en
+    /// O(n log n) : les entrées sont triées à l'appel.
+    ///
+    /// # Examples
+    /// ```
+    /// use lru_cache::Cache;
+    /// use lru_cache::cache::traits::{CacheIterate, CacheStorage};
+    ///
+    /// let mut cache = Cache::new(3);
+    /// cache.put_many([("a", 1), ("b", 2), ("c", 3)]);
+    /// cache.get(&"a");
+    /// cache.put("b", 20);
+    /// assert_eq!(cache.iter().map(|(key, _)| *key).collect::<Vec<_>>(), ["c", "a", "b"]);
+    /// assert_eq!(cache.iter_insertion_order().map(|(key, _)| *key).collect::<Vec<_>>(), ["a", "b", "c"]);
+    /// ```
+    pub fn iter_insertion_order(&self) -> impl Iterator<Item = (&K, &V)> + '_ {
+        let mut indices: Vec<_> = core::iter::successors(self.entries.head(), |&index| self.entries.next(index))
+            .filter(|&index| self.node(index).value.is_some())
+            .collect();
+        indices.sort_unstable_by_key(|&index| self.node(index).meta.inserted);
+        indices.into_iter().map(|index| (&self.node(index).key, self.value(index)))
+    }
+
+    /// Parcourt les entrées de la moins récemment utilisée à la plus
+    /// récente, avec un accès mutable aux valeurs
+    ///
+    /// Le parcours ne compte pas comme une utilisation : l'ordre d'éviction,
+    /// les statistiques et les échéances de durée de vie et d'inactivité
+    /// restent inchangés, comme avec `peek`. Les entrées sont celles de
+    /// `iter`, expirées comprises. Les valeurs modifiées ne sont pas
+    /// repesées et gardent leur version ; chaque entrée parcourue est
+    /// considérée comme modifiée par [`Cache::track_changes`].
+    ///
+    /// # Examples
+    /// ```
+    /// use lru_cache::Cache;
+    /// use lru_cache::cache::traits::{CacheIterate, CacheStorage};
+    ///
+    /// let mut cache = Cache::new(3);
+    /// cache.put_many([("a", 1), ("b", 2), ("c", 3)]);
+    /// cache.get(&"a");
+    /// for (_, value) in cache.iter_mut() {
+    ///     *value *= 10;
+    /// }
+    /// let entries: Vec<_> = cache.iter().map(|(key, value)| (*key, *value)).collect();
+    /// assert_eq!(entries, [("b", 20), ("c", 30), ("a", 10)]);
+    /// ```
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = (&K, &mut V)> + '_ {
+        self.apply_shared_reads();
+        #[cfg(feature = "std")]
+        let mut changes = self.changes.as_mut();
+        self.entries.iter_mut().filter_map(move |(key, value)| {
+            let value = value.as_mut()?;
+            #[cfg(feature = "std")]
+            if let Some(changes) = changes.as_mut() {
+                changes.mark(key);
             }
-            self.storage.insert(key.clone(), value);
-            self.order.push(key);
+            Some((key, value))
+        })
+    }
+
+    fn next_insertion(&mut self) -> u64 {
+        self.insertions += 1;
+        self.insertions
+    }
+
+    /// Retourne le nombre de lectures comptées de l'entrée associée à
+    /// `key`, ou `None` si la clé est absente ou expirée
+    ///
+    /// Le compteur part de 0 à l'insertion de la clé, y compris quand elle
+    /// revient après une éviction ou une suppression ; remplacer la valeur
+    /// d'une clé présente le conserve. Il augmente avec chaque lecture
+    /// comptée dans [`Cache::stats`], jamais avec `peek`. Il est conservé
+    /// par [`Cache::snapshot`] et [`Cache::restore`], pas par les fichiers
+    /// de sauvegarde.
+    pub fn access_count(&self, key: &K) -> Option<u64> {
+        let index = self.find(key)?;
+        if self.is_expired(index, self.clock.now()) {
+            return None;
         }
+        Some(self.node(index).meta.hits)
     }
-}
 
-impl<K: Clone + Eq + Hash + Display + FromStr, V: Display + FromStr> PersistentStorage<K, V> for Cache<K, V> {
-    fn save_to_file(&self, path: &str) -> std::io::Result<()> {
-        let data: Vec<_> = self.order.iter()
-            .filter_map(|k| self.storage.get(k).map(|v| (k, v)))
+    /// Retourne les `n` entrées les plus lues, du plus grand nombre de
+    /// lectures au plus petit, la plus récemment utilisée d'abord à égalité
+    ///
+    /// Les entrées expirées sont ignorées. Voir [`Cache::access_count`].
+    ///
+    /// # Examples
+    /// ```
+    /// use lru_cache::Cache;
+    /// use lru_cache::cache::traits::CacheStorage;
+    ///
+    /// let mut cache = Cache::new(3);
+    /// cache.put_many([("a", 1), ("b", 2), ("c", 3)]);
+    /// cache.get(&"b");
+    /// cache.get(&"b");
+    /// cache.get(&"a");
+    /// assert_eq!(cache.hottest(2), vec![(&"b", 2), (&"a", 1)]);
+    /// ```
+    pub fn hottest(&self, n: usize) -> Vec<(&K, u64)> {
+        let now = self.clock.now();
+        let mut entries: Vec<_> = core::iter::successors(self.entries.tail(), |&index| self.entries.prev(index))
+            .filter(|&index| !self.is_expired(index, now) && self.node(index).value.is_some())
+            .map(|index| {
+                let node = self.node(index);
+                (&node.key, node.meta.hits)
+            })
             .collect();
-        crate::storage::file::FileStorage::save(path, self.capacity, &data)
+        // Tri stable : l'ordre du plus récent au plus ancien départage
+        entries.sort_by_key(|&(_, hits)| core::cmp::Reverse(hits));
+        entries.truncate(n);
+        entries
     }
 
-    fn load_from_file(path: &str, capacity: usize) -> std::io::Result<Self> {
-        let (_, data) = crate::storage::file::FileStorage::load(path)?;
-        let mut cache = Cache::new(capacity);
-        for (key, value) in data {
-            cache.put(key, value);
+    /// Estime la mémoire occupée par les entrées, en octets
+    ///
+    /// Additionne la taille de chaque clé et de chaque valeur selon
+    /// [`MemSize`], plus [`ENTRY_OVERHEAD`] par entrée pour l'ordre
+    /// d'utilisation et l'index. Les entrées expirées ou négatives encore
+    /// présentes sont comptées, elles occupent la mémoire jusqu'à leur retrait.
+    ///
+    /// # Examples
+    /// ```
+    /// use lru_cache::Cache;
+    /// use lru_cache::cache::traits::CacheStorage;
+    ///
+    /// let mut cache = Cache::new(2);
+    /// let empty = cache.estimated_memory();
+    /// cache.put(1u32, String::from("abc"));
+    /// assert!(cache.estimated_memory() > empty);
+    /// ```
+    pub fn estimated_memory(&self) -> usize
+    where
+        K: MemSize,
+        V: MemSize,
+    {
+        self.entries
+            .iter()
+            .map(|(key, value)| ENTRY_OVERHEAD + key.mem_size() + value.mem_size())
+            .sum()
+    }
+
+    /// Retourne les `n` entrées les plus lourdes selon [`MemSize`], de la
+    /// plus grande à la plus petite, la plus récemment utilisée d'abord à
+    /// égalité
+    ///
+    /// La taille donnée est celle de la clé et de la valeur, sans
+    /// [`ENTRY_OVERHEAD`]. Les entrées expirées sont ignorées, comme dans
+    /// [`Cache::hottest`].
+    pub fn heaviest(&self, n: usize) -> Vec<(&K, usize)>
+    where
+        K: MemSize,
+        V: MemSize,
+    {
+        let now = self.clock.now();
+        let mut entries: Vec<_> = core::iter::successors(self.entries.tail(), |&index| self.entries.prev(index))
+            .filter(|&index| !self.is_expired(index, now))
+            .filter_map(|index| {
+                let node = self.node(index);
+                let value = node.value.as_ref()?;
+                Some((&node.key, node.key.mem_size() + value.mem_size()))
+            })
+            .collect();
+        entries.sort_by_key(|&(_, size)| core::cmp::Reverse(size));
+        entries.truncate(n);
+        entries
+    }
+
+    fn times_now(&self) -> Times {
+        let now = self.clock.now();
+        Times {
+            created_at: now,
+            accessed_at: now,
         }
-        Ok(cache)
     }
-}
\ No newline at end of file
+
+    /// Commence à suivre les entrées modifiées, en considérant le contenu
+    /// actuel comme sauvegardé
+    ///
+    /// Chaque insertion, mise à jour ou suppression, y compris par éviction
+    /// ou expiration, marque ensuite la clé jusqu'au prochain
+    /// [`Cache::take_dirty`] ou [`Cache::save_incremental`]. Les clés
+    /// marquées sont copiées, d'où `K: Clone`. Rappeler cette méthode oublie
+    /// les modifications déjà suivies, par exemple après une sauvegarde
+    /// complète.
+    ///
+    /// # Examples
+    /// ```
+    /// use lru_cache::Cache;
+    /// use lru_cache::cache::traits::{CacheRemove, CacheStorage};
+    ///
+    /// let mut cache = Cache::new(2);
+    /// cache.put("a", 1);
+    /// cache.track_changes();
+    /// assert!(!cache.is_dirty());
+    ///
+    /// cache.put("b", 2);
+    /// cache.remove(&"a");
+    /// assert_eq!(cache.take_dirty(), vec![("a", None), ("b", Some(2))]);
+    /// assert!(!cache.is_dirty());
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn track_changes(&mut self)
+    where
+        K: Clone,
+    {
+        self.changes = Some(Changes {
+            keys: HashSet::new(),
+            clone: K::clone,
+        });
+    }
+
+    /// Indique si des entrées ont été modifiées depuis le début du suivi ou
+    /// la dernière sauvegarde incrémentale ; toujours faux sans
+    /// [`Cache::track_changes`]
+    #[cfg(feature = "std")]
+    pub fn is_dirty(&self) -> bool {
+        self.changes.as_ref().is_some_and(|changes| !changes.keys.is_empty())
+    }
+
+    /// Retourne les clés modifiées, présentes ou supprimées, dans un ordre
+    /// quelconque
+    #[cfg(feature = "std")]
+    pub fn dirty(&self) -> impl Iterator<Item = &K> + '_ {
+        self.changes.iter().flat_map(|changes| changes.keys.iter())
+    }
+
+    /// Retire les modifications suivies et les retourne : `None` pour une
+    /// clé supprimée, puis la valeur actuelle des entrées modifiées de la
+    /// moins récemment utilisée à la plus récente
+    ///
+    /// Appliquer le résultat dans l'ordre à une copie du cache à la dernière
+    /// sauvegarde lui redonne le contenu actuel.
+    #[cfg(feature = "std")]
+    pub fn take_dirty(&mut self) -> Vec<(K, Option<V>)>
+    where
+        V: Clone,
+    {
+        let present = self.changed_indices();
+        let Some(changes) = self.changes.as_mut() else {
+            return Vec::new();
+        };
+        let mut keys = core::mem::take(&mut changes.keys);
+        let updated: Vec<_> = present
+            .into_iter()
+            .filter_map(|index| {
+                let node = self.entries.node(index);
+                Some((keys.take(&node.key)?, node.value.clone()))
+            })
+            .collect();
+        keys.into_iter().map(|key| (key, None)).chain(updated).collect()
+    }
+
+    /// Emplacements des clés modifiées encore présentes, de la moins
+    /// récemment utilisée à la plus récente
+    #[cfg(feature = "std")]
+    fn changed_indices(&self) -> Vec<usize> {
+        let Some(changes) = &self.changes else {
+            return Vec::new();
+        };
+        let mut indices = Vec::new();
+        let mut next = self.entries.head();
+        while let Some(index) = next.filter(|_| indices.len() < changes.keys.len()) {
+            if changes.keys.contains(&self.node(index).key) {
+                indices.push(index);
+            }
+            next = self.entries.next(index);
+        }
+        indices
+    }
+
+    /// Supprime une entrée en la passant à l'écouteur avec
+    /// [`RemovalCause::Explicit`], et indique si elle était présente
+    ///
+    /// Une entrée expirée est passée avec [`RemovalCause::Expired`] et
+    /// compte comme absente.
+    pub fn invalidate(&mut self, key: &K) -> bool {
+        let Some(index) = self.find_entry(key) else {
+            self.record("remove", key, "miss");
+            return false;
+        };
+        let expired = self.check_expired(index, self.clock.now());
+        self.record("remove", key, if expired { "miss" } else { "hit" });
+        self.discard(index, if expired { RemovalCause::Expired } else { RemovalCause::Explicit });
+        !expired
+    }
+
+    /// Supprime toutes les entrées, de la moins récemment utilisée à la plus
+    /// récente, en les passant à l'écouteur avec [`RemovalCause::Cleared`]
+    pub fn clear(&mut self) {
+        while let Some(index) = self.entries.head() {
+            self.discard(index, RemovalCause::Cleared);
+        }
+    }
+
+    /// Garde les `limit` derniers retraits d'entrées, consultables par
+    /// [`Cache::eviction_history`]
+    ///
+    /// Les retraits gardés sont ceux passés à l'écouteur de
+    /// [`Cache::set_eviction_listener`], avec leur cause et l'instant selon
+    /// l'horloge du cache, mais sans la valeur : seule une copie de la clé
+    /// est conservée, d'où `K: Clone`. Au-delà de `limit` retraits, les plus
+    /// anciens sont oubliés. Rappeler cette méthode vide l'historique ;
+    /// `limit` nul le désactive. Sans historique, aucun retrait n'est copié.
+    ///
+    /// # Examples
+    /// ```
+    /// use lru_cache::Cache;
+    /// use lru_cache::cache::listener::RemovalCause;
+    /// use lru_cache::cache::traits::CacheStorage;
+    ///
+    /// let mut cache = Cache::new(1);
+    /// cache.enable_eviction_history(8);
+    /// cache.put("a", 1);
+    /// cache.put("b", 2);
+    /// cache.invalidate(&"b");
+    /// let causes: Vec<_> = cache.eviction_history().map(|record| (record.key, record.cause)).collect();
+    /// assert_eq!(causes, vec![("a", RemovalCause::Capacity), ("b", RemovalCause::Explicit)]);
+    /// ```
+    pub fn enable_eviction_history(&mut self, limit: usize)
+    where
+        K: Clone,
+    {
+        self.history = (limit > 0).then(|| EvictionHistory {
+            records: VecDeque::new(),
+            limit,
+            clone: K::clone,
+        });
+    }
+
+    /// Retourne les retraits gardés par l'historique, du plus ancien au plus
+    /// récent ; vide sans [`Cache::enable_eviction_history`]
+    pub fn eviction_history(&self) -> impl Iterator<Item = &EvictionRecord<K>> + '_ {
+        self.history.iter().flat_map(|history| history.records.iter())
+    }
+
+    /// Oublie les retraits gardés, sans désactiver l'historique
+    pub fn clear_eviction_history(&mut self) {
+        if let Some(history) = self.history.as_mut() {
+            history.records.clear();
+        }
+    }
+
+    /// Épingle l'entrée associée à `key` : elle ne sera plus évincée pour
+    /// faire de la place, et retourne `false` si la clé est absente
+    ///
+    /// Une entrée épinglée continue d'être promue par les lectures, de sorte
+    /// qu'une fois désépinglée elle retrouve la place que lui vaut son
+    /// utilisation. Elle peut toujours expirer ou être supprimée
+    /// explicitement. Si toutes les entrées sont épinglées, une insertion
+    /// réussit quand même et le cache dépasse temporairement sa capacité ;
+    /// un désépinglage l'y ramène.
+    ///
+    /// # Examples
+    /// ```
+    /// use lru_cache::Cache;
+    /// use lru_cache::cache::traits::CacheStorage;
+    ///
+    /// let mut cache = Cache::new(2);
+    /// cache.put("schema", 1);
+    /// assert!(cache.pin(&"schema"));
+    /// cache.put("a", 2);
+    /// cache.put("b", 3);
+    /// assert_eq!(cache.get(&"schema"), Some(&1));
+    /// assert_eq!(cache.get(&"a"), None);
+    /// ```
+    pub fn pin(&mut self, key: &K) -> bool {
+        let Some(index) = self.find_live(key) else {
+            return false;
+        };
+        let node = self.node_mut(index);
+        if !node.meta.pinned {
+            node.meta.pinned = true;
+            self.pinned += 1;
+        }
+        true
+    }
+
+    /// Retire l'épinglage de l'entrée associée à `key`, et retourne `false`
+    /// si la clé est absente ou n'était pas épinglée
+    ///
+    /// Si le cache dépassait sa capacité faute d'entrée à évincer, il évince
+    /// jusqu'à y revenir, l'entrée désépinglée comprise.
+    pub fn unpin(&mut self, key: &K) -> bool {
+        let Some(index) = self.find_live(key) else {
+            return false;
+        };
+        let node = self.node_mut(index);
+        if !node.meta.pinned {
+            return false;
+        }
+        node.meta.pinned = false;
+        self.pinned -= 1;
+        if self.total_weight() > self.capacity() {
+            self.shed(|cache| cache.total_weight() > cache.low_water());
+        }
+        true
+    }
+
+    /// Retourne le nombre d'entrées épinglées
+    pub fn pinned_len(&self) -> usize {
+        self.pinned
+    }
+
+    /// Retourne le nombre de clés de la liste fantôme, 0 sans
+    /// [`Cache::with_ghost_capacity`]
+    pub fn ghost_len(&self) -> usize {
+        self.ghosts.as_ref().map_or(0, |ghosts| ghosts.keys.len())
+    }
+
+    /// Consomme le cache et retourne un cache dont chaque valeur est
+    /// remplacée par `f(&key, value)`
+    ///
+    /// Le nouveau cache garde la capacité, les clés, l'ordre d'utilisation
+    /// (la prochaine entrée évincée est la même), les métadonnées des
+    /// entrées (échéances, épinglage, versions), la politique d'éviction, les
+    /// statistiques et le reste de la configuration. Seules l'écoute des
+    /// évictions et la pondération, définies sur le type des valeurs, ne
+    /// sont pas reprises : un cache pondéré compte de nouveau ses entrées,
+    /// et celles qui dépassent alors la capacité sont évincées dans l'ordre
+    /// d'éviction. Les entrées expirées sont retirées sans appeler
+    /// l'écouteur, et `f` est appelée une fois par valeur restante, dans un
+    /// ordre non spécifié.
+    ///
+    /// # Examples
+    /// ```
+    /// use lru_cache::Cache;
+    /// use lru_cache::cache::traits::CacheStorage;
+    ///
+    /// let mut cache = Cache::new(2);
+    /// cache.put("A", "1");
+    /// cache.put("B", "2");
+    /// cache.get(&"A");
+    /// let mut parsed = cache.map_values(|_, value| value.parse::<u32>().unwrap());
+    /// parsed.put("C", 3);
+    /// assert_eq!(parsed.get(&"A"), Some(&1));
+    /// assert_eq!(parsed.get(&"B"), None);
+    /// ```
+    pub fn map_values<U>(self, mut f: impl FnMut(&K, V) -> U) -> Cache<K, U, P> {
+        self.convert(|key, value| value.map(|value| f(key, value)))
+    }
+
+    /// Comme [`Cache::map_values`], avec une transformation qui peut
+    /// échouer
+    ///
+    /// `f` reçoit chaque valeur par référence. À la première erreur, la
+    /// transformation s'arrête et l'erreur est retournée avec le cache
+    /// d'origine, dont seules les entrées expirées ont été retirées ; les
+    /// valeurs déjà transformées sont abandonnées.
+    ///
+    /// # Errors
+    ///
+    /// Retourne la première erreur de `f` et le cache non transformé.
+    ///
+    /// # Examples
+    /// ```
+    /// use lru_cache::Cache;
+    /// use lru_cache::cache::traits::CacheStorage;
+    ///
+    /// let mut cache = Cache::new(2);
+    /// cache.put("A", "1");
+    /// cache.put("B", "deux");
+    /// let (error, mut cache) = cache.try_map_values(|_, value| value.parse::<u32>()).unwrap_err();
+    /// assert!(error.to_string().contains("invalid digit"));
+    /// assert_eq!(cache.get(&"B"), Some(&"deux"));
+    /// ```
+    // Le cache rendu en cas d'erreur n'est pas plus gros que celui du succès
+    #[allow(clippy::result_large_err)]
+    pub fn try_map_values<U, E>(mut self, mut f: impl FnMut(&K, &V) -> Result<U, E>) -> Result<Cache<K, U, P>, (E, Self)> {
+        self.forget_expired();
+        let mapped: Result<Vec<Option<U>>, E> = self
+            .entries
+            .nodes()
+            .map(|node| node.value.as_ref().map(|value| f(&node.key, value)).transpose())
+            .collect();
+        let mapped = match mapped {
+            Ok(mapped) => mapped,
+            Err(error) => return Err((error, self)),
+        };
+        // map_values parcourt les nœuds dans le même ordre que nodes
+        let mut mapped = mapped.into_iter();
+        Ok(self.convert(|_, _| mapped.next().expect("une valeur transformée par nœud")))
+    }
+
+    /// Retire les entrées expirées sans les signaler ni appeler l'écouteur
+    fn forget_expired(&mut self) {
+        if self.expiring == 0 {
+            return;
+        }
+        let now = self.clock.now();
+        let expired: Vec<usize> = core::iter::successors(self.entries.head(), |&index| self.entries.next(index))
+            .filter(|&index| self.is_expired(index, now))
+            .collect();
+        for index in expired {
+            self.remove_slot(index);
+        }
+    }
+
+    /// Construit le cache de valeurs `U` qui reprend la configuration et les
+    /// entrées de celui-ci, chaque valeur étant remplacée par `f`
+    fn convert<U>(mut self, f: impl FnMut(&K, Option<V>) -> Option<U>) -> Cache<K, U, P> {
+        self.commit_transaction();
+        self.apply_shared_reads();
+        self.forget_expired();
+        let Cache {
+            capacity,
+            low_water,
+            insertions,
+            entries,
+            ttl,
+            max_idle,
+            expiring,
+            wheel,
+            ttl_granularity,
+            jitter,
+            refresh_ahead,
+            history,
+            ghosts,
+            transaction: _,
+            pinned,
+            log,
+            policy,
+            hit_order,
+            rng,
+            clock,
+            weighing,
+            listener: _,
+            events,
+            shared,
+            #[cfg(feature = "std")]
+            changes,
+            #[cfg(feature = "std")]
+            file,
+            metadata,
+            eviction,
+        } = self;
+        let mut cache = Cache {
+            capacity,
+            low_water,
+            insertions,
+            entries: entries.map_values(f),
+            ttl,
+            max_idle,
+            expiring,
+            wheel,
+            ttl_granularity,
+            jitter,
+            refresh_ahead,
+            history,
+            ghosts,
+            transaction: None,
+            pinned,
+            log,
+            policy,
+            hit_order,
+            rng,
+            clock,
+            weighing: None,
+            listener: None,
+            events,
+            shared,
+            #[cfg(feature = "std")]
+            changes,
+            #[cfg(feature = "std")]
+            file,
+            metadata,
+            eviction,
+        };
+        if weighing.is_some() {
+            let indices: Vec<usize> = core::iter::successors(cache.entries.head(), |&index| cache.entries.next(index)).collect();
+            for index in indices {
+                cache.node_mut(index).meta.weight = 1;
+            }
+            cache.truncate(cache.capacity.get());
+        }
+        cache
+    }
+
+    /// Fige le cache en une vue en lecture seule, partageable entre
+    /// threads
+    ///
+    /// Les lectures faites par [`Cache::get_shared`] sont d'abord appliquées
+    /// à l'ordre d'utilisation. Voir [`FrozenCache`].
+    #[cfg(feature = "std")]
+    pub fn freeze(mut self) -> FrozenCache<K, V, P> {
+        self.apply_shared_reads();
+        let entries = core::mem::replace(&mut self.entries, NodeList::new());
+        let clock = Arc::clone(&self.clock);
+        FrozenCache::new(entries, clock, self)
+    }
+
+    /// Rend au cache figé par [`Cache::freeze`] la liste de ses entrées
+    #[cfg(feature = "std")]
+    pub(super) fn thaw(mut self, entries: Entries<K, V>) -> Self {
+        self.entries = entries;
+        self
+    }
+
+    /// Copie l'état du cache : capacité et entrées de la prochaine évincée
+    /// à la plus récente, avec leur épinglage et leur échéance
+    ///
+    /// Les entrées expirées ne sont pas copiées. Voir [`CacheSnapshot`].
+    pub fn snapshot(&self) -> CacheSnapshot<K, V>
+    where
+        K: Clone,
+        V: Clone,
+    {
+        let now = self.clock.now();
+        let entries = core::iter::successors(self.entries.head(), |&index| self.entries.next(index))
+            .filter(|&index| !self.is_expired(index, now))
+            .filter_map(|index| {
+                let node = self.node(index);
+                Some(SnapshotEntry {
+                    key: node.key.clone(),
+                    value: node.value.clone()?,
+                    pinned: node.meta.pinned,
+                    hits: node.meta.hits,
+                    ttl: node.meta.ttl,
+                    remaining: node.meta.expires_at.map(|deadline| deadline.saturating_duration_since(now)),
+                })
+            })
+            .collect();
+        CacheSnapshot {
+            capacity: self.capacity,
+            entries,
+        }
+    }
+
+    /// Précharge le cache avec `entries`, données de la moins récemment
+    /// utilisée à la plus récente, sans modifier les statistiques
+    ///
+    /// Les entrées sont insérées dans l'ordre, comme par des `put`
+    /// successifs : s'il y en a plus que la capacité, les premières sont
+    /// évincées au profit des suivantes et le cache garde les plus récentes,
+    /// dans l'ordre donné. Les entrées déjà présentes restent moins récentes
+    /// que les entrées préchargées et sont évincées en premier. Les
+    /// compteurs de [`Cache::stats`] ne changent pas ; les entrées évincées
+    /// sont tout de même passées à l'écouteur de
+    /// [`Cache::set_eviction_listener`].
+    ///
+    /// # Examples
+    /// ```
+    /// use lru_cache::Cache;
+    /// use lru_cache::cache::traits::CacheIterate;
+    ///
+    /// let mut cache = Cache::new(2);
+    /// cache.warm([("A", 1), ("B", 2), ("C", 3)]);
+    /// assert_eq!(cache.iter().map(|(key, _)| *key).collect::<Vec<_>>(), ["B", "C"]);
+    /// assert_eq!(cache.stats().insertions, 0);
+    /// ```
+    pub fn warm(&mut self, entries: impl IntoIterator<Item = (K, V)>) {
+        let stats = self.events.stats;
+        self.put_many(entries);
+        self.events.stats = stats;
+    }
+
+    /// Précharge le cache avec `keys`, données de la moins récemment
+    /// utilisée à la plus récente, en calculant leurs valeurs par `compute`
+    /// sur le pool de rayon
+    ///
+    /// Seul le calcul des valeurs est parallèle : les entrées sont ensuite
+    /// insérées d'un seul fil, dans l'ordre de `keys`, par
+    /// [`Cache::warm`]. Le cache obtenu est le même qu'avec
+    /// `cache.warm(keys.map(|key| { let value = compute(&key); (key, value) }))`,
+    /// quel que soit l'ordre dans lequel les valeurs ont été calculées.
+    ///
+    /// # Examples
+    /// ```
+    /// use lru_cache::Cache;
+    /// use lru_cache::cache::traits::CacheIterate;
+    ///
+    /// let mut cache = Cache::new(2);
+    /// cache.warm_par([1u64, 2, 3], |key| key * key);
+    /// assert_eq!(cache.iter().collect::<Vec<_>>(), [(&2, &4), (&3, &9)]);
+    /// ```
+    #[cfg(feature = "rayon")]
+    pub fn warm_par(&mut self, keys: impl IntoIterator<Item = K>, compute: impl Fn(&K) -> V + Sync)
+    where
+        K: Sync,
+        V: Send,
+    {
+        use rayon::prelude::*;
+
+        let keys: Vec<K> = keys.into_iter().collect();
+        let values: Vec<V> = keys.par_iter().map(&compute).collect();
+        self.warm(keys.into_iter().zip(values));
+    }
+
+    /// Consomme le cache et retourne ses entrées dans une `HashMap`
+    ///
+    /// Les entrées expirées sont ignorées et l'écouteur n'est pas appelé.
+    /// Pour garder l'ordre d'éviction, convertir le cache en
+    /// `Vec<(K, V)>`.
+    #[cfg(feature = "std")]
+    pub fn into_map(self) -> std::collections::HashMap<K, V> {
+        self.into_entries().into_iter().collect()
+    }
+
+    /// Retire toutes les entrées non expirées, de la moins récemment
+    /// utilisée à la plus récente, sans appeler l'écouteur
+    fn into_entries(mut self) -> Vec<(K, V)> {
+        self.apply_shared_reads();
+        let now = self.clock.now();
+        let mut entries = Vec::with_capacity(self.len());
+        while let Some(index) = self.entries.head() {
+            let expired = self.is_expired(index, now);
+            let node = self.entries.remove(index);
+            if let (false, Some(value)) = (expired, node.value) {
+                entries.push((node.key, value));
+            }
+        }
+        entries
+    }
+
+    /// Remplace le contenu et la capacité du cache par ceux de `snapshot`
+    ///
+    /// Le cache garde sa configuration et ses statistiques. Les entrées
+    /// présentes sont retirées comme par [`Cache::clear`] ; celles de la
+    /// copie sont réinsérées dans l'ordre, si bien que la prochaine entrée
+    /// évincée est la même que dans le cache copié. Les échéances reprennent
+    /// le temps restant au moment de la copie, mesuré sur l'horloge de ce
+    /// cache.
+    pub fn restore_into(&mut self, snapshot: CacheSnapshot<K, V>) {
+        let stats = self.events.stats;
+        self.clear();
+        self.set_capacity(snapshot.capacity);
+        let now = self.clock.now();
+        for entry in snapshot.entries {
+            let Some(index) = self.insert(entry.key, entry.value, None) else {
+                continue;
+            };
+            let expires_at = entry.remaining.and_then(|remaining| now.checked_add(remaining));
+            self.set_deadline(index, entry.ttl, expires_at);
+            if let Some(order) = self.hit_order.as_mut() {
+                order.set(index, entry.hits);
+            }
+            let node = self.node_mut(index);
+            node.meta.hits = entry.hits;
+            if entry.pinned {
+                node.meta.pinned = true;
+                self.pinned += 1;
+            }
+        }
+        self.events.stats = stats;
+    }
+
+    /// Retourne les compteurs d'activité du cache
+    ///
+    /// Une lecture par `get`, `get_id`, `get_by_id`,
+    /// [`Cache::get_or_insert_with`] ou [`Cache::get_shared`] compte comme un
+    /// succès ou un échec ; une
+    /// entrée expirée rencontrée compte comme un échec. `get_with` ne compte
+    /// que si [`GetOptions::record_stats`] est vrai. `peek` et
+    /// `contains_key` ne modifient pas le cache et ne comptent pas : une
+    /// sonde ne fausse donc pas le taux de succès.
+    ///
+    /// # Examples
+    /// ```
+    /// use lru_cache::Cache;
+    /// use lru_cache::cache::traits::CacheStorage;
+    ///
+    /// let mut cache = Cache::new(1);
+    /// cache.put("A", 1);
+    /// cache.get(&"A");
+    /// cache.get(&"B");
+    /// cache.put("B", 2);
+    /// let stats = cache.stats();
+    /// assert_eq!((stats.hits, stats.misses, stats.insertions, stats.evictions), (1, 1, 2, 1));
+    /// assert_eq!(stats.hit_ratio(), 0.5);
+    /// ```
+    pub fn stats(&self) -> CacheStats {
+        let mut stats = self.events.stats;
+        stats.hits += self.shared.hits.get();
+        stats.misses += self.shared.misses.get();
+        stats
+    }
+
+    /// Remet tous les compteurs d'activité à zéro
+    pub fn reset_stats(&mut self) {
+        self.events.stats = CacheStats::default();
+        self.shared.hits.set(0);
+        self.shared.misses.set(0);
+    }
+
+    /// Retourne les compteurs de [`Cache::stats`] et les jauges du cache au
+    /// format texte d'exposition de Prometheus, chaque nom de métrique
+    /// commençant par `prefix`
+    ///
+    /// Les compteurs (`<prefix>_hits_total`, `_misses_total`,
+    /// `_insertions_total`, `_updates_total`, `_evictions_total`,
+    /// `_expirations_total`) sont suivis des jauges `<prefix>_entries`,
+    /// `_capacity` et `_total_weight`. L'ordre est toujours le même, si bien
+    /// que deux expositions successives se comparent ligne à ligne.
+    ///
+    /// # Examples
+    /// ```
+    /// use lru_cache::Cache;
+    /// use lru_cache::cache::traits::CacheStorage;
+    ///
+    /// let mut cache = Cache::new(2);
+    /// cache.put("key", 1);
+    /// cache.get(&"key");
+    /// assert!(cache.metrics_text("myapp_cache").contains("\nmyapp_cache_hits_total 1\n"));
+    /// ```
+    pub fn metrics_text(&self, prefix: &str) -> String {
+        metrics::render(prefix, None, &self.metrics_snapshot())
+    }
+
+    /// Comme [`Cache::metrics_text`], chaque échantillon portant le label
+    /// `cache="<name>"` pour distinguer plusieurs caches d'un même processus
+    pub fn metrics_text_named(&self, prefix: &str, name: &str) -> String {
+        metrics::render(prefix, Some(name), &self.metrics_snapshot())
+    }
+
+    fn metrics_snapshot(&self) -> Snapshot {
+        Snapshot {
+            stats: self.events.stats,
+            len: self.len(),
+            capacity: self.capacity(),
+            total_weight: self.total_weight(),
+        }
+    }
+
+    /// Réserve de la place pour au moins `additional` entrées supplémentaires
+    pub fn reserve(&mut self, additional: usize) {
+        self.entries.reserve(additional);
+    }
+
+    /// Libère la mémoire inutilisée par les structures internes
+    ///
+    /// Les emplacements libérés par des suppressions sont compactés. Les
+    /// [`EntryId`] des entrées déplacées lors du compactage deviennent
+    /// invalides ; l'ordre d'utilisation n'est pas modifié.
+    pub fn shrink_to_fit(&mut self) {
+        self.apply_shared_reads();
+        self.entries.shrink_to_fit();
+    }
+
+    /// Retourne la capacité du cache, `usize::MAX` pour un cache créé par
+    /// [`Cache::unbounded`]
+    pub fn capacity(&self) -> usize {
+        self.capacity.get()
+    }
+
+    /// Indique si le cache n'a pas de limite de capacité
+    pub fn is_unbounded(&self) -> bool {
+        self.capacity == NonZeroUsize::MAX
+    }
+
+    /// Indique si la prochaine insertion d'une nouvelle clé évincera une
+    /// entrée ; toujours faux pour un cache sans limite
+    ///
+    /// Pour un cache pondéré, le cache est plein quand le poids total
+    /// atteint la capacité.
+    pub fn is_full(&self) -> bool {
+        self.needs_room(1)
+    }
+
+    /// Change la capacité, puis évince les entrées en trop dans l'ordre
+    /// d'éviction
+    ///
+    /// Les entrées évincées sont passées à l'écouteur avec
+    /// [`RemovalCause::Capacity`], les entrées expirées rencontrées avec
+    /// [`RemovalCause::Expired`]. Les entrées épinglées sont gardées, même
+    /// au-delà de la nouvelle capacité. `NonZeroUsize::MAX` retire la
+    /// limite.
+    ///
+    /// Avec des seuils d'éviction ([`CacheBuilder::watermarks`]), la
+    /// capacité est le seuil haut : le seuil bas est gardé, ramené à la
+    /// nouvelle capacité s'il la dépasse, et un cache au-delà de la
+    /// nouvelle capacité est vidé jusqu'au seuil bas.
+    ///
+    /// # Examples
+    /// ```
+    /// use lru_cache::Cache;
+    /// use lru_cache::cache::traits::{CacheIterate, CacheStorage};
+    /// use std::num::NonZeroUsize;
+    ///
+    /// let mut cache = Cache::new(3);
+    /// cache.put("A", 1);
+    /// cache.put("B", 2);
+    /// cache.put("C", 3);
+    /// cache.get(&"A");
+    /// cache.resize(NonZeroUsize::new(2).unwrap());
+    /// assert_eq!(cache.iter().map(|(key, _)| *key).collect::<Vec<_>>(), ["C", "A"]);
+    /// ```
+    pub fn resize(&mut self, capacity: NonZeroUsize) {
+        self.set_capacity(capacity);
+        if self.total_weight() > self.capacity() {
+            self.shed(|cache| cache.total_weight() > cache.low_water());
+        }
+    }
+
+    /// Retourne les seuils d'éviction `(haut, bas)` : une insertion qui
+    /// ferait dépasser le seuil haut évince jusqu'au seuil bas
+    ///
+    /// Le seuil haut est la capacité ; sans
+    /// [`CacheBuilder::watermarks`], les deux seuils sont égaux.
+    pub fn watermarks(&self) -> (usize, usize) {
+        (self.capacity(), self.low_water())
+    }
+
+    fn low_water(&self) -> usize {
+        self.low_water.map_or(self.capacity(), NonZeroUsize::get)
+    }
+
+    fn set_capacity(&mut self, capacity: NonZeroUsize) {
+        self.capacity = capacity;
+        self.low_water = self.low_water.map(|low| low.min(capacity));
+    }
+
+    /// Vérifie la cohérence des structures internes : chaînage de l'ordre
+    /// d'éviction, table d'index, capacité et compteurs (entrées épinglées,
+    /// entrées à échéance, poids total)
+    ///
+    /// Destinée aux tests et au débogage, la vérification parcourt toutes
+    /// les entrées. La capacité n'est vérifiée que si aucune entrée n'est
+    /// épinglée : les épinglages peuvent maintenir le cache au-delà.
+    ///
+    /// # Errors
+    ///
+    /// Retourne la première incohérence trouvée.
+    ///
+    /// # Examples
+    /// ```
+    /// use lru_cache::Cache;
+    /// use lru_cache::cache::traits::CacheStorage;
+    ///
+    /// let mut cache = Cache::new(2);
+    /// cache.put("A", 1);
+    /// cache.put("B", 2);
+    /// cache.put("C", 3);
+    /// assert_eq!(cache.check_invariants(), Ok(()));
+    /// ```
+    pub fn check_invariants(&self) -> Result<(), InvariantError> {
+        self.entries.check()?;
+        let nodes = || {
+            core::iter::successors(self.entries.head(), |&index| self.entries.next(index)).map(|index| self.node(index))
+        };
+        let counters = [
+            ("pinned", self.pinned, nodes().filter(|node| node.meta.pinned).count()),
+            ("expiring", self.expiring, nodes().filter(|node| node.meta.has_deadline()).count()),
+            (
+                "scheduled",
+                self.wheel.as_ref().map_or(0, |wheel| wheel.len()),
+                nodes().filter(|node| node.meta.has_deadline()).count(),
+            ),
+        ];
+        let weight = self
+            .weighing
+            .as_ref()
+            .map(|weighing| ("weight", weighing.total, nodes().map(|node| node.meta.weight).sum()));
+        let due = self.wheel.as_ref().map(|wheel| {
+            let (forward, backward) = wheel.due_len();
+            ("due", forward, backward)
+        });
+        for (counter, stored, actual) in counters.into_iter().chain(weight).chain(due) {
+            if stored != actual {
+                return Err(InvariantError::CounterMismatch { counter, stored, actual });
+            }
+        }
+        let size = self.weighing.as_ref().map_or(self.len(), |weighing| weighing.total);
+        if self.pinned == 0 && size > self.capacity() {
+            return Err(InvariantError::OverCapacity {
+                size,
+                capacity: self.capacity(),
+            });
+        }
+        Ok(())
+    }
+
+    /// Retourne le nombre d'entrées que les structures internes peuvent
+    /// contenir sans nouvelle allocation (la plus grande des capacités allouées)
+    pub fn allocated_capacity(&self) -> usize {
+        self.entries.allocated_capacity()
+    }
+
+    /// Retourne l'identifiant de l'entrée associée à `key` et la marque comme
+    /// la plus récemment utilisée
+    ///
+    /// # Examples
+    /// ```
+    /// use lru_cache::Cache;
+    /// use lru_cache::cache::traits::CacheStorage;
+    ///
+    /// let mut cache = Cache::new(2);
+    /// cache.put("key", 1);
+    /// let id = cache.get_id(&"key").unwrap();
+    /// assert_eq!(cache.get_by_id(id), Some(&1));
+    /// ```
+    pub fn get_id(&mut self, key: &K) -> Option<EntryId> {
+        let Some(index) = self.find_live(key) else {
+            self.record("get", key, "miss");
+            self.miss(key);
+            return None;
+        };
+        self.record("get", key, "hit");
+        self.events.on_hit(key);
+        self.touch(index, GetOptions::default());
+        Some(EntryId {
+            index,
+            generation: self.entries.generation(index),
+        })
+    }
+
+    /// Récupère la valeur désignée par `id` et la marque comme la plus
+    /// récemment utilisée
+    pub fn get_by_id(&mut self, id: EntryId) -> Option<&V> {
+        if !self.is_valid(id) {
+            self.events.stats.misses += 1;
+            return None;
+        }
+        if self.check_expired(id.index, self.clock.now()) {
+            self.events.on_miss(&self.entries.node(id.index).key);
+            self.discard(id.index, RemovalCause::Expired);
+            return None;
+        }
+        self.record_index("get", id.index, "hit");
+        self.events.on_hit(&self.entries.node(id.index).key);
+        self.touch(id.index, GetOptions::default());
+        self.entries.node(id.index).value.as_ref()
+    }
+
+    /// Retourne le hash sous lequel `get`, `put` et les autres méthodes par
+    /// clé indexent `key`
+    ///
+    /// Le hachage dépend du cache : deux caches peuvent donner des hash
+    /// différents pour la même clé.
+    pub fn hash_key(&self, key: &K) -> u64 {
+        self.entries.hash_of(key)
+    }
+
+    /// Comme `get`, pour l'entrée indexée sous `hash` dont la clé vérifie
+    /// `eq`, sans hacher aucune clé
+    ///
+    /// `hash` est celui donné à [`Cache::put_with_hash`], ou
+    /// [`Cache::hash_key`] pour une entrée insérée par `put`. Seules les
+    /// clés de ce hash sont comparées par `eq`. Une lecture manquée est
+    /// comptée dans [`Cache::stats`] mais, faute de clé, n'est ni passée à
+    /// l'observateur ni enregistrée dans le journal des opérations.
+    ///
+    /// # Examples
+    /// ```
+    /// use lru_cache::Cache;
+    ///
+    /// let mut cache = Cache::new(2);
+    /// let digest = [7u8; 32];
+    /// let hash = u64::from_le_bytes(digest[..8].try_into().unwrap());
+    /// cache.put_with_hash(hash, digest, "contenu");
+    /// assert_eq!(cache.get_with_hash(hash, |key| *key == digest), Some(&"contenu"));
+    /// assert_eq!(cache.get_with_hash(hash + 1, |key| *key == digest), None);
+    /// ```
+    pub fn get_with_hash(&mut self, hash: u64, eq: impl Fn(&K) -> bool) -> Option<&V> {
+        let index = self.get_found(self.entries.find_hashed(hash, eq))?;
+        Some(self.value(index))
+    }
+
+    /// Lecture sans clé de l'entrée `found` : retourne sa position si elle
+    /// est lue et marquée comme utilisée
+    fn get_found(&mut self, found: Option<usize>) -> Option<usize> {
+        let Some(index) = self.live(found) else {
+            self.events.stats.misses += 1;
+            return None;
+        };
+        self.record_index("get", index, "hit");
+        self.events.on_hit(&self.entries.node(index).key);
+        self.touch(index, GetOptions::default());
+        Some(index)
+    }
+
+    /// Comme `put`, en indexant `key` sous `hash` au lieu de la hacher
+    ///
+    /// La même clé doit toujours être donnée avec le même hash. Une clé
+    /// insérée sous un autre hash que [`Cache::hash_key`] reste trouvée par
+    /// `get`, `put` et les autres méthodes par clé, qui parcourent les
+    /// entrées indexées ainsi quand la clé manque sous son propre hash ; de
+    /// même, une clé absente sous `hash` est cherchée parmi les entrées
+    /// insérées par `put` avant d'être ajoutée. Mélanger les deux API ne crée
+    /// donc jamais de doublon, mais rend ces recherches linéaires. Les entrées
+    /// indexées ainsi sont évincées, expirent et sont parcourues comme les
+    /// autres. La clé n'est jamais hachée avec la
+    /// politique d'éviction par défaut ; une politique personnalisée, le
+    /// suivi des modifications ou le journal des opérations hachés peuvent
+    /// encore le faire.
+    pub fn put_with_hash(&mut self, hash: u64, key: K, value: V) {
+        let ttl = self.ttl.map(|(ttl, _)| ttl);
+        self.store_hashed(Some(hash), key, Some(value), ttl);
+    }
+
+    /// Comme `put`, en retournant la version de la valeur écrite
+    ///
+    /// Chaque entrée porte une version qui vaut 1 à l'insertion de la clé et
+    /// augmente de 1 à chaque écriture réussie, par `put` comme par les
+    /// méthodes versionnées. Une clé évincée, expirée ou supprimée puis
+    /// réinsérée repart de 1 : la version ne distingue que les écritures
+    /// successives d'une même présence de la clé. Retourne 0 si la valeur,
+    /// plus lourde que la capacité, est refusée.
+    ///
+    /// # Examples
+    /// ```
+    /// use lru_cache::Cache;
+    /// use lru_cache::cache::traits::CacheStorage;
+    ///
+    /// let mut cache = Cache::new(2);
+    /// assert_eq!(cache.put_versioned("total", 10), 1);
+    /// cache.put("total", 11);
+    /// assert_eq!(cache.get_versioned(&"total"), Some((&11, 2)));
+    /// ```
+    pub fn put_versioned(&mut self, key: K, value: V) -> u64 {
+        let ttl = self.ttl.map(|(ttl, _)| ttl);
+        self.insert(key, value, ttl).map_or(0, |index| self.node(index).meta.version)
+    }
+
+    /// Comme `get`, en retournant aussi la version de la valeur (voir
+    /// [`Cache::put_versioned`])
+    pub fn get_versioned(&mut self, key: &K) -> Option<(&V, u64)> {
+        let index = self.lookup_index(key, GetOptions::default())?;
+        Some((self.value(index), self.node(index).meta.version))
+    }
+
+    /// Remplace la valeur de `key` seulement si sa version vaut
+    /// `expected_version`, et retourne la nouvelle version
+    ///
+    /// Une clé absente a la version 0 : `expected_version` nul insère la clé
+    /// seulement si elle est absente. En cas d'échec, l'entrée n'est ni
+    /// modifiée ni promue et l'erreur rend `value` avec la version actuelle ;
+    /// il en va de même si `value` est plus lourde que la capacité.
+    ///
+    /// # Examples
+    /// ```
+    /// use lru_cache::Cache;
+    ///
+    /// let mut cache = Cache::new(2);
+    /// assert_eq!(cache.compare_and_put("total", 10, 0), Ok(1));
+    /// assert_eq!(cache.compare_and_put("total", 20, 1), Ok(2));
+    ///
+    /// let rejected = cache.compare_and_put("total", 30, 1).unwrap_err();
+    /// assert_eq!((rejected.value, rejected.current_version), (30, 2));
+    /// assert_eq!(cache.get_versioned(&"total"), Some((&20, 2)));
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Retourne [`CasError`] si la version actuelle diffère de
+    /// `expected_version` ou si la valeur est refusée.
+    pub fn compare_and_put(&mut self, key: K, value: V, expected_version: u64) -> Result<u64, CasError<V>> {
+        let current_version = self.find_live(&key).map_or(0, |index| self.node(index).meta.version);
+        if current_version != expected_version || self.weight_of(&key, &value) > self.capacity() {
+            return Err(CasError { value, current_version });
+        }
+        Ok(self.put_versioned(key, value))
+    }
+
+    /// Marque l'entrée désignée par `id` comme la plus récemment utilisée
+    ///
+    /// Retourne `false` si l'identifiant n'est plus valide.
+    pub fn promote_by_id(&mut self, id: EntryId) -> bool {
+        if !self.is_valid(id) {
+            return false;
+        }
+        self.apply_shared_reads();
+        self.record_index("get", id.index, "hit");
+        self.entries.move_to_back(id.index);
+        self.eviction.on_access(&self.entries.node(id.index).key);
+        true
+    }
+
+    /// Supprime l'entrée désignée par `id` et retourne la paire clé-valeur
+    ///
+    /// Une entrée expirée est retirée comme expirée et rien n'est retourné.
+    pub fn remove_by_id(&mut self, id: EntryId) -> Option<(K, V)> {
+        if !self.is_valid(id) {
+            return None;
+        }
+        let expired = self.check_expired(id.index, self.clock.now());
+        self.record_index("remove", id.index, if expired { "miss" } else { "hit" });
+        if expired {
+            self.discard(id.index, RemovalCause::Expired);
+            return None;
+        }
+        let node = self.remove_slot(id.index);
+        Some((node.key, node.value?))
+    }
+
+    /// Insère une valeur qui expirera après la durée `ttl`
+    ///
+    /// L'entrée est expirée dès que l'horloge du cache atteint l'échéance
+    /// (insertion + `ttl`) : à l'instant exact de l'échéance, elle est déjà
+    /// absente. Si l'horloge recule, l'échéance est ramenée à au plus `ttl`
+    /// après l'instant où le recul est observé : une entrée ne devient jamais
+    /// immortelle et n'expire pas prématurément.
+    ///
+    /// Cette durée remplace, pour cette entrée, celle configurée par
+    /// [`Cache::with_ttl`]. Une entrée expirée est considérée comme absente par `get`, `peek` et
+    /// `contains_key`. Elle est retirée du cache (et ne compte plus dans
+    /// `len()`) lorsqu'un accès mutable comme `get` la rencontre. Réinsérer la
+    /// clé remplace l'échéance ; une insertion par `put` lui applique la
+    /// durée par défaut du cache, ou la rend permanente s'il n'y en a pas.
+    ///
+    /// # Examples
+    /// ```
+    /// use lru_cache::Cache;
+    /// use core::time::Duration;
+    ///
+    /// let mut cache = Cache::new(2);
+    /// cache.put_with_ttl("key", 1, Duration::from_secs(60));
+    /// assert_eq!(cache.peek(&"key"), Some(&1));
+    /// ```
+    pub fn put_with_ttl(&mut self, key: K, value: V, ttl: Duration) {
+        self.insert(key, value, Some(ttl));
+    }
+
+    /// Remplace l'échéance de `key` par `ttl` à partir de maintenant, sans
+    /// réécrire sa valeur, et indique si la clé était présente
+    ///
+    /// Une entrée permanente reçoit ainsi une durée de vie. `ttl` devient
+    /// aussi la durée de l'entrée pour [`TtlMode::AfterAccess`]. Une clé
+    /// absente ou déjà expirée retourne `false` ; l'entrée expirée est
+    /// retirée comme par `get`. L'ordre d'utilisation n'est pas modifié.
+    ///
+    /// # Examples
+    /// ```
+    /// use lru_cache::Cache;
+    /// use lru_cache::cache::clock::MockClock;
+    /// use lru_cache::cache::traits::CacheStorage;
+    /// use std::time::Duration;
+    ///
+    /// let mut cache = Cache::with_clock(2, MockClock::new());
+    /// cache.put("session", 1);
+    /// assert_eq!(cache.ttl_remaining(&"session"), None);
+    /// assert!(cache.set_ttl(&"session", Duration::from_secs(30)));
+    /// assert_eq!(cache.ttl_remaining(&"session"), Some(Duration::from_secs(30)));
+    /// assert!(!cache.set_ttl(&"absent", Duration::from_secs(30)));
+    /// ```
+    pub fn set_ttl(&mut self, key: &K, ttl: Duration) -> bool {
+        let Some(index) = self.find_live(key) else {
+            return false;
+        };
+        self.set_ttl_of(index, Some(ttl));
+        true
+    }
+
+    /// Repousse de `by` l'échéance de `key` et indique si la clé était
+    /// présente
+    ///
+    /// La durée de l'entrée pour [`TtlMode::AfterAccess`] augmente d'autant.
+    /// Une entrée permanente le reste. Une clé absente ou déjà expirée
+    /// retourne `false`, comme pour [`Cache::set_ttl`].
+    pub fn extend_ttl(&mut self, key: &K, by: Duration) -> bool {
+        let Some(index) = self.find_live(key) else {
+            return false;
+        };
+        let meta = &self.node(index).meta;
+        if let (Some(ttl), Some(expires_at)) = (meta.ttl, meta.expires_at) {
+            let ttl = ttl.checked_add(by);
+            let expires_at = ttl.and(expires_at.checked_add(by));
+            self.set_deadline(index, ttl, expires_at);
+        }
+        true
+    }
+
+    /// Retourne le temps restant avant l'expiration de `key`, ou `None` si
+    /// la clé est absente, expirée ou permanente
+    ///
+    /// L'échéance d'inactivité de [`Cache::with_max_idle`] compte aussi :
+    /// c'est la plus proche des deux qui est retenue. La lecture n'a aucun
+    /// effet sur le cache.
+    pub fn ttl_remaining(&self, key: &K) -> Option<Duration> {
+        let meta = &self.node(self.find(key)?).meta;
+        let now = self.clock.now();
+        if meta.is_expired(now) {
+            return None;
+        }
+        [meta.expires_at, meta.idle_at]
+            .into_iter()
+            .flatten()
+            .min()
+            .map(|deadline| deadline.saturating_duration_since(now))
+    }
+
+    /// Récupère la valeur associée à `key` en la marquant comme la plus
+    /// récemment utilisée, ou insère celle calculée par `f` si la clé est
+    /// absente
+    ///
+    /// `f` n'est appelée qu'en cas d'échec. L'insertion se comporte comme
+    /// `put` : elle peut évincer une entrée, et la nouvelle entrée reçoit la
+    /// durée de vie par défaut du cache.
+    ///
+    /// # Panics
+    ///
+    /// Panique si le cache est pondéré et que la valeur calculée est plus
+    /// lourde que sa capacité : elle est refusée comme par `put` et aucune
+    /// référence ne peut être retournée.
+    ///
+    /// # Examples
+    /// ```
+    /// use lru_cache::Cache;
+    ///
+    /// let mut cache = Cache::new(2);
+    /// assert_eq!(*cache.get_or_insert_with("key", || 1), 1);
+    /// assert_eq!(*cache.get_or_insert_with("key", || 2), 1);
+    /// ```
+    pub fn get_or_insert_with(&mut self, key: K, f: impl FnOnce() -> V) -> &V {
+        let index = self.get_or_insert_index(key, f);
+        self.value(index)
+    }
+
+    /// Comme [`Cache::get_or_insert_with`], avec une valeur déjà calculée
+    ///
+    /// `value` n'est insérée que si la clé est absente ; sinon elle est
+    /// simplement abandonnée (`drop`) et la valeur présente est retournée.
+    /// [`Cache::get_or_insert_with`] évite de construire une valeur coûteuse
+    /// pour rien.
+    ///
+    /// # Panics
+    ///
+    /// Panique comme [`Cache::get_or_insert_with`] si la valeur est refusée.
+    ///
+    /// # Examples
+    /// ```
+    /// use lru_cache::Cache;
+    ///
+    /// let mut cache = Cache::new(2);
+    /// assert_eq!(*cache.get_or_insert("key", 1), 1);
+    /// assert_eq!(*cache.get_or_insert("key", 2), 1);
+    /// ```
+    pub fn get_or_insert(&mut self, key: K, value: V) -> &V {
+        self.get_or_insert_with(key, || value)
+    }
+
+    /// Retourne une référence modifiable vers la valeur de `key`, en insérant
+    /// `V::default()` si la clé est absente
+    ///
+    /// L'entrée est marquée comme la plus récemment utilisée ; l'insertion
+    /// se comporte comme `put` et peut évincer une entrée. Le poids d'un
+    /// cache pondéré est mesuré à l'insertion et n'est pas recalculé après
+    /// la modification de la valeur.
+    ///
+    /// # Panics
+    ///
+    /// Panique comme [`Cache::get_or_insert_with`] si la valeur par défaut
+    /// est refusée.
+    ///
+    /// # Examples
+    /// ```
+    /// use lru_cache::Cache;
+    ///
+    /// let mut cache: Cache<&str, Vec<i32>> = Cache::new(2);
+    /// cache.get_or_insert_default("pairs").push(2);
+    /// cache.get_or_insert_default("pairs").push(4);
+    /// assert_eq!(cache.peek(&"pairs"), Some(&vec![2, 4]));
+    /// ```
+    pub fn get_or_insert_default(&mut self, key: K) -> &mut V
+    where
+        V: Default,
+    {
+        let index = self.get_or_insert_index(key, V::default);
+        self.node_mut(index).value.as_mut().expect("entrée négative")
+    }
+
+    /// Emplacement de l'entrée vivante de `key`, promue, ou de celle insérée
+    /// avec la valeur calculée par `f`
+    fn get_or_insert_index(&mut self, key: K, f: impl FnOnce() -> V) -> usize {
+        match self.find_live(&key) {
+            Some(index) => {
+                self.record("get", &key, "hit");
+                self.events.on_hit(&key);
+                self.touch(index, GetOptions::default());
+                index
+            }
+            None => {
+                self.record("get", &key, "miss");
+                self.miss(&key);
+                let ttl = self.ttl.map(|(ttl, _)| ttl);
+                self.insert(key, f(), ttl)
+                    .expect("valeur plus lourde que la capacité du cache")
+            }
+        }
+    }
+
+    /// Récupère une valeur à travers une référence partagée et la marque
+    /// comme utilisée
+    ///
+    /// Le cache n'est pas modifié immédiatement : la lecture est notée dans
+    /// l'entrée (par mutabilité intérieure) et appliquée à l'ordre
+    /// d'utilisation, comme un `get`, au début de la prochaine opération
+    /// mutable (`put`, `get`, éviction...). Une éviction tient donc toujours
+    /// compte des lectures partagées qui la précèdent ; seuls `iter`,
+    /// `canonical_string` et `save_to_file` montrent l'ordre d'avant ces
+    /// lectures tant qu'aucune opération mutable n'a eu lieu. L'échéance
+    /// [`TtlMode::AfterAccess`] est repoussée au moment où la lecture est
+    /// appliquée. La lecture est comptée dans [`Cache::stats`] mais n'est
+    /// pas signalée à l'observateur de [`Cache::set_listener`].
+    ///
+    /// `Cache` est `Send` mais pas `Sync` : cette méthode permet de partager
+    /// un cache au sein d'un thread (`Rc<Cache>`, structure immuable), pas
+    /// entre threads. Pour cela, voir
+    /// [`ConcurrentCache`](super::concurrent::ConcurrentCache) et
+    /// [`ShardedCache`](super::sharded::ShardedCache).
+    ///
+    /// # Examples
+    /// ```
+    /// use lru_cache::Cache;
+    /// use lru_cache::cache::traits::CacheStorage;
+    ///
+    /// let mut cache = Cache::new(2);
+    /// cache.put("A", 1);
+    /// cache.put("B", 2);
+    /// let shared = &cache;
+    /// assert_eq!(shared.get_shared(&"A"), Some(&1));
+    /// cache.put("C", 3);
+    /// assert_eq!(cache.get(&"A"), Some(&1));
+    /// assert_eq!(cache.get(&"B"), None);
+    /// ```
+    pub fn get_shared(&self, key: &K) -> Option<&V> {
+        let Some(index) = self.find(key).filter(|&index| !self.is_expired(index, self.clock.now())) else {
+            self.shared.misses.set(self.shared.misses.get() + 1);
+            return None;
+        };
+        self.shared.hits.set(self.shared.hits.get() + 1);
+        let node = self.node(index);
+        if node.meta.shared_read.load(Ordering::Relaxed) == 0 {
+            self.shared.pending.borrow_mut().push(index);
+        }
+        let rank = self.shared.rank.get() + 1;
+        self.shared.rank.set(rank);
+        node.meta.shared_read.store(rank, Ordering::Relaxed);
+        node.value.as_ref()
+    }
+
+    /// Comme [`Cache::get_shared`], en retournant une copie de la valeur
+    pub fn get_cloned(&self, key: &K) -> Option<V>
+    where
+        V: Clone,
+    {
+        self.get_shared(key).cloned()
+    }
+
+    /// Récupère une valeur sans la marquer comme utilisée
+    ///
+    /// La lecture n'est pas comptée dans [`Cache::stats`].
+    pub fn peek(&self, key: &K) -> Option<&V> {
+        let index = self.find(key)?;
+        if self.is_expired(index, self.clock.now()) {
+            return None;
+        }
+        Some(self.value(index))
+    }
+
+    /// Indique si la clé est présente et non expirée, sans modifier l'ordre
+    pub fn contains_key(&self, key: &K) -> bool {
+        self.peek(key).is_some()
+    }
+
+    /// Comme `get`, pour la clé équivalente à `query` (voir
+    /// [`Equivalent`])
+    ///
+    /// `query` est hachée et comparée aux clés sans être convertie en `K` :
+    /// une clé `String` est cherchée par un `&str`, une clé
+    /// [`CaseInsensitive`](super::equivalent::CaseInsensitive) dans n'importe
+    /// quelle casse. Comme pour [`Cache::get_with_hash`], une lecture manquée
+    /// est comptée dans [`Cache::stats`] mais n'est ni passée à l'observateur
+    /// ni enregistrée dans le journal des opérations.
+    ///
+    /// # Examples
+    /// ```
+    /// use lru_cache::Cache;
+    /// use lru_cache::cache::traits::CacheStorage;
+    ///
+    /// let mut cache = Cache::new(2);
+    /// cache.put(String::from("clé"), 1);
+    /// assert_eq!(cache.get_by("clé"), Some(&1));
+    /// ```
+    pub fn get_by<Q: Equivalent<K> + ?Sized>(&mut self, query: &Q) -> Option<&V> {
+        self.get_key_value(query).map(|(_, value)| value)
+    }
+
+    /// Comme [`Cache::get_by`], en retournant aussi la clé stockée
+    ///
+    /// La clé retournée est celle de l'insertion, avec son orthographe
+    /// d'origine, et non la requête.
+    ///
+    /// # Examples
+    /// ```
+    /// use lru_cache::Cache;
+    /// use lru_cache::cache::equivalent::CaseInsensitive;
+    /// use lru_cache::cache::traits::CacheStorage;
+    ///
+    /// let mut cache = Cache::new(2);
+    /// cache.put(CaseInsensitive(String::from("Content-Type")), "text/html");
+    /// let (key, value) = cache.get_key_value(&CaseInsensitive("content-type")).unwrap();
+    /// assert_eq!((key.as_str(), *value), ("Content-Type", "text/html"));
+    /// ```
+    pub fn get_key_value<Q: Equivalent<K> + ?Sized>(&mut self, query: &Q) -> Option<(&K, &V)> {
+        let index = self.get_found(self.entries.find_by(query, |key| query.equivalent(key)))?;
+        Some((&self.node(index).key, self.value(index)))
+    }
+
+    /// Comme [`Cache::peek`], pour la clé équivalente à `query`
+    pub fn peek_by<Q: Equivalent<K> + ?Sized>(&self, query: &Q) -> Option<&V> {
+        self.peek_key_value(query).map(|(_, value)| value)
+    }
+
+    /// Comme [`Cache::peek_by`], en retournant aussi la clé stockée
+    pub fn peek_key_value<Q: Equivalent<K> + ?Sized>(&self, query: &Q) -> Option<(&K, &V)> {
+        let index = self.entries.find_by(query, |key| query.equivalent(key))?;
+        if self.node(index).value.is_none() || self.is_expired(index, self.clock.now()) {
+            return None;
+        }
+        Some((&self.node(index).key, self.value(index)))
+    }
+
+    /// Comme [`Cache::contains_key`], pour la clé équivalente à `query`
+    pub fn contains_key_by<Q: Equivalent<K> + ?Sized>(&self, query: &Q) -> bool {
+        self.peek_by(query).is_some()
+    }
+
+    /// Comme `remove`, pour la clé équivalente à `query`
+    ///
+    /// Une suppression manquée n'est pas enregistrée dans le journal des
+    /// opérations, faute de clé.
+    pub fn remove_by<Q: Equivalent<K> + ?Sized>(&mut self, query: &Q) -> Option<V> {
+        self.remove_entry(query).map(|(_, value)| value)
+    }
+
+    /// Comme [`Cache::remove_by`], en rendant aussi la clé stockée
+    ///
+    /// La clé rendue est celle qui a été insérée, et non une copie construite
+    /// à partir de la requête.
+    ///
+    /// # Examples
+    /// ```
+    /// use lru_cache::Cache;
+    /// use lru_cache::cache::traits::CacheStorage;
+    ///
+    /// let mut cache = Cache::new(2);
+    /// cache.put(String::from("clé"), 1);
+    /// assert_eq!(cache.remove_entry("clé"), Some((String::from("clé"), 1)));
+    /// assert_eq!(cache.remove_entry("clé"), None);
+    /// ```
+    pub fn remove_entry<Q: Equivalent<K> + ?Sized>(&mut self, query: &Q) -> Option<(K, V)> {
+        let index = self.entries.find_by(query, |key| query.equivalent(key))?;
+        let expired = self.check_expired(index, self.clock.now());
+        self.record_index("remove", index, if expired { "miss" } else { "hit" });
+        if expired {
+            self.discard(index, RemovalCause::Expired);
+            return None;
+        }
+        let node = self.remove_slot(index);
+        Some((node.key, node.value?))
+    }
+
+    /// Enregistre que `key` n'existe pas à la source, par une entrée
+    /// négative qui remplace sa valeur éventuelle
+    ///
+    /// L'entrée négative occupe une place (un poids de 1 pour un cache
+    /// pondéré), compte dans `len` et est évincée comme les autres ; elle
+    /// expire après `ttl`, ou après la durée de vie par défaut du cache si
+    /// `ttl` vaut `None`. Seul [`Cache::lookup`] la distingue d'une clé
+    /// absente : `get`, `peek`, `iter` et les sauvegardes l'ignorent et
+    /// l'écouteur d'éviction n'est pas appelé à son retrait. Un `put`
+    /// ultérieur la remplace par la valeur, `remove` la retire.
+    pub fn put_negative(&mut self, key: K, ttl: Option<Duration>) {
+        let ttl = ttl.or(self.ttl.map(|(ttl, _)| ttl));
+        self.store(key, None, ttl);
+    }
+
+    /// Récupère la valeur associée à `key` comme `get`, en distinguant une
+    /// clé connue comme absente ([`Cache::put_negative`]) d'une clé
+    /// inconnue
+    ///
+    /// Une entrée négative trouvée est marquée comme utilisée et compte
+    /// comme un succès dans [`Cache::stats`].
+    ///
+    /// # Examples
+    /// ```
+    /// use lru_cache::Cache;
+    /// use lru_cache::cache::lru::Lookup;
+    /// use lru_cache::cache::traits::CacheStorage;
+    ///
+    /// let mut cache = Cache::new(3);
+    /// cache.put("A", 1);
+    /// cache.put_negative("B", None);
+    /// assert_eq!(cache.lookup(&"A"), Lookup::Hit(&1));
+    /// assert_eq!(cache.lookup(&"B"), Lookup::NegativeHit);
+    /// assert_eq!(cache.lookup(&"C"), Lookup::Miss);
+    /// assert_eq!(cache.get(&"B"), None);
+    /// ```
+    pub fn lookup(&mut self, key: &K) -> Lookup<&V> {
+        if let Some(index) = self.find_entry(key).filter(|&index| self.node(index).value.is_none()) {
+            if !self.check_expired(index, self.clock.now()) {
+                self.record("get", key, "hit");
+                self.events.on_hit(key);
+                self.touch(index, GetOptions::default());
+                return Lookup::NegativeHit;
+            }
+            self.discard(index, RemovalCause::Expired);
+        }
+        match self.get(key) {
+            Some(value) => Lookup::Hit(value),
+            None => Lookup::Miss,
+        }
+    }
+
+    /// Récupère la valeur associée à `key` même si elle a expiré, en
+    /// indiquant depuis combien de temps
+    ///
+    /// Une valeur fraîche est lue comme par [`CacheStorage::get`]. Une
+    /// valeur expirée n'est pas retirée : elle est retournée dans
+    /// [`StaleEntry::Stale`] et la lecture compte comme un succès, mais
+    /// l'entrée n'est pas promue et ses échéances ne sont pas repoussées.
+    /// Elle reste la première candidate à l'éviction jusqu'à ce qu'une
+    /// nouvelle valeur la remplace. Voir
+    /// [`LoadingCache::get_with_stale_while_revalidate`](super::loading::LoadingCache::get_with_stale_while_revalidate).
+    ///
+    /// # Examples
+    /// ```
+    /// use lru_cache::Cache;
+    /// use lru_cache::cache::clock::MockClock;
+    /// use lru_cache::cache::lru::StaleEntry;
+    /// use lru_cache::cache::traits::CacheStorage;
+    /// use lru_cache::cache::ttl::TtlMode;
+    /// use std::time::Duration;
+    ///
+    /// let clock = MockClock::new();
+    /// let mut cache = Cache::with_ttl_and_clock(2, Duration::from_secs(10), TtlMode::AfterWrite, clock.clone());
+    /// cache.put("A", 1);
+    /// assert_eq!(cache.get_stale(&"A"), Some(StaleEntry::Fresh(&1)));
+    /// clock.advance(Duration::from_secs(12));
+    /// assert_eq!(cache.get_stale(&"A"), Some(StaleEntry::Stale { value: &1, expired_for: Duration::from_secs(2) }));
+    /// ```
+    pub fn get_stale(&mut self, key: &K) -> Option<StaleEntry<&V>> {
+        let now = self.clock.now();
+        if let Some(index) = self.find(key) {
+            self.check_expired(index, now);
+            if let Some(expired_for) = self.node(index).meta.expired_for(now) {
+                self.events.on_hit(key);
+                let options = GetOptions {
+                    promote: false,
+                    record_stats: true,
+                };
+                self.touch(index, options);
+                return Some(StaleEntry::Stale {
+                    value: self.value(index),
+                    expired_for,
+                });
+            }
+        }
+        self.get(key).map(StaleEntry::Fresh)
+    }
+
+    /// Indique si le cache garde une valeur pour `key`, même expirée
+    pub(super) fn holds(&self, key: &K) -> bool {
+        self.find(key).is_some()
+    }
+
+    /// Heure courante de l'horloge du cache
+    #[cfg(feature = "std")]
+    pub(super) fn now(&self) -> Instant {
+        self.clock.now()
+    }
+
+    /// Insère ou remplace une entrée et retourne son emplacement, ou `None`
+    /// si son poids dépasse la capacité
+    fn insert(&mut self, key: K, value: V, ttl: Option<Duration>) -> Option<usize> {
+        self.store(key, Some(value), ttl)
+    }
+
+    /// Comme [`Cache::insert`], pour une valeur ou une entrée négative
+    /// (`None`), qui pèse 1
+    fn store(&mut self, key: K, value: Option<V>, ttl: Option<Duration>) -> Option<usize> {
+        self.store_hashed(None, key, value, ttl)
+    }
+
+    /// Comme [`Cache::store`], avec la clé indexée sous `hash` s'il est
+    /// donné par l'appelant, sous son propre hash sinon
+    fn store_hashed(&mut self, hash: Option<u64>, key: K, value: Option<V>, ttl: Option<Duration>) -> Option<usize> {
+        self.apply_shared_reads();
+        let weight = value.as_ref().map_or(1, |value| self.weight_of(&key, value));
+        if weight > self.capacity() {
+            self.record("put", &key, "reject");
+            if let Some(index) = self.find_stored(hash, &key) {
+                self.discard(index, RemovalCause::Capacity);
+            }
+            return None;
+        }
+        #[cfg(feature = "std")]
+        if let Some(changes) = self.changes.as_mut() {
+            changes.mark(&key);
+        }
+        let mut existing = self.find_stored(hash, &key);
+        // Une entrée expirée est retirée comme telle : la nouvelle valeur est
+        // une insertion, pas une mise à jour
+        if let Some(index) = existing.filter(|&index| self.check_expired(index, self.clock.now())) {
+            self.discard(index, RemovalCause::Expired);
+            existing = None;
+        }
+        let index = match existing {
+            Some(index) => {
+                self.record("put", &key, "update");
+                self.events.on_update(&key);
+                let generation = self.entries.generation(index);
+                self.log_meta(index);
+                self.log_move(index);
+                self.entries.renew(index);
+                let live = self.node(index).value.is_some();
+                let meta = &mut self.node_mut(index).meta;
+                meta.version = if live { meta.version + 1 } else { 1 };
+                let previous = core::mem::replace(&mut self.node_mut(index).meta.weight, weight);
+                let replaced = core::mem::replace(&mut self.node_mut(index).value, value);
+                self.entries.move_to_back(index);
+                self.eviction.on_access(&self.entries.node(index).key);
+                if let Some(weighing) = self.weighing.as_mut() {
+                    weighing.total = weighing.total - previous + weight;
+                }
+                if replaced.is_some() {
+                    self.remember(&key, RemovalCause::Replaced);
+                }
+                if let Some(transaction) = self.transaction.as_mut() {
+                    transaction.undo.push(Undo::Replaced {
+                        index,
+                        generation,
+                        key,
+                        value: replaced,
+                    });
+                } else if let (Some(Listener(listener)), Some(replaced)) = (self.listener.as_mut(), replaced) {
+                    listener(key, replaced, RemovalCause::Replaced);
+                }
+                self.make_room(0);
+                index
+            }
+            None => {
+                if let Some(ghosts) = self.ghosts.as_mut() {
+                    ghosts.take(&key);
+                }
+                let evicted = self.make_room(weight);
+                let outcome = match evicted {
+                    Some(evicted) => format!("evict\t{}", evicted),
+                    None => String::from("insert"),
+                };
+                self.record("put", &key, &outcome);
+                self.events.on_insert(&key);
+                let meta = Meta {
+                    ttl: None,
+                    expires_at: None,
+                    idle_at: None,
+                    hits: 0,
+                    inserted: self.next_insertion(),
+                    weight,
+                    version: 1,
+                    pinned: false,
+                    shared_read: ReadRank::new(0),
+                    times: self.metadata.then(|| Box::new(self.times_now())),
+                };
+                if let Some(weighing) = self.weighing.as_mut() {
+                    weighing.total += weight;
+                }
+                let index = match hash {
+                    Some(hash) => self.entries.push_hashed(hash, key, value, meta),
+                    None => self.entries.push(key, value, meta),
+                };
+                self.eviction.on_insert(&self.entries.node(index).key);
+                if let Some(order) = self.hit_order.as_mut() {
+                    order.set(index, 0);
+                }
+                if let Some(transaction) = self.transaction.as_mut() {
+                    transaction.undo.push(Undo::Attached { index });
+                }
+                index
+            }
+        };
+        let ttl = ttl.map(|ttl| self.jittered(ttl));
+        self.set_ttl_of(index, ttl);
+        self.refresh_idle(index);
+        Some(index)
+    }
+
+    /// Évince des entrées jusqu'à pouvoir ajouter le poids `incoming` et
+    /// retourne la première clé évincée telle que le journal des opérations
+    /// l'enregistre
+    ///
+    /// Une entrée qui vient d'être mise à jour est en queue de liste : elle
+    /// n'est choisie que si elle est seule, et son poids tient alors dans la
+    /// capacité. S'il ne reste que des entrées épinglées, le cache dépasse sa
+    /// capacité.
+    fn make_room(&mut self, incoming: usize) -> Option<String> {
+        let mut first = None;
+        if !self.needs_room(incoming) {
+            return first;
+        }
+        let low = self.low_water();
+        while self.exceeds(low, incoming) {
+            let Some(victim) = self.victim() else {
+                return first;
+            };
+            if first.is_none() {
+                first = self.log.as_ref().map(|log| log.key(&self.node(victim).key));
+            }
+            let cause = if self.is_expired(victim, self.clock.now()) {
+                RemovalCause::Expired
+            } else {
+                RemovalCause::Capacity
+            };
+            self.discard(victim, cause);
+        }
+        first
+    }
+
+    /// Indique s'il faut évincer pour ajouter le poids `incoming`
+    fn needs_room(&self, incoming: usize) -> bool {
+        self.exceeds(self.capacity(), incoming)
+    }
+
+    /// Indique si ajouter le poids `incoming` dépasserait `limit` ; un
+    /// poids nul ne fait jamais dépasser un cache non pondéré
+    fn exceeds(&self, limit: usize, incoming: usize) -> bool {
+        match &self.weighing {
+            Some(weighing) => weighing.total.saturating_add(incoming) > limit,
+            None => incoming > 0 && self.len().saturating_add(incoming) > limit,
+        }
+    }
+
+    fn weight_of(&self, key: &K, value: &V) -> usize {
+        match &self.weighing {
+            Some(weighing) => weighing.weigher.weight(key, value),
+            None => 1,
+        }
+    }
+
+    /// Tire la durée de vie effective d'une entrée insérée avec `ttl`,
+    /// uniformément entre `ttl × (1 - fraction)` et `ttl × (1 + fraction)`
+    fn jittered(&mut self, ttl: Duration) -> Duration {
+        let Some(jitter) = self.jitter.as_mut() else {
+            return ttl;
+        };
+        let factor = 1.0 + jitter.fraction * (2.0 * jitter.rng.unit() - 1.0);
+        Duration::try_from_secs_f64(ttl.as_secs_f64() * factor).unwrap_or(Duration::MAX)
+    }
+
+    fn set_ttl_of(&mut self, index: usize, ttl: Option<Duration>) {
+        let expires_at = ttl.and_then(|ttl| self.clock.now().checked_add(ttl));
+        self.set_deadline(index, ttl, expires_at);
+    }
+
+    fn set_deadline(&mut self, index: usize, ttl: Option<Duration>, expires_at: Option<Instant>) {
+        self.update_deadlines(index, |meta| {
+            meta.ttl = ttl;
+            meta.expires_at = expires_at;
+        });
+    }
+
+    /// Repousse l'échéance d'inactivité de l'entrée `index` à `max_idle`
+    /// après maintenant
+    fn refresh_idle(&mut self, index: usize) {
+        let Some(max_idle) = self.max_idle else {
+            return;
+        };
+        let idle_at = self.clock.now().checked_add(max_idle);
+        self.update_deadlines(index, |meta| meta.idle_at = idle_at);
+    }
+
+    /// Modifie les échéances de l'entrée `index` et tient à jour `expiring`
+    fn update_deadlines(&mut self, index: usize, update: impl FnOnce(&mut Meta)) {
+        let meta = &mut self.node_mut(index).meta;
+        let (was_expiring, previous) = (meta.has_deadline(), meta.deadline());
+        update(meta);
+        match (was_expiring, meta.has_deadline()) {
+            (false, true) => self.expiring += 1,
+            (true, false) => self.expiring -= 1,
+            _ => {}
+        }
+        if self.node(index).meta.deadline() != previous {
+            self.schedule(index);
+        }
+    }
+
+    /// Range l'entrée `index` dans la roue selon sa première échéance, ou
+    /// l'en retire si elle n'en a pas
+    fn schedule(&mut self, index: usize) {
+        match self.node(index).meta.deadline() {
+            Some(deadline) => {
+                let (granularity, now) = (self.ttl_granularity, self.clock.now());
+                let wheel = self.wheel.get_or_insert_with(|| Box::new(TimingWheel::new(granularity, now)));
+                wheel.schedule(index, deadline);
+            }
+            None => {
+                if let Some(wheel) = self.wheel.as_mut() {
+                    wheel.cancel(index);
+                }
+            }
+        }
+    }
+
+    fn touch(&mut self, index: usize, options: GetOptions) {
+        self.apply_shared_reads();
+        self.promote(index, options);
+    }
+
+    /// Applique les lectures de `get_shared` dans l'ordre où elles ont eu
+    /// lieu, en ignorant les entrées retirées ou expirées depuis
+    fn apply_shared_reads(&mut self) {
+        let mut pending = core::mem::take(self.shared.pending.get_mut());
+        if pending.is_empty() {
+            return;
+        }
+        let entries = &self.entries;
+        let rank = |index: usize| entries.node(index).meta.shared_read.load(Ordering::Relaxed);
+        pending.retain(|&index| entries.is_occupied(index) && rank(index) != 0);
+        pending.sort_by_key(|&index| rank(index));
+        let now = self.clock.now();
+        for &index in &pending {
+            if self.node(index).meta.shared_read.swap(0, Ordering::Relaxed) != 0 && !self.is_expired(index, now) {
+                self.promote(index, GetOptions::default());
+            }
+        }
+        pending.clear();
+        *self.shared.pending.get_mut() = pending;
+    }
+
+    fn promote(&mut self, index: usize, options: GetOptions) {
+        if options.record_stats || options.promote {
+            self.log_meta(index);
+        }
+        if options.record_stats {
+            let now = self.clock.now();
+            let node = self.node_mut(index);
+            node.meta.hits = node.meta.hits.saturating_add(1);
+            if let Some(times) = node.meta.times.as_mut() {
+                times.accessed_at = now;
+            }
+            if let Some(order) = self.hit_order.as_mut() {
+                order.increment(index);
+            }
+        }
+        if !options.promote {
+            return;
+        }
+        self.log_move(index);
+        self.entries.move_to_back(index);
+        self.eviction.on_access(&self.entries.node(index).key);
+        if let Some((_, TtlMode::AfterAccess)) = self.ttl {
+            let ttl = self.node(index).meta.ttl;
+            self.set_ttl_of(index, ttl);
+        }
+        self.refresh_idle(index);
+    }
+
+    #[allow(deprecated)]
+    fn victim(&mut self) -> Option<usize> {
+        self.apply_shared_reads();
+        if self.expiring > 0 {
+            let now = self.clock.now();
+            if self.wheel.as_ref().is_some_and(|wheel| wheel.is_behind(now)) {
+                self.rebuild_wheel();
+            }
+            let entries = &self.entries;
+            let expired = self
+                .wheel
+                .as_mut()
+                .and_then(|wheel| wheel.first_due(now, |index| entries.node(index).meta.is_expired(now)));
+            if expired.is_some() {
+                return expired;
+            }
+        }
+        match self.policy {
+            Policy::Lru => {
+                let entries = &self.entries;
+                let contains = |key: &K| entries.find(key).is_some_and(|index| !entries.node(index).meta.pinned);
+                let mut keys = entries.keys_where(|meta| !meta.pinned);
+                let key = self.eviction.choose_victim(Candidates::new(&mut keys, &contains))?;
+                // La politique par défaut désigne la première candidate : son
+                // emplacement est retrouvé sans hacher la clé
+                let first = core::iter::successors(entries.head(), |&index| entries.next(index))
+                    .find(|&index| !entries.node(index).meta.pinned);
+                match first {
+                    Some(index) if core::ptr::eq(&entries.node(index).key, key) => Some(index),
+                    _ => entries.find(key).filter(|&index| !entries.node(index).meta.pinned),
+                }
+            }
+            Policy::Mru => core::iter::successors(self.entries.tail(), |&index| self.entries.prev(index))
+                .find(|&index| !self.node(index).meta.pinned),
+            Policy::TwoChoice { .. } => self.two_choice_victim(),
+        }
+    }
+
+    fn two_choice_victim(&mut self) -> Option<usize> {
+        let coldest = core::iter::successors(self.entries.head(), |&index| self.entries.next(index))
+            .find(|&index| !self.node(index).meta.pinned)?;
+        let least_used = self
+            .hit_order
+            .as_ref()?
+            .iter()
+            .find(|&index| !self.node(index).meta.pinned)?;
+        if self.node(coldest).meta.hits > self.node(least_used).meta.hits || self.rng.below(2) == 0 {
+            Some(least_used)
+        } else {
+            Some(coldest)
+        }
+    }
+
+    /// Marque comme les plus récemment utilisées toutes les entrées pour
+    /// lesquelles `pred` est vrai, et retourne leur nombre
+    ///
+    /// Les entrées sont visitées de la moins récente à la plus récente et
+    /// gardent cet ordre relatif en fin de liste. Le parcours est unique :
+    /// O(n) au total. Les entrées expirées sont ignorées.
+    ///
+    /// # Examples
+    /// ```
+    /// use lru_cache::Cache;
+    /// use lru_cache::cache::traits::{CacheIterate, CacheStorage};
+    ///
+    /// let mut cache = Cache::new(4);
+    /// for i in 1..=4 {
+    ///     cache.put(i, i * 10);
+    /// }
+    /// assert_eq!(cache.touch_where(|k, _| k % 2 == 1), 2);
+    /// let order: Vec<_> = cache.iter().map(|(k, _)| *k).collect();
+    /// assert_eq!(order, vec![2, 4, 1, 3]);
+    /// ```
+    pub fn touch_where(&mut self, pred: impl Fn(&K, &V) -> bool) -> usize {
+        self.apply_shared_reads();
+        let now = self.clock.now();
+        let last = self.entries.tail();
+        let mut touched = 0;
+        let mut current = self.entries.head();
+        while let Some(index) = current {
+            let node = self.node(index);
+            current = self.entries.next(index);
+            if node.value.as_ref().is_some_and(|value| pred(&node.key, value)) && !self.is_expired(index, now) {
+                self.record_index("get", index, "hit");
+                self.entries.move_to_back(index);
+                self.eviction.on_access(&self.entries.node(index).key);
+                touched += 1;
+            }
+            if Some(index) == last {
+                break;
+            }
+        }
+        touched
+    }
+
+    /// Retire toutes les entrées expirées et retourne leur nombre
+    ///
+    /// L'expiration est paresseuse : une entrée expirée reste en mémoire
+    /// jusqu'à ce qu'un `get` la rencontre, qu'elle soit choisie pour une
+    /// éviction ou que cette méthode soit appelée. L'ordre d'utilisation des
+    /// entrées restantes est conservé.
+    ///
+    /// Les entrées sont rangées par échéance dans une roue temporelle (voir
+    /// [`Cache::set_ttl_granularity`]) : le coût dépend du nombre d'entrées
+    /// dues et du temps écoulé depuis l'appel précédent, pas du nombre
+    /// d'entrées. Elles sont retirées par échéance croissante. Si
+    /// l'horloge a reculé, toutes les entrées sont examinées et la roue est
+    /// reconstruite.
+    pub fn purge_expired(&mut self) -> usize {
+        if self.expiring == 0 {
+            return 0;
+        }
+        let now = self.clock.now();
+        if let Some(wheel) = self.wheel.as_mut().filter(|wheel| !wheel.is_behind(now)) {
+            let mut due = Vec::new();
+            wheel.advance(now, &mut due);
+            return self.purge_due(due, now);
+        }
+        let mut purged = 0;
+        let mut current = self.entries.head();
+        while let Some(index) = current {
+            current = self.entries.next(index);
+            if self.check_expired(index, now) {
+                self.discard(index, RemovalCause::Expired);
+                purged += 1;
+            }
+        }
+        self.rebuild_wheel();
+        purged
+    }
+
+    /// Range de nouveau toutes les entrées dans une roue neuve, dont le
+    /// tick 0 est maintenant
+    fn rebuild_wheel(&mut self) {
+        if self.wheel.take().is_none() {
+            return;
+        }
+        let mut next = self.entries.head();
+        while let Some(index) = next {
+            next = self.entries.next(index);
+            self.schedule(index);
+        }
+    }
+
+    /// Retire les entrées de `due` qui ont expiré à `now` et range de
+    /// nouveau les autres, dont l'échéance tombe dans le tick courant
+    fn purge_due(&mut self, mut due: Vec<usize>, now: Instant) -> usize {
+        due.sort_by_key(|&index| (self.node(index).meta.deadline(), self.node(index).meta.inserted));
+        let mut purged = 0;
+        for index in due {
+            if self.check_expired(index, now) {
+                self.discard(index, RemovalCause::Expired);
+                purged += 1;
+            } else {
+                self.schedule(index);
+            }
+        }
+        purged
+    }
+
+    /// Évince jusqu'à `n` entrées et les retourne dans l'ordre d'éviction
+    ///
+    /// Les entrées sont choisies comme lors d'une insertion dans un cache
+    /// plein : la moins récemment utilisée d'abord avec la politique par
+    /// défaut, les entrées épinglées n'étant jamais choisies. Les entrées
+    /// expirées rencontrées sont retirées (et passées à l'écouteur) sans être
+    /// retournées ni comptées. Demander plus d'entrées que le cache n'en
+    /// contient le vide simplement.
+    ///
+    /// # Examples
+    /// ```
+    /// use lru_cache::Cache;
+    /// use lru_cache::cache::traits::CacheStorage;
+    ///
+    /// let mut cache = Cache::new(3);
+    /// cache.put("A", 1);
+    /// cache.put("B", 2);
+    /// cache.put("C", 3);
+    /// cache.get(&"A");
+    /// assert_eq!(cache.evict_n(2), vec![("B", 2), ("C", 3)]);
+    /// ```
+    pub fn evict_n(&mut self, n: usize) -> Vec<(K, V)> {
+        let mut evicted = Vec::with_capacity(n.min(self.len()));
+        while evicted.len() < n {
+            let Some(victim) = self.victim() else {
+                break;
+            };
+            if self.is_expired(victim, self.clock.now()) {
+                self.discard(victim, RemovalCause::Expired);
+                continue;
+            }
+            evicted.extend(self.evict_slot(victim));
+        }
+        evicted
+    }
+
+    /// Retire et retourne l'entrée la plus récemment utilisée
+    ///
+    /// C'est l'opposé de `evict_n(1)`, quelle que soit la politique
+    /// d'éviction. Comme avec [`Cache::evict_n`], l'entrée est comptée comme
+    /// une éviction et rendue à l'appelant sans passer par l'écouteur ; les
+    /// entrées épinglées sont ignorées, et les entrées expirées ou négatives
+    /// rencontrées sont retirées sans être retournées.
+    ///
+    /// # Examples
+    /// ```
+    /// use lru_cache::Cache;
+    /// use lru_cache::cache::traits::CacheStorage;
+    ///
+    /// let mut cache = Cache::new(3);
+    /// cache.put("A", 1);
+    /// cache.put("B", 2);
+    /// cache.get(&"A");
+    /// assert_eq!(cache.pop_mru(), Some(("A", 1)));
+    /// assert_eq!(cache.pop_mru(), Some(("B", 2)));
+    /// assert_eq!(cache.pop_mru(), None);
+    /// ```
+    pub fn pop_mru(&mut self) -> Option<(K, V)> {
+        loop {
+            let victim = self.end_victim(true)?;
+            if self.is_expired(victim, self.clock.now()) {
+                self.discard(victim, RemovalCause::Expired);
+                continue;
+            }
+            if let Some(entry) = self.evict_slot(victim) {
+                return Some(entry);
+            }
+        }
+    }
+
+    /// Ne garde que les `n` entrées les plus récemment utilisées et retourne
+    /// les autres, de la moins récemment utilisée à la plus récente
+    ///
+    /// Les entrées sont retirées comme par [`Cache::evict_n`] ; les entrées
+    /// expirées ou négatives sont retirées sans être retournées, et les
+    /// entrées épinglées restent, si bien que le cache peut garder plus de
+    /// `n` entrées. Sans effet si le cache en contient au plus `n`.
+    pub fn keep_newest(&mut self, n: usize) -> Vec<(K, V)> {
+        self.keep(n, false)
+    }
+
+    /// Ne garde que les `n` entrées les moins récemment utilisées et
+    /// retourne les autres, de la plus récemment utilisée à la plus ancienne
+    ///
+    /// Équivaut à appeler [`Cache::pop_mru`] tant que le cache contient plus
+    /// de `n` entrées ; voir [`Cache::keep_newest`] pour les entrées
+    /// expirées, négatives ou épinglées.
+    ///
+    /// # Examples
+    /// ```
+    /// use lru_cache::Cache;
+    /// use lru_cache::cache::traits::CacheStorage;
+    ///
+    /// let mut cache = Cache::new(4);
+    /// for (key, value) in [("A", 1), ("B", 2), ("C", 3), ("D", 4)] {
+    ///     cache.put(key, value);
+    /// }
+    /// assert_eq!(cache.keep_oldest(2), vec![("D", 4), ("C", 3)]);
+    /// assert_eq!(cache.keep_newest(1), vec![("A", 1)]);
+    /// ```
+    pub fn keep_oldest(&mut self, n: usize) -> Vec<(K, V)> {
+        self.keep(n, true)
+    }
+
+    /// Retire des entrées par l'extrémité la plus récente ou la plus
+    /// ancienne jusqu'à n'en garder que `n`
+    fn keep(&mut self, n: usize, newest: bool) -> Vec<(K, V)> {
+        let mut removed = Vec::new();
+        while self.len() > n {
+            let Some(victim) = self.end_victim(newest) else {
+                break;
+            };
+            if self.is_expired(victim, self.clock.now()) {
+                self.discard(victim, RemovalCause::Expired);
+                continue;
+            }
+            removed.extend(self.evict_slot(victim));
+        }
+        removed
+    }
+
+    /// Première entrée non épinglée depuis l'extrémité la plus récente ou la
+    /// plus ancienne de l'ordre d'utilisation
+    fn end_victim(&mut self, newest: bool) -> Option<usize> {
+        self.apply_shared_reads();
+        let entries = &self.entries;
+        let start = if newest { entries.tail() } else { entries.head() };
+        let step = |&index: &usize| if newest { entries.prev(index) } else { entries.next(index) };
+        core::iter::successors(start, step).find(|&index| !entries.node(index).meta.pinned)
+    }
+
+    /// Insère ou remplace une entrée comme `put` et retourne les entrées
+    /// évincées pour lui faire de la place, dans l'ordre d'éviction
+    ///
+    /// Les entrées évincées ne sont pas passées à l'écouteur de
+    /// [`Cache::set_eviction_listener`] : elles sont rendues à l'appelant,
+    /// comme avec [`Cache::evict_n`]. Le résultat est vide quand la clé était
+    /// déjà présente ; il contient au plus une entrée si le cache n'est pas
+    /// pondéré et n'a pas de seuils d'éviction distincts
+    /// ([`CacheBuilder::watermarks`]). Une entrée refusée (plus lourde que la capacité) n'évince
+    /// rien.
+    ///
+    /// # Examples
+    /// ```
+    /// use lru_cache::Cache;
+    ///
+    /// let mut cache = Cache::new(2);
+    /// assert!(cache.put_evicting("A", 1).is_empty());
+    /// assert!(cache.put_evicting("B", 2).is_empty());
+    /// assert_eq!(cache.put_evicting("C", 3), vec![("A", 1)]);
+    /// ```
+    pub fn put_evicting(&mut self, key: K, value: V) -> Vec<(K, V)> {
+        let mut evicted = Vec::new();
+        let weight = self.weight_of(&key, &value);
+        if self.find_entry(&key).is_none() && weight <= self.capacity() && self.needs_room(weight) {
+            let low = self.low_water();
+            while self.exceeds(low, weight) {
+                let Some(victim) = self.victim() else {
+                    break;
+                };
+                if self.is_expired(victim, self.clock.now()) {
+                    self.discard(victim, RemovalCause::Expired);
+                } else {
+                    evicted.extend(self.evict_slot(victim));
+                }
+            }
+        }
+        self.put(key, value);
+        evicted
+    }
+
+    /// Récupère les valeurs associées à `keys`, dans l'ordre des clés, et
+    /// marque chaque entrée trouvée comme la plus récemment utilisée
+    ///
+    /// Le résultat est celui de `get` appelé pour chaque clé dans l'ordre
+    /// (promotions, statistiques, entrées expirées retirées), mais les
+    /// références sont toutes retournées ensemble. Une clé répétée est lue
+    /// à chaque occurrence.
+    ///
+    /// # Examples
+    /// ```
+    /// use lru_cache::Cache;
+    /// use lru_cache::cache::traits::CacheStorage;
+    ///
+    /// let mut cache = Cache::new(3);
+    /// cache.put_many([("A", 1), ("B", 2), ("C", 3)]);
+    /// assert_eq!(cache.get_many([&"A", &"D", &"C"]), vec![Some(&1), None, Some(&3)]);
+    /// assert_eq!(cache.remove_many([&"B", &"D"]), vec![Some(2), None]);
+    /// ```
+    pub fn get_many<'a>(&mut self, keys: impl IntoIterator<Item = &'a K>) -> Vec<Option<&V>>
+    where
+        K: 'a,
+    {
+        let indices: Vec<_> = keys.into_iter().map(|key| self.lookup_index(key, GetOptions::default())).collect();
+        // Une clé répétée qui expire entre deux lectures a été retirée
+        indices
+            .into_iter()
+            .map(|index| index.filter(|&index| self.entries.is_occupied(index)).map(|index| self.value(index)))
+            .collect()
+    }
+
+    /// Insère ou remplace les entrées dans l'ordre, exactement comme des
+    /// `put` successifs : pour une clé répétée, la dernière valeur l'emporte,
+    /// et chaque insertion évince ce qu'il faut au moment où elle a lieu
+    pub fn put_many(&mut self, entries: impl IntoIterator<Item = (K, V)>) {
+        let ttl = self.ttl.map(|(ttl, _)| ttl);
+        for (key, value) in entries {
+            self.insert(key, value, ttl);
+        }
+    }
+
+    /// Verse les entrées de `other` dans ce cache, en réglant les clés
+    /// présentes des deux côtés selon `strategy`
+    ///
+    /// Les entrées de `other` sont appliquées de sa moins récemment utilisée
+    /// à sa plus récente, comme des `put` successifs : elles reçoivent la
+    /// durée de vie par défaut de ce cache, deviennent les plus récentes et
+    /// évincent ce qu'il faut pour respecter sa capacité. Les plus récentes
+    /// de `other` finissent donc les plus récentes du résultat. Avec
+    /// [`MergeStrategy::PreferSelf`], une clé en conflit garde sa valeur,
+    /// sa place et son échéance. Les entrées expirées de `other` sont
+    /// ignorées, et une entrée expirée de ce cache ne crée pas de conflit.
+    ///
+    /// # Examples
+    /// ```
+    /// use lru_cache::Cache;
+    /// use lru_cache::cache::lru::MergeStrategy;
+    /// use lru_cache::cache::traits::{CacheIterate, CacheStorage};
+    ///
+    /// let mut cache = Cache::new(3);
+    /// cache.put_many([("A", 1), ("B", 2)]);
+    /// let mut other = Cache::new(2);
+    /// other.put_many([("B", 20), ("C", 30)]);
+    /// cache.merge(other, MergeStrategy::combine(|_, mine, theirs| mine + theirs));
+    /// assert_eq!(cache.iter().collect::<Vec<_>>(), [(&"A", &1), (&"B", &22), (&"C", &30)]);
+    /// ```
+    pub fn merge<Q: EvictionPolicy<K>>(&mut self, other: Cache<K, V, Q>, mut strategy: MergeStrategy<'_, K, V>) {
+        let ttl = self.ttl.map(|(ttl, _)| ttl);
+        for (key, value) in other.into_entries() {
+            let Some(index) = self.find_live(&key) else {
+                self.insert(key, value, ttl);
+                continue;
+            };
+            let value = match &mut strategy {
+                MergeStrategy::PreferSelf => continue,
+                MergeStrategy::PreferOther => value,
+                MergeStrategy::Combine(combine) => {
+                    let mine = self.node_mut(index).value.take().expect("entrée non négative");
+                    combine(&key, mine, value)
+                }
+            };
+            self.insert(key, value, ttl);
+        }
+    }
+
+    /// Retire les entrées pour lesquelles `pred` est vrai et les retourne
+    /// dans un nouveau cache de capacité `new_capacity`
+    ///
+    /// Les entrées déplacées gardent leur ordre relatif d'utilisation ; si
+    /// elles sont plus nombreuses que `new_capacity`, le nouveau cache ne
+    /// garde que les plus récentes. Les autres entrées restent dans ce cache
+    /// dans le même ordre. Les entrées retirées ne passent pas par
+    /// l'écouteur, comme avec `remove`. Le nouveau cache a la configuration
+    /// de [`Cache::new`] : les entrées déplacées y sont permanentes. Les
+    /// entrées expirées ne sont pas présentées à `pred` et restent ici.
+    ///
+    /// # Panics
+    ///
+    /// Panique si `new_capacity` vaut 0, comme [`Cache::new`].
+    ///
+    /// # Examples
+    /// ```
+    /// use lru_cache::Cache;
+    /// use lru_cache::cache::traits::{CacheIterate, CacheStorage};
+    ///
+    /// let mut cache = Cache::new(4);
+    /// cache.put_many([("a1", 1), ("b1", 2), ("a2", 3), ("b2", 4)]);
+    /// let tenant = cache.split_off_by(|key, _| key.starts_with('a'), 2);
+    /// assert_eq!(tenant.iter().collect::<Vec<_>>(), [(&"a1", &1), (&"a2", &3)]);
+    /// assert_eq!(cache.iter().collect::<Vec<_>>(), [(&"b1", &2), (&"b2", &4)]);
+    /// ```
+    pub fn split_off_by(&mut self, mut pred: impl FnMut(&K, &V) -> bool, new_capacity: usize) -> Cache<K, V> {
+        let mut split = Cache::new(new_capacity);
+        self.apply_shared_reads();
+        let now = self.clock.now();
+        let mut next = self.entries.head();
+        while let Some(index) = next {
+            next = self.entries.next(index);
+            let node = self.node(index);
+            let matches = match &node.value {
+                Some(value) => !node.meta.is_expired(now) && pred(&node.key, value),
+                None => false,
+            };
+            if matches {
+                self.record_index("remove", index, "hit");
+                let node = self.remove_slot(index);
+                if let Some(value) = node.value {
+                    split.put(node.key, value);
+                }
+            }
+        }
+        split
+    }
+
+    /// Supprime les entrées associées à `keys` et retourne leurs valeurs,
+    /// dans l'ordre des clés, comme des `remove` successifs
+    pub fn remove_many<'a>(&mut self, keys: impl IntoIterator<Item = &'a K>) -> Vec<Option<V>>
+    where
+        K: 'a,
+    {
+        keys.into_iter().map(|key| self.remove(key)).collect()
+    }
+
+    /// Cherche une entrée vivante comme `get_with` et retourne son
+    /// emplacement
+    fn lookup_index(&mut self, key: &K, options: GetOptions) -> Option<usize> {
+        let Some(index) = self.find_live(key) else {
+            if options.promote {
+                self.record("get", key, "miss");
+            }
+            if options.record_stats {
+                self.miss(key);
+            }
+            return None;
+        };
+        if options.promote {
+            self.record("get", key, "hit");
+        }
+        if options.record_stats {
+            self.events.on_hit(key);
+        }
+        self.touch(index, options);
+        Some(index)
+    }
+
+    /// Retire l'entrée `victim` comme une éviction et la retourne, sauf si
+    /// elle est négative, sans appeler l'écouteur
+    fn evict_slot(&mut self, victim: usize) -> Option<(K, V)> {
+        self.record_index("remove", victim, "hit");
+        self.events.on_evict(&self.entries.node(victim).key);
+        let node = self.remove_slot(victim);
+        trace_event!(
+            debug,
+            key_hash = %stable_hash(&node.key),
+            cause = ?RemovalCause::Capacity,
+            len = self.len(),
+            capacity = self.capacity(),
+            "cache entry removed"
+        );
+        Some((node.key, node.value?))
+    }
+
+    /// Évince des entrées jusqu'à n'en garder que `keep`, sans changer la
+    /// capacité
+    ///
+    /// Les entrées sont choisies comme par [`Cache::evict_n`] et passées à
+    /// l'écouteur avec [`RemovalCause::Capacity`]. S'il ne reste que des
+    /// entrées épinglées, le cache peut garder plus de `keep` entrées.
+    pub fn truncate(&mut self, keep: usize) {
+        self.shed(|cache| cache.len() > keep);
+    }
+
+    /// Évince des entrées tant que `excess` est vrai, comme
+    /// [`Cache::truncate`]
+    fn shed(&mut self, excess: impl Fn(&Self) -> bool) {
+        while excess(self) {
+            let Some(victim) = self.victim() else {
+                break;
+            };
+            let cause = if self.is_expired(victim, self.clock.now()) {
+                RemovalCause::Expired
+            } else {
+                self.record_index("remove", victim, "hit");
+                RemovalCause::Capacity
+            };
+            self.discard(victim, cause);
+        }
+    }
+
+    /// Change la durée d'un tick de la roue temporelle qui range les
+    /// échéances des entrées pour [`Cache::purge_expired`]
+    ///
+    /// La granularité ne change pas le moment où une entrée expire, qui
+    /// reste exact : elle règle le compromis entre le nombre de cases
+    /// traversées quand le temps avance et le nombre d'entrées de la case
+    /// courante réexaminées à chaque purge. La valeur par défaut est
+    /// [`DEFAULT_TTL_GRANULARITY`] ; les entrées déjà présentes sont
+    /// rangées de nouveau.
+    ///
+    /// # Panics
+    ///
+    /// Panique si `granularity` est nulle.
+    pub fn set_ttl_granularity(&mut self, granularity: Duration) {
+        assert!(!granularity.is_zero(), "la granularité des échéances doit être non nulle");
+        self.ttl_granularity = granularity;
+        self.rebuild_wheel();
+    }
+
+    /// Disperse la durée de vie de chaque entrée insérée ensuite d'au plus
+    /// `fraction` autour de sa valeur, pour que des entrées insérées
+    /// ensemble n'expirent pas au même instant
+    ///
+    /// La durée effective est tirée uniformément entre `ttl × (1 - fraction)`
+    /// et `ttl × (1 + fraction)` à chaque insertion, que la durée vienne du
+    /// cache ou de [`Cache::put_with_ttl`], puis reste celle de l'entrée :
+    /// [`TtlMode::AfterAccess`] la réapplique telle quelle. Les durées
+    /// fixées par [`Cache::set_ttl`] ne sont pas dispersées. Les tirages
+    /// sont reproductibles pour une même graine `seed` ; `fraction` nulle
+    /// rend les durées exactes.
+    ///
+    /// # Examples
+    /// ```
+    /// use lru_cache::Cache;
+    /// use lru_cache::cache::clock::MockClock;
+    /// use lru_cache::cache::traits::CacheStorage;
+    /// use std::time::Duration;
+    ///
+    /// let mut cache = Cache::with_clock(2, MockClock::new());
+    /// cache.set_ttl_jitter(0.1, 42);
+    /// cache.put_with_ttl("a", 1, Duration::from_secs(100));
+    /// let remaining = cache.ttl_remaining(&"a").unwrap();
+    /// assert!(remaining >= Duration::from_secs(90) && remaining <= Duration::from_secs(110));
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panique si `fraction` n'est pas comprise entre 0 et 1.
+    pub fn set_ttl_jitter(&mut self, fraction: f64, seed: u64) {
+        assert!(
+            (0.0..=1.0).contains(&fraction),
+            "la dispersion des durées de vie doit être comprise entre 0 et 1"
+        );
+        self.jitter = (fraction > 0.0).then(|| Jitter {
+            fraction,
+            rng: XorShift64::new(seed),
+        });
+    }
+
+    /// Signale comme à recharger d'avance, pour [`LoadingCache`], les
+    /// entrées dont la durée de vie restante passe sous `fraction` de leur
+    /// durée de vie
+    ///
+    /// Avec une durée de vie de 60 secondes et `fraction` valant 0,2, une
+    /// entrée lue par [`LoadingCache::get`] dans ses 12 dernières secondes
+    /// est servie telle quelle puis rechargée. Seules les entrées avec une
+    /// durée de vie sont concernées, pas l'échéance d'inactivité ;
+    /// `fraction` nulle désactive le rechargement anticipé.
+    ///
+    /// [`LoadingCache`]: super::loading::LoadingCache
+    /// [`LoadingCache::get`]: super::loading::LoadingCache::get
+    ///
+    /// # Panics
+    ///
+    /// Panique si `fraction` n'est pas comprise entre 0 et 1.
+    pub fn set_refresh_ahead(&mut self, fraction: f64) {
+        assert!(
+            (0.0..=1.0).contains(&fraction),
+            "la fraction de rechargement anticipé doit être comprise entre 0 et 1"
+        );
+        self.refresh_ahead = (fraction > 0.0).then_some(fraction);
+    }
+
+    /// Indique si l'entrée vivante de `key` est dans sa fenêtre de
+    /// rechargement anticipé (voir [`Cache::set_refresh_ahead`])
+    pub(super) fn refresh_due(&self, key: &K) -> bool {
+        let Some(fraction) = self.refresh_ahead else {
+            return false;
+        };
+        let Some(index) = self.find(key) else {
+            return false;
+        };
+        let meta = &self.node(index).meta;
+        let (Some(ttl), Some(expires_at)) = (meta.ttl, meta.expires_at) else {
+            return false;
+        };
+        let remaining = expires_at.saturating_duration_since(self.clock.now());
+        !remaining.is_zero() && remaining < ttl.mul_f64(fraction)
+    }
+
+    /// Retourne le nombre d'entrées expirées encore présentes en mémoire
+    pub fn expired_len(&self) -> usize {
+        if self.expiring == 0 {
+            return 0;
+        }
+        let now = self.clock.now();
+        core::iter::successors(self.entries.head(), |&index| self.entries.next(index))
+            .filter(|&index| self.is_expired(index, now))
+            .count()
+    }
+
+    fn record(&mut self, op: &str, key: &K, outcome: &str) {
+        if let Some(log) = self.log.as_mut() {
+            log.push(op, key, outcome);
+        }
+    }
+
+    fn record_index(&mut self, op: &str, index: usize, outcome: &str) {
+        if let Some(log) = self.log.as_mut() {
+            log.push(op, &self.entries.node(index).key, outcome);
+        }
+    }
+
+    /// Active l'enregistrement des opérations en n'identifiant les clés que
+    /// par un hash stable, sans jamais conserver leur contenu
+    ///
+    /// Voir [`Cache::record_operations`] ; le journal produit reproduit les
+    /// mêmes décisions d'éviction lors d'un rejeu.
+    pub fn record_operations_hashed(&mut self, limit: usize) {
+        self.log = Some(OperationLog::new(limit, true, stable_hash::<K>));
+    }
+
+    /// Exporte le journal des opérations enregistrées
+    ///
+    /// Retourne une chaîne vide si l'enregistrement n'est pas activé. Le
+    /// résultat peut être rejoué par [`crate::cache::testing::replay`].
+    pub fn export_replay(&self) -> String {
+        self.log
+            .as_ref()
+            .map(|log| log.export(self.capacity()))
+            .unwrap_or_default()
+    }
+
+    fn is_expired(&self, index: usize, now: Instant) -> bool {
+        self.node(index).meta.is_expired(now)
+    }
+
+    /// Comme `is_expired`, mais ramène d'abord l'échéance à au plus `ttl`
+    /// après `now` : si l'horloge a reculé, l'entrée ne peut pas vivre plus
+    /// longtemps que sa durée de vie à partir du moment où le recul est observé
+    fn check_expired(&mut self, index: usize, now: Instant) -> bool {
+        let max_idle = self.max_idle;
+        let snapshot = self.transaction.is_some().then(|| self.node(index).meta.snapshot());
+        let meta = &mut self.node_mut(index).meta;
+        let previous = meta.deadline();
+        for (limit, deadline) in [(meta.ttl, &mut meta.expires_at), (max_idle, &mut meta.idle_at)] {
+            if let (Some(limit), Some(current)) = (limit, deadline.as_mut()) {
+                if let Some(latest) = now.checked_add(limit) {
+                    *current = (*current).min(latest);
+                }
+            }
+        }
+        if let (Some(transaction), Some(meta)) = (self.transaction.as_mut(), snapshot) {
+            let current = &self.entries.node(index).meta;
+            if (current.expires_at, current.idle_at) != (meta.expires_at, meta.idle_at) {
+                transaction.undo.push(Undo::Meta { index, meta });
+            }
+        }
+        if self.node(index).meta.deadline() != previous {
+            self.schedule(index);
+        }
+        self.is_expired(index, now)
+    }
+
+    fn find_live(&mut self, key: &K) -> Option<usize> {
+        self.live(self.entries.find(key))
+    }
+
+    /// `found` s'il désigne une entrée qui porte une valeur et n'a pas
+    /// expiré ; une entrée expirée est retirée
+    fn live(&mut self, found: Option<usize>) -> Option<usize> {
+        let index = found?;
+        self.node(index).value.as_ref()?;
+        if self.check_expired(index, self.clock.now()) {
+            self.discard(index, RemovalCause::Expired);
+            return None;
+        }
+        Some(index)
+    }
+
+    /// Indique si `id` désigne toujours une entrée, qui n'est pas négative
+    fn is_valid(&self, id: EntryId) -> bool {
+        self.entries.is_live(id.index, id.generation) && self.node(id.index).value.is_some()
+    }
+
+    /// Emplacement de `key`, cherché sous `hash` s'il est donné par
+    /// l'appelant, sous son propre hash sinon
+    fn find_stored(&self, hash: Option<u64>, key: &K) -> Option<usize> {
+        match hash {
+            Some(hash) => self.entries.find_raw(hash, |candidate| candidate == key),
+            None => self.entries.find(key),
+        }
+    }
+
+    /// Emplacement de `key`, s'il porte une valeur
+    fn find(&self, key: &K) -> Option<usize> {
+        self.entries.find(key).filter(|&index| self.node(index).value.is_some())
+    }
+
+    /// Emplacement de `key`, entrée négative comprise
+    fn find_entry(&self, key: &K) -> Option<usize> {
+        self.entries.find(key)
+    }
+
+    fn node(&self, index: usize) -> &Node<K, V> {
+        self.entries.node(index)
+    }
+
+    /// Valeur de l'entrée `index`, qui ne doit pas être négative
+    fn value(&self, index: usize) -> &V {
+        self.node(index).value.as_ref().expect("entrée négative")
+    }
+
+    fn node_mut(&mut self, index: usize) -> &mut Node<K, V> {
+        self.entries.node_mut(index)
+    }
+
+    /// Retire l'entrée, la signale et la passe à l'écouteur
+    fn discard(&mut self, index: usize, cause: RemovalCause) {
+        let (generation, prev) = (self.entries.generation(index), self.entries.prev(index));
+        let position = self.wheel.as_ref().and_then(|wheel| wheel.position(index));
+        let node = self.remove_slot(index);
+        trace_event!(
+            debug,
+            key_hash = %stable_hash(&node.key),
+            cause = ?cause,
+            len = self.len(),
+            capacity = self.capacity(),
+            "cache entry removed"
+        );
+        match cause {
+            RemovalCause::Capacity => {
+                self.events.on_evict(&node.key);
+                if let Some(ghosts) = self.ghosts.as_mut() {
+                    ghosts.push(&node.key);
+                }
+            }
+            RemovalCause::Expired => self.events.on_expire(&node.key),
+            RemovalCause::Explicit | RemovalCause::Replaced | RemovalCause::Cleared => {}
+        }
+        if node.value.is_some() {
+            self.remember(&node.key, cause);
+        }
+        if let Some(transaction) = self.transaction.as_mut() {
+            transaction.undo.push(Undo::Detached {
+                index,
+                generation,
+                prev,
+                position,
+                node,
+                cause,
+            });
+            return;
+        }
+        if let (Some(Listener(listener)), Some(value)) = (self.listener.as_mut(), node.value) {
+            listener(node.key, value, cause);
+        }
+    }
+
+    /// Compte une lecture manquée de `key`, et un succès fantôme si la clé
+    /// venait d'être évincée
+    fn miss(&mut self, key: &K) {
+        self.events.on_miss(key);
+        if self.ghosts.as_mut().is_some_and(|ghosts| ghosts.take(key)) {
+            self.events.stats.ghost_hits += 1;
+        }
+    }
+
+    /// Garde les métadonnées de l'entrée `index` dans le journal de la
+    /// transaction en cours, avant de les modifier
+    fn log_meta(&mut self, index: usize) {
+        if let Some(transaction) = self.transaction.as_mut() {
+            let meta = self.entries.node(index).meta.snapshot();
+            transaction.undo.push(Undo::Meta { index, meta });
+        }
+    }
+
+    /// Garde la place de l'entrée `index` dans le journal de la transaction
+    /// en cours, avant de la déplacer
+    fn log_move(&mut self, index: usize) {
+        if let Some(transaction) = self.transaction.as_mut() {
+            let prev = self.entries.prev(index);
+            transaction.undo.push(Undo::Moved { index, prev });
+        }
+    }
+
+    /// Ouvre le journal d'une transaction, en validant celui d'une
+    /// transaction oubliée par `mem::forget`
+    pub(super) fn begin_transaction(&mut self) {
+        self.commit_transaction();
+        self.apply_shared_reads();
+        self.transaction = Some(UndoLog {
+            undo: Vec::new(),
+            stats: self.events.stats,
+            insertions: self.insertions,
+            rng: self.rng.clone(),
+            jitter: self.jitter.clone(),
+        });
+    }
+
+    /// Ferme le journal et passe à l'écouteur les entrées retirées pendant
+    /// la transaction, dans l'ordre des retraits
+    pub(super) fn commit_transaction(&mut self) {
+        let Some(transaction) = self.transaction.take() else {
+            return;
+        };
+        for undo in transaction.undo {
+            let (key, value, cause) = match undo {
+                Undo::Detached { node, cause, .. } => (node.key, node.value, cause),
+                Undo::Replaced { key, value, .. } => (key, value, RemovalCause::Replaced),
+                Undo::Attached { .. } | Undo::Meta { .. } | Undo::Moved { .. } => continue,
+            };
+            if let (Some(Listener(listener)), Some(value)) = (self.listener.as_mut(), value) {
+                listener(key, value, cause);
+            }
+        }
+    }
+
+    /// Défait les modifications du journal de la plus récente à la plus
+    /// ancienne, puis rétablit les compteurs du début de la transaction
+    pub(super) fn rollback_transaction(&mut self) {
+        let Some(mut transaction) = self.transaction.take() else {
+            return;
+        };
+        while let Some(undo) = transaction.undo.pop() {
+            match undo {
+                Undo::Attached { index } => {
+                    self.remove_slot(index);
+                }
+                Undo::Detached {
+                    index,
+                    generation,
+                    prev,
+                    position,
+                    node,
+                    ..
+                } => self.attach_slot(index, generation, prev, position, node),
+                Undo::Replaced {
+                    index,
+                    generation,
+                    value,
+                    ..
+                } => {
+                    self.node_mut(index).value = value;
+                    self.entries.set_generation(index, generation);
+                }
+                Undo::Meta { index, meta } => {
+                    if let Some(order) = self.hit_order.as_mut() {
+                        order.set(index, meta.hits);
+                    }
+                    let weight = meta.weight;
+                    let previous = core::mem::replace(&mut self.node_mut(index).meta.weight, weight);
+                    if let Some(weighing) = self.weighing.as_mut() {
+                        weighing.total = weighing.total - previous + weight;
+                    }
+                    self.update_deadlines(index, |current| *current = meta);
+                }
+                Undo::Moved { index, prev } => self.entries.move_after(index, prev),
+            }
+        }
+        self.events.stats = transaction.stats;
+        self.insertions = transaction.insertions;
+        self.rng = transaction.rng;
+        self.jitter = transaction.jitter;
+    }
+
+    /// Inverse de [`Cache::remove_slot`] : replace `node` à l'emplacement
+    /// `index`, après `prev`, et à sa `position` dans la roue
+    fn attach_slot(
+        &mut self,
+        index: usize,
+        generation: u64,
+        prev: Option<usize>,
+        position: Option<Position>,
+        node: Node<K, V>,
+    ) {
+        let (deadline, pinned, weight) = (node.meta.deadline(), node.meta.pinned, node.meta.weight);
+        self.entries.restore(index, generation, prev, node);
+        self.eviction.on_insert(&self.entries.node(index).key);
+        if let Some(order) = self.hit_order.as_mut() {
+            order.set(index, self.entries.node(index).meta.hits);
+        }
+        if let Some(deadline) = deadline {
+            self.expiring += 1;
+            match (position, self.wheel.as_mut()) {
+                (Some(position), Some(wheel)) => wheel.restore(index, position, deadline),
+                _ => self.schedule(index),
+            }
+        }
+        if pinned {
+            self.pinned += 1;
+        }
+        if let Some(weighing) = self.weighing.as_mut() {
+            weighing.total += weight;
+        }
+    }
+
+    /// Ajoute le retrait de `key` à l'historique des évictions, s'il est
+    /// activé
+    fn remember(&mut self, key: &K, cause: RemovalCause) {
+        if let Some(history) = self.history.as_mut() {
+            history.push(key, cause, self.clock.now());
+        }
+    }
+
+    fn remove_slot(&mut self, index: usize) -> Node<K, V> {
+        if self.node(index).meta.has_deadline() {
+            self.expiring -= 1;
+            if let Some(wheel) = self.wheel.as_mut() {
+                wheel.cancel(index);
+            }
+        }
+        if self.node(index).meta.pinned {
+            self.pinned -= 1;
+        }
+        let node = self.entries.remove(index);
+        self.eviction.on_remove(&node.key);
+        if let Some(order) = self.hit_order.as_mut() {
+            order.remove(index);
+        }
+        #[cfg(feature = "std")]
+        if let Some(changes) = self.changes.as_mut() {
+            changes.mark(&node.key);
+        }
+        if let Some(weighing) = self.weighing.as_mut() {
+            weighing.total -= node.meta.weight;
+        }
+        node
+    }
+}
+
+impl<K: Eq + Hash, V, P: EvictionPolicy<K>> CacheStorage<K, V> for Cache<K, V, P> {
+    fn get(&mut self, key: &K) -> Option<&V> {
+        self.get_with(key, GetOptions::default())
+    }
+
+    fn put(&mut self, key: K, value: V) {
+        let ttl = self.ttl.map(|(ttl, _)| ttl);
+        self.insert(key, value, ttl);
+    }
+
+    /// Sans `promote`, l'entrée garde sa place, son échéance
+    /// [`TtlMode::AfterAccess`] n'est pas repoussée et la lecture n'est pas
+    /// enregistrée dans le journal des opérations. `record_stats` contrôle
+    /// [`Cache::stats`] et le compteur de lectures utilisé par
+    /// [`Policy::TwoChoice`].
+    fn get_with(&mut self, key: &K, options: GetOptions) -> Option<&V> {
+        let index = self.lookup_index(key, options)?;
+        Some(self.value(index))
+    }
+}
+
+impl<K: Eq + Hash, V, P: EvictionPolicy<K>> Cache<K, Arc<V>, P> {
+    /// Récupère un `Arc` vers la valeur et marque l'entrée comme la plus
+    /// récemment utilisée
+    ///
+    /// Le pointeur retourné n'emprunte pas le cache : l'entrée peut être
+    /// évincée ou remplacée pendant que l'appelant l'utilise, la valeur
+    /// restant valide tant qu'un `Arc` existe. Avec une
+    /// [`ConcurrentCache`](super::concurrent::ConcurrentCache), `get` clone
+    /// déjà l'`Arc` et libère le verrou aussitôt.
+    ///
+    /// # Examples
+    /// ```
+    /// use lru_cache::Cache;
+    ///
+    /// let mut cache = Cache::new(1);
+    /// cache.put_value("doc", String::from("contenu"));
+    /// let doc = cache.get_arc(&"doc").unwrap();
+    /// cache.put_value("other", String::new());
+    /// assert_eq!(*doc, "contenu");
+    /// ```
+    pub fn get_arc(&mut self, key: &K) -> Option<Arc<V>> {
+        self.get(key).cloned()
+    }
+
+    /// Insère `value` enveloppée dans un `Arc`
+    pub fn put_value(&mut self, key: K, value: V) {
+        self.put(key, Arc::new(value));
+    }
+}
+
+impl<N: Eq + Hash, K: Eq + Hash, V, P: EvictionPolicy<(N, K)>> Cache<(N, K), V, P> {
+    /// Retourne une vue du cache limitée à l'espace de noms `namespace`
+    ///
+    /// Voir [`ScopedCache`].
+    pub fn scoped(&mut self, namespace: N) -> ScopedCache<'_, N, K, V, P> {
+        ScopedCache::new(self, namespace)
+    }
+
+    /// Supprime toutes les entrées de l'espace de noms `namespace` et
+    /// retourne le nombre d'entrées non expirées retirées
+    ///
+    /// Les entrées sont passées à l'écouteur comme par
+    /// [`Cache::invalidate`]. Les autres espaces de noms gardent leurs
+    /// entrées et leur ordre.
+    pub fn clear_namespace(&mut self, namespace: &N) -> usize {
+        self.apply_shared_reads();
+        let now = self.clock.now();
+        let mut cleared = 0;
+        let mut next = self.entries.head();
+        while let Some(index) = next {
+            next = self.entries.next(index);
+            if self.node(index).key.0 != *namespace {
+                continue;
+            }
+            let expired = self.check_expired(index, now);
+            self.record_index("remove", index, if expired { "miss" } else { "hit" });
+            if !expired && self.node(index).value.is_some() {
+                cleared += 1;
+            }
+            self.discard(index, if expired { RemovalCause::Expired } else { RemovalCause::Explicit });
+        }
+        cleared
+    }
+}
+
+impl<K: Eq + Hash + Display, V, P: EvictionPolicy<K>> Cache<K, V, P> {
+    /// Active l'enregistrement des `limit` dernières opérations, les clés
+    /// étant conservées sous leur forme `Display`
+    ///
+    /// Seuls le type d'opération, la clé et le résultat (succès, insertion,
+    /// mise à jour, clé évincée) sont enregistrés, jamais les valeurs. Au-delà
+    /// de `limit` opérations, les plus anciennes sont oubliées.
+    pub fn record_operations(&mut self, limit: usize) {
+        self.log = Some(OperationLog::new(limit, false, |key: &K| key.to_string()));
+    }
+
+    /// Représentation canonique de l'état du cache : la capacité puis les clés
+    /// de la moins récemment utilisée à la plus récente, une par ligne
+    pub fn canonical_string(&self) -> String {
+        let mut out = format!("capacity={}\n", self.capacity());
+        for (key, _) in self.iter() {
+            out.push_str(&key.to_string());
+            out.push('\n');
+        }
+        out
+    }
+}
+
+impl<K: Eq + Hash, V, P: EvictionPolicy<K>> CacheRemove<K, V> for Cache<K, V, P> {
+    fn remove(&mut self, key: &K) -> Option<V> {
+        let Some(index) = self.find_entry(key) else {
+            self.record("remove", key, "miss");
+            return None;
+        };
+        let expired = self.check_expired(index, self.clock.now());
+        self.record("remove", key, if expired { "miss" } else { "hit" });
+        if expired {
+            self.discard(index, RemovalCause::Expired);
+            return None;
+        }
+        self.remove_slot(index).value
+    }
+}
+
+impl<K, V, P> CacheLen for Cache<K, V, P> {
+    fn len(&self) -> usize {
+        self.entries.len()
+    }
+}
+
+impl<K: Eq + Hash, V, P: EvictionPolicy<K>> CacheClear for Cache<K, V, P> {
+    fn clear(&mut self) {
+        Cache::clear(self)
+    }
+}
+
+/// Accès indexé `cache[&key]`, qui se comporte comme [`Cache::peek`] :
+/// l'ordre d'utilisation et les statistiques ne changent pas
+///
+/// # Panics
+///
+/// Panique si la clé est absente ou expirée, en affichant la clé.
+///
+/// # Examples
+/// ```
+/// use lru_cache::Cache;
+/// use lru_cache::cache::traits::CacheStorage;
+///
+/// let mut cache = Cache::new(2);
+/// cache.put("key", 1);
+/// assert_eq!(cache[&"key"], 1);
+/// ```
+impl<K: Eq + Hash + fmt::Debug, V, P: EvictionPolicy<K>> core::ops::Index<&K> for Cache<K, V, P> {
+    type Output = V;
+
+    fn index(&self, key: &K) -> &V {
+        match self.peek(key) {
+            Some(value) => value,
+            None => panic!("clé absente du cache : {:?}", key),
+        }
+    }
+}
+
+fn entry_metadata(meta: &Meta) -> Option<EntryMetadata> {
+    let times = meta.times.as_deref()?;
+    Some(EntryMetadata {
+        created_at: times.created_at,
+        last_accessed_at: times.accessed_at,
+        access_count: meta.hits,
+    })
+}
+
+/// Entrées non expirées du cache, de la moins récemment utilisée à la plus
+/// récente ; l'écouteur n'est pas appelé
+///
+/// # Examples
+/// ```
+/// use lru_cache::Cache;
+/// use lru_cache::cache::traits::CacheStorage;
+///
+/// let mut cache = Cache::new(3);
+/// cache.put("A", 1);
+/// cache.put("B", 2);
+/// cache.get(&"A");
+/// assert_eq!(Vec::from(cache), [("B", 2), ("A", 1)]);
+/// ```
+impl<K: Eq + Hash, V, P: EvictionPolicy<K>> From<Cache<K, V, P>> for Vec<(K, V)> {
+    fn from(cache: Cache<K, V, P>) -> Self {
+        cache.into_entries()
+    }
+}
+
+/// Cache rempli avec les entrées dans l'ordre du `Vec`, la première étant
+/// la moins récemment utilisée, comme par [`Cache::put_many`]
+///
+/// La capacité est le nombre d'entrées (au moins 1) : seule une clé
+/// répétée, dont la dernière valeur l'emporte, réduit le nombre
+/// d'entrées gardées. Pour une autre capacité, utiliser [`Cache::new`] puis
+/// [`Cache::put_many`].
+///
+/// # Examples
+/// ```
+/// use lru_cache::Cache;
+/// use lru_cache::cache::traits::CacheStorage;
+///
+/// let mut cache = Cache::from(vec![("A", 1), ("B", 2)]);
+/// cache.put("C", 3);
+/// assert_eq!(cache.get(&"A"), None);
+/// ```
+impl<K: Eq + Hash, V> From<Vec<(K, V)>> for Cache<K, V> {
+    fn from(entries: Vec<(K, V)>) -> Self {
+        let mut cache = Cache::new(entries.len().max(1));
+        cache.put_many(entries);
+        cache
+    }
+}
+
+/// Itérateur sur les entrées d'un [`Cache`], de la moins récemment utilisée
+/// à la plus récente
+pub struct Iter<'a, K, V>(pub(super) list::Iter<'a, K, Option<V>, Meta>);
+
+impl<'a, K, V> Iterator for Iter<'a, K, V> {
+    type Item = (&'a K, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.find_map(|(key, value)| Some((key, value.as_ref()?)))
+    }
+}
+
+impl<K, V, P> CacheIterate<K, V> for Cache<K, V, P> {
+    type Iter<'a> = Iter<'a, K, V> where Self: 'a, K: 'a, V: 'a;
+
+    fn iter(&self) -> Self::Iter<'_> {
+        Iter(self.entries.iter())
+    }
+}
+
+#[cfg(feature = "std")]
+impl<K: Eq + Hash + Display + FromStr, V: Display + FromStr> Cache<K, V> {
+    /// Vérifie qu'un fichier sauvegardé est intègre sans construire le cache
+    ///
+    /// Voir [`FileStorage::verify`](FileStorage::verify).
+    pub fn verify_file(path: impl AsRef<Path>) -> std::io::Result<VerifyReport> {
+        FileStorage::verify::<K, V>(path, VerifyOptions::default())
+    }
+
+    /// Charge toutes les entrées d'un fichier, la capacité étant fixée au
+    /// nombre d'entrées lues (au moins 1)
+    ///
+    /// La capacité enregistrée dans le fichier est ignorée : aucune entrée
+    /// n'est évincée pendant le chargement. Le fichier est lu en une passe
+    /// et les entrées sont gardées en mémoire le temps de connaître leur
+    /// nombre. Les lignes illisibles sont ignorées et les segments
+    /// incrémentaux rejoués comme par [`FileStorage::load`] ; une clé en
+    /// double compte pour une entrée, la dernière valeur l'emporte.
+    ///
+    /// # Examples
+    /// ```
+    /// use lru_cache::Cache;
+    /// use lru_cache::cache::traits::CacheLen;
+    ///
+    /// std::fs::write("load_all_doc.txt", "1\na;1\nb;2\nc;3\n").unwrap();
+    /// let cache = Cache::<String, i32>::load_all_from_file("load_all_doc.txt").unwrap();
+    /// assert_eq!(cache.len(), 3);
+    /// std::fs::remove_file("load_all_doc.txt").unwrap();
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Retourne [`StorageError::Io`] si le fichier ne peut pas être lu et
+    /// [`StorageError::MissingCapacity`] ou
+    /// [`StorageError::InvalidCapacity`] si la première ligne n'est pas une
+    /// capacité.
+    pub fn load_all_from_file(path: impl AsRef<Path>) -> Result<Self, StorageError> {
+        let (_, data) = FileStorage::load(path)?;
+        let mut cache = Cache::with_preallocated(data.len().max(1));
+        for (key, value) in data {
+            cache.put(key, value);
+        }
+        Ok(cache)
+    }
+
+    /// Charge un fichier avec la capacité enregistrée dans son en-tête
+    ///
+    /// Contrairement à [`PersistentStorage::load_from_file`], la capacité
+    /// n'a pas à être redonnée : un cache sauvegardé plein est rechargé sans
+    /// éviction. Les lignes illisibles sont ignorées comme par
+    /// [`FileStorage::load`].
+    ///
+    /// # Examples
+    /// ```
+    /// use lru_cache::Cache;
+    /// use lru_cache::cache::traits::CacheLen;
+    ///
+    /// std::fs::write("load_auto_doc.txt", "3\na;1\nb;2\nc;3\n").unwrap();
+    /// let cache = Cache::<String, i32>::load_from_file_auto("load_auto_doc.txt").unwrap();
+    /// assert_eq!((cache.capacity(), cache.len()), (3, 3));
+    /// std::fs::remove_file("load_auto_doc.txt").unwrap();
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Retourne [`StorageError::MissingCapacity`] si le fichier ou sa
+    /// première ligne est vide, [`StorageError::InvalidCapacity`] si elle
+    /// n'est pas une capacité ou si la capacité enregistrée vaut 0, et
+    /// [`StorageError::Io`] si le fichier ne peut pas être lu.
+    pub fn load_from_file_auto(path: impl AsRef<Path>) -> Result<Self, StorageError> {
+        let path = path.as_ref();
+        let capacity = FileStorage::metadata(path)?.capacity;
+        let capacity = NonZeroUsize::new(capacity).ok_or_else(|| StorageError::InvalidCapacity(capacity.to_string()))?;
+        let mut cache = Cache::with_capacity(capacity);
+        FileStorage::load_records(path, |record| cache.apply(record))?;
+        cache.record_file(path);
+        Ok(cache)
+    }
+
+    /// Charge un fichier avec [`PersistentStorage::load_from_file`] s'il a
+    /// été sauvegardé il y a au plus `max_age`, et retourne `Ok(None)` s'il
+    /// est plus ancien
+    ///
+    /// L'âge est mesuré entre l'heure inscrite dans l'en-tête par
+    /// `save_to_file` et l'heure du système ; un fichier daté dans le futur
+    /// est considéré comme frais.
+    ///
+    /// # Examples
+    /// ```
+    /// use lru_cache::Cache;
+    /// use lru_cache::cache::traits::{CacheStorage, PersistentStorage};
+    /// use core::time::Duration;
+    /// use std::num::NonZeroUsize;
+    ///
+    /// let mut cache = Cache::new(2);
+    /// cache.put(String::from("key"), 1);
+    /// cache.save_to_file("fresh_doc.txt").unwrap();
+    ///
+    /// let loaded = Cache::<String, i32>::load_from_file_if_fresh("fresh_doc.txt", NonZeroUsize::new(2).unwrap(), Duration::from_secs(3600));
+    /// assert!(loaded.unwrap().is_some());
+    /// std::fs::remove_file("fresh_doc.txt").unwrap();
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Retourne [`StorageError::MissingTimestamp`] si l'en-tête n'indique pas
+    /// l'heure de la sauvegarde, [`StorageError::MissingCapacity`] ou
+    /// [`StorageError::InvalidCapacity`] s'il est invalide et
+    /// [`StorageError::Io`] si le fichier ne peut pas être lu.
+    pub fn load_from_file_if_fresh(
+        path: impl AsRef<Path>,
+        capacity: NonZeroUsize,
+        max_age: Duration,
+    ) -> Result<Option<Self>, StorageError> {
+        let path = path.as_ref();
+        let saved_at = FileStorage::metadata(path)?.saved_at.ok_or(StorageError::MissingTimestamp)?;
+        let age = SystemTime::now().duration_since(saved_at).unwrap_or_default();
+        if age > max_age {
+            return Ok(None);
+        }
+        Ok(Some(Cache::load_from_file(path, capacity)?))
+    }
+}
+
+#[cfg(feature = "std")]
+impl<K, V, P> Cache<K, V, P>
+where
+    K: Eq + Hash + Display + FromStr,
+    V: Display + FromStr,
+    P: EvictionPolicy<K> + Default,
+{
+    /// Sauvegarde le cache dans `backend`, de la prochaine entrée évincée à
+    /// la plus récente
+    ///
+    /// # Errors
+    ///
+    /// Les erreurs de [`StorageBackend::save`].
+    pub fn save_to(&self, backend: &mut impl StorageBackend) -> Result<(), StorageError> {
+        let entries: Vec<_> = self.iter().map(|(key, value)| (key.to_string(), value.to_string())).collect();
+        backend.save(self.capacity(), &entries)
+    }
+
+    /// Écrit le cache dans `writer`, octet pour octet comme
+    /// [`PersistentStorage::save_to_file`] l'écrirait dans un fichier
+    ///
+    /// Voir [`FileStorage::save_to_writer`].
+    ///
+    /// # Errors
+    ///
+    /// Retourne les erreurs d'écriture de `writer`.
+    pub fn save_to_writer(&self, mut writer: impl std::io::Write) -> std::io::Result<()> {
+        write_entries(&mut writer, self.capacity(), self.iter(), self.save_options()).map(|_| ())
+    }
+
+    /// Écrit les entrées non expirées dans `writer` au format CSV de la
+    /// RFC 4180, de la prochaine évincée à la plus récente
+    ///
+    /// La première ligne nomme les colonnes : `key,value`, suivies de
+    /// `access_count` si [`Cache::track_metadata`] a été appelée, et de
+    /// `ttl_ms` (temps restant avant l'expiration en millisecondes, vide pour
+    /// une entrée permanente) si le cache a une durée de vie par défaut ou
+    /// des entrées qui expirent. Les champs qui contiennent une virgule, un
+    /// guillemet ou un retour à la ligne sont mis entre guillemets. Le
+    /// format est indépendant de celui de [`PersistentStorage::save_to_file`].
+    ///
+    /// # Examples
+    /// ```
+    /// use lru_cache::Cache;
+    /// use lru_cache::cache::traits::{CacheIterate, CacheStorage};
+    /// use std::num::NonZeroUsize;
+    ///
+    /// let mut cache = Cache::new(2);
+    /// cache.put(String::from("a,b"), String::from("dit \"oui\""));
+    /// let mut csv = Vec::new();
+    /// cache.export_csv(&mut csv).unwrap();
+    /// assert_eq!(csv, b"key,value\r\n\"a,b\",\"dit \"\"oui\"\"\"\r\n");
+    ///
+    /// let imported = Cache::<String, String>::import_csv(&csv[..], NonZeroUsize::new(2).unwrap()).unwrap();
+    /// assert_eq!(imported.iter().collect::<Vec<_>>(), cache.iter().collect::<Vec<_>>());
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Retourne les erreurs d'écriture de `writer`.
+    pub fn export_csv<W: std::io::Write>(&self, mut writer: W) -> std::io::Result<()> {
+        let with_ttl = self.ttl.is_some() || self.expiring > 0;
+        let mut header = vec!["key", "value"];
+        if self.metadata {
+            header.push("access_count");
+        }
+        if with_ttl {
+            header.push("ttl_ms");
+        }
+        csv::write_record(&mut writer, header)?;
+        let now = self.clock.now();
+        let mut next = self.entries.head();
+        while let Some(index) = next {
+            next = self.entries.next(index);
+            let node = self.node(index);
+            let Some(value) = node.value.as_ref().filter(|_| !node.meta.is_expired(now)) else {
+                continue;
+            };
+            let mut row = vec![node.key.to_string(), value.to_string()];
+            if self.metadata {
+                row.push(node.meta.hits.to_string());
+            }
+            if with_ttl {
+                let remaining = node.meta.expires_at.map(|deadline| deadline.saturating_duration_since(now));
+                row.push(remaining.map_or_else(String::new, |remaining| remaining.as_millis().to_string()));
+            }
+            csv::write_record(&mut writer, row)?;
+        }
+        Ok(())
+    }
+
+    /// Construit un cache de capacité `capacity` à partir d'un CSV écrit
+    /// par [`Cache::export_csv`], éventuellement modifié à la main
+    ///
+    /// Les lignes sont insérées dans l'ordre comme par des `put` ; s'il y en
+    /// a plus que la capacité, les premières sont évincées. Une colonne
+    /// `ttl_ms` non vide donne sa durée de vie à l'entrée, et la colonne
+    /// `access_count` est ignorée : les compteurs repartent de zéro.
+    ///
+    /// # Errors
+    ///
+    /// Retourne [`StorageError::Io`] si `reader` ne peut pas être lu ou ne
+    /// contient pas d'UTF-8, et [`StorageError::InvalidEntry`] avec le
+    /// numéro de ligne pour un en-tête inconnu, un guillemet non fermé, un
+    /// nombre de champs différent de l'en-tête, ou une clé, une valeur ou
+    /// une durée refusée par `FromStr` ; le cache n'est alors pas construit.
+    pub fn import_csv<R: std::io::Read>(mut reader: R, capacity: NonZeroUsize) -> Result<Self, StorageError> {
+        let mut text = String::new();
+        reader.read_to_string(&mut text)?;
+        let mut records = csv::parse(&text)?.into_iter();
+        let header = records.next().ok_or(StorageError::InvalidEntry {
+            line: 1,
+            content: String::new(),
+        })?;
+        let column = |name: &str| header.fields.iter().position(|field| field == name);
+        let known = header
+            .fields
+            .iter()
+            .all(|field| ["key", "value", "access_count", "ttl_ms"].contains(&field.as_str()));
+        let (Some(key_column), Some(value_column), true) = (column("key"), column("value"), known) else {
+            return Err(StorageError::InvalidEntry {
+                line: header.line,
+                content: header.raw,
+            });
+        };
+        let ttl_column = column("ttl_ms");
+        let mut cache = Cache::from_policy(capacity, P::default());
+        for record in records {
+            let fields = &record.fields;
+            let parsed = (fields.len() == header.fields.len()).then(|| {
+                let key = fields[key_column].parse().ok()?;
+                let value = fields[value_column].parse().ok()?;
+                let ttl = match ttl_column.map(|column| fields[column].as_str()) {
+                    None | Some("") => None,
+                    Some(millis) => Some(Duration::from_millis(millis.parse().ok()?)),
+                };
+                Some((key, value, ttl))
+            });
+            let Some(Some((key, value, ttl))) = parsed else {
+                return Err(StorageError::InvalidEntry {
+                    line: record.line,
+                    content: record.raw,
+                });
+            };
+            match ttl {
+                Some(ttl) => cache.put_with_ttl(key, value, ttl),
+                None => cache.put(key, value),
+            }
+        }
+        Ok(cache)
+    }
+
+    /// Recharge `path` s'il a changé depuis le dernier chargement ou la
+    /// dernière sauvegarde de ce cache, et indique s'il a été rechargé
+    ///
+    /// Le fichier est considéré comme modifié si sa taille ou sa date de
+    /// modification diffère de celles relevées par le dernier chargement ou
+    /// la dernière sauvegarde au format texte, ou si ce cache n'a encore
+    /// rien relevé pour `path`. Le contenu est alors lu en entier, puis
+    /// remplace celui du cache : les entrées présentes sont retirées comme
+    /// par [`Cache::clear`], et celles du fichier insérées comme par
+    /// [`Cache::warm`], avec la capacité actuelle. En cas d'erreur, le cache
+    /// garde son contenu.
+    ///
+    /// # Examples
+    /// ```
+    /// use lru_cache::Cache;
+    /// use lru_cache::cache::traits::{CacheStorage, PersistentStorage};
+    ///
+    /// let mut cache = Cache::new(2);
+    /// cache.put(String::from("a"), 1);
+    /// cache.save_to_file("reload_doc.txt").unwrap();
+    /// assert!(!cache.reload_if_changed("reload_doc.txt").unwrap());
+    ///
+    /// std::fs::write("reload_doc.txt", "2\nb;20\n").unwrap();
+    /// assert!(cache.reload_if_changed("reload_doc.txt").unwrap());
+    /// assert_eq!(cache.get(&String::from("b")), Some(&20));
+    /// assert_eq!(cache.get(&String::from("a")), None);
+    /// std::fs::remove_file("reload_doc.txt").unwrap();
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Retourne une erreur si le fichier ne peut pas être lu, ou
+    /// [`std::io::ErrorKind::InvalidData`] s'il est corrompu (voir
+    /// [`FileStorage::load`]).
+    pub fn reload_if_changed(&mut self, path: impl AsRef<Path>) -> std::io::Result<bool> {
+        let path = path.as_ref();
+        let current = FileStamp::read(path)?;
+        if self.file.get_mut().as_ref() == Some(&current) {
+            return Ok(false);
+        }
+        let (_, data) = FileStorage::load::<K, V>(path)?;
+        self.clear();
+        self.warm(data);
+        *self.file.get_mut() = Some(current);
+        trace_event!(debug, path = %path.display(), entries = self.len(), "cache file reloaded");
+        Ok(true)
+    }
+
+    /// Sauvegarde le cache dans `path` comme
+    /// [`PersistentStorage::save_to_file`], en écrivant les entrées dans
+    /// l'ordre `order`
+    ///
+    /// Le chargement ne change pas : l'ordre du fichier devient l'ordre
+    /// d'utilisation du cache chargé, si bien qu'un fichier écrit avec
+    /// [`Order::Insertion`] donne un cache dont la prochaine entrée évincée
+    /// est la première insérée.
+    ///
+    /// # Errors
+    ///
+    /// Les erreurs de [`PersistentStorage::save_to_file`].
+    pub fn save_to_file_ordered(&self, path: impl AsRef<Path>, order: Order) -> std::io::Result<()> {
+        let path = path.as_ref();
+        match order {
+            Order::Recency => save_entries(path, self.capacity(), self.iter(), self.save_options()),
+            Order::Insertion => save_entries(path, self.capacity(), self.iter_insertion_order(), self.save_options()),
+        }?;
+        self.record_file(path);
+        Ok(())
+    }
+
+    /// Enregistre dans `path` les modifications suivies depuis la dernière
+    /// sauvegarde, en ajoutant un segment à la fin du fichier sans le
+    /// réécrire
+    ///
+    /// Le segment contient une ligne de suppression pour chaque clé modifiée
+    /// qui n'est plus dans le cache, puis les entrées modifiées de la moins
+    /// récemment utilisée à la plus récente ; voir [`FileStorage::append`].
+    /// [`PersistentStorage::load_from_file`] rejoue les segments dans
+    /// l'ordre. Sans suivi actif, ou si `path` n'existe pas ou a été écrit
+    /// dans un format antérieur, le cache est sauvegardé en entier avec
+    /// [`PersistentStorage::save_to_file`] et le suivi commence.
+    ///
+    /// Chaque segment allonge le fichier : [`FileStorage::compact_file`] le
+    /// réécrit en un seul instantané. Les lectures ne sont pas suivies :
+    /// l'ordre d'utilisation des entrées non modifiées est celui de la
+    /// dernière sauvegarde complète. Après une erreur, les modifications
+    /// restent suivies. Si d'autres processus écrivent le même fichier,
+    /// tenir son [`FileLock`] exclusif pendant l'appel.
+    ///
+    /// # Examples
+    /// ```
+    /// use lru_cache::Cache;
+    /// use lru_cache::cache::traits::{CacheRemove, CacheStorage, PersistentStorage};
+    /// use std::num::NonZeroUsize;
+    ///
+    /// let mut cache = Cache::new(3);
+    /// cache.put(String::from("a"), 1);
+    /// cache.put(String::from("b"), 2);
+    /// cache.save_incremental("incremental_doc.txt").unwrap();
+    ///
+    /// cache.put(String::from("c"), 3);
+    /// cache.remove(&String::from("a"));
+    /// cache.save_incremental("incremental_doc.txt").unwrap();
+    ///
+    /// let mut loaded = Cache::<String, i32>::load_from_file("incremental_doc.txt", NonZeroUsize::new(3).unwrap()).unwrap();
+    /// assert_eq!(loaded.get(&String::from("a")), None);
+    /// assert_eq!(loaded.get(&String::from("c")), Some(&3));
+    /// std::fs::remove_file("incremental_doc.txt").unwrap();
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Les erreurs de [`FileStorage::append`], ou de
+    /// [`PersistentStorage::save_to_file`] pour une sauvegarde complète.
+    pub fn save_incremental(&mut self, path: impl AsRef<Path>) -> Result<(), StorageError>
+    where
+        K: Clone,
+    {
+        let path = path.as_ref();
+        let appendable = FileStorage::detect_version(path).is_ok_and(|version| version >= INCREMENTAL_VERSION);
+        if self.changes.is_none() || !appendable {
+            self.save_to_file(path)?;
+            self.track_changes();
+            return Ok(());
+        }
+        let present = self.changed_indices();
+        if let Some(changes) = &self.changes {
+            let removed = changes.keys.iter().filter(|key| self.find(key).is_none()).map(Record::Remove);
+            let updated = present.iter().filter_map(|&index| {
+                let node = self.node(index);
+                Some(Record::Put(&node.key, node.value.as_ref()?))
+            });
+            FileStorage::append(path, removed.chain(updated))?;
+        }
+        if let Some(changes) = self.changes.as_mut() {
+            changes.keys.clear();
+        }
+        self.record_file(path);
+        Ok(())
+    }
+
+    /// Comme [`PersistentStorage::save_to_file`], en tenant le verrou
+    /// exclusif du fichier pendant l'écriture
+    ///
+    /// Voir [`FileStorage::save_locked`].
+    ///
+    /// # Errors
+    ///
+    /// Les erreurs de [`FileStorage::save_locked`].
+    pub fn save_to_file_locked(&self, path: impl AsRef<Path>, timeout: Duration) -> Result<(), StorageError> {
+        let _lock = FileLock::exclusive(path.as_ref(), timeout)?;
+        self.save_to_file(path)?;
+        Ok(())
+    }
+
+    /// Comme [`PersistentStorage::load_from_file`], en tenant un verrou
+    /// partagé pendant la lecture
+    ///
+    /// # Examples
+    /// ```
+    /// use lru_cache::Cache;
+    /// use lru_cache::cache::traits::CacheStorage;
+    /// use core::time::Duration;
+    /// use std::num::NonZeroUsize;
+    ///
+    /// let timeout = Duration::from_secs(5);
+    /// let mut cache = Cache::new(2);
+    /// cache.put(String::from("key"), 1);
+    /// cache.save_to_file_locked("cache_locked_doc.txt", timeout).unwrap();
+    ///
+    /// let mut loaded = Cache::<String, i32>::load_from_file_locked("cache_locked_doc.txt", NonZeroUsize::new(2).unwrap(), timeout).unwrap();
+    /// assert_eq!(loaded.get(&String::from("key")), Some(&1));
+    /// std::fs::remove_file("cache_locked_doc.txt").unwrap();
+    /// std::fs::remove_file("cache_locked_doc.txt.lock").unwrap();
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Les erreurs de [`FileStorage::load_locked`].
+    pub fn load_from_file_locked(path: impl AsRef<Path>, capacity: NonZeroUsize, timeout: Duration) -> Result<Self, StorageError> {
+        let _lock = FileLock::shared(path.as_ref(), timeout)?;
+        Cache::load_file(path.as_ref(), capacity)
+    }
+
+    /// Charge `path` en insérant chaque entrée dès qu'elle est lue et en
+    /// rejouant les segments incrémentaux
+    fn load_file(path: &Path, capacity: NonZeroUsize) -> Result<Self, StorageError> {
+        let mut cache = Cache::from_policy(capacity, P::default());
+        let mut entries = 0;
+        let stored = FileStorage::load_records(path, |record| {
+            entries += usize::from(matches!(record, Record::Put(..)));
+            cache.apply(record);
+        })?;
+        if stored != capacity.get() {
+            trace_event!(warn, path = %path.display(), stored, capacity = capacity.get(), entries, "cache file capacity mismatch");
+        }
+        cache.record_file(path);
+        Ok(cache)
+    }
+
+    /// Charge un cache de capacité `capacity` depuis `reader`, comme
+    /// [`PersistentStorage::load_from_file`] depuis un fichier
+    ///
+    /// # Errors
+    ///
+    /// Les erreurs de [`FileStorage::load_from_reader`].
+    pub fn load_from_reader(reader: impl std::io::Read, capacity: NonZeroUsize) -> Result<Self, StorageError> {
+        let (stored, data) = FileStorage::load_from_reader(reader)?;
+        if stored != capacity.get() {
+            trace_event!(warn, stored, capacity = capacity.get(), entries = data.len(), "cache file capacity mismatch");
+        }
+        let mut cache = Cache::from_policy(capacity, P::default());
+        for (key, value) in data {
+            cache.put(key, value);
+        }
+        Ok(cache)
+    }
+
+    /// Charge un cache de capacité `capacity` depuis `backend`
+    ///
+    /// `capacity` l'emporte sur la capacité sauvegardée : s'il y a plus
+    /// d'entrées, les plus anciennes sont évincées pendant le chargement (et
+    /// comptées dans [`Cache::stats`]). Les entrées que `FromStr` refuse sont
+    /// ignorées.
+    ///
+    /// # Errors
+    ///
+    /// Les erreurs de [`StorageBackend::load`].
+    pub fn load_from(backend: &mut impl StorageBackend, capacity: NonZeroUsize) -> Result<Self, StorageError> {
+        let (stored, entries) = backend.load()?;
+        if stored != capacity.get() {
+            trace_event!(warn, stored, capacity = capacity.get(), entries = entries.len(), "cache file capacity mismatch");
+        }
+        let mut cache = Cache::from_policy(capacity, P::default());
+        for (key, value) in entries {
+            if let (Ok(key), Ok(value)) = (K::from_str(&key), V::from_str(&value)) {
+                cache.put(key, value);
+            }
+        }
+        Ok(cache)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<K, V, P> PersistentStorage<K, V> for Cache<K, V, P>
+where
+    K: Eq + Hash + Display + FromStr,
+    V: Display + FromStr,
+    P: EvictionPolicy<K> + Default,
+{
+    /// L'en-tête enregistre l'heure de la sauvegarde lue sur l'horloge du
+    /// cache (voir [`Cache::load_from_file_if_fresh`]). Produit le même
+    /// fichier que [`Cache::save_to`] avec un
+    /// [`FileBackend`](crate::storage::backend::FileBackend), mais chaque
+    /// entrée est écrite directement depuis le cache, sans copie en
+    /// `String` : la mémoire utilisée ne dépend pas de la taille du cache.
+    fn save_to_file(&self, path: impl AsRef<Path>) -> std::io::Result<()> {
+        save_entries(path.as_ref(), self.capacity(), self.iter(), self.save_options())?;
+        self.record_file(path.as_ref());
+        Ok(())
+    }
+
+    /// `capacity` l'emporte sur la capacité enregistrée dans le fichier :
+    /// si le fichier contient plus d'entrées, les plus anciennes sont
+    /// évincées pendant le chargement (et comptées dans
+    /// [`Cache::stats`]). [`Cache::load_from_file_auto`] utilise la capacité
+    /// enregistrée. Comme [`Cache::load_from`] avec un
+    /// [`FileBackend`](crate::storage::backend::FileBackend), mais chaque
+    /// entrée est insérée dans le cache dès qu'elle est lue, et chaque
+    /// segment ajouté par [`Cache::save_incremental`] est rejoué sur les
+    /// entrées qui le précèdent.
+    fn load_from_file(path: impl AsRef<Path>, capacity: NonZeroUsize) -> std::io::Result<Self> {
+        Ok(Cache::load_file(path.as_ref(), capacity)?)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<K: Eq + Hash, V, P: EvictionPolicy<K> + Default> Cache<K, V, P> {
+    /// Sauvegarde le cache dans `path` comme
+    /// [`PersistentStorage::save_to_file`], en encodant les clés avec
+    /// `key_codec` et les valeurs avec `value_codec`
+    ///
+    /// Avec [`DisplayCodec`](crate::storage::codec::DisplayCodec) des deux
+    /// côtés, le fichier est le même qu'avec `save_to_file`. Les clés et
+    /// valeurs n'ont besoin ni de `Display` ni de `FromStr`.
+    ///
+    /// # Examples
+    /// ```
+    /// use lru_cache::Cache;
+    /// use lru_cache::cache::traits::{CacheIterate, CacheStorage};
+    /// use lru_cache::storage::codec::{Base64Codec, DisplayCodec};
+    /// use std::num::NonZeroUsize;
+    ///
+    /// let mut cache = Cache::new(2);
+    /// cache.put(1u32, vec![b';', b'\n', 0]);
+    /// cache.save_to_file_with("codec_doc.txt", &DisplayCodec, &Base64Codec).unwrap();
+    ///
+    /// let capacity = NonZeroUsize::new(2).unwrap();
+    /// let loaded = Cache::<u32, Vec<u8>>::load_from_file_with("codec_doc.txt", capacity, &DisplayCodec, &Base64Codec).unwrap();
+    /// assert_eq!(loaded.iter().collect::<Vec<_>>(), [(&1, &vec![b';', b'\n', 0])]);
+    /// std::fs::remove_file("codec_doc.txt").unwrap();
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Les erreurs de [`PersistentStorage::save_to_file`].
+    pub fn save_to_file_with(
+        &self,
+        path: impl AsRef<Path>,
+        key_codec: &impl ValueCodec<K>,
+        value_codec: &impl ValueCodec<V>,
+    ) -> std::io::Result<()> {
+        let entries = self.iter().map(|(key, value)| (key_codec.encode(key), value_codec.encode(value)));
+        save_entries(path.as_ref(), self.capacity(), entries, self.save_options())?;
+        self.record_file(path.as_ref());
+        Ok(())
+    }
+
+    /// Charge un fichier écrit par [`Cache::save_to_file_with`], en
+    /// décodant avec les mêmes codecs
+    ///
+    /// `capacity` l'emporte sur la capacité enregistrée, comme pour
+    /// [`PersistentStorage::load_from_file`], et les segments incrémentaux
+    /// sont rejoués de la même façon.
+    ///
+    /// # Errors
+    ///
+    /// Les erreurs de [`FileStorage::load`], et [`StorageError::Codec`] pour
+    /// la première clé ou valeur refusée par son codec.
+    pub fn load_from_file_with(
+        path: impl AsRef<Path>,
+        capacity: NonZeroUsize,
+        key_codec: &impl ValueCodec<K>,
+        value_codec: &impl ValueCodec<V>,
+    ) -> Result<Self, StorageError> {
+        let path = path.as_ref();
+        let mut cache = Cache::from_policy(capacity, P::default());
+        let mut failure = None;
+        FileStorage::load_records::<String, String>(path, |record| {
+            if failure.is_some() {
+                return;
+            }
+            let decoded = match record {
+                Record::Put(key, value) => key_codec
+                    .decode(&key)
+                    .and_then(|key| Ok(Record::Put(key, value_codec.decode(&value)?))),
+                Record::Remove(key) => key_codec.decode(&key).map(Record::Remove),
+            };
+            match decoded {
+                Ok(record) => cache.apply(record),
+                Err(error) => failure = Some(error),
+            }
+        })?;
+        if let Some(error) = failure {
+            return Err(StorageError::Codec(error));
+        }
+        cache.record_file(path);
+        Ok(cache)
+    }
+
+    /// Applique un enregistrement lu dans un fichier
+    fn apply(&mut self, record: Record<K, V>) {
+        match record {
+            Record::Put(key, value) => self.put(key, value),
+            Record::Remove(key) => {
+                self.remove(&key);
+            }
+        }
+    }
+
+    /// Relève l'empreinte de `path` qui vient d'être chargé ou écrit
+    fn record_file(&self, path: &Path) {
+        *self.file.borrow_mut() = FileStamp::read(path).ok();
+    }
+
+    /// Options de sauvegarde, avec l'heure lue sur l'horloge du cache
+    pub(super) fn save_options(&self) -> SaveOptions {
+        SaveOptions {
+            saved_at: Some(self.clock.system_time()),
+            ..SaveOptions::default()
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<K, V, P> BinaryPersistentStorage<K, V> for Cache<K, V, P>
+where
+    K: Eq + Hash + ToBytes + FromBytes,
+    V: ToBytes + FromBytes,
+    P: EvictionPolicy<K> + Default,
+{
+    fn save_to_file_binary(&self, path: impl AsRef<Path>) -> std::io::Result<()> {
+        let data: Vec<_> = self.iter().collect();
+        BinaryStorage::save(path, self.capacity(), &data)
+    }
+
+    fn load_from_file_binary(path: impl AsRef<Path>, capacity: NonZeroUsize) -> Result<Self, BinaryError> {
+        let (_, data) = BinaryStorage::load(path)?;
+        let mut cache = Cache::from_policy(capacity, P::default());
+        for (key, value) in data {
+            cache.put(key, value);
+        }
+        Ok(cache)
+    }
+}
+
+#[cfg(feature = "compression")]
+impl<K, V, P> Cache<K, V, P>
+where
+    K: Eq + Hash + Display + FromStr,
+    V: Display + FromStr,
+    P: EvictionPolicy<K> + Default,
+{
+    /// Comme [`PersistentStorage::save_to_file`], en compressant le fichier
+    /// en gzip avec le niveau `level` (de 0 à 9)
+    ///
+    /// Voir [`FileStorage::save_compressed`].
+    pub fn save_to_file_gz(&self, path: impl AsRef<Path>, level: u32) -> std::io::Result<()> {
+        let data: Vec<_> = self.iter().collect();
+        let options = SaveOptions {
+            saved_at: Some(self.clock.system_time()),
+            ..SaveOptions::default()
+        };
+        FileStorage::save_compressed(path, self.capacity(), &data, options, level).map(|_| ())
+    }
+
+    /// Charge un fichier écrit par [`Cache::save_to_file_gz`], avec la
+    /// capacité `capacity`
+    ///
+    /// # Errors
+    ///
+    /// Les erreurs de [`FileStorage::load_compressed`].
+    pub fn load_from_file_gz(path: impl AsRef<Path>, capacity: NonZeroUsize) -> Result<Self, StorageError> {
+        let (_, data) = FileStorage::load_compressed(path)?;
+        let mut cache = Cache::from_policy(capacity, P::default());
+        for (key, value) in data {
+            cache.put(key, value);
+        }
+        Ok(cache)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<K, V, P> Cache<K, V, P>
+where
+    K: Eq + Hash + serde::Serialize + serde::de::DeserializeOwned,
+    V: serde::Serialize + serde::de::DeserializeOwned,
+    P: EvictionPolicy<K> + Default,
+{
+    /// Sauvegarde le cache au format JSON de [`JsonStorage`], avec sa capacité
+    ///
+    /// # Examples
+    /// ```
+    /// use lru_cache::Cache;
+    /// use lru_cache::cache::traits::CacheStorage;
+    ///
+    /// let mut cache = Cache::new(2);
+    /// cache.put(String::from("key"), vec![1, 2]);
+    /// cache.save_to_json("cache_doc.json").unwrap();
+    ///
+    /// let mut restored: Cache<String, Vec<i32>> = Cache::load_from_json("cache_doc.json").unwrap();
+    /// assert_eq!(restored.get(&String::from("key")), Some(&vec![1, 2]));
+    /// std::fs::remove_file("cache_doc.json").unwrap();
+    /// ```
+    pub fn save_to_json(&self, path: impl AsRef<Path>) -> std::io::Result<()> {
+        let data: Vec<_> = self.iter().collect();
+        JsonStorage::save(path, self.capacity(), &data)
+    }
+
+    /// Charge un cache sauvegardé par [`Cache::save_to_json`], avec la
+    /// capacité inscrite dans le fichier
+    ///
+    /// # Errors
+    ///
+    /// Retourne [`JsonError::Malformed`] si le fichier n'est pas du JSON de
+    /// la forme attendue, plutôt qu'un cache vide.
+    pub fn load_from_json(path: impl AsRef<Path>) -> Result<Self, JsonError> {
+        let (capacity, data) = JsonStorage::load(path)?;
+        let mut cache = Cache::from_policy(check_capacity(capacity)?, P::default());
+        for (key, value) in data {
+            cache.put(key, value);
+        }
+        Ok(cache)
+    }
+
+    /// Sauvegarde le cache dans le format binaire de [`BincodeStorage`], avec
+    /// sa capacité
+    ///
+    /// # Examples
+    /// ```
+    /// use lru_cache::Cache;
+    /// use lru_cache::cache::traits::CacheStorage;
+    ///
+    /// let mut cache = Cache::new(2);
+    /// cache.put(1u32, vec![Some(String::from("a")), None]);
+    /// cache.save_to_file_bincode("cache_doc.bin").unwrap();
+    ///
+    /// let mut restored: Cache<u32, Vec<Option<String>>> = Cache::load_from_file_bincode("cache_doc.bin").unwrap();
+    /// assert_eq!(restored.get(&1), Some(&vec![Some(String::from("a")), None]));
+    /// std::fs::remove_file("cache_doc.bin").unwrap();
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Les erreurs de [`BincodeStorage::save`](crate::storage::bincode::BincodeStorage::save).
+    pub fn save_to_file_bincode(&self, path: impl AsRef<Path>) -> std::io::Result<()> {
+        let data: Vec<_> = self.iter().collect();
+        BincodeStorage::save(path, self.capacity(), &data)
+    }
+
+    /// Charge un cache sauvegardé par [`Cache::save_to_file_bincode`], avec
+    /// la capacité inscrite dans le fichier
+    ///
+    /// # Errors
+    ///
+    /// Les erreurs de [`BincodeStorage::load`](crate::storage::bincode::BincodeStorage::load),
+    /// et [`StorageError::Io`] de genre [`std::io::ErrorKind::InvalidInput`]
+    /// si la capacité inscrite est 0.
+    pub fn load_from_file_bincode(path: impl AsRef<Path>) -> Result<Self, StorageError> {
+        let (capacity, data) = BincodeStorage::load(path)?;
+        let mut cache = Cache::from_policy(check_capacity(capacity)?, P::default());
+        for (key, value) in data {
+            cache.put(key, value);
+        }
+        Ok(cache)
+    }
+}
+
+/// Capacité d'un constructeur qui prend un `usize`
+#[track_caller]
+fn non_zero(capacity: usize) -> NonZeroUsize {
+    NonZeroUsize::new(capacity).expect("la capacité d'un cache doit être au moins 1")
+}
+
+/// Refuse la capacité 0 d'un cache chargé, comme [`Cache::try_new`]
+#[cfg(feature = "std")]
+pub(crate) fn check_capacity(capacity: usize) -> std::io::Result<NonZeroUsize> {
+    NonZeroUsize::new(capacity)
+        .ok_or_else(|| std::io::Error::new(std::io::ErrorKind::InvalidInput, CacheError::ZeroCapacity))
+}
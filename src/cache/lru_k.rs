@@ -0,0 +1,178 @@
+use super::traits::{CacheIterate, CacheLen, CacheRemove, CacheStorage, GetOptions};
+use std::collections::{btree_map, BTreeMap, HashMap, VecDeque};
+use std::hash::Hash;
+
+#[derive(Debug)]
+struct Entry<V> {
+    value: V,
+    history: VecDeque<u64>,
+}
+
+/// Cache LRU-K (O'Neil et al., 1993) qui évince selon le K-ième accès le
+/// plus récent
+///
+/// Chaque entrée mémorise les instants de ses K derniers accès (`get` ou
+/// `put`). Les entrées qui n'ont pas encore K accès sont évincées en
+/// priorité, de la moins récemment utilisée à la plus récente, comme avec
+/// LRU. Parmi les autres, l'entrée évincée est celle dont le K-ième accès le
+/// plus récent est le plus ancien : deux accès rapprochés suivis d'un
+/// abandon ne suffisent pas à garder une clé face à une clé relue
+/// régulièrement. Avec `k = 1`, le cache se comporte comme un LRU.
+///
+/// # Examples
+/// ```
+/// use lru_cache::cache::lru_k::LruKCache;
+/// use lru_cache::cache::traits::CacheStorage;
+///
+/// let mut cache = LruKCache::new(2, 2);
+/// cache.put("hot", 1);
+/// cache.get(&"hot");
+/// cache.put("a", 2);
+/// cache.put("b", 3);
+/// assert_eq!(cache.get(&"a"), None);
+/// assert_eq!(cache.get(&"hot"), Some(&1));
+/// ```
+#[derive(Debug)]
+pub struct LruKCache<K, V> {
+    capacity: usize,
+    k: usize,
+    entries: HashMap<K, Entry<V>>,
+    order: BTreeMap<(bool, u64), K>,
+    tick: u64,
+}
+
+impl<K: Clone + Eq + Hash, V> LruKCache<K, V> {
+    /// Crée un cache de capacité `capacity` qui retient les `k` derniers
+    /// accès de chaque entrée (`k` est ramené à 1 s'il est nul)
+    pub fn new(capacity: usize, k: usize) -> Self {
+        LruKCache {
+            capacity,
+            k: k.max(1),
+            entries: HashMap::new(),
+            order: BTreeMap::new(),
+            tick: 0,
+        }
+    }
+
+    /// Retourne la capacité du cache
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Retourne le nombre d'accès mémorisés par entrée
+    pub fn k(&self) -> usize {
+        self.k
+    }
+
+    /// Retourne le nombre d'accès mémorisés pour une clé, au plus `k`
+    pub fn history_len(&self, key: &K) -> Option<usize> {
+        self.entries.get(key).map(|entry| entry.history.len())
+    }
+
+    /// Clé d'ordre d'une entrée : les historiques incomplets d'abord, triés
+    /// par dernier accès, puis les autres triés par K-ième accès
+    fn rank(&self, history: &VecDeque<u64>) -> (bool, u64) {
+        if history.len() < self.k {
+            (false, *history.back().expect("historique vide"))
+        } else {
+            (true, *history.front().expect("historique vide"))
+        }
+    }
+
+    fn next_tick(&mut self) -> u64 {
+        self.tick += 1;
+        self.tick
+    }
+
+    fn access(&mut self, key: &K) -> Option<&mut Entry<V>> {
+        let tick = self.next_tick();
+        let k = self.k;
+        let rank = self.rank(&self.entries.get(key)?.history);
+        let owned = self.order.remove(&rank).expect("entrée absente de l'ordre LRU-K");
+        let entry = self.entries.get_mut(key)?;
+        if entry.history.len() == k {
+            entry.history.pop_front();
+        }
+        entry.history.push_back(tick);
+        let rank = if entry.history.len() < k {
+            (false, tick)
+        } else {
+            (true, entry.history[0])
+        };
+        self.order.insert(rank, owned);
+        Some(entry)
+    }
+}
+
+impl<K: Clone + Eq + Hash, V> CacheStorage<K, V> for LruKCache<K, V> {
+    fn get(&mut self, key: &K) -> Option<&V> {
+        self.access(key).map(|entry| &entry.value)
+    }
+
+    fn get_with(&mut self, key: &K, options: GetOptions) -> Option<&V> {
+        if options.promote {
+            return self.get(key);
+        }
+        self.entries.get(key).map(|entry| &entry.value)
+    }
+
+    fn put(&mut self, key: K, value: V) {
+        if let Some(entry) = self.access(&key) {
+            entry.value = value;
+            return;
+        }
+        if self.capacity == 0 {
+            return;
+        }
+        if self.entries.len() >= self.capacity {
+            if let Some((_, victim)) = self.order.pop_first() {
+                self.entries.remove(&victim);
+            }
+        }
+        let tick = self.next_tick();
+        let history = VecDeque::from([tick]);
+        self.order.insert(self.rank(&history), key.clone());
+        self.entries.insert(key, Entry { value, history });
+    }
+}
+
+impl<K: Clone + Eq + Hash, V> CacheRemove<K, V> for LruKCache<K, V> {
+    fn remove(&mut self, key: &K) -> Option<V> {
+        let entry = self.entries.remove(key)?;
+        self.order.remove(&self.rank(&entry.history));
+        Some(entry.value)
+    }
+}
+
+impl<K, V> CacheLen for LruKCache<K, V> {
+    fn len(&self) -> usize {
+        self.entries.len()
+    }
+}
+
+/// Itérateur sur les entrées d'un [`LruKCache`], de la prochaine évincée à
+/// la dernière
+pub struct Iter<'a, K, V> {
+    order: btree_map::Values<'a, (bool, u64), K>,
+    entries: &'a HashMap<K, Entry<V>>,
+}
+
+impl<'a, K: Eq + Hash, V> Iterator for Iter<'a, K, V> {
+    type Item = (&'a K, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let key = self.order.next()?;
+        self.entries.get_key_value(key).map(|(key, entry)| (key, &entry.value))
+    }
+}
+
+impl<K: Eq + Hash, V> CacheIterate<K, V> for LruKCache<K, V> {
+    type Iter<'a> = Iter<'a, K, V> where Self: 'a, K: 'a, V: 'a;
+
+    fn iter(&self) -> Self::Iter<'_> {
+        Iter {
+            order: self.order.values(),
+            entries: &self.entries,
+        }
+    }
+}
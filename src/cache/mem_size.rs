@@ -0,0 +1,90 @@
+//! Estimation de la mémoire occupée par les clés et les valeurs
+
+use super::list::Node;
+use super::lru::Meta;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::mem::size_of;
+
+/// Taille approximative en octets d'une valeur, y compris ce qu'elle alloue
+/// sur le tas
+///
+/// La taille comprend `size_of::<Self>()` : un entier mesure donc sa taille
+/// en ligne, une `String` ajoute sa capacité allouée.
+///
+/// # Examples
+/// ```
+/// use lru_cache::cache::mem_size::MemSize;
+/// use std::mem::size_of;
+///
+/// let text = String::with_capacity(16);
+/// assert_eq!(text.mem_size(), size_of::<String>() + 16);
+/// assert_eq!(7u32.mem_size(), 4);
+/// ```
+pub trait MemSize {
+    /// Retourne la taille estimée en octets
+    fn mem_size(&self) -> usize;
+}
+
+/// Surcoût fixe estimé par entrée d'un [`Cache`](super::lru::Cache), en
+/// plus de la taille de la clé et de la valeur
+///
+/// Il compte l'emplacement du nœud (liens de l'ordre d'utilisation, chaînage
+/// de l'index, empreinte, génération et métadonnées) et l'entrée de la table
+/// de hachage avec son octet de contrôle. La place réservée mais inoccupée
+/// n'est pas comptée.
+pub const ENTRY_OVERHEAD: usize =
+    size_of::<Option<Node<(), (), Meta>>>() + size_of::<u64>() + size_of::<(u64, usize)>() + 1;
+
+/// Ce qu'une valeur alloue hors de sa taille en ligne
+fn heap_size<T: MemSize>(value: &T) -> usize {
+    value.mem_size() - size_of::<T>()
+}
+
+macro_rules! inline_mem_size {
+    ($($ty:ty),*) => {
+        $(impl MemSize for $ty {
+            fn mem_size(&self) -> usize {
+                size_of::<Self>()
+            }
+        })*
+    };
+}
+
+inline_mem_size!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize, bool, char, f32, f64, ());
+
+impl MemSize for String {
+    fn mem_size(&self) -> usize {
+        size_of::<Self>() + self.capacity()
+    }
+}
+
+impl<T: MemSize> MemSize for Vec<T> {
+    fn mem_size(&self) -> usize {
+        let unused = (self.capacity() - self.len()) * size_of::<T>();
+        size_of::<Self>() + unused + self.iter().map(MemSize::mem_size).sum::<usize>()
+    }
+}
+
+impl<T: MemSize> MemSize for Option<T> {
+    fn mem_size(&self) -> usize {
+        size_of::<Self>() + self.as_ref().map_or(0, heap_size)
+    }
+}
+
+macro_rules! tuple_mem_size {
+    ($($name:ident),+) => {
+        impl<$($name: MemSize),+> MemSize for ($($name,)+) {
+            #[allow(non_snake_case)]
+            fn mem_size(&self) -> usize {
+                let ($($name,)+) = self;
+                size_of::<Self>() $(+ heap_size($name))+
+            }
+        }
+    };
+}
+
+tuple_mem_size!(A);
+tuple_mem_size!(A, B);
+tuple_mem_size!(A, B, C);
+tuple_mem_size!(A, B, C, D);
@@ -0,0 +1,68 @@
+//! Exposition des compteurs d'un cache au format texte de Prometheus
+
+use super::stats::CacheStats;
+use alloc::format;
+use alloc::string::String;
+use core::fmt::Write;
+
+/// Valeurs exposées par [`render`]
+pub(crate) struct Snapshot {
+    pub(crate) stats: CacheStats,
+    pub(crate) len: usize,
+    pub(crate) capacity: usize,
+    pub(crate) total_weight: usize,
+}
+
+/// Produit les métriques de `snapshot`, toujours dans le même ordre : les
+/// compteurs puis les jauges, chacun précédé de ses lignes `# HELP` et
+/// `# TYPE`
+///
+/// Si `name` est fourni, chaque échantillon porte le label `cache="name"`.
+pub(crate) fn render(prefix: &str, name: Option<&str>, snapshot: &Snapshot) -> String {
+    let stats = &snapshot.stats;
+    let counters = [
+        ("hits_total", "Lookups that found a live entry.", stats.hits),
+        ("misses_total", "Lookups that found no live entry.", stats.misses),
+        ("insertions_total", "Insertions of an absent key.", stats.insertions),
+        ("updates_total", "Replacements of the value of a present key.", stats.updates),
+        ("evictions_total", "Entries evicted to make room.", stats.evictions),
+        ("expirations_total", "Expired entries removed.", stats.expirations),
+    ];
+    let gauges = [
+        ("entries", "Entries currently stored.", snapshot.len),
+        ("capacity", "Maximum number of entries or total weight.", snapshot.capacity),
+        ("total_weight", "Total weight of the stored entries.", snapshot.total_weight),
+    ];
+    let labels = name
+        .map(|name| format!("{{cache=\"{}\"}}", escape_label(name)))
+        .unwrap_or_default();
+    let mut out = String::new();
+    for (suffix, help, value) in counters {
+        sample(&mut out, prefix, suffix, help, "counter", &labels, value as u128);
+    }
+    for (suffix, help, value) in gauges {
+        sample(&mut out, prefix, suffix, help, "gauge", &labels, value as u128);
+    }
+    out
+}
+
+fn sample(out: &mut String, prefix: &str, suffix: &str, help: &str, kind: &str, labels: &str, value: u128) {
+    let name = format!("{}_{}", prefix, suffix);
+    // L'écriture dans une `String` ne peut pas échouer
+    let _ = writeln!(out, "# HELP {} {}", name, help);
+    let _ = writeln!(out, "# TYPE {} {}", name, kind);
+    let _ = writeln!(out, "{}{} {}", name, labels, value);
+}
+
+fn escape_label(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '\\' => escaped.push_str("\\\\"),
+            '"' => escaped.push_str("\\\""),
+            '\n' => escaped.push_str("\\n"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
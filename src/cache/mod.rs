@@ -1,2 +1,63 @@
+pub mod adapters;
+pub mod arc_policy;
+pub mod builder;
+pub mod clock;
+#[cfg(feature = "std")]
+pub mod clock_cache;
+#[cfg(feature = "std")]
+pub mod concurrent;
+pub mod equivalent;
+pub mod error;
+#[cfg(feature = "std")]
+pub mod fifo;
+#[cfg(feature = "std")]
+pub(crate) mod flight;
+#[cfg(feature = "std")]
+pub mod frozen;
+#[cfg(feature = "std")]
+pub mod global;
+pub(crate) mod hit_order;
+#[cfg(feature = "std")]
+pub mod lfu;
+pub(crate) mod list;
+pub mod listener;
+pub mod loading;
 pub mod lru;
-pub mod traits;
\ No newline at end of file
+#[cfg(feature = "std")]
+pub mod lru_k;
+pub mod mem_size;
+pub(crate) mod metrics;
+#[cfg(feature = "test-util")]
+pub mod model;
+pub mod ordered;
+#[cfg(feature = "std")]
+pub mod persistent;
+pub mod policy;
+#[cfg(feature = "std")]
+pub mod random;
+pub(crate) mod rng;
+#[cfg(feature = "std")]
+pub mod sampled;
+pub mod scoped;
+pub mod simulate;
+#[cfg(feature = "std")]
+pub mod sharded;
+pub mod slru;
+pub mod snapshot;
+pub mod stats;
+pub mod testing;
+#[cfg(feature = "std")]
+pub mod tiered;
+#[cfg(feature = "std")]
+pub mod tiny_lfu;
+pub mod traits;
+pub mod transaction;
+pub mod ttl;
+pub mod two_q;
+#[cfg(feature = "std")]
+pub mod watched;
+pub mod weak;
+pub mod weigher;
+pub(crate) mod wheel;
+#[cfg(feature = "std")]
+pub mod write_through;
@@ -0,0 +1,214 @@
+//! Comparaison de `Cache` avec un modèle de référence (feature `test-util`)
+//!
+//! [`random_ops`] tire une suite reproductible d'opérations (`put`, `get`,
+//! `remove`, `resize`) et [`check_model`] l'applique à la fois à un
+//! [`Cache`] et à [`ReferenceLru`], un LRU naïf dans un `Vec`. Après chaque
+//! opération, les résultats et l'ordre d'éviction doivent être identiques et
+//! [`Cache::check_invariants`] doit réussir. [`shrink`] réduit une suite
+//! divergente à une suite plus courte qui diverge encore.
+//!
+//! # Examples
+//! ```
+//! use lru_cache::cache::model::{check_model, random_ops};
+//! use std::num::NonZeroUsize;
+//!
+//! let capacity = NonZeroUsize::new(3).unwrap();
+//! for seed in 0..10 {
+//!     let ops = random_ops(seed, 200, 8, 4);
+//!     assert_eq!(check_model(capacity, &ops), Ok(()));
+//! }
+//! ```
+
+use super::error::InvariantError;
+use super::lru::Cache;
+use super::rng::XorShift64;
+use super::traits::{CacheIterate, CacheRemove, CacheStorage};
+use core::fmt;
+use core::num::NonZeroUsize;
+
+/// Opération appliquée au cache et au modèle
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Op {
+    Put(u32, u32),
+    Get(u32),
+    Remove(u32),
+    Resize(NonZeroUsize),
+}
+
+/// Tire `len` opérations sur les clés `0..keys` avec la graine `seed`
+///
+/// Les capacités tirées pour `Resize` sont comprises entre 1 et
+/// `max_capacity`.
+///
+/// # Panics
+///
+/// Panique si `keys` ou `max_capacity` vaut 0.
+pub fn random_ops(seed: u64, len: usize, keys: u32, max_capacity: usize) -> Vec<Op> {
+    assert!(keys > 0 && max_capacity > 0, "il faut au moins une clé et une capacité");
+    let mut rng = XorShift64::new(seed);
+    (0..len)
+        .map(|_| {
+            let key = rng.below(keys as usize) as u32;
+            match rng.below(10) {
+                0..=3 => Op::Put(key, rng.next_u64() as u32),
+                4..=6 => Op::Get(key),
+                7 | 8 => Op::Remove(key),
+                _ => Op::Resize(NonZeroUsize::new(1 + rng.below(max_capacity)).expect("capacité non nulle")),
+            }
+        })
+        .collect()
+}
+
+/// LRU de référence : les entrées sont gardées dans un `Vec`, de la moins
+/// récemment utilisée à la plus récente, et chaque opération le parcourt
+#[derive(Debug, Clone)]
+pub struct ReferenceLru {
+    capacity: usize,
+    entries: Vec<(u32, u32)>,
+}
+
+impl ReferenceLru {
+    /// Crée un modèle vide de capacité `capacity`
+    pub fn new(capacity: NonZeroUsize) -> Self {
+        ReferenceLru {
+            capacity: capacity.get(),
+            entries: Vec::new(),
+        }
+    }
+
+    /// Insère ou remplace `key`, qui devient la plus récente
+    pub fn put(&mut self, key: u32, value: u32) {
+        self.remove(key);
+        if self.entries.len() == self.capacity {
+            self.entries.remove(0);
+        }
+        self.entries.push((key, value));
+    }
+
+    /// Lit `key`, qui devient la plus récente
+    pub fn get(&mut self, key: u32) -> Option<u32> {
+        let value = self.remove(key)?;
+        self.entries.push((key, value));
+        Some(value)
+    }
+
+    /// Retire `key` et retourne sa valeur
+    pub fn remove(&mut self, key: u32) -> Option<u32> {
+        let position = self.entries.iter().position(|&(stored, _)| stored == key)?;
+        Some(self.entries.remove(position).1)
+    }
+
+    /// Change la capacité et retire les entrées les plus anciennes en trop
+    pub fn resize(&mut self, capacity: NonZeroUsize) {
+        self.capacity = capacity.get();
+        let excess = self.entries.len().saturating_sub(self.capacity);
+        self.entries.drain(..excess);
+    }
+
+    /// Entrées dans l'ordre d'éviction
+    pub fn entries(&self) -> &[(u32, u32)] {
+        &self.entries
+    }
+}
+
+/// Première divergence entre le cache et le modèle
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Divergence {
+    /// Position de l'opération fautive dans la suite
+    pub step: usize,
+    /// Opération fautive
+    pub op: Op,
+    /// Nature de la divergence
+    pub kind: DivergenceKind,
+}
+
+/// Nature d'une [`Divergence`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DivergenceKind {
+    /// Le cache et le modèle ont retourné des résultats différents
+    Result { cache: Option<u32>, model: Option<u32> },
+    /// Les entrées ou leur ordre d'éviction diffèrent
+    Entries { cache: Vec<(u32, u32)>, model: Vec<(u32, u32)> },
+    /// [`Cache::check_invariants`] a échoué
+    Invariant(InvariantError),
+}
+
+impl fmt::Display for Divergence {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "étape {} ({:?}) : ", self.step, self.op)?;
+        match &self.kind {
+            DivergenceKind::Result { cache, model } => {
+                write!(f, "le cache retourne {:?}, le modèle {:?}", cache, model)
+            }
+            DivergenceKind::Entries { cache, model } => {
+                write!(f, "le cache contient {:?}, le modèle {:?}", cache, model)
+            }
+            DivergenceKind::Invariant(error) => write!(f, "{}", error),
+        }
+    }
+}
+
+impl std::error::Error for Divergence {}
+
+/// Applique `ops` à un cache et à un [`ReferenceLru`] de capacité
+/// `capacity`, en les comparant après chaque opération
+///
+/// # Errors
+///
+/// Retourne la première [`Divergence`] constatée.
+pub fn check_model(capacity: NonZeroUsize, ops: &[Op]) -> Result<(), Divergence> {
+    let mut cache = Cache::with_capacity(capacity);
+    let mut model = ReferenceLru::new(capacity);
+    for (step, &op) in ops.iter().enumerate() {
+        let diverged = |kind| Divergence { step, op, kind };
+        let (from_cache, from_model) = match op {
+            Op::Put(key, value) => {
+                cache.put(key, value);
+                model.put(key, value);
+                (None, None)
+            }
+            Op::Get(key) => (cache.get(&key).copied(), model.get(key)),
+            Op::Remove(key) => (cache.remove(&key), model.remove(key)),
+            Op::Resize(capacity) => {
+                cache.resize(capacity);
+                model.resize(capacity);
+                (None, None)
+            }
+        };
+        if from_cache != from_model {
+            return Err(diverged(DivergenceKind::Result {
+                cache: from_cache,
+                model: from_model,
+            }));
+        }
+        cache.check_invariants().map_err(|error| diverged(DivergenceKind::Invariant(error)))?;
+        let entries: Vec<_> = cache.iter().map(|(&key, &value)| (key, value)).collect();
+        if entries != model.entries() {
+            return Err(diverged(DivergenceKind::Entries {
+                cache: entries,
+                model: model.entries().to_vec(),
+            }));
+        }
+    }
+    Ok(())
+}
+
+/// Réduit une suite qui diverge en retirant des opérations tant que
+/// [`check_model`] échoue encore, et retourne la suite réduite
+pub fn shrink(capacity: NonZeroUsize, ops: &[Op]) -> Vec<Op> {
+    let mut ops = ops.to_vec();
+    if check_model(capacity, &ops).is_ok() {
+        return ops;
+    }
+    let mut index = 0;
+    while index < ops.len() {
+        let mut candidate = ops.clone();
+        candidate.remove(index);
+        if check_model(capacity, &candidate).is_err() {
+            ops = candidate;
+        } else {
+            index += 1;
+        }
+    }
+    ops
+}
@@ -0,0 +1,211 @@
+use super::traits::{CacheIterate, CacheLen, CacheRemove, CacheStorage, GetOptions};
+#[cfg(feature = "std")]
+use super::traits::PersistentStorage;
+use alloc::collections::{btree_map, BTreeMap};
+use core::ops::RangeBounds;
+#[cfg(feature = "std")]
+use std::fmt::Display;
+#[cfg(feature = "std")]
+use std::num::NonZeroUsize;
+#[cfg(feature = "std")]
+use std::path::Path;
+#[cfg(feature = "std")]
+use std::str::FromStr;
+
+#[derive(Debug, Clone)]
+struct Slot<V> {
+    value: V,
+    /// Rang de la dernière utilisation, clé de `recency`
+    tick: u64,
+}
+
+/// Cache LRU dont les clés sont aussi rangées dans leur ordre naturel, pour
+/// lire toutes les entrées d'un intervalle de clés
+///
+/// Les entrées sont indexées par un `BTreeMap` plutôt qu'une table de
+/// hachage, et l'ordre d'utilisation est tenu à part : l'éviction est
+/// exactement celle de [`Cache`](super::lru::Cache), indépendante de l'ordre
+/// des clés. Chaque clé est stockée deux fois, d'où `K: Clone`, et chaque
+/// opération coûte un temps logarithmique.
+///
+/// [`OrderedCache::range`], [`OrderedCache::first_key_value`] et
+/// [`OrderedCache::last_key_value`] ne modifient pas l'ordre d'utilisation ;
+/// [`OrderedCache::range_touch`] compte les entrées lues comme utilisées.
+///
+/// # Examples
+/// ```
+/// use lru_cache::cache::ordered::OrderedCache;
+/// use lru_cache::cache::traits::CacheStorage;
+///
+/// let mut cache = OrderedCache::new(3);
+/// cache.put(30, "c");
+/// cache.put(10, "a");
+/// cache.put(20, "b");
+/// let found: Vec<_> = cache.range(15..=30).collect();
+/// assert_eq!(found, vec![(&20, &"b"), (&30, &"c")]);
+///
+/// cache.put(40, "d");
+/// assert_eq!(cache.get(&30), None);
+/// assert_eq!(cache.first_key_value(), Some((&10, &"a")));
+/// ```
+#[derive(Debug, Clone)]
+pub struct OrderedCache<K, V> {
+    capacity: usize,
+    entries: BTreeMap<K, Slot<V>>,
+    /// Clés de la moins récemment utilisée à la plus récente
+    recency: BTreeMap<u64, K>,
+    next_tick: u64,
+}
+
+impl<K: Ord + Clone, V> OrderedCache<K, V> {
+    /// Crée un nouveau cache avec la capacité spécifiée
+    ///
+    /// Un cache de capacité 0 ne garde aucune entrée.
+    pub fn new(capacity: usize) -> Self {
+        OrderedCache {
+            capacity,
+            entries: BTreeMap::new(),
+            recency: BTreeMap::new(),
+            next_tick: 0,
+        }
+    }
+
+    /// Retourne la capacité du cache
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Parcourt dans l'ordre croissant des clés les entrées dont la clé est
+    /// dans `range`, sans modifier l'ordre d'utilisation
+    pub fn range<R: RangeBounds<K>>(&self, range: R) -> impl Iterator<Item = (&K, &V)> + '_ {
+        self.entries.range(range).map(|(key, slot)| (key, &slot.value))
+    }
+
+    /// Comme [`OrderedCache::range`], en marquant chaque entrée parcourue
+    /// comme utilisée
+    ///
+    /// Les entrées sont marquées dans l'ordre croissant des clés : la plus
+    /// grande clé de l'intervalle devient la plus récemment utilisée.
+    pub fn range_touch<R: RangeBounds<K>>(&mut self, range: R) -> impl Iterator<Item = (&K, &V)> + '_ {
+        let bounds = (range.start_bound(), range.end_bound());
+        for slot in self.entries.range_mut::<K, _>(bounds).map(|(_, slot)| slot) {
+            Self::touch(&mut self.recency, &mut self.next_tick, slot);
+        }
+        self.entries
+            .range::<K, _>(bounds)
+            .map(|(key, slot)| (key, &slot.value))
+    }
+
+    /// Retourne l'entrée de plus petite clé, sans modifier l'ordre
+    /// d'utilisation
+    pub fn first_key_value(&self) -> Option<(&K, &V)> {
+        self.entries.first_key_value().map(|(key, slot)| (key, &slot.value))
+    }
+
+    /// Retourne l'entrée de plus grande clé, sans modifier l'ordre
+    /// d'utilisation
+    pub fn last_key_value(&self) -> Option<(&K, &V)> {
+        self.entries.last_key_value().map(|(key, slot)| (key, &slot.value))
+    }
+
+    /// Marque `slot` comme la plus récemment utilisée
+    fn touch(recency: &mut BTreeMap<u64, K>, next_tick: &mut u64, slot: &mut Slot<V>) {
+        let key = recency.remove(&slot.tick).expect("entrée absente de l'ordre d'utilisation");
+        slot.tick = *next_tick;
+        *next_tick += 1;
+        recency.insert(slot.tick, key);
+    }
+}
+
+impl<K: Ord + Clone, V> CacheStorage<K, V> for OrderedCache<K, V> {
+    fn get(&mut self, key: &K) -> Option<&V> {
+        self.get_with(key, GetOptions::default())
+    }
+
+    fn get_with(&mut self, key: &K, options: GetOptions) -> Option<&V> {
+        let slot = self.entries.get_mut(key)?;
+        if options.promote {
+            Self::touch(&mut self.recency, &mut self.next_tick, slot);
+        }
+        Some(&slot.value)
+    }
+
+    fn put(&mut self, key: K, value: V) {
+        if let Some(slot) = self.entries.get_mut(&key) {
+            slot.value = value;
+            Self::touch(&mut self.recency, &mut self.next_tick, slot);
+            return;
+        }
+        if self.capacity == 0 {
+            return;
+        }
+        if self.entries.len() >= self.capacity {
+            if let Some((_, oldest)) = self.recency.pop_first() {
+                self.entries.remove(&oldest);
+            }
+        }
+        let tick = self.next_tick;
+        self.next_tick += 1;
+        self.recency.insert(tick, key.clone());
+        self.entries.insert(key, Slot { value, tick });
+    }
+}
+
+impl<K: Ord + Clone, V> CacheRemove<K, V> for OrderedCache<K, V> {
+    fn remove(&mut self, key: &K) -> Option<V> {
+        let slot = self.entries.remove(key)?;
+        self.recency.remove(&slot.tick);
+        Some(slot.value)
+    }
+}
+
+impl<K, V> CacheLen for OrderedCache<K, V> {
+    fn len(&self) -> usize {
+        self.entries.len()
+    }
+}
+
+/// Itérateur sur les entrées d'un [`OrderedCache`], de la moins récemment
+/// utilisée à la plus récente
+pub struct Iter<'a, K, V> {
+    recency: btree_map::Values<'a, u64, K>,
+    entries: &'a BTreeMap<K, Slot<V>>,
+}
+
+impl<'a, K: Ord, V> Iterator for Iter<'a, K, V> {
+    type Item = (&'a K, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let key = self.recency.next()?;
+        let (key, slot) = self.entries.get_key_value(key)?;
+        Some((key, &slot.value))
+    }
+}
+
+impl<K: Ord, V> CacheIterate<K, V> for OrderedCache<K, V> {
+    type Iter<'a> = Iter<'a, K, V> where Self: 'a, K: 'a, V: 'a;
+
+    fn iter(&self) -> Self::Iter<'_> {
+        Iter {
+            recency: self.recency.values(),
+            entries: &self.entries,
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<K: Ord + Clone + Display + FromStr, V: Display + FromStr> PersistentStorage<K, V> for OrderedCache<K, V> {
+    fn save_to_file(&self, path: impl AsRef<Path>) -> std::io::Result<()> {
+        let data: Vec<_> = self.iter().collect();
+        crate::storage::file::FileStorage::save(path, self.capacity, &data)
+    }
+
+    fn load_from_file(path: impl AsRef<Path>, capacity: NonZeroUsize) -> std::io::Result<Self> {
+        let (_, data) = crate::storage::file::FileStorage::load(path)?;
+        let mut cache = OrderedCache::new(capacity.get());
+        for (key, value) in data {
+            cache.put(key, value);
+        }
+        Ok(cache)
+    }
+}
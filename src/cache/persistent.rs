@@ -0,0 +1,162 @@
+//! Cache lié à un fichier, sauvegardé automatiquement à sa destruction
+
+use super::lru::{check_capacity, Cache};
+use super::traits::PersistentStorage;
+use std::fmt::{self, Display};
+use std::fs;
+use std::hash::Hash;
+use std::io;
+use std::ops::{Deref, DerefMut};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+type ErrorHandler = Box<dyn FnMut(&Path, &io::Error) + Send>;
+
+/// [`Cache`] qui se recharge depuis son fichier à l'ouverture et s'y
+/// sauvegarde lorsqu'il est détruit
+///
+/// Le cache s'utilise directement à travers `Deref`/`DerefMut`. Une erreur
+/// pendant la sauvegarde de `Drop` ne peut pas être propagée : elle est
+/// transmise au gestionnaire défini par
+/// [`PersistentCache::set_error_handler`] ; sans gestionnaire, elle n'est
+/// signalée que par un événement `tracing` (fonctionnalité `tracing`). Pour
+/// traiter l'erreur, appeler [`PersistentCache::flush`] avant la
+/// destruction.
+///
+/// # Examples
+/// ```
+/// use lru_cache::cache::persistent::PersistentCache;
+/// use lru_cache::cache::traits::CacheStorage;
+///
+/// let path = std::env::temp_dir().join("persistent_doc").join("cache.txt");
+/// {
+///     let mut cache = PersistentCache::<String, i32>::open(&path, 10).unwrap();
+///     cache.put(String::from("key"), 1);
+/// }
+///
+/// let mut cache = PersistentCache::<String, i32>::open(&path, 10).unwrap();
+/// assert_eq!(cache.get(&String::from("key")), Some(&1));
+/// # drop(cache);
+/// # std::fs::remove_dir_all(std::env::temp_dir().join("persistent_doc")).unwrap();
+/// ```
+pub struct PersistentCache<K, V>
+where
+    K: Eq + Hash + Display + FromStr,
+    V: Display + FromStr,
+{
+    cache: Cache<K, V>,
+    path: PathBuf,
+    on_error: Option<ErrorHandler>,
+}
+
+impl<K, V> PersistentCache<K, V>
+where
+    K: Eq + Hash + Display + FromStr,
+    V: Display + FromStr,
+{
+    /// Ouvre le cache lié à `path`, chargé depuis le fichier s'il existe
+    ///
+    /// Ni le fichier ni son répertoire n'ont besoin d'exister : ils sont
+    /// créés à la première sauvegarde.
+    ///
+    /// # Errors
+    ///
+    /// Retourne une erreur si le fichier existe mais ne peut pas être lu,
+    /// ou une erreur [`io::ErrorKind::InvalidInput`] si `capacity` vaut 0.
+    pub fn open(path: impl Into<PathBuf>, capacity: usize) -> io::Result<Self> {
+        let path = path.into();
+        let capacity = check_capacity(capacity)?;
+        let cache = if path.exists() {
+            Cache::load_from_file(&path, capacity)?
+        } else {
+            Cache::with_capacity(capacity)
+        };
+        Ok(PersistentCache {
+            cache,
+            path,
+            on_error: None,
+        })
+    }
+
+    /// Remplace le gestionnaire des erreurs de la sauvegarde automatique,
+    /// appelé avec le chemin du fichier et l'erreur
+    pub fn set_error_handler<F>(&mut self, handler: F)
+    where
+        F: FnMut(&Path, &io::Error) + Send + 'static,
+    {
+        self.on_error = Some(Box::new(handler));
+    }
+
+    /// Retourne le chemin du fichier
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Sauvegarde le cache maintenant, en créant le répertoire du fichier
+    /// si nécessaire
+    ///
+    /// # Errors
+    ///
+    /// Retourne une erreur si le répertoire ou le fichier ne peut pas être
+    /// écrit ; le fichier précédent reste alors intact.
+    pub fn flush(&mut self) -> io::Result<()> {
+        if let Some(parent) = self.path.parent() {
+            if !parent.as_os_str().is_empty() {
+                fs::create_dir_all(parent)?;
+            }
+        }
+        self.cache.save_to_file(&self.path)
+    }
+}
+
+impl<K, V> Deref for PersistentCache<K, V>
+where
+    K: Eq + Hash + Display + FromStr,
+    V: Display + FromStr,
+{
+    type Target = Cache<K, V>;
+
+    fn deref(&self) -> &Cache<K, V> {
+        &self.cache
+    }
+}
+
+impl<K, V> DerefMut for PersistentCache<K, V>
+where
+    K: Eq + Hash + Display + FromStr,
+    V: Display + FromStr,
+{
+    fn deref_mut(&mut self) -> &mut Cache<K, V> {
+        &mut self.cache
+    }
+}
+
+impl<K, V> Drop for PersistentCache<K, V>
+where
+    K: Eq + Hash + Display + FromStr,
+    V: Display + FromStr,
+{
+    fn drop(&mut self) {
+        if let Err(error) = self.flush() {
+            match self.on_error.as_mut() {
+                Some(handler) => handler(&self.path, &error),
+                None => {
+                    trace_event!(warn, path = %self.path.display(), %error, "cache save on drop failed");
+                }
+            }
+        }
+    }
+}
+
+impl<K, V> fmt::Debug for PersistentCache<K, V>
+where
+    K: Eq + Hash + Display + FromStr + fmt::Debug,
+    V: Display + FromStr + fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PersistentCache")
+            .field("cache", &self.cache)
+            .field("path", &self.path)
+            .finish_non_exhaustive()
+    }
+}
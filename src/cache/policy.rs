@@ -0,0 +1,249 @@
+//! Choix de la politique d'éviction du [`Cache`](super::lru::Cache)
+//!
+//! Le trait [`EvictionPolicy`] choisit l'entrée évincée : le cache lui
+//! signale les insertions, accès et suppressions, et lui demande quelle
+//! entrée évincer. [`Lru`] (la politique par défaut), [`Mru`], [`Fifo`] et
+//! [`TwoChoice`] l'implémentent.
+//!
+//! L'énumération [`Policy`], qui sélectionnait une politique intégrée au
+//! cache, est dépréciée au profit de ces types. Elle ne s'applique qu'à un
+//! cache de politique [`Lru`] : [`CacheBuilder::build_with_eviction_policy`]
+//! refuse une configuration qui combine les deux.
+//!
+//! [`CacheBuilder::build_with_eviction_policy`]: super::builder::CacheBuilder::build_with_eviction_policy
+
+use super::hit_order::HitOrder;
+use super::list::NodeList;
+use super::rng::XorShift64;
+use core::hash::Hash;
+
+/// Politique utilisée pour choisir l'entrée évincée quand le cache est plein
+///
+/// Dans tous les cas, une entrée expirée est évincée en priorité. Une
+/// politique autre que `Lru` remplace celle d'un cache de politique [`Lru`],
+/// le seul auquel elle puisse s'appliquer.
+#[deprecated(note = "utiliser `Cache::with_eviction_policy` avec `policy::Mru` ou `policy::TwoChoice`")]
+#[allow(deprecated)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Policy {
+    /// Évince l'entrée la moins récemment utilisée
+    #[default]
+    Lru,
+    /// Évince l'entrée la plus récemment utilisée, comme [`Mru`]
+    Mru,
+    /// Compare l'entrée la moins récemment utilisée et l'entrée la moins
+    /// souvent lue (compteur de lectures par entrée) et évince la plus
+    /// froide, comme [`TwoChoice`]
+    ///
+    /// Les égalités sont départagées par un générateur initialisé avec `seed`,
+    /// ce qui rend les évictions reproductibles. Les entrées sont rangées par
+    /// nombre de lectures : l'éviction ne parcourt pas le cache.
+    TwoChoice {
+        /// Graine du départage des égalités
+        seed: u64,
+    },
+}
+
+/// Entrées du cache proposées à [`EvictionPolicy::choose_victim`]
+///
+/// Les clés sont parcourues de la moins récemment utilisée à la plus récente
+/// (`next`), ou dans l'ordre inverse (`next_back`). Les entrées épinglées ne
+/// sont pas candidates.
+pub struct Candidates<'a, K> {
+    keys: &'a mut dyn DoubleEndedIterator<Item = &'a K>,
+    contains: &'a dyn Fn(&K) -> bool,
+}
+
+impl<'a, K> Candidates<'a, K> {
+    pub(crate) fn new(keys: &'a mut dyn DoubleEndedIterator<Item = &'a K>, contains: &'a dyn Fn(&K) -> bool) -> Self {
+        Candidates { keys, contains }
+    }
+
+    /// Indique si `key` est candidate, sans parcourir les entrées
+    pub fn contains(&self, key: &K) -> bool {
+        (self.contains)(key)
+    }
+}
+
+impl<'a, K> Iterator for Candidates<'a, K> {
+    type Item = &'a K;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.keys.next()
+    }
+}
+
+impl<K> DoubleEndedIterator for Candidates<'_, K> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.keys.next_back()
+    }
+}
+
+/// Choix de l'entrée évincée par un [`Cache`](super::lru::Cache)
+///
+/// Le cache appelle `on_insert` pour chaque nouvelle clé, `on_access` quand
+/// une entrée est lue ou mise à jour, et `on_remove` quand une entrée quitte
+/// le cache pour n'importe quelle raison (éviction comprise). Quand il doit
+/// faire de la place, il appelle `choose_victim` avec les entrées qui peuvent
+/// être évincées ; la clé retournée doit être l'une d'elles, sinon aucune
+/// entrée n'est évincée. Les
+/// entrées expirées restent évincées en priorité, sans consulter la
+/// politique.
+///
+/// Les méthodes `on_*` ne font rien par défaut : une politique qui se
+/// contente de l'ordre d'utilisation tenu par le cache n'a pas d'état.
+pub trait EvictionPolicy<K> {
+    /// Une nouvelle clé a été insérée
+    fn on_insert(&mut self, key: &K) {
+        let _ = key;
+    }
+
+    /// L'entrée a été lue ou sa valeur remplacée
+    fn on_access(&mut self, key: &K) {
+        let _ = key;
+    }
+
+    /// L'entrée a quitté le cache
+    fn on_remove(&mut self, key: &K) {
+        let _ = key;
+    }
+
+    /// Retourne la clé de l'entrée à évincer
+    fn choose_victim<'a>(&'a mut self, candidates: Candidates<'a, K>) -> Option<&'a K>;
+}
+
+/// Évince l'entrée la moins récemment utilisée
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Lru;
+
+impl<K> EvictionPolicy<K> for Lru {
+    fn choose_victim<'a>(&'a mut self, mut candidates: Candidates<'a, K>) -> Option<&'a K> {
+        candidates.next()
+    }
+}
+
+/// Évince l'entrée la plus récemment utilisée
+///
+/// Adapté aux parcours séquentiels répétés d'un ensemble légèrement plus
+/// grand que le cache, où LRU évince chaque entrée juste avant qu'elle soit
+/// relue. Les lectures continuent de promouvoir les entrées.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Mru;
+
+impl<K> EvictionPolicy<K> for Mru {
+    fn choose_victim<'a>(&'a mut self, mut candidates: Candidates<'a, K>) -> Option<&'a K> {
+        candidates.next_back()
+    }
+}
+
+/// Évince l'entrée insérée depuis le plus longtemps, sans tenir compte des
+/// accès
+///
+/// Une mise à jour de la valeur ne change pas l'ordre d'insertion. La
+/// politique garde une copie de chaque clé.
+#[derive(Debug)]
+pub struct Fifo<K> {
+    order: NodeList<K, (), ()>,
+}
+
+impl<K> Fifo<K> {
+    /// Crée une politique sans clé
+    pub fn new() -> Self {
+        Fifo { order: NodeList::new() }
+    }
+}
+
+impl<K> Default for Fifo<K> {
+    fn default() -> Self {
+        Fifo::new()
+    }
+}
+
+impl<K: Clone + Eq + Hash> EvictionPolicy<K> for Fifo<K> {
+    fn on_insert(&mut self, key: &K) {
+        self.order.push(key.clone(), (), ());
+    }
+
+    fn on_remove(&mut self, key: &K) {
+        if let Some(index) = self.order.find(key) {
+            self.order.remove(index);
+        }
+    }
+
+    fn choose_victim<'a>(&'a mut self, candidates: Candidates<'a, K>) -> Option<&'a K> {
+        core::iter::successors(self.order.head(), |&index| self.order.next(index))
+            .map(|index| &self.order.node(index).key)
+            .find(|key| candidates.contains(key))
+    }
+}
+
+/// Compare l'entrée la moins récemment utilisée et l'entrée la moins
+/// souvent utilisée, et évince la plus froide
+///
+/// La politique compte les accès de chaque clé, lectures et mises à jour, et
+/// garde une copie de chaque clé, rangée par nombre d'accès : la moins
+/// utilisée est trouvée sans parcourir les candidates, entrées épinglées
+/// mises à part. Parmi les moins utilisées, la première arrivée à ce nombre
+/// d'accès est choisie ; une égalité avec la moins récente est départagée
+/// par un générateur initialisé avec la graine, ce qui rend les évictions
+/// reproductibles.
+#[derive(Debug)]
+pub struct TwoChoice<K> {
+    keys: NodeList<K, (), ()>,
+    /// Nombre d'accès de chaque clé de `keys` depuis son insertion
+    hits: HitOrder,
+    rng: XorShift64,
+}
+
+impl<K> TwoChoice<K> {
+    /// Crée une politique sans clé dont les égalités sont départagées par la
+    /// graine `seed`
+    pub fn new(seed: u64) -> Self {
+        TwoChoice {
+            keys: NodeList::new(),
+            hits: HitOrder::new(),
+            rng: XorShift64::new(seed),
+        }
+    }
+}
+
+impl<K: Clone + Eq + Hash> TwoChoice<K> {
+    fn hits(&self, key: &K) -> u64 {
+        self.keys.find(key).map_or(0, |index| self.hits.hits(index))
+    }
+}
+
+impl<K: Clone + Eq + Hash> EvictionPolicy<K> for TwoChoice<K> {
+    fn on_insert(&mut self, key: &K) {
+        let index = self.keys.push(key.clone(), (), ());
+        self.hits.set(index, 0);
+    }
+
+    fn on_access(&mut self, key: &K) {
+        if let Some(index) = self.keys.find(key) {
+            self.hits.increment(index);
+        }
+    }
+
+    fn on_remove(&mut self, key: &K) {
+        if let Some(index) = self.keys.find(key) {
+            self.keys.remove(index);
+            self.hits.remove(index);
+        }
+    }
+
+    fn choose_victim<'a>(&'a mut self, mut candidates: Candidates<'a, K>) -> Option<&'a K> {
+        let coldest = candidates.next()?;
+        let coldest_hits = self.hits(coldest);
+        let (least_used, least_hits) = self
+            .hits
+            .iter()
+            .map(|index| (&self.keys.node(index).key, self.hits.hits(index)))
+            .find(|(key, _)| candidates.contains(key))?;
+        if coldest_hits > least_hits || self.rng.below(2) == 0 {
+            Some(least_used)
+        } else {
+            Some(coldest)
+        }
+    }
+}
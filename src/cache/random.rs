@@ -0,0 +1,120 @@
+use super::rng::XorShift64;
+use super::traits::{CacheIterate, CacheLen, CacheRemove, CacheStorage};
+use std::collections::hash_map::RandomState;
+use std::collections::HashMap;
+use std::hash::{BuildHasher, Hash};
+
+/// Cache à remplacement aléatoire : quand il est plein, une insertion évince
+/// une entrée choisie uniformément parmi les entrées présentes
+///
+/// Sert de référence pour mesurer ce qu'apporte une politique d'éviction :
+/// ni les lectures ni l'ordre d'insertion n'influencent le choix. Les
+/// entrées sont rangées dans un vecteur, ce qui rend l'éviction O(1).
+///
+/// # Examples
+/// ```
+/// use lru_cache::cache::random::RandomCache;
+/// use lru_cache::cache::traits::{CacheLen, CacheStorage};
+///
+/// let mut cache = RandomCache::with_seed(2, 42);
+/// cache.put("A", 1);
+/// cache.put("B", 2);
+/// cache.put("C", 3);
+/// assert_eq!(cache.len(), 2);
+/// assert_eq!(cache.get(&"C"), Some(&3));
+/// ```
+#[derive(Debug)]
+pub struct RandomCache<K, V> {
+    capacity: usize,
+    entries: Vec<(K, V)>,
+    index: HashMap<K, usize>,
+    rng: XorShift64,
+}
+
+impl<K: Clone + Eq + Hash, V> RandomCache<K, V> {
+    /// Crée un cache dont les évictions dépendent d'une graine aléatoire
+    pub fn new(capacity: usize) -> Self {
+        RandomCache::with_seed(capacity, RandomState::new().hash_one(capacity))
+    }
+
+    /// Crée un cache dont la suite des évictions est fixée par `seed`
+    pub fn with_seed(capacity: usize, seed: u64) -> Self {
+        RandomCache {
+            capacity,
+            entries: Vec::new(),
+            index: HashMap::new(),
+            rng: XorShift64::new(seed),
+        }
+    }
+
+    /// Retourne la capacité du cache
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    fn remove_at(&mut self, position: usize) -> (K, V) {
+        let (key, value) = self.entries.swap_remove(position);
+        self.index.remove(&key);
+        if let Some((moved, _)) = self.entries.get(position) {
+            *self.index.get_mut(moved).expect("clé absente de l'index") = position;
+        }
+        (key, value)
+    }
+}
+
+impl<K: Clone + Eq + Hash, V> CacheStorage<K, V> for RandomCache<K, V> {
+    fn get(&mut self, key: &K) -> Option<&V> {
+        let position = *self.index.get(key)?;
+        Some(&self.entries[position].1)
+    }
+
+    fn put(&mut self, key: K, value: V) {
+        if let Some(&position) = self.index.get(&key) {
+            self.entries[position].1 = value;
+            return;
+        }
+        if self.capacity == 0 {
+            return;
+        }
+        if self.entries.len() >= self.capacity {
+            let victim = self.rng.below(self.entries.len());
+            self.remove_at(victim);
+        }
+        self.index.insert(key.clone(), self.entries.len());
+        self.entries.push((key, value));
+    }
+}
+
+impl<K: Clone + Eq + Hash, V> CacheRemove<K, V> for RandomCache<K, V> {
+    fn remove(&mut self, key: &K) -> Option<V> {
+        let position = *self.index.get(key)?;
+        Some(self.remove_at(position).1)
+    }
+}
+
+impl<K, V> CacheLen for RandomCache<K, V> {
+    fn len(&self) -> usize {
+        self.entries.len()
+    }
+}
+
+/// Itérateur sur les entrées d'un [`RandomCache`], dans un ordre quelconque
+pub struct Iter<'a, K, V>(std::slice::Iter<'a, (K, V)>);
+
+impl<'a, K, V> Iterator for Iter<'a, K, V> {
+    type Item = (&'a K, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next().map(|(key, value)| (key, value))
+    }
+}
+
+impl<K, V> CacheIterate<K, V> for RandomCache<K, V> {
+    type Iter<'a> = Iter<'a, K, V> where Self: 'a, K: 'a, V: 'a;
+
+    /// Parcourt les entrées dans un ordre quelconque : le choix de la
+    /// prochaine entrée évincée n'est pas connu à l'avance
+    fn iter(&self) -> Self::Iter<'_> {
+        Iter(self.entries.iter())
+    }
+}
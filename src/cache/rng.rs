@@ -0,0 +1,44 @@
+//! Générateur pseudo-aléatoire minimal (xorshift64*) pour les choix aléatoires
+//! reproductibles des politiques d'éviction
+
+/// Générateur xorshift64* initialisable par une graine
+#[derive(Debug, Clone)]
+pub(crate) struct XorShift64 {
+    state: u64,
+}
+
+impl XorShift64 {
+    pub(crate) fn new(seed: u64) -> Self {
+        // L'état ne doit jamais être nul
+        XorShift64 {
+            state: seed ^ 0x9E37_79B9_7F4A_7C15,
+        }
+        .nonzero()
+    }
+
+    fn nonzero(mut self) -> Self {
+        if self.state == 0 {
+            self.state = 0x9E37_79B9_7F4A_7C15;
+        }
+        self
+    }
+
+    pub(crate) fn next_u64(&mut self) -> u64 {
+        let mut x = self.state;
+        x ^= x >> 12;
+        x ^= x << 25;
+        x ^= x >> 27;
+        self.state = x;
+        x.wrapping_mul(0x2545_F491_4F6C_DD1D)
+    }
+
+    /// Retourne un entier uniforme dans `0..bound` (`bound` non nul)
+    pub(crate) fn below(&mut self, bound: usize) -> usize {
+        (self.next_u64() % bound as u64) as usize
+    }
+
+    /// Retourne un flottant uniforme dans `[0, 1)`
+    pub(crate) fn unit(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+}
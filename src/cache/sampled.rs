@@ -0,0 +1,200 @@
+use super::rng::XorShift64;
+use super::traits::{CacheIterate, CacheLen, CacheRemove, CacheStorage, GetOptions};
+use std::collections::hash_map::RandomState;
+use std::collections::HashMap;
+use std::hash::{BuildHasher, Hash};
+
+/// Nombre d'entrées tirées par éviction par défaut
+pub const DEFAULT_SAMPLES: usize = 5;
+
+#[derive(Debug)]
+struct Slot<K, V> {
+    key: K,
+    value: V,
+    /// Rang de la dernière utilisation
+    tick: u64,
+}
+
+/// Cache LRU approché par échantillonnage, comme celui de Redis
+///
+/// Aucun ordre d'utilisation n'est tenu : chaque entrée garde seulement le
+/// rang logique de sa dernière utilisation, un compteur incrémenté à chaque
+/// lecture ou écriture sans lire l'horloge. Quand le cache est plein, une
+/// insertion tire `samples` entrées au hasard (avec remise) et évince la
+/// moins récemment utilisée d'entre elles. Une entrée n'est donc évincée
+/// que si aucune entrée tirée avec elle n'est plus ancienne ; la plus
+/// ancienne du cache peut survivre si elle n'est pas tirée.
+///
+/// Une lecture ne fait qu'écrire un compteur, sans déplacer de nœud, et les
+/// entrées sont rangées dans un vecteur : le cache est plus rapide et plus
+/// compact que [`Cache`](super::lru::Cache) sur de très grands volumes, au
+/// prix d'un taux de succès un peu moins bon. Plus `samples` est grand,
+/// plus l'éviction se rapproche du LRU exact, et plus elle coûte.
+///
+/// # Examples
+/// ```
+/// use lru_cache::cache::sampled::SampledLruCache;
+/// use lru_cache::cache::traits::{CacheLen, CacheStorage};
+///
+/// let mut cache = SampledLruCache::with_samples(2, 16, 42);
+/// cache.put("A", 1);
+/// cache.put("B", 2);
+/// cache.get(&"A");
+/// cache.put("C", 3);
+/// assert_eq!(cache.len(), 2);
+/// assert_eq!(cache.get(&"A"), Some(&1));
+/// ```
+#[derive(Debug)]
+pub struct SampledLruCache<K, V> {
+    capacity: usize,
+    samples: usize,
+    entries: Vec<Slot<K, V>>,
+    index: HashMap<K, usize>,
+    rng: XorShift64,
+    next_tick: u64,
+}
+
+impl<K: Clone + Eq + Hash, V> SampledLruCache<K, V> {
+    /// Crée un cache qui tire [`DEFAULT_SAMPLES`] entrées par éviction,
+    /// avec une graine aléatoire
+    ///
+    /// Un cache de capacité 0 ne garde aucune entrée.
+    pub fn new(capacity: usize) -> Self {
+        SampledLruCache::with_seed(capacity, RandomState::new().hash_one(capacity))
+    }
+
+    /// Crée un cache dont la suite des tirages est fixée par `seed`
+    pub fn with_seed(capacity: usize, seed: u64) -> Self {
+        SampledLruCache::with_samples(capacity, DEFAULT_SAMPLES, seed)
+    }
+
+    /// Crée un cache qui tire `samples` entrées par éviction, avec la graine
+    /// `seed`
+    ///
+    /// # Panics
+    ///
+    /// Panique si `samples` vaut 0.
+    pub fn with_samples(capacity: usize, samples: usize, seed: u64) -> Self {
+        assert!(samples > 0, "une éviction doit tirer au moins une entrée");
+        SampledLruCache {
+            capacity,
+            samples,
+            entries: Vec::new(),
+            index: HashMap::new(),
+            rng: XorShift64::new(seed),
+            next_tick: 0,
+        }
+    }
+
+    /// Retourne la capacité du cache
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Retourne le nombre d'entrées tirées par éviction
+    pub fn samples(&self) -> usize {
+        self.samples
+    }
+
+    /// Récupère une valeur sans la marquer comme utilisée
+    pub fn peek(&self, key: &K) -> Option<&V> {
+        let position = *self.index.get(key)?;
+        Some(&self.entries[position].value)
+    }
+
+    fn tick(&mut self) -> u64 {
+        self.next_tick += 1;
+        self.next_tick
+    }
+
+    /// Position de l'entrée la moins récemment utilisée parmi `samples`
+    /// entrées tirées au hasard
+    fn victim(&mut self) -> usize {
+        let mut victim = self.rng.below(self.entries.len());
+        for _ in 1..self.samples {
+            let candidate = self.rng.below(self.entries.len());
+            if self.entries[candidate].tick < self.entries[victim].tick {
+                victim = candidate;
+            }
+        }
+        victim
+    }
+
+    fn remove_at(&mut self, position: usize) -> Slot<K, V> {
+        let slot = self.entries.swap_remove(position);
+        self.index.remove(&slot.key);
+        if let Some(moved) = self.entries.get(position) {
+            *self.index.get_mut(&moved.key).expect("clé absente de l'index") = position;
+        }
+        slot
+    }
+}
+
+impl<K: Clone + Eq + Hash, V> CacheStorage<K, V> for SampledLruCache<K, V> {
+    fn get(&mut self, key: &K) -> Option<&V> {
+        self.get_with(key, GetOptions::default())
+    }
+
+    fn get_with(&mut self, key: &K, options: GetOptions) -> Option<&V> {
+        let position = *self.index.get(key)?;
+        if options.promote {
+            let tick = self.tick();
+            self.entries[position].tick = tick;
+        }
+        Some(&self.entries[position].value)
+    }
+
+    fn put(&mut self, key: K, value: V) {
+        let tick = self.tick();
+        if let Some(&position) = self.index.get(&key) {
+            let slot = &mut self.entries[position];
+            slot.value = value;
+            slot.tick = tick;
+            return;
+        }
+        if self.capacity == 0 {
+            return;
+        }
+        if self.entries.len() >= self.capacity {
+            let victim = self.victim();
+            self.remove_at(victim);
+        }
+        self.index.insert(key.clone(), self.entries.len());
+        self.entries.push(Slot { key, value, tick });
+    }
+}
+
+impl<K: Clone + Eq + Hash, V> CacheRemove<K, V> for SampledLruCache<K, V> {
+    fn remove(&mut self, key: &K) -> Option<V> {
+        let position = *self.index.get(key)?;
+        Some(self.remove_at(position).value)
+    }
+}
+
+impl<K, V> CacheLen for SampledLruCache<K, V> {
+    fn len(&self) -> usize {
+        self.entries.len()
+    }
+}
+
+/// Itérateur sur les entrées d'un [`SampledLruCache`], dans un ordre
+/// quelconque
+pub struct Iter<'a, K, V>(std::slice::Iter<'a, Slot<K, V>>);
+
+impl<'a, K, V> Iterator for Iter<'a, K, V> {
+    type Item = (&'a K, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next().map(|slot| (&slot.key, &slot.value))
+    }
+}
+
+impl<K, V> CacheIterate<K, V> for SampledLruCache<K, V> {
+    type Iter<'a> = Iter<'a, K, V> where Self: 'a, K: 'a, V: 'a;
+
+    /// Parcourt les entrées dans un ordre quelconque, sans rapport avec
+    /// l'ordre d'utilisation
+    fn iter(&self) -> Self::Iter<'_> {
+        Iter(self.entries.iter())
+    }
+}
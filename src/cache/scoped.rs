@@ -0,0 +1,115 @@
+//! Espaces de noms au-dessus d'un cache partagé
+
+use super::lru::Cache;
+use super::policy::{EvictionPolicy, Lru};
+use super::traits::{CacheIterate, CacheRemove, CacheStorage};
+use core::fmt;
+use core::hash::Hash;
+
+/// Vue d'un [`Cache`] limitée à un espace de noms, obtenue par
+/// [`Cache::scoped`]
+///
+/// Les clés de la vue sont rangées dans le cache sous `(namespace, key)` :
+/// deux espaces de noms peuvent utiliser les mêmes clés sans se gêner, et
+/// partagent la capacité du cache. L'éviction reste globale : une insertion
+/// dans un espace de noms peut évincer l'entrée la moins récemment utilisée
+/// d'un autre. Les lectures et suppressions clonent l'espace de noms et la
+/// clé pour former la clé composée.
+///
+/// # Examples
+/// ```
+/// use lru_cache::Cache;
+///
+/// let mut cache = Cache::new(3);
+/// cache.scoped("users").put(1, "alice");
+/// cache.scoped("groups").put(1, "admins");
+/// assert_eq!(cache.scoped("users").get(&1), Some(&"alice"));
+/// assert_eq!(cache.scoped("groups").get(&1), Some(&"admins"));
+/// ```
+pub struct ScopedCache<'a, N, K, V, P = Lru> {
+    cache: &'a mut Cache<(N, K), V, P>,
+    namespace: N,
+}
+
+impl<'a, N, K, V, P> ScopedCache<'a, N, K, V, P> {
+    pub(super) fn new(cache: &'a mut Cache<(N, K), V, P>, namespace: N) -> Self {
+        ScopedCache { cache, namespace }
+    }
+
+    /// Retourne l'espace de noms de la vue
+    pub fn namespace(&self) -> &N {
+        &self.namespace
+    }
+}
+
+impl<N, K, V, P> ScopedCache<'_, N, K, V, P>
+where
+    N: Eq + Hash + Clone,
+    K: Eq + Hash + Clone,
+    P: EvictionPolicy<(N, K)>,
+{
+    fn composite(&self, key: &K) -> (N, K) {
+        (self.namespace.clone(), key.clone())
+    }
+
+    /// Récupère la valeur associée à `key` dans l'espace de noms et la
+    /// marque comme la plus récemment utilisée de tout le cache
+    pub fn get(&mut self, key: &K) -> Option<&V> {
+        let key = self.composite(key);
+        self.cache.get(&key)
+    }
+
+    /// Lit la valeur associée à `key` sans modifier l'ordre d'utilisation
+    pub fn peek(&self, key: &K) -> Option<&V> {
+        self.cache.peek(&self.composite(key))
+    }
+
+    /// Indique si `key` est présente dans l'espace de noms
+    pub fn contains_key(&self, key: &K) -> bool {
+        self.cache.contains_key(&self.composite(key))
+    }
+
+    /// Insère ou remplace `key` dans l'espace de noms, en évinçant si
+    /// besoin dans n'importe quel espace de noms
+    pub fn put(&mut self, key: K, value: V) {
+        self.cache.put((self.namespace.clone(), key), value);
+    }
+
+    /// Supprime `key` de l'espace de noms et retourne sa valeur
+    pub fn remove(&mut self, key: &K) -> Option<V> {
+        let key = self.composite(key);
+        self.cache.remove(&key)
+    }
+
+    /// Parcourt les entrées de l'espace de noms de la prochaine évincée à
+    /// la plus récente
+    pub fn iter(&self) -> impl Iterator<Item = (&K, &V)> {
+        self.cache
+            .iter()
+            .filter(|((namespace, _), _)| *namespace == self.namespace)
+            .map(|((_, key), value)| (key, value))
+    }
+
+    /// Retourne le nombre d'entrées de l'espace de noms, en parcourant le
+    /// cache
+    pub fn len(&self) -> usize {
+        self.iter().count()
+    }
+
+    /// Indique si l'espace de noms ne contient aucune entrée
+    pub fn is_empty(&self) -> bool {
+        self.iter().next().is_none()
+    }
+
+    /// Supprime toutes les entrées de l'espace de noms, comme
+    /// [`Cache::clear_namespace`]
+    pub fn clear(&mut self) -> usize {
+        self.cache.clear_namespace(&self.namespace)
+    }
+}
+
+impl<N: fmt::Debug, K, V, P> fmt::Debug for ScopedCache<'_, N, K, V, P> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ScopedCache").field("namespace", &self.namespace).finish_non_exhaustive()
+    }
+}
@@ -0,0 +1,160 @@
+//! Cache partageable entre threads, réparti sur plusieurs verrous
+
+use super::lru::Cache;
+use super::stats::CacheStats;
+use super::traits::{CacheLen, CacheRemove, CacheStorage, GetOptions};
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hash};
+use std::sync::{Mutex, MutexGuard, PoisonError};
+use std::thread;
+
+/// Ensemble de [`Cache`] indépendants, chacun protégé par son propre verrou,
+/// entre lesquels les clés sont réparties selon leur hash
+///
+/// Deux threads qui accèdent à des clés de partitions différentes ne se
+/// bloquent pas, contrairement à
+/// [`ConcurrentCache`](super::concurrent::ConcurrentCache). En contrepartie,
+/// l'ordre LRU n'est qu'approximatif à l'échelle du cache : chaque partition
+/// gère sa propre liste et évince sa propre entrée la moins récemment
+/// utilisée, même si une entrée plus ancienne existe dans une autre
+/// partition. Une partition peut ainsi évincer alors que le cache dans son
+/// ensemble n'est pas plein.
+///
+/// # Examples
+/// ```
+/// use lru_cache::cache::sharded::ShardedCache;
+/// use std::sync::Arc;
+/// use std::thread;
+///
+/// let cache = Arc::new(ShardedCache::new(1024));
+/// let writer = Arc::clone(&cache);
+/// thread::spawn(move || writer.put("key", 1)).join().unwrap();
+/// assert_eq!(cache.get(&"key"), Some(1));
+/// ```
+#[derive(Debug)]
+pub struct ShardedCache<K, V> {
+    shards: Box<[Mutex<Cache<K, V>>]>,
+    hasher: RandomState,
+}
+
+impl<K: Eq + Hash, V> ShardedCache<K, V> {
+    /// Crée un cache dont le nombre de partitions est le nombre de
+    /// processeurs disponibles arrondi à la puissance de deux supérieure
+    pub fn new(capacity: usize) -> Self {
+        let cpus = thread::available_parallelism().map_or(1, |cpus| cpus.get());
+        ShardedCache::with_shards(capacity, cpus)
+    }
+
+    /// Crée un cache réparti sur `shards` partitions, arrondi à la puissance
+    /// de deux supérieure
+    ///
+    /// La capacité est partagée aussi également que possible entre les
+    /// partitions. Le nombre de partitions est réduit si nécessaire pour que
+    /// chacune puisse contenir au moins une entrée.
+    ///
+    /// # Panics
+    ///
+    /// Panique si `capacity` vaut 0, comme [`Cache::new`].
+    pub fn with_shards(capacity: usize, shards: usize) -> Self {
+        let mut count = shards.max(1).next_power_of_two();
+        while count > 1 && count > capacity {
+            count /= 2;
+        }
+        let shards = (0..count)
+            .map(|index| Mutex::new(Cache::new(capacity / count + usize::from(index < capacity % count))))
+            .collect();
+        ShardedCache {
+            shards,
+            hasher: RandomState::new(),
+        }
+    }
+
+    /// Verrouille la partition qui contient `key` pour accéder à toute
+    /// l'API du cache
+    ///
+    /// Comme pour [`ConcurrentCache::lock`](super::concurrent::ConcurrentCache::lock),
+    /// un verrou empoisonné est récupéré.
+    pub fn lock_shard(&self, key: &K) -> MutexGuard<'_, Cache<K, V>> {
+        let index = self.hasher.hash_one(key) as usize & (self.shards.len() - 1);
+        lock(&self.shards[index])
+    }
+
+    /// Récupère une copie de la valeur et marque l'entrée comme utilisée
+    /// dans sa partition
+    pub fn get(&self, key: &K) -> Option<V>
+    where
+        V: Clone,
+    {
+        self.lock_shard(key).get(key).cloned()
+    }
+
+    /// Comme [`ShardedCache::get`], en choisissant les effets de la lecture
+    /// (voir [`CacheStorage::get_with`])
+    pub fn get_with(&self, key: &K, options: GetOptions) -> Option<V>
+    where
+        V: Clone,
+    {
+        self.lock_shard(key).get_with(key, options).cloned()
+    }
+
+    /// Insère une valeur dans la partition de sa clé
+    pub fn put(&self, key: K, value: V) {
+        self.lock_shard(&key).put(key, value);
+    }
+
+    /// Supprime une entrée et retourne sa valeur
+    pub fn remove(&self, key: &K) -> Option<V> {
+        self.lock_shard(key).remove(key)
+    }
+
+    /// Retire les entrées expirées de toutes les partitions et retourne leur
+    /// nombre
+    pub fn purge_expired(&self) -> usize {
+        self.shards.iter().map(|shard| lock(shard).purge_expired()).sum()
+    }
+
+    /// Retourne la somme des capacités des partitions
+    pub fn capacity(&self) -> usize {
+        self.shards.iter().map(|shard| lock(shard).capacity()).sum()
+    }
+
+    /// Retourne la somme des compteurs d'activité des partitions
+    pub fn stats(&self) -> CacheStats {
+        let mut total = CacheStats::default();
+        for shard in self.shards.iter() {
+            total += lock(shard).stats();
+        }
+        total
+    }
+
+    /// Remet à zéro les compteurs d'activité de toutes les partitions
+    pub fn reset_stats(&self) {
+        for shard in self.shards.iter() {
+            lock(shard).reset_stats();
+        }
+    }
+}
+
+impl<K, V> ShardedCache<K, V> {
+    /// Retourne le nombre de partitions
+    pub fn shard_count(&self) -> usize {
+        self.shards.len()
+    }
+
+    /// Retourne le nombre d'entrées de toutes les partitions, expirées ou non
+    ///
+    /// Les partitions sont verrouillées l'une après l'autre : sous écriture
+    /// concurrente, le total n'est pas un instantané exact.
+    pub fn len(&self) -> usize {
+        self.shards.iter().map(|shard| lock(shard).len()).sum()
+    }
+
+    /// Indique si le cache est vide
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+fn lock<K, V>(shard: &Mutex<Cache<K, V>>) -> MutexGuard<'_, Cache<K, V>> {
+    shard.lock().unwrap_or_else(PoisonError::into_inner)
+}
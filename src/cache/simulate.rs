@@ -0,0 +1,89 @@
+//! Simulation d'une trace d'accès sur plusieurs capacités, pour
+//! dimensionner un cache
+//!
+//! Chaque capacité candidate reçoit son propre cache réel, et la trace est
+//! parcourue une seule fois : chaque clé est lue dans tous les caches puis
+//! insérée dans ceux où elle manquait, comme le fait
+//! [`run_trace`](super::testing::run_trace). Les résultats reflètent donc
+//! le comportement exact des caches simulés, y compris
+//! [`SlruCache`](super::slru::SlruCache) ou
+//! [`TwoQCache`](super::two_q::TwoQCache) avec [`simulate`].
+//!
+//! # Examples
+//! ```
+//! use lru_cache::cache::simulate::simulate_lru;
+//!
+//! let results = simulate_lru(&[1, 2], ["A", "B", "A", "B"]);
+//! assert_eq!(results[0].stats.hits, 0);
+//! assert_eq!(results[1].stats.hits, 2);
+//! ```
+
+use super::lru::Cache;
+use super::testing::TraceStats;
+use super::traits::{CacheLen, CacheStorage};
+use alloc::vec::Vec;
+use core::hash::Hash;
+
+/// Résultat de la simulation pour une capacité
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SimResult {
+    /// Capacité simulée
+    pub capacity: usize,
+    /// Succès, échecs et évictions observés sur la trace
+    pub stats: TraceStats,
+}
+
+impl SimResult {
+    /// Retourne la part des lectures réussies (0 pour une trace vide)
+    pub fn hit_rate(&self) -> f64 {
+        self.stats.hit_rate()
+    }
+}
+
+/// Rejoue `trace` sur un [`Cache`] LRU par capacité de `capacities`, en un
+/// seul parcours, et retourne les résultats dans l'ordre des capacités
+///
+/// # Panics
+///
+/// Panique si une capacité vaut 0, comme [`Cache::new`].
+pub fn simulate_lru<K: Eq + Hash + Clone>(capacities: &[usize], trace: impl IntoIterator<Item = K>) -> Vec<SimResult> {
+    simulate(capacities, trace, Cache::<K, ()>::new)
+}
+
+/// Comme [`simulate_lru`], avec les caches construits par `build` pour
+/// chaque capacité
+///
+/// # Examples
+/// ```
+/// use lru_cache::cache::simulate::simulate;
+/// use lru_cache::cache::two_q::TwoQCache;
+///
+/// let trace = [1, 2, 1, 3, 4, 5, 1];
+/// let results = simulate(&[2, 4], trace, TwoQCache::<u32, ()>::new);
+/// assert_eq!(results[1].capacity, 4);
+/// assert_eq!(results[1].stats.misses + results[1].stats.hits, 7);
+/// ```
+pub fn simulate<K, C>(
+    capacities: &[usize],
+    trace: impl IntoIterator<Item = K>,
+    mut build: impl FnMut(usize) -> C,
+) -> Vec<SimResult>
+where
+    K: Clone,
+    C: CacheStorage<K, ()> + CacheLen,
+{
+    let mut caches: Vec<_> = capacities.iter().map(|&capacity| build(capacity)).collect();
+    let mut results: Vec<_> = capacities
+        .iter()
+        .map(|&capacity| SimResult {
+            capacity,
+            stats: TraceStats::default(),
+        })
+        .collect();
+    for key in trace {
+        for (cache, result) in caches.iter_mut().zip(&mut results) {
+            result.stats.access(cache, &key);
+        }
+    }
+    results
+}
@@ -0,0 +1,162 @@
+use super::list::{self, NodeList};
+use super::traits::{CacheIterate, CacheLen, CacheRemove, CacheStorage, GetOptions};
+use core::hash::Hash;
+use core::iter::Chain;
+
+/// Cache SLRU (Segmented LRU) à deux segments
+///
+/// Une nouvelle entrée entre dans le segment probatoire. Une lecture d'une
+/// entrée probatoire la promeut dans le segment protégé ; lorsque celui-ci
+/// est plein, son entrée la moins récemment utilisée redescend dans le
+/// segment probatoire. Les évictions se font toujours depuis l'extrémité LRU
+/// du segment probatoire : une rafale de clés lues une seule fois ne peut
+/// donc pas évincer les entrées déjà relues.
+///
+/// Un `put` sur une clé existante remplace la valeur et marque l'entrée
+/// comme la plus récente de son segment, sans la promouvoir.
+///
+/// # Examples
+/// ```
+/// use lru_cache::cache::slru::SlruCache;
+/// use lru_cache::cache::traits::CacheStorage;
+///
+/// let mut cache = SlruCache::new(2, 2);
+/// cache.put("hot", 1);
+/// cache.get(&"hot");
+/// for key in ["a", "b", "c", "d"] {
+///     cache.put(key, 0);
+/// }
+/// assert_eq!(cache.get(&"hot"), Some(&1));
+/// assert_eq!(cache.protected_len(), 1);
+/// ```
+#[derive(Debug)]
+pub struct SlruCache<K, V> {
+    probation_capacity: usize,
+    protected_capacity: usize,
+    probation: NodeList<K, V, ()>,
+    protected: NodeList<K, V, ()>,
+}
+
+impl<K: Eq + Hash, V> SlruCache<K, V> {
+    /// Crée un cache dont les segments probatoire et protégé contiennent au
+    /// plus `probation_capacity` et `protected_capacity` entrées
+    pub fn new(probation_capacity: usize, protected_capacity: usize) -> Self {
+        SlruCache {
+            probation_capacity,
+            protected_capacity,
+            probation: NodeList::new(),
+            protected: NodeList::new(),
+        }
+    }
+
+    /// Retourne la capacité totale du cache
+    pub fn capacity(&self) -> usize {
+        self.probation_capacity + self.protected_capacity
+    }
+
+    /// Retourne le nombre d'entrées du segment probatoire
+    pub fn probation_len(&self) -> usize {
+        self.probation.len()
+    }
+
+    /// Retourne le nombre d'entrées du segment protégé
+    pub fn protected_len(&self) -> usize {
+        self.protected.len()
+    }
+
+    fn promote(&mut self, index: usize) -> &V {
+        if self.protected_capacity == 0 {
+            self.probation.move_to_back(index);
+            return &self.probation.node(index).value;
+        }
+        let node = self.probation.remove(index);
+        if self.protected.len() >= self.protected_capacity {
+            if let Some(coldest) = self.protected.head() {
+                let demoted = self.protected.remove(coldest);
+                self.probation.push(demoted.key, demoted.value, ());
+            }
+        }
+        let index = self.protected.push(node.key, node.value, ());
+        &self.protected.node(index).value
+    }
+}
+
+impl<K: Eq + Hash, V> CacheStorage<K, V> for SlruCache<K, V> {
+    fn get(&mut self, key: &K) -> Option<&V> {
+        if let Some(index) = self.protected.find(key) {
+            self.protected.move_to_back(index);
+            return Some(&self.protected.node(index).value);
+        }
+        let index = self.probation.find(key)?;
+        Some(self.promote(index))
+    }
+
+    fn get_with(&mut self, key: &K, options: GetOptions) -> Option<&V> {
+        if options.promote {
+            return self.get(key);
+        }
+        let (segment, index) = match self.protected.find(key) {
+            Some(index) => (&self.protected, index),
+            None => (&self.probation, self.probation.find(key)?),
+        };
+        Some(&segment.node(index).value)
+    }
+
+    fn put(&mut self, key: K, value: V) {
+        if let Some(index) = self.protected.find(&key) {
+            self.protected.node_mut(index).value = value;
+            self.protected.move_to_back(index);
+            return;
+        }
+        if let Some(index) = self.probation.find(&key) {
+            self.probation.node_mut(index).value = value;
+            self.probation.move_to_back(index);
+            return;
+        }
+        if self.probation_capacity == 0 {
+            return;
+        }
+        if self.probation.len() >= self.probation_capacity {
+            if let Some(oldest) = self.probation.head() {
+                self.probation.remove(oldest);
+            }
+        }
+        self.probation.push(key, value, ());
+    }
+}
+
+impl<K: Eq + Hash, V> CacheRemove<K, V> for SlruCache<K, V> {
+    fn remove(&mut self, key: &K) -> Option<V> {
+        if let Some(index) = self.protected.find(key) {
+            return Some(self.protected.remove(index).value);
+        }
+        let index = self.probation.find(key)?;
+        Some(self.probation.remove(index).value)
+    }
+}
+
+impl<K, V> CacheLen for SlruCache<K, V> {
+    fn len(&self) -> usize {
+        self.probation.len() + self.protected.len()
+    }
+}
+
+/// Itérateur sur les entrées d'un [`SlruCache`] : le segment probatoire puis
+/// le segment protégé, chacun du moins au plus récemment utilisé
+pub struct Iter<'a, K, V>(Chain<list::Iter<'a, K, V, ()>, list::Iter<'a, K, V, ()>>);
+
+impl<'a, K, V> Iterator for Iter<'a, K, V> {
+    type Item = (&'a K, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next()
+    }
+}
+
+impl<K, V> CacheIterate<K, V> for SlruCache<K, V> {
+    type Iter<'a> = Iter<'a, K, V> where Self: 'a, K: 'a, V: 'a;
+
+    fn iter(&self) -> Self::Iter<'_> {
+        Iter(self.probation.iter().chain(self.protected.iter()))
+    }
+}
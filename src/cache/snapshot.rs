@@ -0,0 +1,76 @@
+//! Copie en mémoire de l'état d'un cache
+
+use alloc::vec::Vec;
+use core::num::NonZeroUsize;
+use core::time::Duration;
+
+/// État d'un [`Cache`](super::lru::Cache) copié par
+/// [`Cache::snapshot`](super::lru::Cache::snapshot), que
+/// [`Cache::restore`](super::lru::Cache::restore) reconstruit à l'identique
+///
+/// La copie contient la capacité et les entrées de la prochaine évincée à
+/// la plus récente, avec ce qui décide de leur éviction : épinglage, durée
+/// de vie et échéance restante, compteur de lectures. Les clés et valeurs
+/// sont clonées telles quelles, sans passer par `Display` ni `FromStr`.
+/// La configuration du cache (politique, pondération, écouteurs) et ses
+/// statistiques n'en font pas partie.
+///
+/// # Examples
+/// ```
+/// use lru_cache::Cache;
+/// use lru_cache::cache::traits::CacheStorage;
+///
+/// let mut cache = Cache::new(2);
+/// cache.put("A", vec![1]);
+/// cache.put("B", vec![2]);
+/// let snapshot = cache.snapshot();
+/// assert_eq!(snapshot.iter().map(|(key, _)| *key).collect::<Vec<_>>(), ["A", "B"]);
+///
+/// let mut restored = Cache::restore(snapshot);
+/// restored.put("C", vec![3]);
+/// assert_eq!(restored.get(&"A"), None);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CacheSnapshot<K, V> {
+    pub(crate) capacity: NonZeroUsize,
+    pub(crate) entries: Vec<SnapshotEntry<K, V>>,
+}
+
+/// Entrée d'un [`CacheSnapshot`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct SnapshotEntry<K, V> {
+    pub(crate) key: K,
+    pub(crate) value: V,
+    pub(crate) pinned: bool,
+    pub(crate) hits: u64,
+    pub(crate) ttl: Option<Duration>,
+    /// Temps restant avant l'échéance au moment de la copie
+    pub(crate) remaining: Option<Duration>,
+}
+
+impl<K, V> CacheSnapshot<K, V> {
+    /// Retourne la capacité du cache copié
+    pub fn capacity(&self) -> usize {
+        self.capacity.get()
+    }
+
+    /// Retourne le nombre d'entrées copiées
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Indique si la copie ne contient aucune entrée
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Parcourt les entrées de la prochaine évincée à la plus récente
+    pub fn iter(&self) -> impl Iterator<Item = (&K, &V)> + '_ {
+        self.entries.iter().map(|entry| (&entry.key, &entry.value))
+    }
+
+    /// Retourne les entrées de la prochaine évincée à la plus récente
+    pub fn into_entries(self) -> Vec<(K, V)> {
+        self.entries.into_iter().map(|entry| (entry.key, entry.value)).collect()
+    }
+}
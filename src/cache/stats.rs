@@ -0,0 +1,77 @@
+//! Compteurs d'activité d'un cache
+
+use super::listener::CacheEventListener;
+use core::ops::AddAssign;
+
+/// Compteurs d'activité d'un [`Cache`](super::lru::Cache) depuis sa création
+/// ou le dernier [`Cache::reset_stats`](super::lru::Cache::reset_stats)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct CacheStats {
+    /// Lectures qui ont trouvé une entrée vivante
+    pub hits: u64,
+    /// Lectures qui n'ont rien trouvé, entrée expirée comprise
+    pub misses: u64,
+    /// Insertions d'une clé absente
+    pub insertions: u64,
+    /// Remplacements de la valeur d'une clé présente
+    pub updates: u64,
+    /// Entrées évincées pour faire de la place ou par `evict_n` et `truncate`
+    pub evictions: u64,
+    /// Entrées expirées retirées du cache
+    pub expirations: u64,
+    /// Lectures manquées d'une clé encore dans la liste fantôme (voir
+    /// [`Cache::with_ghost_capacity`](super::lru::Cache::with_ghost_capacity)),
+    /// comptées aussi dans `misses`
+    pub ghost_hits: u64,
+}
+
+impl CacheStats {
+    /// Retourne la part des lectures réussies (0 si aucune lecture)
+    pub fn hit_ratio(&self) -> f64 {
+        let total = self.hits + self.misses;
+        if total == 0 {
+            return 0.0;
+        }
+        self.hits as f64 / total as f64
+    }
+}
+
+/// Additionne les compteurs, par exemple pour agréger ceux de plusieurs
+/// caches
+impl AddAssign for CacheStats {
+    fn add_assign(&mut self, other: CacheStats) {
+        self.hits += other.hits;
+        self.misses += other.misses;
+        self.insertions += other.insertions;
+        self.updates += other.updates;
+        self.evictions += other.evictions;
+        self.expirations += other.expirations;
+        self.ghost_hits += other.ghost_hits;
+    }
+}
+
+impl<K> CacheEventListener<K> for CacheStats {
+    fn on_hit(&mut self, _key: &K) {
+        self.hits += 1;
+    }
+
+    fn on_miss(&mut self, _key: &K) {
+        self.misses += 1;
+    }
+
+    fn on_insert(&mut self, _key: &K) {
+        self.insertions += 1;
+    }
+
+    fn on_update(&mut self, _key: &K) {
+        self.updates += 1;
+    }
+
+    fn on_evict(&mut self, _key: &K) {
+        self.evictions += 1;
+    }
+
+    fn on_expire(&mut self, _key: &K) {
+        self.expirations += 1;
+    }
+}
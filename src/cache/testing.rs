@@ -0,0 +1,402 @@
+//! Outils de débogage : journal des opérations et rejeu déterministe
+//!
+//! Un cache peut enregistrer ses opérations (`Cache::record_operations` ou
+//! `Cache::record_operations_hashed`) dans un journal borné en mémoire,
+//! exporté par `Cache::export_replay`. Le journal contient pour chaque
+//! opération son type, la clé (ou son hash) et son résultat, mais jamais les
+//! valeurs. [`replay`] reconstruit à partir de ce journal un cache dont les
+//! décisions d'éviction sont identiques à celles du cache d'origine.
+//!
+//! Les expirations liées au temps (TTL) ne sont pas rejouées : un journal
+//! provenant d'un cache avec TTL peut diverger lors du rejeu.
+//!
+//! Le module fournit aussi de quoi comparer des politiques sur une charge
+//! synthétique ([`zipf_trace`] et [`hit_rate`]) ou sur une trace réelle
+//! ([`read_trace`] et [`run_trace`], utilisés par `lru-cache-bench`).
+//!
+//! # Examples
+//! ```
+//! use lru_cache::Cache;
+//! use lru_cache::cache::testing::replay;
+//! use lru_cache::cache::traits::CacheStorage;
+//!
+//! let mut cache = Cache::new(2);
+//! cache.record_operations(100);
+//! cache.put("A", 1);
+//! cache.put("B", 2);
+//! cache.get(&"A");
+//! cache.put("C", 3);
+//!
+//! let replayed = replay(&cache.export_replay(), 2).unwrap();
+//! assert_eq!(replayed.evictions, vec![String::from("B")]);
+//! assert_eq!(replayed.cache.canonical_string(), cache.canonical_string());
+//! ```
+
+use super::lru::Cache;
+#[cfg(feature = "std")]
+use super::rng::XorShift64;
+use super::traits::{CacheIterate, CacheLen, CacheRemove, CacheStorage};
+use alloc::collections::VecDeque;
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::fmt;
+use core::hash::{Hash, Hasher};
+#[cfg(feature = "std")]
+use std::fs::File;
+#[cfg(feature = "std")]
+use std::hash::DefaultHasher;
+#[cfg(feature = "std")]
+use std::io::{self, BufRead, BufReader};
+#[cfg(feature = "std")]
+use std::path::Path;
+
+const HEADER: &str = "#replay v1";
+
+/// Journal borné des opérations d'un cache
+pub(crate) struct OperationLog<K> {
+    limit: usize,
+    hashed: bool,
+    format: fn(&K) -> String,
+    dropped: usize,
+    lines: VecDeque<String>,
+}
+
+impl<K> OperationLog<K> {
+    pub(crate) fn new(limit: usize, hashed: bool, format: fn(&K) -> String) -> Self {
+        OperationLog {
+            limit,
+            hashed,
+            format,
+            dropped: 0,
+            lines: VecDeque::new(),
+        }
+    }
+
+    pub(crate) fn key(&self, key: &K) -> String {
+        escape(&(self.format)(key))
+    }
+
+    pub(crate) fn push(&mut self, op: &str, key: &K, outcome: &str) {
+        if self.limit == 0 {
+            self.dropped += 1;
+            return;
+        }
+        if self.lines.len() == self.limit {
+            self.lines.pop_front();
+            self.dropped += 1;
+        }
+        let line = format!("{}\t{}\t{}", op, self.key(key), outcome);
+        self.lines.push_back(line);
+    }
+
+    pub(crate) fn export(&self, capacity: usize) -> String {
+        let mode = if self.hashed { "hashes" } else { "keys" };
+        let mut out = format!("{} {} capacity={} dropped={}\n", HEADER, mode, capacity, self.dropped);
+        for line in &self.lines {
+            out.push_str(line);
+            out.push('\n');
+        }
+        out
+    }
+}
+
+impl<K> fmt::Debug for OperationLog<K> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("OperationLog")
+            .field("limit", &self.limit)
+            .field("hashed", &self.hashed)
+            .field("dropped", &self.dropped)
+            .field("len", &self.lines.len())
+            .finish()
+    }
+}
+
+/// Hash stable d'une clé, identique d'une exécution à l'autre
+///
+/// Sans la feature `std`, l'algorithme (SipHash 2-4 au lieu de 1-3) et donc
+/// les valeurs diffèrent de celles d'un cache compilé avec `std`.
+pub(crate) fn stable_hash<K: Hash>(key: &K) -> String {
+    #[cfg(feature = "std")]
+    let mut hasher = DefaultHasher::new();
+    #[cfg(not(feature = "std"))]
+    #[allow(deprecated)]
+    let mut hasher = core::hash::SipHasher::new();
+    key.hash(&mut hasher);
+    format!("{:016x}", hasher.finish())
+}
+
+fn escape(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '\\' => out.push_str("\\\\"),
+            '\t' => out.push_str("\\t"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+fn unescape(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    let mut chars = s.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            out.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('t') => out.push('\t'),
+            Some('n') => out.push('\n'),
+            Some('r') => out.push('\r'),
+            Some(other) => out.push(other),
+            None => out.push('\\'),
+        }
+    }
+    out
+}
+
+/// Résultat d'un rejeu
+#[derive(Debug)]
+pub struct Replay {
+    /// Cache reconstruit ; les clés sont celles du journal (ou leurs hashes)
+    pub cache: Cache<String, ()>,
+    /// Clés évincées, dans l'ordre des évictions
+    pub evictions: Vec<String>,
+}
+
+/// Erreur rencontrée lors d'un rejeu
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ReplayError {
+    /// L'en-tête du journal est absent ou invalide
+    InvalidHeader,
+    /// Une ligne du journal ne peut pas être interprétée
+    InvalidLine {
+        /// Numéro de la ligne (à partir de 1)
+        line: usize,
+        /// Contenu de la ligne
+        content: String,
+    },
+    /// Le rejeu a produit un résultat différent de celui enregistré
+    Diverged {
+        /// Numéro de la ligne (à partir de 1)
+        line: usize,
+        /// Résultat enregistré
+        expected: String,
+        /// Résultat obtenu lors du rejeu
+        found: String,
+    },
+}
+
+impl fmt::Display for ReplayError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ReplayError::InvalidHeader => write!(f, "en-tête de journal invalide"),
+            ReplayError::InvalidLine { line, content } => {
+                write!(f, "ligne {} invalide : {:?}", line, content)
+            }
+            ReplayError::Diverged { line, expected, found } => write!(
+                f,
+                "divergence ligne {} : attendu {:?}, obtenu {:?}",
+                line, expected, found
+            ),
+        }
+    }
+}
+
+impl core::error::Error for ReplayError {}
+
+/// Rejoue un journal exporté par `Cache::export_replay` dans un cache neuf de
+/// capacité `capacity`
+///
+/// Chaque résultat enregistré (succès/échec de lecture, insertion, mise à
+/// jour, éviction) est comparé au résultat du rejeu ; la première différence
+/// est signalée par [`ReplayError::Diverged`]. Si le journal a été tronqué
+/// (`dropped` non nul), le rejeu part d'un cache vide à la première opération
+/// conservée et peut donc diverger.
+pub fn replay(log: &str, capacity: usize) -> Result<Replay, ReplayError> {
+    let mut lines = log.lines();
+    if !lines.next().is_some_and(|header| header.starts_with(HEADER)) {
+        return Err(ReplayError::InvalidHeader);
+    }
+
+    let mut cache: Cache<String, ()> = Cache::new(capacity);
+    let mut evictions = Vec::new();
+    for (number, line) in lines.enumerate() {
+        let number = number + 2;
+        let invalid = || ReplayError::InvalidLine {
+            line: number,
+            content: line.to_string(),
+        };
+        let mut fields = line.split('\t');
+        let (op, key, outcome) = match (fields.next(), fields.next(), fields.next()) {
+            (Some(op), Some(key), Some(outcome)) => (op, unescape(key), outcome),
+            _ => return Err(invalid()),
+        };
+        let victim = fields.next().map(unescape);
+
+        let found = match op {
+            "get" => {
+                if cache.get(&key).is_some() { "hit" } else { "miss" }.to_string()
+            }
+            "remove" => {
+                if cache.remove(&key).is_some() { "hit" } else { "miss" }.to_string()
+            }
+            "put" => {
+                let evicted = if !cache.contains_key(&key) && cache.len() >= capacity {
+                    cache.iter().next().map(|(k, _)| k.clone())
+                } else {
+                    None
+                };
+                let outcome = match (&evicted, cache.contains_key(&key)) {
+                    (Some(_), _) => "evict",
+                    (None, true) => "update",
+                    (None, false) => "insert",
+                };
+                cache.put(key, ());
+                if let Some(evicted) = evicted {
+                    if victim.as_ref() != Some(&evicted) {
+                        return Err(ReplayError::Diverged {
+                            line: number,
+                            expected: victim.unwrap_or_default(),
+                            found: evicted,
+                        });
+                    }
+                    evictions.push(evicted);
+                }
+                outcome.to_string()
+            }
+            _ => return Err(invalid()),
+        };
+        if found != outcome {
+            return Err(ReplayError::Diverged {
+                line: number,
+                expected: outcome.to_string(),
+                found,
+            });
+        }
+    }
+    Ok(Replay { cache, evictions })
+}
+
+/// Génère une trace de `len` clés tirées parmi `0..keys` selon une loi de
+/// Zipf d'exposant `exponent` (la clé 0 est la plus populaire)
+///
+/// La trace est entièrement déterminée par `seed`.
+#[cfg(feature = "std")]
+pub fn zipf_trace(keys: usize, len: usize, exponent: f64, seed: u64) -> Vec<u64> {
+    let mut cumulative = Vec::with_capacity(keys);
+    let mut total = 0.0;
+    for rank in 1..=keys {
+        total += 1.0 / (rank as f64).powf(exponent);
+        cumulative.push(total);
+    }
+    let mut rng = XorShift64::new(seed);
+    (0..len)
+        .map(|_| {
+            let target = (rng.next_u64() as f64 / u64::MAX as f64) * total;
+            cumulative.partition_point(|&c| c < target).min(keys - 1) as u64
+        })
+        .collect()
+}
+
+/// Rejoue une trace en lecture-insertion (`get`, puis `put` en cas d'échec)
+/// et retourne le taux de succès observé
+pub fn hit_rate<C: CacheStorage<u64, u64>>(cache: &mut C, trace: &[u64]) -> f64 {
+    if trace.is_empty() {
+        return 0.0;
+    }
+    let mut hits = 0;
+    for &key in trace {
+        if cache.get(&key).is_some() {
+            hits += 1;
+        } else {
+            cache.put(key, key);
+        }
+    }
+    hits as f64 / trace.len() as f64
+}
+
+/// Lit une trace d'accès : une clé par ligne, les lignes vides sont ignorées
+#[cfg(feature = "std")]
+pub fn read_trace<P: AsRef<Path>>(path: P) -> io::Result<Vec<String>> {
+    let mut trace = Vec::new();
+    for line in BufReader::new(File::open(path)?).lines() {
+        let line = line?;
+        let key = line.trim_end_matches('\r');
+        if !key.is_empty() {
+            trace.push(key.to_string());
+        }
+    }
+    Ok(trace)
+}
+
+/// Statistiques du rejeu d'une trace par [`run_trace`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct TraceStats {
+    /// Lectures réussies
+    pub hits: u64,
+    /// Lectures échouées, chacune suivie d'une insertion
+    pub misses: u64,
+    /// Entrées sorties du cache (ou insertions refusées) lors des insertions
+    pub evictions: u64,
+    /// Nombre maximal d'entrées présentes simultanément
+    pub peak_len: usize,
+}
+
+impl TraceStats {
+    /// Lit `key` dans `cache`, l'insère en cas d'échec et compte le résultat
+    pub(crate) fn access<K, V, C>(&mut self, cache: &mut C, key: &K)
+    where
+        K: Clone,
+        V: Default,
+        C: CacheStorage<K, V> + CacheLen,
+    {
+        if cache.get(key).is_some() {
+            self.hits += 1;
+            return;
+        }
+        self.misses += 1;
+        let before = cache.len();
+        cache.put(key.clone(), V::default());
+        let after = cache.len();
+        self.evictions += (before + 1).saturating_sub(after) as u64;
+        self.peak_len = self.peak_len.max(after);
+    }
+
+    /// Retourne la part des lectures réussies (0 pour une trace vide)
+    pub fn hit_rate(&self) -> f64 {
+        let total = self.hits + self.misses;
+        if total == 0 {
+            return 0.0;
+        }
+        self.hits as f64 / total as f64
+    }
+}
+
+/// Rejoue une trace en lecture-insertion, comme [`hit_rate`], et compte les
+/// succès, les échecs et les évictions
+///
+/// # Examples
+/// ```
+/// use lru_cache::Cache;
+/// use lru_cache::cache::testing::run_trace;
+///
+/// let stats = run_trace(&mut Cache::<_, ()>::new(1), &["A", "A", "B", "A"]);
+/// assert_eq!((stats.hits, stats.misses, stats.evictions), (1, 3, 2));
+/// ```
+pub fn run_trace<K, V, C>(cache: &mut C, trace: &[K]) -> TraceStats
+where
+    K: Clone,
+    V: Default,
+    C: CacheStorage<K, V> + CacheLen,
+{
+    let mut stats = TraceStats::default();
+    for key in trace {
+        stats.access(cache, key);
+    }
+    stats
+}
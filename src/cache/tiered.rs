@@ -0,0 +1,303 @@
+//! Cache à deux niveaux : un petit cache en mémoire devant un second niveau
+//! plus grand, sauvegardé
+
+use super::lru::Cache;
+use super::traits::{CacheClear, CacheIterate, CacheLen, CacheRemove, CacheStorage, GetOptions};
+use crate::storage::backend::{FileBackend, StorageBackend};
+use crate::storage::error::StorageError;
+use std::fmt::{self, Display};
+use std::hash::Hash;
+use std::path::PathBuf;
+use std::str::FromStr;
+
+/// Lectures d'un [`TieredCache`] selon le niveau qui les a servies
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct TieredStats {
+    /// Lectures trouvées dans le premier niveau
+    pub l1_hits: u64,
+    /// Lectures trouvées dans le second niveau, et promues dans le premier
+    pub l2_hits: u64,
+    /// Lectures qui n'ont trouvé la clé dans aucun niveau
+    pub misses: u64,
+}
+
+impl TieredStats {
+    /// Retourne la part des lectures réussies, tous niveaux confondus (0 si
+    /// aucune lecture)
+    pub fn hit_ratio(&self) -> f64 {
+        let hits = self.l1_hits + self.l2_hits;
+        let total = hits + self.misses;
+        if total == 0 {
+            return 0.0;
+        }
+        hits as f64 / total as f64
+    }
+}
+
+/// Cache LRU à deux niveaux
+///
+/// Le premier niveau (L1) reçoit les insertions et les lectures. L'entrée
+/// qu'il évince descend dans le second niveau (L2) au lieu d'être perdue ;
+/// une lecture absente de L1 consulte L2 et, si elle y trouve la clé, remonte
+/// l'entrée dans L1, ce qui peut en faire descendre une autre. Une clé n'est
+/// jamais présente dans les deux niveaux. L2 évince ses propres entrées
+/// lorsqu'il est plein : elles quittent alors le cache.
+///
+/// Le second niveau est conservé par un [`StorageBackend`] :
+/// [`TieredCache::flush`] y sauvegarde les deux niveaux, de la prochaine
+/// entrée évincée de L2 à la plus récente de L1, et [`TieredCache::load`]
+/// les répartit de nouveau, les plus récentes dans L1.
+///
+/// # Examples
+/// ```
+/// use lru_cache::cache::tiered::TieredCache;
+/// use lru_cache::cache::traits::{CacheLen, CacheStorage};
+/// use lru_cache::storage::backend::MemoryBackend;
+///
+/// let mut cache = TieredCache::new(1, MemoryBackend::new(), 10);
+/// cache.put("A", 1);
+/// cache.put("B", 2);
+/// assert_eq!(cache.l2().len(), 1);
+/// assert_eq!(cache.get(&"A"), Some(&1));
+/// assert_eq!(cache.stats().l2_hits, 1);
+/// assert_eq!(cache.l2().peek(&"B"), Some(&2));
+/// ```
+pub struct TieredCache<K, V, B = FileBackend> {
+    l1: Cache<K, V>,
+    l2: Cache<K, V>,
+    backend: B,
+    stats: TieredStats,
+}
+
+impl<K: Eq + Hash, V, B> TieredCache<K, V, B> {
+    /// Crée un cache vide dont les niveaux contiennent `l1_capacity` et
+    /// `l2_capacity` entrées, sauvegardé dans `backend`
+    ///
+    /// Le contenu de `backend` n'est pas lu : voir [`TieredCache::load`].
+    ///
+    /// # Panics
+    ///
+    /// Panique si `l1_capacity` ou `l2_capacity` vaut 0, comme
+    /// [`Cache::new`].
+    pub fn new(l1_capacity: usize, backend: B, l2_capacity: usize) -> Self {
+        TieredCache {
+            l1: Cache::new(l1_capacity),
+            l2: Cache::new(l2_capacity),
+            backend,
+            stats: TieredStats::default(),
+        }
+    }
+
+    /// Retourne le premier niveau
+    pub fn l1(&self) -> &Cache<K, V> {
+        &self.l1
+    }
+
+    /// Retourne le second niveau
+    pub fn l2(&self) -> &Cache<K, V> {
+        &self.l2
+    }
+
+    /// Retourne la destination de sauvegarde
+    pub fn backend(&self) -> &B {
+        &self.backend
+    }
+
+    /// Retourne les lectures par niveau depuis la création ou le dernier
+    /// [`TieredCache::reset_stats`]
+    ///
+    /// Les compteurs de chaque niveau restent disponibles avec
+    /// [`Cache::stats`].
+    pub fn stats(&self) -> TieredStats {
+        self.stats
+    }
+
+    /// Remet à zéro les lectures par niveau
+    pub fn reset_stats(&mut self) {
+        self.stats = TieredStats::default();
+    }
+
+    /// Indique si la clé est présente dans l'un des niveaux, sans modifier
+    /// l'ordre
+    pub fn contains_key(&self, key: &K) -> bool {
+        self.l1.contains_key(key) || self.l2.contains_key(key)
+    }
+
+    /// Récupère la valeur associée à `key` sans la promouvoir ni compter la
+    /// lecture
+    pub fn peek(&self, key: &K) -> Option<&V> {
+        self.l1.peek(key).or_else(|| self.l2.peek(key))
+    }
+
+    /// Vide les deux niveaux
+    pub fn clear(&mut self) {
+        self.l1.clear();
+        self.l2.clear();
+    }
+
+    /// Insère l'entrée dans L1 en retirant de L2 une ancienne valeur
+    fn insert(&mut self, key: K, value: V) {
+        self.l2.remove(&key);
+        self.promote(key, value);
+    }
+
+    /// Insère l'entrée dans L1 et fait descendre dans L2 celles qu'elle
+    /// évince
+    fn promote(&mut self, key: K, value: V) {
+        for (key, value) in self.l1.put_evicting(key, value) {
+            self.l2.put(key, value);
+        }
+    }
+}
+
+impl<K, V, B> TieredCache<K, V, B>
+where
+    K: Eq + Hash + Display + FromStr,
+    V: Display + FromStr,
+    B: StorageBackend,
+{
+    /// Crée un cache comme [`TieredCache::new`], rempli avec le contenu de
+    /// `backend`
+    ///
+    /// Les `l1_capacity` entrées les plus récentes vont dans L1, les
+    /// précédentes dans L2 ; au-delà, les plus anciennes sont écartées. Les
+    /// entrées que `FromStr` refuse sont ignorées.
+    ///
+    /// # Errors
+    ///
+    /// Les erreurs de [`StorageBackend::load`].
+    ///
+    /// # Panics
+    ///
+    /// Panique si `l1_capacity` ou `l2_capacity` vaut 0.
+    pub fn load(l1_capacity: usize, mut backend: B, l2_capacity: usize) -> Result<Self, StorageError> {
+        let (_, entries) = backend.load()?;
+        let mut cache = TieredCache::new(l1_capacity, backend, l2_capacity);
+        for (key, value) in entries {
+            if let (Ok(key), Ok(value)) = (K::from_str(&key), V::from_str(&value)) {
+                cache.insert(key, value);
+            }
+        }
+        Ok(cache)
+    }
+
+    /// Sauvegarde les deux niveaux dans la destination
+    ///
+    /// # Errors
+    ///
+    /// Les erreurs de [`StorageBackend::save`].
+    pub fn flush(&mut self) -> Result<(), StorageError> {
+        let entries: Vec<_> = self
+            .l2
+            .iter()
+            .chain(self.l1.iter())
+            .map(|(key, value)| (key.to_string(), value.to_string()))
+            .collect();
+        self.backend.save(self.l1.capacity() + self.l2.capacity(), &entries)
+    }
+}
+
+impl<K, V> TieredCache<K, V>
+where
+    K: Eq + Hash + Display + FromStr,
+    V: Display + FromStr,
+{
+    /// Ouvre le cache sauvegardé dans le fichier `path`, rechargé s'il
+    /// existe
+    ///
+    /// # Errors
+    ///
+    /// Retourne une erreur si le fichier existe mais ne peut pas être
+    /// chargé.
+    ///
+    /// # Panics
+    ///
+    /// Panique si `l1_capacity` ou `l2_capacity` vaut 0.
+    pub fn open(l1_capacity: usize, path: impl Into<PathBuf>, l2_capacity: usize) -> Result<Self, StorageError> {
+        let backend = FileBackend::new(path);
+        if backend.path().exists() {
+            TieredCache::load(l1_capacity, backend, l2_capacity)
+        } else {
+            Ok(TieredCache::new(l1_capacity, backend, l2_capacity))
+        }
+    }
+}
+
+impl<K: Eq + Hash + Clone, V, B> CacheStorage<K, V> for TieredCache<K, V, B> {
+    /// Cherche dans L1 puis dans L2 ; une entrée trouvée dans L2 remonte
+    /// dans L1.
+    fn get(&mut self, key: &K) -> Option<&V> {
+        self.get_with(key, GetOptions::default())
+    }
+
+    /// Comme [`TieredCache::get`] ; sans `promote`, une entrée trouvée dans
+    /// L2 y reste et aucun niveau ne change d'ordre, et sans `record_stats`
+    /// les compteurs de [`TieredCache::stats`] ne bougent pas.
+    fn get_with(&mut self, key: &K, options: GetOptions) -> Option<&V> {
+        if self.l1.contains_key(key) {
+            if options.record_stats {
+                self.stats.l1_hits += 1;
+            }
+            return self.l1.get_with(key, options);
+        }
+        if !options.promote {
+            let value = self.l2.get_with(key, options);
+            if options.record_stats {
+                match value {
+                    Some(_) => self.stats.l2_hits += 1,
+                    None => self.stats.misses += 1,
+                }
+            }
+            return value;
+        }
+        match self.l2.remove(key) {
+            Some(value) => {
+                if options.record_stats {
+                    self.stats.l2_hits += 1;
+                }
+                self.promote(key.clone(), value);
+                self.l1.peek(key)
+            }
+            None => {
+                if options.record_stats {
+                    self.stats.misses += 1;
+                }
+                None
+            }
+        }
+    }
+
+    /// Insère dans L1 ; une ancienne valeur présente dans L2 en est retirée.
+    fn put(&mut self, key: K, value: V) {
+        self.insert(key, value);
+    }
+}
+
+impl<K: Eq + Hash + Clone, V, B> CacheRemove<K, V> for TieredCache<K, V, B> {
+    fn remove(&mut self, key: &K) -> Option<V> {
+        self.l1.remove(key).or_else(|| self.l2.remove(key))
+    }
+}
+
+impl<K: Eq + Hash, V, B> CacheLen for TieredCache<K, V, B> {
+    fn len(&self) -> usize {
+        self.l1.len() + self.l2.len()
+    }
+}
+
+impl<K: Eq + Hash, V, B> CacheClear for TieredCache<K, V, B> {
+    fn clear(&mut self) {
+        TieredCache::clear(self)
+    }
+}
+
+impl<K: fmt::Debug, V: fmt::Debug, B: fmt::Debug> fmt::Debug for TieredCache<K, V, B> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TieredCache")
+            .field("l1", &self.l1)
+            .field("l2", &self.l2)
+            .field("backend", &self.backend)
+            .field("stats", &self.stats)
+            .finish()
+    }
+}
@@ -0,0 +1,185 @@
+use super::lru::{self, Cache};
+use super::rng::XorShift64;
+use super::traits::{CacheIterate, CacheLen, CacheRemove, CacheStorage, GetOptions};
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hash};
+
+/// Nombre de lignes par défaut du sketch
+pub const DEFAULT_DEPTH: usize = 4;
+
+/// Sketch count-min à compteurs de 8 bits, vieilli par division par deux
+#[derive(Debug)]
+struct CountMinSketch {
+    width: usize,
+    seeds: Vec<u64>,
+    counters: Vec<u8>,
+    additions: u64,
+    reset_interval: u64,
+}
+
+impl CountMinSketch {
+    fn new(width: usize, depth: usize, reset_interval: u64) -> Self {
+        let width = width.max(1);
+        let depth = depth.max(1);
+        let mut rng = XorShift64::new(width as u64);
+        CountMinSketch {
+            width,
+            seeds: (0..depth).map(|_| rng.next_u64() | 1).collect(),
+            counters: vec![0; width * depth],
+            additions: 0,
+            reset_interval: reset_interval.max(1),
+        }
+    }
+
+    fn slot(&self, row: usize, hash: u64) -> usize {
+        let mixed = (hash ^ (hash >> 29)).wrapping_mul(self.seeds[row]);
+        row * self.width + ((mixed >> 32) % self.width as u64) as usize
+    }
+
+    fn estimate(&self, hash: u64) -> u8 {
+        (0..self.seeds.len())
+            .map(|row| self.counters[self.slot(row, hash)])
+            .min()
+            .unwrap_or(0)
+    }
+
+    fn increment(&mut self, hash: u64) {
+        for row in 0..self.seeds.len() {
+            let slot = self.slot(row, hash);
+            self.counters[slot] = self.counters[slot].saturating_add(1);
+        }
+        self.additions += 1;
+        if self.additions >= self.reset_interval {
+            self.age();
+        }
+    }
+
+    /// Divise tous les compteurs par deux pour oublier l'historique ancien
+    fn age(&mut self) {
+        for counter in &mut self.counters {
+            *counter /= 2;
+        }
+        self.additions /= 2;
+    }
+}
+
+/// Cache LRU précédé d'un filtre d'admission TinyLFU (Einziger et al., 2017)
+///
+/// Un sketch count-min estime la fréquence d'accès de chaque clé, qu'elle
+/// soit présente ou non. Lorsque le cache est plein, une nouvelle clé n'est
+/// admise que si sa fréquence estimée dépasse strictement celle de l'entrée
+/// qui serait évincée ; sinon elle est rejetée et le cache reste inchangé.
+/// Les clés vues une seule fois ne chassent donc pas les entrées utiles.
+///
+/// Les `get` (y compris les échecs) et les `put` incrémentent le sketch.
+/// Après `reset_interval` incréments, tous les compteurs sont divisés par
+/// deux afin que les fréquences reflètent l'activité récente.
+///
+/// # Examples
+/// ```
+/// use lru_cache::cache::tiny_lfu::TinyLfuCache;
+/// use lru_cache::cache::traits::CacheStorage;
+///
+/// let mut cache = TinyLfuCache::new(1);
+/// cache.put("hot", 1);
+/// cache.get(&"hot");
+/// cache.put("once", 2);
+/// assert_eq!(cache.get(&"once"), None);
+/// assert_eq!(cache.get(&"hot"), Some(&1));
+/// ```
+#[derive(Debug)]
+pub struct TinyLfuCache<K, V> {
+    capacity: usize,
+    cache: Cache<K, V>,
+    sketch: CountMinSketch,
+    hasher: RandomState,
+}
+
+impl<K: Eq + Hash, V> TinyLfuCache<K, V> {
+    /// Crée un cache avec un sketch de `DEFAULT_DEPTH` lignes de
+    /// `4 × capacity` compteurs, vieilli tous les `10 × capacity` accès
+    ///
+    /// Un cache de capacité 0 ne garde aucune entrée.
+    pub fn new(capacity: usize) -> Self {
+        let width = (4 * capacity).max(16);
+        TinyLfuCache::with_sketch(capacity, width, DEFAULT_DEPTH, 10 * capacity.max(1) as u64)
+    }
+
+    /// Crée un cache dont le sketch compte `depth` lignes de `width`
+    /// compteurs et est vieilli tous les `reset_interval` incréments
+    ///
+    /// Des valeurs nulles de `width`, `depth` ou `reset_interval` sont
+    /// ramenées à 1.
+    pub fn with_sketch(capacity: usize, width: usize, depth: usize, reset_interval: u64) -> Self {
+        TinyLfuCache {
+            capacity,
+            cache: Cache::new(capacity.max(1)),
+            sketch: CountMinSketch::new(width, depth, reset_interval),
+            hasher: RandomState::new(),
+        }
+    }
+
+    /// Retourne la capacité du cache
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Retourne la fréquence d'accès estimée d'une clé, présente ou non
+    pub fn frequency(&self, key: &K) -> u8 {
+        self.sketch.estimate(self.hasher.hash_one(key))
+    }
+
+    fn admits(&self, candidate: u64) -> bool {
+        if self.cache.len() < self.cache.capacity() {
+            return true;
+        }
+        match self.cache.iter().next() {
+            Some((victim, _)) => self.sketch.estimate(candidate) > self.frequency(victim),
+            None => false,
+        }
+    }
+}
+
+impl<K: Eq + Hash, V> CacheStorage<K, V> for TinyLfuCache<K, V> {
+    fn get(&mut self, key: &K) -> Option<&V> {
+        self.get_with(key, GetOptions::default())
+    }
+
+    fn get_with(&mut self, key: &K, options: GetOptions) -> Option<&V> {
+        if options.promote {
+            self.sketch.increment(self.hasher.hash_one(key));
+        }
+        self.cache.get_with(key, options)
+    }
+
+    fn put(&mut self, key: K, value: V) {
+        let hash = self.hasher.hash_one(&key);
+        self.sketch.increment(hash);
+        if self.capacity == 0 {
+            return;
+        }
+        if self.cache.contains_key(&key) || self.admits(hash) {
+            self.cache.put(key, value);
+        }
+    }
+}
+
+impl<K: Eq + Hash, V> CacheRemove<K, V> for TinyLfuCache<K, V> {
+    fn remove(&mut self, key: &K) -> Option<V> {
+        self.cache.remove(key)
+    }
+}
+
+impl<K, V> CacheLen for TinyLfuCache<K, V> {
+    fn len(&self) -> usize {
+        self.cache.len()
+    }
+}
+
+impl<K, V> CacheIterate<K, V> for TinyLfuCache<K, V> {
+    type Iter<'a> = lru::Iter<'a, K, V> where Self: 'a, K: 'a, V: 'a;
+
+    fn iter(&self) -> Self::Iter<'_> {
+        self.cache.iter()
+    }
+}
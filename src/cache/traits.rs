@@ -1,17 +1,233 @@
+//! Traits décrivant les capacités d'un cache
+//!
+//! `CacheStorage` reste volontairement minimal (`get`/`put`, plus `get_with`
+//! qui a une implémentation par défaut) : les capacités
+//! supplémentaires sont exposées par des traits optionnels (`CacheRemove`,
+//! `CacheLen`, `CacheClear`, `CacheIterate`) afin qu'ajouter une
+//! fonctionnalité ne casse pas les implémentations existantes. Le code
+//! générique n'exige que ce dont il a besoin, par exemple
+//! `T: CacheStorage<K, V> + CacheRemove<K, V>`.
+//!
+//! Le code qui manipule un cache choisi à l'exécution (injection de
+//! dépendances, cache désactivé par configuration) utilise
+//! `Box<dyn DynCache<K, V>>`, qui réunit lecture, écriture, suppression,
+//! taille et vidage. Le module [`adapters`](super::adapters) en fournit deux
+//! implémentations triviales.
+//!
+//! Les méthodes de confort construites uniquement sur `get`/`put` sont
+//! fournies par `CacheStorageExt`, implémenté automatiquement pour tout
+//! `CacheStorage`.
+//!
+//! Les traits de persistance n'existent qu'avec la feature `std`.
+
+#[cfg(feature = "std")]
+use core::num::NonZeroUsize;
+#[cfg(feature = "std")]
+use std::path::Path;
+
+/// Options d'une lecture par [`CacheStorage::get_with`]
+///
+/// Par défaut, une lecture se comporte comme `get`. Une lecture
+/// administrative (sonde de santé, export) utilise [`GetOptions::passive`]
+/// pour ne perturber ni l'ordre d'éviction ni les compteurs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GetOptions {
+    /// Marque l'entrée comme utilisée pour la politique d'éviction
+    pub promote: bool,
+    /// Compte la lecture dans les statistiques du cache
+    pub record_stats: bool,
+}
+
+impl GetOptions {
+    /// Lecture sans effet sur l'ordre d'éviction ni sur les statistiques
+    pub fn passive() -> Self {
+        GetOptions {
+            promote: false,
+            record_stats: false,
+        }
+    }
+}
+
+impl Default for GetOptions {
+    fn default() -> Self {
+        GetOptions {
+            promote: true,
+            record_stats: true,
+        }
+    }
+}
+
 /// Définit les opérations de base d'un cache
 pub trait CacheStorage<K, V> {
     /// Récupère une valeur du cache
     fn get(&mut self, key: &K) -> Option<&V>;
     /// Insère une valeur dans le cache
     fn put(&mut self, key: K, value: V);
+
+    /// Récupère une valeur en choisissant les effets de la lecture
+    ///
+    /// L'implémentation par défaut appelle `get` et ignore donc les
+    /// options : un cache dont les lectures modifient l'état doit la
+    /// redéfinir.
+    ///
+    /// # Examples
+    /// ```
+    /// use lru_cache::Cache;
+    /// use lru_cache::cache::traits::{CacheIterate, CacheStorage, GetOptions};
+    ///
+    /// let mut cache = Cache::new(2);
+    /// cache.put("A", 1);
+    /// cache.put("B", 2);
+    /// assert_eq!(cache.get_with(&"A", GetOptions::passive()), Some(&1));
+    /// assert_eq!(cache.iter().next(), Some((&"A", &1)));
+    /// ```
+    fn get_with(&mut self, key: &K, options: GetOptions) -> Option<&V> {
+        let _ = options;
+        self.get(key)
+    }
+}
+
+/// Suppression explicite d'une entrée
+pub trait CacheRemove<K, V>: CacheStorage<K, V> {
+    /// Supprime une entrée et retourne sa valeur
+    fn remove(&mut self, key: &K) -> Option<V>;
+}
+
+/// Nombre d'entrées présentes dans le cache
+pub trait CacheLen {
+    /// Retourne le nombre d'entrées
+    fn len(&self) -> usize;
+    /// Indique si le cache est vide
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+/// Suppression de toutes les entrées
+pub trait CacheClear {
+    /// Vide le cache
+    fn clear(&mut self);
+}
+
+/// Parcours des entrées sans modifier l'ordre d'utilisation
+pub trait CacheIterate<K, V> {
+    /// Type de l'itérateur retourné par `iter`
+    type Iter<'a>: Iterator<Item = (&'a K, &'a V)>
+    where
+        Self: 'a,
+        K: 'a,
+        V: 'a;
+
+    /// Parcourt les entrées dans l'ordre d'éviction (la prochaine entrée
+    /// évincée en premier)
+    fn iter(&self) -> Self::Iter<'_>;
 }
 
+/// Regroupe toutes les capacités optionnelles, pour le code générique qui en
+/// a besoin
+pub trait FullCache<K, V>: CacheRemove<K, V> + CacheLen + CacheIterate<K, V> {}
+
+impl<K, V, T: CacheRemove<K, V> + CacheLen + CacheIterate<K, V>> FullCache<K, V> for T {}
+
+/// Capacités utilisables à travers un objet trait `dyn DynCache<K, V>`
+///
+/// Contrairement à [`FullCache`], ce trait n'exige pas [`CacheIterate`],
+/// dont l'itérateur associé empêche la création d'un objet trait.
+///
+/// # Examples
+/// ```
+/// use lru_cache::Cache;
+/// use lru_cache::cache::adapters::NullCache;
+/// use lru_cache::cache::traits::DynCache;
+///
+/// fn make(enabled: bool) -> Box<dyn DynCache<&'static str, i32>> {
+///     if enabled {
+///         Box::new(Cache::new(16))
+///     } else {
+///         Box::new(NullCache::new())
+///     }
+/// }
+///
+/// let mut cache = make(false);
+/// cache.put("key", 1);
+/// assert_eq!(cache.get(&"key"), None);
+/// assert!(cache.is_empty());
+/// ```
+pub trait DynCache<K, V>: CacheRemove<K, V> + CacheLen + CacheClear {}
+
+impl<K, V, T: CacheRemove<K, V> + CacheLen + CacheClear + ?Sized> DynCache<K, V> for T {}
+
+mod sealed {
+    pub trait Sealed<K, V> {}
+
+    impl<K, V, T: super::CacheStorage<K, V> + ?Sized> Sealed<K, V> for T {}
+}
+
+/// Méthodes de confort disponibles pour tout `CacheStorage`
+///
+/// Ce trait est scellé : il est implémenté automatiquement et ne peut pas
+/// l'être à la main, ce qui permet d'y ajouter des méthodes sans casser les
+/// implémentations tierces.
+///
+/// # Examples
+/// ```
+/// use lru_cache::Cache;
+/// use lru_cache::cache::traits::{CacheStorage, CacheStorageExt};
+///
+/// let mut cache = Cache::new(2);
+/// cache.put_all([("A", 1), ("B", 2)]);
+/// assert_eq!(cache.get_cloned(&"A"), Some(1));
+/// assert!(!cache.contains(&"C"));
+/// ```
+pub trait CacheStorageExt<K, V>: CacheStorage<K, V> + sealed::Sealed<K, V> {
+    /// Récupère une copie de la valeur (l'entrée est considérée comme utilisée)
+    fn get_cloned(&mut self, key: &K) -> Option<V>
+    where
+        V: Clone,
+    {
+        self.get(key).cloned()
+    }
+
+    /// Indique si la clé est présente (l'entrée est considérée comme utilisée)
+    fn contains(&mut self, key: &K) -> bool {
+        self.get(key).is_some()
+    }
+
+    /// Insère toutes les paires dans l'ordre fourni
+    fn put_all<I: IntoIterator<Item = (K, V)>>(&mut self, entries: I)
+    where
+        Self: Sized,
+    {
+        for (key, value) in entries {
+            self.put(key, value);
+        }
+    }
+}
+
+impl<K, V, T: CacheStorage<K, V> + ?Sized> CacheStorageExt<K, V> for T {}
+
 /// Définit les opérations de persistance d'un cache
+#[cfg(feature = "std")]
 pub trait PersistentStorage<K, V> {
     /// Sauvegarde le cache dans un fichier
-    fn save_to_file(&self, path: &str) -> std::io::Result<()>;
+    fn save_to_file(&self, path: impl AsRef<Path>) -> std::io::Result<()>;
     /// Charge le cache depuis un fichier
-    fn load_from_file(path: &str, capacity: usize) -> std::io::Result<Self>
+    fn load_from_file(path: impl AsRef<Path>, capacity: NonZeroUsize) -> std::io::Result<Self>
     where
         Self: Sized;
-}
\ No newline at end of file
+}
+
+/// Sauvegarde et chargement au format binaire de
+/// [`BinaryStorage`](crate::storage::binary::BinaryStorage)
+#[cfg(feature = "std")]
+pub trait BinaryPersistentStorage<K, V> {
+    /// Sauvegarde le cache dans un fichier binaire
+    fn save_to_file_binary(&self, path: impl AsRef<Path>) -> std::io::Result<()>;
+    /// Charge le cache depuis un fichier binaire
+    ///
+    /// Un fichier invalide ou tronqué est une erreur : aucun cache
+    /// partiellement rempli n'est retourné.
+    fn load_from_file_binary(path: impl AsRef<Path>, capacity: NonZeroUsize) -> Result<Self, crate::storage::binary::BinaryError>
+    where
+        Self: Sized;
+}
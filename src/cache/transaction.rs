@@ -0,0 +1,93 @@
+//! Groupes de modifications d'un cache validés ou défaits ensemble
+
+use super::lru::Cache;
+use super::traits::CacheStorage;
+use core::fmt;
+use core::hash::Hash;
+
+/// Transaction ouverte sur un [`Cache`] par [`Cache::transaction`]
+///
+/// Les opérations s'appliquent immédiatement au cache, comme celles du
+/// cache lui-même, et sont notées dans un journal qui garde les entrées
+/// évincées ou supprimées. [`Transaction::commit`] oublie le journal ;
+/// [`Transaction::rollback`], ou l'abandon de la transaction sans
+/// validation, le rejoue à l'envers pour rendre au cache son état
+/// d'ouverture.
+///
+/// # Examples
+/// ```
+/// use lru_cache::Cache;
+/// use lru_cache::cache::traits::CacheStorage;
+///
+/// let mut cache = Cache::new(2);
+/// cache.put("stock", 5);
+/// {
+///     let mut transaction = cache.transaction();
+///     transaction.put("stock", 4);
+///     transaction.put("commande", 1);
+///     // Validation échouée : la transaction est abandonnée
+/// }
+/// assert_eq!(cache.get(&"stock"), Some(&5));
+/// assert_eq!(cache.get(&"commande"), None);
+/// ```
+pub struct Transaction<'a, K: Eq + Hash, V> {
+    cache: &'a mut Cache<K, V>,
+    open: bool,
+}
+
+impl<'a, K: Eq + Hash, V> Transaction<'a, K, V> {
+    pub(super) fn new(cache: &'a mut Cache<K, V>) -> Self {
+        cache.begin_transaction();
+        Transaction { cache, open: true }
+    }
+
+    /// Insère ou remplace la valeur de `key`, comme `put`
+    pub fn put(&mut self, key: K, value: V) {
+        self.cache.put(key, value);
+    }
+
+    /// Supprime `key`, comme [`Cache::invalidate`], et indique si elle était
+    /// présente ; la valeur reste dans le journal jusqu'à la fin de la
+    /// transaction
+    pub fn remove(&mut self, key: &K) -> bool {
+        self.cache.invalidate(key)
+    }
+
+    /// Lit la valeur de `key` et la marque comme la plus récemment utilisée,
+    /// comme `get`
+    pub fn get(&mut self, key: &K) -> Option<&V> {
+        self.cache.get(key)
+    }
+
+    /// Lit la valeur de `key` sans la promouvoir, comme [`Cache::peek`]
+    pub fn peek(&self, key: &K) -> Option<&V> {
+        self.cache.peek(key)
+    }
+
+    /// Garde les modifications et passe à l'écouteur les entrées retirées
+    pub fn commit(mut self) {
+        self.open = false;
+        self.cache.commit_transaction();
+    }
+
+    /// Défait les modifications et rend au cache son état d'ouverture
+    pub fn rollback(mut self) {
+        self.open = false;
+        self.cache.rollback_transaction();
+    }
+}
+
+/// Défait une transaction abandonnée sans [`Transaction::commit`]
+impl<K: Eq + Hash, V> Drop for Transaction<'_, K, V> {
+    fn drop(&mut self) {
+        if self.open {
+            self.cache.rollback_transaction();
+        }
+    }
+}
+
+impl<K: Eq + Hash, V> fmt::Debug for Transaction<'_, K, V> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Transaction").field("open", &self.open).finish_non_exhaustive()
+    }
+}
@@ -0,0 +1,14 @@
+//! Options d'expiration des entrées
+
+/// Point de départ du délai d'expiration par défaut d'un cache
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TtlMode {
+    /// L'entrée expire un délai fixe après son insertion
+    AfterWrite,
+    /// Le délai repart de zéro à chaque accès par `get`
+    AfterAccess,
+}
+
+/// Granularité par défaut de la roue temporelle qui range les échéances
+/// (voir [`Cache::set_ttl_granularity`](super::lru::Cache::set_ttl_granularity))
+pub const DEFAULT_TTL_GRANULARITY: core::time::Duration = core::time::Duration::from_millis(1);
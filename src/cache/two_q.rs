@@ -0,0 +1,200 @@
+use super::list::{self, NodeList};
+use super::traits::{CacheIterate, CacheLen, CacheRemove, CacheStorage, GetOptions};
+use core::hash::Hash;
+use core::iter::Chain;
+
+/// Part par défaut de la capacité réservée à la file d'entrée `A1in`
+pub const DEFAULT_IN_RATIO: f64 = 0.25;
+/// Taille par défaut de la file fantôme `A1out`, en part de la capacité
+pub const DEFAULT_OUT_RATIO: f64 = 0.5;
+
+/// Cache 2Q (Johnson et Shasha, 1994), version complète
+///
+/// Une clé vue pour la première fois entre dans la file FIFO `A1in`. Quand
+/// `A1in` dépasse sa part de la capacité, ses entrées les plus anciennes en
+/// sortent et seule leur clé est mémorisée dans la file fantôme `A1out`. Une
+/// clé réinsérée alors qu'elle est encore dans `A1out` entre directement
+/// dans la file principale `Am`, gérée en LRU. Un parcours unique de clés
+/// froides ne traverse donc que `A1in` et ne déplace pas les entrées de `Am`.
+///
+/// Les lectures dans `A1in` ne modifient pas l'ordre ; les lectures dans
+/// `Am` promeuvent l'entrée. La file fantôme ne conserve que des clés et
+/// est bornée, indépendamment de la taille des valeurs.
+///
+/// # Examples
+/// ```
+/// use lru_cache::cache::two_q::TwoQCache;
+/// use lru_cache::cache::traits::CacheStorage;
+///
+/// let mut cache = TwoQCache::new(4);
+/// for key in 0..5 {
+///     cache.put(key, key);
+/// }
+/// assert_eq!(cache.ghost_len(), 1);
+/// cache.put(0, 0);
+/// assert_eq!(cache.main_len(), 1);
+/// ```
+#[derive(Debug)]
+pub struct TwoQCache<K, V> {
+    capacity: usize,
+    in_capacity: usize,
+    out_capacity: usize,
+    a1in: NodeList<K, V, ()>,
+    a1out: NodeList<K, (), ()>,
+    am: NodeList<K, V, ()>,
+}
+
+impl<K: Eq + Hash, V> TwoQCache<K, V> {
+    /// Crée un cache de capacité `capacity` avec les proportions du papier :
+    /// 25 % pour `A1in` et une file fantôme de 50 % de la capacité
+    pub fn new(capacity: usize) -> Self {
+        TwoQCache::with_ratios(capacity, DEFAULT_IN_RATIO, DEFAULT_OUT_RATIO)
+    }
+
+    /// Crée un cache dont `A1in` occupe la part `in_ratio` de la capacité
+    /// et dont la file fantôme retient `out_ratio × capacity` clés
+    ///
+    /// `A1in` garde toujours au moins une place lorsque la capacité est non
+    /// nulle.
+    pub fn with_ratios(capacity: usize, in_ratio: f64, out_ratio: f64) -> Self {
+        let share = |ratio: f64| (capacity as f64 * ratio.max(0.0)) as usize;
+        TwoQCache {
+            capacity,
+            in_capacity: share(in_ratio).clamp(capacity.min(1), capacity),
+            out_capacity: share(out_ratio),
+            a1in: NodeList::new(),
+            a1out: NodeList::new(),
+            am: NodeList::new(),
+        }
+    }
+
+    /// Retourne la capacité du cache
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Retourne le nombre d'entrées de la file d'entrée `A1in`
+    pub fn in_len(&self) -> usize {
+        self.a1in.len()
+    }
+
+    /// Retourne le nombre d'entrées de la file principale `Am`
+    pub fn main_len(&self) -> usize {
+        self.am.len()
+    }
+
+    /// Retourne le nombre de clés mémorisées par la file fantôme `A1out`
+    pub fn ghost_len(&self) -> usize {
+        self.a1out.len()
+    }
+
+    fn reclaim(&mut self) {
+        if self.a1in.len() + self.am.len() < self.capacity {
+            return;
+        }
+        if self.a1in.len() > self.in_capacity || self.am.len() == 0 {
+            if let Some(oldest) = self.a1in.head() {
+                let node = self.a1in.remove(oldest);
+                self.remember(node.key);
+            }
+        } else if let Some(coldest) = self.am.head() {
+            self.am.remove(coldest);
+        }
+    }
+
+    fn remember(&mut self, key: K) {
+        if self.out_capacity == 0 {
+            return;
+        }
+        if self.a1out.len() >= self.out_capacity {
+            if let Some(oldest) = self.a1out.head() {
+                self.a1out.remove(oldest);
+            }
+        }
+        self.a1out.push(key, (), ());
+    }
+}
+
+impl<K: Eq + Hash, V> CacheStorage<K, V> for TwoQCache<K, V> {
+    fn get(&mut self, key: &K) -> Option<&V> {
+        if let Some(index) = self.am.find(key) {
+            self.am.move_to_back(index);
+            return Some(&self.am.node(index).value);
+        }
+        let index = self.a1in.find(key)?;
+        Some(&self.a1in.node(index).value)
+    }
+
+    fn get_with(&mut self, key: &K, options: GetOptions) -> Option<&V> {
+        if options.promote {
+            return self.get(key);
+        }
+        let (queue, index) = match self.am.find(key) {
+            Some(index) => (&self.am, index),
+            None => (&self.a1in, self.a1in.find(key)?),
+        };
+        Some(&queue.node(index).value)
+    }
+
+    fn put(&mut self, key: K, value: V) {
+        if let Some(index) = self.am.find(&key) {
+            self.am.node_mut(index).value = value;
+            self.am.move_to_back(index);
+            return;
+        }
+        if let Some(index) = self.a1in.find(&key) {
+            self.a1in.node_mut(index).value = value;
+            return;
+        }
+        if self.capacity == 0 {
+            return;
+        }
+        match self.a1out.find(&key) {
+            Some(ghost) => {
+                self.a1out.remove(ghost);
+                self.reclaim();
+                self.am.push(key, value, ());
+            }
+            None => {
+                self.reclaim();
+                self.a1in.push(key, value, ());
+            }
+        }
+    }
+}
+
+impl<K: Eq + Hash, V> CacheRemove<K, V> for TwoQCache<K, V> {
+    fn remove(&mut self, key: &K) -> Option<V> {
+        if let Some(index) = self.am.find(key) {
+            return Some(self.am.remove(index).value);
+        }
+        let index = self.a1in.find(key)?;
+        Some(self.a1in.remove(index).value)
+    }
+}
+
+impl<K, V> CacheLen for TwoQCache<K, V> {
+    fn len(&self) -> usize {
+        self.a1in.len() + self.am.len()
+    }
+}
+
+/// Itérateur sur les entrées d'un [`TwoQCache`] : `A1in` de la plus ancienne
+/// à la plus récente, puis `Am` de la moins à la plus récemment utilisée
+pub struct Iter<'a, K, V>(Chain<list::Iter<'a, K, V, ()>, list::Iter<'a, K, V, ()>>);
+
+impl<'a, K, V> Iterator for Iter<'a, K, V> {
+    type Item = (&'a K, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next()
+    }
+}
+
+impl<K, V> CacheIterate<K, V> for TwoQCache<K, V> {
+    type Iter<'a> = Iter<'a, K, V> where Self: 'a, K: 'a, V: 'a;
+
+    fn iter(&self) -> Self::Iter<'_> {
+        Iter(self.a1in.iter().chain(self.am.iter()))
+    }
+}
@@ -0,0 +1,128 @@
+//! Cache rechargé quand son fichier de sauvegarde change
+
+use super::clock::Instant;
+use super::lru::Cache;
+use super::policy::{EvictionPolicy, Lru};
+use super::traits::CacheStorage;
+use std::fmt::{self, Display};
+use std::hash::Hash;
+use std::io;
+use std::ops::{Deref, DerefMut};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::time::Duration;
+
+/// [`Cache`] qui vérifie, au plus une fois par `interval`, si son fichier a
+/// été réécrit par un autre programme et le recharge alors
+///
+/// La vérification est faite par [`WatchedCache::get`], avant la lecture,
+/// avec [`Cache::reload_if_changed`] ; l'intervalle est mesuré sur
+/// l'horloge du cache. Une erreur de rechargement (fichier absent ou en
+/// cours d'écriture) est ignorée par `get` : le cache garde son contenu et
+/// réessaie à l'intervalle suivant. [`WatchedCache::check`] vérifie tout de
+/// suite et retourne l'erreur. Le cache s'utilise aussi directement à
+/// travers `Deref`/`DerefMut`.
+///
+/// # Examples
+/// ```
+/// use lru_cache::Cache;
+/// use lru_cache::cache::clock::MockClock;
+/// use lru_cache::cache::watched::WatchedCache;
+/// use std::time::Duration;
+///
+/// std::fs::write("watched_doc.txt", "2\na;1\n").unwrap();
+/// let clock = MockClock::new();
+/// let cache = Cache::<String, i32>::with_clock(2, clock.clone());
+/// let mut watched = WatchedCache::new(cache, "watched_doc.txt", Duration::from_secs(5));
+/// assert_eq!(watched.get(&String::from("a")), Some(&1));
+///
+/// std::fs::write("watched_doc.txt", "2\na;10\n").unwrap();
+/// assert_eq!(watched.get(&String::from("a")), Some(&1));
+/// clock.advance(Duration::from_secs(5));
+/// assert_eq!(watched.get(&String::from("a")), Some(&10));
+/// std::fs::remove_file("watched_doc.txt").unwrap();
+/// ```
+pub struct WatchedCache<K, V, P = Lru> {
+    cache: Cache<K, V, P>,
+    path: PathBuf,
+    interval: Duration,
+    /// Heure de la dernière vérification, `None` avant la première
+    checked_at: Option<Instant>,
+}
+
+impl<K, V, P> WatchedCache<K, V, P> {
+    /// Surveille `path` pour `cache` ; la première lecture vérifie le
+    /// fichier
+    pub fn new(cache: Cache<K, V, P>, path: impl AsRef<Path>, interval: Duration) -> Self {
+        WatchedCache {
+            cache,
+            path: path.as_ref().to_path_buf(),
+            interval,
+            checked_at: None,
+        }
+    }
+
+    /// Retourne le cache, sans la surveillance
+    pub fn into_inner(self) -> Cache<K, V, P> {
+        self.cache
+    }
+
+    /// Retourne le fichier surveillé
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+impl<K, V, P> WatchedCache<K, V, P>
+where
+    K: Eq + Hash + Display + FromStr,
+    V: Display + FromStr,
+    P: EvictionPolicy<K> + Default,
+{
+    /// Recharge le fichier s'il a changé, sans attendre l'intervalle, et
+    /// indique s'il a été rechargé
+    ///
+    /// # Errors
+    ///
+    /// Les erreurs de [`Cache::reload_if_changed`].
+    pub fn check(&mut self) -> io::Result<bool> {
+        self.checked_at = Some(self.cache.now());
+        self.cache.reload_if_changed(&self.path)
+    }
+
+    /// Récupère la valeur associée à `key` comme [`CacheStorage::get`],
+    /// après avoir rechargé le fichier s'il a changé et que la dernière
+    /// vérification date d'au moins `interval`
+    pub fn get(&mut self, key: &K) -> Option<&V> {
+        let now = self.cache.now();
+        let due = self.checked_at.is_none_or(|checked_at| now.saturating_duration_since(checked_at) >= self.interval);
+        if due {
+            let _ = self.check();
+        }
+        self.cache.get(key)
+    }
+}
+
+impl<K, V, P> Deref for WatchedCache<K, V, P> {
+    type Target = Cache<K, V, P>;
+
+    fn deref(&self) -> &Cache<K, V, P> {
+        &self.cache
+    }
+}
+
+impl<K, V, P> DerefMut for WatchedCache<K, V, P> {
+    fn deref_mut(&mut self) -> &mut Cache<K, V, P> {
+        &mut self.cache
+    }
+}
+
+impl<K: fmt::Debug, V: fmt::Debug, P: fmt::Debug> fmt::Debug for WatchedCache<K, V, P> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("WatchedCache")
+            .field("cache", &self.cache)
+            .field("path", &self.path)
+            .field("interval", &self.interval)
+            .finish_non_exhaustive()
+    }
+}
@@ -0,0 +1,122 @@
+use super::list::NodeList;
+use super::traits::CacheLen;
+use alloc::sync::{Arc, Weak};
+use core::hash::Hash;
+
+/// Cache LRU qui ne garde que des références faibles vers ses valeurs
+///
+/// Les valeurs appartiennent à l'appelant, qui les partage avec le cache
+/// par des [`Arc`] : le cache ne les maintient pas en vie. Une entrée dont
+/// la valeur n'a plus de référence forte est morte ; elle est traitée comme
+/// absente par `get`, qui la retire au passage, et
+/// [`WeakValueCache::purge_dead`] les retire toutes. Une entrée morte pas
+/// encore rencontrée compte encore dans `len`.
+///
+/// Quand le cache est plein, une insertion retire la première entrée morte
+/// dans l'ordre d'éviction, en parcourant les entrées vivantes les moins
+/// récemment utilisées ; s'il n'y en a pas, elle évince l'entrée la moins
+/// récemment utilisée.
+///
+/// # Examples
+/// ```
+/// use lru_cache::cache::traits::CacheLen;
+/// use lru_cache::cache::weak::WeakValueCache;
+/// use std::sync::Arc;
+///
+/// let mut cache = WeakValueCache::new(2);
+/// let texture = Arc::new([0u8; 16]);
+/// cache.put("sol", Arc::clone(&texture));
+/// assert!(cache.get(&"sol").is_some());
+///
+/// drop(texture);
+/// assert_eq!(cache.get(&"sol"), None);
+/// assert_eq!(cache.len(), 0);
+/// ```
+#[derive(Debug)]
+pub struct WeakValueCache<K, V> {
+    capacity: usize,
+    entries: NodeList<K, Weak<V>, ()>,
+}
+
+impl<K: Eq + Hash, V> WeakValueCache<K, V> {
+    /// Crée un nouveau cache avec la capacité spécifiée
+    ///
+    /// # Panics
+    ///
+    /// Panique si `capacity` vaut 0.
+    pub fn new(capacity: usize) -> Self {
+        assert!(capacity > 0, "la capacité d'un cache doit être au moins 1");
+        WeakValueCache {
+            capacity,
+            entries: NodeList::new(),
+        }
+    }
+
+    /// Retourne la capacité du cache
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Associe `key` à une référence faible vers `value` et la marque comme
+    /// la plus récemment utilisée
+    pub fn put(&mut self, key: K, value: Arc<V>) {
+        let value = Arc::downgrade(&value);
+        if let Some(index) = self.entries.find(&key) {
+            self.entries.node_mut(index).value = value;
+            self.entries.move_to_back(index);
+            return;
+        }
+        if self.entries.len() >= self.capacity {
+            let dead = core::iter::successors(self.entries.head(), |&index| self.entries.next(index))
+                .find(|&index| self.entries.node(index).value.strong_count() == 0);
+            if let Some(victim) = dead.or(self.entries.head()) {
+                self.entries.remove(victim);
+            }
+        }
+        self.entries.push(key, value, ());
+    }
+
+    /// Retourne la valeur de `key` si elle est encore vivante et la marque
+    /// comme la plus récemment utilisée
+    ///
+    /// Une entrée morte est retirée et `None` retourné.
+    pub fn get(&mut self, key: &K) -> Option<Arc<V>> {
+        let index = self.entries.find(key)?;
+        match self.entries.node(index).value.upgrade() {
+            Some(value) => {
+                self.entries.move_to_back(index);
+                Some(value)
+            }
+            None => {
+                self.entries.remove(index);
+                None
+            }
+        }
+    }
+
+    /// Retire `key` du cache et retourne sa valeur si elle était vivante
+    pub fn remove(&mut self, key: &K) -> Option<Arc<V>> {
+        let index = self.entries.find(key)?;
+        self.entries.remove(index).value.upgrade()
+    }
+
+    /// Retire toutes les entrées mortes et retourne leur nombre
+    pub fn purge_dead(&mut self) -> usize {
+        let mut purged = 0;
+        let mut current = self.entries.head();
+        while let Some(index) = current {
+            current = self.entries.next(index);
+            if self.entries.node(index).value.strong_count() == 0 {
+                self.entries.remove(index);
+                purged += 1;
+            }
+        }
+        purged
+    }
+}
+
+impl<K, V> CacheLen for WeakValueCache<K, V> {
+    fn len(&self) -> usize {
+        self.entries.len()
+    }
+}
@@ -0,0 +1,24 @@
+//! Poids des entrées pour une capacité exprimée en unités arbitraires
+
+/// Calcule le poids d'une entrée, par exemple sa taille en octets
+///
+/// Toute fonction `Fn(&K, &V) -> usize` est un `Weigher`. Le poids d'une
+/// entrée est calculé à son insertion et n'est pas recalculé ensuite.
+///
+/// # Examples
+/// ```
+/// use lru_cache::cache::weigher::Weigher;
+///
+/// let by_len = |_: &&str, value: &String| value.len();
+/// assert_eq!(by_len.weight(&"key", &String::from("abc")), 3);
+/// ```
+pub trait Weigher<K, V>: Send + Sync {
+    /// Retourne le poids de l'entrée
+    fn weight(&self, key: &K, value: &V) -> usize;
+}
+
+impl<K, V, F: Fn(&K, &V) -> usize + Send + Sync> Weigher<K, V> for F {
+    fn weight(&self, key: &K, value: &V) -> usize {
+        self(key, value)
+    }
+}
@@ -0,0 +1,358 @@
+//! Roue temporelle hiérarchique qui range les emplacements d'un cache par
+//! échéance
+//!
+//! Le temps est découpé en ticks d'une durée fixe (la granularité) comptés
+//! depuis la création de la roue. Chacun des `LEVELS` niveaux a 64 cases :
+//! une case du niveau 0 couvre un tick, une case du niveau `n` en couvre
+//! `64^n`. Une entrée est rangée au niveau le plus bas dont la fenêtre
+//! courante contient son échéance ; en avançant, la roue vide les cases
+//! atteintes et redescend d'un niveau les entrées qui ne sont pas encore
+//! dues. Avancer coûte donc le nombre d'entrées dues et de cases
+//! traversées, pas le nombre d'entrées rangées.
+//!
+//! Les cases sont des listes doublement chaînées dont les liens sont
+//! indexés par emplacement, si bien que déplacer ou retirer une entrée est
+//! en temps constant.
+//!
+//! Les entrées atteintes sont gardées, par tick croissant, dans une liste
+//! des dues jusqu'à ce qu'on les en retire : l'éviction y trouve une entrée
+//! expirée sans parcourir le cache.
+
+use super::clock::Instant;
+use alloc::vec::Vec;
+use core::time::Duration;
+
+const SLOT_BITS: usize = 6;
+const SLOTS: usize = 1 << SLOT_BITS;
+/// Assez de niveaux pour couvrir tous les ticks sur 64 bits
+const LEVELS: usize = 64 / SLOT_BITS + 1;
+/// Niveau fictif qui désigne la liste des dues dans `Link::bucket`
+const DUE: usize = LEVELS;
+
+#[derive(Debug, Clone, Copy)]
+struct Level {
+    /// Bit `i` levé si la case `i` n'est pas vide
+    occupied: u64,
+    heads: [Option<usize>; SLOTS],
+}
+
+/// Position d'un emplacement dans la roue
+#[derive(Debug, Clone, Copy, Default)]
+struct Link {
+    tick: u64,
+    /// Niveau et case, `(DUE, 0)` dans la liste des dues, `None` si
+    /// l'emplacement n'est pas rangé
+    bucket: Option<(usize, usize)>,
+    prev: Option<usize>,
+    next: Option<usize>,
+}
+
+/// Place qu'occupait un emplacement retiré de la roue, pour l'y remettre
+/// avec [`TimingWheel::restore`]
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Position {
+    origin: Instant,
+    granularity: Duration,
+    tick: u64,
+    bucket: (usize, usize),
+    prev: Option<usize>,
+    next: Option<usize>,
+}
+
+#[derive(Debug)]
+pub(crate) struct TimingWheel {
+    granularity: Duration,
+    origin: Instant,
+    /// Tick jusqu'auquel la roue a avancé
+    elapsed: u64,
+    /// Instant le plus tardif donné à `advance`, ou la création
+    latest: Instant,
+    levels: [Level; LEVELS],
+    /// Tête et queue de la liste des dues
+    due: Option<(usize, usize)>,
+    links: Vec<Link>,
+    len: usize,
+}
+
+impl TimingWheel {
+    /// Crée une roue vide dont le tick 0 commence à `origin`
+    pub(crate) fn new(granularity: Duration, origin: Instant) -> Self {
+        TimingWheel {
+            granularity,
+            origin,
+            elapsed: 0,
+            latest: origin,
+            levels: [Level {
+                occupied: 0,
+                heads: [None; SLOTS],
+            }; LEVELS],
+            due: None,
+            links: Vec::new(),
+            len: 0,
+        }
+    }
+
+    /// Nombre d'emplacements rangés
+    pub(crate) fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Nombre d'emplacements de la liste des dues parcourue de la tête et
+    /// de la queue, qui diffèrent si le chaînage est rompu
+    ///
+    /// Chaque parcours s'arrête après `len + 1` emplacements.
+    pub(crate) fn due_len(&self) -> (usize, usize) {
+        let count = |start: Option<usize>, step: fn(&Link) -> Option<usize>| {
+            core::iter::successors(start, |&index| step(&self.links[index]))
+                .take(self.len + 1)
+                .count()
+        };
+        (
+            count(self.due.map(|(head, _)| head), |link| link.next),
+            count(self.due.map(|(_, tail)| tail), |link| link.prev),
+        )
+    }
+
+    /// Indique si `now` précède un instant déjà vu : l'horloge a reculé et
+    /// les échéances ramenées depuis ne sont plus à leur place
+    pub(crate) fn is_behind(&self, now: Instant) -> bool {
+        now < self.latest
+    }
+
+    /// Premier tick qui commence à `instant` ou après : une échéance rangée
+    /// au tick `t` est atteinte au début de ce tick
+    fn tick_ceil(&self, instant: Instant) -> u64 {
+        let since = instant.saturating_duration_since(self.origin).as_nanos();
+        let granularity = self.granularity.as_nanos();
+        u64::try_from(since.div_ceil(granularity)).unwrap_or(u64::MAX)
+    }
+
+    /// Range l'emplacement `index` à l'échéance `deadline`, ou le déplace
+    ///
+    /// Une échéance déjà dépassée est rangée au tick courant et sera due à
+    /// la prochaine avance.
+    pub(crate) fn schedule(&mut self, index: usize, deadline: Instant) {
+        let tick = self.tick_ceil(deadline).max(self.elapsed);
+        if let Some(link) = self.links.get(index) {
+            if link.bucket.is_some() && link.tick == tick {
+                return;
+            }
+        }
+        self.cancel(index);
+        if self.links.len() <= index {
+            self.links.resize(index + 1, Link::default());
+        }
+        self.links[index].tick = tick;
+        self.insert(index);
+        self.len += 1;
+    }
+
+    /// Place de l'emplacement `index`, s'il est rangé
+    pub(crate) fn position(&self, index: usize) -> Option<Position> {
+        let link = self.links.get(index)?;
+        Some(Position {
+            origin: self.origin,
+            granularity: self.granularity,
+            tick: link.tick,
+            bucket: link.bucket?,
+            prev: link.prev,
+            next: link.next,
+        })
+    }
+
+    /// Range de nouveau l'emplacement `index` à la place `position` qu'il
+    /// occupait, ou à l'échéance `deadline` si cette place n'existe plus
+    ///
+    /// L'emplacement est remis à côté de l'un de ses anciens voisins encore
+    /// dans la même case. Sans voisin restant, une entrée de la liste des
+    /// dues y revient en tête si elle y était la première, en queue sinon.
+    /// Une place prise dans une autre roue ou dans une case que la roue a
+    /// dépassée depuis ne compte pas.
+    pub(crate) fn restore(&mut self, index: usize, position: Position, deadline: Instant) {
+        let tick = position.tick.max(self.elapsed);
+        let same_wheel = position.origin == self.origin && position.granularity == self.granularity;
+        let bucket = position.bucket;
+        if !same_wheel || (bucket.0 != DUE && self.bucket_of(tick) != bucket) {
+            self.schedule(index, deadline);
+            return;
+        }
+        self.cancel(index);
+        if self.links.len() <= index {
+            self.links.resize(index + 1, Link::default());
+        }
+        self.links[index].tick = if bucket.0 == DUE { position.tick } else { tick };
+        let linked = |neighbor: Option<usize>| neighbor.filter(|&neighbor| self.links[neighbor].bucket == Some(bucket));
+        let prev = match (linked(position.prev), linked(position.next)) {
+            (Some(prev), _) => Some(prev),
+            (None, Some(next)) => self.links[next].prev,
+            (None, None) if bucket.0 == DUE && position.prev.is_some() => self.due.map(|(_, tail)| tail),
+            (None, None) => None,
+        };
+        self.link_after(index, bucket, prev);
+        self.len += 1;
+    }
+
+    /// Retire l'emplacement `index` de la roue, s'il y est rangé
+    pub(crate) fn cancel(&mut self, index: usize) {
+        if self.links.get(index).is_some_and(|link| link.bucket.is_some()) {
+            self.unlink(index);
+            self.len -= 1;
+        }
+    }
+
+    /// Avance la roue jusqu'au tick qui contient `now` et ajoute à `due`
+    /// les emplacements dont le tick est atteint, qui ne sont plus rangés
+    ///
+    /// Une échéance de `due` peut tomber un peu après `now`, dans le même
+    /// tick. Ne fait rien si `now` précède le tick courant.
+    pub(crate) fn advance(&mut self, now: Instant, due: &mut Vec<usize>) {
+        self.collect(now);
+        while let Some((head, _)) = self.due {
+            due.push(head);
+            self.unlink(head);
+            self.len -= 1;
+        }
+    }
+
+    /// Avance la roue jusqu'au tick qui contient `now` et retourne le
+    /// premier emplacement dû pour lequel `expired` est vrai, sans le
+    /// retirer
+    ///
+    /// Les dues sont examinées par tick croissant ; seules celles dont
+    /// l'échéance tombe après `now`, dans le tick courant, sont passées.
+    pub(crate) fn first_due(&mut self, now: Instant, expired: impl Fn(usize) -> bool) -> Option<usize> {
+        self.collect(now);
+        let mut current = self.due.map(|(head, _)| head);
+        while let Some(index) = current {
+            if expired(index) {
+                return Some(index);
+            }
+            current = self.links[index].next;
+        }
+        None
+    }
+
+    /// Avance la roue jusqu'au tick qui contient `now` et déplace dans la
+    /// liste des dues les emplacements dont le tick est atteint
+    fn collect(&mut self, now: Instant) {
+        self.latest = self.latest.max(now);
+        let target = self.tick_ceil(now);
+        while let Some((level, slot, start)) = self.next_expiration() {
+            if start > target {
+                break;
+            }
+            self.elapsed = self.elapsed.max(start);
+            let mut current = self.levels[level].heads[slot].take();
+            self.levels[level].occupied &= !(1 << slot);
+            while let Some(index) = current {
+                current = self.links[index].next;
+                self.links[index].bucket = None;
+                if self.links[index].tick <= target {
+                    self.push_due(index);
+                } else {
+                    self.insert(index);
+                }
+            }
+        }
+        self.elapsed = self.elapsed.max(target);
+    }
+
+    /// Ajoute `index` en queue de la liste des dues
+    fn push_due(&mut self, index: usize) {
+        let tail = self.due.map(|(_, tail)| tail);
+        self.link_after(index, (DUE, 0), tail);
+    }
+
+    /// Chaîne `index` dans la case `bucket` après `prev`, ou en tête
+    fn link_after(&mut self, index: usize, bucket: (usize, usize), prev: Option<usize>) {
+        let (level, slot) = bucket;
+        let next = match prev {
+            Some(prev) => self.links[prev].next.replace(index),
+            None if level == DUE => self.due.map(|(head, _)| head),
+            None => {
+                self.levels[level].occupied |= 1 << slot;
+                self.levels[level].heads[slot].replace(index)
+            }
+        };
+        if let Some(next) = next {
+            self.links[next].prev = Some(index);
+        }
+        if level == DUE {
+            let (head, tail) = self.due.unwrap_or((index, index));
+            self.due = Some((
+                if prev.is_none() { index } else { head },
+                if next.is_none() { index } else { tail },
+            ));
+        }
+        let link = &mut self.links[index];
+        link.bucket = Some(bucket);
+        link.prev = prev;
+        link.next = next;
+    }
+
+    /// Niveau, case et tick de début de la prochaine case non vide
+    ///
+    /// Les cases d'un niveau sont toutes avant celles des niveaux
+    /// supérieurs : le premier niveau non vide contient la suivante.
+    fn next_expiration(&self) -> Option<(usize, usize, u64)> {
+        let (level, state) = self.levels.iter().enumerate().find(|(_, state)| state.occupied != 0)?;
+        let shift = level * SLOT_BITS;
+        let current = (self.elapsed >> shift) as usize % SLOTS;
+        let slot = (state.occupied.rotate_right(current as u32).trailing_zeros() as usize + current) % SLOTS;
+        let window = shift + SLOT_BITS;
+        let level_start = if window >= 64 { 0 } else { self.elapsed & !((1 << window) - 1) };
+        let mut start = level_start + ((slot as u64) << shift);
+        if start < self.elapsed && window < 64 {
+            start = start.saturating_add(1 << window);
+        }
+        Some((level, slot, start))
+    }
+
+    /// Range `index` selon son tick et le tick courant
+    fn insert(&mut self, index: usize) {
+        let bucket = self.bucket_of(self.links[index].tick);
+        self.link_after(index, bucket, None);
+    }
+
+    /// Niveau et case du tick `tick`, qui ne précède pas le tick courant
+    fn bucket_of(&self, tick: u64) -> (usize, usize) {
+        let significant = 63 - ((self.elapsed ^ tick) | (SLOTS as u64 - 1)).leading_zeros() as usize;
+        let level = significant / SLOT_BITS;
+        (level, (tick >> (level * SLOT_BITS)) as usize % SLOTS)
+    }
+
+    fn unlink(&mut self, index: usize) {
+        let Link { bucket, prev, next, .. } = self.links[index];
+        let Some((level, slot)) = bucket else {
+            return;
+        };
+        if level == DUE {
+            let (head, tail) = self.due.expect("une entrée due dans une liste non vide");
+            self.due = match (prev, next) {
+                (None, None) => None,
+                (None, Some(next)) => Some((next, tail)),
+                (Some(prev), None) => Some((head, prev)),
+                (Some(_), Some(_)) => Some((head, tail)),
+            };
+            if let Some(prev) = prev {
+                self.links[prev].next = next;
+            }
+            if let Some(next) = next {
+                self.links[next].prev = prev;
+            }
+            self.links[index].bucket = None;
+            return;
+        }
+        match prev {
+            Some(prev) => self.links[prev].next = next,
+            None => self.levels[level].heads[slot] = next,
+        }
+        if let Some(next) = next {
+            self.links[next].prev = prev;
+        }
+        if self.levels[level].heads[slot].is_none() {
+            self.levels[level].occupied &= !(1 << slot);
+        }
+        self.links[index].bucket = None;
+    }
+}
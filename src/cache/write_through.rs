@@ -0,0 +1,226 @@
+//! Cache dont chaque modification est enregistrée aussitôt dans son fichier
+
+use super::lru::{check_capacity, Cache};
+use super::traits::{CacheRemove, CacheStorage, GetOptions, PersistentStorage};
+use crate::storage::error::StorageError;
+use std::fmt::{self, Display};
+use std::fs;
+use std::hash::Hash;
+use std::io;
+use std::ops::Deref;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+/// Enregistre le cache dans son fichier après une modification
+type Persist<K, V> = fn(&mut Cache<K, V>, &Path) -> Result<(), StorageError>;
+
+/// [`Cache`] lié à un fichier, qui l'enregistre après chaque insertion ou
+/// suppression
+///
+/// Les méthodes qui modifient le cache retournent une erreur si
+/// l'enregistrement échoue ; la modification reste alors faite en mémoire et
+/// sera enregistrée par la suivante ou par [`WriteThroughCache::flush`].
+/// Deux modes sont proposés :
+///
+/// - [`WriteThroughCache::open`] réécrit le fichier entier à chaque fois, de
+///   façon atomique : adapté à un petit cache.
+/// - [`WriteThroughCache::open_appending`] ajoute à la fin du fichier un
+///   segment contenant seulement les entrées modifiées (voir
+///   [`Cache::save_incremental`]) ; [`WriteThroughCache::flush`] réécrit le
+///   fichier en un seul instantané.
+///
+/// Les lectures ne sont pas enregistrées : l'ordre d'utilisation qu'elles
+/// produisent est écrit par la prochaine réécriture complète. Le cache se
+/// lit à travers `Deref` ; il ne se modifie que par les méthodes de
+/// `WriteThroughCache`, qui ne peuvent pas être contournées.
+///
+/// # Examples
+/// ```
+/// use lru_cache::cache::write_through::WriteThroughCache;
+///
+/// let path = std::env::temp_dir().join("write_through_doc.txt");
+/// {
+///     let mut cache = WriteThroughCache::<String, i32>::open(&path, 10).unwrap();
+///     cache.put(String::from("key"), 1).unwrap();
+/// }
+///
+/// let mut cache = WriteThroughCache::<String, i32>::open(&path, 10).unwrap();
+/// assert_eq!(cache.get(&String::from("key")), Some(&1));
+/// # std::fs::remove_file(path).unwrap();
+/// ```
+pub struct WriteThroughCache<K, V> {
+    cache: Cache<K, V>,
+    path: PathBuf,
+    persist: Persist<K, V>,
+    rewrite: Persist<K, V>,
+}
+
+impl<K, V> WriteThroughCache<K, V>
+where
+    K: Eq + Hash + Display + FromStr,
+    V: Display + FromStr,
+{
+    /// Ouvre le cache lié à `path`, rechargé depuis le fichier s'il existe,
+    /// qui réécrit le fichier après chaque modification
+    ///
+    /// Un fichier absent est créé aussitôt, ainsi que son répertoire.
+    ///
+    /// # Errors
+    ///
+    /// Retourne une erreur si le fichier existe mais ne peut pas être
+    /// chargé, ou s'il ne peut pas être créé, et une erreur
+    /// [`io::ErrorKind::InvalidInput`] si `capacity` vaut 0.
+    pub fn open(path: impl Into<PathBuf>, capacity: usize) -> io::Result<Self> {
+        let path = path.into();
+        let cache = load_or_create(&path, capacity)?;
+        let mut cache = WriteThroughCache {
+            cache,
+            path,
+            persist: |cache, path| Ok(cache.save_to_file(path)?),
+            rewrite: |cache, path| Ok(cache.save_to_file(path)?),
+        };
+        if !cache.path.exists() {
+            cache.flush()?;
+        }
+        Ok(cache)
+    }
+
+    /// Ouvre le cache lié à `path` comme [`WriteThroughCache::open`], mais
+    /// enregistre chaque modification en ajoutant un segment à la fin du
+    /// fichier
+    ///
+    /// # Errors
+    ///
+    /// Les erreurs de [`WriteThroughCache::open`].
+    pub fn open_appending(path: impl Into<PathBuf>, capacity: usize) -> io::Result<Self>
+    where
+        K: Clone,
+    {
+        let path = path.into();
+        let mut cache = WriteThroughCache {
+            cache: load_or_create(&path, capacity)?,
+            path,
+            persist: |cache, path| cache.save_incremental(path),
+            rewrite: |cache, path| {
+                cache.save_to_file(path)?;
+                cache.track_changes();
+                Ok(())
+            },
+        };
+        if cache.path.exists() {
+            cache.cache.track_changes();
+        } else {
+            cache.flush()?;
+        }
+        Ok(cache)
+    }
+
+    /// Retourne le chemin du fichier
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Récupère la valeur associée à `key`, comme [`CacheStorage::get`]
+    pub fn get(&mut self, key: &K) -> Option<&V> {
+        self.cache.get(key)
+    }
+
+    /// Récupère la valeur associée à `key`, comme [`CacheStorage::get_with`]
+    pub fn get_with(&mut self, key: &K, options: GetOptions) -> Option<&V> {
+        self.cache.get_with(key, options)
+    }
+
+    /// Insère ou remplace une entrée, puis enregistre le cache
+    ///
+    /// # Errors
+    ///
+    /// Retourne une erreur si l'enregistrement échoue ; l'entrée reste
+    /// insérée.
+    pub fn put(&mut self, key: K, value: V) -> io::Result<()> {
+        self.cache.put(key, value);
+        self.persist()
+    }
+
+    /// Supprime une entrée et retourne sa valeur, puis enregistre le cache
+    /// si elle était présente
+    ///
+    /// # Errors
+    ///
+    /// Retourne une erreur si l'enregistrement échoue ; l'entrée reste
+    /// supprimée.
+    pub fn remove(&mut self, key: &K) -> io::Result<Option<V>> {
+        let value = self.cache.remove(key);
+        if value.is_some() {
+            self.persist()?;
+        }
+        Ok(value)
+    }
+
+    /// Supprime toutes les entrées, puis enregistre le cache
+    ///
+    /// # Errors
+    ///
+    /// Retourne une erreur si l'enregistrement échoue.
+    pub fn clear(&mut self) -> io::Result<()> {
+        self.cache.clear();
+        self.persist()
+    }
+
+    /// Réécrit le fichier entier avec le contenu et l'ordre d'utilisation
+    /// actuels
+    ///
+    /// En mode ajout, le fichier ne contient plus ensuite qu'un instantané.
+    ///
+    /// # Errors
+    ///
+    /// Retourne une erreur si le fichier ou son répertoire ne peut pas être
+    /// écrit.
+    pub fn flush(&mut self) -> io::Result<()> {
+        if let Some(parent) = self.path.parent() {
+            if !parent.as_os_str().is_empty() {
+                fs::create_dir_all(parent)?;
+            }
+        }
+        Ok((self.rewrite)(&mut self.cache, &self.path)?)
+    }
+
+    /// Retourne le cache en le détachant du fichier
+    pub fn into_inner(self) -> Cache<K, V> {
+        self.cache
+    }
+
+    fn persist(&mut self) -> io::Result<()> {
+        Ok((self.persist)(&mut self.cache, &self.path)?)
+    }
+}
+
+/// Charge `path` s'il existe, sinon crée un cache vide
+fn load_or_create<K, V>(path: &Path, capacity: usize) -> io::Result<Cache<K, V>>
+where
+    K: Eq + Hash + Display + FromStr,
+    V: Display + FromStr,
+{
+    let capacity = check_capacity(capacity)?;
+    if path.exists() {
+        Cache::load_from_file(path, capacity)
+    } else {
+        Ok(Cache::with_capacity(capacity))
+    }
+}
+
+impl<K, V> Deref for WriteThroughCache<K, V> {
+    type Target = Cache<K, V>;
+
+    fn deref(&self) -> &Cache<K, V> {
+        &self.cache
+    }
+}
+
+impl<K: fmt::Debug, V: fmt::Debug> fmt::Debug for WriteThroughCache<K, V> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("WriteThroughCache")
+            .field("cache", &self.cache)
+            .field("path", &self.path)
+            .finish_non_exhaustive()
+    }
+}
@@ -0,0 +1,234 @@
+//! Interface C d'un cache de chaînes (feature `ffi`)
+//!
+//! Le cache manipulé est un [`Cache<String, String>`](Cache) caché derrière
+//! le type opaque [`LruCache`]. Les clés et les valeurs sont des chaînes C
+//! terminées par un octet nul et encodées en UTF-8 ; elles sont copiées,
+//! l'appelant garde donc la propriété de ce qu'il passe. Les fonctions
+//! acceptent des pointeurs nuls et les signalent par
+//! [`LruCacheStatus::NullPointer`] ou un résultat nul, et aucune panique ne
+//! traverse la frontière : elle devient [`LruCacheStatus::Panic`].
+//!
+//! L'en-tête `include/lru_cache.h` déclare ces fonctions ; il suit la
+//! disposition que produirait `cbindgen`. La bibliothèque se construit avec
+//! `cargo rustc --release --features ffi --crate-type cdylib`.
+//!
+//! # Examples
+//! ```
+//! use lru_cache::ffi::*;
+//! use std::ffi::{CStr, CString};
+//!
+//! let key = CString::new("clé").unwrap();
+//! let value = CString::new("valeur").unwrap();
+//! unsafe {
+//!     let cache = lru_cache_new(2);
+//!     assert_eq!(lru_cache_put(cache, key.as_ptr(), value.as_ptr()), LruCacheStatus::Ok);
+//!     let found = lru_cache_get(cache, key.as_ptr());
+//!     assert_eq!(CStr::from_ptr(found).to_str(), Ok("valeur"));
+//!     lru_cache_string_free(found);
+//!     lru_cache_free(cache);
+//! }
+//! ```
+
+use crate::cache::traits::{CacheLen, CacheRemove, CacheStorage, PersistentStorage};
+use crate::Cache;
+use std::ffi::{c_char, CStr, CString};
+use std::num::NonZeroUsize;
+use std::panic::{self, AssertUnwindSafe};
+use std::ptr;
+
+/// Cache de chaînes manipulé par pointeur depuis le C
+pub struct LruCache {
+    inner: Cache<String, String>,
+}
+
+/// Résultat des fonctions qui ne retournent pas de pointeur
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LruCacheStatus {
+    /// L'opération a réussi
+    Ok = 0,
+    /// Un pointeur requis est nul
+    NullPointer = 1,
+    /// Une chaîne n'est pas de l'UTF-8 valide
+    InvalidUtf8 = 2,
+    /// La clé est absente du cache
+    NotFound = 3,
+    /// Le fichier n'a pas pu être lu ou écrit
+    Io = 4,
+    /// Une panique a été interceptée ; le cache reste utilisable mais son
+    /// contenu peut être incomplet
+    Panic = 5,
+}
+
+/// Lit une chaîne C en UTF-8, sans en prendre la propriété
+///
+/// # Safety
+///
+/// `s` doit être nul ou pointer vers une chaîne terminée par un octet nul.
+unsafe fn read_str<'a>(s: *const c_char) -> Result<&'a str, LruCacheStatus> {
+    if s.is_null() {
+        return Err(LruCacheStatus::NullPointer);
+    }
+    CStr::from_ptr(s).to_str().map_err(|_| LruCacheStatus::InvalidUtf8)
+}
+
+/// Exécute `f` en transformant une panique en [`LruCacheStatus::Panic`]
+fn guard(f: impl FnOnce() -> Result<(), LruCacheStatus>) -> LruCacheStatus {
+    match panic::catch_unwind(AssertUnwindSafe(f)) {
+        Ok(Ok(())) => LruCacheStatus::Ok,
+        Ok(Err(status)) => status,
+        Err(_) => LruCacheStatus::Panic,
+    }
+}
+
+/// Exécute `f` et retourne un pointeur nul en cas d'erreur ou de panique
+fn guard_ptr<T>(f: impl FnOnce() -> Option<*mut T>) -> *mut T {
+    panic::catch_unwind(AssertUnwindSafe(f)).ok().flatten().unwrap_or(ptr::null_mut())
+}
+
+fn into_raw(cache: Cache<String, String>) -> *mut LruCache {
+    Box::into_raw(Box::new(LruCache { inner: cache }))
+}
+
+/// Crée un cache vide de capacité `capacity`
+///
+/// Retourne un pointeur nul si `capacity` vaut 0. Le cache se libère par
+/// [`lru_cache_free`].
+#[no_mangle]
+pub extern "C" fn lru_cache_new(capacity: usize) -> *mut LruCache {
+    guard_ptr(|| Cache::try_new(capacity).ok().map(into_raw))
+}
+
+/// Libère un cache créé par [`lru_cache_new`] ou [`lru_cache_load`] ; un
+/// pointeur nul est ignoré
+///
+/// # Safety
+///
+/// `cache` doit être nul ou provenir de ces fonctions et ne plus être
+/// utilisé ensuite.
+#[no_mangle]
+pub unsafe extern "C" fn lru_cache_free(cache: *mut LruCache) {
+    if !cache.is_null() {
+        let _ = panic::catch_unwind(AssertUnwindSafe(|| drop(Box::from_raw(cache))));
+    }
+}
+
+/// Insère ou remplace la valeur de `key`, en évinçant l'entrée la moins
+/// récemment utilisée si le cache est plein
+///
+/// # Safety
+///
+/// `cache` doit être nul ou valide, `key` et `value` nulles ou terminées
+/// par un octet nul.
+#[no_mangle]
+pub unsafe extern "C" fn lru_cache_put(
+    cache: *mut LruCache,
+    key: *const c_char,
+    value: *const c_char,
+) -> LruCacheStatus {
+    guard(|| {
+        let cache = cache.as_mut().ok_or(LruCacheStatus::NullPointer)?;
+        let key = read_str(key)?;
+        let value = read_str(value)?;
+        cache.inner.put(key.to_string(), value.to_string());
+        Ok(())
+    })
+}
+
+/// Retourne une copie de la valeur de `key` et la marque comme la plus
+/// récemment utilisée
+///
+/// Retourne un pointeur nul si la clé est absente, si un argument est nul
+/// ou invalide, ou si la valeur contient un octet nul. La copie se libère
+/// par [`lru_cache_string_free`].
+///
+/// # Safety
+///
+/// `cache` doit être nul ou valide, `key` nulle ou terminée par un octet
+/// nul.
+#[no_mangle]
+pub unsafe extern "C" fn lru_cache_get(cache: *mut LruCache, key: *const c_char) -> *mut c_char {
+    guard_ptr(|| {
+        let cache = cache.as_mut()?;
+        let key = read_str(key).ok()?;
+        let value = cache.inner.get(&key.to_string())?;
+        CString::new(value.as_str()).ok().map(CString::into_raw)
+    })
+}
+
+/// Libère une chaîne retournée par [`lru_cache_get`] ; un pointeur nul est
+/// ignoré
+///
+/// # Safety
+///
+/// `s` doit être nul ou provenir de [`lru_cache_get`] et ne plus être
+/// utilisé ensuite.
+#[no_mangle]
+pub unsafe extern "C" fn lru_cache_string_free(s: *mut c_char) {
+    if !s.is_null() {
+        let _ = panic::catch_unwind(AssertUnwindSafe(|| drop(CString::from_raw(s))));
+    }
+}
+
+/// Retire `key` du cache
+///
+/// Retourne [`LruCacheStatus::NotFound`] si la clé était absente.
+///
+/// # Safety
+///
+/// `cache` doit être nul ou valide, `key` nulle ou terminée par un octet
+/// nul.
+#[no_mangle]
+pub unsafe extern "C" fn lru_cache_remove(cache: *mut LruCache, key: *const c_char) -> LruCacheStatus {
+    guard(|| {
+        let cache = cache.as_mut().ok_or(LruCacheStatus::NullPointer)?;
+        let key = read_str(key)?;
+        match cache.inner.remove(&key.to_string()) {
+            Some(_) => Ok(()),
+            None => Err(LruCacheStatus::NotFound),
+        }
+    })
+}
+
+/// Retourne le nombre d'entrées du cache, 0 pour un pointeur nul
+///
+/// # Safety
+///
+/// `cache` doit être nul ou valide.
+#[no_mangle]
+pub unsafe extern "C" fn lru_cache_len(cache: *const LruCache) -> usize {
+    panic::catch_unwind(AssertUnwindSafe(|| cache.as_ref().map_or(0, |cache| cache.inner.len()))).unwrap_or(0)
+}
+
+/// Sauvegarde le cache dans le fichier `path`, au format de
+/// [`PersistentStorage::save_to_file`]
+///
+/// # Safety
+///
+/// `cache` doit être nul ou valide, `path` nul ou terminé par un octet nul.
+#[no_mangle]
+pub unsafe extern "C" fn lru_cache_save(cache: *const LruCache, path: *const c_char) -> LruCacheStatus {
+    guard(|| {
+        let cache = cache.as_ref().ok_or(LruCacheStatus::NullPointer)?;
+        let path = read_str(path)?;
+        cache.inner.save_to_file(path).map_err(|_| LruCacheStatus::Io)
+    })
+}
+
+/// Charge un cache de capacité `capacity` depuis le fichier `path`
+///
+/// Retourne un pointeur nul si `capacity` vaut 0, si `path` est nul ou
+/// invalide, ou si le fichier ne peut pas être lu. Le cache se libère par
+/// [`lru_cache_free`].
+///
+/// # Safety
+///
+/// `path` doit être nul ou terminé par un octet nul.
+#[no_mangle]
+pub unsafe extern "C" fn lru_cache_load(path: *const c_char, capacity: usize) -> *mut LruCache {
+    guard_ptr(|| {
+        let path = read_str(path).ok()?;
+        let capacity = NonZeroUsize::new(capacity)?;
+        Cache::load_from_file(path, capacity).ok().map(into_raw)
+    })
+}
@@ -7,6 +7,19 @@
 //! - Cache générique supportant différents types de clés et valeurs
 //! - Persistance des données dans un fichier
 //! - Implémentation basée sur les traits pour plus de flexibilité
+//! - Événements [`tracing`](https://docs.rs/tracing) optionnels (feature
+//!   `tracing`) lors des insertions, lectures, évictions et des accès aux
+//!   fichiers ; les clés y sont identifiées par un hash stable (`key_hash`)
+//! - Sauvegarde au format JSON (feature `serde`), lisible et modifiable à
+//!   la main
+//! - Fichiers de sauvegarde compressés en gzip (feature `compression`),
+//!   reconnus automatiquement au chargement
+//! - Utilisable sans la bibliothèque standard : sans la feature `std`
+//!   (active par défaut), [`Cache`] ne dépend que de `core` et `alloc` ; la
+//!   persistance, les caches partagés entre threads et l'horloge du système
+//!   restent réservés à `std`
+//! - Interface C pour un cache de chaînes (feature `ffi`), décrite par
+//!   `include/lru_cache.h`
 //! 
 //! ## Exemple d'utilisation
 //! 
@@ -51,7 +64,18 @@
 //! fs::remove_file(path).unwrap();
 //! ```
 
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+#[macro_use]
+mod trace;
+mod macros;
+
 pub mod cache;
+#[cfg(feature = "ffi")]
+pub mod ffi;
+#[cfg(feature = "std")]
 pub mod storage;
 
-pub use cache::lru::Cache;
\ No newline at end of file
+pub use cache::lru::{Cache, EntryId};
\ No newline at end of file
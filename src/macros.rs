@@ -0,0 +1,48 @@
+//! Macros publiques de construction
+
+/// Crée un [`Cache`](crate::Cache) rempli avec les entrées données
+///
+/// Les entrées sont insérées dans l'ordre par `put` : la première listée est
+/// la moins récemment utilisée, donc la prochaine évincée, et la dernière la
+/// plus récente. Sans `capacity`, la capacité est le nombre d'entrées
+/// listées. Une virgule finale est acceptée. Le trait
+/// [`CacheStorage`](crate::cache::traits::CacheStorage) n'a pas besoin
+/// d'être importé.
+///
+/// # Panics
+///
+/// Panique si la capacité vaut 0, comme [`Cache::new`](crate::Cache::new) :
+/// sans `capacity`, au moins une entrée doit être listée.
+///
+/// # Examples
+/// ```
+/// use lru_cache::cache;
+/// use lru_cache::cache::traits::{CacheIterate, CacheStorage};
+///
+/// let mut cache = cache! { capacity: 3; "A" => 1, "B" => 2 };
+/// assert_eq!(cache.capacity(), 3);
+/// cache.put("C", 3);
+/// cache.put("D", 4);
+/// assert_eq!(cache.get(&"A"), None);
+///
+/// let cache = cache! {
+///     String::from("x") => vec![1],
+///     String::from("y") => vec![2],
+/// };
+/// assert_eq!(cache.capacity(), 2);
+/// assert_eq!(cache.iter().next(), Some((&String::from("x"), &vec![1])));
+/// ```
+#[macro_export]
+macro_rules! cache {
+    (@unit $key:expr) => {
+        ()
+    };
+    (capacity: $capacity:expr; $($key:expr => $value:expr),* $(,)?) => {{
+        let mut cache = $crate::Cache::new($capacity);
+        $($crate::cache::traits::CacheStorage::put(&mut cache, $key, $value);)*
+        cache
+    }};
+    ($($key:expr => $value:expr),* $(,)?) => {
+        $crate::cache!(capacity: <[()]>::len(&[$($crate::cache!(@unit $key)),*]); $($key => $value),*)
+    };
+}
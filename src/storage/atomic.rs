@@ -0,0 +1,72 @@
+//! Écriture atomique d'un fichier de sauvegarde
+//!
+//! Le contenu est écrit dans un fichier temporaire du même répertoire,
+//! synchronisé sur le disque puis renommé sur la destination. Un processus
+//! interrompu pendant l'écriture laisse au pire un fichier temporaire
+//! orphelin : la destination contient toujours l'ancienne sauvegarde
+//! complète ou la nouvelle, jamais un mélange des deux.
+
+use std::fs::{self, File};
+use std::io::{self, BufWriter};
+use std::path::{Path, PathBuf};
+use std::process;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// Distingue les écritures simultanées d'un même processus vers un même
+/// chemin
+static SEQUENCE: AtomicUsize = AtomicUsize::new(0);
+
+/// Chemin temporaire `<path>.tmp.<pid>.<n>`, dans le répertoire de `path`
+fn temp_path(path: &Path) -> PathBuf {
+    let mut name = path.as_os_str().to_os_string();
+    name.push(format!(".tmp.{}.{}", process::id(), SEQUENCE.fetch_add(1, Ordering::Relaxed)));
+    PathBuf::from(name)
+}
+
+/// Fichier temporaire supprimé à la destruction s'il n'a pas été renommé,
+/// y compris lorsque l'écriture panique
+struct TempFile(PathBuf);
+
+impl Drop for TempFile {
+    fn drop(&mut self) {
+        let _ = fs::remove_file(&self.0);
+    }
+}
+
+/// Écrit `path` avec `write`, sans jamais laisser de fichier partiel
+///
+/// Si `write`, la synchronisation ou le renommage échoue, le fichier
+/// temporaire est supprimé et `path` n'est pas modifié. `fs::rename`
+/// remplace une destination existante sur toutes les plateformes prises en
+/// charge par la bibliothèque standard, y compris Windows.
+pub(crate) fn write_atomic<T>(
+    path: impl AsRef<Path>,
+    write: impl FnOnce(&mut BufWriter<File>) -> io::Result<T>,
+) -> io::Result<T> {
+    let path = path.as_ref();
+    let temp = TempFile(temp_path(path));
+    let mut writer = BufWriter::new(File::create(&temp.0)?);
+    let value = write(&mut writer)?;
+    writer.into_inner().map_err(io::IntoInnerError::into_error)?.sync_all()?;
+    fs::rename(&temp.0, path)?;
+    std::mem::forget(temp);
+    sync_parent(path);
+    Ok(value)
+}
+
+/// Synchronise le répertoire pour rendre le renommage durable ; sans effet
+/// là où un répertoire ne peut pas être ouvert comme un fichier
+fn sync_parent(path: &Path) {
+    #[cfg(unix)]
+    {
+        let parent = match path.parent() {
+            Some(parent) if !parent.as_os_str().is_empty() => parent,
+            _ => Path::new("."),
+        };
+        if let Ok(dir) = File::open(parent) {
+            let _ = dir.sync_all();
+        }
+    }
+    #[cfg(not(unix))]
+    let _ = path;
+}
@@ -0,0 +1,141 @@
+//! Destinations de sauvegarde indépendantes du système de fichiers
+//!
+//! [`StorageBackend`] découple le cache de l'endroit où ses entrées sont
+//! conservées : un fichier ([`FileBackend`]), un tampon en mémoire
+//! ([`MemoryBackend`]) ou tout autre service (base clé-valeur, stockage
+//! objet...). Le cache convertit ses clés et valeurs en chaînes avec
+//! `Display` et les relit avec `FromStr`, comme pour un fichier.
+//!
+//! # Examples
+//! ```
+//! use lru_cache::Cache;
+//! use lru_cache::cache::traits::CacheStorage;
+//! use lru_cache::storage::backend::MemoryBackend;
+//! use std::num::NonZeroUsize;
+//!
+//! let mut cache = Cache::new(2);
+//! cache.put(String::from("key"), 1);
+//!
+//! let mut backend = MemoryBackend::new();
+//! cache.save_to(&mut backend).unwrap();
+//!
+//! let mut loaded = Cache::<String, i32>::load_from(&mut backend, NonZeroUsize::new(2).unwrap()).unwrap();
+//! assert_eq!(loaded.get(&String::from("key")), Some(&1));
+//! ```
+
+use super::error::StorageError;
+use super::file::{FileStorage, SaveOptions};
+use std::path::{Path, PathBuf};
+
+/// Destination où sauvegarder et recharger les entrées d'un cache
+///
+/// Les entrées sont passées de la prochaine évincée à la plus récente, et
+/// `load` doit les rendre dans le même ordre pour que l'ordre d'éviction
+/// soit reconstitué. Une implémentation qui dépend d'un service externe
+/// convertit ses erreurs en [`StorageError::Io`].
+pub trait StorageBackend {
+    /// Remplace le contenu sauvegardé par `entries`
+    ///
+    /// # Errors
+    ///
+    /// Retourne une erreur si les entrées ne peuvent pas être écrites ; le
+    /// contenu précédent doit alors rester lisible.
+    fn save(&mut self, capacity: usize, entries: &[(String, String)]) -> Result<(), StorageError>;
+
+    /// Relit la capacité et les entrées sauvegardées
+    ///
+    /// # Errors
+    ///
+    /// Retourne une erreur si le contenu ne peut pas être lu ou est
+    /// corrompu.
+    fn load(&mut self) -> Result<(usize, Vec<(String, String)>), StorageError>;
+}
+
+/// Sauvegarde dans un fichier, avec [`FileStorage`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileBackend {
+    path: PathBuf,
+    options: SaveOptions,
+}
+
+impl FileBackend {
+    /// Crée une destination qui écrit dans `path`
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        FileBackend::with_options(path, SaveOptions::default())
+    }
+
+    /// Crée une destination qui écrit dans `path` avec les options de
+    /// [`FileStorage::save_with`]
+    pub fn with_options(path: impl Into<PathBuf>, options: SaveOptions) -> Self {
+        FileBackend {
+            path: path.into(),
+            options,
+        }
+    }
+
+    /// Retourne le chemin du fichier
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+impl StorageBackend for FileBackend {
+    /// Voir [`FileStorage::save_with`] ; les entrées trop longues sont
+    /// ignorées.
+    fn save(&mut self, capacity: usize, entries: &[(String, String)]) -> Result<(), StorageError> {
+        FileStorage::save_with(&self.path, capacity, entries, self.options)?;
+        Ok(())
+    }
+
+    /// Voir [`FileStorage::load`].
+    fn load(&mut self) -> Result<(usize, Vec<(String, String)>), StorageError> {
+        FileStorage::load(&self.path)
+    }
+}
+
+/// Sauvegarde dans un tampon en mémoire, au format des fichiers de
+/// [`FileStorage`]
+///
+/// Utile pour les tests, et comme implémentation de référence : le tampon
+/// contient exactement les octets qu'écrirait [`FileStorage::save`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MemoryBackend {
+    buffer: Vec<u8>,
+}
+
+impl MemoryBackend {
+    /// Crée un tampon vide ; le charger échoue tant que rien n'y a été
+    /// sauvegardé
+    pub fn new() -> Self {
+        MemoryBackend::default()
+    }
+
+    /// Crée un tampon à partir d'un contenu déjà sauvegardé
+    pub fn from_bytes(buffer: Vec<u8>) -> Self {
+        MemoryBackend { buffer }
+    }
+
+    /// Retourne le contenu sauvegardé
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.buffer
+    }
+
+    /// Retourne le contenu sauvegardé en consommant le tampon
+    pub fn into_bytes(self) -> Vec<u8> {
+        self.buffer
+    }
+}
+
+impl StorageBackend for MemoryBackend {
+    fn save(&mut self, capacity: usize, entries: &[(String, String)]) -> Result<(), StorageError> {
+        let mut buffer = Vec::new();
+        FileStorage::save_to_writer(&mut buffer, capacity, entries)?;
+        self.buffer = buffer;
+        Ok(())
+    }
+
+    /// Comme [`FileStorage::load`], les entrées illisibles sont ignorées.
+    fn load(&mut self) -> Result<(usize, Vec<(String, String)>), StorageError> {
+        FileStorage::load_from_reader(self.buffer.as_slice())
+    }
+}
@@ -0,0 +1,331 @@
+//! Format de sauvegarde binaire
+//!
+//! Contrairement au format texte de [`FileStorage`](super::file::FileStorage),
+//! clés et valeurs sont écrites octet pour octet, préfixées par leur
+//! longueur : aucun échappement, aucune conversion en texte, et n'importe
+//! quel contenu (octets nuls, retours à la ligne, données non UTF-8) relit
+//! à l'identique.
+//!
+//! # Disposition
+//!
+//! Tous les entiers sont en petit-boutiste.
+//!
+//! | Champ          | Taille       | Contenu                              |
+//! |----------------|--------------|--------------------------------------|
+//! | magie          | 4 octets     | [`MAGIC`]                            |
+//! | version        | 4 octets     | [`BINARY_FORMAT_VERSION`]            |
+//! | capacité       | 8 octets     | capacité du cache                    |
+//! | nombre         | 8 octets     | nombre d'entrées qui suivent         |
+//!
+//! L'en-tête est suivi de chaque entrée :
+//!
+//! | Champ          | Taille       | Contenu                              |
+//! |----------------|--------------|--------------------------------------|
+//! | `key_len`      | 4 octets     | longueur de la clé                   |
+//! | `key_bytes`    | `key_len`    | clé encodée par [`ToBytes`]          |
+//! | `val_len`      | 4 octets     | longueur de la valeur                |
+//! | `val_bytes`    | `val_len`    | valeur encodée par [`ToBytes`]       |
+//!
+//! Les entrées sont écrites de la prochaine évincée à la plus récente. Le
+//! fichier se termine après la dernière entrée.
+//!
+//! # Examples
+//! ```
+//! use lru_cache::storage::binary::BinaryStorage;
+//!
+//! let data = vec![(String::from("a\0b"), vec![0u8, 255, 10])];
+//! BinaryStorage::save("binary_doc.bin", 4, &data).unwrap();
+//!
+//! let (capacity, loaded) = BinaryStorage::load::<String, Vec<u8>>("binary_doc.bin").unwrap();
+//! assert_eq!((capacity, loaded), (4, data));
+//! std::fs::remove_file("binary_doc.bin").unwrap();
+//! ```
+
+use super::atomic::write_atomic;
+use std::fmt;
+use std::fs::File;
+use std::io::{self, BufReader, Read, Write};
+use std::path::Path;
+
+/// Octets qui ouvrent tout fichier binaire
+pub const MAGIC: [u8; 4] = *b"LRUB";
+
+/// Version de la disposition ci-dessus
+pub const BINARY_FORMAT_VERSION: u32 = 1;
+
+/// Encodage binaire d'une clé ou d'une valeur
+pub trait ToBytes {
+    /// Ajoute la forme binaire de la valeur à la fin de `out`
+    fn to_bytes(&self, out: &mut Vec<u8>);
+}
+
+/// Décodage de la forme produite par [`ToBytes`]
+pub trait FromBytes: Sized {
+    /// Décode une valeur à partir de tous les octets de `bytes`, ou
+    /// retourne `None` s'ils ne forment pas une valeur valide
+    fn from_bytes(bytes: &[u8]) -> Option<Self>;
+}
+
+macro_rules! int_bytes {
+    ($($t:ty),*) => {$(
+        impl ToBytes for $t {
+            fn to_bytes(&self, out: &mut Vec<u8>) {
+                out.extend_from_slice(&self.to_le_bytes());
+            }
+        }
+
+        impl FromBytes for $t {
+            fn from_bytes(bytes: &[u8]) -> Option<Self> {
+                bytes.try_into().ok().map(<$t>::from_le_bytes)
+            }
+        }
+    )*};
+}
+
+int_bytes!(i8, i16, i32, i64, i128, u8, u16, u32, u64, u128);
+
+/// `usize` et `isize` sont écrits sur 8 octets, quelle que soit la
+/// plateforme
+macro_rules! size_bytes {
+    ($($t:ty => $wide:ty),*) => {$(
+        impl ToBytes for $t {
+            fn to_bytes(&self, out: &mut Vec<u8>) {
+                (*self as $wide).to_bytes(out);
+            }
+        }
+
+        impl FromBytes for $t {
+            fn from_bytes(bytes: &[u8]) -> Option<Self> {
+                <$wide>::from_bytes(bytes)?.try_into().ok()
+            }
+        }
+    )*};
+}
+
+size_bytes!(usize => u64, isize => i64);
+
+impl ToBytes for String {
+    fn to_bytes(&self, out: &mut Vec<u8>) {
+        out.extend_from_slice(self.as_bytes());
+    }
+}
+
+impl FromBytes for String {
+    fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        String::from_utf8(bytes.to_vec()).ok()
+    }
+}
+
+impl ToBytes for str {
+    fn to_bytes(&self, out: &mut Vec<u8>) {
+        out.extend_from_slice(self.as_bytes());
+    }
+}
+
+impl<T: ToBytes + ?Sized> ToBytes for &T {
+    fn to_bytes(&self, out: &mut Vec<u8>) {
+        (**self).to_bytes(out);
+    }
+}
+
+impl ToBytes for Vec<u8> {
+    fn to_bytes(&self, out: &mut Vec<u8>) {
+        out.extend_from_slice(self);
+    }
+}
+
+impl FromBytes for Vec<u8> {
+    fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        Some(bytes.to_vec())
+    }
+}
+
+/// Partie d'une entrée binaire
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EntryField {
+    /// La clé
+    Key,
+    /// La valeur
+    Value,
+}
+
+impl EntryField {
+    fn label(self) -> &'static str {
+        match self {
+            EntryField::Key => "clé",
+            EntryField::Value => "valeur",
+        }
+    }
+}
+
+/// Erreur de chargement d'un fichier binaire
+///
+/// Le chargement est tout ou rien : en cas d'erreur, aucune entrée n'est
+/// retournée.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum BinaryError {
+    /// Le fichier ne peut pas être ouvert ou lu
+    Io(io::Error),
+    /// Le fichier ne commence pas par [`MAGIC`]
+    BadMagic([u8; 4]),
+    /// La version du fichier n'est pas prise en charge
+    UnsupportedVersion(u32),
+    /// Le fichier s'arrête avant la fin de l'entrée `index` (comptée à partir
+    /// de 0), ou de l'en-tête si `index` vaut `None`
+    Truncated {
+        /// Entrée en cours de lecture
+        index: Option<usize>,
+    },
+    /// Les octets d'une entrée ne se décodent pas
+    InvalidEntry {
+        /// Position de l'entrée, à partir de 0
+        index: usize,
+        /// Partie illisible
+        field: EntryField,
+    },
+    /// Des octets suivent la dernière entrée annoncée par l'en-tête
+    TrailingData,
+}
+
+impl fmt::Display for BinaryError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BinaryError::Io(error) => write!(f, "lecture impossible : {}", error),
+            BinaryError::BadMagic(magic) => write!(f, "fichier binaire de cache invalide (début {:?})", magic),
+            BinaryError::UnsupportedVersion(version) => write!(f, "version du format binaire non prise en charge : {}", version),
+            BinaryError::Truncated { index: None } => write!(f, "fichier tronqué dans l'en-tête"),
+            BinaryError::Truncated { index: Some(index) } => write!(f, "fichier tronqué dans l'entrée {}", index),
+            BinaryError::InvalidEntry { index, field } => write!(f, "entrée {} : {} illisible", index, field.label()),
+            BinaryError::TrailingData => write!(f, "octets inattendus après la dernière entrée"),
+        }
+    }
+}
+
+impl std::error::Error for BinaryError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            BinaryError::Io(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for BinaryError {
+    fn from(error: io::Error) -> Self {
+        BinaryError::Io(error)
+    }
+}
+
+/// Gère la persistance des données du cache dans un fichier binaire
+pub struct BinaryStorage;
+
+impl BinaryStorage {
+    /// Sauvegarde les données dans un fichier binaire
+    ///
+    /// Comme [`FileStorage::save`](super::file::FileStorage::save),
+    /// l'écriture passe par un fichier temporaire renommé sur `path`.
+    ///
+    /// # Errors
+    ///
+    /// Retourne une erreur d'écriture, ou [`io::ErrorKind::InvalidInput`] si
+    /// une clé ou une valeur encodée dépasse `u32::MAX` octets.
+    pub fn save<K: ToBytes, V: ToBytes>(path: impl AsRef<Path>, capacity: usize, data: &[(K, V)]) -> io::Result<()> {
+        let path = path.as_ref();
+        write_atomic(path, |writer| {
+            writer.write_all(&MAGIC)?;
+            writer.write_all(&BINARY_FORMAT_VERSION.to_le_bytes())?;
+            writer.write_all(&(capacity as u64).to_le_bytes())?;
+            writer.write_all(&(data.len() as u64).to_le_bytes())?;
+
+            let mut buffer = Vec::new();
+            for (key, value) in data {
+                write_field(writer, &mut buffer, key)?;
+                write_field(writer, &mut buffer, value)?;
+            }
+            Ok(())
+        })?;
+        trace_event!(debug, path = %path.display(), capacity, entries = data.len(), "cache file saved");
+        Ok(())
+    }
+
+    /// Charge les données d'un fichier écrit par [`BinaryStorage::save`]
+    ///
+    /// # Errors
+    ///
+    /// Retourne [`BinaryError::BadMagic`] si le fichier n'est pas un fichier
+    /// binaire de cache, [`BinaryError::Truncated`] s'il s'arrête au milieu
+    /// d'une entrée et [`BinaryError::InvalidEntry`] si une clé ou une valeur
+    /// ne se décode pas.
+    pub fn load<K: FromBytes, V: FromBytes>(path: impl AsRef<Path>) -> Result<(usize, Vec<(K, V)>), BinaryError> {
+        let path = path.as_ref();
+        let mut reader = BufReader::new(File::open(path)?);
+        let mut magic = [0; 4];
+        read_exact(&mut reader, &mut magic, None)?;
+        if magic != MAGIC {
+            return Err(BinaryError::BadMagic(magic));
+        }
+        let version = u32::from_le_bytes(read_array(&mut reader, None)?);
+        if version != BINARY_FORMAT_VERSION {
+            return Err(BinaryError::UnsupportedVersion(version));
+        }
+        let capacity = usize::try_from(u64::from_le_bytes(read_array(&mut reader, None)?)).unwrap_or(usize::MAX);
+        let count = u64::from_le_bytes(read_array(&mut reader, None)?);
+
+        let mut data = Vec::new();
+        let mut buffer = Vec::new();
+        for index in 0..count as usize {
+            read_field(&mut reader, &mut buffer, index)?;
+            let key = K::from_bytes(&buffer).ok_or(BinaryError::InvalidEntry {
+                index,
+                field: EntryField::Key,
+            })?;
+            read_field(&mut reader, &mut buffer, index)?;
+            let value = V::from_bytes(&buffer).ok_or(BinaryError::InvalidEntry {
+                index,
+                field: EntryField::Value,
+            })?;
+            data.push((key, value));
+        }
+        if reader.read(&mut [0])? != 0 {
+            return Err(BinaryError::TrailingData);
+        }
+        trace_event!(debug, path = %path.display(), capacity, entries = data.len(), "cache file loaded");
+        Ok((capacity, data))
+    }
+}
+
+fn write_field<W: Write, T: ToBytes + ?Sized>(writer: &mut W, buffer: &mut Vec<u8>, field: &T) -> io::Result<()> {
+    buffer.clear();
+    field.to_bytes(buffer);
+    let len = u32::try_from(buffer.len())
+        .map_err(|_| io::Error::new(io::ErrorKind::InvalidInput, "entrée de plus de 4 Gio"))?;
+    writer.write_all(&len.to_le_bytes())?;
+    writer.write_all(buffer)
+}
+
+/// Lit un champ préfixé par sa longueur dans `buffer`
+///
+/// Le champ est lu par morceaux : une longueur corrompue ne provoque pas
+/// d'allocation démesurée avant que la fin du fichier soit constatée.
+fn read_field<R: Read>(reader: &mut R, buffer: &mut Vec<u8>, index: usize) -> Result<(), BinaryError> {
+    let len = u32::from_le_bytes(read_array(reader, Some(index))?) as u64;
+    buffer.clear();
+    if reader.take(len).read_to_end(buffer)? as u64 != len {
+        return Err(BinaryError::Truncated { index: Some(index) });
+    }
+    Ok(())
+}
+
+fn read_array<R: Read, const N: usize>(reader: &mut R, index: Option<usize>) -> Result<[u8; N], BinaryError> {
+    let mut bytes = [0; N];
+    read_exact(reader, &mut bytes, index)?;
+    Ok(bytes)
+}
+
+fn read_exact<R: Read>(reader: &mut R, bytes: &mut [u8], index: Option<usize>) -> Result<(), BinaryError> {
+    reader.read_exact(bytes).map_err(|error| match error.kind() {
+        io::ErrorKind::UnexpectedEof => BinaryError::Truncated { index },
+        _ => BinaryError::Io(error),
+    })
+}
@@ -0,0 +1,589 @@
+//! Sauvegarde binaire compacte pour tout type `serde` (feature `serde`)
+//!
+//! [`BinaryStorage`](super::binary::BinaryStorage) n'accepte que les types
+//! qui implémentent `ToBytes`/`FromBytes` et [`JsonStorage`](super::json::JsonStorage)
+//! passe par du texte. [`BincodeStorage`] écrit n'importe quelle clé et
+//! valeur sérialisable dans une disposition binaire à la manière de
+//! bincode : pas de noms de champs ni de séparateurs, seulement les données.
+//!
+//! # Disposition
+//!
+//! Tous les entiers sont en petit-boutiste.
+//!
+//! | Champ          | Taille       | Contenu                              |
+//! |----------------|--------------|--------------------------------------|
+//! | magie          | 4 octets     | [`MAGIC`]                            |
+//! | version        | 4 octets     | [`BINCODE_FORMAT_VERSION`]           |
+//! | capacité       | 8 octets     | capacité du cache                    |
+//! | nombre         | 8 octets     | nombre d'entrées qui suivent         |
+//!
+//! Chaque entrée est la clé puis la valeur, encodées ainsi :
+//! - entiers et flottants : leur taille en ligne (`usize`/`isize` sur 8
+//!   octets), `bool` sur un octet, `char` sur 4 octets ;
+//! - chaînes, octets, séquences et tables : le nombre d'éléments sur 8
+//!   octets, puis les éléments ;
+//! - `Option` : un octet 0 (`None`) ou 1 (`Some`) suivi de la valeur ;
+//! - structures et tuples : les champs dans l'ordre ;
+//! - variantes d'énumération : l'indice de la variante sur 4 octets, puis
+//!   ses champs.
+//!
+//! Le format n'est pas auto-descriptif : les types du chargement doivent
+//! être ceux de la sauvegarde.
+//!
+//! # Examples
+//! ```
+//! use lru_cache::storage::bincode::BincodeStorage;
+//!
+//! let data = vec![(String::from("a"), (vec![1u8, 2], Some(-3i64)))];
+//! BincodeStorage::save("bincode_doc.bin", 4, &data).unwrap();
+//!
+//! let (capacity, loaded) = BincodeStorage::load::<String, (Vec<u8>, Option<i64>)>("bincode_doc.bin").unwrap();
+//! assert_eq!((capacity, loaded), (4, data));
+//! std::fs::remove_file("bincode_doc.bin").unwrap();
+//! ```
+
+use super::atomic::write_atomic;
+use super::error::StorageError;
+use serde::de::{self, DeserializeOwned, DeserializeSeed, IntoDeserializer, Visitor};
+use serde::ser::{self, Serialize};
+use std::fmt::{self, Display};
+use std::fs;
+use std::io::{self, Write};
+use std::path::Path;
+
+/// Octets qui ouvrent tout fichier de [`BincodeStorage`]
+pub const MAGIC: [u8; 4] = *b"LRUS";
+
+/// Version de la disposition ci-dessus
+pub const BINCODE_FORMAT_VERSION: u32 = 1;
+
+/// Gère la persistance des données du cache dans le format binaire décrit
+/// par le [module](self)
+pub struct BincodeStorage;
+
+impl BincodeStorage {
+    /// Sauvegarde les données dans `path`
+    ///
+    /// Comme [`FileStorage::save`](super::file::FileStorage::save),
+    /// l'écriture passe par un fichier temporaire renommé sur `path`.
+    ///
+    /// # Errors
+    ///
+    /// Retourne une erreur d'écriture, ou [`io::ErrorKind::InvalidData`] si
+    /// une clé ou une valeur ne se sérialise pas (séquence de longueur
+    /// inconnue, par exemple).
+    pub fn save<K: Serialize, V: Serialize>(path: impl AsRef<Path>, capacity: usize, data: &[(K, V)]) -> io::Result<()> {
+        let path = path.as_ref();
+        let mut encoder = Encoder { out: Vec::new() };
+        encoder.out.extend_from_slice(&MAGIC);
+        encoder.out.extend_from_slice(&BINCODE_FORMAT_VERSION.to_le_bytes());
+        encoder.out.extend_from_slice(&(capacity as u64).to_le_bytes());
+        encoder.out.extend_from_slice(&(data.len() as u64).to_le_bytes());
+        for (key, value) in data {
+            key.serialize(&mut encoder).map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error))?;
+            value.serialize(&mut encoder).map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error))?;
+        }
+        write_atomic(path, |writer| writer.write_all(&encoder.out))?;
+        trace_event!(debug, path = %path.display(), capacity, entries = data.len(), "cache file saved");
+        Ok(())
+    }
+
+    /// Charge les données d'un fichier écrit par [`BincodeStorage::save`]
+    ///
+    /// # Errors
+    ///
+    /// - [`StorageError::Io`] si le fichier ne peut pas être lu ;
+    /// - [`StorageError::InvalidMagic`] s'il ne commence pas par [`MAGIC`],
+    ///   par exemple pour un fichier au format texte ;
+    /// - [`StorageError::UnsupportedVersion`] pour une autre version ;
+    /// - [`StorageError::Undecodable`] si le contenu ne correspond pas aux
+    ///   types `K` et `V`, est tronqué ou suivi d'octets en trop.
+    pub fn load<K: DeserializeOwned, V: DeserializeOwned>(path: impl AsRef<Path>) -> Result<(usize, Vec<(K, V)>), StorageError> {
+        let path = path.as_ref();
+        let bytes = fs::read(path)?;
+        let body = bytes.strip_prefix(&MAGIC).ok_or(StorageError::InvalidMagic)?;
+        let mut decoder = Decoder { input: body };
+        let version = decoder.read_u32().map_err(|_| StorageError::InvalidMagic)?;
+        if version != BINCODE_FORMAT_VERSION {
+            return Err(StorageError::UnsupportedVersion(version));
+        }
+        let (capacity, data) = decoder.read_body::<K, V>().map_err(|error| StorageError::Undecodable(error.0))?;
+        trace_event!(debug, path = %path.display(), capacity, entries = data.len(), "cache file loaded");
+        Ok((capacity, data))
+    }
+}
+
+/// Erreur de sérialisation ou de désérialisation
+#[derive(Debug)]
+struct Error(String);
+
+impl Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl ser::Error for Error {
+    fn custom<T: Display>(msg: T) -> Self {
+        Error(msg.to_string())
+    }
+}
+
+impl de::Error for Error {
+    fn custom<T: Display>(msg: T) -> Self {
+        Error(msg.to_string())
+    }
+}
+
+struct Encoder {
+    out: Vec<u8>,
+}
+
+impl Encoder {
+    fn write_len(&mut self, len: usize) {
+        self.out.extend_from_slice(&(len as u64).to_le_bytes());
+    }
+
+    fn write_variant(&mut self, index: u32) {
+        self.out.extend_from_slice(&index.to_le_bytes());
+    }
+}
+
+macro_rules! serialize_le {
+    ($($method:ident: $t:ty),*) => {$(
+        fn $method(self, value: $t) -> Result<(), Error> {
+            self.out.extend_from_slice(&value.to_le_bytes());
+            Ok(())
+        }
+    )*};
+}
+
+impl ser::Serializer for &mut Encoder {
+    type Ok = ();
+    type Error = Error;
+    type SerializeSeq = Self;
+    type SerializeTuple = Self;
+    type SerializeTupleStruct = Self;
+    type SerializeTupleVariant = Self;
+    type SerializeMap = Self;
+    type SerializeStruct = Self;
+    type SerializeStructVariant = Self;
+
+    serialize_le!(
+        serialize_i8: i8, serialize_i16: i16, serialize_i32: i32, serialize_i64: i64, serialize_i128: i128,
+        serialize_u8: u8, serialize_u16: u16, serialize_u32: u32, serialize_u64: u64, serialize_u128: u128,
+        serialize_f32: f32, serialize_f64: f64
+    );
+
+    fn serialize_bool(self, value: bool) -> Result<(), Error> {
+        self.out.push(u8::from(value));
+        Ok(())
+    }
+
+    fn serialize_char(self, value: char) -> Result<(), Error> {
+        self.serialize_u32(u32::from(value))
+    }
+
+    fn serialize_str(self, value: &str) -> Result<(), Error> {
+        self.serialize_bytes(value.as_bytes())
+    }
+
+    fn serialize_bytes(self, value: &[u8]) -> Result<(), Error> {
+        self.write_len(value.len());
+        self.out.extend_from_slice(value);
+        Ok(())
+    }
+
+    fn serialize_none(self) -> Result<(), Error> {
+        self.out.push(0);
+        Ok(())
+    }
+
+    fn serialize_some<T: Serialize + ?Sized>(self, value: &T) -> Result<(), Error> {
+        self.out.push(1);
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> Result<(), Error> {
+        Ok(())
+    }
+
+    fn serialize_unit_struct(self, _: &'static str) -> Result<(), Error> {
+        Ok(())
+    }
+
+    fn serialize_unit_variant(self, _: &'static str, index: u32, _: &'static str) -> Result<(), Error> {
+        self.write_variant(index);
+        Ok(())
+    }
+
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(self, _: &'static str, value: &T) -> Result<(), Error> {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(
+        self,
+        _: &'static str,
+        index: u32,
+        _: &'static str,
+        value: &T,
+    ) -> Result<(), Error> {
+        self.write_variant(index);
+        value.serialize(self)
+    }
+
+    fn serialize_seq(self, len: Option<usize>) -> Result<Self, Error> {
+        let len = len.ok_or_else(|| Error(String::from("séquence de longueur inconnue")))?;
+        self.write_len(len);
+        Ok(self)
+    }
+
+    fn serialize_tuple(self, _: usize) -> Result<Self, Error> {
+        Ok(self)
+    }
+
+    fn serialize_tuple_struct(self, _: &'static str, _: usize) -> Result<Self, Error> {
+        Ok(self)
+    }
+
+    fn serialize_tuple_variant(self, _: &'static str, index: u32, _: &'static str, _: usize) -> Result<Self, Error> {
+        self.write_variant(index);
+        Ok(self)
+    }
+
+    fn serialize_map(self, len: Option<usize>) -> Result<Self, Error> {
+        let len = len.ok_or_else(|| Error(String::from("table de longueur inconnue")))?;
+        self.write_len(len);
+        Ok(self)
+    }
+
+    fn serialize_struct(self, _: &'static str, _: usize) -> Result<Self, Error> {
+        Ok(self)
+    }
+
+    fn serialize_struct_variant(self, _: &'static str, index: u32, _: &'static str, _: usize) -> Result<Self, Error> {
+        self.write_variant(index);
+        Ok(self)
+    }
+
+    fn is_human_readable(&self) -> bool {
+        false
+    }
+}
+
+/// Les éléments composés s'écrivent les uns après les autres, sans
+/// séparateur ni fin
+macro_rules! serialize_compound {
+    ($($trait:ident :: $method:ident),*) => {$(
+        impl ser::$trait for &mut Encoder {
+            type Ok = ();
+            type Error = Error;
+
+            fn $method<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Error> {
+                value.serialize(&mut **self)
+            }
+
+            fn end(self) -> Result<(), Error> {
+                Ok(())
+            }
+        }
+    )*};
+}
+
+serialize_compound!(
+    SerializeSeq::serialize_element,
+    SerializeTuple::serialize_element,
+    SerializeTupleStruct::serialize_field,
+    SerializeTupleVariant::serialize_field
+);
+
+impl ser::SerializeMap for &mut Encoder {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_key<T: Serialize + ?Sized>(&mut self, key: &T) -> Result<(), Error> {
+        key.serialize(&mut **self)
+    }
+
+    fn serialize_value<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Error> {
+        value.serialize(&mut **self)
+    }
+
+    fn end(self) -> Result<(), Error> {
+        Ok(())
+    }
+}
+
+macro_rules! serialize_fields {
+    ($($trait:ident),*) => {$(
+        impl ser::$trait for &mut Encoder {
+            type Ok = ();
+            type Error = Error;
+
+            fn serialize_field<T: Serialize + ?Sized>(&mut self, _: &'static str, value: &T) -> Result<(), Error> {
+                value.serialize(&mut **self)
+            }
+
+            fn end(self) -> Result<(), Error> {
+                Ok(())
+            }
+        }
+    )*};
+}
+
+serialize_fields!(SerializeStruct, SerializeStructVariant);
+
+struct Decoder<'de> {
+    input: &'de [u8],
+}
+
+macro_rules! read_le {
+    ($($method:ident: $t:ty),*) => {$(
+        fn $method(&mut self) -> Result<$t, Error> {
+            let bytes = self.take(size_of::<$t>())?;
+            Ok(<$t>::from_le_bytes(bytes.try_into().expect("taille lue")))
+        }
+    )*};
+}
+
+impl<'de> Decoder<'de> {
+    fn take(&mut self, len: usize) -> Result<&'de [u8], Error> {
+        if self.input.len() < len {
+            return Err(Error(String::from("fichier tronqué")));
+        }
+        let (taken, rest) = self.input.split_at(len);
+        self.input = rest;
+        Ok(taken)
+    }
+
+    read_le!(
+        read_i8: i8, read_i16: i16, read_i32: i32, read_i64: i64, read_i128: i128,
+        read_u8: u8, read_u16: u16, read_u32: u32, read_u64: u64, read_u128: u128,
+        read_f32: f32, read_f64: f64
+    );
+
+    fn read_len(&mut self) -> Result<usize, Error> {
+        let len = self.read_u64()?;
+        usize::try_from(len).map_err(|_| Error(format!("longueur {} trop grande", len)))
+    }
+
+    fn read_bytes(&mut self) -> Result<&'de [u8], Error> {
+        let len = self.read_len()?;
+        self.take(len)
+    }
+
+    fn read_body<K: DeserializeOwned, V: DeserializeOwned>(&mut self) -> Result<(usize, Vec<(K, V)>), Error> {
+        let capacity = self.read_len()?;
+        let len = self.read_len()?;
+        // Chaque entrée occupe au moins un octet, sauf des types vides
+        let mut data = Vec::with_capacity(len.min(self.input.len()));
+        for _ in 0..len {
+            let key = K::deserialize(&mut *self)?;
+            let value = V::deserialize(&mut *self)?;
+            data.push((key, value));
+        }
+        if !self.input.is_empty() {
+            return Err(Error(format!("{} octets en trop après la dernière entrée", self.input.len())));
+        }
+        Ok((capacity, data))
+    }
+}
+
+macro_rules! deserialize_le {
+    ($($method:ident => $read:ident, $visit:ident);*) => {$(
+        fn $method<W: Visitor<'de>>(self, visitor: W) -> Result<W::Value, Error> {
+            visitor.$visit(self.$read()?)
+        }
+    )*};
+}
+
+impl<'de> de::Deserializer<'de> for &mut Decoder<'de> {
+    type Error = Error;
+
+    deserialize_le!(
+        deserialize_i8 => read_i8, visit_i8;
+        deserialize_i16 => read_i16, visit_i16;
+        deserialize_i32 => read_i32, visit_i32;
+        deserialize_i64 => read_i64, visit_i64;
+        deserialize_i128 => read_i128, visit_i128;
+        deserialize_u8 => read_u8, visit_u8;
+        deserialize_u16 => read_u16, visit_u16;
+        deserialize_u32 => read_u32, visit_u32;
+        deserialize_u64 => read_u64, visit_u64;
+        deserialize_u128 => read_u128, visit_u128;
+        deserialize_f32 => read_f32, visit_f32;
+        deserialize_f64 => read_f64, visit_f64;
+        deserialize_identifier => read_u32, visit_u32
+    );
+
+    fn deserialize_any<W: Visitor<'de>>(self, _: W) -> Result<W::Value, Error> {
+        Err(Error(String::from("le format ne décrit pas ses types")))
+    }
+
+    fn deserialize_ignored_any<W: Visitor<'de>>(self, visitor: W) -> Result<W::Value, Error> {
+        self.deserialize_any(visitor)
+    }
+
+    fn deserialize_bool<W: Visitor<'de>>(self, visitor: W) -> Result<W::Value, Error> {
+        match self.read_u8()? {
+            0 => visitor.visit_bool(false),
+            1 => visitor.visit_bool(true),
+            byte => Err(Error(format!("booléen invalide {}", byte))),
+        }
+    }
+
+    fn deserialize_char<W: Visitor<'de>>(self, visitor: W) -> Result<W::Value, Error> {
+        let code = self.read_u32()?;
+        visitor.visit_char(char::from_u32(code).ok_or_else(|| Error(format!("caractère invalide {:#x}", code)))?)
+    }
+
+    fn deserialize_str<W: Visitor<'de>>(self, visitor: W) -> Result<W::Value, Error> {
+        let bytes = self.read_bytes()?;
+        let text = std::str::from_utf8(bytes).map_err(|error| Error(error.to_string()))?;
+        visitor.visit_borrowed_str(text)
+    }
+
+    fn deserialize_string<W: Visitor<'de>>(self, visitor: W) -> Result<W::Value, Error> {
+        self.deserialize_str(visitor)
+    }
+
+    fn deserialize_bytes<W: Visitor<'de>>(self, visitor: W) -> Result<W::Value, Error> {
+        visitor.visit_borrowed_bytes(self.read_bytes()?)
+    }
+
+    fn deserialize_byte_buf<W: Visitor<'de>>(self, visitor: W) -> Result<W::Value, Error> {
+        self.deserialize_bytes(visitor)
+    }
+
+    fn deserialize_option<W: Visitor<'de>>(self, visitor: W) -> Result<W::Value, Error> {
+        match self.read_u8()? {
+            0 => visitor.visit_none(),
+            1 => visitor.visit_some(self),
+            tag => Err(Error(format!("option invalide {}", tag))),
+        }
+    }
+
+    fn deserialize_unit<W: Visitor<'de>>(self, visitor: W) -> Result<W::Value, Error> {
+        visitor.visit_unit()
+    }
+
+    fn deserialize_unit_struct<W: Visitor<'de>>(self, _: &'static str, visitor: W) -> Result<W::Value, Error> {
+        visitor.visit_unit()
+    }
+
+    fn deserialize_newtype_struct<W: Visitor<'de>>(self, _: &'static str, visitor: W) -> Result<W::Value, Error> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_seq<W: Visitor<'de>>(self, visitor: W) -> Result<W::Value, Error> {
+        let remaining = self.read_len()?;
+        visitor.visit_seq(Counted { decoder: self, remaining })
+    }
+
+    fn deserialize_tuple<W: Visitor<'de>>(self, len: usize, visitor: W) -> Result<W::Value, Error> {
+        visitor.visit_seq(Counted { decoder: self, remaining: len })
+    }
+
+    fn deserialize_tuple_struct<W: Visitor<'de>>(self, _: &'static str, len: usize, visitor: W) -> Result<W::Value, Error> {
+        self.deserialize_tuple(len, visitor)
+    }
+
+    fn deserialize_map<W: Visitor<'de>>(self, visitor: W) -> Result<W::Value, Error> {
+        let remaining = self.read_len()?;
+        visitor.visit_map(Counted { decoder: self, remaining })
+    }
+
+    fn deserialize_struct<W: Visitor<'de>>(
+        self,
+        _: &'static str,
+        fields: &'static [&'static str],
+        visitor: W,
+    ) -> Result<W::Value, Error> {
+        self.deserialize_tuple(fields.len(), visitor)
+    }
+
+    fn deserialize_enum<W: Visitor<'de>>(
+        self,
+        _: &'static str,
+        _: &'static [&'static str],
+        visitor: W,
+    ) -> Result<W::Value, Error> {
+        visitor.visit_enum(self)
+    }
+
+    fn is_human_readable(&self) -> bool {
+        false
+    }
+}
+
+/// Éléments d'une séquence, d'un tuple ou d'une table dont le nombre est
+/// connu
+struct Counted<'a, 'de> {
+    decoder: &'a mut Decoder<'de>,
+    remaining: usize,
+}
+
+impl<'de> de::SeqAccess<'de> for Counted<'_, 'de> {
+    type Error = Error;
+
+    fn next_element_seed<T: DeserializeSeed<'de>>(&mut self, seed: T) -> Result<Option<T::Value>, Error> {
+        if self.remaining == 0 {
+            return Ok(None);
+        }
+        self.remaining -= 1;
+        seed.deserialize(&mut *self.decoder).map(Some)
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.remaining)
+    }
+}
+
+impl<'de> de::MapAccess<'de> for Counted<'_, 'de> {
+    type Error = Error;
+
+    fn next_key_seed<T: DeserializeSeed<'de>>(&mut self, seed: T) -> Result<Option<T::Value>, Error> {
+        de::SeqAccess::next_element_seed(self, seed)
+    }
+
+    fn next_value_seed<T: DeserializeSeed<'de>>(&mut self, seed: T) -> Result<T::Value, Error> {
+        seed.deserialize(&mut *self.decoder)
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.remaining)
+    }
+}
+
+impl<'de> de::EnumAccess<'de> for &mut Decoder<'de> {
+    type Error = Error;
+    type Variant = Self;
+
+    fn variant_seed<T: DeserializeSeed<'de>>(self, seed: T) -> Result<(T::Value, Self), Error> {
+        let index = self.read_u32()?;
+        let variant = seed.deserialize(index.into_deserializer())?;
+        Ok((variant, self))
+    }
+}
+
+impl<'de> de::VariantAccess<'de> for &mut Decoder<'de> {
+    type Error = Error;
+
+    fn unit_variant(self) -> Result<(), Error> {
+        Ok(())
+    }
+
+    fn newtype_variant_seed<T: DeserializeSeed<'de>>(self, seed: T) -> Result<T::Value, Error> {
+        seed.deserialize(self)
+    }
+
+    fn tuple_variant<W: Visitor<'de>>(self, len: usize, visitor: W) -> Result<W::Value, Error> {
+        de::Deserializer::deserialize_tuple(self, len, visitor)
+    }
+
+    fn struct_variant<W: Visitor<'de>>(self, fields: &'static [&'static str], visitor: W) -> Result<W::Value, Error> {
+        de::Deserializer::deserialize_tuple(self, fields.len(), visitor)
+    }
+}
@@ -0,0 +1,268 @@
+//! Encodage texte canonique des clés et valeurs
+//!
+//! `FileStorage::save`/`load` s'appuient sur `Display`/`FromStr`, ce qui ne
+//! garantit pas l'aller-retour : un flottant peut perdre des chiffres, un `;`
+//! dans une clé coupe la ligne au mauvais endroit, et les entrées illisibles
+//! sont ignorées sans bruit. [`TextCodec`] définit un encodage canonique,
+//! indépendant de l'environnement, pour les scalaires de la bibliothèque
+//! standard ; `FileStorage::save_encoded`/`load_encoded` l'utilisent.
+//!
+//! Encodages :
+//! - entiers et `bool` : leur forme `Display` ;
+//! - flottants : la plus courte écriture décimale qui relit la même valeur
+//!   (`{:?}`), avec `-0.0`, `NaN`, `inf` et `-inf` ; au chargement, `nan`,
+//!   `infinity` et `+inf` sont aussi acceptés, sans tenir compte de la casse ;
+//! - `char` et `String` : le texte avec `\`, `;`, retour à la ligne et
+//!   retour chariot échappés (`\\`, `\;`, `\n`, `\r`) ;
+//! - `()` : la chaîne vide.
+//!
+//! [`ValueCodec`] est la variante à instance de cet encodage, choisie au
+//! moment de la sauvegarde plutôt que par le type : `Cache::save_to_file_with`
+//! et `Cache::load_from_file_with` acceptent un codec pour les clés et un
+//! pour les valeurs. [`DisplayCodec`] reprend `Display`/`FromStr`, et
+//! [`Base64Codec`] écrit des octets quelconques en base64.
+//!
+//! # Examples
+//! ```
+//! use lru_cache::storage::codec::{verify_roundtrip, TextCodec};
+//!
+//! assert_eq!(f64::NEG_INFINITY.encode(), "-inf");
+//! assert_eq!(';'.encode(), "\\;");
+//! assert_eq!(f64::decode("NaN").map(f64::is_nan), Ok(true));
+//! verify_roundtrip(&1e-300_f64).unwrap();
+//! ```
+
+use super::format::{escape, unescape};
+use std::any::type_name;
+use std::fmt::{self, Display};
+use std::str::FromStr;
+
+/// Encodage texte sans perte d'un type
+///
+/// Pour un nouveau type (par exemple `struct UserId(u64)`), il suffit en
+/// général de déléguer au type contenu.
+pub trait TextCodec: Sized {
+    /// Encode la valeur ; le résultat ne contient ni `;` non échappé ni
+    /// retour à la ligne
+    fn encode(&self) -> String;
+    /// Décode une valeur produite par `encode`
+    fn decode(s: &str) -> Result<Self, CodecError>;
+}
+
+/// Erreur de décodage ou d'aller-retour
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CodecError {
+    /// Type attendu
+    pub type_name: &'static str,
+    /// Texte qui n'a pas pu être décodé
+    pub input: String,
+}
+
+impl CodecError {
+    /// Crée une erreur pour le type `T`
+    pub fn new<T>(input: &str) -> Self {
+        CodecError {
+            type_name: type_name::<T>(),
+            input: input.to_string(),
+        }
+    }
+}
+
+impl fmt::Display for CodecError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "impossible de décoder {:?} en {}", self.input, self.type_name)
+    }
+}
+
+impl std::error::Error for CodecError {}
+
+/// Vérifie que `value` survit à un aller-retour encodage/décodage
+///
+/// La comparaison porte sur les encodages, ce qui permet de traiter `NaN`
+/// (différent de lui-même) et de distinguer `-0.0` de `0.0`.
+pub fn verify_roundtrip<T: TextCodec>(value: &T) -> Result<(), CodecError> {
+    let encoded = value.encode();
+    let decoded = T::decode(&encoded)?;
+    if decoded.encode() == encoded {
+        Ok(())
+    } else {
+        Err(CodecError::new::<T>(&encoded))
+    }
+}
+
+macro_rules! display_codec {
+    ($($t:ty),*) => {$(
+        impl TextCodec for $t {
+            fn encode(&self) -> String {
+                self.to_string()
+            }
+
+            fn decode(s: &str) -> Result<Self, CodecError> {
+                s.parse().map_err(|_| CodecError::new::<$t>(s))
+            }
+        }
+    )*};
+}
+
+display_codec!(bool, i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
+
+macro_rules! float_codec {
+    ($($t:ty),*) => {$(
+        impl TextCodec for $t {
+            fn encode(&self) -> String {
+                format!("{:?}", self)
+            }
+
+            fn decode(s: &str) -> Result<Self, CodecError> {
+                s.parse().map_err(|_| CodecError::new::<$t>(s))
+            }
+        }
+    )*};
+}
+
+float_codec!(f32, f64);
+
+impl TextCodec for char {
+    fn encode(&self) -> String {
+        escape(self.encode_utf8(&mut [0; 4]))
+    }
+
+    fn decode(s: &str) -> Result<Self, CodecError> {
+        let text = unescape(s).ok_or_else(|| CodecError::new::<char>(s))?;
+        let mut chars = text.chars();
+        match (chars.next(), chars.next()) {
+            (Some(c), None) => Ok(c),
+            _ => Err(CodecError::new::<char>(s)),
+        }
+    }
+}
+
+impl TextCodec for String {
+    fn encode(&self) -> String {
+        escape(self)
+    }
+
+    fn decode(s: &str) -> Result<Self, CodecError> {
+        unescape(s).ok_or_else(|| CodecError::new::<String>(s))
+    }
+}
+
+impl TextCodec for () {
+    fn encode(&self) -> String {
+        String::new()
+    }
+
+    fn decode(s: &str) -> Result<Self, CodecError> {
+        if s.is_empty() {
+            Ok(())
+        } else {
+            Err(CodecError::new::<()>(s))
+        }
+    }
+}
+
+/// Encodage texte d'un type `T` porté par une valeur, pour les types qui
+/// n'implémentent pas `Display`/`FromStr` ou qui en ont une forme texte
+/// différente
+///
+/// # Examples
+/// ```
+/// use lru_cache::storage::codec::{CodecError, ValueCodec};
+///
+/// struct Pair;
+///
+/// impl ValueCodec<(u8, u8)> for Pair {
+///     fn encode(&self, value: &(u8, u8)) -> String {
+///         format!("{}-{}", value.0, value.1)
+///     }
+///
+///     fn decode(&self, s: &str) -> Result<(u8, u8), CodecError> {
+///         let parse = |part: &str| part.parse().map_err(|_| CodecError::new::<(u8, u8)>(s));
+///         let (a, b) = s.split_once('-').ok_or_else(|| CodecError::new::<(u8, u8)>(s))?;
+///         Ok((parse(a)?, parse(b)?))
+///     }
+/// }
+///
+/// assert_eq!(Pair.decode(&Pair.encode(&(1, 2))), Ok((1, 2)));
+/// ```
+pub trait ValueCodec<T> {
+    /// Encode la valeur ; le fichier échappe ensuite `;` et les retours à
+    /// la ligne
+    fn encode(&self, value: &T) -> String;
+    /// Décode une valeur produite par `encode`
+    fn decode(&self, s: &str) -> Result<T, CodecError>;
+}
+
+/// Codec par `Display`/`FromStr`, qui produit le même fichier que
+/// `PersistentStorage::save_to_file`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct DisplayCodec;
+
+impl<T: Display + FromStr> ValueCodec<T> for DisplayCodec {
+    fn encode(&self, value: &T) -> String {
+        value.to_string()
+    }
+
+    fn decode(&self, s: &str) -> Result<T, CodecError> {
+        s.parse().map_err(|_| CodecError::new::<T>(s))
+    }
+}
+
+/// Codec base64 (alphabet standard, avec remplissage `=`) pour des octets
+/// quelconques
+///
+/// L'encodage ne contient que des lettres, des chiffres, `+`, `/` et `=` :
+/// aucun caractère n'a besoin d'être échappé dans le fichier.
+///
+/// # Examples
+/// ```
+/// use lru_cache::storage::codec::{Base64Codec, ValueCodec};
+///
+/// assert_eq!(Base64Codec.encode(&b"lru;\n".to_vec()), "bHJ1Owo=");
+/// assert_eq!(Base64Codec.decode("AP8="), Ok(vec![0, 255]));
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Base64Codec;
+
+const BASE64_ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+impl ValueCodec<Vec<u8>> for Base64Codec {
+    fn encode(&self, value: &Vec<u8>) -> String {
+        let mut encoded = String::with_capacity(value.len().div_ceil(3) * 4);
+        for chunk in value.chunks(3) {
+            let bits = chunk.iter().fold(0u32, |bits, &byte| bits << 8 | u32::from(byte)) << (8 * (3 - chunk.len()));
+            for position in 0..4 {
+                if position <= chunk.len() {
+                    encoded.push(char::from(BASE64_ALPHABET[(bits >> (18 - 6 * position) & 0x3f) as usize]));
+                } else {
+                    encoded.push('=');
+                }
+            }
+        }
+        encoded
+    }
+
+    fn decode(&self, s: &str) -> Result<Vec<u8>, CodecError> {
+        let error = || CodecError::new::<Vec<u8>>(s);
+        if !s.len().is_multiple_of(4) {
+            return Err(error());
+        }
+        let mut decoded = Vec::with_capacity(s.len() / 4 * 3);
+        let chunks = s.as_bytes().chunks(4);
+        let last = chunks.len().saturating_sub(1);
+        for (index, chunk) in chunks.enumerate() {
+            let padding = chunk.iter().rev().take_while(|&&byte| byte == b'=').count();
+            if padding > 2 || (padding > 0 && index != last) {
+                return Err(error());
+            }
+            let mut bits = 0u32;
+            for &byte in &chunk[..4 - padding] {
+                let sextet = BASE64_ALPHABET.iter().position(|&symbol| symbol == byte).ok_or_else(error)?;
+                bits = bits << 6 | sextet as u32;
+            }
+            bits <<= 6 * padding;
+            decoded.extend_from_slice(&bits.to_be_bytes()[1..4 - padding]);
+        }
+        Ok(decoded)
+    }
+}
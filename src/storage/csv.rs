@@ -0,0 +1,111 @@
+//! Lecture et écriture de CSV selon la RFC 4180, pour
+//! `Cache::export_csv`/`Cache::import_csv`
+//!
+//! Un champ qui contient une virgule, un guillemet ou un retour à la ligne
+//! est entouré de guillemets, et ses guillemets sont doublés. Les lignes se
+//! terminent par `\r\n` ; à la lecture, `\n` seul est aussi accepté et les
+//! lignes vides sont ignorées.
+
+use super::error::StorageError;
+use std::io::{self, Write};
+
+/// Ligne d'un fichier CSV découpée en champs
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct CsvRecord {
+    /// Numéro de la ligne où l'enregistrement commence, à partir de 1
+    pub(crate) line: usize,
+    pub(crate) fields: Vec<String>,
+    /// Texte de l'enregistrement, pour les messages d'erreur
+    pub(crate) raw: String,
+}
+
+/// Écrit un enregistrement, en protégeant les champs qui le demandent
+pub(crate) fn write_record<W: Write + ?Sized, S: AsRef<str>>(
+    writer: &mut W,
+    fields: impl IntoIterator<Item = S>,
+) -> io::Result<()> {
+    for (index, field) in fields.into_iter().enumerate() {
+        if index > 0 {
+            writer.write_all(b",")?;
+        }
+        let field = field.as_ref();
+        if field.contains([',', '"', '\r', '\n']) {
+            write!(writer, "\"{}\"", field.replace('"', "\"\""))?;
+        } else {
+            writer.write_all(field.as_bytes())?;
+        }
+    }
+    writer.write_all(b"\r\n")
+}
+
+/// Découpe `text` en enregistrements
+///
+/// # Errors
+///
+/// [`StorageError::InvalidEntry`] pour un guillemet non fermé ou un texte
+/// collé après un guillemet fermant.
+pub(crate) fn parse(text: &str) -> Result<Vec<CsvRecord>, StorageError> {
+    let mut records = Vec::new();
+    let mut chars = text.char_indices().peekable();
+    let mut line = 1;
+    while chars.peek().is_some() {
+        let start_line = line;
+        let start = chars.peek().map_or(text.len(), |&(index, _)| index);
+        let mut fields = Vec::new();
+        let mut field = String::new();
+        let mut quoted = false;
+        let mut end = text.len();
+        let invalid = |end: usize| StorageError::InvalidEntry {
+            line: start_line,
+            content: text[start..end].to_string(),
+        };
+        while let Some((index, c)) = chars.next() {
+            if quoted {
+                match c {
+                    '"' if chars.peek().is_some_and(|&(_, next)| next == '"') => {
+                        chars.next();
+                        field.push('"');
+                    }
+                    '"' => {
+                        quoted = false;
+                        match chars.peek() {
+                            None | Some((_, ',' | '\r' | '\n')) => {}
+                            Some(&(after, _)) => return Err(invalid(after + 1)),
+                        }
+                    }
+                    '\n' => {
+                        line += 1;
+                        field.push(c);
+                    }
+                    _ => field.push(c),
+                }
+                continue;
+            }
+            match c {
+                '"' if field.is_empty() => quoted = true,
+                ',' => fields.push(std::mem::take(&mut field)),
+                '\r' if chars.peek().is_some_and(|&(_, next)| next == '\n') => {}
+                '\n' => {
+                    line += 1;
+                    end = index;
+                    break;
+                }
+                _ => field.push(c),
+            }
+        }
+        if quoted {
+            return Err(invalid(text.len()));
+        }
+        fields.push(field);
+        let raw = text[start..end].trim_end_matches('\r');
+        if raw.is_empty() {
+            continue;
+        }
+        records.push(CsvRecord {
+            line: start_line,
+            fields,
+            raw: raw.to_string(),
+        });
+    }
+    Ok(records)
+}
@@ -0,0 +1,113 @@
+//! Erreurs de chargement des fichiers de sauvegarde
+
+use super::codec::CodecError;
+use super::file::OversizedEntry;
+use std::fmt;
+use std::io;
+use std::time::Duration;
+
+/// Erreur de chargement d'un fichier de sauvegarde texte
+///
+/// Distingue un fichier absent ou illisible ([`StorageError::Io`]) d'un
+/// fichier corrompu. Une `io::Error` se convertit en `StorageError` avec
+/// `?`, et inversement pour les API qui retournent `io::Result` : l'erreur
+/// devient alors [`io::ErrorKind::InvalidData`] ([`io::ErrorKind::TimedOut`]
+/// pour [`StorageError::LockTimeout`]) et reste accessible par
+/// [`io::Error::get_ref`].
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum StorageError {
+    /// Le fichier ne peut pas être ouvert ou lu
+    Io(io::Error),
+    /// Le fichier est vide ou sa première ligne est vide
+    MissingCapacity,
+    /// La première ligne n'est pas un en-tête valide ; contient la ligne lue
+    InvalidCapacity(String),
+    /// La ligne de version indique une version du format que cette version
+    /// de la bibliothèque ne sait pas lire
+    UnsupportedVersion(u32),
+    /// Une ligne n'est pas une entrée lisible (pas de séparateur, ou clé ou
+    /// valeur refusée par `FromStr`) lors d'un chargement strict
+    InvalidEntry {
+        /// Numéro de la ligne, à partir de 1 en comptant l'en-tête
+        line: usize,
+        /// Contenu de la ligne
+        content: String,
+    },
+    /// Une entrée dépasse les limites d'un chargement strict
+    Oversized(OversizedEntry),
+    /// La somme de contrôle du fichier ne correspond pas à son contenu
+    ChecksumMismatch {
+        /// Somme écrite dans le fichier
+        expected: u32,
+        /// Somme calculée sur le contenu lu
+        found: u32,
+    },
+    /// Un fichier qui doit se terminer par une somme de contrôle n'en a
+    /// pas : il a été tronqué
+    MissingChecksum,
+    /// L'en-tête n'indique pas l'heure de la sauvegarde alors que
+    /// l'ancienneté du fichier est vérifiée
+    MissingTimestamp,
+    /// Le verrou du fichier n'a pas été obtenu dans le délai indiqué ; voir
+    /// [`FileLock`](super::lock::FileLock)
+    LockTimeout(Duration),
+    /// Le fichier ne commence pas par la signature du format binaire
+    /// attendu, par exemple un fichier texte donné à
+    /// `BincodeStorage::load` (fonctionnalité `serde`)
+    InvalidMagic,
+    /// Le contenu d'un fichier binaire ne se décode pas dans les types
+    /// attendus ; contient la raison
+    Undecodable(String),
+    /// Une clé ou une valeur a été refusée par le codec de
+    /// `Cache::load_from_file_with`
+    Codec(CodecError),
+}
+
+impl fmt::Display for StorageError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            StorageError::Io(error) => write!(f, "lecture impossible : {}", error),
+            StorageError::MissingCapacity => write!(f, "capacité absente de l'en-tête"),
+            StorageError::InvalidCapacity(line) => write!(f, "en-tête de sauvegarde invalide : {:?}", line),
+            StorageError::UnsupportedVersion(version) => write!(f, "version {} du format non prise en charge", version),
+            StorageError::InvalidEntry { line, content } => write!(f, "ligne {} : entrée illisible {:?}", line, content),
+            StorageError::Oversized(entry) => write!(f, "ligne {} : {} trop longue", entry.position, entry.field.label()),
+            StorageError::ChecksumMismatch { expected, found } => {
+                write!(f, "somme de contrôle invalide : {:08x} attendue, {:08x} calculée", expected, found)
+            }
+            StorageError::MissingChecksum => write!(f, "somme de contrôle absente : fichier tronqué"),
+            StorageError::MissingTimestamp => write!(f, "heure de sauvegarde absente de l'en-tête"),
+            StorageError::LockTimeout(timeout) => write!(f, "verrou du fichier non obtenu en {:?}", timeout),
+            StorageError::InvalidMagic => write!(f, "signature de fichier inconnue"),
+            StorageError::Undecodable(reason) => write!(f, "contenu binaire illisible : {}", reason),
+            StorageError::Codec(error) => write!(f, "entrée refusée par le codec : {}", error),
+        }
+    }
+}
+
+impl std::error::Error for StorageError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            StorageError::Io(error) => Some(error),
+            StorageError::Codec(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for StorageError {
+    fn from(error: io::Error) -> Self {
+        StorageError::Io(error)
+    }
+}
+
+impl From<StorageError> for io::Error {
+    fn from(error: StorageError) -> Self {
+        match error {
+            StorageError::Io(error) => error,
+            error @ StorageError::LockTimeout(_) => io::Error::new(io::ErrorKind::TimedOut, error),
+            error => io::Error::new(io::ErrorKind::InvalidData, error),
+        }
+    }
+}
@@ -1,7 +1,245 @@
+use super::atomic::write_atomic;
+use super::codec::TextCodec;
+use super::error::StorageError;
+use super::format::{
+    crc32, parse_checksum_line, parse_entry, parse_full_header, parse_header, parse_removal, parse_version_line,
+    split_escaped_entry, write_entry, write_header, write_removal, ChecksumWriter, Escaped, CHECKSUM_PREFIX,
+    CHECKSUM_VERSION, FILE_FORMAT_VERSION, INCREMENTAL_VERSION, LINE_END, SEPARATOR,
+};
+#[cfg(feature = "rayon")]
+use super::format::crc32_combine;
+use super::lock::FileLock;
+#[cfg(feature = "compression")]
+use flate2::{read::MultiGzDecoder, write::GzEncoder, Compression};
+use std::borrow::Cow;
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{HashMap, HashSet};
 use std::fs::{File, OpenOptions};
-use std::io::{self, BufReader, BufWriter, Read, Write};
-use std::fmt::Display;
+use std::hash::{Hash, Hasher};
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::fmt::{self, Display, Write as _};
+use std::path::Path;
 use std::str::FromStr;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// Options de [`FileStorage::verify`]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct VerifyOptions {
+    /// Détecte les clés en double en conservant les clés elles-mêmes plutôt
+    /// que leur hash 64 bits : exact, mais la mémoire utilisée croît avec la
+    /// taille des clés
+    pub exact_duplicates: bool,
+}
+
+/// Rapport produit par [`FileStorage::verify`]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct VerifyReport {
+    /// Capacité lue sur la première ligne, si elle est valide
+    pub capacity: Option<usize>,
+    /// Nombre d'entrées valides
+    pub entries: usize,
+    /// Lignes sans séparateur `;`
+    pub malformed_lines: usize,
+    /// Clés qui ne peuvent pas être parsées
+    pub invalid_keys: usize,
+    /// Valeurs qui ne peuvent pas être parsées
+    pub invalid_values: usize,
+    /// Entrées dont la clé apparaît déjà plus haut dans le fichier
+    pub duplicate_keys: usize,
+    /// Résultat de la vérification de la somme de contrôle : `None` pour un
+    /// fichier qui n'en a pas (versions 1 et 2), `Some(false)` si elle est
+    /// fausse ou absente d'un fichier qui doit en avoir une
+    pub checksum_ok: Option<bool>,
+}
+
+impl VerifyReport {
+    /// Indique si le nombre d'entrées valides dépasse la capacité
+    pub fn over_capacity(&self) -> bool {
+        self.capacity.is_some_and(|capacity| self.entries > capacity)
+    }
+
+    /// Indique si le fichier est entièrement valide
+    pub fn is_ok(&self) -> bool {
+        self.capacity.is_some()
+            && self.malformed_lines == 0
+            && self.invalid_keys == 0
+            && self.invalid_values == 0
+            && self.duplicate_keys == 0
+            && self.checksum_ok != Some(false)
+            && !self.over_capacity()
+    }
+}
+
+/// Rapport de [`FileStorage::load_lossy`] et [`FileStorage::repair`]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SalvageReport {
+    /// Capacité lue sur la première ligne, si elle est complète et valide
+    pub capacity: Option<usize>,
+    /// Nombre d'entrées récupérées
+    pub entries: usize,
+    /// Lignes complètes ignorées car illisibles
+    pub skipped_lines: usize,
+    /// Numéros des lignes ignorées, à partir de 1 en comptant l'en-tête
+    pub skipped_line_numbers: Vec<usize>,
+    /// Indique si la dernière ligne n'est pas terminée par un retour à la
+    /// ligne (fichier coupé en cours d'écriture)
+    pub truncated: bool,
+    /// Nombre d'octets de lignes complètes au début du fichier : les données
+    /// au-delà de cette position sont incomplètes
+    pub clean_len: u64,
+}
+
+/// Entrées récupérées par [`FileStorage::load_lossy`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LossyLoad<K, V> {
+    /// Entrées lisibles, dans l'ordre du fichier
+    pub data: Vec<(K, V)>,
+    /// Rapport de récupération
+    pub report: SalvageReport,
+}
+
+/// Longueur maximale par défaut d'une clé écrite ou lue, en octets (64 Kio)
+pub const DEFAULT_MAX_KEY_LEN: usize = 64 * 1024;
+
+/// Longueur maximale par défaut d'une valeur écrite ou lue, en octets
+/// (16 Mio)
+pub const DEFAULT_MAX_VALUE_LEN: usize = 16 * 1024 * 1024;
+
+/// Options de [`FileStorage::save_with`]
+///
+/// Les longueurs sont mesurées en octets sur la forme écrite de la clé et
+/// de la valeur, c'est-à-dire leur `Display` échappé : elles correspondent
+/// à celles que [`LoadOptions`] mesure à la relecture.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SaveOptions {
+    /// Longueur maximale d'une clé
+    pub max_key_len: usize,
+    /// Longueur maximale d'une valeur
+    pub max_value_len: usize,
+    /// Heure de la sauvegarde à inscrire dans l'en-tête, à la seconde près
+    /// (ignorée si elle précède 1970)
+    pub saved_at: Option<SystemTime>,
+}
+
+impl Default for SaveOptions {
+    fn default() -> Self {
+        SaveOptions {
+            max_key_len: DEFAULT_MAX_KEY_LEN,
+            max_value_len: DEFAULT_MAX_VALUE_LEN,
+            saved_at: None,
+        }
+    }
+}
+
+/// En-tête d'un fichier de sauvegarde, lu par [`FileStorage::metadata`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SnapshotMetadata {
+    /// Capacité du cache sauvegardé
+    pub capacity: usize,
+    /// Heure de la sauvegarde, si l'en-tête l'indique
+    pub saved_at: Option<SystemTime>,
+}
+
+/// Limites de [`FileStorage::load_with`]
+///
+/// Les longueurs sont mesurées en octets sur le texte lu dans le fichier.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LoadOptions {
+    /// Longueur maximale d'une clé
+    pub max_key_len: usize,
+    /// Longueur maximale d'une valeur
+    pub max_value_len: usize,
+    /// Fait échouer le chargement à la première entrée trop longue au lieu
+    /// de l'ignorer
+    pub strict: bool,
+}
+
+impl Default for LoadOptions {
+    fn default() -> Self {
+        LoadOptions {
+            max_key_len: DEFAULT_MAX_KEY_LEN,
+            max_value_len: DEFAULT_MAX_VALUE_LEN,
+            strict: false,
+        }
+    }
+}
+
+/// Partie d'une entrée qui dépasse sa limite de longueur
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OversizedField {
+    /// La clé est trop longue
+    Key,
+    /// La valeur est trop longue
+    Value,
+}
+
+impl OversizedField {
+    pub(crate) fn label(self) -> &'static str {
+        match self {
+            OversizedField::Key => "clé",
+            OversizedField::Value => "valeur",
+        }
+    }
+}
+
+/// Avertissement émis pour une entrée écartée car trop longue
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OversizedEntry {
+    /// Indice de l'entrée dans les données sauvegardées, ou numéro de la
+    /// ligne lue (à partir de 1, en-tête compris) au chargement
+    pub position: usize,
+    /// Partie trop longue (la clé si les deux le sont)
+    pub field: OversizedField,
+}
+
+impl fmt::Display for OversizedEntry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "entrée {} ignorée : {} trop longue", self.position, self.field.label())
+    }
+}
+
+/// Rapport de [`FileStorage::save_with`]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SaveReport {
+    /// Nombre d'entrées écrites
+    pub entries: usize,
+    /// Entrées non écrites car trop longues
+    pub oversized: Vec<OversizedEntry>,
+}
+
+/// Entrées lues par [`FileStorage::load_with`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LimitedLoad<K, V> {
+    /// Capacité lue sur la première ligne
+    pub capacity: usize,
+    /// Entrées lisibles, dans l'ordre du fichier
+    pub data: Vec<(K, V)>,
+    /// Entrées ignorées car trop longues
+    pub oversized: Vec<OversizedEntry>,
+}
+
+/// Entrées lues par [`FileStorage::load_checked`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CheckedLoad<K, V> {
+    /// Capacité lue sur la première ligne
+    pub capacity: usize,
+    /// Entrées lisibles, dans l'ordre du fichier
+    pub data: Vec<(K, V)>,
+    /// Vrai si le fichier comporte une somme de contrôle et qu'elle a été
+    /// vérifiée ; faux pour un fichier des versions 1 et 2, chargé sans
+    /// vérification
+    pub verified: bool,
+}
+
+/// Ligne d'entrée ou de suppression d'un fichier, passée par
+/// [`FileStorage::load_records`] et écrite par [`FileStorage::append`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Record<K, V> {
+    /// Entrée ajoutée ou remplacée
+    Put(K, V),
+    /// Clé supprimée
+    Remove(K),
+}
 
 /// Gère la persistance des données du cache dans un fichier
 /// 
@@ -50,6 +288,11 @@ pub struct FileStorage;
 impl FileStorage {
     /// Sauvegarde les données du cache dans un fichier
     /// 
+    /// L'écriture est atomique : les données sont écrites dans un fichier
+    /// temporaire du même répertoire (`<path>.tmp.<pid>.<n>`), synchronisé
+    /// sur le disque puis renommé sur `path`. Une sauvegarde interrompue ou
+    /// en échec laisse l'ancien fichier intact.
+    /// 
     /// # Arguments
     /// 
     /// * `path` - Le chemin du fichier où sauvegarder les données
@@ -58,8 +301,14 @@ impl FileStorage {
     /// 
     /// # Format du fichier
     /// 
-    /// La première ligne contient la capacité du cache.
+    /// Après la ligne de version, la première ligne contient la capacité du
+    /// cache, sans heure de sauvegarde (voir [`SaveOptions::saved_at`]).
     /// Chaque ligne suivante contient une paire clé-valeur séparée par ';'.
+    /// Les `\`, `;`, retours à la ligne et retours chariot de la clé et de
+    /// la valeur sont échappés (`\\`, `\;`, `\n`, `\r`), si bien que
+    /// [`FileStorage::load`] relit exactement les mêmes chaînes. La dernière
+    /// ligne contient le CRC-32 du reste du fichier, vérifié au chargement ;
+    /// voir [`format`](super::format).
     /// 
     /// # Exemple
     /// 
@@ -70,9 +319,11 @@ impl FileStorage {
     /// FileStorage::save("test.txt", 2, &data).unwrap();
     /// 
     /// // Le fichier contiendra :
+    /// // #lru_cache v4
     /// // 2
     /// // key1;42
     /// // key2;84
+    /// // #crc32 <somme de contrôle>
     /// 
     /// std::fs::remove_file("test.txt").unwrap();
     /// ```
@@ -80,24 +331,164 @@ impl FileStorage {
     /// # Errors
     /// 
     /// Retourne une erreur si :
-    /// - Le fichier ne peut pas être créé ou ouvert
-    /// - L'écriture dans le fichier échoue
-    pub fn save<K: Display, V: Display>(path: &str, capacity: usize, data: &[(K, V)]) -> io::Result<()> {
-        let mut content = String::new();
-        content.push_str(&format!("{}\n", capacity));
-        
-        for (key, value) in data {
-            content.push_str(&format!("{};{}\n", key, value));
-        }
-        
-        let file = OpenOptions::new()
-            .write(true)
-            .create(true)
-            .truncate(true)
-            .open(path)?;
-        let mut writer = BufWriter::new(file);
-        writer.write_all(content.as_bytes())?;
-        writer.flush()
+    /// - Le fichier temporaire ne peut pas être créé
+    /// - L'écriture, la synchronisation ou le renommage échoue
+    ///
+    /// # Note
+    ///
+    /// Les entrées qui dépassent les limites de [`SaveOptions::default`]
+    /// sont ignorées ; [`FileStorage::save_with`] les signale.
+    pub fn save<K: Display, V: Display>(path: impl AsRef<Path>, capacity: usize, data: &[(K, V)]) -> io::Result<()> {
+        FileStorage::save_with(path, capacity, data, SaveOptions::default()).map(|_| ())
+    }
+
+    /// Sauvegarde les données en ignorant les entrées trop longues
+    ///
+    /// La longueur de chaque clé et valeur est mesurée en formatant sans
+    /// conserver le texte, et la mesure s'arrête dès que la limite est
+    /// dépassée : une valeur démesurée n'est jamais copiée en mémoire ni
+    /// écrite. Les entrées écartées sont listées dans le rapport. Comme pour
+    /// [`FileStorage::save`], l'écriture est atomique.
+    ///
+    /// # Exemple
+    ///
+    /// ```
+    /// use lru_cache::storage::file::{FileStorage, OversizedField, SaveOptions};
+    ///
+    /// let options = SaveOptions { max_key_len: 8, max_value_len: 4, ..SaveOptions::default() };
+    /// let data = vec![("key1", "1234"), ("key2", "12345")];
+    /// let report = FileStorage::save_with("save_with_doc.txt", 2, &data, options).unwrap();
+    /// assert_eq!(report.entries, 1);
+    /// assert_eq!(report.oversized[0].position, 1);
+    /// assert_eq!(report.oversized[0].field, OversizedField::Value);
+    ///
+    /// std::fs::remove_file("save_with_doc.txt").unwrap();
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Retourne une erreur si le fichier ne peut pas être créé ou écrit.
+    pub fn save_with<K: Display, V: Display>(
+        path: impl AsRef<Path>,
+        capacity: usize,
+        data: &[(K, V)],
+        options: SaveOptions,
+    ) -> io::Result<SaveReport> {
+        save_entries(path.as_ref(), capacity, data.iter().map(|(key, value)| (key, value)), options)
+    }
+
+    /// Écrit les données dans `writer`, octet pour octet comme
+    /// [`FileStorage::save`] les écrirait dans un fichier
+    ///
+    /// Permet d'envoyer le cache dans un flux (corps de réponse HTTP,
+    /// socket...). Le tampon éventuel de `writer` n'est pas vidé : appeler
+    /// `flush` si nécessaire.
+    ///
+    /// # Exemple
+    ///
+    /// ```
+    /// use lru_cache::storage::file::FileStorage;
+    /// use std::io::Cursor;
+    ///
+    /// let mut buffer = Vec::new();
+    /// FileStorage::save_to_writer(&mut buffer, 2, &[("key", 1)]).unwrap();
+    ///
+    /// let (capacity, data) = FileStorage::load_from_reader::<String, i32>(Cursor::new(buffer)).unwrap();
+    /// assert_eq!((capacity, data), (2, vec![(String::from("key"), 1)]));
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Retourne les erreurs d'écriture de `writer`.
+    pub fn save_to_writer<K: Display, V: Display>(writer: impl Write, capacity: usize, data: &[(K, V)]) -> io::Result<()> {
+        FileStorage::save_to_writer_with(writer, capacity, data, SaveOptions::default()).map(|_| ())
+    }
+
+    /// Comme [`FileStorage::save_to_writer`], avec les options de
+    /// [`FileStorage::save_with`]
+    ///
+    /// # Errors
+    ///
+    /// Retourne les erreurs d'écriture de `writer`.
+    pub fn save_to_writer_with<K: Display, V: Display>(
+        mut writer: impl Write,
+        capacity: usize,
+        data: &[(K, V)],
+        options: SaveOptions,
+    ) -> io::Result<SaveReport> {
+        write_entries(&mut writer, capacity, data.iter().map(|(key, value)| (key, value)), options)
+    }
+
+    /// Comme [`FileStorage::save_with`], en compressant le fichier en gzip
+    /// (feature `compression`)
+    ///
+    /// `level` va de 0 (aucune compression) à 9 (compression maximale) ; une
+    /// valeur plus grande est ramenée à 9. Les fonctions de chargement
+    /// reconnaissent un fichier compressé à ses premiers octets : un fichier
+    /// écrit ainsi se relit avec [`FileStorage::load`] comme avec
+    /// [`FileStorage::load_compressed`].
+    ///
+    /// # Exemple
+    ///
+    /// ```
+    /// use lru_cache::storage::file::{FileStorage, SaveOptions};
+    ///
+    /// let data = vec![(String::from("key"), 1)];
+    /// FileStorage::save_compressed("compressed_doc.txt.gz", 2, &data, SaveOptions::default(), 6).unwrap();
+    /// let (capacity, loaded) = FileStorage::load::<String, i32>("compressed_doc.txt.gz").unwrap();
+    /// assert_eq!((capacity, loaded), (2, data));
+    ///
+    /// std::fs::remove_file("compressed_doc.txt.gz").unwrap();
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Retourne une erreur si le fichier ne peut pas être créé ou écrit.
+    #[cfg(feature = "compression")]
+    pub fn save_compressed<K: Display, V: Display>(
+        path: impl AsRef<Path>,
+        capacity: usize,
+        data: &[(K, V)],
+        options: SaveOptions,
+        level: u32,
+    ) -> io::Result<SaveReport> {
+        let path = path.as_ref();
+        let report = write_atomic(path, |writer| {
+            let mut encoder = GzEncoder::new(writer, Compression::new(level.min(9)));
+            let report = write_entries(&mut encoder, capacity, data.iter().map(|(key, value)| (key, value)), options)?;
+            encoder.finish()?;
+            Ok(report)
+        })?;
+        trace_event!(
+            debug,
+            path = %path.display(),
+            capacity,
+            entries = report.entries,
+            level,
+            "cache file saved"
+        );
+        Ok(report)
+    }
+
+    /// Charge un fichier compressé par [`FileStorage::save_compressed`]
+    /// (feature `compression`)
+    ///
+    /// [`FileStorage::load`] lit aussi ces fichiers ; cette fonction refuse
+    /// en plus un fichier qui n'est pas compressé.
+    ///
+    /// # Errors
+    ///
+    /// Les erreurs de [`FileStorage::load`], et une erreur
+    /// [`io::ErrorKind::InvalidData`] si le fichier n'est pas au format gzip.
+    #[cfg(feature = "compression")]
+    pub fn load_compressed<K: FromStr, V: FromStr>(path: impl AsRef<Path>) -> Result<(usize, Vec<(K, V)>), StorageError> {
+        let path = path.as_ref();
+        let mut magic = [0; 2];
+        let read = File::open(path)?.read(&mut magic)?;
+        if magic[..read] != GZIP_MAGIC {
+            return Err(io::Error::new(io::ErrorKind::InvalidData, "le fichier n'est pas compressé en gzip").into());
+        }
+        FileStorage::load(path)
     }
 
     /// Charge les données du cache depuis un fichier
@@ -132,34 +523,1440 @@ impl FileStorage {
     /// 
     /// # Errors
     /// 
-    /// Retourne une erreur si :
-    /// - Le fichier ne peut pas être ouvert
-    /// - La lecture du fichier échoue
-    /// - Le format du fichier est invalide
+    /// Retourne :
+    /// - [`StorageError::Io`] si le fichier ne peut pas être ouvert ou lu
+    /// - [`StorageError::MissingCapacity`] si le fichier ou sa première
+    ///   ligne est vide
+    /// - [`StorageError::InvalidCapacity`] si la première ligne n'est pas
+    ///   une capacité
+    /// - [`StorageError::UnsupportedVersion`] si le fichier vient d'une
+    ///   version plus récente du format
+    /// - [`StorageError::ChecksumMismatch`] ou
+    ///   [`StorageError::MissingChecksum`] si le contenu d'un fichier avec
+    ///   somme de contrôle a été modifié ou tronqué
     /// 
     /// # Note
     /// 
-    /// Les entrées qui ne peuvent pas être parsées sont silencieusement ignorées.
-    pub fn load<K: FromStr, V: FromStr>(path: &str) -> io::Result<(usize, Vec<(K, V)>)> {
-        let file = File::open(path)?;
-        let mut reader = BufReader::new(file);
-        let mut content = String::new();
-        reader.read_to_string(&mut content)?;
-        
-        let mut lines = content.lines();
-        let capacity = lines.next()
-            .and_then(|l| l.parse().ok())
-            .unwrap_or(0);
-            
-        let mut data = Vec::new();
-        for line in lines {
-            if let Some((key_str, value_str)) = line.split_once(';') {
-                if let (Ok(key), Ok(value)) = (K::from_str(key_str), V::from_str(value_str)) {
-                    data.push((key, value));
+    /// Les entrées qui ne peuvent pas être parsées ou qui dépassent les
+    /// limites de [`LoadOptions::default`] sont silencieusement ignorées ;
+    /// [`FileStorage::load_strict`] les refuse et
+    /// [`FileStorage::load_lossy`] les énumère.
+    pub fn load<K: FromStr, V: FromStr>(path: impl AsRef<Path>) -> Result<(usize, Vec<(K, V)>), StorageError> {
+        let mut replay = Replay::new();
+        let apply = |record, key: &str, replays| replay.apply(record, key, replays);
+        let (capacity, _) = load_limited(path.as_ref(), LoadOptions::default(), false, apply, |_| {})?;
+        Ok((capacity, replay.into_vec()))
+    }
+
+    /// Comme [`FileStorage::load`], en lisant les lignes du fichier sur le
+    /// pool de rayon
+    ///
+    /// Le fichier, décompressé s'il est compressé en gzip, est lu en entier
+    /// en mémoire, puis découpé en blocs de lignes d'environ 1 Mio. Chaque
+    /// bloc est lu par un thread de rayon : décodage UTF-8, séparation et
+    /// désechappement des champs, `K::from_str` et `V::from_str`, CRC-32 des
+    /// octets. Les résultats des blocs sont ensuite assemblés dans l'ordre
+    /// du fichier : les sommes de contrôle sont vérifiées et les entrées
+    /// rejouées comme par [`FileStorage::load`], dont le résultat est
+    /// identique, erreurs comprises. La mémoire occupée pendant le
+    /// chargement comprend tout le texte du fichier.
+    ///
+    /// # Exemple
+    ///
+    /// ```
+    /// use lru_cache::storage::file::FileStorage;
+    ///
+    /// FileStorage::save("parallel_doc.txt", 2, &[("a", 1), ("b", 2)]).unwrap();
+    /// let (capacity, data) = FileStorage::load_parallel::<String, u32>("parallel_doc.txt").unwrap();
+    /// assert_eq!((capacity, data), FileStorage::load("parallel_doc.txt").unwrap());
+    ///
+    /// std::fs::remove_file("parallel_doc.txt").unwrap();
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Les erreurs de [`FileStorage::load`].
+    #[cfg(feature = "rayon")]
+    pub fn load_parallel<K, V>(path: impl AsRef<Path>) -> Result<(usize, Vec<(K, V)>), StorageError>
+    where
+        K: FromStr + Send,
+        V: FromStr + Send,
+    {
+        use rayon::prelude::*;
+
+        let path = path.as_ref();
+        let mut bytes = Vec::new();
+        open_reader(path)?.read_to_end(&mut bytes)?;
+        let options = LoadOptions::default();
+        let limit = line_limit(&options);
+        let mut reader = ChecksumReader::new(&bytes[..]);
+        let version = skip_version(&mut reader)?;
+        let capacity = read_header(&mut reader, &mut Vec::new(), limit)?;
+
+        let chunks: Vec<Chunk<K, V>> = split_chunks(reader.inner, PARALLEL_CHUNK_LEN)
+            .into_par_iter()
+            .map(|chunk| parse_chunk(chunk, version, &options, limit))
+            .collect();
+        let mut replay = Replay::new();
+        for chunk in chunks {
+            for line in chunk.lines {
+                match line {
+                    ChunkLine::Record(record, text) => replay.apply_keyed(record, text),
+                    ChunkLine::Checksum { text, before, own, record } => {
+                        reader.crc = crc32_combine(reader.crc, before.crc, before.len);
+                        reader.pending += before.len;
+                        reader.mark();
+                        if let Some(expected) = reader.checksum_line(version, &text) {
+                            reader.end_segment(expected);
+                            continue;
+                        }
+                        reader.crc = crc32_combine(reader.crc, own.crc, own.len);
+                        reader.pending += own.len;
+                        if let Some((record, text)) = record {
+                            replay.apply_keyed(record, text);
+                        }
+                    }
+                    ChunkLine::Invalid(error) => return Err(error.into()),
                 }
             }
+            reader.crc = crc32_combine(reader.crc, chunk.tail.crc, chunk.tail.len);
+            reader.pending += chunk.tail.len;
         }
-        
+        reader.check(version)?;
+        trace_event!(debug, path = %path.display(), capacity, "cache file loaded in parallel");
+        Ok((capacity, replay.into_vec()))
+    }
+
+    /// Comme [`FileStorage::save`], en tenant le verrou exclusif du fichier
+    /// pendant l'écriture
+    ///
+    /// Deux processus qui sauvegardent avec cette méthode écrivent l'un
+    /// après l'autre ; voir [`FileLock`] pour tenir le verrou pendant une
+    /// lecture suivie d'une écriture.
+    ///
+    /// # Errors
+    ///
+    /// [`StorageError::LockTimeout`] si le verrou n'est pas obtenu dans le
+    /// délai `timeout`, et [`StorageError::Io`] si le verrou ne peut pas
+    /// être pris ou si la sauvegarde échoue.
+    pub fn save_locked<K: Display, V: Display>(
+        path: impl AsRef<Path>,
+        capacity: usize,
+        data: &[(K, V)],
+        timeout: Duration,
+    ) -> Result<(), StorageError> {
+        let _lock = FileLock::exclusive(path.as_ref(), timeout)?;
+        FileStorage::save(path, capacity, data)?;
+        Ok(())
+    }
+
+    /// Comme [`FileStorage::load`], en tenant un verrou partagé pendant la
+    /// lecture : une sauvegarde verrouillée attend la fin du chargement
+    ///
+    /// # Exemple
+    ///
+    /// ```
+    /// use lru_cache::storage::file::FileStorage;
+    /// use std::time::Duration;
+    ///
+    /// let timeout = Duration::from_secs(5);
+    /// FileStorage::save_locked("locked_doc.txt", 2, &[("key", 1)], timeout).unwrap();
+    /// let (_, data) = FileStorage::load_locked::<String, i32>("locked_doc.txt", timeout).unwrap();
+    /// assert_eq!(data, vec![(String::from("key"), 1)]);
+    ///
+    /// std::fs::remove_file("locked_doc.txt").unwrap();
+    /// std::fs::remove_file("locked_doc.txt.lock").unwrap();
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Les erreurs de [`FileStorage::load`], et [`StorageError::LockTimeout`]
+    /// si le verrou n'est pas obtenu dans le délai `timeout`.
+    pub fn load_locked<K: FromStr, V: FromStr>(
+        path: impl AsRef<Path>,
+        timeout: Duration,
+    ) -> Result<(usize, Vec<(K, V)>), StorageError> {
+        let _lock = FileLock::shared(path.as_ref(), timeout)?;
+        FileStorage::load(path)
+    }
+
+    /// Charge les données lues dans `reader`, comme [`FileStorage::load`]
+    /// les lirait dans un fichier
+    ///
+    /// `reader` est lu par blocs jusqu'à la fin du flux ; un contenu
+    /// compressé en gzip est reconnu de la même façon.
+    ///
+    /// # Errors
+    ///
+    /// Les erreurs de [`FileStorage::load`] ; une erreur de lecture de
+    /// `reader` devient [`StorageError::Io`].
+    pub fn load_from_reader<K: FromStr, V: FromStr>(reader: impl Read) -> Result<(usize, Vec<(K, V)>), StorageError> {
+        let mut replay = Replay::new();
+        let reader = decode_reader(BufReader::new(reader))?;
+        let apply = |record, key: &str, replays| replay.apply(record, key, replays);
+        let (capacity, _) = read_entries(reader, LoadOptions::default(), false, apply, |_| {})?;
+        Ok((capacity, replay.into_vec()))
+    }
+
+    /// Comme [`FileStorage::load`], en indiquant si la somme de contrôle du
+    /// fichier a été vérifiée
+    ///
+    /// # Exemple
+    ///
+    /// ```
+    /// use lru_cache::storage::file::FileStorage;
+    /// use std::fs::write;
+    ///
+    /// FileStorage::save("checked_doc.txt", 2, &[("key", 1)]).unwrap();
+    /// assert!(FileStorage::load_checked::<String, i32>("checked_doc.txt").unwrap().verified);
+    ///
+    /// write("checked_doc.txt", "2\nkey;1\n").unwrap();
+    /// let loaded = FileStorage::load_checked::<String, i32>("checked_doc.txt").unwrap();
+    /// assert_eq!(loaded.data, vec![(String::from("key"), 1)]);
+    /// assert!(!loaded.verified);
+    ///
+    /// std::fs::remove_file("checked_doc.txt").unwrap();
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Les erreurs de [`FileStorage::load`].
+    pub fn load_checked<K: FromStr, V: FromStr>(path: impl AsRef<Path>) -> Result<CheckedLoad<K, V>, StorageError> {
+        let mut replay = Replay::new();
+        let apply = |record, key: &str, replays| replay.apply(record, key, replays);
+        let (capacity, verified) = load_limited(path.as_ref(), LoadOptions::default(), false, apply, |_| {})?;
+        Ok(CheckedLoad {
+            capacity,
+            data: replay.into_vec(),
+            verified,
+        })
+    }
+
+    /// Comme [`FileStorage::load`], mais échoue sur la première ligne
+    /// illisible au lieu de l'ignorer
+    ///
+    /// # Exemple
+    ///
+    /// ```
+    /// use lru_cache::storage::error::StorageError;
+    /// use lru_cache::storage::file::FileStorage;
+    /// use std::fs::write;
+    ///
+    /// write("strict_doc.txt", "2\nkey1;42\nkey2;oops\n").unwrap();
+    /// let error = FileStorage::load_strict::<String, i32>("strict_doc.txt").unwrap_err();
+    /// assert!(matches!(error, StorageError::InvalidEntry { line: 3, .. }));
+    ///
+    /// std::fs::remove_file("strict_doc.txt").unwrap();
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Les erreurs de [`FileStorage::load`], plus
+    /// [`StorageError::InvalidEntry`] pour une ligne sans séparateur ou dont
+    /// la clé ou la valeur est refusée par `FromStr`, et
+    /// [`StorageError::Oversized`] pour une entrée qui dépasse les limites de
+    /// [`LoadOptions::default`].
+    pub fn load_strict<K: FromStr, V: FromStr>(path: impl AsRef<Path>) -> Result<(usize, Vec<(K, V)>), StorageError> {
+        let mut replay = Replay::new();
+        let options = LoadOptions {
+            strict: true,
+            ..LoadOptions::default()
+        };
+        let apply = |record, key: &str, replays| replay.apply(record, key, replays);
+        let (capacity, _) = load_limited(path.as_ref(), options, true, apply, |_| {})?;
+        Ok((capacity, replay.into_vec()))
+    }
+
+    /// Lit le fichier ligne par ligne et passe chaque entrée valide à `f`,
+    /// dans l'ordre du fichier
+    ///
+    /// Retourne la capacité de l'en-tête. Comme pour [`FileStorage::load`],
+    /// les entrées qui ne peuvent pas être parsées ou qui sont trop longues
+    /// sont ignorées ; seule la ligne courante est gardée en mémoire. La
+    /// somme de contrôle n'est vérifiée qu'à la fin du fichier : en cas
+    /// d'erreur, `f` a déjà reçu les entrées lues.
+    ///
+    /// Les entrées sont passées telles qu'écrites : dans un fichier avec des
+    /// segments incrémentaux, une clé peut revenir et les suppressions ne
+    /// sont pas transmises. [`FileStorage::load_records`] les transmet.
+    ///
+    /// # Errors
+    ///
+    /// Les erreurs de [`FileStorage::load`].
+    pub fn load_each<K: FromStr, V: FromStr>(path: impl AsRef<Path>, mut f: impl FnMut(K, V)) -> Result<usize, StorageError> {
+        let put = |record, _: &str, _| {
+            if let Record::Put(key, value) = record {
+                f(key, value);
+            }
+        };
+        load_limited(path.as_ref(), LoadOptions::default(), false, put, |_| {}).map(|(capacity, _)| capacity)
+    }
+
+    /// Comme [`FileStorage::load_each`], en passant aussi à `f` les lignes
+    /// de suppression des segments incrémentaux
+    ///
+    /// Appliquer les enregistrements dans l'ordre reconstitue le contenu
+    /// sauvegardé, sans garder le fichier en mémoire.
+    ///
+    /// # Exemple
+    ///
+    /// ```
+    /// use lru_cache::storage::file::{FileStorage, Record};
+    ///
+    /// FileStorage::save("records_doc.txt", 2, &[("a", 1), ("b", 2)]).unwrap();
+    /// FileStorage::append("records_doc.txt", [Record::Remove("a"), Record::Put("c", 3)]).unwrap();
+    ///
+    /// let mut records = Vec::new();
+    /// FileStorage::load_records::<String, i32>("records_doc.txt", |record| records.push(record)).unwrap();
+    /// assert_eq!(records[2], Record::Remove(String::from("a")));
+    ///
+    /// let (_, data) = FileStorage::load::<String, i32>("records_doc.txt").unwrap();
+    /// assert_eq!(data, vec![(String::from("b"), 2), (String::from("c"), 3)]);
+    ///
+    /// std::fs::remove_file("records_doc.txt").unwrap();
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Les erreurs de [`FileStorage::load`].
+    pub fn load_records<K: FromStr, V: FromStr>(
+        path: impl AsRef<Path>,
+        mut f: impl FnMut(Record<K, V>),
+    ) -> Result<usize, StorageError> {
+        let apply = |record, _: &str, _| f(record);
+        load_limited(path.as_ref(), LoadOptions::default(), false, apply, |_| {}).map(|(capacity, _)| capacity)
+    }
+
+    /// Ajoute un segment incrémental à la fin d'un fichier existant, sans
+    /// réécrire son contenu
+    ///
+    /// Le segment contient `records` dans l'ordre, suivi de sa propre somme
+    /// de contrôle ; voir [`format`](super::format). Il est écrit en une
+    /// fois puis synchronisé sur le disque. Contrairement à
+    /// [`FileStorage::save`], l'écriture n'est pas atomique : un ajout
+    /// interrompu laisse un segment sans somme de contrôle, et le fichier
+    /// est alors refusé avec [`StorageError::MissingChecksum`] ou
+    /// [`StorageError::ChecksumMismatch`]. Retourne le nombre
+    /// d'enregistrements écrits ; rien n'est écrit s'il n'y en a aucun.
+    ///
+    /// # Errors
+    ///
+    /// Retourne [`StorageError::Io`] si le fichier ne peut pas être lu ou
+    /// écrit, ou avec [`io::ErrorKind::InvalidInput`] s'il est compressé ou
+    /// d'une version antérieure à [`INCREMENTAL_VERSION`] ; les erreurs de
+    /// version de [`FileStorage::detect_version`].
+    pub fn append<K: Display, V: Display>(
+        path: impl AsRef<Path>,
+        records: impl IntoIterator<Item = Record<K, V>>,
+    ) -> Result<usize, StorageError> {
+        let path = path.as_ref();
+        let mut file = OpenOptions::new().read(true).append(true).open(path)?;
+        let mut reader = BufReader::new(&mut file);
+        if reader.fill_buf()?.starts_with(&GZIP_MAGIC) {
+            return Err(invalid_input("impossible d'ajouter un segment à un fichier compressé").into());
+        }
+        let version = skip_version(&mut reader)?.unwrap_or(1);
+        if version < INCREMENTAL_VERSION {
+            return Err(invalid_input(format!("fichier de version {} : une sauvegarde complète est nécessaire", version)).into());
+        }
+
+        let mut buffer = Vec::new();
+        let mut writer = ChecksumWriter::new(&mut buffer);
+        let mut written = 0;
+        for record in records {
+            match record {
+                Record::Put(key, value) => write_entry(&mut writer, Escaped(key), Escaped(value))?,
+                Record::Remove(key) => write_removal(&mut writer, key)?,
+            }
+            written += 1;
+        }
+        if written == 0 {
+            return Ok(0);
+        }
+        writer.finish()?;
+        file.write_all(&buffer)?;
+        file.sync_data()?;
+        trace_event!(debug, path = %path.display(), records = written, "cache file segment appended");
+        Ok(written)
+    }
+
+    /// Réécrit un fichier avec des segments incrémentaux en un seul
+    /// instantané, sans modifier son contenu
+    ///
+    /// Les entrées sont rejouées comme par [`FileStorage::load`], sans être
+    /// parsées ni limitées en longueur, puis écrites de façon atomique au
+    /// format courant ; la capacité et l'heure de sauvegarde de l'en-tête
+    /// sont conservées.
+    ///
+    /// # Exemple
+    ///
+    /// ```
+    /// use lru_cache::storage::file::{FileStorage, Record};
+    ///
+    /// FileStorage::save("compact_doc.txt", 2, &[("a", 1), ("b", 2)]).unwrap();
+    /// FileStorage::append("compact_doc.txt", [Record::Put("a", 3)]).unwrap();
+    /// FileStorage::compact_file("compact_doc.txt").unwrap();
+    ///
+    /// let mut expected = Vec::new();
+    /// FileStorage::save_to_writer(&mut expected, 2, &[("b", 2), ("a", 3)]).unwrap();
+    /// assert_eq!(std::fs::read("compact_doc.txt").unwrap(), expected);
+    ///
+    /// std::fs::remove_file("compact_doc.txt").unwrap();
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Les erreurs de [`FileStorage::load`] si le fichier ne peut pas être
+    /// lu, et [`StorageError::Io`] si la réécriture échoue.
+    pub fn compact_file(path: impl AsRef<Path>) -> Result<(), StorageError> {
+        let path = path.as_ref();
+        let metadata = FileStorage::metadata(path)?;
+        let limits = LoadOptions {
+            max_key_len: usize::MAX,
+            max_value_len: usize::MAX,
+            strict: false,
+        };
+        let loaded = FileStorage::load_with::<String, String>(path, limits)?;
+        let options = SaveOptions {
+            max_key_len: usize::MAX,
+            max_value_len: usize::MAX,
+            saved_at: metadata.saved_at,
+        };
+        FileStorage::save_with(path, loaded.capacity, &loaded.data, options)?;
+        Ok(())
+    }
+
+    /// Charge les données en appliquant des limites de longueur aux clés et
+    /// aux valeurs
+    ///
+    /// Le fichier est lu par blocs : au plus `max_key_len + max_value_len + 1`
+    /// octets d'une ligne sont gardés en mémoire, le reste d'une ligne trop
+    /// longue est sauté sans être conservé. Une entrée trop longue est
+    /// signalée dans `oversized`, ou fait échouer le chargement avec
+    /// [`LoadOptions::strict`]. Les autres lignes illisibles sont ignorées
+    /// comme par [`FileStorage::load`].
+    ///
+    /// # Exemple
+    ///
+    /// ```
+    /// use lru_cache::storage::file::{FileStorage, LoadOptions};
+    /// use std::fs::write;
+    ///
+    /// write("load_with_doc.txt", "2\nkey1;1234\nkey2;12345\n").unwrap();
+    /// let options = LoadOptions { max_key_len: 8, max_value_len: 4, strict: false };
+    /// let loaded = FileStorage::load_with::<String, i32>("load_with_doc.txt", options).unwrap();
+    /// assert_eq!(loaded.data, vec![(String::from("key1"), 1234)]);
+    /// assert_eq!(loaded.oversized[0].position, 3);
+    ///
+    /// std::fs::remove_file("load_with_doc.txt").unwrap();
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Les erreurs d'en-tête de [`FileStorage::load`], et
+    /// [`StorageError::Oversized`] pour la première entrée trop longue d'un
+    /// chargement strict.
+    pub fn load_with<K: FromStr, V: FromStr>(
+        path: impl AsRef<Path>,
+        options: LoadOptions,
+    ) -> Result<LimitedLoad<K, V>, StorageError> {
+        let mut replay = Replay::new();
+        let mut oversized = Vec::new();
+        let apply = |record, key: &str, replays| replay.apply(record, key, replays);
+        let (capacity, _) = load_limited(path.as_ref(), options, false, apply, |entry| oversized.push(entry))?;
+        Ok(LimitedLoad {
+            capacity,
+            data: replay.into_vec(),
+            oversized,
+        })
+    }
+
+    /// Sauvegarde les données avec l'encodage canonique de [`TextCodec`]
+    ///
+    /// Le format est celui de [`FileStorage::save`], mais les `;` et retours
+    /// à la ligne des clés et valeurs sont échappés et les flottants relus à
+    /// l'identique.
+    ///
+    /// # Exemple
+    ///
+    /// ```
+    /// use lru_cache::storage::file::FileStorage;
+    ///
+    /// let data = vec![(';', f64::NAN), ('a', -0.0)];
+    /// FileStorage::save_encoded("encoded_doc.txt", 2, &data).unwrap();
+    ///
+    /// let (_, loaded) = FileStorage::load_encoded::<char, f64>("encoded_doc.txt").unwrap();
+    /// assert_eq!(loaded[0].0, ';');
+    /// assert!(loaded[0].1.is_nan());
+    /// assert!(loaded[1].1.is_sign_negative());
+    ///
+    /// std::fs::remove_file("encoded_doc.txt").unwrap();
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Retourne une erreur si le fichier ne peut pas être créé ou écrit.
+    pub fn save_encoded<K: TextCodec, V: TextCodec>(path: impl AsRef<Path>, capacity: usize, data: &[(K, V)]) -> io::Result<()> {
+        write_atomic(path, |writer| {
+            let mut writer = ChecksumWriter::new(writer);
+            write_header(&mut writer, capacity, None)?;
+            for (key, value) in data {
+                write_entry(&mut writer, key.encode(), value.encode())?;
+            }
+            writer.finish()
+        })
+    }
+
+    /// Charge un fichier écrit par [`FileStorage::save_encoded`]
+    ///
+    /// Contrairement à [`FileStorage::load`], aucune entrée n'est ignorée :
+    /// la première ligne illisible fait échouer le chargement.
+    ///
+    /// # Errors
+    ///
+    /// Retourne une erreur si le fichier ne peut pas être lu, ou une erreur
+    /// [`io::ErrorKind::InvalidData`] indiquant la ligne fautive si la
+    /// capacité, une clé ou une valeur ne peut pas être décodée.
+    pub fn load_encoded<K: TextCodec, V: TextCodec>(path: impl AsRef<Path>) -> io::Result<(usize, Vec<(K, V)>)> {
+        let mut reader = ChecksumReader::new(open_reader(path.as_ref())?);
+        let version = skip_version(&mut reader)?;
+        let mut number = usize::from(version.is_some()) + 1;
+        let mut line = String::new();
+        if reader.read_line(&mut line)? == 0 {
+            return Err(invalid_data(number, "fichier vide"));
+        }
+        let header = line.strip_suffix(LINE_END).unwrap_or(&line);
+        let capacity = parse_header(header).ok_or_else(|| invalid_data(number, header))?;
+
+        let mut data = Vec::new();
+        loop {
+            reader.mark();
+            line.clear();
+            if reader.read_line(&mut line)? == 0 {
+                break;
+            }
+            number += 1;
+            let line = line.strip_suffix(LINE_END).unwrap_or(&line);
+            if let Some(expected) = reader.checksum_line(version, line) {
+                reader.end_segment(expected);
+                continue;
+            }
+            let (key, value) = split_escaped_entry(line).ok_or_else(|| invalid_data(number, line))?;
+            let key = K::decode(key).map_err(|e| invalid_data(number, &e))?;
+            let value = V::decode(value).map_err(|e| invalid_data(number, &e))?;
+            data.push((key, value));
+        }
+        reader.check(version)?;
         Ok((capacity, data))
     }
-}
\ No newline at end of file
+
+    /// Charge les entrées lisibles d'un fichier éventuellement abîmé et
+    /// décrit ce qui a été perdu
+    ///
+    /// Une dernière ligne sans retour à la ligne est le signe d'une écriture
+    /// interrompue : elle est ignorée, même si elle se parse (une valeur
+    /// tronquée peut rester valide), et le rapport indique `truncated`.
+    ///
+    /// # Exemple
+    ///
+    /// ```
+    /// use lru_cache::storage::file::FileStorage;
+    /// use std::fs::write;
+    ///
+    /// write("lossy_doc.txt", "2\nkey1;42\nkey2;8").unwrap();
+    /// let loaded = FileStorage::load_lossy::<String, i32>("lossy_doc.txt").unwrap();
+    /// assert_eq!(loaded.data, vec![(String::from("key1"), 42)]);
+    /// assert!(loaded.report.truncated);
+    /// assert_eq!(loaded.report.clean_len, 10);
+    ///
+    /// std::fs::remove_file("lossy_doc.txt").unwrap();
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Retourne [`StorageError::Io`] si le fichier ne peut pas être ouvert
+    /// ou lu ; un en-tête invalide est seulement signalé par
+    /// `report.capacity`.
+    pub fn load_lossy<K: FromStr, V: FromStr>(path: impl AsRef<Path>) -> Result<LossyLoad<K, V>, StorageError> {
+        let mut replay = Replay::new();
+        let report = salvage(path.as_ref(), |line, replays| {
+            if let Some(key) = parse_removal(line).filter(|_| replays) {
+                replay.apply(Record::Remove(K::from_str(&key).ok()?), &key, replays);
+                return Some(());
+            }
+            let (key, value) = parse_entry(line)?;
+            let record = Record::Put(K::from_str(&key).ok()?, V::from_str(&value).ok()?);
+            replay.apply(record, &key, replays);
+            Some(())
+        })?;
+        Ok(LossyLoad {
+            data: replay.into_vec(),
+            report,
+        })
+    }
+
+    /// Écrit dans `out_path` la partie récupérable de `path`
+    ///
+    /// Seules les lignes complètes contenant un séparateur sont recopiées,
+    /// sans vérifier que clé et valeur se parsent, ainsi que les lignes de
+    /// suppression d'un fichier v4, dans l'ordre. L'en-tête est réécrit ;
+    /// s'il est absent ou invalide, la capacité devient le nombre d'entrées
+    /// recopiées. `path` n'est pas modifié.
+    ///
+    /// # Errors
+    ///
+    /// Retourne une erreur si la lecture de `path` ou l'écriture de
+    /// `out_path` échoue.
+    pub fn repair(path: impl AsRef<Path>, out_path: impl AsRef<Path>) -> io::Result<SalvageReport> {
+        let mut lines = Vec::new();
+        let report = salvage(path.as_ref(), |line, replays| {
+            if !(replays && parse_removal(line).is_some()) {
+                split_escaped_entry(line)?;
+            }
+            lines.push(line.to_string());
+            Some(())
+        })?;
+        write_atomic(out_path, |writer| {
+            let mut writer = ChecksumWriter::new(writer);
+            write_header(&mut writer, report.capacity.unwrap_or(report.entries), None)?;
+            for line in &lines {
+                write!(writer, "{}{}", line, LINE_END)?;
+            }
+            writer.finish()
+        })?;
+        Ok(report)
+    }
+
+    /// Retourne la version du format d'un fichier, sans vérifier qu'elle
+    /// est prise en charge
+    ///
+    /// Un fichier sans ligne de version est de version 1.
+    ///
+    /// # Exemple
+    ///
+    /// ```
+    /// use lru_cache::storage::file::FileStorage;
+    /// use lru_cache::storage::format::FILE_FORMAT_VERSION;
+    ///
+    /// FileStorage::save("version_doc.txt", 2, &[("key", 1)]).unwrap();
+    /// assert_eq!(FileStorage::detect_version("version_doc.txt").unwrap(), FILE_FORMAT_VERSION);
+    ///
+    /// std::fs::write("version_doc.txt", "2\nkey;1\n").unwrap();
+    /// assert_eq!(FileStorage::detect_version("version_doc.txt").unwrap(), 1);
+    ///
+    /// std::fs::remove_file("version_doc.txt").unwrap();
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Retourne [`StorageError::Io`] si le fichier ne peut pas être lu, et
+    /// [`StorageError::InvalidCapacity`] si la première ligne commence par
+    /// `#` sans être une ligne de version.
+    pub fn detect_version(path: impl AsRef<Path>) -> Result<u32, StorageError> {
+        Ok(read_version(&mut *open_reader(path.as_ref())?)?.unwrap_or(1))
+    }
+
+    /// Lit l'en-tête d'un fichier sans charger les entrées
+    ///
+    /// # Exemple
+    ///
+    /// ```
+    /// use lru_cache::storage::file::{FileStorage, SaveOptions};
+    /// use std::time::{Duration, UNIX_EPOCH};
+    ///
+    /// let saved_at = UNIX_EPOCH + Duration::from_secs(1_700_000_000);
+    /// let options = SaveOptions { saved_at: Some(saved_at), ..SaveOptions::default() };
+    /// FileStorage::save_with("metadata_doc.txt", 2, &[("key", 1)], options).unwrap();
+    ///
+    /// let metadata = FileStorage::metadata("metadata_doc.txt").unwrap();
+    /// assert_eq!(metadata.capacity, 2);
+    /// assert_eq!(metadata.saved_at, Some(saved_at));
+    ///
+    /// std::fs::remove_file("metadata_doc.txt").unwrap();
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Retourne [`StorageError::Io`] si le fichier ne peut pas être lu, et
+    /// [`StorageError::MissingCapacity`] ou
+    /// [`StorageError::InvalidCapacity`] si la première ligne n'est pas un
+    /// en-tête valide.
+    pub fn metadata(path: impl AsRef<Path>) -> Result<SnapshotMetadata, StorageError> {
+        let mut reader = open_reader(path.as_ref())?;
+        skip_version(&mut reader)?;
+        let mut line = String::new();
+        reader.read_line(&mut line)?;
+        let line = line.strip_suffix(LINE_END).unwrap_or(&line);
+        let line = line.strip_suffix('\r').unwrap_or(line);
+        if line.is_empty() {
+            return Err(StorageError::MissingCapacity);
+        }
+        let (capacity, saved_at) = parse_full_header(line).ok_or_else(|| StorageError::InvalidCapacity(line.to_string()))?;
+        Ok(SnapshotMetadata {
+            capacity,
+            saved_at: saved_at.map(|seconds| UNIX_EPOCH + Duration::from_secs(seconds)),
+        })
+    }
+
+    /// Vérifie l'intégrité d'un fichier sans construire de cache
+    ///
+    /// Le fichier est lu ligne par ligne : la mémoire utilisée ne dépend que
+    /// du nombre de clés distinctes (un hash de 64 bits par clé, ou la clé
+    /// elle-même avec [`VerifyOptions::exact_duplicates`]). Avec les hashes,
+    /// une collision peut faire signaler à tort un doublon, avec une
+    /// probabilité négligeable (de l'ordre de n² / 2⁶⁵ pour n clés).
+    ///
+    /// Les lignes d'un fichier v4 sont rejouées : une clé écrite à nouveau
+    /// n'est pas un doublon, et `entries` compte les clés restantes après
+    /// les suppressions.
+    ///
+    /// # Exemple
+    ///
+    /// ```
+    /// use lru_cache::storage::file::{FileStorage, VerifyOptions};
+    /// use std::fs::write;
+    ///
+    /// write("verify_doc.txt", "2\nkey1;42\nkey1;84\n").unwrap();
+    /// let report = FileStorage::verify::<String, i32>("verify_doc.txt", VerifyOptions::default()).unwrap();
+    /// assert_eq!(report.entries, 1);
+    /// assert_eq!(report.duplicate_keys, 1);
+    /// assert!(!report.is_ok());
+    ///
+    /// std::fs::remove_file("verify_doc.txt").unwrap();
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Retourne une erreur si le fichier ne peut pas être ouvert ou lu ; les
+    /// problèmes de format sont comptés dans le rapport.
+    pub fn verify<K: FromStr, V: FromStr>(path: impl AsRef<Path>, options: VerifyOptions) -> io::Result<VerifyReport> {
+        let mut reader = ChecksumReader::new(open_reader(path.as_ref())?);
+        let version = skip_version(&mut reader)?;
+        let mut line = String::new();
+        let mut report = VerifyReport {
+            capacity: match reader.read_line(&mut line)? {
+                0 => None,
+                _ => parse_header(line.strip_suffix(LINE_END).unwrap_or(&line)),
+            },
+            ..VerifyReport::default()
+        };
+
+        let replays = has_increments(version);
+        let mut hashes = HashSet::new();
+        let mut keys = HashSet::new();
+        loop {
+            reader.mark();
+            line.clear();
+            if reader.read_line(&mut line)? == 0 {
+                break;
+            }
+            let line = line.strip_suffix(LINE_END).unwrap_or(&line);
+            if let Some(expected) = reader.checksum_line(version, line) {
+                reader.end_segment(expected);
+                continue;
+            }
+            if let Some(key_str) = parse_removal(line).filter(|_| replays) {
+                if K::from_str(&key_str).is_err() {
+                    report.invalid_keys += 1;
+                    continue;
+                }
+                let removed = if options.exact_duplicates {
+                    keys.remove(key_str.as_ref())
+                } else {
+                    hashes.remove(&key_hash(&key_str))
+                };
+                report.entries -= usize::from(removed);
+                continue;
+            }
+            let Some((key_str, value_str)) = parse_entry(line) else {
+                report.malformed_lines += 1;
+                continue;
+            };
+            if K::from_str(&key_str).is_err() {
+                report.invalid_keys += 1;
+                continue;
+            }
+            if V::from_str(&value_str).is_err() {
+                report.invalid_values += 1;
+                continue;
+            }
+            let first_seen = if options.exact_duplicates {
+                keys.insert(key_str.to_string())
+            } else {
+                hashes.insert(key_hash(&key_str))
+            };
+            if first_seen {
+                report.entries += 1;
+            } else if !replays {
+                report.duplicate_keys += 1;
+            }
+        }
+        report.checksum_ok = match reader.check(version) {
+            Ok(verified) => verified.then_some(true),
+            Err(_) => Some(false),
+        };
+        Ok(report)
+    }
+}
+
+/// Écrit `entries` dans `path` de façon atomique, sans les copier
+pub(crate) fn save_entries<K: Display, V: Display>(
+    path: &Path,
+    capacity: usize,
+    entries: impl IntoIterator<Item = (K, V)>,
+    options: SaveOptions,
+) -> io::Result<SaveReport> {
+    let report = write_atomic(path, |writer| write_entries(writer, capacity, entries, options))?;
+    trace_event!(
+        debug,
+        path = %path.display(),
+        capacity,
+        entries = report.entries,
+        oversized = report.oversized.len(),
+        "cache file saved"
+    );
+    Ok(report)
+}
+
+/// Écrit l'en-tête puis les entrées qui respectent les limites de
+/// `options`, chacune directement dans `writer` au fil de l'itération
+pub(crate) fn write_entries<W: Write, K: Display, V: Display>(
+    writer: &mut W,
+    capacity: usize,
+    entries: impl IntoIterator<Item = (K, V)>,
+    options: SaveOptions,
+) -> io::Result<SaveReport> {
+    let saved_at = options
+        .saved_at
+        .and_then(|time| time.duration_since(UNIX_EPOCH).ok())
+        .map(|elapsed| elapsed.as_secs());
+    let mut report = SaveReport::default();
+    let mut writer = ChecksumWriter::new(writer);
+    write_header(&mut writer, capacity, saved_at)?;
+    for (position, (key, value)) in entries.into_iter().enumerate() {
+        let (key, value) = (Escaped(key), Escaped(value));
+        let field = if !fits(&key, options.max_key_len) {
+            OversizedField::Key
+        } else if !fits(&value, options.max_value_len) {
+            OversizedField::Value
+        } else {
+            write_entry(&mut writer, key, value)?;
+            report.entries += 1;
+            continue;
+        };
+        report.oversized.push(OversizedEntry { position, field });
+    }
+    writer.finish()?;
+    Ok(report)
+}
+
+/// Premiers octets d'un fichier gzip
+const GZIP_MAGIC: [u8; 2] = [0x1f, 0x8b];
+
+/// Ouvre `path` en lecture avec [`decode_reader`]
+fn open_reader(path: &Path) -> io::Result<Box<dyn BufRead>> {
+    decode_reader(BufReader::new(File::open(path)?))
+}
+
+/// Retourne `reader`, en décompressant à la volée un contenu gzip
+///
+/// Sans la feature `compression`, un contenu gzip est refusé avec une
+/// erreur explicite plutôt que lu comme du texte.
+fn decode_reader<'a, R: BufRead + 'a>(mut reader: R) -> io::Result<Box<dyn BufRead + 'a>> {
+    if !reader.fill_buf()?.starts_with(&GZIP_MAGIC) {
+        return Ok(Box::new(reader));
+    }
+    #[cfg(feature = "compression")]
+    return Ok(Box::new(BufReader::new(MultiGzDecoder::new(reader))));
+    #[cfg(not(feature = "compression"))]
+    Err(io::Error::new(
+        io::ErrorKind::InvalidData,
+        "fichier compressé en gzip : la feature `compression` est nécessaire",
+    ))
+}
+
+/// Longueur maximale lue pour une ligne de version
+const MAX_VERSION_LINE: u64 = 64;
+
+/// Lit la ligne de version si le fichier commence par une, sans vérifier
+/// que la version est prise en charge ; `None` pour un fichier v1
+///
+/// Un en-tête de capacité commence par un chiffre : un premier octet `#`
+/// suffit à reconnaître la ligne de version.
+fn read_version(reader: &mut dyn BufRead) -> Result<Option<u32>, StorageError> {
+    if reader.fill_buf()?.first() != Some(&b'#') {
+        return Ok(None);
+    }
+    let mut buffer = Vec::new();
+    reader.take(MAX_VERSION_LINE).read_until(LINE_END as u8, &mut buffer)?;
+    let line = String::from_utf8_lossy(&buffer);
+    let line = line.strip_suffix(LINE_END).unwrap_or(&line);
+    let line = line.strip_suffix('\r').unwrap_or(line);
+    parse_version_line(line)
+        .map(Some)
+        .ok_or_else(|| StorageError::InvalidCapacity(line.to_string()))
+}
+
+/// Refuse une version que cette bibliothèque ne sait pas lire
+fn check_version(version: u32) -> Result<(), StorageError> {
+    if version == 0 || version > FILE_FORMAT_VERSION {
+        return Err(StorageError::UnsupportedVersion(version));
+    }
+    Ok(())
+}
+
+/// Passe la ligne de version après avoir vérifié la version, et retourne
+/// la version lue (`None` pour un fichier v1, sans ligne de version)
+fn skip_version(reader: &mut dyn BufRead) -> Result<Option<u32>, StorageError> {
+    let version = read_version(reader)?;
+    if let Some(version) = version {
+        check_version(version)?;
+    }
+    Ok(version)
+}
+
+/// Lecteur qui calcule le CRC-32 de chaque segment lu, hors ligne de
+/// somme de contrôle
+struct ChecksumReader<R> {
+    inner: R,
+    /// CRC des octets lus depuis la fin du segment précédent
+    crc: u32,
+    /// CRC au début de la ligne en cours de lecture
+    mark: u32,
+    /// Octets lus depuis la fin du segment précédent
+    pending: u64,
+    /// Nombre de lignes de somme de contrôle lues
+    segments: usize,
+    /// Première somme fausse, avec la somme calculée
+    mismatch: Option<(u32, u32)>,
+}
+
+impl<R: BufRead> ChecksumReader<R> {
+    fn new(inner: R) -> Self {
+        ChecksumReader {
+            inner,
+            crc: 0,
+            mark: 0,
+            pending: 0,
+            segments: 0,
+            mismatch: None,
+        }
+    }
+
+    /// Retient le CRC courant, au début d'une ligne
+    fn mark(&mut self) {
+        self.mark = self.crc;
+    }
+
+    /// Retourne la somme de `line` si c'est une ligne de somme de contrôle
+    /// attendue dans un fichier de `version` : la première, ou celle d'un
+    /// segment incrémental
+    fn checksum_line(&self, version: Option<u32>, line: &str) -> Option<u32> {
+        if !has_checksum(version) || (self.segments > 0 && !has_increments(version)) {
+            return None;
+        }
+        parse_checksum_line(line.strip_suffix('\r').unwrap_or(line))
+    }
+
+    /// Compare la somme lue au CRC du segment avant [`ChecksumReader::mark`]
+    /// et commence un nouveau segment ; la première différence est retenue
+    /// pour [`ChecksumReader::check`]
+    fn end_segment(&mut self, expected: u32) {
+        if expected != self.mark && self.mismatch.is_none() {
+            self.mismatch = Some((expected, self.mark));
+        }
+        self.crc = 0;
+        self.pending = 0;
+        self.segments += 1;
+    }
+
+    /// Vérifie que chaque segment lu avait une somme de contrôle correcte,
+    /// et indique si la vérification a eu lieu
+    fn check(&self, version: Option<u32>) -> Result<bool, StorageError> {
+        if !has_checksum(version) {
+            return Ok(false);
+        }
+        if let Some((expected, found)) = self.mismatch {
+            return Err(StorageError::ChecksumMismatch { expected, found });
+        }
+        if self.segments == 0 || self.pending > 0 {
+            return Err(StorageError::MissingChecksum);
+        }
+        Ok(true)
+    }
+}
+
+impl<R: BufRead> Read for ChecksumReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let available = self.fill_buf()?;
+        let read = available.len().min(buf.len());
+        buf[..read].copy_from_slice(&available[..read]);
+        self.consume(read);
+        Ok(read)
+    }
+}
+
+impl<R: BufRead> BufRead for ChecksumReader<R> {
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        self.inner.fill_buf()
+    }
+
+    fn consume(&mut self, amt: usize) {
+        // Les octets consommés sont ceux du dernier `fill_buf`, que le
+        // lecteur rend à nouveau sans lire
+        if let Ok(buffer) = self.inner.fill_buf() {
+            let consumed = &buffer[..amt.min(buffer.len())];
+            self.crc = crc32(self.crc, consumed);
+            self.pending += consumed.len() as u64;
+        }
+        self.inner.consume(amt);
+    }
+}
+
+/// Indique si les fichiers de `version` se terminent par une somme de
+/// contrôle
+fn has_checksum(version: Option<u32>) -> bool {
+    version.is_some_and(|version| version >= CHECKSUM_VERSION)
+}
+
+/// Indique si les lignes des fichiers de `version` sont rejouées, avec des
+/// segments incrémentaux et des lignes de suppression
+fn has_increments(version: Option<u32>) -> bool {
+    version.is_some_and(|version| version >= INCREMENTAL_VERSION)
+}
+
+/// Hash de 64 bits du texte d'une clé
+fn key_hash(key: &str) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    key.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// Entrées d'un fichier rejouées dans l'ordre de ses lignes
+///
+/// Quand les lignes sont rejouées (fichiers v4), une entrée remplace
+/// l'entrée précédente de même clé et une suppression la retire ; les clés
+/// sont comparées par leur texte dans le fichier, et non par un hash qui
+/// confondrait deux clés en collision.
+struct Replay<K, V> {
+    entries: Vec<Option<(K, V)>>,
+    /// Position dans `entries` de la dernière entrée de chaque texte de clé
+    positions: HashMap<String, usize>,
+}
+
+impl<K, V> Replay<K, V> {
+    fn new() -> Self {
+        Replay {
+            entries: Vec::new(),
+            positions: HashMap::new(),
+        }
+    }
+
+    /// Applique `record`, dont la clé s'écrit `key` dans le fichier
+    fn apply(&mut self, record: Record<K, V>, key: &str, replays: bool) {
+        self.apply_keyed(record, replays.then(|| key.to_string()));
+    }
+
+    /// Applique `record`, dont la clé s'écrit `text` si les lignes sont
+    /// rejouées
+    fn apply_keyed(&mut self, record: Record<K, V>, text: Option<String>) {
+        if let Some(previous) = text.as_ref().and_then(|text| self.positions.remove(text)) {
+            self.entries[previous] = None;
+        }
+        if let Record::Put(key, value) = record {
+            if let Some(text) = text {
+                self.positions.insert(text, self.entries.len());
+            }
+            self.entries.push(Some((key, value)));
+        }
+    }
+
+    /// Retourne les entrées restantes, dans l'ordre de leur dernière
+    /// écriture
+    fn into_vec(self) -> Vec<(K, V)> {
+        self.entries.into_iter().flatten().collect()
+    }
+}
+
+/// Parcourt les lignes complètes de `path` en passant chaque ligne
+/// d'entrée à `accept`, qui retourne `None` pour une ligne illisible, avec
+/// l'indication que les lignes du fichier sont rejouées
+fn salvage(path: &Path, mut accept: impl FnMut(&str, bool) -> Option<()>) -> io::Result<SalvageReport> {
+    let mut reader = open_reader(path)?;
+    let mut report = SalvageReport::default();
+    let mut buffer = Vec::new();
+    let mut header = true;
+    let mut version = None;
+    let mut number = 0;
+    loop {
+        buffer.clear();
+        let read = reader.read_until(LINE_END as u8, &mut buffer)?;
+        if read == 0 {
+            break;
+        }
+        if buffer.last() != Some(&(LINE_END as u8)) {
+            report.truncated = true;
+            break;
+        }
+        report.clean_len += read as u64;
+        number += 1;
+        let line = std::str::from_utf8(&buffer[..read - 1]).ok();
+        if header {
+            if let Some(line_version) = line.filter(|_| number == 1).and_then(parse_version_line) {
+                check_version(line_version)?;
+                version = Some(line_version);
+                continue;
+            }
+            header = false;
+            report.capacity = line.and_then(parse_header);
+            continue;
+        }
+        if has_checksum(version) && line.and_then(parse_checksum_line).is_some() {
+            continue;
+        }
+        match line.and_then(|line| accept(line, has_increments(version))) {
+            Some(()) => report.entries += 1,
+            None => {
+                report.skipped_lines += 1;
+                report.skipped_line_numbers.push(number);
+            }
+        }
+    }
+    Ok(report)
+}
+
+/// Lit `path` avec [`read_entries`]
+fn load_limited<K: FromStr, V: FromStr>(
+    path: &Path,
+    options: LoadOptions,
+    strict_entries: bool,
+    f: impl FnMut(Record<K, V>, &str, bool),
+    warn: impl FnMut(OversizedEntry),
+) -> Result<(usize, bool), StorageError> {
+    let (capacity, verified) = read_entries(open_reader(path)?, options, strict_entries, f, warn)?;
+    trace_event!(debug, path = %path.display(), capacity, verified, "cache file loaded");
+    Ok((capacity, verified))
+}
+
+/// Lit un contenu au format texte en passant les entrées et suppressions
+/// valides à `f` et les entrées trop longues à `warn`, et retourne la
+/// capacité de l'en-tête et si la somme de contrôle a été vérifiée
+///
+/// `f` reçoit aussi le texte de la clé et l'indication que les lignes du
+/// fichier sont rejouées (voir [`Replay`]). Avec `strict_entries`, une
+/// ligne illisible fait échouer la lecture au lieu d'être ignorée.
+fn read_entries<K: FromStr, V: FromStr>(
+    reader: impl BufRead,
+    options: LoadOptions,
+    strict_entries: bool,
+    mut f: impl FnMut(Record<K, V>, &str, bool),
+    mut warn: impl FnMut(OversizedEntry),
+) -> Result<(usize, bool), StorageError> {
+    let mut reader = ChecksumReader::new(reader);
+    let version = skip_version(&mut reader)?;
+    let replays = has_increments(version);
+    let mut position = usize::from(version.is_some()) + 1;
+    let limit = line_limit(&options);
+    let mut buffer = Vec::new();
+    let capacity = read_header(&mut reader, &mut buffer, limit)?;
+
+    loop {
+        reader.mark();
+        let Some(complete) = read_bounded_line(&mut reader, &mut buffer, limit)? else {
+            break;
+        };
+        position += 1;
+        let line = utf8(&buffer, complete)?;
+        if let Some(expected) = reader.checksum_line(version, line).filter(|_| complete) {
+            reader.end_segment(expected);
+            continue;
+        }
+        match parse_line(line, complete, replays, &options) {
+            ParsedLine::Record(record, key) => f(record, &key, replays),
+            ParsedLine::Oversized(field) => {
+                let entry = OversizedEntry { position, field };
+                if options.strict {
+                    return Err(StorageError::Oversized(entry));
+                }
+                warn(entry);
+            }
+            ParsedLine::Invalid if strict_entries => {
+                return Err(StorageError::InvalidEntry {
+                    line: position,
+                    content: line.to_string(),
+                })
+            }
+            ParsedLine::Invalid => {}
+        }
+    }
+    let verified = reader.check(version)?;
+    Ok((capacity, verified))
+}
+
+/// Longueur visée des blocs lus par [`FileStorage::load_parallel`] ; un
+/// bloc se termine toujours à une fin de ligne
+#[cfg(feature = "rayon")]
+const PARALLEL_CHUNK_LEN: usize = 1 << 20;
+
+/// CRC-32 d'une suite d'octets, avec sa longueur
+#[cfg(feature = "rayon")]
+#[derive(Default)]
+struct Span {
+    crc: u32,
+    len: u64,
+}
+
+/// Ligne d'un bloc utile à l'assemblage
+#[cfg(feature = "rayon")]
+enum ChunkLine<K, V> {
+    /// Entrée ou suppression valide, avec le texte de sa clé si les lignes
+    /// sont rejouées
+    Record(Record<K, V>, Option<String>),
+    /// Ligne qui a la forme d'une somme de contrôle : seul l'assemblage sait
+    /// si elle en est une, selon les segments qui la précèdent
+    Checksum {
+        text: String,
+        /// Octets du bloc depuis la ligne de somme précédente ou le début du
+        /// bloc
+        before: Span,
+        own: Span,
+        /// Lecture de la ligne si ce n'est pas une somme de contrôle
+        record: Option<(Record<K, V>, Option<String>)>,
+    },
+    /// Ligne qui n'est pas de l'UTF-8 : la lecture du fichier échoue
+    Invalid(io::Error),
+}
+
+/// Résultat de la lecture d'un bloc par [`parse_chunk`]
+#[cfg(feature = "rayon")]
+struct Chunk<K, V> {
+    lines: Vec<ChunkLine<K, V>>,
+    /// Octets du bloc après sa dernière ligne de somme de contrôle
+    tail: Span,
+}
+
+/// Découpe `bytes` en blocs d'au moins `len` octets qui se terminent à une
+/// fin de ligne, sauf le dernier
+#[cfg(feature = "rayon")]
+fn split_chunks(mut bytes: &[u8], len: usize) -> Vec<&[u8]> {
+    let mut chunks = Vec::new();
+    while bytes.len() > len {
+        let end = bytes[len..]
+            .iter()
+            .position(|&byte| byte == LINE_END as u8)
+            .map_or(bytes.len(), |end| len + end + 1);
+        let (chunk, rest) = bytes.split_at(end);
+        chunks.push(chunk);
+        bytes = rest;
+    }
+    if !bytes.is_empty() {
+        chunks.push(bytes);
+    }
+    chunks
+}
+
+/// Lit les lignes d'un bloc comme [`read_entries`], sans connaître les
+/// lignes de somme de contrôle des blocs précédents
+#[cfg(feature = "rayon")]
+fn parse_chunk<K: FromStr, V: FromStr>(
+    chunk: &[u8],
+    version: Option<u32>,
+    options: &LoadOptions,
+    limit: usize,
+) -> Chunk<K, V> {
+    let replays = has_increments(version);
+    let checksums = has_checksum(version);
+    let mut lines = Vec::new();
+    let mut run = Span::default();
+    for raw in chunk.split_inclusive(|&byte| byte == LINE_END as u8) {
+        // Même découpe que `read_bounded_line`
+        let content = raw.strip_suffix(&[LINE_END as u8]).unwrap_or(raw);
+        let (kept, complete) = match content.len() > limit {
+            true => (&content[..limit], false),
+            false => (content.strip_suffix(b"\r").unwrap_or(content), true),
+        };
+        let line = match utf8(kept, complete) {
+            Ok(line) => line,
+            Err(error) => {
+                lines.push(ChunkLine::Invalid(error));
+                break;
+            }
+        };
+        let record = match parse_line(line, complete, replays, options) {
+            ParsedLine::Record(record, key) => Some((record, replays.then(|| key.into_owned()))),
+            ParsedLine::Oversized(_) | ParsedLine::Invalid => None,
+        };
+        if checksums && complete && parse_checksum_line(line.strip_suffix('\r').unwrap_or(line)).is_some() {
+            lines.push(ChunkLine::Checksum {
+                text: line.to_string(),
+                before: std::mem::take(&mut run),
+                own: Span {
+                    crc: crc32(0, raw),
+                    len: raw.len() as u64,
+                },
+                record,
+            });
+            continue;
+        }
+        if checksums {
+            run.crc = crc32(run.crc, raw);
+            run.len += raw.len() as u64;
+        }
+        if let Some((record, text)) = record {
+            lines.push(ChunkLine::Record(record, text));
+        }
+    }
+    Chunk { lines, tail: run }
+}
+
+/// Longueur gardée d'une ligne lue avec `options`
+fn line_limit(options: &LoadOptions) -> usize {
+    // La ligne de somme de contrôle doit être lue en entier, quelles que
+    // soient les limites
+    options
+        .max_key_len
+        .saturating_add(options.max_value_len)
+        .saturating_add(SEPARATOR.len_utf8())
+        .max(CHECKSUM_PREFIX.len() + 8)
+}
+
+/// Lit la ligne d'en-tête et retourne la capacité
+fn read_header(reader: &mut impl BufRead, buffer: &mut Vec<u8>, limit: usize) -> Result<usize, StorageError> {
+    let Some(complete) = read_bounded_line(reader, buffer, limit)? else {
+        return Err(StorageError::MissingCapacity);
+    };
+    let header = utf8(buffer, complete)?;
+    if header.is_empty() {
+        return Err(StorageError::MissingCapacity);
+    }
+    parse_header(header)
+        .filter(|_| complete)
+        .ok_or_else(|| StorageError::InvalidCapacity(header.to_string()))
+}
+
+/// Ligne d'entrée ou de suppression lue par [`parse_line`]
+enum ParsedLine<'a, K, V> {
+    /// Entrée ou suppression valide, avec le texte de sa clé
+    Record(Record<K, V>, Cow<'a, str>),
+    /// Clé ou valeur au-delà des limites de lecture
+    Oversized(OversizedField),
+    /// Ligne illisible
+    Invalid,
+}
+
+/// Lit une ligne qui n'est pas une somme de contrôle ; `complete` est faux
+/// si la ligne a été coupée à la limite de lecture
+fn parse_line<'a, K: FromStr, V: FromStr>(
+    line: &'a str,
+    complete: bool,
+    replays: bool,
+    options: &LoadOptions,
+) -> ParsedLine<'a, K, V> {
+    if let Some(key) = parse_removal(line).filter(|_| replays && complete) {
+        if key.len() > options.max_key_len {
+            return ParsedLine::Oversized(OversizedField::Key);
+        }
+        return match K::from_str(&key) {
+            Ok(parsed) => ParsedLine::Record(Record::Remove(parsed), key),
+            Err(_) => ParsedLine::Invalid,
+        };
+    }
+    let field = match split_escaped_entry(line).map(|(key, _)| key.len()) {
+        Some(key_len) if key_len > options.max_key_len => Some(OversizedField::Key),
+        None if !complete => Some(OversizedField::Key),
+        Some(key_len) if !complete || line.len() - key_len - SEPARATOR.len_utf8() > options.max_value_len => {
+            Some(OversizedField::Value)
+        }
+        _ => None,
+    };
+    if let Some(field) = field {
+        return ParsedLine::Oversized(field);
+    }
+    let entry = parse_entry(line).and_then(|(key, value)| {
+        let record = Record::Put(K::from_str(&key).ok()?, V::from_str(&value).ok()?);
+        Some((record, key))
+    });
+    match entry {
+        Some((record, key)) => ParsedLine::Record(record, key),
+        None => ParsedLine::Invalid,
+    }
+}
+
+/// Lit une ligne dans `buffer` sans sa fin de ligne, en ne conservant que ses
+/// `limit` premiers octets
+///
+/// Retourne `None` en fin de fichier, sinon indique si la ligne tenait dans
+/// la limite ; le reste d'une ligne trop longue est consommé sans être gardé.
+fn read_bounded_line<R: BufRead>(reader: &mut R, buffer: &mut Vec<u8>, limit: usize) -> io::Result<Option<bool>> {
+    buffer.clear();
+    let mut read_any = false;
+    let mut complete = true;
+    loop {
+        let available = reader.fill_buf()?;
+        if available.is_empty() {
+            break;
+        }
+        read_any = true;
+        let (chunk, used, done) = match available.iter().position(|&b| b == LINE_END as u8) {
+            Some(end) => (&available[..end], end + 1, true),
+            None => (available, available.len(), false),
+        };
+        let room = limit.saturating_sub(buffer.len());
+        if chunk.len() > room {
+            complete = false;
+        }
+        buffer.extend_from_slice(&chunk[..chunk.len().min(room)]);
+        reader.consume(used);
+        if done {
+            break;
+        }
+    }
+    if !read_any {
+        return Ok(None);
+    }
+    if complete && buffer.last() == Some(&b'\r') {
+        buffer.pop();
+    }
+    Ok(Some(complete))
+}
+
+/// Décode une ligne ; une ligne coupée à la limite (`complete` faux) peut
+/// finir au milieu d'un caractère, qui est alors retiré
+fn utf8(bytes: &[u8], complete: bool) -> io::Result<&str> {
+    match std::str::from_utf8(bytes) {
+        Ok(line) => Ok(line),
+        Err(error) if !complete && error.error_len().is_none() => {
+            Ok(std::str::from_utf8(&bytes[..error.valid_up_to()]).expect("préfixe UTF-8 valide"))
+        }
+        Err(error) => Err(io::Error::new(io::ErrorKind::InvalidData, error)),
+    }
+}
+
+/// Indique si la forme `Display` de `item` tient en `limit` octets, sans la
+/// conserver ni la mesurer au-delà de la limite
+fn fits(item: impl Display, limit: usize) -> bool {
+    struct Counter {
+        len: usize,
+        limit: usize,
+    }
+
+    impl fmt::Write for Counter {
+        fn write_str(&mut self, s: &str) -> fmt::Result {
+            self.len += s.len();
+            if self.len > self.limit {
+                return Err(fmt::Error);
+            }
+            Ok(())
+        }
+    }
+
+    write!(Counter { len: 0, limit }, "{}", item).is_ok()
+}
+
+fn invalid_input(message: impl Into<String>) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidInput, message.into())
+}
+
+fn invalid_data(line: usize, detail: impl Display) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, format!("ligne {} : {}", line, detail))
+}
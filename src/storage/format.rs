@@ -0,0 +1,386 @@
+//! Format des fichiers de sauvegarde, octet par octet
+//!
+//! Tous les octets écrits par [`FileStorage`](super::file::FileStorage)
+//! proviennent de ce module ; des fichiers de référence
+//! (`tests/golden/`) figent le résultat. Toute modification de ce qui suit
+//! doit incrémenter [`FORMAT_SPEC_VERSION`].
+//!
+//! # Spécification (version 7)
+//!
+//! - Le fichier est en UTF-8, sans BOM. Chaque ligne se termine par `\n`
+//!   ([`LINE_END`]), y compris la dernière ; aucun `\r` n'est émis.
+//! - La première ligne est la ligne de version : [`VERSION_PREFIX`] suivi
+//!   du numéro de version du fichier en décimal ([`FILE_FORMAT_VERSION`],
+//!   soit `#lru_cache v4`). Un fichier sans cette ligne est un fichier v1,
+//!   écrit selon les spécifications 1 à 4, et se relit comme avant ; la
+//!   version 2 n'ajoute que la ligne de version, la version 3 la somme de
+//!   contrôle et la version 4 les segments incrémentaux décrits plus bas.
+//!   Un fichier d'une version supérieure à [`FILE_FORMAT_VERSION`] est
+//!   refusé plutôt que mal interprété.
+//! - La ligne suivante (l'en-tête) contient la capacité du cache, en
+//!   décimal sans signe ni zéro initial. Elle peut être suivie de
+//!   [`SEPARATOR`] et de l'heure de la sauvegarde, en secondes entières
+//!   depuis le 1er janvier 1970 UTC, dans le même format. Un en-tête de la
+//!   version 1 (capacité seule) reste valide.
+//! - Chaque ligne suivante contient une entrée : la clé, le séparateur `;`
+//!   ([`SEPARATOR`]), puis la valeur. Les entrées sont écrites de la
+//!   prochaine évincée à la plus récente.
+//! - Avec `save`, clé et valeur sont écrites telles que leur `Display` les
+//!   produit, les caractères de [`ESCAPES`] étant précédés de `\`
+//!   ([`ESCAPE`]). À la lecture, la ligne est coupée au premier `;` non
+//!   échappé puis les séquences de [`ESCAPES`] sont décodées ; toute autre
+//!   séquence commençant par `\` est gardée telle quelle, de sorte qu'un
+//!   fichier des versions 1 et 2 (sans échappement) se relit à l'identique
+//!   tant qu'il ne contient aucune de ces séquences.
+//! - Avec `save_encoded`, clé et valeur sont produites par
+//!   [`TextCodec`](super::codec::TextCodec), qui échappe de la même façon :
+//!   à la lecture, la ligne est coupée au premier `;` non échappé et une
+//!   séquence d'échappement inconnue est une erreur.
+//! - À partir de la version 3 ([`CHECKSUM_VERSION`]), la dernière ligne
+//!   est [`CHECKSUM_PREFIX`] suivi du CRC-32 (IEEE, celui de gzip) de tous
+//!   les octets qui la précèdent, ligne de version comprise, en 8 chiffres
+//!   hexadécimaux minuscules. Une entrée contient toujours un séparateur :
+//!   cette ligne ne peut pas être confondue avec une entrée. Un fichier v3
+//!   ou plus sans elle a été tronqué. Les fichiers v1 et v2 n'ont pas de
+//!   somme de contrôle et se chargent sans vérification.
+//! - À partir de la version 4 ([`INCREMENTAL_VERSION`]), la ligne de somme
+//!   de contrôle peut être suivie de segments incrémentaux, ajoutés en fin
+//!   de fichier sans réécrire ce qui précède. Un segment contient des
+//!   lignes d'entrée et des lignes de suppression ([`REMOVE_PREFIX`] suivi
+//!   de la clé, échappée comme une clé d'entrée) et se termine par sa propre
+//!   ligne de somme de contrôle, calculée sur les octets du segment. Une
+//!   ligne de suppression n'a pas de séparateur non échappé : elle ne peut
+//!   pas être confondue avec une entrée. Les lignes d'un fichier v4 sont
+//!   rejouées dans l'ordre : une entrée remplace l'entrée précédente de même
+//!   clé et une suppression la retire.
+//! - Avec `save_compressed` (feature `compression`), le fichier entier est
+//!   un flux gzip dont le contenu décompressé suit les règles ci-dessus. Le
+//!   chargement le reconnaît à ses deux premiers octets, `1f 8b`, qui ne
+//!   peuvent pas commencer un fichier texte valide.
+
+use std::borrow::Cow;
+use std::fmt::{self, Display, Write as _};
+use std::io::{self, Write};
+
+/// Version de la spécification ci-dessus
+pub const FORMAT_SPEC_VERSION: u32 = 7;
+
+/// Version des fichiers écrits, indiquée par la ligne de version
+pub const FILE_FORMAT_VERSION: u32 = 4;
+
+/// Début de la ligne de version, suivi du numéro de version
+pub const VERSION_PREFIX: &str = "#lru_cache v";
+
+/// Première version dont les fichiers se terminent par une somme de
+/// contrôle
+pub const CHECKSUM_VERSION: u32 = 3;
+
+/// Début de la ligne de somme de contrôle, suivi du CRC-32 en hexadécimal
+pub const CHECKSUM_PREFIX: &str = "#crc32 ";
+
+/// Première version dont les fichiers peuvent recevoir des segments
+/// incrémentaux
+pub const INCREMENTAL_VERSION: u32 = 4;
+
+/// Début d'une ligne de suppression, suivi de la clé supprimée
+pub const REMOVE_PREFIX: &str = "#del ";
+
+/// Séparateur entre la clé et la valeur
+pub const SEPARATOR: char = ';';
+
+/// Fin de ligne
+pub const LINE_END: char = '\n';
+
+/// Caractère d'échappement
+pub const ESCAPE: char = '\\';
+
+/// Caractères échappés et leur forme écrite après [`ESCAPE`]
+pub const ESCAPES: [(char, char); 4] = [('\\', '\\'), (';', ';'), ('\n', 'n'), ('\r', 'r')];
+
+/// Écrit la ligne de version puis l'en-tête, avec l'heure de la sauvegarde
+/// en secondes si elle est connue
+pub(crate) fn write_header<W: Write>(writer: &mut W, capacity: usize, saved_at: Option<u64>) -> io::Result<()> {
+    write!(writer, "{}{}{}", VERSION_PREFIX, FILE_FORMAT_VERSION, LINE_END)?;
+    match saved_at {
+        Some(seconds) => write!(writer, "{}{}{}{}", capacity, SEPARATOR, seconds, LINE_END),
+        None => write!(writer, "{}{}", capacity, LINE_END),
+    }
+}
+
+/// Écrit une ligne d'entrée
+pub(crate) fn write_entry<W: Write>(writer: &mut W, key: impl Display, value: impl Display) -> io::Result<()> {
+    write!(writer, "{}{}{}{}", key, SEPARATOR, value, LINE_END)
+}
+
+/// Écrivain qui calcule le CRC-32 des octets écrits, pour terminer le
+/// fichier par la ligne de somme de contrôle
+pub(crate) struct ChecksumWriter<W> {
+    inner: W,
+    crc: u32,
+}
+
+impl<W: Write> ChecksumWriter<W> {
+    pub(crate) fn new(inner: W) -> Self {
+        ChecksumWriter { inner, crc: 0 }
+    }
+
+    /// Écrit la ligne de somme de contrôle des octets écrits jusqu'ici
+    pub(crate) fn finish(mut self) -> io::Result<()> {
+        write!(self.inner, "{}{:08x}{}", CHECKSUM_PREFIX, self.crc, LINE_END)
+    }
+}
+
+impl<W: Write> Write for ChecksumWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let written = self.inner.write(buf)?;
+        self.crc = crc32(self.crc, &buf[..written]);
+        Ok(written)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+/// Table du CRC-32 IEEE (polynôme inversé `0xedb88320`)
+const CRC32_TABLE: [u32; 256] = {
+    let mut table = [0; 256];
+    let mut i = 0;
+    while i < 256 {
+        let mut crc = i as u32;
+        let mut bit = 0;
+        while bit < 8 {
+            crc = if crc & 1 == 1 { (crc >> 1) ^ 0xedb8_8320 } else { crc >> 1 };
+            bit += 1;
+        }
+        table[i] = crc;
+        i += 1;
+    }
+    table
+};
+
+/// Prolonge le CRC-32 `crc` des octets précédents avec `bytes` ; le CRC-32
+/// d'une suite vide est 0
+pub(crate) fn crc32(crc: u32, bytes: &[u8]) -> u32 {
+    let mut crc = !crc;
+    for &byte in bytes {
+        crc = CRC32_TABLE[((crc ^ byte as u32) & 0xff) as usize] ^ (crc >> 8);
+    }
+    !crc
+}
+
+/// CRC-32 de la concaténation de deux suites, à partir du CRC `first` de la
+/// première et du CRC `second` de la seconde, longue de `second_len`
+/// octets (méthode de `crc32_combine` de zlib)
+#[cfg(feature = "rayon")]
+pub(crate) fn crc32_combine(first: u32, second: u32, mut second_len: u64) -> u32 {
+    /// Produit de la matrice `matrix` sur GF(2) par le vecteur `vector`
+    fn times(matrix: &[u32; 32], mut vector: u32) -> u32 {
+        let mut sum = 0;
+        let mut row = 0;
+        while vector != 0 {
+            if vector & 1 == 1 {
+                sum ^= matrix[row];
+            }
+            vector >>= 1;
+            row += 1;
+        }
+        sum
+    }
+
+    fn square(matrix: &[u32; 32]) -> [u32; 32] {
+        core::array::from_fn(|row| times(matrix, matrix[row]))
+    }
+
+    if second_len == 0 {
+        return first;
+    }
+    // Opérateur d'un bit nul, puis de deux et de quatre bits : la boucle
+    // commence à un octet et double la longueur à chaque tour
+    let mut odd = [0; 32];
+    odd[0] = 0xedb8_8320;
+    for (row, value) in odd.iter_mut().enumerate().skip(1) {
+        *value = 1 << (row - 1);
+    }
+    let mut even = square(&odd);
+    odd = square(&even);
+    let mut crc = first;
+    loop {
+        even = square(&odd);
+        if second_len & 1 == 1 {
+            crc = times(&even, crc);
+        }
+        second_len >>= 1;
+        if second_len == 0 {
+            break;
+        }
+        odd = square(&even);
+        if second_len & 1 == 1 {
+            crc = times(&odd, crc);
+        }
+        second_len >>= 1;
+        if second_len == 0 {
+            break;
+        }
+    }
+    crc ^ second
+}
+
+/// Écrit une ligne de suppression
+pub(crate) fn write_removal<W: Write>(writer: &mut W, key: impl Display) -> io::Result<()> {
+    write!(writer, "{}{}{}", REMOVE_PREFIX, Escaped(key), LINE_END)
+}
+
+/// Lit la clé d'une ligne de suppression, décodée avec
+/// [`unescape_lenient`]
+pub(crate) fn parse_removal(line: &str) -> Option<Cow<'_, str>> {
+    let key = line.strip_prefix(REMOVE_PREFIX)?;
+    if split_escaped_entry(key).is_some() {
+        return None;
+    }
+    Some(unescape_lenient(key))
+}
+
+/// Lit la somme d'une ligne de somme de contrôle
+pub(crate) fn parse_checksum_line(line: &str) -> Option<u32> {
+    let digits = line.strip_prefix(CHECKSUM_PREFIX)?;
+    if digits.len() != 8 || !digits.bytes().all(|b| b.is_ascii_hexdigit()) {
+        return None;
+    }
+    u32::from_str_radix(digits, 16).ok()
+}
+
+/// Lit le numéro d'une ligne de version
+pub(crate) fn parse_version_line(line: &str) -> Option<u32> {
+    let digits = line.strip_prefix(VERSION_PREFIX)?;
+    if !digits.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    digits.parse().ok()
+}
+
+/// Lit la capacité de l'en-tête
+pub(crate) fn parse_header(line: &str) -> Option<usize> {
+    parse_full_header(line).map(|(capacity, _)| capacity)
+}
+
+/// Lit la capacité et l'heure de sauvegarde de l'en-tête
+pub(crate) fn parse_full_header(line: &str) -> Option<(usize, Option<u64>)> {
+    match line.split_once(SEPARATOR) {
+        Some((capacity, seconds)) => Some((parse_capacity(capacity)?, Some(seconds.parse().ok()?))),
+        None => Some((parse_capacity(line)?, None)),
+    }
+}
+
+/// Lit une capacité ; celle d'un cache sans limite sauvegardé sur une
+/// plateforme 64 bits est ramenée à `usize::MAX` sur une plateforme 32 bits
+fn parse_capacity(capacity: &str) -> Option<usize> {
+    let capacity: u64 = capacity.parse().ok()?;
+    Some(usize::try_from(capacity).unwrap_or(usize::MAX))
+}
+
+/// Forme écrite par `save` d'une valeur : son `Display` avec les
+/// caractères de [`ESCAPES`] échappés, produit sans copie intermédiaire
+pub(crate) struct Escaped<T>(pub(crate) T);
+
+impl<T: Display> Display for Escaped<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        struct Escaper<'a, 'b>(&'a mut fmt::Formatter<'b>);
+
+        impl fmt::Write for Escaper<'_, '_> {
+            fn write_str(&mut self, s: &str) -> fmt::Result {
+                let mut start = 0;
+                for (i, c) in s.char_indices() {
+                    if let Some((_, written)) = ESCAPES.iter().find(|(raw, _)| *raw == c) {
+                        self.0.write_str(&s[start..i])?;
+                        self.0.write_char(ESCAPE)?;
+                        self.0.write_char(*written)?;
+                        start = i + c.len_utf8();
+                    }
+                }
+                self.0.write_str(&s[start..])
+            }
+        }
+
+        write!(Escaper(f), "{}", self.0)
+    }
+}
+
+/// Sépare une ligne écrite par `save` sur le premier séparateur non échappé
+/// et décode clé et valeur avec [`unescape_lenient`]
+pub(crate) fn parse_entry(line: &str) -> Option<(Cow<'_, str>, Cow<'_, str>)> {
+    let (key, value) = split_escaped_entry(line)?;
+    Some((unescape_lenient(key), unescape_lenient(value)))
+}
+
+/// Sépare une ligne sur le premier séparateur non échappé
+pub(crate) fn split_escaped_entry(line: &str) -> Option<(&str, &str)> {
+    let mut escaped = false;
+    for (i, c) in line.char_indices() {
+        match c {
+            _ if escaped => escaped = false,
+            ESCAPE => escaped = true,
+            SEPARATOR => return Some((&line[..i], &line[i + SEPARATOR.len_utf8()..])),
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Échappe les caractères de [`ESCAPES`]
+pub(crate) fn escape(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for c in s.chars() {
+        match ESCAPES.iter().find(|(raw, _)| *raw == c) {
+            Some((_, written)) => {
+                out.push(ESCAPE);
+                out.push(*written);
+            }
+            None => out.push(c),
+        }
+    }
+    out
+}
+
+/// Inverse de [`escape`] ; `None` si une séquence d'échappement est invalide
+pub(crate) fn unescape(s: &str) -> Option<String> {
+    let mut out = String::with_capacity(s.len());
+    let mut chars = s.chars();
+    while let Some(c) = chars.next() {
+        if c != ESCAPE {
+            out.push(c);
+            continue;
+        }
+        let written = chars.next()?;
+        let (raw, _) = ESCAPES.iter().find(|(_, w)| *w == written)?;
+        out.push(*raw);
+    }
+    Some(out)
+}
+
+/// Comme [`unescape`], en gardant telles quelles les séquences inconnues et
+/// un `\` final ; n'alloue que si `s` contient [`ESCAPE`]
+pub(crate) fn unescape_lenient(s: &str) -> Cow<'_, str> {
+    if !s.contains(ESCAPE) {
+        return Cow::Borrowed(s);
+    }
+    let mut out = String::with_capacity(s.len());
+    let mut chars = s.chars();
+    while let Some(c) = chars.next() {
+        if c != ESCAPE {
+            out.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some(written) => match ESCAPES.iter().find(|(_, w)| *w == written) {
+                Some((raw, _)) => out.push(*raw),
+                None => {
+                    out.push(ESCAPE);
+                    out.push(written);
+                }
+            },
+            None => out.push(ESCAPE),
+        }
+    }
+    Cow::Owned(out)
+}
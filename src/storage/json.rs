@@ -0,0 +1,128 @@
+//! Sauvegarde au format JSON (feature `serde`)
+//!
+//! Le fichier est un objet lisible et modifiable à la main :
+//!
+//! ```json
+//! {
+//!   "capacity": 2,
+//!   "entries": [
+//!     ["key1", {"name": "a"}],
+//!     ["key2", {"name": "b"}]
+//!   ]
+//! }
+//! ```
+//!
+//! Les entrées sont écrites de la prochaine évincée à la plus récente ; les
+//! recharger dans cet ordre reconstitue l'ordre d'éviction. Les champs
+//! inconnus de l'objet sont ignorés au chargement, ce qui permet d'annoter
+//! un fichier.
+//!
+//! # Examples
+//! ```
+//! use lru_cache::storage::json::JsonStorage;
+//!
+//! let data = vec![(String::from("key"), vec![1, 2, 3])];
+//! JsonStorage::save("json_doc.json", 4, &data).unwrap();
+//!
+//! let (capacity, loaded) = JsonStorage::load::<String, Vec<i32>>("json_doc.json").unwrap();
+//! assert_eq!((capacity, loaded), (4, data));
+//! std::fs::remove_file("json_doc.json").unwrap();
+//! ```
+
+use super::atomic::write_atomic;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::fs;
+use std::io::{self, Write};
+use std::path::Path;
+
+#[derive(Serialize)]
+struct DumpRef<'a, K, V> {
+    capacity: usize,
+    entries: &'a [(K, V)],
+}
+
+#[derive(Deserialize)]
+#[serde(bound = "K: DeserializeOwned, V: DeserializeOwned")]
+struct Dump<K, V> {
+    capacity: usize,
+    entries: Vec<(K, V)>,
+}
+
+/// Erreur de chargement d'un fichier JSON
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum JsonError {
+    /// Le fichier ne peut pas être ouvert ou lu
+    Io(io::Error),
+    /// Le contenu n'est pas du JSON valide ou n'a pas la structure attendue
+    /// (objet avec `capacity` et `entries`, entrées de la forme
+    /// `[clé, valeur]` des bons types) ; le message donne la ligne et la
+    /// colonne fautives
+    Malformed(serde_json::Error),
+}
+
+impl fmt::Display for JsonError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            JsonError::Io(error) => write!(f, "lecture impossible : {}", error),
+            JsonError::Malformed(error) => write!(f, "fichier JSON invalide : {}", error),
+        }
+    }
+}
+
+impl std::error::Error for JsonError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            JsonError::Io(error) => Some(error),
+            JsonError::Malformed(error) => Some(error),
+        }
+    }
+}
+
+impl From<io::Error> for JsonError {
+    fn from(error: io::Error) -> Self {
+        JsonError::Io(error)
+    }
+}
+
+/// Gère la persistance des données du cache dans un fichier JSON
+pub struct JsonStorage;
+
+impl JsonStorage {
+    /// Sauvegarde les données dans un fichier JSON indenté
+    ///
+    /// Comme [`FileStorage::save`](super::file::FileStorage::save),
+    /// l'écriture passe par un fichier temporaire renommé sur `path`.
+    ///
+    /// # Errors
+    ///
+    /// Retourne une erreur d'écriture, ou [`io::ErrorKind::InvalidData`] si
+    /// une clé ou une valeur ne se sérialise pas.
+    pub fn save<K: Serialize, V: Serialize>(path: impl AsRef<Path>, capacity: usize, data: &[(K, V)]) -> io::Result<()> {
+        let path = path.as_ref();
+        let dump = DumpRef { capacity, entries: data };
+        write_atomic(path, |writer| {
+            serde_json::to_writer_pretty(&mut *writer, &dump).map_err(io::Error::from)?;
+            writer.write_all(b"\n")
+        })?;
+        trace_event!(debug, path = %path.display(), capacity, entries = data.len(), "cache file saved");
+        Ok(())
+    }
+
+    /// Charge les données d'un fichier écrit par [`JsonStorage::save`]
+    ///
+    /// # Errors
+    ///
+    /// Retourne [`JsonError::Io`] si le fichier ne peut pas être lu et
+    /// [`JsonError::Malformed`] si son contenu n'a pas la structure
+    /// attendue : aucune entrée n'est alors retournée.
+    pub fn load<K: DeserializeOwned, V: DeserializeOwned>(path: impl AsRef<Path>) -> Result<(usize, Vec<(K, V)>), JsonError> {
+        let path = path.as_ref();
+        let text = fs::read_to_string(path)?;
+        let dump: Dump<K, V> = serde_json::from_str(&text).map_err(JsonError::Malformed)?;
+        trace_event!(debug, path = %path.display(), capacity = dump.capacity, entries = dump.entries.len(), "cache file loaded");
+        Ok((dump.capacity, dump.entries))
+    }
+}
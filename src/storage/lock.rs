@@ -0,0 +1,120 @@
+//! Verrous consultatifs entre processus sur un fichier de sauvegarde
+//!
+//! Le verrou est pris sur un fichier voisin, `<path>.lock`, et non sur la
+//! sauvegarde elle-même : [`FileStorage::save`](super::file::FileStorage::save)
+//! remplace le fichier par renommage, et un verrou posé sur l'ancien fichier
+//! ne protégerait pas le nouveau. Le verrou utilise `flock` sous Unix et
+//! `LockFileEx` sous Windows (voir [`File::lock`]) ; il est libéré quand
+//! le [`FileLock`] est détruit, ou par le système si le processus se
+//! termine. Le fichier `.lock` est laissé en place : le supprimer pendant
+//! qu'un autre processus l'ouvre lui ferait verrouiller un fichier
+//! différent.
+//!
+//! Les verrous sont consultatifs : seuls les accès qui les prennent sont
+//! coordonnés. Plusieurs lecteurs peuvent tenir un verrou partagé en même
+//! temps ; un verrou exclusif exclut tous les autres.
+//!
+//! # Examples
+//! ```
+//! use lru_cache::storage::file::FileStorage;
+//! use lru_cache::storage::lock::FileLock;
+//! use std::time::Duration;
+//!
+//! FileStorage::save("lock_doc.txt", 2, &[("a", 1)]).unwrap();
+//!
+//! // Lecture, modification et réécriture sans qu'un autre processus ne
+//! // sauvegarde entre les deux
+//! let lock = FileLock::exclusive("lock_doc.txt", Duration::from_secs(5)).unwrap();
+//! let (capacity, mut data) = FileStorage::load::<String, i32>("lock_doc.txt").unwrap();
+//! data.push((String::from("b"), 2));
+//! FileStorage::save("lock_doc.txt", capacity, &data).unwrap();
+//! drop(lock);
+//!
+//! std::fs::remove_file("lock_doc.txt").unwrap();
+//! std::fs::remove_file(FileLock::lock_path("lock_doc.txt")).unwrap();
+//! ```
+
+use super::error::StorageError;
+use std::fs::{File, OpenOptions, TryLockError};
+use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// Attente maximale entre deux tentatives de verrouillage
+const MAX_RETRY_DELAY: Duration = Duration::from_millis(50);
+
+/// Verrou tenu sur un fichier de sauvegarde, libéré à la destruction
+#[derive(Debug)]
+pub struct FileLock {
+    file: File,
+    exclusive: bool,
+}
+
+impl FileLock {
+    /// Prend le verrou exclusif de `path`, pour écrire
+    ///
+    /// # Errors
+    ///
+    /// Retourne [`StorageError::LockTimeout`] si le verrou n'est pas obtenu
+    /// dans le délai `timeout`, et [`StorageError::Io`] si le fichier
+    /// `.lock` ne peut pas être ouvert ou si la plateforme ne prend pas en
+    /// charge les verrous.
+    pub fn exclusive(path: impl AsRef<Path>, timeout: Duration) -> Result<Self, StorageError> {
+        FileLock::acquire(path.as_ref(), timeout, true)
+    }
+
+    /// Prend un verrou partagé sur `path`, pour lire
+    ///
+    /// # Errors
+    ///
+    /// Les erreurs de [`FileLock::exclusive`].
+    pub fn shared(path: impl AsRef<Path>, timeout: Duration) -> Result<Self, StorageError> {
+        FileLock::acquire(path.as_ref(), timeout, false)
+    }
+
+    /// Retourne le chemin du fichier verrouillé pour `path`
+    pub fn lock_path(path: impl AsRef<Path>) -> PathBuf {
+        let mut name = path.as_ref().as_os_str().to_os_string();
+        name.push(".lock");
+        PathBuf::from(name)
+    }
+
+    /// Indique si le verrou est exclusif
+    pub fn is_exclusive(&self) -> bool {
+        self.exclusive
+    }
+
+    /// Réessaie de prendre le verrou, avec une attente croissante, jusqu'à
+    /// l'échéance
+    fn acquire(path: &Path, timeout: Duration, exclusive: bool) -> Result<Self, StorageError> {
+        let file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(false)
+            .open(FileLock::lock_path(path))?;
+        let started = Instant::now();
+        let mut delay = Duration::from_millis(1);
+        loop {
+            let attempt = if exclusive { file.try_lock() } else { file.try_lock_shared() };
+            match attempt {
+                Ok(()) => return Ok(FileLock { file, exclusive }),
+                Err(TryLockError::Error(error)) => return Err(error.into()),
+                Err(TryLockError::WouldBlock) => {}
+            }
+            let remaining = timeout.saturating_sub(started.elapsed());
+            if remaining.is_zero() {
+                trace_event!(warn, path = %path.display(), exclusive, "cache file lock timed out");
+                return Err(StorageError::LockTimeout(timeout));
+            }
+            thread::sleep(delay.min(remaining));
+            delay = (delay * 2).min(MAX_RETRY_DELAY);
+        }
+    }
+}
+
+impl Drop for FileLock {
+    fn drop(&mut self) {
+        let _ = self.file.unlock();
+    }
+}
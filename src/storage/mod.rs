@@ -1,2 +1,15 @@
 //! Module de gestion du stockage persistant
-pub mod file;
\ No newline at end of file
+mod atomic;
+pub mod backend;
+pub mod binary;
+#[cfg(feature = "serde")]
+pub mod bincode;
+pub mod codec;
+pub(crate) mod csv;
+pub mod error;
+pub mod file;
+pub mod format;
+pub mod lock;
+#[cfg(feature = "serde")]
+pub mod json;
+pub mod rolling;
@@ -0,0 +1,251 @@
+//! Instantanés quotidiens d'un cache, avec rétention
+//!
+//! [`RollingSaver`] écrit un fichier `prefix-AAAA-MM-JJ.txt` par jour (le
+//! dernier enregistrement du jour remplace les précédents) et supprime les
+//! fichiers sortis de la période de rétention. [`load_latest`] et
+//! [`load_as_of`] relisent l'instantané voulu. La date du jour vient d'un
+//! [`Clock`], ce qui permet de simuler plusieurs jours dans les tests.
+//!
+//! # Examples
+//! ```
+//! use lru_cache::Cache;
+//! use lru_cache::cache::traits::CacheStorage;
+//! use lru_cache::storage::rolling::{load_latest, RollingSaver};
+//!
+//! let dir = std::env::temp_dir().join(format!("rolling_doc_{}", std::process::id()));
+//! std::fs::create_dir_all(&dir).unwrap();
+//!
+//! let mut cache = Cache::new(2);
+//! cache.put(String::from("key"), 1);
+//! RollingSaver::new(&dir, "cache", 7).save(&cache).unwrap();
+//!
+//! let mut restored: Cache<String, i32> = load_latest(&dir, "cache", 2).unwrap();
+//! assert_eq!(restored.get(&String::from("key")), Some(&1));
+//! std::fs::remove_dir_all(dir).unwrap();
+//! ```
+
+use crate::cache::clock::{Clock, SystemClock};
+use crate::cache::lru::{check_capacity, Cache};
+use crate::cache::traits::PersistentStorage;
+use std::fmt::{self, Display};
+use std::fs;
+use std::hash::Hash;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::sync::Arc;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+const SECONDS_PER_DAY: u64 = 24 * 3600;
+
+/// Date du calendrier grégorien (UTC)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Date {
+    year: i32,
+    month: u32,
+    day: u32,
+}
+
+impl Date {
+    /// Crée une date, ou `None` si elle n'existe pas
+    pub fn new(year: i32, month: u32, day: u32) -> Option<Self> {
+        let date = Date { year, month, day };
+        (Date::from_days(date.to_days()) == date).then_some(date)
+    }
+
+    /// Date UTC de l'instant `time`
+    pub fn from_system_time(time: SystemTime) -> Self {
+        let days = match time.duration_since(UNIX_EPOCH) {
+            Ok(elapsed) => (elapsed.as_secs() / SECONDS_PER_DAY) as i64,
+            Err(before) => -(before.duration().as_secs().div_ceil(SECONDS_PER_DAY) as i64),
+        };
+        Date::from_days(days)
+    }
+
+    /// Année
+    pub fn year(&self) -> i32 {
+        self.year
+    }
+
+    /// Mois, de 1 à 12
+    pub fn month(&self) -> u32 {
+        self.month
+    }
+
+    /// Jour du mois, à partir de 1
+    pub fn day(&self) -> u32 {
+        self.day
+    }
+
+    /// Nombre de jours depuis le 1er janvier 1970
+    fn to_days(self) -> i64 {
+        let year = self.year as i64 - i64::from(self.month <= 2);
+        let era = year.div_euclid(400);
+        let year_of_era = year.rem_euclid(400);
+        let month = self.month as i64;
+        let day_of_year = (153 * (month + if month > 2 { -3 } else { 9 }) + 2) / 5 + self.day as i64 - 1;
+        let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+        era * 146_097 + day_of_era - 719_468
+    }
+
+    fn from_days(days: i64) -> Self {
+        let days = days + 719_468;
+        let era = days.div_euclid(146_097);
+        let day_of_era = days.rem_euclid(146_097);
+        let year_of_era = (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+        let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+        let shifted_month = (5 * day_of_year + 2) / 153;
+        let day = (day_of_year - (153 * shifted_month + 2) / 5 + 1) as u32;
+        let month = if shifted_month < 10 { shifted_month + 3 } else { shifted_month - 9 } as u32;
+        let year = year_of_era + era * 400 + i64::from(month <= 2);
+        Date {
+            year: year as i32,
+            month,
+            day,
+        }
+    }
+}
+
+impl Display for Date {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:04}-{:02}-{:02}", self.year, self.month, self.day)
+    }
+}
+
+impl FromStr for Date {
+    type Err = ();
+
+    /// Lit une date au format `AAAA-MM-JJ`
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parts = s.splitn(3, '-');
+        let (Some(year), Some(month), Some(day)) = (parts.next(), parts.next(), parts.next()) else {
+            return Err(());
+        };
+        if year.len() != 4 || month.len() != 2 || day.len() != 2 {
+            return Err(());
+        }
+        let parse = |part: &str| part.parse::<u32>().map_err(|_| ());
+        Date::new(parse(year)? as i32, parse(month)?, parse(day)?).ok_or(())
+    }
+}
+
+/// Enregistre un instantané par jour et applique la rétention
+#[derive(Debug, Clone)]
+pub struct RollingSaver {
+    dir: PathBuf,
+    prefix: String,
+    retention_days: u32,
+    clock: Arc<dyn Clock>,
+}
+
+impl RollingSaver {
+    /// Crée un enregistreur qui écrit dans `dir` et conserve les instantanés
+    /// des `retention_days` derniers jours, jour courant compris
+    pub fn new(dir: impl AsRef<Path>, prefix: &str, retention_days: u32) -> Self {
+        RollingSaver::with_clock(dir, prefix, retention_days, SystemClock)
+    }
+
+    /// Comme [`RollingSaver::new`], la date du jour étant lue sur `clock`
+    pub fn with_clock<C: Clock + 'static>(dir: impl AsRef<Path>, prefix: &str, retention_days: u32, clock: C) -> Self {
+        RollingSaver {
+            dir: dir.as_ref().to_path_buf(),
+            prefix: prefix.to_string(),
+            retention_days,
+            clock: Arc::new(clock),
+        }
+    }
+
+    /// Enregistre l'instantané du jour et retourne son chemin
+    ///
+    /// Le fichier est d'abord écrit sous un nom temporaire puis renommé :
+    /// une interruption ne laisse jamais d'instantané partiel. Les
+    /// instantanés plus anciens que la rétention sont ensuite supprimés.
+    ///
+    /// # Errors
+    ///
+    /// Retourne une erreur si l'écriture, le renommage ou la suppression
+    /// d'un ancien instantané échoue.
+    pub fn save<K, V, C: PersistentStorage<K, V>>(&self, cache: &C) -> io::Result<PathBuf> {
+        let today = Date::from_system_time(self.clock.system_time());
+        let path = snapshot_path(&self.dir, &self.prefix, today);
+        let tmp = path.with_extension("txt.tmp");
+        cache.save_to_file(&tmp)?;
+        fs::rename(&tmp, &path)?;
+
+        let oldest_kept = Date::from_days(today.to_days() - i64::from(self.retention_days) + 1);
+        for (date, old) in snapshots(&self.dir, &self.prefix)? {
+            if date < oldest_kept {
+                fs::remove_file(old)?;
+            }
+        }
+        Ok(path)
+    }
+}
+
+/// Charge l'instantané le plus récent de `dir` pour `prefix`
+///
+/// # Errors
+///
+/// Retourne une erreur [`io::ErrorKind::NotFound`] s'il n'existe aucun
+/// instantané, l'erreur de lecture du fichier, ou une erreur
+/// [`io::ErrorKind::InvalidInput`] si `capacity` vaut 0.
+pub fn load_latest<K, V>(dir: impl AsRef<Path>, prefix: &str, capacity: usize) -> io::Result<Cache<K, V>>
+where
+    K: Eq + Hash + Display + FromStr,
+    V: Display + FromStr,
+{
+    let latest = snapshots(dir.as_ref(), prefix)?.into_iter().max_by_key(|(date, _)| *date);
+    load_snapshot(latest, capacity)
+}
+
+/// Charge l'état du cache au jour `date` : le dernier instantané enregistré
+/// ce jour-là ou avant
+///
+/// # Errors
+///
+/// Retourne une erreur [`io::ErrorKind::NotFound`] s'il n'existe aucun
+/// instantané antérieur ou égal à `date`, l'erreur de lecture du fichier,
+/// ou une erreur [`io::ErrorKind::InvalidInput`] si `capacity` vaut 0.
+pub fn load_as_of<K, V>(dir: impl AsRef<Path>, prefix: &str, date: Date, capacity: usize) -> io::Result<Cache<K, V>>
+where
+    K: Eq + Hash + Display + FromStr,
+    V: Display + FromStr,
+{
+    let selected = snapshots(dir.as_ref(), prefix)?
+        .into_iter()
+        .filter(|(day, _)| *day <= date)
+        .max_by_key(|(day, _)| *day);
+    load_snapshot(selected, capacity)
+}
+
+fn load_snapshot<K, V>(snapshot: Option<(Date, PathBuf)>, capacity: usize) -> io::Result<Cache<K, V>>
+where
+    K: Eq + Hash + Display + FromStr,
+    V: Display + FromStr,
+{
+    let (_, path) = snapshot.ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "aucun instantané"))?;
+    Cache::load_from_file(path, check_capacity(capacity)?)
+}
+
+fn snapshot_path(dir: &Path, prefix: &str, date: Date) -> PathBuf {
+    dir.join(format!("{}-{}.txt", prefix, date))
+}
+
+/// Instantanés de `dir` pour `prefix`, dans un ordre quelconque
+fn snapshots(dir: &Path, prefix: &str) -> io::Result<Vec<(Date, PathBuf)>> {
+    let mut found = Vec::new();
+    for entry in fs::read_dir(dir)? {
+        let entry = entry?;
+        let name = entry.file_name();
+        let date = name
+            .to_str()
+            .and_then(|name| name.strip_prefix(prefix))
+            .and_then(|rest| rest.strip_prefix('-'))
+            .and_then(|rest| rest.strip_suffix(".txt"))
+            .and_then(|date| date.parse().ok());
+        if let Some(date) = date {
+            found.push((date, entry.path()));
+        }
+    }
+    Ok(found)
+}
@@ -0,0 +1,11 @@
+//! Événements `tracing`, émis seulement avec la feature `tracing`
+//!
+//! Sans la feature, `trace_event!` ne produit aucun code : ses arguments ne
+//! sont même pas évalués.
+
+macro_rules! trace_event {
+    ($level:ident, $($arg:tt)+) => {
+        #[cfg(feature = "tracing")]
+        ::tracing::$level!($($arg)+);
+    };
+}
@@ -0,0 +1,79 @@
+use lru_cache::Cache;
+use lru_cache::cache::adapters::{NullCache, UnboundedMapCache};
+use lru_cache::cache::traits::{CacheIterate, CacheStorage, CacheStorageExt, DynCache, GetOptions};
+
+/// Contrat commun, vérifié uniquement à travers `dyn DynCache` : une clé
+/// peut ne pas être gardée, mais une clé gardée rend sa dernière valeur et
+/// compte dans `len` jusqu'à `remove` ou `clear`
+fn suite(cache: &mut dyn DynCache<u32, String>) {
+    assert!(cache.is_empty());
+    assert_eq!(cache.get(&1), None);
+
+    cache.put(1, String::from("un"));
+    cache.put(2, String::from("deux"));
+    let kept: Vec<u32> = (1..=2).filter(|key| cache.get(key).is_some()).collect();
+    assert_eq!(cache.len(), kept.len());
+
+    cache.put(1, String::from("uno"));
+    if kept.contains(&1) {
+        assert_eq!(cache.get_cloned(&1), Some(String::from("uno")));
+    }
+    assert_eq!(cache.get_with(&3, GetOptions::passive()), None);
+
+    assert_eq!(cache.remove(&2).is_some(), kept.contains(&2));
+    assert_eq!(cache.remove(&2), None);
+    assert_eq!(cache.get(&2), None);
+    assert_eq!(cache.len(), kept.len() - usize::from(kept.contains(&2)));
+
+    cache.clear();
+    assert!(cache.is_empty());
+    assert_eq!(cache.get(&1), None);
+}
+
+#[test]
+fn test_suite_runs_against_every_implementation() {
+    let mut caches: Vec<Box<dyn DynCache<u32, String>>> = vec![
+        Box::new(Cache::new(4)),
+        Box::new(UnboundedMapCache::new()),
+        Box::new(NullCache::new()),
+    ];
+    for cache in &mut caches {
+        suite(cache.as_mut());
+    }
+}
+
+#[test]
+fn test_unbounded_cache_keeps_everything() {
+    let mut cache = UnboundedMapCache::new();
+    for key in 0..10_000u32 {
+        cache.put(key, key);
+    }
+    cache.put(0, 42);
+    assert_eq!(cache.get(&0), Some(&42));
+    assert_eq!(cache.get(&9_999), Some(&9_999));
+    assert_eq!(cache.get(&10_000), None);
+    assert_eq!(cache.iter().count(), 10_000);
+
+    let stats = cache.stats();
+    assert_eq!((stats.hits, stats.misses), (2, 1));
+    assert_eq!((stats.insertions, stats.updates, stats.evictions), (10_000, 1, 0));
+}
+
+#[test]
+fn test_null_cache_reports_only_misses() {
+    let mut cache = NullCache::new();
+    for key in 0..10 {
+        cache.put(key, key);
+        assert_eq!(cache.get(&key), None);
+    }
+    cache.get_with(&0, GetOptions::passive());
+    assert_eq!(cache.iter().count(), 0);
+
+    let stats = cache.stats();
+    assert_eq!(stats.misses, 10);
+    assert_eq!((stats.hits, stats.insertions, stats.updates), (0, 0, 0));
+    assert_eq!(stats.hit_ratio(), 0.0);
+
+    cache.reset_stats();
+    assert_eq!(cache.stats().misses, 0);
+}
@@ -0,0 +1,123 @@
+#![cfg(feature = "async")]
+
+use lru_cache::cache::concurrent::ConcurrentCache;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::Barrier;
+
+#[tokio::test]
+async fn test_loader_result_lands_in_cache() {
+    let cache = ConcurrentCache::new(2);
+    let value = cache.get_or_insert_with_async("key", async { 42 }).await;
+    assert_eq!(value, 42);
+    assert_eq!(cache.get(&"key"), Some(42));
+
+    let cached = cache.get_or_insert_with_async("key", async { unreachable!() }).await;
+    assert_eq!(cached, 42);
+    let stats = cache.lock().stats();
+    assert_eq!((stats.hits, stats.misses), (2, 1));
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn test_put_during_load_is_kept() {
+    let cache = Arc::new(ConcurrentCache::new(8));
+    let started = Arc::new(Barrier::new(2));
+    let loader = {
+        let cache = Arc::clone(&cache);
+        let started = Arc::clone(&started);
+        tokio::spawn(async move {
+            cache
+                .get_or_insert_with_async("key", async move {
+                    started.wait().await;
+                    tokio::time::sleep(Duration::from_millis(20)).await;
+                    "loaded"
+                })
+                .await
+        })
+    };
+    started.wait().await;
+    cache.put("key", "put");
+
+    assert_eq!(loader.await.unwrap(), "put");
+    assert_eq!(cache.get(&"key"), Some("put"));
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn test_concurrent_loads_of_same_key_run_once() {
+    let cache = Arc::new(ConcurrentCache::new(8));
+    let loads = Arc::new(AtomicUsize::new(0));
+    let tasks: Vec<_> = (0..50)
+        .map(|_| {
+            let cache = Arc::clone(&cache);
+            let loads = Arc::clone(&loads);
+            tokio::spawn(async move {
+                cache
+                    .get_or_insert_with_async("key", async move {
+                        loads.fetch_add(1, Ordering::SeqCst);
+                        tokio::time::sleep(Duration::from_millis(20)).await;
+                        42
+                    })
+                    .await
+            })
+        })
+        .collect();
+    for task in tasks {
+        assert_eq!(task.await.unwrap(), 42);
+    }
+
+    assert_eq!(loads.load(Ordering::SeqCst), 1);
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn test_waiters_retry_after_loader_panics() {
+    let cache = Arc::new(ConcurrentCache::new(8));
+    let started = Arc::new(Barrier::new(2));
+    let failing = {
+        let cache = Arc::clone(&cache);
+        let started = Arc::clone(&started);
+        tokio::spawn(async move {
+            cache
+                .get_or_insert_with_async("key", async move {
+                    started.wait().await;
+                    tokio::time::sleep(Duration::from_millis(20)).await;
+                    panic!("chargement en échec")
+                })
+                .await
+        })
+    };
+    started.wait().await;
+    let waiter = cache.get_or_insert_with_async("key", async { 7 }).await;
+
+    assert!(failing.await.is_err());
+    assert_eq!(waiter, 7);
+    assert_eq!(cache.get(&"key"), Some(7));
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn test_concurrent_loads_of_different_keys() {
+    let cache = Arc::new(ConcurrentCache::new(64));
+    let loads = Arc::new(AtomicUsize::new(0));
+    let tasks: Vec<_> = (0..32)
+        .map(|i| {
+            let cache = Arc::clone(&cache);
+            let loads = Arc::clone(&loads);
+            tokio::spawn(async move {
+                cache
+                    .get_or_insert_with_async(i, async move {
+                        loads.fetch_add(1, Ordering::SeqCst);
+                        tokio::task::yield_now().await;
+                        i * 10
+                    })
+                    .await
+            })
+        })
+        .collect();
+    for (i, task) in tasks.into_iter().enumerate() {
+        assert_eq!(task.await.unwrap(), i * 10);
+    }
+
+    assert_eq!(loads.load(Ordering::SeqCst), 32);
+    assert_eq!(cache.len(), 32);
+    assert_eq!(cache.get(&7), Some(70));
+}
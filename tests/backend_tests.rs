@@ -0,0 +1,111 @@
+mod common;
+
+use lru_cache::Cache;
+use lru_cache::cache::traits::{CacheIterate, CacheLen, CacheStorage, PersistentStorage};
+use lru_cache::storage::backend::{FileBackend, MemoryBackend, StorageBackend};
+use lru_cache::storage::error::StorageError;
+use lru_cache::storage::file::FileStorage;
+use std::fs;
+use std::io;
+use std::num::NonZeroUsize;
+
+/// Destination qui garde les entrées telles quelles, comme le ferait un
+/// service externe
+#[derive(Default)]
+struct RecordingBackend {
+    saved: Option<(usize, Vec<(String, String)>)>,
+    fail: bool,
+}
+
+impl StorageBackend for RecordingBackend {
+    fn save(&mut self, capacity: usize, entries: &[(String, String)]) -> Result<(), StorageError> {
+        if self.fail {
+            return Err(io::Error::new(io::ErrorKind::ConnectionRefused, "service indisponible").into());
+        }
+        self.saved = Some((capacity, entries.to_vec()));
+        Ok(())
+    }
+
+    fn load(&mut self) -> Result<(usize, Vec<(String, String)>), StorageError> {
+        self.saved
+            .clone()
+            .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "rien de sauvegardé").into())
+    }
+}
+
+#[test]
+fn test_memory_round_trip_keeps_order() {
+    let mut cache = Cache::new(3);
+    cache.put(String::from("a;b"), String::from("1\n2"));
+    cache.put(String::from("b"), String::from("2"));
+    cache.put(String::from("c"), String::from("3"));
+    cache.get(&String::from("a;b"));
+
+    let mut backend = MemoryBackend::new();
+    cache.save_to(&mut backend).unwrap();
+    let mut loaded = Cache::<String, String>::load_from(&mut backend, NonZeroUsize::new(3).unwrap()).unwrap();
+    assert_eq!(loaded.iter().collect::<Vec<_>>(), cache.iter().collect::<Vec<_>>());
+
+    loaded.put(String::from("d"), String::from("4"));
+    assert_eq!(loaded.get(&String::from("b")), None);
+}
+
+#[test]
+fn test_memory_backend_matches_file_format() {
+    let path = common::temp_path("backend_format.txt");
+    let data = vec![(String::from("k;1"), String::from("v\\1")), (String::from("k2"), String::new())];
+    FileStorage::save(&path, 4, &data).unwrap();
+
+    let mut backend = MemoryBackend::new();
+    backend.save(4, &data).unwrap();
+    assert_eq!(backend.as_bytes(), fs::read(&path).unwrap().as_slice());
+
+    let mut reloaded = MemoryBackend::from_bytes(backend.into_bytes());
+    assert_eq!(reloaded.load().unwrap(), (4, data));
+    fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn test_memory_backend_errors() {
+    assert!(matches!(MemoryBackend::new().load(), Err(StorageError::MissingCapacity)));
+
+    let mut backend = MemoryBackend::from_bytes(b"#lru_cache v3\n2\na;1\n#crc32 00000000\n".to_vec());
+    assert!(matches!(backend.load(), Err(StorageError::ChecksumMismatch { .. })));
+}
+
+#[test]
+fn test_custom_backend() {
+    let mut cache = Cache::new(2);
+    cache.put(1, 10);
+    cache.put(2, 20);
+
+    let mut backend = RecordingBackend::default();
+    cache.save_to(&mut backend).unwrap();
+    let expected = vec![(String::from("1"), String::from("10")), (String::from("2"), String::from("20"))];
+    assert_eq!(backend.saved, Some((2, expected)));
+
+    backend.saved.as_mut().unwrap().1.push((String::from("x"), String::from("30")));
+    let mut loaded = Cache::<i32, i32>::load_from(&mut backend, NonZeroUsize::new(1).unwrap()).unwrap();
+    assert_eq!(loaded.len(), 1);
+    assert_eq!(loaded.get(&2), Some(&20));
+
+    backend.fail = true;
+    let error = cache.save_to(&mut backend).unwrap_err();
+    assert!(matches!(error, StorageError::Io(ref e) if e.kind() == io::ErrorKind::ConnectionRefused));
+    assert!(backend.saved.is_some());
+}
+
+#[test]
+fn test_file_backend_matches_save_to_file() {
+    let path = common::temp_path("backend_file.txt");
+    let mut cache = Cache::new(2);
+    cache.put(String::from("a"), 1);
+    cache.save_to(&mut FileBackend::new(&path)).unwrap();
+
+    let mut backend = FileBackend::new(&path);
+    assert_eq!(backend.path(), path.as_path());
+    assert_eq!(backend.load().unwrap(), (2, vec![(String::from("a"), String::from("1"))]));
+    let mut loaded = Cache::<String, i32>::load_from_file(&path, NonZeroUsize::new(2).unwrap()).unwrap();
+    assert_eq!(loaded.get(&String::from("a")), Some(&1));
+    fs::remove_file(path).unwrap();
+}
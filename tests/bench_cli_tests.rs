@@ -0,0 +1,67 @@
+use std::process::{Command, Output};
+
+const TRACE: &str = "tests/fixtures/trace.txt";
+
+fn bench(args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_lru-cache-bench"))
+        .args(args)
+        .output()
+        .expect("lancement de lru-cache-bench")
+}
+
+fn stdout(args: &[&str]) -> String {
+    let output = bench(args);
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    String::from_utf8(output.stdout).unwrap()
+}
+
+#[test]
+fn test_single_capacity_report() {
+    assert_eq!(
+        stdout(&[TRACE, "--capacity", "2"]),
+        "policy=lru capacity=2 accesses=10\n\
+         hits=1\n\
+         misses=9\n\
+         evictions=7\n\
+         hit_rate=0.1000\n\
+         peak_bytes=178\n"
+    );
+}
+
+#[test]
+fn test_compare_capacities_table() {
+    let out = stdout(&[TRACE, "--compare", "capacity=2,3,4"]);
+    let rows: Vec<Vec<&str>> = out.lines().skip(2).map(|line| line.split_whitespace().collect()).collect();
+
+    assert_eq!(out.lines().next(), Some("policy=lru accesses=10"));
+    assert_eq!(
+        rows,
+        vec![
+            vec!["2", "1", "9", "7", "0.1000", "178"],
+            vec!["3", "4", "6", "3", "0.4000", "267"],
+            vec!["4", "5", "5", "1", "0.5000", "356"],
+        ]
+    );
+}
+
+#[test]
+fn test_ttl_counts_expired_entries_as_misses() {
+    let out = stdout(&[TRACE, "--capacity", "4", "--ttl", "3"]);
+
+    assert!(out.contains("hits=1\nmisses=9\n"), "{}", out);
+}
+
+#[test]
+fn test_other_policies() {
+    let out = stdout(&[TRACE, "--capacity", "3", "--policy", "fifo"]);
+
+    assert!(out.contains("hits=3\nmisses=7\nevictions=4\n"), "{}", out);
+}
+
+#[test]
+fn test_invalid_arguments() {
+    assert_eq!(bench(&[TRACE]).status.code(), Some(2));
+    assert_eq!(bench(&[TRACE, "--capacity", "2", "--policy", "nope"]).status.code(), Some(2));
+    assert_eq!(bench(&[TRACE, "--capacity", "2", "--policy", "fifo", "--ttl", "3"]).status.code(), Some(2));
+    assert_eq!(bench(&["tests/fixtures/absent.txt", "--capacity", "2"]).status.code(), Some(1));
+}
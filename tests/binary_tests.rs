@@ -0,0 +1,124 @@
+mod common;
+
+use lru_cache::Cache;
+use lru_cache::cache::traits::{BinaryPersistentStorage, CacheIterate, CacheStorage};
+use lru_cache::storage::binary::{BinaryError, BinaryStorage, EntryField, MAGIC};
+use std::fs;
+use std::num::NonZeroUsize;
+
+#[test]
+fn test_round_trip_binary_unsafe_content() {
+    let path = common::temp_path("unsafe.bin");
+    let big: Vec<u8> = (0..3 * 1024 * 1024).map(|i| (i % 251) as u8).collect();
+    let data = vec![
+        (String::from("nul\0key"), vec![0u8, 0, 0]),
+        (String::from("a;b\nc\r\\"), Vec::new()),
+        (String::new(), vec![b'\n', 255, 0, b';']),
+        (String::from("big"), big),
+    ];
+    BinaryStorage::save(&path, 8, &data).unwrap();
+
+    let (capacity, loaded) = BinaryStorage::load::<String, Vec<u8>>(&path).unwrap();
+    assert_eq!(capacity, 8);
+    assert_eq!(loaded, data);
+    fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn test_round_trip_integers() {
+    let path = common::temp_path("integers.bin");
+    let data = vec![(u64::MAX, i8::MIN), (0, -1), (usize::MAX as u64, 127)];
+    BinaryStorage::save(&path, 3, &data).unwrap();
+    assert_eq!(BinaryStorage::load::<u64, i8>(&path).unwrap().1, data);
+
+    let sizes = vec![(usize::MAX, isize::MIN)];
+    BinaryStorage::save(&path, 1, &sizes).unwrap();
+    assert_eq!(BinaryStorage::load::<usize, isize>(&path).unwrap().1, sizes);
+    fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn test_cache_round_trip_keeps_order() {
+    let path = common::temp_path("cache.bin");
+    let mut cache = Cache::new(3);
+    cache.put(String::from("a"), String::from("1\0"));
+    cache.put(String::from("b"), String::from("2"));
+    cache.put(String::from("c"), String::from("3"));
+    cache.get(&String::from("a"));
+    cache.save_to_file_binary(&path).unwrap();
+
+    let mut loaded: Cache<String, String> = Cache::load_from_file_binary(&path, NonZeroUsize::new(3).unwrap()).unwrap();
+    let original: Vec<_> = cache.iter().collect();
+    assert_eq!(loaded.iter().collect::<Vec<_>>(), original);
+    loaded.put(String::from("d"), String::from("4"));
+    assert_eq!(loaded.get(&String::from("b")), None);
+    fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn test_load_rejects_wrong_magic() {
+    let path = common::temp_path("magic.bin");
+    fs::write(&path, "3\na;1\n").unwrap();
+    let error = BinaryStorage::load::<String, String>(&path).unwrap_err();
+    assert!(matches!(error, BinaryError::BadMagic(magic) if &magic == b"3\na;"));
+
+    let error = Cache::<String, String>::load_from_file_binary(&path, NonZeroUsize::new(3).unwrap()).unwrap_err();
+    assert!(matches!(error, BinaryError::BadMagic(_)));
+    fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn test_load_rejects_truncated_file_at_every_offset() {
+    let path = common::temp_path("truncated.bin");
+    let data = vec![(String::from("key"), vec![1u8, 2, 3]), (String::from("k2"), vec![4])];
+    BinaryStorage::save(&path, 2, &data).unwrap();
+    let bytes = fs::read(&path).unwrap();
+
+    for len in 0..bytes.len() {
+        fs::write(&path, &bytes[..len]).unwrap();
+        let error = BinaryStorage::load::<String, Vec<u8>>(&path).unwrap_err();
+        let expected = match len {
+            ..=23 => None,
+            24..=37 => Some(0),
+            _ => Some(1),
+        };
+        assert!(
+            matches!(error, BinaryError::Truncated { index } if index == expected),
+            "longueur {} : {:?}",
+            len,
+            error
+        );
+    }
+    fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn test_load_rejects_invalid_entry_and_trailing_data() {
+    let path = common::temp_path("invalid.bin");
+    let data = vec![(vec![b'o', b'k'], 1u32), (vec![0xff, 0xfe], 2u32)];
+    BinaryStorage::save(&path, 2, &data).unwrap();
+    let error = BinaryStorage::load::<String, u32>(&path).unwrap_err();
+    assert!(matches!(
+        error,
+        BinaryError::InvalidEntry {
+            index: 1,
+            field: EntryField::Key
+        }
+    ));
+    let error = BinaryStorage::load::<Vec<u8>, u16>(&path).unwrap_err();
+    assert!(matches!(
+        error,
+        BinaryError::InvalidEntry {
+            index: 0,
+            field: EntryField::Value
+        }
+    ));
+
+    let mut bytes = fs::read(&path).unwrap();
+    bytes.push(0);
+    fs::write(&path, &bytes).unwrap();
+    let error = BinaryStorage::load::<Vec<u8>, u32>(&path).unwrap_err();
+    assert!(matches!(error, BinaryError::TrailingData));
+    assert_eq!(&bytes[..4], &MAGIC);
+    fs::remove_file(path).unwrap();
+}
@@ -0,0 +1,101 @@
+#![cfg(feature = "serde")]
+
+mod common;
+
+use lru_cache::Cache;
+use lru_cache::cache::traits::{CacheIterate, CacheStorage, PersistentStorage};
+use lru_cache::storage::bincode::{BincodeStorage, BINCODE_FORMAT_VERSION, MAGIC};
+use lru_cache::storage::error::StorageError;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+enum Role {
+    Reader,
+    Writer(u8),
+    Owner { since: i64 },
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct Account {
+    name: String,
+    roles: Vec<Role>,
+    tags: Vec<Vec<Option<String>>>,
+    limits: BTreeMap<String, f64>,
+    manager: Option<Box<Account>>,
+    initial: char,
+    active: bool,
+}
+
+fn account(name: &str, manager: Option<Account>) -> Account {
+    Account {
+        name: name.to_string(),
+        roles: vec![Role::Reader, Role::Writer(3), Role::Owner { since: -42 }],
+        tags: vec![vec![Some(String::from("a;b\n")), None], Vec::new()],
+        limits: BTreeMap::from([(String::from("cpu"), 1.5), (String::from("mem"), f64::MAX)]),
+        manager: manager.map(Box::new),
+        initial: 'é',
+        active: !name.is_empty(),
+    }
+}
+
+#[test]
+fn test_cache_of_structs_roundtrips() {
+    let path = common::temp_path("bincode_structs.bin");
+    let mut cache = Cache::new(3);
+    cache.put((1u64, String::from("alice")), account("alice", None));
+    cache.put((2, String::from("bob")), account("bob", Some(account("alice", None))));
+    cache.put((3, String::new()), account("", None));
+    cache.get(&(1, String::from("alice")));
+    cache.save_to_file_bincode(&path).unwrap();
+
+    let loaded: Cache<(u64, String), Account> = Cache::load_from_file_bincode(&path).unwrap();
+    assert_eq!(loaded.capacity(), 3);
+    assert_eq!(loaded.iter().collect::<Vec<_>>(), cache.iter().collect::<Vec<_>>());
+    fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn test_header_identifies_the_format() {
+    let path = common::temp_path("bincode_header.bin");
+    BincodeStorage::save(&path, 7, &[(1u8, ())]).unwrap();
+    let bytes = fs::read(&path).unwrap();
+    assert_eq!(bytes[..4], MAGIC);
+    assert_eq!(bytes[4..8], BINCODE_FORMAT_VERSION.to_le_bytes());
+    assert_eq!(BincodeStorage::load::<u8, ()>(&path).unwrap(), (7, vec![(1, ())]));
+    fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn test_text_file_is_rejected_by_header() {
+    let path = common::temp_path("bincode_text.txt");
+    let mut cache = Cache::new(2);
+    cache.put(String::from("a"), 1u32);
+    cache.save_to_file(&path).unwrap();
+    let error = Cache::<String, u32>::load_from_file_bincode(&path).unwrap_err();
+    assert!(matches!(error, StorageError::InvalidMagic), "{:?}", error);
+    fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn test_wrong_types_and_truncation_are_errors() {
+    let path = common::temp_path("bincode_bad.bin");
+    BincodeStorage::save(&path, 2, &[(String::from("a"), vec![1u32, 2, 3])]).unwrap();
+    let wrong = BincodeStorage::load::<String, (u32, u32, u32, u32)>(&path).unwrap_err();
+    assert!(matches!(wrong, StorageError::Undecodable(_)), "{:?}", wrong);
+    let trailing = BincodeStorage::load::<String, u64>(&path).unwrap_err();
+    assert!(matches!(trailing, StorageError::Undecodable(_)), "{:?}", trailing);
+
+    let mut bytes = fs::read(&path).unwrap();
+    bytes.truncate(bytes.len() - 1);
+    fs::write(&path, &bytes).unwrap();
+    let truncated = BincodeStorage::load::<String, Vec<u32>>(&path).unwrap_err();
+    assert!(matches!(truncated, StorageError::Undecodable(_)), "{:?}", truncated);
+
+    bytes[4] = 9;
+    fs::write(&path, &bytes).unwrap();
+    let version = BincodeStorage::load::<String, Vec<u32>>(&path).unwrap_err();
+    assert!(matches!(version, StorageError::UnsupportedVersion(9)), "{:?}", version);
+    fs::remove_file(&path).unwrap();
+}
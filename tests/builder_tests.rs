@@ -0,0 +1,176 @@
+use lru_cache::Cache;
+use lru_cache::cache::builder::CacheBuilder;
+use lru_cache::cache::clock::MockClock;
+use lru_cache::cache::error::CacheError;
+use lru_cache::cache::listener::RemovalCause;
+use lru_cache::cache::policy::Mru;
+use lru_cache::cache::traits::{CacheIterate, CacheLen, CacheStorage};
+use std::num::NonZeroUsize;
+use std::sync::mpsc;
+use std::time::Duration;
+
+fn keys(cache: &Cache<&'static str, String>) -> Vec<&'static str> {
+    cache.iter().map(|(key, _)| *key).collect()
+}
+
+#[test]
+fn test_default_options_match_new() {
+    let mut built = CacheBuilder::new().capacity(NonZeroUsize::new(3).unwrap()).build().unwrap();
+    let mut plain = Cache::new(3);
+    for (key, value) in [("a", 1), ("b", 2), ("c", 3), ("a", 4), ("d", 5)] {
+        built.put(key, value);
+        plain.put(key, value);
+    }
+    built.get(&"c");
+    plain.get(&"c");
+    assert_eq!(built.iter().collect::<Vec<_>>(), plain.iter().collect::<Vec<_>>());
+    assert_eq!(built.stats(), plain.stats());
+    assert_eq!(built.capacity(), plain.capacity());
+}
+
+#[test]
+fn test_weigher_with_ttl_and_clock() {
+    let clock = MockClock::new();
+    let mut cache = Cache::builder()
+        .capacity(NonZeroUsize::new(10).unwrap())
+        .weigher(|_: &&str, value: &String| value.len())
+        .time_to_live(Duration::from_secs(5))
+        .clock(clock.clone())
+        .build()
+        .unwrap();
+    cache.put("a", String::from("1234"));
+    cache.put("b", String::from("1234"));
+    cache.put("c", String::from("123"));
+    assert_eq!(keys(&cache), ["b", "c"]);
+    assert_eq!(cache.total_weight(), 7);
+
+    clock.advance(Duration::from_secs(5));
+    assert_eq!(cache.get(&"b"), None);
+    assert_eq!(cache.peek(&"c"), None);
+}
+
+#[test]
+fn test_listener_with_idle_expiry_and_policy() {
+    let clock = MockClock::new();
+    let (sender, removed) = mpsc::channel();
+    let mut cache = CacheBuilder::new()
+        .capacity(NonZeroUsize::new(2).unwrap())
+        .time_to_idle(Duration::from_secs(10))
+        .clock(clock.clone())
+        .eviction_listener(move |key, _, cause| sender.send((key, cause)).unwrap())
+        .track_metadata()
+        .build_with_eviction_policy(Mru)
+        .unwrap();
+    cache.put("a", String::from("1"));
+    cache.put("b", String::from("2"));
+    cache.put("c", String::from("3"));
+    assert_eq!(removed.try_recv(), Ok(("b", RemovalCause::Capacity)));
+
+    clock.advance(Duration::from_secs(8));
+    cache.get(&"a");
+    clock.advance(Duration::from_secs(8));
+    assert!(cache.contains_key(&"a"));
+    assert!(!cache.contains_key(&"c"));
+    cache.get(&"c");
+    assert_eq!(removed.try_recv(), Ok(("c", RemovalCause::Expired)));
+    assert_eq!(cache.metadata(&"a").unwrap().access_count, 1);
+    assert_eq!(cache.len(), 1);
+}
+
+#[test]
+fn test_invalid_configuration_is_rejected() {
+    assert_eq!(CacheBuilder::<&str, i32>::new().build().unwrap_err(), CacheError::ZeroCapacity);
+    let weighted = CacheBuilder::new().weigher(|_: &&str, _: &i32| 1).build();
+    assert_eq!(weighted.unwrap_err(), CacheError::ZeroCapacity);
+    let preallocated = CacheBuilder::<u32, u32>::new().capacity(NonZeroUsize::new(64).unwrap()).preallocate().build().unwrap();
+    assert!(preallocated.allocated_capacity() >= 64);
+}
+
+#[test]
+#[allow(deprecated)]
+fn test_builtin_policy_cannot_override_eviction_policy() {
+    use lru_cache::cache::policy::Policy;
+
+    let capacity = NonZeroUsize::new(2).unwrap();
+    let mixed = CacheBuilder::<&str, i32>::new().capacity(capacity).policy(Policy::Mru).build_with_eviction_policy(Mru);
+    assert_eq!(mixed.unwrap_err(), CacheError::ConflictingPolicies);
+
+    let mut builtin = CacheBuilder::new().capacity(capacity).policy(Policy::Mru).build().unwrap();
+    builtin.put("a", 1);
+    builtin.put("b", 2);
+    builtin.put("c", 3);
+    assert_eq!(builtin.peek(&"b"), None);
+}
+
+fn watermarked(high: usize, low: usize) -> (Cache<u32, u32>, mpsc::Receiver<u32>) {
+    let (sender, removed) = mpsc::channel();
+    let cache = CacheBuilder::new()
+        .watermarks(NonZeroUsize::new(high).unwrap(), NonZeroUsize::new(low).unwrap())
+        .eviction_listener(move |key, _, cause| {
+            assert_eq!(cause, RemovalCause::Capacity);
+            sender.send(key).unwrap();
+        })
+        .build()
+        .unwrap();
+    (cache, removed)
+}
+
+#[test]
+fn test_watermarks_evict_in_batches() {
+    let (mut cache, removed) = watermarked(5, 2);
+    assert_eq!(cache.watermarks(), (5, 2));
+    for key in 0..5 {
+        cache.put(key, key);
+    }
+    assert_eq!(cache.len(), 5);
+    assert!(removed.try_recv().is_err());
+
+    cache.get(&0);
+    cache.put(5, 5);
+    assert_eq!(removed.try_iter().collect::<Vec<_>>(), [1, 2, 3, 4]);
+    assert_eq!(cache.iter().map(|(key, _)| *key).collect::<Vec<_>>(), [0, 5]);
+
+    for key in 6..9 {
+        cache.put(key, key);
+    }
+    assert_eq!(cache.len(), 5);
+    assert!(removed.try_recv().is_err());
+}
+
+#[test]
+fn test_equal_watermarks_evict_one_entry() {
+    let (mut cache, removed) = watermarked(3, 3);
+    for key in 0..5 {
+        cache.put(key, key);
+    }
+    assert_eq!(removed.try_iter().collect::<Vec<_>>(), [0, 1]);
+    assert_eq!(cache.len(), 3);
+}
+
+#[test]
+fn test_resize_keeps_low_watermark() {
+    let (mut cache, removed) = watermarked(6, 3);
+    for key in 0..6 {
+        cache.put(key, key);
+    }
+    cache.resize(NonZeroUsize::new(8).unwrap());
+    assert_eq!(cache.watermarks(), (8, 3));
+    assert!(removed.try_recv().is_err());
+
+    cache.resize(NonZeroUsize::new(5).unwrap());
+    assert_eq!(removed.try_iter().collect::<Vec<_>>(), [0, 1, 2]);
+    assert_eq!(cache.len(), 3);
+
+    cache.resize(NonZeroUsize::new(2).unwrap());
+    assert_eq!(cache.watermarks(), (2, 2));
+    assert_eq!(removed.try_iter().collect::<Vec<_>>(), [3]);
+}
+
+#[test]
+fn test_low_watermark_above_capacity_is_rejected() {
+    let built = CacheBuilder::<u32, u32>::new()
+        .watermarks(NonZeroUsize::new(4).unwrap(), NonZeroUsize::new(2).unwrap())
+        .capacity(NonZeroUsize::new(1).unwrap())
+        .build();
+    assert_eq!(built.unwrap_err(), CacheError::InvalidWatermarks { high: 1, low: 2 });
+}
@@ -0,0 +1,189 @@
+mod common;
+
+use lru_cache::Cache;
+use lru_cache::cache::clock::MockClock;
+use lru_cache::cache::traits::{CacheIterate, CacheStorage, PersistentStorage};
+use lru_cache::storage::codec::{verify_roundtrip, Base64Codec, CodecError, DisplayCodec, TextCodec, ValueCodec};
+use lru_cache::storage::error::StorageError;
+use lru_cache::storage::file::FileStorage;
+use std::fs;
+use std::num::NonZeroUsize;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Meters(f64);
+
+impl TextCodec for Meters {
+    fn encode(&self) -> String {
+        self.0.encode()
+    }
+
+    fn decode(s: &str) -> Result<Self, CodecError> {
+        f64::decode(s).map(Meters)
+    }
+}
+
+#[derive(Debug, PartialEq)]
+struct Marker;
+
+impl TextCodec for Marker {
+    fn encode(&self) -> String {
+        ().encode()
+    }
+
+    fn decode(s: &str) -> Result<Self, CodecError> {
+        <()>::decode(s).map(|_| Marker)
+    }
+}
+
+#[test]
+fn test_float_roundtrips() {
+    let values = [
+        f64::NAN,
+        f64::INFINITY,
+        f64::NEG_INFINITY,
+        -0.0,
+        0.0,
+        f64::MAX,
+        f64::MIN_POSITIVE,
+        5e-324,
+        1e300,
+        0.1 + 0.2,
+    ];
+    for value in values {
+        verify_roundtrip(&value).unwrap();
+        let decoded = f64::decode(&value.encode()).unwrap();
+        assert!(decoded.to_bits() == value.to_bits() || (decoded.is_nan() && value.is_nan()));
+    }
+    verify_roundtrip(&f32::MIN_POSITIVE).unwrap();
+    assert_eq!((-0.0_f64).encode(), "-0.0");
+}
+
+#[test]
+fn test_float_tokens_accepted_on_load() {
+    assert!(f64::decode("nan").unwrap().is_nan());
+    assert_eq!(f64::decode("+inf"), Ok(f64::INFINITY));
+    assert_eq!(f64::decode("Infinity"), Ok(f64::INFINITY));
+    assert_eq!(f64::decode("-INF"), Ok(f64::NEG_INFINITY));
+    assert!(f64::decode("1,5").is_err());
+}
+
+#[test]
+fn test_bool_char_and_string_roundtrips() {
+    for value in [true, false] {
+        verify_roundtrip(&value).unwrap();
+    }
+    for value in [';', '\\', '\n', 'é', '🦀'] {
+        verify_roundtrip(&value).unwrap();
+        assert_eq!(char::decode(&value.encode()), Ok(value));
+    }
+    let text = String::from("a;b\\c\nd");
+    assert_eq!(String::decode(&text.encode()), Ok(text));
+    assert!(char::decode("ab").is_err());
+    assert!(String::decode("bad\\escape").is_err());
+}
+
+#[test]
+fn test_newtypes_roundtrip() {
+    verify_roundtrip(&Meters(f64::NEG_INFINITY)).unwrap();
+    verify_roundtrip(&Marker).unwrap();
+    assert_eq!(Marker::decode(""), Ok(Marker));
+    assert!(Marker::decode("x").is_err());
+}
+
+#[test]
+fn test_encoded_file_roundtrip() {
+    let path = common::temp_path("encoded.txt");
+    let path = path.to_str().unwrap();
+    let data = vec![(';', Meters(f64::NAN)), ('\n', Meters(-0.0)), ('x', Meters(1e-300))];
+    FileStorage::save_encoded(path, 3, &data).unwrap();
+
+    let (capacity, loaded) = FileStorage::load_encoded::<char, Meters>(path).unwrap();
+    assert_eq!(capacity, 3);
+    assert_eq!(loaded.len(), 3);
+    assert_eq!(loaded[0].0, ';');
+    assert!(loaded[0].1 .0.is_nan());
+    assert_eq!(loaded[1], ('\n', Meters(-0.0)));
+    assert!(loaded[1].1 .0.is_sign_negative());
+    assert_eq!(loaded[2], ('x', Meters(1e-300)));
+    fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn test_encoded_load_reports_bad_line() {
+    let path = common::temp_path("encoded_bad.txt");
+    fs::write(&path, "2\na;1.5\nb;1,5\n").unwrap();
+
+    let error = FileStorage::load_encoded::<String, f64>(path.to_str().unwrap()).unwrap_err();
+    assert_eq!(error.kind(), std::io::ErrorKind::InvalidData);
+    assert!(error.to_string().starts_with("ligne 3"));
+    fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn test_base64_matches_reference_vectors() {
+    let vectors: [(&[u8], &str); 7] = [
+        (b"", ""),
+        (b"f", "Zg=="),
+        (b"fo", "Zm8="),
+        (b"foo", "Zm9v"),
+        (b"foob", "Zm9vYg=="),
+        (b"fooba", "Zm9vYmE="),
+        (b"foobar", "Zm9vYmFy"),
+    ];
+    for (bytes, encoded) in vectors {
+        assert_eq!(Base64Codec.encode(&bytes.to_vec()), encoded);
+        assert_eq!(Base64Codec.decode(encoded), Ok(bytes.to_vec()));
+    }
+    let all: Vec<u8> = (0..=255).collect();
+    assert_eq!(Base64Codec.decode(&Base64Codec.encode(&all)), Ok(all));
+    for invalid in ["Zg=", "Z===", "Zg==Zg==", "Zm9*"] {
+        assert!(Base64Codec.decode(invalid).is_err(), "{:?}", invalid);
+    }
+}
+
+#[test]
+fn test_codec_file_roundtrip_with_unsafe_bytes() {
+    let path = common::temp_path("codec_with.txt");
+    let mut cache = Cache::new(3);
+    cache.put(String::from("a;b"), b"x;y\nz\r\\".to_vec());
+    cache.put(String::from("vide"), Vec::new());
+    cache.put(String::from("binaire"), vec![0, 0xff, b';', b'\n']);
+    cache.save_to_file_with(&path, &DisplayCodec, &Base64Codec).unwrap();
+    assert!(!fs::read_to_string(&path).unwrap().contains("x;y"));
+
+    let capacity = NonZeroUsize::new(3).unwrap();
+    let loaded = Cache::<String, Vec<u8>>::load_from_file_with(&path, capacity, &DisplayCodec, &Base64Codec).unwrap();
+    assert_eq!(loaded.iter().collect::<Vec<_>>(), cache.iter().collect::<Vec<_>>());
+    fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn test_display_codec_matches_save_to_file() {
+    let with_codec = common::temp_path("codec_display.txt");
+    let plain = common::temp_path("codec_plain.txt");
+    let mut cache = Cache::with_clock(2, MockClock::new());
+    cache.put(1u32, String::from("un;deux"));
+    cache.put(2, String::from("trois"));
+    cache.save_to_file_with(&with_codec, &DisplayCodec, &DisplayCodec).unwrap();
+    cache.save_to_file(&plain).unwrap();
+    assert_eq!(fs::read(&with_codec).unwrap(), fs::read(&plain).unwrap());
+
+    let capacity = NonZeroUsize::new(2).unwrap();
+    let loaded = Cache::<u32, String>::load_from_file_with(&plain, capacity, &DisplayCodec, &DisplayCodec).unwrap();
+    assert_eq!(loaded.iter().collect::<Vec<_>>(), cache.iter().collect::<Vec<_>>());
+    fs::remove_file(&with_codec).unwrap();
+    fs::remove_file(&plain).unwrap();
+}
+
+#[test]
+fn test_codec_rejection_is_reported() {
+    let path = common::temp_path("codec_bad.txt");
+    FileStorage::save(&path, 2, &[("a", "Zg=="), ("b", "pas*base64")]).unwrap();
+    let capacity = NonZeroUsize::new(2).unwrap();
+    let error = Cache::<String, Vec<u8>>::load_from_file_with(&path, capacity, &DisplayCodec, &Base64Codec).unwrap_err();
+    match error {
+        StorageError::Codec(error) => assert_eq!(error.input, "pas*base64"),
+        other => panic!("erreur inattendue : {:?}", other),
+    }
+    fs::remove_file(&path).unwrap();
+}
@@ -0,0 +1,41 @@
+//! Suite de conformance partagée par les implémentations de `CacheStorage`
+//! et outils communs aux tests
+//!
+//! Chaque fichier de test compile ce module à part et n'en utilise qu'une
+//! partie.
+#![allow(dead_code)]
+
+use lru_cache::cache::traits::{CacheLen, CacheStorage};
+use std::env;
+use std::path::PathBuf;
+
+/// Chemin d'un fichier temporaire propre à ce processus
+pub fn temp_path(name: &str) -> PathBuf {
+    env::temp_dir().join(format!("lru_cache_{}_{}", std::process::id(), name))
+}
+
+/// Vérifie le contrat de base d'un cache borné construit par `make(capacity)`
+pub fn conformance<C, F>(make: F)
+where
+    C: CacheStorage<u32, u32> + CacheLen,
+    F: Fn(usize) -> C,
+{
+    let mut cache = make(3);
+    assert!(cache.is_empty());
+    assert_eq!(cache.get(&1), None);
+
+    cache.put(1, 10);
+    assert_eq!(cache.get(&1), Some(&10));
+    assert_eq!(cache.len(), 1);
+
+    cache.put(1, 11);
+    assert_eq!(cache.get(&1), Some(&11));
+    assert_eq!(cache.len(), 1);
+
+    for key in 2..50 {
+        cache.put(key, key * 10);
+        assert_eq!(cache.get(&key), Some(&(key * 10)));
+        assert!(cache.len() <= 3);
+    }
+    assert_eq!(cache.len(), 3);
+}
@@ -0,0 +1,77 @@
+#![cfg(feature = "compression")]
+
+mod common;
+
+use lru_cache::Cache;
+use lru_cache::cache::traits::{CacheIterate, CacheStorage, PersistentStorage};
+use lru_cache::storage::error::StorageError;
+use lru_cache::storage::file::{FileStorage, SaveOptions};
+use std::fs;
+use std::num::NonZeroUsize;
+
+fn large_data() -> Vec<(String, String)> {
+    (0..10_000)
+        .map(|i| (format!("user:{:05}", i), format!("profil de l'utilisateur {} ; groupe {}", i, i % 7)))
+        .collect()
+}
+
+#[test]
+fn test_compressed_round_trip_is_smaller() {
+    let plain = common::temp_path("large.txt");
+    let compressed = common::temp_path("large.txt.gz");
+    let data = large_data();
+    FileStorage::save(&plain, data.len(), &data).unwrap();
+    let report = FileStorage::save_compressed(&compressed, data.len(), &data, SaveOptions::default(), 6).unwrap();
+    assert_eq!(report.entries, data.len());
+
+    let plain_len = fs::metadata(&plain).unwrap().len();
+    let compressed_len = fs::metadata(&compressed).unwrap().len();
+    assert!(compressed_len * 4 < plain_len, "{} / {}", compressed_len, plain_len);
+
+    let (capacity, loaded) = FileStorage::load_compressed::<String, String>(&compressed).unwrap();
+    assert_eq!(capacity, data.len());
+    assert_eq!(loaded, data);
+    fs::remove_file(plain).unwrap();
+    fs::remove_file(compressed).unwrap();
+}
+
+#[test]
+fn test_load_detects_compression() {
+    let plain = common::temp_path("detect.txt");
+    let compressed = common::temp_path("detect.txt.gz");
+    let data = vec![(String::from("a;b"), 1), (String::from("c\nd"), 2)];
+    FileStorage::save(&plain, 2, &data).unwrap();
+    FileStorage::save_compressed(&compressed, 2, &data, SaveOptions::default(), 9).unwrap();
+
+    assert_eq!(FileStorage::load::<String, i32>(&plain).unwrap(), (2, data.clone()));
+    assert_eq!(FileStorage::load::<String, i32>(&compressed).unwrap(), (2, data));
+    fs::remove_file(plain).unwrap();
+    fs::remove_file(compressed).unwrap();
+}
+
+#[test]
+fn test_load_compressed_rejects_plain_file() {
+    let path = common::temp_path("not_gzip.txt");
+    FileStorage::save(&path, 1, &[(String::from("a"), 1)]).unwrap();
+
+    let error = FileStorage::load_compressed::<String, i32>(&path).unwrap_err();
+    assert!(matches!(error, StorageError::Io(ref error) if error.kind() == std::io::ErrorKind::InvalidData));
+    fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn test_cache_round_trip_gz() {
+    let path = common::temp_path("cache.gz");
+    let mut cache = Cache::new(3);
+    cache.put(String::from("a"), 1);
+    cache.put(String::from("b"), 2);
+    cache.put(String::from("c"), 3);
+    cache.get(&String::from("a"));
+    cache.save_to_file_gz(&path, 6).unwrap();
+
+    let loaded: Cache<String, i32> = Cache::load_from_file_gz(&path, NonZeroUsize::new(3).unwrap()).unwrap();
+    assert_eq!(loaded.iter().collect::<Vec<_>>(), cache.iter().collect::<Vec<_>>());
+    let reloaded: Cache<String, i32> = Cache::load_from_file(&path, NonZeroUsize::new(3).unwrap()).unwrap();
+    assert_eq!(reloaded.iter().collect::<Vec<_>>(), cache.iter().collect::<Vec<_>>());
+    fs::remove_file(path).unwrap();
+}
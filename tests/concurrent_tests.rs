@@ -0,0 +1,300 @@
+mod common;
+
+use lru_cache::Cache;
+use lru_cache::cache::clock::MockClock;
+use lru_cache::cache::concurrent::{ConcurrentCache, FlushPolicy};
+use lru_cache::cache::sharded::ShardedCache;
+use lru_cache::storage::file::FileStorage;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{mpsc, Arc, Barrier};
+use std::thread;
+use std::time::{Duration, Instant};
+
+#[test]
+fn test_concurrent_cache_shared_between_threads() {
+    let cache = Arc::new(ConcurrentCache::new(100));
+    let handles: Vec<_> = (0..4)
+        .map(|t| {
+            let cache = Arc::clone(&cache);
+            thread::spawn(move || {
+                for i in 0..25 {
+                    cache.put(t * 25 + i, i);
+                }
+            })
+        })
+        .collect();
+    for handle in handles {
+        handle.join().unwrap();
+    }
+
+    assert_eq!(cache.len(), 100);
+    assert_eq!(cache.get(&30), Some(5));
+}
+
+#[test]
+fn test_expiration_thread_purges_without_get() {
+    let clock = MockClock::new();
+    let mut cache = ConcurrentCache::from_cache(Cache::with_clock(10, clock.clone()));
+    cache.put(1, "permanent");
+    cache.lock().put_with_ttl(2, "short", Duration::from_secs(5));
+    cache.lock().put_with_ttl(3, "short", Duration::from_secs(5));
+    cache.spawn_expiration_thread(Duration::from_millis(5));
+
+    clock.advance(Duration::from_secs(10));
+    let deadline = Instant::now() + Duration::from_secs(5);
+    while cache.len() > 1 && Instant::now() < deadline {
+        thread::sleep(Duration::from_millis(5));
+    }
+
+    assert_eq!(cache.len(), 1);
+    cache.stop_expiration_thread();
+}
+
+#[test]
+fn test_drop_joins_expiration_thread() {
+    let mut cache: ConcurrentCache<u32, u32> = ConcurrentCache::new(10);
+    cache.spawn_expiration_thread(Duration::from_secs(3600));
+    let started = Instant::now();
+    drop(cache);
+
+    assert!(started.elapsed() < Duration::from_secs(60));
+}
+
+#[test]
+fn test_sharded_cache_rounds_and_divides_capacity() {
+    let cache = ShardedCache::<u32, u32>::with_shards(10, 3);
+    assert_eq!(cache.shard_count(), 4);
+    assert_eq!(cache.capacity(), 10);
+
+    let small = ShardedCache::<u32, u32>::with_shards(3, 8);
+    assert_eq!(small.shard_count(), 2);
+    assert_eq!(small.capacity(), 3);
+}
+
+#[test]
+fn test_sharded_cache_shared_between_threads() {
+    let cache = Arc::new(ShardedCache::with_shards(1000, 8));
+    let handles: Vec<_> = (0..8)
+        .map(|t| {
+            let cache = Arc::clone(&cache);
+            thread::spawn(move || {
+                for i in 0..50 {
+                    cache.put(t * 50 + i, i);
+                    assert_eq!(cache.get(&(t * 50 + i)), Some(i));
+                }
+            })
+        })
+        .collect();
+    for handle in handles {
+        handle.join().unwrap();
+    }
+
+    assert_eq!(cache.len(), 400);
+    assert_eq!(cache.get(&130), Some(30));
+    assert_eq!(cache.remove(&130), Some(30));
+    let stats = cache.stats();
+    assert_eq!((stats.hits, stats.insertions, stats.evictions), (401, 400, 0));
+}
+
+#[test]
+fn test_sharded_cache_never_exceeds_capacity() {
+    let cache = Arc::new(ShardedCache::with_shards(64, 4));
+    let handles: Vec<_> = (0..4)
+        .map(|t| {
+            let cache = Arc::clone(&cache);
+            thread::spawn(move || {
+                for i in 0..1000 {
+                    cache.put(t * 1000 + i, i);
+                }
+            })
+        })
+        .collect();
+    for handle in handles {
+        handle.join().unwrap();
+    }
+
+    assert_eq!(cache.len(), 64);
+    assert_eq!(cache.stats().evictions, 4000 - 64);
+}
+
+#[test]
+fn test_get_or_insert_with_runs_loader_once_for_concurrent_misses() {
+    let cache = Arc::new(ConcurrentCache::new(8));
+    let loads = Arc::new(AtomicUsize::new(0));
+    let barrier = Arc::new(Barrier::new(50));
+    let handles: Vec<_> = (0..50)
+        .map(|_| {
+            let cache = Arc::clone(&cache);
+            let loads = Arc::clone(&loads);
+            let barrier = Arc::clone(&barrier);
+            thread::spawn(move || {
+                barrier.wait();
+                cache.get_or_insert_with("key", || {
+                    loads.fetch_add(1, Ordering::SeqCst);
+                    thread::sleep(Duration::from_millis(50));
+                    42
+                })
+            })
+        })
+        .collect();
+    for handle in handles {
+        assert_eq!(handle.join().unwrap(), 42);
+    }
+
+    assert_eq!(loads.load(Ordering::SeqCst), 1);
+    assert_eq!(cache.get(&"key"), Some(42));
+}
+
+#[test]
+fn test_get_or_insert_with_waiters_survive_loader_panic() {
+    let cache = Arc::new(ConcurrentCache::new(8));
+    let (started, loading) = mpsc::channel();
+    let failing = {
+        let cache = Arc::clone(&cache);
+        thread::spawn(move || {
+            cache.get_or_insert_with("key", || {
+                started.send(()).unwrap();
+                thread::sleep(Duration::from_millis(50));
+                panic!("chargement en échec")
+            })
+        })
+    };
+    loading.recv().unwrap();
+    let loads = Arc::new(AtomicUsize::new(0));
+    let waiters: Vec<_> = (0..8)
+        .map(|_| {
+            let cache = Arc::clone(&cache);
+            let loads = Arc::clone(&loads);
+            thread::spawn(move || {
+                cache.get_or_insert_with("key", || {
+                    loads.fetch_add(1, Ordering::SeqCst);
+                    7
+                })
+            })
+        })
+        .collect();
+
+    assert!(failing.join().is_err());
+    for waiter in waiters {
+        assert_eq!(waiter.join().unwrap(), 7);
+    }
+    assert_eq!(loads.load(Ordering::SeqCst), 1);
+}
+
+fn flush_path(name: &str) -> PathBuf {
+    let path = common::temp_path(&format!("{}.txt", name));
+    let _ = fs::remove_file(&path);
+    path
+}
+
+/// Attend que `path` contienne exactement `expected`
+fn wait_for_file(path: &Path, expected: &[(String, u32)]) {
+    let deadline = Instant::now() + Duration::from_secs(5);
+    loop {
+        if let Ok((_, entries)) = FileStorage::load::<String, u32>(path) {
+            if entries == expected {
+                return;
+            }
+        }
+        assert!(Instant::now() < deadline, "sauvegarde attendue dans {}", path.display());
+        thread::sleep(Duration::from_millis(5));
+    }
+}
+
+fn entries(pairs: &[(&str, u32)]) -> Vec<(String, u32)> {
+    pairs.iter().map(|&(key, value)| (key.to_string(), value)).collect()
+}
+
+#[test]
+fn test_persistence_flushes_after_max_dirty_ops() {
+    let path = flush_path("flush_ops");
+    let policy = FlushPolicy {
+        interval: Duration::from_secs(3600),
+        max_dirty_ops: 3,
+    };
+    let cache = ConcurrentCache::new(10).with_persistence(&path, policy);
+    cache.put("a".to_string(), 1);
+    cache.put("b".to_string(), 2);
+    thread::sleep(Duration::from_millis(50));
+    assert!(!path.exists());
+
+    cache.put("c".to_string(), 3);
+    wait_for_file(&path, &entries(&[("a", 1), ("b", 2), ("c", 3)]));
+    assert!(cache.last_flush_error().is_none());
+    drop(cache);
+    fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn test_persistence_flushes_every_interval() {
+    let path = flush_path("flush_interval");
+    let policy = FlushPolicy {
+        interval: Duration::from_millis(10),
+        max_dirty_ops: 0,
+    };
+    let cache = ConcurrentCache::new(10).with_persistence(&path, policy);
+    cache.put("a".to_string(), 1);
+    wait_for_file(&path, &entries(&[("a", 1)]));
+
+    cache.put("b".to_string(), 2);
+    assert_eq!(cache.remove(&"a".to_string()), Some(1));
+    wait_for_file(&path, &entries(&[("b", 2)]));
+    drop(cache);
+    fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn test_persistence_flushes_on_drop() {
+    let path = flush_path("flush_drop");
+    let policy = FlushPolicy {
+        interval: Duration::from_secs(3600),
+        max_dirty_ops: 0,
+    };
+    let cache = ConcurrentCache::new(2).with_persistence(&path, policy);
+    cache.put("a".to_string(), 1);
+    cache.put("b".to_string(), 2);
+    cache.put("c".to_string(), 3);
+    drop(cache);
+
+    let (capacity, loaded) = FileStorage::load::<String, u32>(&path).unwrap();
+    assert_eq!(capacity, 2);
+    assert_eq!(loaded, entries(&[("b", 2), ("c", 3)]));
+    fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn test_persistence_failed_flush_is_reported_and_retried() {
+    let dir = common::temp_path("flush_retry");
+    let _ = fs::remove_dir_all(&dir);
+    let path = dir.join("cache.txt");
+    let policy = FlushPolicy {
+        interval: Duration::from_millis(10),
+        max_dirty_ops: 0,
+    };
+    let cache = ConcurrentCache::new(10).with_persistence(&path, policy);
+    cache.put("a".to_string(), 1);
+    let error = cache.flush_now().unwrap_err();
+    assert_eq!(error.kind(), std::io::ErrorKind::NotFound);
+    assert_eq!(cache.last_flush_error().map(|error| error.kind()), Some(error.kind()));
+
+    // Sans nouvelle modification, l'essai suivant réussit dès que possible
+    fs::create_dir(&dir).unwrap();
+    wait_for_file(&path, &entries(&[("a", 1)]));
+    let deadline = Instant::now() + Duration::from_secs(5);
+    while cache.last_flush_error().is_some() {
+        assert!(Instant::now() < deadline);
+        thread::sleep(Duration::from_millis(5));
+    }
+    drop(cache);
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn test_flush_now_without_persistence_does_nothing() {
+    let cache: ConcurrentCache<u32, u32> = ConcurrentCache::new(1);
+    cache.put(1, 1);
+    assert!(cache.flush_now().is_ok());
+    assert!(cache.last_flush_error().is_none());
+}
@@ -0,0 +1,65 @@
+//! Fonctionnalités de `Cache` disponibles sans la feature `std` : ces tests
+//! n'utilisent que `core`, `alloc` et les chemins communs aux deux modes
+//! (`cache::clock::Instant`)
+
+extern crate alloc;
+
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::sync::atomic::{AtomicU64, Ordering};
+use core::time::Duration;
+use lru_cache::Cache;
+use lru_cache::cache::clock::{Clock, Instant};
+use lru_cache::cache::traits::{CacheIterate, CacheLen, CacheRemove, CacheStorage};
+
+/// Horloge à ticks d'une milliseconde, comme celle d'une minuterie
+/// matérielle
+#[derive(Debug)]
+struct TickClock {
+    origin: Instant,
+    ticks: AtomicU64,
+}
+
+impl Clock for TickClock {
+    fn now(&self) -> Instant {
+        self.origin + Duration::from_millis(self.ticks.load(Ordering::Relaxed))
+    }
+}
+
+#[test]
+fn test_core_operations() {
+    let mut cache = Cache::new(2);
+    cache.put(String::from("a"), alloc::vec![1]);
+    cache.put(String::from("b"), alloc::vec![2]);
+    cache.get(&String::from("a"));
+    cache.put(String::from("c"), alloc::vec![3]);
+    assert_eq!(cache.iter().map(|(key, _)| key.as_str()).collect::<Vec<_>>(), ["a", "c"]);
+    assert_eq!(cache.remove(&String::from("a")), Some(alloc::vec![1]));
+    assert_eq!(cache.len(), 1);
+
+    let restored = Cache::restore(cache.snapshot());
+    assert_eq!(restored.peek(&String::from("c")), Some(&alloc::vec![3]));
+}
+
+#[test]
+fn test_custom_clock_drives_expiration() {
+    let clock = alloc::sync::Arc::new(TickClock {
+        origin: Instant::now(),
+        ticks: AtomicU64::new(0),
+    });
+    let mut cache = Cache::with_clock(2, SharedTicks(alloc::sync::Arc::clone(&clock)));
+    cache.put_with_ttl("key", 1, Duration::from_millis(10));
+    clock.ticks.store(9, Ordering::Relaxed);
+    assert_eq!(cache.get(&"key"), Some(&1));
+    clock.ticks.store(10, Ordering::Relaxed);
+    assert_eq!(cache.get(&"key"), None);
+}
+
+#[derive(Debug)]
+struct SharedTicks(alloc::sync::Arc<TickClock>);
+
+impl Clock for SharedTicks {
+    fn now(&self) -> Instant {
+        self.0.now()
+    }
+}
@@ -0,0 +1,134 @@
+use lru_cache::Cache;
+use lru_cache::cache::clock::MockClock;
+use lru_cache::cache::traits::{CacheIterate, CacheLen, CacheStorage};
+use lru_cache::storage::error::StorageError;
+use std::num::NonZeroUsize;
+use std::time::Duration;
+
+fn capacity(n: usize) -> NonZeroUsize {
+    NonZeroUsize::new(n).unwrap()
+}
+
+fn export(cache: &Cache<String, String>) -> String {
+    let mut out = Vec::new();
+    cache.export_csv(&mut out).unwrap();
+    String::from_utf8(out).unwrap()
+}
+
+#[test]
+fn test_csv_roundtrip_special_characters() {
+    let mut cache = Cache::new(4);
+    cache.put(String::from("plain"), String::from("value"));
+    cache.put(String::from("a,b"), String::from("say \"hi\""));
+    cache.put(String::from("multi\nline"), String::from("crlf\r\nend"));
+    cache.put(String::new(), String::from("\"\""));
+
+    let text = export(&cache);
+    assert!(text.starts_with("key,value\r\n"));
+    assert!(text.contains("\"a,b\",\"say \"\"hi\"\"\"\r\n"));
+
+    let imported = Cache::<String, String>::import_csv(text.as_bytes(), capacity(4)).unwrap();
+    assert_eq!(imported.iter().collect::<Vec<_>>(), cache.iter().collect::<Vec<_>>());
+}
+
+#[test]
+fn test_csv_keeps_recency_order() {
+    let mut cache = Cache::new(3);
+    cache.put(String::from("a"), String::from("1"));
+    cache.put(String::from("b"), String::from("2"));
+    cache.put(String::from("c"), String::from("3"));
+    cache.get(&String::from("a"));
+
+    assert_eq!(export(&cache), "key,value\r\nb,2\r\nc,3\r\na,1\r\n");
+    let mut imported = Cache::<String, String>::import_csv(export(&cache).as_bytes(), capacity(2)).unwrap();
+    assert_eq!(imported.len(), 2);
+    assert!(!imported.contains_key(&String::from("b")));
+    imported.put(String::from("d"), String::from("4"));
+    assert!(!imported.contains_key(&String::from("c")));
+}
+
+#[test]
+fn test_csv_optional_columns() {
+    let clock = MockClock::new();
+    let mut cache = Cache::with_clock(3, clock.clone());
+    cache.track_metadata();
+    cache.put(String::from("short"), String::from("1"));
+    cache.put_with_ttl(String::from("timed"), String::from("2"), Duration::from_secs(10));
+    cache.get(&String::from("short"));
+    cache.get(&String::from("short"));
+    clock.advance(Duration::from_secs(4));
+
+    assert_eq!(
+        export(&cache),
+        "key,value,access_count,ttl_ms\r\ntimed,2,0,6000\r\nshort,1,2,\r\n"
+    );
+    clock.advance(Duration::from_secs(6));
+    assert_eq!(export(&cache), "key,value,access_count,ttl_ms\r\nshort,1,2,\r\n");
+}
+
+#[test]
+fn test_csv_import_ttl_column() {
+    let text = "value,ttl_ms,key\nfresh,,a\nsoon,1000,b\n";
+    let mut cache = Cache::<String, String>::import_csv(text.as_bytes(), capacity(2)).unwrap();
+    assert_eq!(cache.get(&String::from("a")), Some(&String::from("fresh")));
+    assert!(cache.ttl_remaining(&String::from("a")).is_none());
+    assert!(cache.ttl_remaining(&String::from("b")).is_some_and(|ttl| ttl <= Duration::from_secs(1)));
+}
+
+#[test]
+fn test_csv_import_rejects_bad_rows() {
+    let wrong_count = "key,value\r\na,1\r\nb,2,3\r\n";
+    match Cache::<String, String>::import_csv(wrong_count.as_bytes(), capacity(2)) {
+        Err(StorageError::InvalidEntry { line, content }) => {
+            assert_eq!(line, 3);
+            assert_eq!(content, "b,2,3");
+        }
+        other => panic!("unexpected result: {other:?}"),
+    }
+
+    let unclosed = "key,value\n\"a\nb,1\n";
+    assert!(matches!(
+        Cache::<String, String>::import_csv(unclosed.as_bytes(), capacity(2)),
+        Err(StorageError::InvalidEntry { line: 2, .. })
+    ));
+
+    let trailing = "key,value\n\"a\"x,1\n";
+    assert!(matches!(
+        Cache::<String, String>::import_csv(trailing.as_bytes(), capacity(2)),
+        Err(StorageError::InvalidEntry { line: 2, .. })
+    ));
+
+    let unparsable = "key,value\nx,1\n";
+    assert!(matches!(
+        Cache::<u32, u32>::import_csv(unparsable.as_bytes(), capacity(2)),
+        Err(StorageError::InvalidEntry { line: 2, .. })
+    ));
+
+    let bad_ttl = "key,value,ttl_ms\na,1,-5\n";
+    assert!(matches!(
+        Cache::<String, String>::import_csv(bad_ttl.as_bytes(), capacity(2)),
+        Err(StorageError::InvalidEntry { line: 2, .. })
+    ));
+}
+
+#[test]
+fn test_csv_import_rejects_bad_header() {
+    for text in ["", "value\na\n", "key,value,extra\na,1,2\n"] {
+        assert!(matches!(
+            Cache::<String, String>::import_csv(text.as_bytes(), capacity(2)),
+            Err(StorageError::InvalidEntry { line: 1, .. })
+        ));
+    }
+}
+
+#[test]
+fn test_csv_multiline_field_line_numbers() {
+    let text = "key,value\n\"a\nb\",1\nc\n";
+    match Cache::<String, String>::import_csv(text.as_bytes(), capacity(2)) {
+        Err(StorageError::InvalidEntry { line, content }) => {
+            assert_eq!(line, 4);
+            assert_eq!(content, "c");
+        }
+        other => panic!("unexpected result: {other:?}"),
+    }
+}
@@ -0,0 +1,132 @@
+use lru_cache::Cache;
+use lru_cache::cache::equivalent::CaseInsensitive;
+use lru_cache::cache::traits::{CacheIterate, CacheLen, CacheRemove, CacheStorage};
+
+fn header(name: &str) -> CaseInsensitive<String> {
+    CaseInsensitive::from(name)
+}
+
+fn spellings(cache: &Cache<CaseInsensitive<String>, u32>) -> Vec<String> {
+    cache.iter().map(|(key, _)| key.as_str().to_string()).collect()
+}
+
+#[test]
+fn test_lookups_ignore_case() {
+    let mut cache = Cache::new(4);
+    cache.put(header("Content-Type"), 1);
+
+    assert_eq!(cache.get_by(&CaseInsensitive("content-type")), Some(&1));
+    assert_eq!(cache.get(&header("CONTENT-TYPE")), Some(&1));
+    assert_eq!(cache.peek_by(&CaseInsensitive("cOnTeNt-TyPe")), Some(&1));
+    assert!(cache.contains_key_by(&CaseInsensitive("CONTENT-type")));
+    assert!(!cache.contains_key_by(&CaseInsensitive("Content-Length")));
+    assert_eq!(cache.get_by(&CaseInsensitive("Content-Length")), None);
+    assert_eq!(cache.stats().hits, 2);
+    assert_eq!(cache.stats().misses, 1);
+}
+
+#[test]
+fn test_put_with_other_case_updates_existing_entry() {
+    let mut cache = Cache::new(4);
+    cache.put(header("FOO"), 1);
+    cache.put(header("foo"), 2);
+    cache.put(header("Foo"), 3);
+
+    assert_eq!(cache.len(), 1);
+    assert_eq!(cache.get_by(&CaseInsensitive("fOO")), Some(&3));
+    assert_eq!(spellings(&cache), vec!["FOO"]);
+}
+
+#[test]
+fn test_remove_ignores_case() {
+    let mut cache = Cache::new(4);
+    cache.put(header("Accept"), 1);
+    cache.put(header("Host"), 2);
+
+    assert_eq!(cache.remove_by(&CaseInsensitive("ACCEPT")), Some(1));
+    assert_eq!(cache.remove_by(&CaseInsensitive("accept")), None);
+    assert_eq!(cache.remove(&header("HOST")), Some(2));
+    assert!(cache.is_empty());
+}
+
+#[test]
+fn test_other_case_counts_as_use_for_eviction() {
+    let mut cache = Cache::new(2);
+    cache.put(header("Accept"), 1);
+    cache.put(header("Host"), 2);
+
+    // Lire « ACCEPT » rafraîchit « Accept », « Host » devient la plus ancienne
+    assert_eq!(cache.get_by(&CaseInsensitive("ACCEPT")), Some(&1));
+    cache.put(header("Date"), 3);
+    assert!(!cache.contains_key_by(&CaseInsensitive("host")));
+    assert_eq!(spellings(&cache), vec!["Accept", "Date"]);
+
+    // Une mise à jour dans une autre casse rafraîchit aussi l'entrée
+    cache.put(header("accept"), 4);
+    cache.put(header("Etag"), 5);
+    assert!(!cache.contains_key_by(&CaseInsensitive("DATE")));
+    assert_eq!(spellings(&cache), vec!["Accept", "Etag"]);
+    assert_eq!(cache.peek_by(&CaseInsensitive("ACCEPT")), Some(&4));
+}
+
+#[test]
+fn test_borrowed_lookup_of_string_keys() {
+    let mut cache = Cache::new(2);
+    cache.put(String::from("clé"), 1);
+    assert_eq!(cache.get_by("clé"), Some(&1));
+    assert_eq!(cache.peek_by("CLÉ"), None);
+    assert_eq!(cache.remove_by("clé"), Some(1));
+}
+
+#[test]
+fn test_non_ascii_letters_keep_their_case() {
+    assert_eq!(CaseInsensitive("Éta"), CaseInsensitive("ÉTA"));
+    assert_ne!(CaseInsensitive("Été"), CaseInsensitive("été"));
+}
+
+#[test]
+fn test_get_key_value_returns_stored_key_and_promotes() {
+    let mut cache = Cache::new(2);
+    cache.put(String::from("Paris"), 1);
+    cache.put(String::from("Lyon"), 2);
+
+    let (key, value) = cache.get_key_value("Paris").unwrap();
+    assert_eq!((key.as_str(), *value), ("Paris", 1));
+    cache.put(String::from("Nice"), 3);
+    assert!(cache.contains_key_by("Paris"));
+    assert!(!cache.contains_key_by("Lyon"));
+    assert_eq!(cache.get_key_value("Lyon"), None);
+    assert_eq!(cache.stats().misses, 1);
+}
+
+#[test]
+fn test_peek_key_value_does_not_promote() {
+    let mut cache = Cache::new(2);
+    cache.put(header("X-Request-Id"), 1);
+    cache.put(header("Accept"), 2);
+
+    let (key, value) = cache.peek_key_value(&CaseInsensitive("x-request-id")).unwrap();
+    assert_eq!((key.as_str(), *value), ("X-Request-Id", 1));
+    cache.put(header("Host"), 3);
+    assert_eq!(cache.peek_key_value(&CaseInsensitive("X-REQUEST-ID")), None);
+    assert_eq!(spellings(&cache), vec!["Accept", "Host"]);
+}
+
+#[test]
+fn test_remove_entry_gives_back_inserted_key() {
+    let mut cache = Cache::new(2);
+    let key = String::from("Bordeaux");
+    let address = key.as_ptr();
+    cache.put(key, 1);
+
+    let (removed, value) = cache.remove_entry("Bordeaux").unwrap();
+    assert_eq!((removed.as_str(), value), ("Bordeaux", 1));
+    assert_eq!(removed.as_ptr(), address);
+    assert!(cache.is_empty());
+    assert_eq!(cache.remove_entry("Bordeaux"), None);
+
+    let mut headers = Cache::new(2);
+    headers.put(header("Content-Type"), 7);
+    let (removed, _) = headers.remove_entry(&CaseInsensitive("content-type")).unwrap();
+    assert_eq!(removed.into_inner(), "Content-Type");
+}
@@ -0,0 +1,98 @@
+mod common;
+
+use lru_cache::ffi::*;
+use std::env;
+use std::ffi::{c_char, CStr, CString};
+use std::fs;
+use std::ptr;
+
+fn c(s: &str) -> CString {
+    CString::new(s).unwrap()
+}
+
+/// Lit et libère une chaîne retournée par `lru_cache_get`
+unsafe fn take(s: *mut c_char) -> Option<String> {
+    if s.is_null() {
+        return None;
+    }
+    let value = CStr::from_ptr(s).to_str().unwrap().to_string();
+    lru_cache_string_free(s);
+    Some(value)
+}
+
+#[test]
+fn test_ffi_put_get_remove() {
+    unsafe {
+        let cache = lru_cache_new(2);
+        assert!(!cache.is_null());
+        assert_eq!(lru_cache_put(cache, c("a").as_ptr(), c("1").as_ptr()), LruCacheStatus::Ok);
+        assert_eq!(lru_cache_put(cache, c("b").as_ptr(), c("2").as_ptr()), LruCacheStatus::Ok);
+        assert_eq!(take(lru_cache_get(cache, c("a").as_ptr())), Some(String::from("1")));
+
+        assert_eq!(lru_cache_put(cache, c("c").as_ptr(), c("3").as_ptr()), LruCacheStatus::Ok);
+        assert_eq!(take(lru_cache_get(cache, c("b").as_ptr())), None);
+        assert_eq!(lru_cache_len(cache), 2);
+
+        assert_eq!(lru_cache_remove(cache, c("a").as_ptr()), LruCacheStatus::Ok);
+        assert_eq!(lru_cache_remove(cache, c("a").as_ptr()), LruCacheStatus::NotFound);
+        assert_eq!(lru_cache_len(cache), 1);
+        lru_cache_free(cache);
+    }
+}
+
+#[test]
+fn test_ffi_null_and_invalid_arguments() {
+    unsafe {
+        assert!(lru_cache_new(0).is_null());
+        let cache = lru_cache_new(1);
+        let key = c("k");
+        assert_eq!(lru_cache_put(ptr::null_mut(), key.as_ptr(), key.as_ptr()), LruCacheStatus::NullPointer);
+        assert_eq!(lru_cache_put(cache, ptr::null(), key.as_ptr()), LruCacheStatus::NullPointer);
+        assert_eq!(lru_cache_put(cache, key.as_ptr(), ptr::null()), LruCacheStatus::NullPointer);
+        assert!(lru_cache_get(ptr::null_mut(), key.as_ptr()).is_null());
+        assert!(lru_cache_get(cache, ptr::null()).is_null());
+        assert_eq!(lru_cache_remove(cache, ptr::null()), LruCacheStatus::NullPointer);
+        assert_eq!(lru_cache_len(ptr::null()), 0);
+        assert_eq!(lru_cache_save(cache, ptr::null()), LruCacheStatus::NullPointer);
+        assert!(lru_cache_load(ptr::null(), 1).is_null());
+
+        let invalid = CString::from_vec_unchecked(vec![0xff, 0xfe]);
+        assert_eq!(lru_cache_put(cache, invalid.as_ptr(), key.as_ptr()), LruCacheStatus::InvalidUtf8);
+        assert_eq!(lru_cache_len(cache), 0);
+
+        lru_cache_string_free(ptr::null_mut());
+        lru_cache_free(ptr::null_mut());
+        lru_cache_free(cache);
+    }
+}
+
+#[test]
+fn test_ffi_save_and_load() {
+    let path = common::temp_path("ffi.txt");
+    let c_path = c(path.to_str().unwrap());
+    unsafe {
+        let cache = lru_cache_new(3);
+        lru_cache_put(cache, c("clé").as_ptr(), c("valeur, avec\tséparateurs").as_ptr());
+        lru_cache_put(cache, c("b").as_ptr(), c("2").as_ptr());
+        assert_eq!(lru_cache_save(cache, c_path.as_ptr()), LruCacheStatus::Ok);
+        lru_cache_free(cache);
+
+        assert!(lru_cache_load(c_path.as_ptr(), 0).is_null());
+        let loaded = lru_cache_load(c_path.as_ptr(), 3);
+        assert!(!loaded.is_null());
+        assert_eq!(lru_cache_len(loaded), 2);
+        assert_eq!(
+            take(lru_cache_get(loaded, c("clé").as_ptr())),
+            Some(String::from("valeur, avec\tséparateurs"))
+        );
+        lru_cache_free(loaded);
+
+        let missing = c(path.with_extension("absent").to_str().unwrap());
+        assert!(lru_cache_load(missing.as_ptr(), 3).is_null());
+        let directory = c(env::temp_dir().to_str().unwrap());
+        let empty = lru_cache_new(1);
+        assert_eq!(lru_cache_save(empty, directory.as_ptr()), LruCacheStatus::Io);
+        lru_cache_free(empty);
+    }
+    let _ = fs::remove_file(&path);
+}
@@ -0,0 +1,103 @@
+mod common;
+
+use lru_cache::Cache;
+use lru_cache::cache::clock::MockClock;
+use lru_cache::cache::traits::{CacheStorage, PersistentStorage};
+use lru_cache::storage::file::FileStorage;
+use lru_cache::storage::format::FORMAT_SPEC_VERSION;
+use std::fs;
+use std::path::PathBuf;
+use std::time::{Duration, UNIX_EPOCH};
+
+/// Horloge fixée au 14 novembre 2023, 22:13:20 UTC
+fn golden_clock() -> MockClock {
+    let clock = MockClock::new();
+    clock.advance(Duration::from_secs(1_700_000_000));
+    clock
+}
+
+fn assert_golden(path: &PathBuf, golden: &[u8]) {
+    let written = fs::read(path).unwrap();
+    fs::remove_file(path).unwrap();
+    assert_eq!(
+        written,
+        golden,
+        "format modifié :\n{}",
+        String::from_utf8_lossy(&written)
+    );
+}
+
+#[test]
+fn test_format_spec_version() {
+    assert_eq!(FORMAT_SPEC_VERSION, 7);
+}
+
+#[test]
+fn test_golden_plain_strings() {
+    let path = common::temp_path("golden_plain.txt");
+    let data = vec![("key1", "value1"), ("key2", "value2")];
+    FileStorage::save(path.to_str().unwrap(), 2, &data).unwrap();
+    assert_golden(&path, include_bytes!("golden/plain_strings.txt"));
+}
+
+#[test]
+fn test_golden_lru_order() {
+    let path = common::temp_path("golden_lru.txt");
+    let mut cache = Cache::with_clock(3, golden_clock());
+    cache.put(String::from("a"), 1);
+    cache.put(String::from("b"), 2);
+    cache.put(String::from("c"), 3);
+    cache.get(&String::from("a"));
+    cache.save_to_file(path.to_str().unwrap()).unwrap();
+    assert_golden(&path, include_bytes!("golden/lru_order.txt"));
+}
+
+#[test]
+fn test_golden_empty() {
+    let path = common::temp_path("golden_empty.txt");
+    Cache::<String, i32>::with_clock(1, golden_clock())
+        .save_to_file(path.to_str().unwrap())
+        .unwrap();
+    assert_golden(&path, include_bytes!("golden/empty.txt"));
+}
+
+#[test]
+fn test_golden_encoded_scalars() {
+    let path = common::temp_path("golden_encoded.txt");
+    let data = vec![
+        (String::from("a;b"), f64::NAN),
+        (String::from("line\nbreak"), -0.0),
+        (String::from("back\\slash"), f64::INFINITY),
+        (String::from("é"), 1e-300),
+    ];
+    FileStorage::save_encoded(path.to_str().unwrap(), 4, &data).unwrap();
+    assert_golden(&path, include_bytes!("golden/encoded_scalars.txt"));
+}
+
+#[test]
+fn test_golden_files_read_back() {
+    let golden = env!("CARGO_MANIFEST_DIR");
+    let (capacity, data) = FileStorage::load::<String, i32>(&format!("{}/tests/golden/lru_order.txt", golden)).unwrap();
+    assert_eq!(capacity, 3);
+    assert_eq!(data[2], (String::from("a"), 1));
+
+    let (capacity, data) =
+        FileStorage::load_encoded::<String, f64>(&format!("{}/tests/golden/encoded_scalars.txt", golden)).unwrap();
+    assert_eq!(capacity, 4);
+    assert_eq!(data[0].0, "a;b");
+    assert!(data[0].1.is_nan());
+    assert_eq!(data[1].0, "line\nbreak");
+    assert_eq!(data[2], (String::from("back\\slash"), f64::INFINITY));
+}
+
+#[test]
+fn test_golden_headers_read_back() {
+    let golden = env!("CARGO_MANIFEST_DIR");
+    let metadata = FileStorage::metadata(format!("{}/tests/golden/lru_order.txt", golden)).unwrap();
+    assert_eq!(metadata.capacity, 3);
+    assert_eq!(metadata.saved_at, Some(UNIX_EPOCH + Duration::from_secs(1_700_000_000)));
+
+    let metadata = FileStorage::metadata(format!("{}/tests/golden/plain_strings.txt", golden)).unwrap();
+    assert_eq!(metadata.capacity, 2);
+    assert_eq!(metadata.saved_at, None);
+}
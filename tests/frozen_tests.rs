@@ -0,0 +1,52 @@
+use lru_cache::Cache;
+use lru_cache::cache::frozen::FrozenCache;
+use lru_cache::cache::traits::{CacheIterate, CacheLen, CacheStorage};
+use std::sync::Arc;
+use std::thread;
+
+fn assert_sync<T: Sync + Send>() {}
+
+#[test]
+fn test_frozen_cache_is_shared_then_thawed_in_order() {
+    assert_sync::<FrozenCache<String, Vec<u8>>>();
+    let mut cache = Cache::new(8);
+    for key in 0..8 {
+        cache.put(key, key * 10);
+    }
+    cache.get(&2);
+    cache.get_shared(&5);
+    cache.pin(&0);
+    let stats = cache.stats();
+
+    let frozen = Arc::new(cache.freeze());
+    let order: Vec<u32> = frozen.iter().map(|(key, _)| *key).collect();
+    assert_eq!(order, [0, 1, 3, 4, 6, 7, 2, 5]);
+    assert_eq!(frozen.peek_lru(), Some((&0, &0)));
+
+    let readers: Vec<_> = (0..4)
+        .map(|reader| {
+            let frozen = Arc::clone(&frozen);
+            thread::spawn(move || {
+                for key in (reader..20).step_by(4) {
+                    assert_eq!(frozen.get(&key), (key < 8).then(|| key * 10).as_ref());
+                    assert_eq!(frozen.contains_key(&key), key < 8);
+                }
+                frozen.len()
+            })
+        })
+        .collect();
+    for reader in readers {
+        assert_eq!(reader.join().unwrap(), 8);
+    }
+
+    let mut cache = Arc::try_unwrap(frozen).unwrap().thaw();
+    assert_eq!(cache.iter().map(|(key, _)| *key).collect::<Vec<_>>(), order);
+    assert_eq!(cache.stats(), stats);
+    assert_eq!(cache.pinned_len(), 1);
+    cache.put(8, 80);
+    cache.put(9, 90);
+    assert_eq!(cache.len(), 8);
+    assert!(cache.contains_key(&0));
+    assert!(!cache.contains_key(&1));
+    assert!(!cache.contains_key(&3));
+}
@@ -0,0 +1,73 @@
+use lru_cache::Cache;
+use lru_cache::cache::traits::CacheStorage;
+
+/// Parcourt cycliquement `distinct` clés en lisant puis en insérant sur
+/// défaut
+fn cycle(cache: &mut Cache<u32, u32>, distinct: u32, rounds: u32) {
+    for _ in 0..rounds {
+        for key in 0..distinct {
+            if cache.get(&key).is_none() {
+                cache.put(key, key);
+            }
+        }
+    }
+}
+
+#[test]
+fn test_ghost_hits_count_misses_a_larger_cache_would_serve() {
+    // 6 clés en boucle dans 4 entrées : LRU manque tout, 4 + 2 aurait tout
+    // trouvé après le premier tour
+    let mut cache = Cache::with_ghost_capacity(4, 2);
+    cycle(&mut cache, 6, 5);
+    let stats = cache.stats();
+    assert_eq!(stats.hits, 0);
+    assert_eq!(stats.misses, 30);
+    assert_eq!(stats.ghost_hits, 24);
+
+    let mut small = Cache::with_ghost_capacity(4, 1);
+    cycle(&mut small, 6, 5);
+    assert_eq!(small.stats().ghost_hits, 0);
+}
+
+#[test]
+fn test_ghost_list_respects_its_capacity() {
+    let mut cache = Cache::with_ghost_capacity(2, 3);
+    for key in 0..10 {
+        cache.put(key, key);
+        assert!(cache.ghost_len() <= 3);
+    }
+    assert_eq!(cache.ghost_len(), 3);
+    // Seules les trois dernières clés évincées (5, 6, 7) sont retenues
+    for key in 0..8 {
+        assert_eq!(cache.get(&key), None);
+    }
+    assert_eq!(cache.stats().ghost_hits, 3);
+    assert_eq!(cache.ghost_len(), 0);
+}
+
+#[test]
+fn test_ghost_list_ignores_other_removals() {
+    let mut cache = Cache::with_ghost_capacity(2, 4);
+    cache.put(1, 1);
+    cache.put(2, 2);
+    cache.invalidate(&1);
+    cache.put(3, 3);
+    cache.put(4, 4);
+    assert_eq!(cache.ghost_len(), 1);
+    // Réinsérer une clé évincée la retire de la liste
+    cache.put(2, 2);
+    assert_eq!(cache.ghost_len(), 1);
+    assert_eq!(cache.get(&2), Some(&2));
+    assert_eq!(cache.get(&1), None);
+    assert_eq!(cache.stats().ghost_hits, 0);
+
+    cache.reset_stats();
+    cache.get(&3);
+    assert_eq!(cache.stats().ghost_hits, 1);
+
+    let mut plain = Cache::new(1);
+    plain.put(1, 1);
+    plain.put(2, 2);
+    assert_eq!(plain.get(&1), None);
+    assert_eq!((plain.ghost_len(), plain.stats().ghost_hits), (0, 0));
+}
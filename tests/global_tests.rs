@@ -0,0 +1,104 @@
+use lru_cache::cache::concurrent::ConcurrentCache;
+use lru_cache::cache::global::GlobalCache;
+use lru_cache::cache::stats::CacheStats;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Barrier;
+use std::thread;
+use std::time::Duration;
+
+const THREADS: u64 = 8;
+
+#[test]
+fn test_concurrent_first_access_initializes_once() {
+    static INITS: AtomicUsize = AtomicUsize::new(0);
+    static CACHE: GlobalCache<u64, u64> = GlobalCache::with_init(|| {
+        INITS.fetch_add(1, Ordering::SeqCst);
+        // Laisse aux autres threads le temps d'arriver pendant la création
+        thread::sleep(Duration::from_millis(20));
+        ConcurrentCache::new(64)
+    });
+
+    assert!(!CACHE.is_initialized());
+    let barrier = Barrier::new(THREADS as usize);
+    thread::scope(|scope| {
+        for id in 0..THREADS {
+            let barrier = &barrier;
+            scope.spawn(move || {
+                barrier.wait();
+                CACHE.put(id, id * 10);
+            });
+        }
+    });
+
+    assert_eq!(INITS.load(Ordering::SeqCst), 1);
+    assert!(CACHE.is_initialized());
+    assert_eq!(CACHE.len(), THREADS as usize);
+    assert!((0..THREADS).all(|id| CACHE.get(&id) == Some(id * 10)));
+}
+
+#[test]
+fn test_capacity_is_shared_by_all_threads() {
+    static CACHE: GlobalCache<u64, u64> = GlobalCache::new(100);
+
+    thread::scope(|scope| {
+        for id in 0..THREADS {
+            scope.spawn(move || {
+                for key in id * 1_000..(id + 1) * 1_000 {
+                    CACHE.put(key, key);
+                    assert!(CACHE.len() <= 100);
+                }
+            });
+        }
+    });
+
+    assert_eq!(CACHE.capacity(), 100);
+    assert_eq!(CACHE.len(), 100);
+    assert_eq!(CACHE.stats().evictions, THREADS * 1_000 - 100);
+}
+
+#[test]
+fn test_get_or_insert_with_loads_once_across_threads() {
+    static LOADS: AtomicUsize = AtomicUsize::new(0);
+    static CACHE: GlobalCache<&str, String> = GlobalCache::new(16);
+
+    let barrier = Barrier::new(THREADS as usize);
+    let values: Vec<String> = thread::scope(|scope| {
+        let handles: Vec<_> = (0..THREADS)
+            .map(|_| {
+                let barrier = &barrier;
+                scope.spawn(move || {
+                    barrier.wait();
+                    CACHE.get_or_insert_with("config", || {
+                        LOADS.fetch_add(1, Ordering::SeqCst);
+                        thread::sleep(Duration::from_millis(20));
+                        String::from("chargée")
+                    })
+                })
+            })
+            .collect();
+        handles.into_iter().map(|handle| handle.join().unwrap()).collect()
+    });
+
+    assert_eq!(LOADS.load(Ordering::SeqCst), 1);
+    assert!(values.iter().all(|value| value == "chargée"));
+}
+
+#[test]
+fn test_clear_and_reset_stats_isolate_cases() {
+    static CACHE: GlobalCache<u64, u64> = GlobalCache::new(4);
+
+    CACHE.put(1, 1);
+    assert_eq!(CACHE.get(&1), Some(1));
+    assert_eq!(CACHE.get(&2), None);
+    assert_eq!(CACHE.remove(&1), Some(1));
+    CACHE.put(3, 3);
+    assert_eq!((CACHE.stats().hits, CACHE.stats().misses), (1, 1));
+
+    CACHE.clear();
+    assert!(CACHE.is_empty());
+    assert_eq!(CACHE.stats().hits, 1);
+    CACHE.reset_stats();
+    assert_eq!(CACHE.stats(), CacheStats::default());
+    assert_eq!(CACHE.get(&3), None);
+    assert_eq!(CACHE.stats().misses, 1);
+}
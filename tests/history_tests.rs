@@ -0,0 +1,118 @@
+use lru_cache::Cache;
+use lru_cache::cache::clock::MockClock;
+use lru_cache::cache::listener::{EvictionRecord, RemovalCause};
+use lru_cache::cache::traits::{CacheRemove, CacheStorage};
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+use std::time::Duration;
+
+/// Allocateur qui compte les allocations du fil courant
+struct Counting;
+
+thread_local! {
+    static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+}
+
+unsafe impl GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let _ = ALLOCATIONS.try_with(|count| count.set(count.get() + 1));
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: Counting = Counting;
+
+fn allocations() -> usize {
+    ALLOCATIONS.with(Cell::get)
+}
+
+fn keys(cache: &Cache<u32, u32>) -> Vec<u32> {
+    cache.eviction_history().map(|record| record.key).collect()
+}
+
+#[test]
+fn test_history_keeps_newest_records() {
+    const LIMIT: u32 = 4;
+    let mut cache = Cache::new(1);
+    cache.enable_eviction_history(LIMIT as usize);
+    for key in 0..=LIMIT + 5 {
+        cache.put(key, key);
+    }
+    assert_eq!(keys(&cache), (LIMIT + 1..LIMIT + 5).collect::<Vec<_>>());
+    assert!(cache.eviction_history().all(|record| record.cause == RemovalCause::Capacity));
+
+    cache.clear_eviction_history();
+    assert_eq!(cache.eviction_history().count(), 0);
+    cache.put(100, 100);
+    assert_eq!(keys(&cache), vec![LIMIT + 5]);
+}
+
+#[test]
+fn test_history_records_mixed_causes() {
+    let clock = MockClock::new();
+    let mut cache = Cache::with_clock(2, clock.clone());
+    cache.enable_eviction_history(10);
+    cache.put_with_ttl(1, 1, Duration::from_secs(5));
+    cache.put(2, 2);
+    cache.put(2, 20);
+    clock.advance(Duration::from_secs(10));
+    cache.purge_expired();
+    cache.put(3, 3);
+    cache.put(4, 4);
+    cache.invalidate(&3);
+    cache.remove(&4);
+    cache.put(5, 5);
+    cache.clear();
+
+    let records: Vec<EvictionRecord<u32>> = cache.eviction_history().cloned().collect();
+    let causes: Vec<(u32, RemovalCause)> = records.iter().map(|record| (record.key, record.cause)).collect();
+    assert_eq!(
+        causes,
+        vec![
+            (2, RemovalCause::Replaced),
+            (1, RemovalCause::Expired),
+            (2, RemovalCause::Capacity),
+            (3, RemovalCause::Explicit),
+            (5, RemovalCause::Cleared),
+        ]
+    );
+    assert!(records[0].at < records[1].at);
+    assert_eq!(records[1].at, records[4].at);
+}
+
+/// Allocations faites par 96 insertions de clés nouvelles dans `cache`
+fn put_allocations(cache: &mut Cache<u32, u32>) -> usize {
+    let before = allocations();
+    for key in 1_000..1_096 {
+        cache.put(key, key);
+    }
+    allocations() - before
+}
+
+#[test]
+fn test_history_disabled_does_not_allocate() {
+    let mut roomy = Cache::with_preallocated(200);
+    let inserting = put_allocations(&mut roomy);
+
+    // Les premières évictions peuvent encore agrandir les structures internes
+    let mut full = Cache::new(4);
+    for key in 0..8 {
+        full.put(key, key);
+    }
+    assert_eq!(put_allocations(&mut full), inserting);
+    let before = allocations();
+    assert_eq!(full.eviction_history().count(), 0);
+    full.clear_eviction_history();
+    assert_eq!(allocations(), before);
+
+    full.enable_eviction_history(0);
+    assert_eq!(put_allocations(&mut full), inserting);
+    full.enable_eviction_history(2);
+    assert!(put_allocations(&mut full) > inserting);
+    assert_eq!(keys(&full), vec![1_090, 1_091]);
+}
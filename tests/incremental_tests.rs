@@ -0,0 +1,150 @@
+mod common;
+
+use lru_cache::Cache;
+use lru_cache::cache::traits::{CacheIterate, CacheLen, CacheRemove, CacheStorage, PersistentStorage};
+use lru_cache::storage::error::StorageError;
+use lru_cache::storage::file::{FileStorage, Record, VerifyOptions};
+use std::fs;
+use std::io;
+use std::num::NonZeroUsize;
+
+fn sorted(cache: &Cache<String, i32>) -> Vec<(String, i32)> {
+    let mut entries: Vec<_> = cache.iter().map(|(key, value)| (key.clone(), *value)).collect();
+    entries.sort();
+    entries
+}
+
+#[test]
+fn test_incremental_saves_reload_like_the_cache() {
+    let path = common::temp_path("incremental_reload.txt");
+    let mut cache = Cache::new(4);
+    for (i, key) in ["a", "b;1", "c\nd", "e"].iter().enumerate() {
+        cache.put(key.to_string(), i as i32);
+    }
+    cache.save_incremental(&path).unwrap();
+    assert!(!cache.is_dirty());
+
+    cache.put(String::from("b;1"), 10);
+    cache.remove(&String::from("e"));
+    cache.put(String::from("f"), 5);
+    cache.put(String::from("g"), 6);
+    assert_eq!(cache.dirty().count(), 5);
+    let before = fs::metadata(&path).unwrap().len();
+    cache.save_incremental(&path).unwrap();
+    assert!(!cache.is_dirty());
+    assert!(fs::metadata(&path).unwrap().len() - before < before);
+
+    cache.remove(&String::from("f"));
+    cache.put(String::from("f"), 7);
+    cache.save_incremental(&path).unwrap();
+
+    let loaded = Cache::<String, i32>::load_from_file(&path, NonZeroUsize::new(4).unwrap()).unwrap();
+    assert_eq!(sorted(&loaded), sorted(&cache));
+    let auto = Cache::<String, i32>::load_from_file_auto(&path).unwrap();
+    assert_eq!(sorted(&auto), sorted(&cache));
+    let (_, data) = FileStorage::load::<String, i32>(&path).unwrap();
+    assert_eq!(data.len(), cache.len());
+
+    let report = FileStorage::verify::<String, i32>(&path, VerifyOptions::default()).unwrap();
+    assert_eq!(report.entries, cache.len());
+    assert!(report.is_ok());
+    fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn test_take_dirty_includes_evictions_and_removals() {
+    let mut cache = Cache::new(2);
+    cache.put("a", 1);
+    cache.put("b", 2);
+    assert!(!cache.is_dirty());
+    assert!(cache.take_dirty().is_empty());
+
+    cache.track_changes();
+    cache.put("c", 3);
+    cache.get(&"b");
+    let mut dirty = cache.take_dirty();
+    dirty.sort();
+    assert_eq!(dirty, vec![("a", None), ("c", Some(3))]);
+
+    cache.put("b", 20);
+    cache.clear();
+    let mut dirty = cache.take_dirty();
+    dirty.sort();
+    assert_eq!(dirty, vec![("b", None), ("c", None)]);
+}
+
+#[test]
+fn test_incremental_save_falls_back_to_full_save() {
+    let path = common::temp_path("incremental_fallback.txt");
+    fs::write(&path, "#lru_cache v3\n2\nold;1\n#crc32 00000000\n").unwrap();
+    let mut cache = Cache::new(2);
+    cache.put(String::from("a"), 1);
+    cache.track_changes();
+    cache.save_incremental(&path).unwrap();
+
+    let (_, data) = FileStorage::load::<String, i32>(&path).unwrap();
+    assert_eq!(data, vec![(String::from("a"), 1)]);
+
+    fs::write(&path, "#lru_cache v3\n2\n#crc32 00000000\n").unwrap();
+    let error = FileStorage::append(&path, [Record::<_, i32>::Remove("b")]).unwrap_err();
+    assert!(matches!(error, StorageError::Io(ref e) if e.kind() == io::ErrorKind::InvalidInput));
+    fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn test_damaged_segment_is_detected() {
+    let path = common::temp_path("incremental_damaged.txt");
+    FileStorage::save(&path, 2, &[("a", 1)]).unwrap();
+    let snapshot = fs::read_to_string(&path).unwrap();
+    assert_eq!(FileStorage::append(&path, [Record::Put("b", 2)]).unwrap(), 1);
+    assert_eq!(FileStorage::append(&path, Vec::<Record<&str, i32>>::new()).unwrap(), 0);
+    let content = fs::read_to_string(&path).unwrap();
+    assert!(FileStorage::load_checked::<String, i32>(&path).unwrap().verified);
+
+    fs::write(&path, &content[..content.len() - 3]).unwrap();
+    assert!(matches!(FileStorage::load::<String, i32>(&path), Err(StorageError::MissingChecksum)));
+
+    fs::write(&path, content.replace("b;2", "b;3")).unwrap();
+    assert!(matches!(
+        FileStorage::load::<String, i32>(&path),
+        Err(StorageError::ChecksumMismatch { .. })
+    ));
+    let report = FileStorage::verify::<String, i32>(&path, VerifyOptions::default()).unwrap();
+    assert_eq!(report.checksum_ok, Some(false));
+
+    fs::write(&path, format!("{}b;2\n", snapshot)).unwrap();
+    let lossy = FileStorage::load_lossy::<String, i32>(&path).unwrap();
+    assert_eq!(lossy.data.len(), 2);
+    fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn test_compact_file_folds_segments() {
+    let path = common::temp_path("incremental_compact.txt");
+    let mut cache = Cache::new(3);
+    cache.put(String::from("a"), 1);
+    cache.put(String::from("b"), 2);
+    cache.save_incremental(&path).unwrap();
+    cache.remove(&String::from("a"));
+    cache.put(String::from("b"), 20);
+    cache.put(String::from("c"), 3);
+    cache.save_incremental(&path).unwrap();
+    assert!(fs::read_to_string(&path).unwrap().contains("#del a\n"));
+
+    FileStorage::compact_file(&path).unwrap();
+    let content = fs::read_to_string(&path).unwrap();
+    assert_eq!(content.matches("#crc32").count(), 1);
+    assert!(!content.contains("#del"));
+    assert!(FileStorage::metadata(&path).unwrap().saved_at.is_some());
+    let (_, data) = FileStorage::load::<String, i32>(&path).unwrap();
+    assert_eq!(data, vec![(String::from("b"), 20), (String::from("c"), 3)]);
+
+    let repaired = common::temp_path("incremental_repaired.txt");
+    cache.put(String::from("d"), 4);
+    cache.save_incremental(&path).unwrap();
+    FileStorage::repair(&path, &repaired).unwrap();
+    let loaded = Cache::<String, i32>::load_from_file(&repaired, NonZeroUsize::new(3).unwrap()).unwrap();
+    assert_eq!(sorted(&loaded), sorted(&cache));
+    fs::remove_file(path).unwrap();
+    fs::remove_file(repaired).unwrap();
+}
@@ -1,7 +1,11 @@
+mod common;
+
 use lru_cache::Cache;
-use lru_cache::cache::traits::{CacheStorage, PersistentStorage};
+use lru_cache::cache::lru::MergeStrategy;
+use lru_cache::cache::traits::{CacheIterate, CacheLen, CacheRemove, CacheStorage, PersistentStorage};
 use lru_cache::storage::file::FileStorage;
 use std::fs;
+use std::num::NonZeroUsize;
 
 #[test]
 fn test_lru_cache_basic() {
@@ -35,7 +39,7 @@ fn test_persistent_cache() {
         cache.save_to_file(path).unwrap();
     }
 
-    let mut cache = Cache::<String, String>::load_from_file(path, 2).unwrap();
+    let mut cache = Cache::<String, String>::load_from_file(path, NonZeroUsize::new(2).unwrap()).unwrap();
     assert_eq!(cache.get(&String::from("X")), Some(&String::from("test")));
     
     fs::remove_file(path).unwrap();
@@ -87,4 +91,820 @@ fn test_file_storage_numeric() {
     assert_eq!(loaded_data, data);
     
     fs::remove_file(path).unwrap();
-}
\ No newline at end of file
+}
+
+#[test]
+fn test_entry_id_invalidated_by_eviction() {
+    let mut cache = Cache::new(2);
+    cache.put("A", 1);
+    let id = cache.get_id(&"A").unwrap();
+    cache.put("B", 2);
+    cache.put("C", 3);
+
+    assert_eq!(cache.get_by_id(id), None);
+    assert!(!cache.promote_by_id(id));
+    assert_eq!(cache.remove_by_id(id), None);
+}
+
+#[test]
+fn test_entry_id_invalidated_by_replacement() {
+    let mut cache = Cache::new(2);
+    cache.put("A", 1);
+    let id = cache.get_id(&"A").unwrap();
+    cache.put("A", 10);
+
+    assert_eq!(cache.get_by_id(id), None);
+    let new_id = cache.get_id(&"A").unwrap();
+    assert_ne!(id, new_id);
+    assert_eq!(cache.get_by_id(new_id), Some(&10));
+}
+
+#[test]
+fn test_entry_id_invalidated_by_removal() {
+    let mut cache = Cache::new(2);
+    cache.put("A", 1);
+    cache.put("B", 2);
+    let id = cache.get_id(&"A").unwrap();
+
+    assert_eq!(cache.remove_by_id(id), Some(("A", 1)));
+    assert_eq!(cache.remove_by_id(id), None);
+    assert_eq!(cache.get(&"A"), None);
+    assert_eq!(cache.get(&"B"), Some(&2));
+}
+
+#[test]
+fn test_entry_id_slot_reused_by_new_key() {
+    let mut cache = Cache::new(1);
+    cache.put("A", 1);
+    let old_id = cache.get_id(&"A").unwrap();
+    cache.put("B", 2);
+    let new_id = cache.get_id(&"B").unwrap();
+
+    assert_eq!(cache.get_by_id(old_id), None);
+    assert_eq!(cache.get_by_id(new_id), Some(&2));
+}
+
+#[test]
+fn test_promote_by_id_changes_eviction_order() {
+    let mut cache = Cache::new(2);
+    cache.put("A", 1);
+    cache.put("B", 2);
+    let id = cache.get_id(&"A").unwrap();
+    cache.get(&"B");
+
+    assert!(cache.promote_by_id(id));
+    cache.put("C", 3);
+
+    assert_eq!(cache.get(&"B"), None);
+    assert_eq!(cache.get_by_id(id), Some(&1));
+}
+
+#[derive(Debug, PartialEq, Eq, Hash)]
+struct Token(u32);
+
+#[test]
+fn test_non_clone_keys() {
+    let mut cache = Cache::new(2);
+    cache.put(Token(1), "one");
+    cache.put(Token(2), "two");
+    assert_eq!(cache.get(&Token(1)), Some(&"one"));
+
+    cache.put(Token(3), "three");
+
+    assert_eq!(cache.get(&Token(2)), None);
+    assert_eq!(cache.get(&Token(1)), Some(&"one"));
+    assert_eq!(cache.get(&Token(3)), Some(&"three"));
+}
+
+#[test]
+fn test_new_does_not_preallocate() {
+    let cache: Cache<u32, u32> = Cache::new(1_000_000);
+    assert!(cache.allocated_capacity() < 1_000_000);
+
+    let cache: Cache<u32, u32> = Cache::with_preallocated(1_000);
+    assert!(cache.allocated_capacity() >= 1_000);
+}
+
+#[test]
+fn test_shrink_to_fit_keeps_memory_modest() {
+    let mut cache = Cache::with_preallocated(1_000_000);
+    for i in 0..5 {
+        cache.put(i, i * 10);
+    }
+    cache.shrink_to_fit();
+
+    assert!(cache.allocated_capacity() < 100);
+    assert_eq!(cache.get(&0), Some(&0));
+    assert_eq!(cache.get(&4), Some(&40));
+}
+
+#[test]
+fn test_shrink_to_fit_compacts_removed_slots() {
+    let mut cache = Cache::new(4);
+    for key in ["A", "B", "C", "D"] {
+        cache.put(key, key.to_lowercase());
+    }
+    let id_a = cache.get_id(&"A").unwrap();
+    let id_b = cache.get_id(&"B").unwrap();
+    cache.remove_by_id(id_a);
+    cache.shrink_to_fit();
+
+    assert_eq!(cache.get_by_id(id_a), None);
+    assert_eq!(cache.get_by_id(id_b), Some(&String::from("b")));
+
+    cache.put("E", String::from("e"));
+    cache.put("F", String::from("f"));
+
+    assert_eq!(cache.get(&"C"), None);
+    assert_eq!(cache.get(&"D"), Some(&String::from("d")));
+    assert_eq!(cache.get(&"B"), Some(&String::from("b")));
+    assert_eq!(cache.get(&"E"), Some(&String::from("e")));
+    assert_eq!(cache.get(&"F"), Some(&String::from("f")));
+}
+
+#[test]
+fn test_reserve_grows_allocation() {
+    let mut cache: Cache<u32, u32> = Cache::new(10_000);
+    cache.reserve(500);
+    assert!(cache.allocated_capacity() >= 500);
+}
+
+#[test]
+fn test_touch_where_promotes_in_visit_order() {
+    let mut cache = Cache::new(5);
+    for i in 1..=5 {
+        cache.put(i, i * 10);
+    }
+
+    assert_eq!(cache.touch_where(|k, _| *k != 3), 4);
+    let order: Vec<_> = cache.iter().map(|(k, _)| *k).collect();
+    assert_eq!(order, vec![3, 1, 2, 4, 5]);
+
+    assert_eq!(cache.touch_where(|_, v| *v >= 40), 2);
+    let order: Vec<_> = cache.iter().map(|(k, _)| *k).collect();
+    assert_eq!(order, vec![3, 1, 2, 4, 5]);
+
+    assert_eq!(cache.touch_where(|k, _| *k == 1 || *k == 3), 2);
+    cache.put(6, 60);
+    assert_eq!(cache.get(&2), None);
+    assert_eq!(cache.get(&3), Some(&30));
+}
+
+#[test]
+fn test_touch_where_is_linear_on_large_cache() {
+    let mut cache = Cache::new(200_000);
+    for i in 0..200_000 {
+        cache.put(i, i);
+    }
+    let started = std::time::Instant::now();
+
+    assert_eq!(cache.touch_where(|k, _| k % 2 == 0), 100_000);
+    assert!(started.elapsed() < std::time::Duration::from_secs(5));
+    assert_eq!(cache.iter().next(), Some((&1, &1)));
+}
+
+#[test]
+fn test_evict_n_returns_entries_in_eviction_order() {
+    let mut cache = Cache::new(5);
+    for (key, value) in [("A", 1), ("B", 2), ("C", 3), ("D", 4), ("E", 5)] {
+        cache.put(key, value);
+    }
+    cache.get(&"B");
+
+    assert_eq!(cache.evict_n(2), vec![("A", 1), ("C", 3)]);
+    cache.put("F", 6);
+    cache.put("G", 7);
+    cache.put("H", 8);
+    let order: Vec<_> = cache.iter().map(|(k, _)| *k).collect();
+    assert_eq!(order, vec!["E", "B", "F", "G", "H"]);
+
+    assert_eq!(cache.evict_n(10).len(), 5);
+    assert!(cache.evict_n(1).is_empty());
+}
+
+#[test]
+fn test_truncate_keeps_most_recent_entries() {
+    let mut cache = Cache::new(4);
+    for key in 1..=4 {
+        cache.put(key, key * 10);
+    }
+    cache.get(&1);
+    cache.truncate(2);
+
+    let entries: Vec<_> = cache.iter().map(|(k, v)| (*k, *v)).collect();
+    assert_eq!(entries, vec![(4, 40), (1, 10)]);
+    assert_eq!(cache.capacity(), 4);
+    cache.truncate(10);
+    assert_eq!(cache.iter().count(), 2);
+    cache.truncate(0);
+    assert_eq!(cache.iter().count(), 0);
+}
+
+#[test]
+fn test_get_shared_updates_eviction_order() {
+    let mut cache = Cache::new(3);
+    cache.put("A", 1);
+    cache.put("B", 2);
+    cache.put("C", 3);
+    {
+        let shared = &cache;
+        assert_eq!(shared.get_shared(&"B"), Some(&2));
+        assert_eq!(shared.get_shared(&"A"), Some(&1));
+        assert_eq!(shared.get_cloned(&"B"), Some(2));
+        assert_eq!(shared.get_shared(&"D"), None);
+    }
+    cache.put("D", 4);
+    cache.put("E", 5);
+
+    let order: Vec<_> = cache.iter().map(|(k, _)| *k).collect();
+    assert_eq!(order, vec!["B", "D", "E"]);
+    let stats = cache.stats();
+    assert_eq!((stats.hits, stats.misses), (3, 1));
+}
+
+#[test]
+fn test_get_shared_is_ordered_with_mutable_gets() {
+    let mut cache = Cache::new(3);
+    cache.put("A", 1);
+    cache.put("B", 2);
+    cache.put("C", 3);
+    cache.get_shared(&"A");
+    cache.get(&"B");
+    cache.put("D", 4);
+
+    let order: Vec<_> = cache.iter().map(|(k, _)| *k).collect();
+    assert_eq!(order, vec!["A", "B", "D"]);
+}
+
+#[test]
+fn test_get_shared_ignores_entries_removed_before_apply() {
+    let mut cache = Cache::new(2);
+    cache.put("A", 1);
+    cache.put("B", 2);
+    cache.get_shared(&"A");
+    cache.remove(&"A");
+    cache.put("C", 3);
+    cache.get_shared(&"B");
+    cache.put("D", 4);
+
+    let order: Vec<_> = cache.iter().map(|(k, _)| *k).collect();
+    assert_eq!(order, vec!["B", "D"]);
+}
+
+#[test]
+fn test_get_shared_through_rc() {
+    let cache = std::rc::Rc::new({
+        let mut cache = Cache::new(2);
+        cache.put("A", 1);
+        cache
+    });
+    let reader = std::rc::Rc::clone(&cache);
+    assert_eq!(reader.get_shared(&"A"), Some(&1));
+    assert_eq!(cache.stats().hits, 1);
+}
+
+#[test]
+fn test_get_arc_outlives_eviction() {
+    let mut cache = Cache::new(1);
+    cache.put_value("A", vec![1, 2, 3]);
+    let held = cache.get_arc(&"A").unwrap();
+    assert_eq!(std::sync::Arc::strong_count(&held), 2);
+
+    cache.put_value("B", vec![4]);
+    assert_eq!(cache.get_arc(&"A"), None);
+    assert_eq!(std::sync::Arc::strong_count(&held), 1);
+    assert_eq!(*held, vec![1, 2, 3]);
+}
+
+#[test]
+fn test_get_arc_shares_one_allocation() {
+    let mut cache = Cache::new(2);
+    cache.put_value("A", String::from("document"));
+    let first = cache.get_arc(&"A").unwrap();
+    let second = cache.get_arc(&"A").unwrap();
+    assert!(std::sync::Arc::ptr_eq(&first, &second));
+
+    cache.put_value("A", String::from("replaced"));
+    assert_eq!(*first, "document");
+    assert_eq!(*cache.get_arc(&"A").unwrap(), "replaced");
+}
+
+#[test]
+#[should_panic(expected = "la capacité d'un cache doit être au moins 1")]
+fn test_zero_capacity_panics() {
+    Cache::<i32, i32>::new(0);
+}
+
+#[test]
+fn test_with_capacity_matches_new() {
+    let mut cache = Cache::with_capacity(NonZeroUsize::new(2).unwrap());
+    let mut plain = Cache::new(2);
+    for i in 0..5 {
+        cache.put(i, i);
+        plain.put(i, i);
+    }
+    assert_eq!(cache.capacity(), 2);
+    assert_eq!(cache.iter().collect::<Vec<_>>(), plain.iter().collect::<Vec<_>>());
+}
+
+#[test]
+fn test_try_new_rejects_zero_capacity() {
+    use lru_cache::cache::error::CacheError;
+
+    assert_eq!(Cache::<i32, i32>::try_new(0).unwrap_err(), CacheError::ZeroCapacity);
+    let mut cache = Cache::try_new(1).unwrap();
+    cache.put(String::from("A"), 1);
+    assert_eq!(cache.get(&String::from("A")), Some(&1));
+
+    let path = common::temp_path("zero_capacity.txt");
+    cache.save_to_file(&path).unwrap();
+    let error = lru_cache::cache::persistent::PersistentCache::<String, i32>::open(&path, 0).unwrap_err();
+    assert_eq!(error.kind(), std::io::ErrorKind::InvalidInput);
+    assert_eq!(error.get_ref().unwrap().downcast_ref(), Some(&CacheError::ZeroCapacity));
+
+    fs::write(&path, "garbage\nA;1\n").unwrap();
+    assert!(Cache::<String, i32>::load_from_file(&path, NonZeroUsize::new(1).unwrap()).is_err());
+    fs::remove_file(path).unwrap();
+}
+
+fn evictions_of(cache: &mut Cache<u32, u32>) -> std::sync::mpsc::Receiver<(u32, u32)> {
+    let (sender, receiver) = std::sync::mpsc::channel();
+    cache.set_eviction_listener(move |key, value, _| sender.send((key, value)).unwrap());
+    receiver
+}
+
+#[test]
+fn test_put_many_matches_sequential_puts() {
+    let batch: Vec<(u32, u32)> = [1, 2, 3, 2, 4, 5, 1, 6, 7, 3, 8, 2]
+        .iter()
+        .enumerate()
+        .map(|(i, &key)| (key, i as u32))
+        .collect();
+    let mut reference = Cache::new(4);
+    let mut batched = Cache::new(4);
+    reference.put(0, 0);
+    batched.put(0, 0);
+    let reference_evictions = evictions_of(&mut reference);
+    let batched_evictions = evictions_of(&mut batched);
+
+    for &(key, value) in &batch {
+        reference.put(key, value);
+    }
+    batched.put_many(batch);
+
+    let entries = |cache: &Cache<u32, u32>| cache.iter().map(|(k, v)| (*k, *v)).collect::<Vec<_>>();
+    assert_eq!(entries(&batched), entries(&reference));
+    assert_eq!(entries(&batched), vec![(7, 8), (3, 9), (8, 10), (2, 11)]);
+    assert_eq!(batched.stats(), reference.stats());
+    assert_eq!(
+        batched_evictions.try_iter().collect::<Vec<_>>(),
+        reference_evictions.try_iter().collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn test_get_and_remove_many_match_sequential_calls() {
+    let mut reference = Cache::new(4);
+    let mut batched = Cache::new(4);
+    reference.put_many((0..4).map(|key| (key, key * 10)));
+    batched.put_many((0..4).map(|key| (key, key * 10)));
+
+    let keys = [2, 9, 0, 2];
+    let expected: Vec<_> = keys.iter().map(|key| reference.get(key).copied()).collect();
+    let found: Vec<_> = batched.get_many(&keys).into_iter().map(|value| value.copied()).collect();
+    assert_eq!(found, expected);
+    assert_eq!(found, vec![Some(20), None, Some(0), Some(20)]);
+    assert_eq!(batched.stats(), reference.stats());
+    let order = |cache: &Cache<u32, u32>| cache.iter().map(|(k, _)| *k).collect::<Vec<_>>();
+    assert_eq!(order(&batched), vec![1, 3, 0, 2]);
+    assert_eq!(order(&batched), order(&reference));
+
+    assert_eq!(batched.remove_many(&[3, 3, 7, 1]), vec![Some(30), None, None, Some(10)]);
+    assert_eq!(order(&batched), vec![0, 2]);
+}
+
+#[test]
+fn test_recency_rank_follows_gets_puts_and_evictions() {
+    let mut cache = Cache::new(4);
+    cache.put_many([("a", 1), ("b", 2), ("c", 3), ("d", 4)]);
+    let ranks = |cache: &Cache<&str, i32>| {
+        ["a", "b", "c", "d", "e"].map(|key| cache.recency_rank(&key))
+    };
+    assert_eq!(ranks(&cache), [Some(0), Some(1), Some(2), Some(3), None]);
+
+    cache.get(&"b");
+    assert_eq!(ranks(&cache), [Some(0), Some(3), Some(1), Some(2), None]);
+    cache.put("c", 30);
+    assert_eq!(ranks(&cache), [Some(0), Some(2), Some(3), Some(1), None]);
+
+    cache.put("e", 5);
+    assert_eq!(ranks(&cache), [None, Some(1), Some(2), Some(0), Some(3)]);
+    for rank in 0..4 {
+        let (key, _) = cache.nth_lru(rank).unwrap();
+        assert_eq!(cache.recency_rank(key), Some(rank));
+    }
+    assert_eq!(cache.nth_lru(3), Some((&"e", &5)));
+    assert_eq!(cache.nth_lru(4), None);
+    assert_eq!(cache.iter().next(), cache.nth_lru(0));
+}
+
+#[test]
+fn test_index_reads_like_peek() {
+    let mut cache = Cache::new(2);
+    cache.put("a", 1);
+    cache.put("b", 2);
+    assert_eq!(cache[&"a"], 1);
+    assert_eq!(cache.stats().hits, 0);
+
+    cache.put("c", 3);
+    assert_eq!(cache.peek(&"a"), None);
+    assert_eq!(cache[&"b"] + cache[&"c"], 5);
+}
+
+#[test]
+#[should_panic(expected = "clé absente du cache : \"missing\"")]
+fn test_index_panics_on_missing_key() {
+    let mut cache = Cache::new(2);
+    cache.put("a", 1);
+    let _ = cache[&"missing"];
+}
+
+#[test]
+fn test_cache_macro_inserts_in_listed_order() {
+    let mut cache = lru_cache::cache! { capacity: 4; "A" => 1, "B" => 2, "C" => 3, };
+    assert_eq!(cache.iter().map(|(key, _)| *key).collect::<Vec<_>>(), ["A", "B", "C"]);
+    cache.put("D", 4);
+    cache.put("E", 5);
+    assert_eq!(cache.peek(&"A"), None);
+
+    let counted = lru_cache::cache! { 1u8 => 'a', 2 => 'b', 1 => 'c' };
+    assert_eq!(counted.capacity(), 3);
+    assert_eq!(counted.iter().collect::<Vec<_>>(), [(&2, &'b'), (&1, &'c')]);
+}
+
+#[test]
+fn test_unbounded_cache_never_evicts_until_resized() {
+    let mut cache = Cache::unbounded();
+    assert!(cache.is_unbounded());
+    assert_eq!(cache.capacity(), usize::MAX);
+    for i in 0..100_000u32 {
+        cache.put(i, i * 2);
+        assert!(!cache.is_full());
+    }
+    assert_eq!(cache.len(), 100_000);
+    cache.get(&0);
+    cache.get(&1);
+
+    let (sender, receiver) = std::sync::mpsc::channel();
+    cache.set_eviction_listener(move |key, _, _| sender.send(key).unwrap());
+    cache.resize(NonZeroUsize::new(3).unwrap());
+    assert!(!cache.is_unbounded() && cache.is_full());
+    assert_eq!(receiver.try_iter().take(3).collect::<Vec<_>>(), [2, 3, 4]);
+    assert_eq!(receiver.try_iter().count(), 100_000 - 3 - 3);
+    assert_eq!(cache.iter().map(|(key, _)| *key).collect::<Vec<_>>(), [99_999, 0, 1]);
+
+    cache.put(7, 7);
+    assert_eq!(receiver.try_recv(), Ok(99_999));
+    cache.resize(NonZeroUsize::MAX);
+    cache.put(8, 8);
+    assert_eq!(cache.len(), 4);
+}
+
+#[test]
+fn test_unbounded_capacity_survives_save_and_load() {
+    let path = common::temp_path("unbounded.txt");
+    let mut cache = Cache::unbounded();
+    cache.put(String::from("a"), 1);
+    cache.put(String::from("b"), 2);
+    cache.save_to_file(&path).unwrap();
+
+    let mut loaded = Cache::<String, i32>::load_from_file_auto(&path).unwrap();
+    assert!(loaded.is_unbounded());
+    assert_eq!(FileStorage::metadata(&path).unwrap().capacity, usize::MAX);
+    loaded.put(String::from("c"), 3);
+    assert_eq!(loaded.len(), 3);
+    assert!(Cache::restore(loaded.snapshot()).is_unbounded());
+    fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn test_from_map_keeps_largest_keys_when_over_capacity() {
+    let map: std::collections::HashMap<_, _> = (0..10).map(|key| (key, key * 10)).collect();
+    for _ in 0..5 {
+        let cache = Cache::from_map(3, map.clone());
+        assert_eq!(Vec::from(cache), [(7, 70), (8, 80), (9, 90)]);
+    }
+    let cache = Cache::from_map(20, map.clone());
+    assert_eq!(cache.capacity(), 20);
+    assert_eq!(cache.into_map(), map);
+}
+
+#[test]
+fn test_vec_round_trip_preserves_recency_order() {
+    let mut cache = Cache::from(vec![("A", 1), ("B", 2), ("C", 3)]);
+    assert_eq!(cache.capacity(), 3);
+    cache.get(&"A");
+    let entries = Vec::from(cache);
+    assert_eq!(entries, [("B", 2), ("C", 3), ("A", 1)]);
+
+    let mut restored = Cache::from(entries);
+    restored.put("D", 4);
+    assert_eq!(restored.iter().map(|(key, _)| *key).collect::<Vec<_>>(), ["C", "A", "D"]);
+}
+
+#[test]
+fn test_from_vec_with_repeated_keys_and_empty_vec() {
+    let cache = Cache::from(vec![("A", 1), ("B", 2), ("A", 3)]);
+    assert_eq!(Vec::from(cache), [("B", 2), ("A", 3)]);
+
+    let empty = Cache::<&str, i32>::from(Vec::new());
+    assert_eq!(empty.capacity(), 1);
+    assert!(empty.is_empty());
+}
+
+#[test]
+fn test_into_collections_skip_expired_entries() {
+    let mut cache = Cache::new(3);
+    cache.put_with_ttl("old", 1, std::time::Duration::ZERO);
+    cache.put("kept", 2);
+    assert_eq!(Vec::from(cache), [("kept", 2)]);
+}
+
+#[test]
+fn test_insertion_order_ignores_reads_and_updates() {
+    let mut cache = Cache::new(4);
+    cache.put("a", 1);
+    cache.put("b", 2);
+    cache.get(&"a");
+    cache.put("c", 3);
+    cache.put("b", 20);
+    cache.get(&"a");
+    let keys = |cache: &Cache<&'static str, i32>, insertion: bool| -> Vec<&'static str> {
+        if insertion {
+            cache.iter_insertion_order().map(|(key, _)| *key).collect()
+        } else {
+            cache.iter().map(|(key, _)| *key).collect()
+        }
+    };
+    assert_eq!(keys(&cache, false), ["c", "b", "a"]);
+    assert_eq!(keys(&cache, true), ["a", "b", "c"]);
+    assert_eq!(cache.iter_insertion_order().nth(1), Some((&"b", &20)));
+
+    cache.remove(&"a");
+    cache.put("a", 10);
+    cache.put("d", 4);
+    cache.put("e", 5);
+    assert_eq!(keys(&cache, false), ["b", "a", "d", "e"]);
+    assert_eq!(keys(&cache, true), ["b", "a", "d", "e"]);
+    cache.get(&"b");
+    assert_eq!(keys(&cache, false), ["a", "d", "e", "b"]);
+    assert_eq!(keys(&cache, true), ["b", "a", "d", "e"]);
+}
+
+#[test]
+fn test_save_in_insertion_order_round_trips() {
+    use lru_cache::cache::lru::Order;
+
+    let path = common::temp_path("insertion_order.txt");
+    let mut cache = Cache::new(3);
+    cache.put(String::from("a"), 1);
+    cache.put(String::from("b"), 2);
+    cache.put(String::from("c"), 3);
+    cache.get(&String::from("a"));
+
+    cache.save_to_file_ordered(&path, Order::Insertion).unwrap();
+    let loaded = Cache::<String, i32>::load_from_file(&path, NonZeroUsize::new(3).unwrap()).unwrap();
+    let keys: Vec<_> = loaded.iter().map(|(key, _)| key.as_str()).collect();
+    assert_eq!(keys, ["a", "b", "c"]);
+
+    cache.save_to_file_ordered(&path, Order::Recency).unwrap();
+    let loaded = Cache::<String, i32>::load_from_file(&path, NonZeroUsize::new(3).unwrap()).unwrap();
+    let keys: Vec<_> = loaded.iter().map(|(key, _)| key.as_str()).collect();
+    assert_eq!(keys, ["b", "c", "a"]);
+    fs::remove_file(path).unwrap();
+}
+
+fn merge_sources() -> (Cache<&'static str, i32>, Cache<&'static str, i32>) {
+    let mut cache = Cache::new(4);
+    cache.put_many([("a", 1), ("b", 2), ("c", 3)]);
+    let mut other = Cache::new(4);
+    other.put_many([("c", 30), ("d", 40), ("b", 20)]);
+    (cache, other)
+}
+
+fn merged(cache: &Cache<&'static str, i32>) -> Vec<(&'static str, i32)> {
+    cache.iter().map(|(&key, &value)| (key, value)).collect()
+}
+
+#[test]
+fn test_merge_resolves_conflicts_with_each_strategy() {
+    let (mut cache, other) = merge_sources();
+    cache.merge(other, MergeStrategy::PreferSelf);
+    assert_eq!(merged(&cache), [("a", 1), ("b", 2), ("c", 3), ("d", 40)]);
+
+    let (mut cache, other) = merge_sources();
+    cache.merge(other, MergeStrategy::PreferOther);
+    assert_eq!(merged(&cache), [("a", 1), ("c", 30), ("d", 40), ("b", 20)]);
+
+    let (mut cache, other) = merge_sources();
+    let mut conflicts = Vec::new();
+    cache.merge(
+        other,
+        MergeStrategy::combine(|key: &&str, mine: i32, theirs| {
+            conflicts.push(*key);
+            mine.max(theirs) + 1
+        }),
+    );
+    assert_eq!(conflicts, ["c", "b"]);
+    assert_eq!(merged(&cache), [("a", 1), ("c", 31), ("d", 40), ("b", 21)]);
+}
+
+#[test]
+fn test_merge_overflowing_capacity_keeps_most_recent_of_other() {
+    let mut cache = Cache::new(3);
+    cache.put_many([("a", 1), ("b", 2)]);
+    let mut other = Cache::new(3);
+    other.put_many([("x", 10), ("y", 20), ("z", 30)]);
+    other.get(&"x");
+    cache.merge(other, MergeStrategy::PreferOther);
+    assert_eq!(merged(&cache), [("y", 20), ("z", 30), ("x", 10)]);
+    assert_eq!(cache.len(), 3);
+}
+
+fn tenants() -> Cache<(char, u32), u32> {
+    let mut cache = Cache::new(6);
+    for (index, tenant) in ['a', 'b', 'a', 'b', 'a', 'b'].into_iter().enumerate() {
+        cache.put((tenant, index as u32), index as u32);
+    }
+    cache.get(&('a', 0));
+    cache
+}
+
+fn tenant_keys(cache: &Cache<(char, u32), u32>) -> Vec<(char, u32)> {
+    cache.iter().map(|(key, _)| *key).collect()
+}
+
+#[test]
+fn test_split_off_by_moves_matching_entries_in_order() {
+    let mut cache = tenants();
+    let split = cache.split_off_by(|key, _| key.0 == 'a', 5);
+    assert_eq!(tenant_keys(&split), [('a', 2), ('a', 4), ('a', 0)]);
+    assert_eq!(split.capacity(), 5);
+    assert_eq!(tenant_keys(&cache), [('b', 1), ('b', 3), ('b', 5)]);
+
+    cache.put(('c', 6), 6);
+    cache.put(('c', 7), 7);
+    cache.put(('c', 8), 8);
+    cache.put(('c', 9), 9);
+    assert_eq!(cache.iter().next(), Some((&('b', 3), &3)));
+}
+
+#[test]
+fn test_split_off_by_overflow_keeps_most_recent() {
+    let mut cache = tenants();
+    let split = cache.split_off_by(|_, value| value % 2 == 0, 2);
+    assert_eq!(tenant_keys(&split), [('a', 4), ('a', 0)]);
+    assert_eq!(cache.len(), 3);
+
+    let empty = cache.split_off_by(|_, _| false, 1);
+    assert_eq!(empty.len(), 0);
+    assert_eq!(tenant_keys(&cache), [('b', 1), ('b', 3), ('b', 5)]);
+}
+
+#[test]
+fn test_get_or_insert_default_accumulates() {
+    let mut cache: Cache<&str, Vec<i32>> = Cache::new(2);
+    for item in 1..=3 {
+        cache.get_or_insert_default("odd").push(item * 2 - 1);
+    }
+    cache.get_or_insert_default("even").push(2);
+    assert_eq!(cache.peek(&"odd"), Some(&vec![1, 3, 5]));
+
+    // « odd » est promue par l'accès : c'est « even » qui est évincée
+    cache.get_or_insert_default("odd").push(7);
+    assert!(cache.get_or_insert_default("other").is_empty());
+    assert_eq!(cache.peek(&"even"), None);
+    assert_eq!(cache.peek(&"odd"), Some(&vec![1, 3, 5, 7]));
+    assert_eq!(cache.len(), 2);
+    assert_eq!(cache.stats().hits, 3);
+    assert_eq!(cache.stats().evictions, 1);
+}
+
+#[test]
+fn test_get_or_insert_drops_value_on_hit() {
+    use std::rc::Rc;
+
+    let mut cache = Cache::new(1);
+    let first = Rc::new(1);
+    let second = Rc::new(2);
+    assert_eq!(**cache.get_or_insert("key", Rc::clone(&first)), 1);
+    assert_eq!(**cache.get_or_insert("key", Rc::clone(&second)), 1);
+    assert_eq!((Rc::strong_count(&first), Rc::strong_count(&second)), (2, 1));
+
+    let mut calls = 0;
+    cache.get_or_insert_with("key", || {
+        calls += 1;
+        Rc::new(3)
+    });
+    assert_eq!(calls, 0);
+    assert_eq!(**cache.get_or_insert("other", second), 2);
+    assert_eq!(Rc::strong_count(&first), 1);
+}
+
+fn filled(keys: &[&'static str]) -> Cache<&'static str, usize> {
+    let mut cache = Cache::new(keys.len());
+    for (value, &key) in keys.iter().enumerate() {
+        cache.put(key, value);
+    }
+    cache
+}
+
+fn order(cache: &Cache<&'static str, usize>) -> Vec<&'static str> {
+    cache.iter().map(|(&key, _)| key).collect()
+}
+
+#[test]
+fn test_pop_mru_takes_most_recent_first() {
+    let mut cache = filled(&["a", "b", "c"]);
+    cache.get(&"a");
+    assert_eq!(cache.pop_mru(), Some(("a", 0)));
+    assert_eq!(order(&cache), vec!["b", "c"]);
+    assert_eq!(cache.pop_mru(), Some(("c", 2)));
+    assert_eq!(cache.pop_mru(), Some(("b", 1)));
+    assert_eq!(cache.pop_mru(), None);
+    assert_eq!(cache.stats().evictions, 3);
+}
+
+#[test]
+fn test_keep_newest_removes_oldest_entries() {
+    let mut cache = filled(&["a", "b", "c", "d", "e"]);
+    cache.get(&"b");
+    assert_eq!(cache.keep_newest(2), vec![("a", 0), ("c", 2), ("d", 3)]);
+    assert_eq!(order(&cache), vec!["e", "b"]);
+    assert_eq!(cache.stats().evictions, 3);
+}
+
+#[test]
+fn test_keep_oldest_removes_newest_entries() {
+    let mut cache = filled(&["a", "b", "c", "d", "e"]);
+    cache.get(&"b");
+    assert_eq!(cache.keep_oldest(2), vec![("b", 1), ("e", 4), ("d", 3)]);
+    assert_eq!(order(&cache), vec!["a", "c"]);
+    assert_eq!(cache.keep_oldest(0), vec![("c", 2), ("a", 0)]);
+    assert!(cache.is_empty());
+}
+
+#[test]
+fn test_keep_more_than_len_is_noop() {
+    let mut cache = filled(&["a", "b", "c"]);
+    assert!(cache.keep_newest(3).is_empty());
+    assert!(cache.keep_oldest(10).is_empty());
+    assert_eq!(order(&cache), vec!["a", "b", "c"]);
+    assert_eq!(cache.stats().evictions, 0);
+}
+
+#[test]
+fn test_keep_skips_pinned_entries() {
+    let mut cache = filled(&["a", "b", "c", "d"]);
+    cache.pin(&"d");
+    assert_eq!(cache.keep_oldest(2), vec![("c", 2), ("b", 1)]);
+    assert_eq!(order(&cache), vec!["a", "d"]);
+    assert_eq!(cache.pop_mru(), Some(("a", 0)));
+    assert_eq!(cache.pop_mru(), None);
+    assert_eq!(cache.len(), 1);
+}
+
+#[test]
+fn test_iter_mut_updates_values_without_promoting() {
+    let mut cache = filled(&["a", "b", "c", "d"]);
+    cache.get(&"a");
+    let before = order(&cache);
+    let stats = cache.stats();
+
+    let mut visited = Vec::new();
+    for (&key, value) in cache.iter_mut() {
+        visited.push(key);
+        *value += 100;
+    }
+    assert_eq!(visited, before);
+    assert_eq!(order(&cache), before);
+    assert_eq!(cache.stats(), stats);
+    assert_eq!(cache.peek(&"a"), Some(&100));
+    assert_eq!(cache.peek(&"d"), Some(&103));
+
+    // La prochaine victime reste « b », la moins récemment utilisée
+    cache.put("e", 4);
+    assert!(!cache.contains_key(&"b"));
+    assert_eq!(order(&cache), vec!["c", "d", "a", "e"]);
+}
+
+#[test]
+fn test_iter_mut_skips_negative_entries() {
+    let mut cache: Cache<&str, usize> = Cache::new(3);
+    cache.put("a", 1);
+    cache.put_negative("missing", None);
+    cache.put("b", 2);
+    let keys: Vec<_> = cache.iter_mut().map(|(&key, _)| key).collect();
+    assert_eq!(keys, vec!["a", "b"]);
+    assert_eq!(cache.len(), 3);
+}
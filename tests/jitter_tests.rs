@@ -0,0 +1,107 @@
+use lru_cache::Cache;
+use lru_cache::cache::builder::CacheBuilder;
+use lru_cache::cache::clock::MockClock;
+use lru_cache::cache::error::CacheError;
+use lru_cache::cache::traits::{CacheLen, CacheStorage};
+use std::collections::BTreeSet;
+use std::num::NonZeroUsize;
+use std::time::Duration;
+
+const ENTRIES: u32 = 200;
+const TTL: Duration = Duration::from_secs(100);
+
+/// Cache de `ENTRIES` entrées insérées au même instant avec la durée `TTL`
+fn filled(fraction: f64, seed: u64, clock: &MockClock) -> Cache<u32, u32> {
+    let mut cache = CacheBuilder::new()
+        .capacity(NonZeroUsize::new(ENTRIES as usize).unwrap())
+        .time_to_live(TTL)
+        .ttl_jitter(fraction, seed)
+        .clock(clock.clone())
+        .build()
+        .unwrap();
+    for key in 0..ENTRIES {
+        cache.put(key, key);
+    }
+    cache
+}
+
+/// Nombre d'entrées purgées à chaque seconde de 0 à 150 s
+fn purges_per_second(cache: &mut Cache<u32, u32>, clock: &MockClock) -> Vec<usize> {
+    (0..=150)
+        .map(|second| {
+            if second > 0 {
+                clock.advance(Duration::from_secs(1));
+            }
+            cache.purge_expired()
+        })
+        .collect()
+}
+
+#[test]
+fn test_jitter_spreads_expirations() {
+    let clock = MockClock::new();
+    let mut cache = filled(0.2, 7, &clock);
+    let remaining: BTreeSet<Duration> = (0..ENTRIES).filter_map(|key| cache.ttl_remaining(&key)).collect();
+    assert!(remaining.len() > ENTRIES as usize / 2);
+    assert!(remaining.iter().all(|ttl| (Duration::from_secs(80)..=Duration::from_secs(120)).contains(ttl)));
+
+    let purges = purges_per_second(&mut cache, &clock);
+    assert_eq!(purges.iter().sum::<usize>(), ENTRIES as usize);
+    assert!(purges[..80].iter().all(|&count| count == 0));
+    assert!(purges[121..].iter().all(|&count| count == 0));
+    assert!(purges.iter().filter(|&&count| count > 0).count() > 20);
+    assert!(purges.iter().all(|&count| count < ENTRIES as usize / 4));
+    assert!(cache.is_empty());
+}
+
+#[test]
+fn test_zero_jitter_is_exact() {
+    let clock = MockClock::new();
+    let mut cache = filled(0.0, 7, &clock);
+    assert!((0..ENTRIES).all(|key| cache.ttl_remaining(&key) == Some(TTL)));
+    let purges = purges_per_second(&mut cache, &clock);
+    assert_eq!(purges[100], ENTRIES as usize);
+    assert_eq!(purges.iter().sum::<usize>(), ENTRIES as usize);
+}
+
+#[test]
+fn test_jitter_is_reproducible_for_a_seed() {
+    let clock = MockClock::new();
+    let remaining = |seed| {
+        let cache = filled(0.5, seed, &clock);
+        (0..ENTRIES).map(|key| cache.ttl_remaining(&key)).collect::<Vec<_>>()
+    };
+    assert_eq!(remaining(1), remaining(1));
+    assert_ne!(remaining(1), remaining(2));
+}
+
+#[test]
+fn test_jitter_applies_to_explicit_ttl_but_not_set_ttl() {
+    let clock = MockClock::new();
+    let mut cache = Cache::with_clock(2, clock.clone());
+    cache.set_ttl_jitter(1.0, 3);
+    cache.put_with_ttl("a", 1, TTL);
+    assert!(cache.ttl_remaining(&"a").unwrap() <= TTL * 2);
+    assert!(cache.set_ttl(&"a", TTL));
+    assert_eq!(cache.ttl_remaining(&"a"), Some(TTL));
+    cache.put("b", 2);
+    assert_eq!(cache.ttl_remaining(&"b"), None);
+    assert_eq!(cache.check_invariants(), Ok(()));
+}
+
+#[test]
+fn test_invalid_jitter_is_rejected() {
+    for fraction in [-0.1, 1.5, f64::NAN] {
+        let built = CacheBuilder::<u32, u32>::new()
+            .capacity(NonZeroUsize::new(1).unwrap())
+            .ttl_jitter(fraction, 0)
+            .build();
+        assert_eq!(built.unwrap_err(), CacheError::InvalidJitter);
+    }
+}
+
+#[test]
+#[should_panic(expected = "dispersion")]
+fn test_set_ttl_jitter_panics_out_of_range() {
+    Cache::<u32, u32>::new(1).set_ttl_jitter(2.0, 0);
+}
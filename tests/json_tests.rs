@@ -0,0 +1,99 @@
+#![cfg(feature = "serde")]
+
+mod common;
+
+use lru_cache::Cache;
+use lru_cache::cache::traits::{CacheIterate, CacheStorage};
+use lru_cache::storage::json::{JsonError, JsonStorage};
+use serde::{Deserialize, Serialize};
+use std::fs;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct User {
+    name: String,
+    roles: Vec<String>,
+    age: Option<u32>,
+}
+
+fn user(name: &str, age: Option<u32>) -> User {
+    User {
+        name: name.to_string(),
+        roles: vec![String::from("admin"), String::from("a;b\n")],
+        age,
+    }
+}
+
+#[test]
+fn test_json_round_trip_struct_values() {
+    let path = common::temp_path("users.json");
+    let mut cache = Cache::new(3);
+    cache.put(1u32, user("alice", Some(30)));
+    cache.put(2, user("bob", None));
+    cache.put(3, user("carol", Some(41)));
+    cache.get(&1);
+    cache.save_to_json(&path).unwrap();
+
+    let mut loaded: Cache<u32, User> = Cache::load_from_json(&path).unwrap();
+    assert_eq!(loaded.capacity(), 3);
+    assert_eq!(loaded.iter().collect::<Vec<_>>(), cache.iter().collect::<Vec<_>>());
+    loaded.put(4, user("dave", None));
+    assert_eq!(loaded.get(&2), None);
+    assert_eq!(loaded.get(&1), Some(&user("alice", Some(30))));
+    fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn test_json_file_layout() {
+    let path = common::temp_path("layout.json");
+    JsonStorage::save(&path, 5, &[("a", 1), ("b", 2)]).unwrap();
+    let value: serde_json::Value = serde_json::from_str(&fs::read_to_string(&path).unwrap()).unwrap();
+    assert_eq!(value, serde_json::json!({ "capacity": 5, "entries": [["a", 1], ["b", 2]] }));
+    fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn test_hand_edited_file_with_unknown_fields_loads() {
+    let path = common::temp_path("edited.json");
+    let text = r#"{
+        "comment": "édité à la main",
+        "capacity": 2,
+        "entries": [
+            [7, {"name": "eve", "roles": [], "age": 22, "team": "ops"}]
+        ],
+        "saved_by": {"host": "srv1"}
+    }"#;
+    fs::write(&path, text).unwrap();
+
+    let mut cache: Cache<u32, User> = Cache::load_from_json(&path).unwrap();
+    assert_eq!(cache.capacity(), 2);
+    let expected = User {
+        name: String::from("eve"),
+        roles: Vec::new(),
+        age: Some(22),
+    };
+    assert_eq!(cache.get(&7), Some(&expected));
+    fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn test_malformed_json_is_an_error() {
+    let path = common::temp_path("malformed.json");
+    let cases = [
+        "",
+        "{\"capacity\": 2, \"entries\": [[1, ",
+        "{\"entries\": []}",
+        "{\"capacity\": 2, \"entries\": [[1]]}",
+        "{\"capacity\": 2, \"entries\": [[\"not a key\", {\"name\": \"x\", \"roles\": []}]]}",
+        "[1, 2]",
+    ];
+    for text in cases {
+        fs::write(&path, text).unwrap();
+        let error = Cache::<u32, User>::load_from_json(&path).unwrap_err();
+        assert!(matches!(error, JsonError::Malformed(_)), "{:?} : {}", text, error);
+        assert!(error.to_string().starts_with("fichier JSON invalide"));
+    }
+    fs::remove_file(&path).unwrap();
+
+    let error = Cache::<u32, User>::load_from_json(&path).unwrap_err();
+    assert!(matches!(error, JsonError::Io(_)));
+}
@@ -0,0 +1,200 @@
+use lru_cache::cache::clock::MockClock;
+use lru_cache::cache::error::CacheError;
+use lru_cache::cache::lfu::LfuCache;
+use lru_cache::cache::traits::{CacheIterate, CacheLen, CacheRemove, CacheStorage};
+use std::num::{NonZeroU64, NonZeroUsize};
+use std::time::Duration;
+
+const MINUTE: Duration = Duration::from_secs(60);
+
+/// Rend `hot` très demandée, puis ne lit plus que `steady`, une fois par
+/// minute pendant une heure, avant d'insérer une troisième clé
+fn hot_then_idle(cache: &mut LfuCache<&'static str, i32>, clock: &MockClock) {
+    cache.put("hot", 1);
+    for _ in 0..1000 {
+        cache.get(&"hot");
+    }
+    cache.put("steady", 2);
+    for _ in 0..60 {
+        clock.advance(MINUTE);
+        cache.get(&"steady");
+    }
+    cache.put("new", 3);
+}
+
+#[test]
+fn test_idle_hot_key_is_evicted_after_decay() {
+    let clock = MockClock::new();
+    let mut cache = LfuCache::builder()
+        .capacity(NonZeroUsize::new(2).unwrap())
+        .decay_interval(MINUTE)
+        .clock(clock.clone())
+        .build()
+        .unwrap();
+    hot_then_idle(&mut cache, &clock);
+
+    assert_eq!(cache.get(&"hot"), None);
+    assert_eq!(cache.get(&"steady"), Some(&2));
+}
+
+#[test]
+fn test_idle_hot_key_survives_without_decay() {
+    let clock = MockClock::new();
+    let mut cache = LfuCache::new(2);
+    hot_then_idle(&mut cache, &clock);
+
+    assert_eq!(cache.get(&"hot"), Some(&1));
+    assert_eq!(cache.get(&"steady"), None);
+}
+
+#[test]
+fn test_frequency_is_halved_per_elapsed_interval() {
+    let clock = MockClock::new();
+    let mut cache = LfuCache::builder()
+        .capacity(NonZeroUsize::new(4).unwrap())
+        .decay_interval(MINUTE)
+        .clock(clock.clone())
+        .build()
+        .unwrap();
+    cache.put("key", 1);
+    for _ in 0..15 {
+        cache.get(&"key");
+    }
+    assert_eq!(cache.frequency(&"key"), Some(16));
+
+    // Trois périodes révolues : 16 / 8, puis la lecture ajoute 1
+    clock.advance(MINUTE * 3 + Duration::from_secs(30));
+    cache.get(&"key");
+    assert_eq!(cache.frequency(&"key"), Some(3));
+
+    // Les vingt secondes suivantes ne complètent pas la période en cours
+    clock.advance(Duration::from_secs(20));
+    cache.get(&"key");
+    assert_eq!(cache.frequency(&"key"), Some(4));
+
+    // La période commencée avant la lecture précédente s'achève : 4 / 2 + 1
+    clock.advance(Duration::from_secs(10));
+    cache.get(&"key");
+    assert_eq!(cache.frequency(&"key"), Some(3));
+}
+
+#[test]
+fn test_partial_periods_carry_over_between_calls() {
+    let clock = MockClock::new();
+    let mut cache = LfuCache::builder()
+        .capacity(NonZeroUsize::new(4).unwrap())
+        .decay_interval(Duration::from_secs(10))
+        .clock(clock.clone())
+        .build()
+        .unwrap();
+    cache.put("key", 1);
+    cache.put("other", 2);
+    for _ in 0..1023 {
+        cache.get(&"key");
+    }
+    assert_eq!(cache.frequency(&"key"), Some(1024));
+
+    // Une lecture toutes les quinze secondes : six périodes en une minute
+    for _ in 0..4 {
+        clock.advance(Duration::from_secs(15));
+        cache.get(&"other");
+    }
+    assert_eq!(cache.frequency(&"key"), Some(16));
+}
+
+#[test]
+fn test_rewound_clock_does_not_decay() {
+    let clock = MockClock::new();
+    let mut cache = LfuCache::builder()
+        .capacity(NonZeroUsize::new(4).unwrap())
+        .decay_interval(MINUTE)
+        .clock(clock.clone())
+        .build()
+        .unwrap();
+    cache.put("key", 1);
+    cache.get(&"key");
+    clock.rewind(MINUTE * 10);
+    cache.get(&"key");
+    assert_eq!(cache.frequency(&"key"), Some(3));
+}
+
+#[test]
+fn test_frequency_is_halved_every_n_operations() {
+    let mut cache = LfuCache::builder()
+        .capacity(NonZeroUsize::new(4).unwrap())
+        .decay_every_ops(NonZeroU64::new(10).unwrap())
+        .build()
+        .unwrap();
+    cache.put("key", 1);
+    for _ in 0..8 {
+        cache.get(&"key");
+    }
+    assert_eq!(cache.frequency(&"key"), Some(9));
+
+    // Les échecs comptent aussi comme appels : le dixième divise par deux
+    cache.get(&"missing");
+    cache.get(&"key");
+    assert_eq!(cache.frequency(&"key"), Some(5));
+}
+
+#[test]
+fn test_decayed_key_is_evicted_before_new_key() {
+    let mut cache = LfuCache::builder()
+        .capacity(NonZeroUsize::new(2).unwrap())
+        .decay_every_ops(NonZeroU64::new(1).unwrap())
+        .build()
+        .unwrap();
+    cache.put("old", 1);
+    cache.put("young", 2);
+    assert_eq!(cache.frequency(&"old"), Some(0));
+    assert_eq!(cache.frequency(&"young"), Some(1));
+
+    cache.put("new", 3);
+    assert_eq!(cache.get(&"old"), None);
+    assert_eq!(cache.get(&"young"), Some(&2));
+}
+
+#[test]
+fn test_builder_rejects_invalid_configuration() {
+    let built = LfuCache::<&str, i32>::builder().build();
+    assert_eq!(built.unwrap_err(), CacheError::ZeroCapacity);
+
+    let built = LfuCache::<&str, i32>::builder()
+        .capacity(NonZeroUsize::new(2).unwrap())
+        .decay_interval(Duration::ZERO)
+        .build();
+    assert_eq!(built.unwrap_err(), CacheError::ZeroDecayPeriod);
+}
+
+#[test]
+fn test_zero_capacity_keeps_nothing() {
+    let mut cache = LfuCache::new(0);
+    cache.put(1, 1);
+    cache.put(2, 2);
+    assert_eq!(cache.len(), 0);
+    assert_eq!(cache.get(&2), None);
+}
+
+/// Clé qui n'implémente pas `Clone`
+#[derive(Debug, PartialEq, Eq, Hash)]
+struct Key(u32);
+
+#[test]
+fn test_keys_need_not_be_clone() {
+    let mut cache = LfuCache::builder()
+        .capacity(NonZeroUsize::new(2).unwrap())
+        .decay_every_ops(NonZeroU64::new(3).unwrap())
+        .build()
+        .unwrap();
+    cache.put(Key(1), 1);
+    cache.get(&Key(1));
+    cache.put(Key(2), 2);
+    assert_eq!(cache.frequency(&Key(1)), Some(1));
+    let keys: Vec<_> = cache.iter().map(|(key, _)| key.0).collect();
+    assert_eq!(keys, [1, 2]);
+
+    cache.put(Key(3), 3);
+    assert_eq!(cache.get(&Key(1)), None);
+    assert_eq!(cache.remove(&Key(2)), Some(2));
+    assert_eq!(cache.len(), 1);
+}
@@ -0,0 +1,247 @@
+use lru_cache::Cache;
+use lru_cache::cache::clock::MockClock;
+use lru_cache::cache::listener::{CacheEventListener, RemovalCause};
+use lru_cache::cache::stats::CacheStats;
+use lru_cache::cache::traits::{CacheLen, CacheRemove, CacheStorage};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+type Counts = Arc<Mutex<HashMap<RemovalCause, usize>>>;
+
+fn counting<K, V>(cache: &mut Cache<K, V>) -> Counts
+where
+    K: Eq + std::hash::Hash,
+{
+    let counts = Counts::default();
+    let seen = Arc::clone(&counts);
+    cache.set_eviction_listener(move |_, _, cause| *seen.lock().unwrap().entry(cause).or_default() += 1);
+    counts
+}
+
+fn count(counts: &Counts, cause: RemovalCause) -> usize {
+    counts.lock().unwrap().get(&cause).copied().unwrap_or(0)
+}
+
+#[test]
+fn test_listener_receives_evicted_entries() {
+    let evicted = Arc::new(Mutex::new(Vec::new()));
+    let sink = Arc::clone(&evicted);
+    let mut cache = Cache::with_eviction_listener(2, move |key, value, cause| {
+        sink.lock().unwrap().push((key, value, cause));
+    });
+    cache.put("A", 1);
+    cache.put("B", 2);
+    cache.get(&"A");
+    cache.put("C", 3);
+    cache.put("D", 4);
+
+    assert_eq!(
+        *evicted.lock().unwrap(),
+        vec![("B", 2, RemovalCause::Capacity), ("A", 1, RemovalCause::Capacity)]
+    );
+}
+
+#[test]
+fn test_listener_counts_invalidate_and_clear() {
+    let mut cache = Cache::new(4);
+    let counts = counting(&mut cache);
+    for key in 0..4 {
+        cache.put(key, key);
+    }
+
+    assert!(cache.invalidate(&1));
+    assert!(!cache.invalidate(&1));
+    assert_eq!(cache.remove(&2), Some(2));
+    assert_eq!(count(&counts, RemovalCause::Explicit), 1);
+
+    cache.clear();
+    assert!(cache.is_empty());
+    assert_eq!(count(&counts, RemovalCause::Cleared), 2);
+    assert_eq!(count(&counts, RemovalCause::Capacity), 0);
+}
+
+#[test]
+fn test_listener_reports_expired_entries() {
+    let clock = MockClock::new();
+    let mut cache = Cache::with_clock(3, clock.clone());
+    let counts = counting(&mut cache);
+    cache.put_with_ttl("A", 1, Duration::from_secs(1));
+    cache.put_with_ttl("B", 2, Duration::from_secs(1));
+    cache.put_with_ttl("C", 3, Duration::from_secs(1));
+    clock.advance(Duration::from_secs(1));
+
+    assert_eq!(cache.get(&"A"), None);
+    cache.put("D", 4);
+    cache.put("E", 5);
+    assert_eq!(cache.purge_expired(), 1);
+    assert_eq!(count(&counts, RemovalCause::Expired), 3);
+    assert_eq!(count(&counts, RemovalCause::Capacity), 0);
+}
+
+#[derive(Clone, Default)]
+struct Recorder(Arc<Mutex<Vec<String>>>);
+
+impl Recorder {
+    fn push(&self, event: &str, key: &&str) {
+        self.0.lock().unwrap().push(format!("{} {}", event, key));
+    }
+
+    fn take(&self) -> Vec<String> {
+        std::mem::take(&mut *self.0.lock().unwrap())
+    }
+}
+
+impl CacheEventListener<&str> for Recorder {
+    fn on_hit(&mut self, key: &&str) {
+        self.push("hit", key);
+    }
+
+    fn on_miss(&mut self, key: &&str) {
+        self.push("miss", key);
+    }
+
+    fn on_insert(&mut self, key: &&str) {
+        self.push("insert", key);
+    }
+
+    fn on_update(&mut self, key: &&str) {
+        self.push("update", key);
+    }
+
+    fn on_evict(&mut self, key: &&str) {
+        self.push("evict", key);
+    }
+
+    fn on_expire(&mut self, key: &&str) {
+        self.push("expire", key);
+    }
+}
+
+#[test]
+fn test_event_listener_sequence() {
+    let recorder = Recorder::default();
+    let mut cache = Cache::with_listener(2, recorder.clone());
+    cache.put("A", 1);
+    cache.put("B", 2);
+    cache.get(&"A");
+    cache.get(&"C");
+    cache.put("B", 20);
+    cache.put("C", 3);
+    cache.peek(&"B");
+    cache.invalidate(&"B");
+    cache.clear();
+
+    assert_eq!(
+        recorder.take(),
+        vec!["insert A", "insert B", "hit A", "miss C", "update B", "evict A", "insert C"]
+    );
+}
+
+#[test]
+fn test_event_listener_expiration() {
+    let clock = MockClock::new();
+    let recorder = Recorder::default();
+    let mut cache = Cache::with_clock(2, clock.clone());
+    cache.set_listener(recorder.clone());
+    cache.put_with_ttl("A", 1, Duration::from_secs(10));
+    clock.advance(Duration::from_secs(10));
+    assert_eq!(cache.get(&"A"), None);
+
+    assert_eq!(recorder.take(), vec!["insert A", "expire A", "miss A"]);
+}
+
+#[test]
+fn test_stats_match_event_listener() {
+    let mut stats = CacheStats::default();
+    let mut cache = Cache::with_listener(1, Recorder::default());
+    cache.put("A", 1);
+    cache.get(&"A");
+    cache.get(&"B");
+    cache.put("B", 2);
+
+    stats.on_insert(&"A");
+    stats.on_hit(&"A");
+    stats.on_miss(&"B");
+    stats.on_evict(&"A");
+    stats.on_insert(&"B");
+    assert_eq!(cache.stats(), stats);
+}
+
+#[test]
+fn test_every_removal_path_reports_its_cause() {
+    let clock = MockClock::new();
+    let mut cache = Cache::with_clock(3, clock.clone());
+    let removed = Arc::new(Mutex::new(Vec::new()));
+    let sink = Arc::clone(&removed);
+    cache.set_eviction_listener(move |key, value, cause| sink.lock().unwrap().push((key, value, cause)));
+    let take = || std::mem::take(&mut *removed.lock().unwrap());
+
+    cache.put("a", 1);
+    cache.put("a", 2);
+    assert_eq!(take(), [("a", 1, RemovalCause::Replaced)]);
+
+    cache.put("b", 3);
+    cache.put("c", 4);
+    cache.put("d", 5);
+    assert_eq!(take(), [("a", 2, RemovalCause::Capacity)]);
+
+    assert_eq!(cache.remove(&"b"), Some(3));
+    assert!(cache.invalidate(&"c"));
+    assert_eq!(take(), [("c", 4, RemovalCause::Explicit)]);
+
+    cache.put_with_ttl("e", 6, Duration::from_secs(1));
+    cache.put_with_ttl("f", 7, Duration::from_secs(1));
+    clock.advance(Duration::from_secs(1));
+    assert_eq!(cache.get(&"e"), None);
+    assert_eq!(take(), [("e", 6, RemovalCause::Expired)]);
+    assert_eq!(cache.purge_expired(), 1);
+    assert_eq!(take(), [("f", 7, RemovalCause::Expired)]);
+
+    cache.put("g", 8);
+    cache.put("h", 9);
+    cache.resize(std::num::NonZeroUsize::new(1).unwrap());
+    assert_eq!(take(), [("d", 5, RemovalCause::Capacity), ("g", 8, RemovalCause::Capacity)]);
+
+    cache.clear();
+    assert_eq!(take(), [("h", 9, RemovalCause::Cleared)]);
+    assert!(cache.is_empty());
+}
+
+#[test]
+fn test_put_over_expired_entry_is_an_insertion() {
+    let clock = MockClock::new();
+    let mut cache = Cache::with_clock(3, clock.clone());
+    let removed = Arc::new(Mutex::new(Vec::new()));
+    let sink = Arc::clone(&removed);
+    cache.set_eviction_listener(move |key, value, cause| sink.lock().unwrap().push((key, value, cause)));
+
+    cache.put_with_ttl("a", 1, Duration::from_secs(1));
+    assert!(cache.pin(&"a"));
+    clock.advance(Duration::from_secs(1));
+    cache.put("a", 2);
+
+    assert_eq!(*removed.lock().unwrap(), [("a", 1, RemovalCause::Expired)]);
+    let stats = cache.stats();
+    assert_eq!((stats.insertions, stats.updates, stats.expirations), (2, 0, 1));
+    assert_eq!(cache.pinned_len(), 0);
+    assert_eq!(cache.get(&"a"), Some(&2));
+}
+
+#[test]
+fn test_remove_by_id_reports_expired_entry() {
+    let clock = MockClock::new();
+    let mut cache = Cache::with_clock(3, clock.clone());
+    let removed = Arc::new(Mutex::new(Vec::new()));
+    let sink = Arc::clone(&removed);
+    cache.set_eviction_listener(move |key, value, cause| sink.lock().unwrap().push((key, value, cause)));
+
+    cache.put_with_ttl("a", 1, Duration::from_secs(1));
+    let id = cache.get_id(&"a").unwrap();
+    clock.advance(Duration::from_secs(1));
+
+    assert_eq!(cache.remove_by_id(id), None);
+    assert_eq!(*removed.lock().unwrap(), [("a", 1, RemovalCause::Expired)]);
+    assert_eq!(cache.stats().expirations, 1);
+    assert!(cache.is_empty());
+}
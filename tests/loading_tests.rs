@@ -0,0 +1,229 @@
+use lru_cache::Cache;
+use lru_cache::cache::builder::CacheBuilder;
+use lru_cache::cache::error::CacheError;
+use lru_cache::cache::loading::LoadingCache;
+use lru_cache::cache::clock::MockClock;
+use lru_cache::cache::lru::StaleEntry;
+use lru_cache::cache::traits::{CacheLen, CacheRemove, CacheStorage};
+use lru_cache::cache::ttl::TtlMode;
+use std::cell::Cell;
+use std::num::NonZeroUsize;
+use std::rc::Rc;
+use std::time::Duration;
+
+fn counting_loader(loads: Rc<Cell<usize>>) -> impl FnMut(&u32) -> Result<u32, String> {
+    move |key| {
+        loads.set(loads.get() + 1);
+        match key {
+            13 => Err(format!("clé {} introuvable", key)),
+            _ => Ok(key * 10),
+        }
+    }
+}
+
+#[test]
+fn test_one_load_per_distinct_key() {
+    let loads = Rc::new(Cell::new(0));
+    let mut cache = LoadingCache::new(3, counting_loader(Rc::clone(&loads)));
+    for _ in 0..5 {
+        for key in 1..=3 {
+            assert_eq!(cache.get(&key), Ok(&(key * 10)));
+        }
+    }
+    assert_eq!(loads.get(), 3);
+    assert_eq!(cache.stats().hits, 12);
+    assert_eq!(cache.stats().misses, 3);
+}
+
+#[test]
+fn test_evicted_keys_are_reloaded() {
+    let loads = Rc::new(Cell::new(0));
+    let mut cache = LoadingCache::new(2, counting_loader(Rc::clone(&loads)));
+    cache.get(&1).unwrap();
+    cache.get(&2).unwrap();
+    cache.get(&3).unwrap();
+    assert_eq!(cache.get_if_cached(&1), None);
+    assert_eq!(loads.get(), 3);
+
+    assert_eq!(cache.get(&1), Ok(&10));
+    assert_eq!(loads.get(), 4);
+    assert_eq!(cache.get_if_cached(&2), None);
+    assert_eq!(cache.len(), 2);
+}
+
+#[test]
+fn test_loader_errors_are_not_cached() {
+    let loads = Rc::new(Cell::new(0));
+    let mut cache = LoadingCache::new(2, counting_loader(Rc::clone(&loads)));
+    cache.get(&1).unwrap();
+    assert_eq!(cache.get(&13), Err(String::from("clé 13 introuvable")));
+    assert_eq!(cache.get(&13), Err(String::from("clé 13 introuvable")));
+    assert_eq!(loads.get(), 3);
+    assert_eq!(cache.len(), 1);
+    assert_eq!(cache.get_if_cached(&13), None);
+    assert_eq!(cache.get_if_cached(&1), Some(&10));
+}
+
+#[test]
+fn test_prefilled_cache_skips_loader() {
+    let mut inner = Cache::new(2);
+    inner.put(5, 7);
+    let loads = Rc::new(Cell::new(0));
+    let mut cache = LoadingCache::from_cache(inner, counting_loader(Rc::clone(&loads)));
+    cache.put(6, 8);
+    assert_eq!(cache.get(&5), Ok(&7));
+    assert_eq!(cache.get(&6), Ok(&8));
+    assert_eq!(loads.get(), 0);
+    assert_eq!(cache.into_inner().len(), 2);
+}
+
+fn stale_cache(clock: &MockClock, loads: Rc<Cell<u32>>) -> LoadingCache<u32, u32, impl FnMut(&u32) -> Result<u32, String>> {
+    let cache = Cache::with_ttl_and_clock(4, Duration::from_secs(10), TtlMode::AfterWrite, clock.clone());
+    LoadingCache::from_cache(cache, move |key: &u32| {
+        loads.set(loads.get() + 1);
+        Ok(key * 100 + loads.get())
+    })
+}
+
+#[test]
+fn test_get_stale_reports_fresh_and_expired_values() {
+    let clock = MockClock::new();
+    let mut cache = Cache::with_ttl_and_clock(2, Duration::from_secs(10), TtlMode::AfterWrite, clock.clone());
+    cache.put(1, "un");
+    assert_eq!(cache.get_stale(&1), Some(StaleEntry::Fresh(&"un")));
+    assert_eq!(cache.get_stale(&2), None);
+
+    clock.advance(Duration::from_secs(15));
+    let entry = cache.get_stale(&1);
+    assert_eq!(entry, Some(StaleEntry::Stale { value: &"un", expired_for: Duration::from_secs(5) }));
+    assert_eq!(cache.len(), 1);
+    assert_eq!(cache.stats().hits, 2);
+    assert_eq!(cache.get(&1), None);
+}
+
+#[test]
+fn test_stale_hit_is_served_then_refreshed() {
+    let clock = MockClock::new();
+    let loads = Rc::new(Cell::new(0));
+    let mut cache = stale_cache(&clock, Rc::clone(&loads));
+    assert_eq!(cache.get_with_stale_while_revalidate(&1), Ok(StaleEntry::Fresh(&101)));
+    assert_eq!(cache.get_with_stale_while_revalidate(&1), Ok(StaleEntry::Fresh(&101)));
+    assert_eq!(loads.get(), 1);
+
+    clock.advance(Duration::from_secs(12));
+    let entry = cache.get_with_stale_while_revalidate(&1);
+    assert_eq!(entry, Ok(StaleEntry::Stale { value: &101, expired_for: Duration::from_secs(2) }));
+    assert_eq!(loads.get(), 1);
+
+    assert_eq!(cache.get_with_stale_while_revalidate(&1), Ok(StaleEntry::Fresh(&102)));
+    assert_eq!(loads.get(), 2);
+}
+
+#[test]
+fn test_refresh_stale_skips_removed_keys() {
+    let clock = MockClock::new();
+    let loads = Rc::new(Cell::new(0));
+    let mut cache = stale_cache(&clock, Rc::clone(&loads));
+    cache.get_with_stale_while_revalidate(&1).unwrap();
+    cache.get_with_stale_while_revalidate(&2).unwrap();
+    clock.advance(Duration::from_secs(10));
+    assert!(cache.get_with_stale_while_revalidate(&1).unwrap().is_stale());
+    cache.remove(&1);
+    assert_eq!(cache.refresh_stale::<String>(), Ok(0));
+    assert_eq!(loads.get(), 2);
+
+    assert!(cache.get_with_stale_while_revalidate(&2).unwrap().is_stale());
+    assert_eq!(cache.refresh_stale::<String>(), Ok(1));
+    assert_eq!(cache.get_if_cached(&2), Some(&203));
+    assert_eq!(cache.get_if_cached(&1), None);
+}
+
+/// Cache d'une durée de vie de 10 secondes rechargé dans ses 2 dernières
+/// secondes, dont le chargeur retourne le numéro de son appel
+fn refresh_ahead_cache(
+    clock: &MockClock,
+    loads: Rc<Cell<u32>>,
+    fail: Rc<Cell<bool>>,
+) -> LoadingCache<&'static str, u32, impl FnMut(&&'static str) -> Result<u32, String>> {
+    let cache = CacheBuilder::new()
+        .capacity(NonZeroUsize::new(4).unwrap())
+        .time_to_live(Duration::from_secs(10))
+        .refresh_ahead(0.2)
+        .clock(clock.clone())
+        .build()
+        .unwrap();
+    LoadingCache::from_cache(cache, move |_: &&'static str| {
+        loads.set(loads.get() + 1);
+        if fail.get() {
+            return Err(String::from("source indisponible"));
+        }
+        Ok(loads.get())
+    })
+}
+
+#[test]
+fn test_refresh_ahead_not_triggered_before_window() {
+    let clock = MockClock::new();
+    let loads = Rc::new(Cell::new(0));
+    let mut cache = refresh_ahead_cache(&clock, Rc::clone(&loads), Rc::default());
+    assert_eq!(cache.get(&"A"), Ok(&1));
+
+    clock.advance(Duration::from_secs(8));
+    for _ in 0..5 {
+        assert_eq!(cache.get(&"A"), Ok(&1));
+    }
+    assert_eq!(cache.refresh_stale(), Ok(0));
+    assert_eq!(loads.get(), 1);
+}
+
+#[test]
+fn test_refresh_ahead_reloads_once_in_window() {
+    let clock = MockClock::new();
+    let loads = Rc::new(Cell::new(0));
+    let mut cache = refresh_ahead_cache(&clock, Rc::clone(&loads), Rc::default());
+    assert_eq!(cache.get(&"A"), Ok(&1));
+
+    clock.advance(Duration::from_secs(9));
+    // Servie sans attendre, puis rechargée au début de l'appel suivant
+    assert_eq!(cache.get(&"A"), Ok(&1));
+    assert_eq!(loads.get(), 1);
+    for _ in 0..5 {
+        assert_eq!(cache.get(&"A"), Ok(&2));
+    }
+    assert_eq!(loads.get(), 2);
+
+    // La nouvelle valeur repart d'une durée de vie entière
+    clock.advance(Duration::from_secs(7));
+    assert_eq!(cache.get(&"A"), Ok(&2));
+    assert_eq!(cache.ttl_remaining(&"A"), Some(Duration::from_secs(3)));
+    assert_eq!(loads.get(), 2);
+}
+
+#[test]
+fn test_refresh_ahead_keeps_old_value_on_loader_failure() {
+    let clock = MockClock::new();
+    let loads = Rc::new(Cell::new(0));
+    let fail = Rc::new(Cell::new(false));
+    let mut cache = refresh_ahead_cache(&clock, Rc::clone(&loads), Rc::clone(&fail));
+    assert_eq!(cache.get(&"A"), Ok(&1));
+
+    clock.advance(Duration::from_secs(9));
+    fail.set(true);
+    assert_eq!(cache.get(&"A"), Ok(&1));
+    assert_eq!(cache.refresh_stale(), Err(String::from("source indisponible")));
+    assert_eq!(cache.get(&"A"), Ok(&1));
+    assert_eq!(loads.get(), 2);
+
+    // Marquée de nouveau, la clé est rechargée dès que la source répond
+    fail.set(false);
+    assert_eq!(cache.get(&"A"), Ok(&3));
+}
+
+#[test]
+fn test_refresh_ahead_fraction_is_validated() {
+    let built = CacheBuilder::<u32, u32>::new()
+        .capacity(NonZeroUsize::new(2).unwrap())
+        .refresh_ahead(1.5)
+        .build();
+    assert_eq!(built.unwrap_err(), CacheError::InvalidRefreshAhead);
+}
@@ -0,0 +1,111 @@
+mod common;
+
+use lru_cache::Cache;
+use lru_cache::cache::traits::{CacheLen, CacheStorage};
+use lru_cache::storage::error::StorageError;
+use lru_cache::storage::file::FileStorage;
+use lru_cache::storage::lock::FileLock;
+use std::fs;
+use std::io;
+use std::num::NonZeroUsize;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+const TIMEOUT: Duration = Duration::from_secs(30);
+
+fn cleanup(path: PathBuf) {
+    fs::remove_file(FileLock::lock_path(&path)).unwrap();
+    fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn test_concurrent_saves_never_mix_snapshots() {
+    let path = Arc::new(common::temp_path("lock_concurrent.txt"));
+    FileStorage::save_locked(path.as_path(), 500, &[(0, 0)], TIMEOUT).unwrap();
+
+    let writers: Vec<_> = (1..=4)
+        .map(|writer| {
+            let path = Arc::clone(&path);
+            thread::spawn(move || {
+                let data: Vec<_> = (0..500).map(|key| (key, writer)).collect();
+                for _ in 0..10 {
+                    FileStorage::save_locked(path.as_path(), 500, &data, TIMEOUT).unwrap();
+                }
+            })
+        })
+        .collect();
+    let reader = {
+        let path = Arc::clone(&path);
+        thread::spawn(move || {
+            for _ in 0..20 {
+                let (_, data) = FileStorage::load_locked::<u32, u32>(path.as_path(), TIMEOUT).unwrap();
+                let writer = data[0].1;
+                assert!(data.iter().all(|&(_, value)| value == writer), "instantanés mélangés");
+            }
+        })
+    };
+    for writer in writers {
+        writer.join().unwrap();
+    }
+    reader.join().unwrap();
+
+    let (_, data) = FileStorage::load::<u32, u32>(path.as_path()).unwrap();
+    assert_eq!(data.len(), 500);
+    assert!((1..=4).contains(&data[0].1));
+    cleanup(Arc::try_unwrap(path).unwrap());
+}
+
+#[test]
+fn test_exclusive_lock_serializes_read_modify_write() {
+    let path = Arc::new(common::temp_path("lock_update.txt"));
+    FileStorage::save(path.as_path(), 16, &Vec::<(String, u32)>::new()).unwrap();
+
+    let workers: Vec<_> = (0..8)
+        .map(|worker| {
+            let path = Arc::clone(&path);
+            thread::spawn(move || {
+                let _lock = FileLock::exclusive(path.as_path(), TIMEOUT).unwrap();
+                let (capacity, mut data) = FileStorage::load::<String, u32>(path.as_path()).unwrap();
+                thread::sleep(Duration::from_millis(2));
+                data.push((format!("worker{}", worker), worker));
+                FileStorage::save(path.as_path(), capacity, &data).unwrap();
+            })
+        })
+        .collect();
+    for worker in workers {
+        worker.join().unwrap();
+    }
+
+    let cache = Cache::<String, u32>::load_from_file_locked(path.as_path(), NonZeroUsize::new(16).unwrap(), TIMEOUT).unwrap();
+    assert_eq!(cache.len(), 8);
+    cleanup(Arc::try_unwrap(path).unwrap());
+}
+
+#[test]
+fn test_lock_timeout() {
+    let path = common::temp_path("lock_timeout.txt");
+    let mut cache = Cache::new(2);
+    cache.put(1, 1);
+    cache.save_to_file_locked(&path, TIMEOUT).unwrap();
+    let short = Duration::from_millis(20);
+
+    let first = FileLock::shared(&path, short).unwrap();
+    let second = FileLock::shared(&path, short).unwrap();
+    assert!(!first.is_exclusive());
+    assert!(FileStorage::load_locked::<i32, i32>(&path, short).is_ok());
+    assert!(matches!(cache.save_to_file_locked(&path, short), Err(StorageError::LockTimeout(timeout)) if timeout == short));
+    drop((first, second));
+
+    let exclusive = FileLock::exclusive(&path, short).unwrap();
+    assert!(exclusive.is_exclusive());
+    let error = FileStorage::load_locked::<i32, i32>(&path, short).unwrap_err();
+    assert!(matches!(error, StorageError::LockTimeout(_)));
+    assert_eq!(io::Error::from(error).kind(), io::ErrorKind::TimedOut);
+    assert!(Cache::<i32, i32>::load_from_file_locked(&path, NonZeroUsize::new(2).unwrap(), Duration::ZERO).is_err());
+    drop(exclusive);
+
+    assert_eq!(Cache::<i32, i32>::load_from_file_locked(&path, NonZeroUsize::new(2).unwrap(), short).unwrap().len(), 1);
+    cleanup(path);
+}
@@ -0,0 +1,107 @@
+use lru_cache::Cache;
+use lru_cache::cache::clock::MockClock;
+use lru_cache::cache::traits::{CacheIterate, CacheLen, CacheStorage};
+use std::time::Duration;
+
+#[derive(Debug, PartialEq)]
+struct Port(u16);
+
+fn raw_cache() -> Cache<&'static str, String> {
+    let mut cache = Cache::new(4);
+    for (key, value) in [("http", "80"), ("ssh", "22"), ("dns", "53"), ("smtp", "25")] {
+        cache.put(key, value.to_string());
+    }
+    cache.get(&"http");
+    cache.get(&"dns");
+    cache
+}
+
+fn keys<V>(cache: &Cache<&'static str, V>) -> Vec<&'static str> {
+    cache.iter().map(|(&key, _)| key).collect()
+}
+
+#[test]
+fn test_map_values_keeps_recency_order() {
+    let cache = raw_cache();
+    let before = keys(&cache);
+    let stats = cache.stats();
+
+    let mut parsed = cache.map_values(|_, value| Port(value.parse().unwrap()));
+    assert_eq!(keys(&parsed), before);
+    assert_eq!(parsed.capacity(), 4);
+    assert_eq!(parsed.stats(), stats);
+    assert_eq!(parsed.peek(&"ssh"), Some(&Port(22)));
+
+    // La prochaine victime est la même qu'avant la transformation
+    parsed.put("ftp", Port(21));
+    assert_eq!(keys(&parsed), vec!["smtp", "http", "dns", "ftp"]);
+    assert!(!parsed.contains_key(&before[0]));
+}
+
+#[test]
+fn test_map_values_keeps_entry_metadata() {
+    let clock = MockClock::new();
+    let mut cache = Cache::with_clock(3, clock.clone());
+    cache.put_with_ttl("short", "1".to_string(), Duration::from_secs(10));
+    cache.put("pinned", "2".to_string());
+    cache.put("other", "3".to_string());
+    cache.pin(&"pinned");
+
+    let mut parsed = cache.map_values(|_, value| value.parse::<u32>().unwrap());
+    clock.advance(Duration::from_secs(20));
+    assert_eq!(parsed.get(&"short"), None);
+    parsed.put("a", 4);
+    parsed.put("b", 5);
+    assert_eq!(parsed.peek(&"pinned"), Some(&2));
+    assert_eq!(parsed.peek(&"other"), None);
+}
+
+#[test]
+fn test_map_values_drops_expired_entries() {
+    let clock = MockClock::new();
+    let mut cache = Cache::with_clock(3, clock.clone());
+    cache.put_with_ttl("old", 1, Duration::from_secs(1));
+    cache.put("kept", 2);
+    clock.advance(Duration::from_secs(2));
+
+    let mut seen = Vec::new();
+    let doubled = cache.map_values(|&key, value| {
+        seen.push(key);
+        value * 2
+    });
+    assert_eq!(seen, vec!["kept"]);
+    assert_eq!(doubled.len(), 1);
+    assert_eq!(doubled.peek(&"kept"), Some(&4));
+}
+
+#[test]
+fn test_try_map_values_returns_untouched_cache_on_error() {
+    let mut cache = raw_cache();
+    cache.put("bad", "quatre-vingts".to_string());
+    let before = keys(&cache);
+
+    let (error, mut cache) = cache.try_map_values(|_, value| value.parse::<u16>().map(Port)).unwrap_err();
+    assert!(error.to_string().contains("invalid digit"));
+    assert_eq!(keys(&cache), before);
+    assert_eq!(cache.get(&"bad").map(String::as_str), Some("quatre-vingts"));
+
+    cache.put("bad", "8080".to_string());
+    let mut parsed = cache.try_map_values(|_, value| value.parse::<u16>().map(Port)).unwrap();
+    assert_eq!(parsed.get(&"bad"), Some(&Port(8080)));
+    assert_eq!(parsed.len(), 4);
+}
+
+#[test]
+fn test_map_values_of_weighted_cache_counts_entries() {
+    let mut cache = Cache::with_weigher(6, |_: &&str, value: &String| value.len());
+    cache.put("a", "aaa".to_string());
+    cache.put("b", "bbb".to_string());
+    assert!(cache.is_full());
+
+    let mut lengths = cache.map_values(|_, value| value.len());
+    for key in ["c", "d", "e", "f"] {
+        lengths.put(key, 0);
+    }
+    assert_eq!(lengths.len(), 6);
+    assert_eq!(lengths.peek(&"a"), Some(&3));
+}
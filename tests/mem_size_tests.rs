@@ -0,0 +1,54 @@
+use lru_cache::Cache;
+use lru_cache::cache::mem_size::{MemSize, ENTRY_OVERHEAD};
+use lru_cache::cache::traits::{CacheRemove, CacheStorage};
+use std::mem::size_of;
+
+#[test]
+fn test_string_and_vec_sizes_count_their_capacity() {
+    let mut text = String::with_capacity(32);
+    text.push_str("abc");
+    assert_eq!(text.mem_size(), size_of::<String>() + 32);
+
+    let mut bytes: Vec<u8> = Vec::with_capacity(10);
+    bytes.extend_from_slice(b"abcd");
+    assert_eq!(bytes.mem_size(), size_of::<Vec<u8>>() + 10);
+
+    let strings = vec![String::with_capacity(5), String::with_capacity(7)];
+    assert_eq!(strings.mem_size(), size_of::<Vec<String>>() + 2 * size_of::<String>() + 12);
+}
+
+#[test]
+fn test_option_and_tuple_sizes_add_heap_once() {
+    let text = String::with_capacity(8);
+    assert_eq!(Some(String::with_capacity(8)).mem_size(), size_of::<Option<String>>() + 8);
+    assert_eq!(None::<String>.mem_size(), size_of::<Option<String>>());
+    assert_eq!((1u64, text).mem_size(), size_of::<(u64, String)>() + 8);
+    assert_eq!(3i16.mem_size(), 2);
+}
+
+#[test]
+fn test_estimate_grows_and_shrinks_with_entries() {
+    let mut cache = Cache::new(4);
+    assert_eq!(cache.estimated_memory(), 0);
+    cache.put(1u32, String::from("a"));
+    let one = cache.estimated_memory();
+
+    cache.put(2, String::with_capacity(100));
+    let two = cache.estimated_memory();
+    assert!(two >= one + ENTRY_OVERHEAD + 100);
+
+    cache.remove(&2);
+    assert_eq!(cache.estimated_memory(), one);
+}
+
+#[test]
+fn test_heaviest_lists_largest_entries_first() {
+    let mut cache = Cache::new(4);
+    cache.put(1u32, String::with_capacity(10));
+    cache.put(2, String::with_capacity(50));
+    cache.put(3, String::with_capacity(10));
+    cache.put(4, String::with_capacity(30));
+    let base = size_of::<u32>() + size_of::<String>();
+    assert_eq!(cache.heaviest(3), vec![(&2, base + 50), (&4, base + 30), (&3, base + 10)]);
+    assert_eq!(cache.heaviest(0), vec![]);
+}
@@ -0,0 +1,73 @@
+use lru_cache::Cache;
+use lru_cache::cache::error::InvariantError;
+use lru_cache::cache::model::{check_model, random_ops, shrink, Op};
+use lru_cache::cache::traits::{CacheLen, CacheRemove, CacheStorage};
+use lru_cache::cache::weigher::Weigher;
+use std::num::NonZeroUsize;
+
+fn capacity(n: usize) -> NonZeroUsize {
+    NonZeroUsize::new(n).unwrap()
+}
+
+#[test]
+fn test_random_operations_match_reference_model() {
+    for seed in 0..200 {
+        let ops = random_ops(seed, 300, 12, 6);
+        for initial in [1, 3, 8] {
+            if let Err(divergence) = check_model(capacity(initial), &ops) {
+                let ops = shrink(capacity(initial), &ops);
+                panic!("graine {} : {}\nsuite réduite : {:?}", seed, divergence, ops);
+            }
+        }
+    }
+}
+
+#[test]
+fn test_random_ops_are_reproducible() {
+    assert_eq!(random_ops(7, 50, 4, 3), random_ops(7, 50, 4, 3));
+    assert_ne!(random_ops(7, 50, 4, 3), random_ops(8, 50, 4, 3));
+    assert!(random_ops(7, 500, 4, 3).iter().all(|op| match op {
+        Op::Put(key, _) | Op::Get(key) | Op::Remove(key) => *key < 4,
+        Op::Resize(capacity) => capacity.get() <= 3,
+    }));
+}
+
+#[test]
+fn test_shrink_keeps_passing_sequence() {
+    let ops = random_ops(1, 20, 4, 3);
+    assert_eq!(shrink(capacity(2), &ops), ops);
+}
+
+#[test]
+fn test_invariants_hold_with_pins_ttl_and_weights() {
+    struct Length;
+
+    impl Weigher<&'static str, String> for Length {
+        fn weight(&self, _key: &&'static str, value: &String) -> usize {
+            value.len()
+        }
+    }
+
+    let mut cache = Cache::with_weigher(10, Length);
+    cache.put("a", String::from("aaaa"));
+    cache.put_with_ttl("b", String::from("bbb"), std::time::Duration::from_secs(60));
+    cache.pin(&"a");
+    cache.put("c", String::from("cccccc"));
+    assert_eq!(cache.check_invariants(), Ok(()));
+    cache.remove(&"b");
+    cache.unpin(&"a");
+    assert_eq!(cache.check_invariants(), Ok(()));
+
+    let mut pinned = Cache::new(1);
+    pinned.put(1, 1);
+    pinned.pin(&1);
+    pinned.put(2, 2);
+    assert_eq!(pinned.len(), 2);
+    assert_eq!(pinned.check_invariants(), Ok(()));
+}
+
+#[test]
+fn test_invariant_error_display() {
+    let error = InvariantError::OverCapacity { size: 3, capacity: 2 };
+    assert_eq!(error.to_string(), "taille 3 au-delà de la capacité 2");
+}
@@ -0,0 +1,116 @@
+use lru_cache::Cache;
+use lru_cache::cache::clock::MockClock;
+use lru_cache::cache::loading::LoadingCache;
+use lru_cache::cache::lru::Lookup;
+use lru_cache::cache::traits::{CacheIterate, CacheLen, CacheRemove, CacheStorage};
+use lru_cache::cache::ttl::TtlMode;
+use std::cell::Cell;
+use std::time::Duration;
+
+#[test]
+fn test_lookup_distinguishes_hit_negative_hit_and_miss() {
+    let mut cache = Cache::new(3);
+    cache.put("A", 1);
+    cache.put_negative("B", None);
+
+    assert_eq!(cache.lookup(&"A"), Lookup::Hit(&1));
+    assert_eq!(cache.lookup(&"B"), Lookup::NegativeHit);
+    assert_eq!(cache.lookup(&"C"), Lookup::Miss);
+    assert_eq!(cache.stats().hits, 2);
+    assert_eq!(cache.stats().misses, 1);
+
+    assert_eq!(cache.get(&"B"), None);
+    assert_eq!(cache.peek(&"B"), None);
+    assert!(!cache.contains_key(&"B"));
+    assert_eq!(cache.iter().collect::<Vec<_>>(), [(&"A", &1)]);
+    assert_eq!(cache.check_invariants(), Ok(()));
+}
+
+#[test]
+fn test_negative_entry_occupies_capacity_and_is_evicted() {
+    let mut cache = Cache::new(2);
+    cache.put_negative("A", None);
+    cache.put("B", 2);
+    assert_eq!(cache.len(), 2);
+    assert!(cache.is_full());
+
+    cache.put("C", 3);
+    assert_eq!(cache.lookup(&"A"), Lookup::Miss);
+
+    cache.put_negative("D", None);
+    assert_eq!(cache.lookup(&"B"), Lookup::Miss);
+    assert_eq!(cache.lookup(&"D"), Lookup::NegativeHit);
+    assert_eq!(cache.evict_n(2), [("C", 3)]);
+    assert!(cache.is_empty());
+}
+
+#[test]
+fn test_put_replaces_negative_entry_and_back() {
+    let mut cache = Cache::new(2);
+    cache.put_negative("A", None);
+    cache.put("A", 1);
+    assert_eq!(cache.lookup(&"A"), Lookup::Hit(&1));
+    assert_eq!(cache.len(), 1);
+
+    cache.put_negative("A", None);
+    assert_eq!(cache.lookup(&"A"), Lookup::NegativeHit);
+    assert_eq!(cache.len(), 1);
+    assert_eq!(cache.remove(&"A"), None);
+    assert_eq!(cache.lookup(&"A"), Lookup::Miss);
+    assert!(cache.is_empty());
+}
+
+#[test]
+fn test_negative_entry_expires() {
+    let clock = MockClock::new();
+    let mut cache: Cache<&str, i32> = Cache::with_clock(3, clock.clone());
+    cache.put_negative("A", Some(Duration::from_secs(10)));
+    cache.put_negative("B", None);
+
+    clock.advance(Duration::from_secs(9));
+    assert_eq!(cache.lookup(&"A"), Lookup::NegativeHit);
+    clock.advance(Duration::from_secs(1));
+    assert_eq!(cache.lookup(&"A"), Lookup::Miss);
+    assert_eq!(cache.len(), 1);
+    assert_eq!(cache.lookup(&"B"), Lookup::NegativeHit);
+}
+
+#[test]
+fn test_negative_entry_uses_default_ttl() {
+    let clock = MockClock::new();
+    let mut cache: Cache<&str, i32> = Cache::with_ttl_and_clock(2, Duration::from_secs(5), TtlMode::AfterWrite, clock.clone());
+    cache.put_negative("A", None);
+    clock.advance(Duration::from_secs(5));
+    assert_eq!(cache.lookup(&"A"), Lookup::Miss);
+}
+
+#[test]
+fn test_negative_entries_are_not_saved() {
+    let mut cache = Cache::new(3);
+    cache.put(String::from("a"), 1);
+    cache.put_negative(String::from("b"), None);
+    let restored = Cache::restore(cache.snapshot());
+    assert_eq!(restored.len(), 1);
+    assert_eq!(Vec::from(cache), [(String::from("a"), 1)]);
+}
+
+#[test]
+fn test_loading_cache_honors_negative_hits() {
+    let calls = Cell::new(0);
+    let mut cache = LoadingCache::new(3, |key: &u32| -> Result<Option<u32>, String> {
+        calls.set(calls.get() + 1);
+        Ok((*key < 10).then_some(key * 10))
+    });
+    assert_eq!(cache.get_optional(&1), Ok(Some(&10)));
+    assert_eq!(cache.get_optional(&42), Ok(None));
+    assert_eq!(cache.get_optional(&42), Ok(None));
+    assert_eq!(calls.get(), 2);
+
+    cache.put_negative(2, None);
+    assert_eq!(cache.get_optional(&2), Ok(None));
+    assert_eq!(calls.get(), 2);
+
+    cache.invalidate(&42);
+    assert_eq!(cache.get_optional(&42), Ok(None));
+    assert_eq!(calls.get(), 3);
+}
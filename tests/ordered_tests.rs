@@ -0,0 +1,101 @@
+mod common;
+
+use lru_cache::Cache;
+use lru_cache::cache::ordered::OrderedCache;
+use lru_cache::cache::traits::{CacheIterate, CacheLen, CacheRemove, CacheStorage, GetOptions, PersistentStorage};
+use std::fs;
+use std::num::NonZeroUsize;
+
+fn keys_in<'a>(entries: impl Iterator<Item = (&'a u64, &'a String)>) -> Vec<u64> {
+    entries.map(|(key, _)| *key).collect()
+}
+
+#[test]
+fn test_range_before_and_after_evictions() {
+    let mut cache = OrderedCache::new(4);
+    for timestamp in [40u64, 10, 30, 20] {
+        cache.put(timestamp, format!("t{}", timestamp));
+    }
+    assert_eq!(keys_in(cache.range(15..=35)), vec![20, 30]);
+    assert_eq!(keys_in(cache.range(..)), vec![10, 20, 30, 40]);
+    assert_eq!(cache.first_key_value(), Some((&10, &String::from("t10"))));
+    assert_eq!(cache.last_key_value(), Some((&40, &String::from("t40"))));
+
+    // 40 puis 10 sont les moins récemment utilisées
+    cache.put(25, String::from("t25"));
+    cache.put(50, String::from("t50"));
+    assert_eq!(keys_in(cache.range(..)), vec![20, 25, 30, 50]);
+    assert_eq!(keys_in(cache.range(15..=35)), vec![20, 25, 30]);
+    assert_eq!(keys_in(cache.range(31..50)), Vec::<u64>::new());
+    assert_eq!(cache.first_key_value().map(|(key, _)| *key), Some(20));
+    assert_eq!(cache.last_key_value().map(|(key, _)| *key), Some(50));
+
+    assert_eq!(cache.remove(&50), Some(String::from("t50")));
+    assert_eq!(cache.last_key_value().map(|(key, _)| *key), Some(30));
+    assert_eq!(cache.len(), 3);
+}
+
+#[test]
+fn test_eviction_matches_lru_regardless_of_key_order() {
+    let mut ordered = OrderedCache::new(3);
+    let mut lru = Cache::new(3);
+    let operations = [(5u64, true), (1, true), (9, true), (5, false), (3, true), (1, false), (7, true), (9, false), (2, true), (5, true)];
+    for (key, write) in operations {
+        if write {
+            ordered.put(key, key.to_string());
+            lru.put(key, key.to_string());
+        } else {
+            assert_eq!(ordered.get(&key), lru.get(&key));
+        }
+        let ordered_keys: Vec<u64> = ordered.iter().map(|(key, _)| *key).collect();
+        let lru_keys: Vec<u64> = lru.iter().map(|(key, _)| *key).collect();
+        assert_eq!(ordered_keys, lru_keys);
+    }
+}
+
+#[test]
+fn test_range_does_not_perturb_recency_but_range_touch_does() {
+    let mut cache = OrderedCache::new(3);
+    cache.put(1u64, String::from("a"));
+    cache.put(2, String::from("b"));
+    cache.put(3, String::from("c"));
+
+    assert_eq!(cache.range(..=2).count(), 2);
+    assert_eq!(cache.first_key_value().map(|(key, _)| *key), Some(1));
+    cache.put(4, String::from("d"));
+    assert_eq!(keys_in(cache.range(..)), vec![2, 3, 4]);
+
+    assert_eq!(keys_in(cache.range_touch(2..4)), vec![2, 3]);
+    assert_eq!(keys_in(cache.iter()), vec![4, 2, 3]);
+    cache.put(5, String::from("e"));
+    assert_eq!(keys_in(cache.range(..)), vec![2, 3, 5]);
+}
+
+#[test]
+fn test_passive_get_does_not_promote() {
+    let mut cache = OrderedCache::new(2);
+    cache.put(1u64, String::from("a"));
+    cache.put(2, String::from("b"));
+
+    assert_eq!(cache.get_with(&1, GetOptions::passive()), Some(&String::from("a")));
+    cache.put(3, String::from("c"));
+    assert_eq!(keys_in(cache.range(..)), vec![2, 3]);
+}
+
+#[test]
+fn test_ordered_cache_persistence() {
+    let path = common::temp_path("ordered.txt");
+    let mut cache = OrderedCache::new(3);
+    cache.put(30u64, String::from("c"));
+    cache.put(10, String::from("a"));
+    cache.put(20, String::from("b"));
+    cache.get(&30);
+    cache.save_to_file(&path).unwrap();
+
+    let mut loaded = OrderedCache::<u64, String>::load_from_file(&path, NonZeroUsize::new(3).unwrap()).unwrap();
+    assert_eq!(keys_in(loaded.iter()), vec![10, 20, 30]);
+    assert_eq!(keys_in(loaded.range(15..)), vec![20, 30]);
+    loaded.put(40, String::from("d"));
+    assert_eq!(loaded.get(&10), None);
+    fs::remove_file(&path).unwrap();
+}
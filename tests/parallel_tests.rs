@@ -0,0 +1,156 @@
+mod common;
+
+use lru_cache::Cache;
+use lru_cache::cache::traits::{CacheIterate, CacheStorage};
+use lru_cache::storage::file::{FileStorage, Record, SaveOptions};
+use std::fmt::Debug;
+use std::fs;
+use std::path::PathBuf;
+use std::str::FromStr;
+
+const LINES: u64 = 100_000;
+
+/// Entrées dont les valeurs contiennent des caractères à échapper
+fn generated() -> Vec<(u64, String)> {
+    (0..LINES).map(|i| (i, format!("valeur;{}\\n{}", i, "é".repeat((i % 7) as usize)))).collect()
+}
+
+/// Vérifie que les deux chargements donnent le même résultat, erreur
+/// comprise, et retourne les entrées
+fn same_load<K, V>(path: &PathBuf) -> Option<(usize, Vec<(K, V)>)>
+where
+    K: FromStr + Send + PartialEq + Debug,
+    V: FromStr + Send + PartialEq + Debug,
+{
+    let sequential = FileStorage::load::<K, V>(path);
+    let parallel = FileStorage::load_parallel::<K, V>(path);
+    match (sequential, parallel) {
+        (Ok(sequential), Ok(parallel)) => {
+            assert_eq!(sequential, parallel);
+            Some(parallel)
+        }
+        (Err(sequential), Err(parallel)) => {
+            assert_eq!(sequential.to_string(), parallel.to_string());
+            None
+        }
+        (sequential, parallel) => panic!("résultats différents : {:?} / {:?}", sequential.err(), parallel.err()),
+    }
+}
+
+#[test]
+fn test_load_parallel_matches_load_on_100k_lines() {
+    let path = common::temp_path("parallel_100k.txt");
+    let data = generated();
+    FileStorage::save(&path, LINES as usize, &data).unwrap();
+
+    let (capacity, loaded) = same_load::<u64, String>(&path).unwrap();
+    assert_eq!((capacity, loaded), (LINES as usize, data));
+    same_load::<String, String>(&path).unwrap();
+    // Les valeurs ne sont pas des nombres : toutes les lignes sont ignorées
+    assert_eq!(same_load::<u64, u64>(&path).unwrap().1, vec![]);
+
+    // Les entrées des deux chargements se sauvegardent à l'octet près de la
+    // même façon
+    let copy = common::temp_path("parallel_100k_copy.txt");
+    let (_, sequential) = FileStorage::load::<u64, String>(&path).unwrap();
+    let (_, parallel) = FileStorage::load_parallel::<u64, String>(&path).unwrap();
+    FileStorage::save(&path, LINES as usize, &sequential).unwrap();
+    FileStorage::save(&copy, LINES as usize, &parallel).unwrap();
+    assert_eq!(fs::read(&path).unwrap(), fs::read(&copy).unwrap());
+
+    fs::remove_file(&path).unwrap();
+    fs::remove_file(&copy).unwrap();
+}
+
+#[test]
+fn test_load_parallel_replays_incremental_segments() {
+    let path = common::temp_path("parallel_segments.txt");
+    FileStorage::save(&path, LINES as usize, &generated()).unwrap();
+    for round in 0..3u64 {
+        let records = (0..LINES).step_by(3).map(|i| match (i + round) % 2 {
+            0 => Record::Put(i, format!("tour {}", round)),
+            _ => Record::Remove(i + round),
+        });
+        FileStorage::append(&path, records).unwrap();
+    }
+
+    let (_, loaded) = same_load::<u64, String>(&path).unwrap();
+    assert!(loaded.iter().any(|(_, value)| value == "tour 2"));
+    assert!(loaded.len() < LINES as usize);
+
+    fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn test_load_parallel_reports_the_same_errors() {
+    let path = common::temp_path("parallel_errors.txt");
+    FileStorage::save(&path, LINES as usize, &generated()).unwrap();
+    let saved = fs::read(&path).unwrap();
+
+    // Octet modifié au milieu du fichier, dans un bloc éloigné de la somme
+    let mut corrupted = saved.clone();
+    let middle = corrupted.len() / 2;
+    corrupted[middle] = if corrupted[middle] == b'0' { b'1' } else { b'0' };
+    fs::write(&path, &corrupted).unwrap();
+    assert!(same_load::<u64, String>(&path).is_none());
+
+    // Ligne qui n'est pas de l'UTF-8
+    let mut invalid = saved.clone();
+    invalid.splice(middle..middle, [0xff, 0xfe]);
+    fs::write(&path, &invalid).unwrap();
+    assert!(same_load::<u64, String>(&path).is_none());
+
+    // Fin du fichier tronquée : la somme de contrôle manque
+    fs::write(&path, &saved[..saved.len() - 20]).unwrap();
+    assert!(same_load::<u64, String>(&path).is_none());
+
+    fs::write(&path, "").unwrap();
+    assert!(same_load::<u64, String>(&path).is_none());
+    fs::write(&path, "pas une capacité\n").unwrap();
+    assert!(same_load::<u64, String>(&path).is_none());
+
+    fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn test_load_parallel_reads_legacy_and_unusual_lines() {
+    let path = common::temp_path("parallel_legacy.txt");
+    let long_value = "x".repeat(17 * 1024 * 1024);
+    let text = format!("3\r\na;1\r\n#crc32 00000000\r\nligne illisible\nb;{}\nc;3\n\nd;4", long_value);
+    fs::write(&path, text).unwrap();
+
+    let (capacity, loaded) = same_load::<String, String>(&path).unwrap();
+    assert_eq!(capacity, 3);
+    let keys: Vec<&str> = loaded.iter().map(|(key, _)| key.as_str()).collect();
+    assert_eq!(keys, ["a", "c", "d"]);
+
+    fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn test_load_parallel_reads_compressed_files() {
+    let path = common::temp_path("parallel_compressed.txt.gz");
+    FileStorage::save_compressed(&path, LINES as usize, &generated(), SaveOptions::default(), 1).unwrap();
+
+    let (_, loaded) = same_load::<u64, String>(&path).unwrap();
+    assert_eq!(loaded.len(), LINES as usize);
+
+    fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn test_warm_par_matches_warm() {
+    let compute = |key: &u64| format!("{:x}", key.wrapping_mul(0x9e37_79b9_7f4a_7c15));
+    for capacity in [10, 1_000, 200_000] {
+        let mut sequential = Cache::new(capacity);
+        let mut parallel = Cache::new(capacity);
+        sequential.put(u64::MAX, String::from("déjà là"));
+        parallel.put(u64::MAX, String::from("déjà là"));
+
+        sequential.warm((0..LINES).map(|key| (key, compute(&key))));
+        parallel.warm_par(0..LINES, compute);
+
+        assert!(sequential.iter().eq(parallel.iter()));
+        assert_eq!(sequential.stats(), parallel.stats());
+    }
+}
@@ -0,0 +1,50 @@
+mod common;
+
+use lru_cache::cache::persistent::PersistentCache;
+use lru_cache::cache::traits::{CacheLen, CacheStorage};
+use std::fs;
+use std::sync::{Arc, Mutex};
+
+#[test]
+fn test_entries_survive_drop_and_reopen() {
+    let dir = common::temp_path("persistent");
+    let path = dir.join("nested").join("cache.txt");
+    assert!(!dir.exists());
+    {
+        let mut cache = PersistentCache::<String, i32>::open(&path, 3).unwrap();
+        assert_eq!(cache.len(), 0);
+        cache.put(String::from("a"), 1);
+        cache.put(String::from("b"), 2);
+    }
+
+    let mut cache = PersistentCache::<String, i32>::open(&path, 3).unwrap();
+    assert_eq!(cache.get(&String::from("a")), Some(&1));
+    assert_eq!(cache.get(&String::from("b")), Some(&2));
+    cache.put(String::from("c"), 3);
+    drop(cache);
+
+    let cache = PersistentCache::<String, i32>::open(&path, 3).unwrap();
+    assert_eq!(cache.len(), 3);
+    drop(cache);
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn test_flush_reports_errors() {
+    let blocker = common::temp_path("persistent_blocker");
+    fs::write(&blocker, "").unwrap();
+    let path = blocker.join("cache.txt");
+    let mut cache = PersistentCache::<String, i32>::open(&path, 1).unwrap();
+    cache.put(String::from("a"), 1);
+    assert!(cache.flush().is_err());
+
+    let errors = Arc::new(Mutex::new(Vec::new()));
+    let seen = Arc::clone(&errors);
+    cache.set_error_handler(move |path, error| seen.lock().unwrap().push((path.to_path_buf(), error.kind())));
+    drop(cache);
+
+    let errors = errors.lock().unwrap();
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].0, path);
+    fs::remove_file(blocker).unwrap();
+}
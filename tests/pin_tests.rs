@@ -0,0 +1,89 @@
+use lru_cache::Cache;
+use lru_cache::cache::policy::{Fifo, Mru};
+use lru_cache::cache::traits::{CacheIterate, CacheLen, CacheRemove, CacheStorage};
+
+fn keys(cache: &Cache<&'static str, i32>) -> Vec<&'static str> {
+    cache.iter().map(|(key, _)| *key).collect()
+}
+
+#[test]
+fn test_pinned_entry_survives_traffic() {
+    let mut cache = Cache::new(3);
+    cache.put("schema", 0);
+    assert!(cache.pin(&"schema"));
+    assert!(!cache.pin(&"missing"));
+    for i in 0..100 {
+        cache.put("other", i);
+        cache.put("more", i);
+        cache.put("again", i);
+    }
+    assert_eq!(cache.get(&"schema"), Some(&0));
+    assert_eq!(cache.pinned_len(), 1);
+    assert_eq!(cache.len(), 3);
+}
+
+#[test]
+fn test_full_of_pinned_entries_grows_then_shrinks() {
+    let mut cache = Cache::new(2);
+    cache.put("A", 1);
+    cache.put("B", 2);
+    cache.pin(&"A");
+    cache.pin(&"B");
+
+    cache.put("C", 3);
+    assert_eq!(cache.len(), 3);
+    cache.put("D", 4);
+    assert_eq!(keys(&cache), vec!["A", "B", "D"]);
+
+    assert!(cache.unpin(&"A"));
+    assert!(!cache.unpin(&"A"));
+    assert_eq!(keys(&cache), vec!["B", "D"]);
+    assert_eq!(cache.check_invariants(), Ok(()));
+    cache.put("E", 5);
+    assert_eq!(keys(&cache), vec!["B", "E"]);
+    assert_eq!(cache.pinned_len(), 1);
+}
+
+#[test]
+fn test_pinned_entries_keep_their_recency() {
+    let mut cache = Cache::new(3);
+    cache.put("A", 1);
+    cache.put("B", 2);
+    cache.put("C", 3);
+    cache.pin(&"A");
+    cache.get(&"A");
+    cache.put("D", 4);
+    assert_eq!(keys(&cache), vec!["C", "A", "D"]);
+
+    cache.unpin(&"A");
+    cache.put("E", 5);
+    assert_eq!(keys(&cache), vec!["A", "D", "E"]);
+}
+
+#[test]
+fn test_pinned_entry_can_be_removed() {
+    let mut cache = Cache::new(2);
+    cache.put("A", 1);
+    cache.pin(&"A");
+    assert_eq!(cache.remove(&"A"), Some(1));
+    assert_eq!(cache.pinned_len(), 0);
+}
+
+#[test]
+fn test_pins_apply_to_every_policy() {
+    let mut mru = Cache::with_eviction_policy(2, Mru);
+    let mut fifo = Cache::with_eviction_policy(2, Fifo::new());
+    mru.put("A", 1);
+    mru.put("B", 2);
+    mru.pin(&"B");
+    mru.put("C", 3);
+    let order: Vec<_> = mru.iter().map(|(key, _)| *key).collect();
+    assert_eq!(order, vec!["B", "C"]);
+
+    fifo.put("A", 1);
+    fifo.put("B", 2);
+    fifo.pin(&"A");
+    fifo.put("C", 3);
+    let order: Vec<_> = fifo.iter().map(|(key, _)| *key).collect();
+    assert_eq!(order, vec!["A", "C"]);
+}
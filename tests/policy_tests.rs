@@ -0,0 +1,593 @@
+mod common;
+
+use lru_cache::Cache;
+use lru_cache::cache::arc_policy::ArcCache;
+use lru_cache::cache::clock_cache::ClockCache;
+use lru_cache::cache::policy::{Candidates, EvictionPolicy, Fifo, Lru, Mru, TwoChoice};
+use lru_cache::cache::random::RandomCache;
+use lru_cache::cache::slru::SlruCache;
+use lru_cache::cache::testing::{hit_rate, zipf_trace};
+use lru_cache::cache::tiny_lfu::TinyLfuCache;
+use lru_cache::cache::fifo::FifoCache;
+use lru_cache::cache::lfu::LfuCache;
+use lru_cache::cache::lru_k::LruKCache;
+use lru_cache::cache::ordered::OrderedCache;
+use lru_cache::cache::sampled::SampledLruCache;
+use lru_cache::cache::traits::{
+    CacheIterate, CacheLen, CacheRemove, CacheStorage, GetOptions, PersistentStorage,
+};
+use lru_cache::cache::two_q::TwoQCache;
+use std::num::NonZeroUsize;
+
+#[test]
+fn test_lru_conformance() {
+    common::conformance(Cache::new);
+}
+
+#[test]
+fn test_two_choice_conformance() {
+    common::conformance(|capacity| Cache::with_eviction_policy(capacity, TwoChoice::new(7)));
+}
+
+#[test]
+fn test_two_choice_evicts_least_read_entry() {
+    let mut cache = Cache::with_eviction_policy(3, TwoChoice::new(1));
+    cache.put("A", 1);
+    cache.put("B", 2);
+    cache.put("C", 3);
+    for _ in 0..5 {
+        cache.get(&"A");
+    }
+    cache.get(&"B");
+    cache.get(&"C");
+    cache.get(&"C");
+
+    // A est la moins récente mais la plus lue : B, la moins lue, est évincée
+    cache.put("D", 4);
+
+    assert_eq!(cache.get(&"B"), None);
+    assert_eq!(cache.get(&"A"), Some(&1));
+}
+
+#[test]
+fn test_two_choice_is_deterministic_under_seed() {
+    let trace = zipf_trace(200, 5_000, 1.0, 3);
+    let run = |seed| {
+        let mut cache = Cache::with_eviction_policy(50, TwoChoice::new(seed));
+        hit_rate(&mut cache, &trace)
+    };
+
+    assert_eq!(run(11), run(11));
+}
+
+#[test]
+fn test_two_choice_hit_rate_against_lru() {
+    let trace = zipf_trace(1_000, 50_000, 1.0, 42);
+    let lru = hit_rate(&mut Cache::new(100), &trace);
+    let two_choice = hit_rate(&mut Cache::with_eviction_policy(100, TwoChoice::new(42)), &trace);
+    assert!(lru > 0.0 && lru < 1.0);
+    assert!(two_choice > lru && two_choice < 1.0, "TwoChoice {:.3} contre LRU {:.3}", two_choice, lru);
+}
+
+#[test]
+fn test_lfu_conformance() {
+    common::conformance(LfuCache::new);
+}
+
+#[test]
+fn test_lfu_keeps_hot_key_through_insert_storm() {
+    let mut lru = Cache::new(3);
+    let mut lfu = LfuCache::new(3);
+    lru.put(0, 0);
+    lfu.put(0, 0);
+    for _ in 0..5 {
+        lru.get(&0);
+        lfu.get(&0);
+    }
+    for key in 1..20 {
+        lru.put(key, key);
+        lfu.put(key, key);
+    }
+
+    assert_eq!(lru.get(&0), None);
+    assert_eq!(lfu.get(&0), Some(&0));
+    assert_eq!(lfu.frequency(&0), Some(7));
+}
+
+#[test]
+fn test_lfu_breaks_ties_by_recency() {
+    let mut cache = LfuCache::new(3);
+    cache.put("A", 1);
+    cache.put("B", 2);
+    cache.put("C", 3);
+    cache.get(&"A");
+    cache.get(&"B");
+
+    cache.put("D", 4);
+    assert_eq!(cache.get(&"C"), None);
+    cache.put("E", 5);
+    assert_eq!(cache.get(&"D"), None);
+    let keys: Vec<_> = cache.iter().map(|(k, _)| *k).collect();
+    assert_eq!(keys, vec!["E", "A", "B"]);
+}
+
+#[test]
+fn test_lfu_persistence_resets_counters() {
+    let path = common::temp_path("lfu.txt");
+    let path = path.to_str().unwrap();
+    let mut cache = LfuCache::new(3);
+    cache.put(String::from("cold"), 1);
+    cache.put(String::from("hot"), 2);
+    cache.get(&String::from("hot"));
+    cache.save_to_file(path).unwrap();
+
+    let mut loaded = LfuCache::<String, i32>::load_from_file(path, NonZeroUsize::new(3).unwrap()).unwrap();
+    assert_eq!(loaded.frequency(&String::from("hot")), Some(1));
+    assert_eq!(loaded.get(&String::from("cold")), Some(&1));
+    std::fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn test_fifo_conformance() {
+    common::conformance(FifoCache::new);
+}
+
+#[test]
+fn test_fifo_get_does_not_rescue_entry() {
+    let mut lru = Cache::new(2);
+    let mut fifo = FifoCache::new(2);
+    for (key, value) in [("A", 1), ("B", 2)] {
+        lru.put(key, value);
+        fifo.put(key, value);
+    }
+    lru.get(&"A");
+    fifo.get(&"A");
+    lru.put("C", 3);
+    fifo.put("C", 3);
+
+    assert_eq!(lru.get(&"A"), Some(&1));
+    assert_eq!(lru.get(&"B"), None);
+    assert_eq!(fifo.get(&"A"), None);
+    assert_eq!(fifo.get(&"B"), Some(&2));
+}
+
+#[test]
+fn test_fifo_update_keeps_insertion_order() {
+    let mut cache = FifoCache::new(2);
+    cache.put("A", 1);
+    cache.put("B", 2);
+    cache.put("A", 10);
+    cache.put("C", 3);
+
+    assert_eq!(cache.get(&"A"), None);
+    let entries: Vec<_> = cache.iter().map(|(k, v)| (*k, *v)).collect();
+    assert_eq!(entries, vec![("B", 2), ("C", 3)]);
+}
+
+#[test]
+fn test_fifo_zero_capacity_keeps_nothing() {
+    let mut cache = FifoCache::new(0);
+    cache.put("A", 1);
+    cache.put("B", 2);
+    assert_eq!(cache.len(), 0);
+    assert_eq!(cache.get(&"B"), None);
+}
+
+#[test]
+fn test_mru_conformance() {
+    common::conformance(|capacity| Cache::with_eviction_policy(capacity, Mru));
+}
+
+#[test]
+fn test_mru_survives_cyclic_scan() {
+    let trace: Vec<u64> = (0..10).flat_map(|_| 1..=4).collect();
+    let lru = hit_rate(&mut Cache::new(3), &trace);
+    let mru = hit_rate(&mut Cache::with_eviction_policy(3, Mru), &trace);
+
+    assert_eq!(lru, 0.0);
+    assert!(mru > 0.5, "MRU {}", mru);
+}
+
+#[test]
+fn test_slru_conformance() {
+    common::conformance(|capacity| SlruCache::new(capacity - capacity / 2, capacity / 2));
+}
+
+#[test]
+fn test_slru_scan_keeps_reread_entries() {
+    let mut cache = SlruCache::new(2, 2);
+    for key in [1, 2] {
+        cache.put(key, key);
+        cache.get(&key);
+        cache.get(&key);
+    }
+    for key in 100..200 {
+        cache.put(key, key);
+    }
+
+    assert_eq!(cache.get(&1), Some(&1));
+    assert_eq!(cache.get(&2), Some(&2));
+    assert_eq!(cache.protected_len(), 2);
+    assert_eq!(cache.probation_len(), 2);
+}
+
+#[test]
+fn test_slru_protected_overflow_demotes_to_probation() {
+    let mut cache = SlruCache::new(2, 1);
+    cache.put("A", 1);
+    cache.put("B", 2);
+    cache.get(&"A");
+    cache.get(&"B");
+
+    assert_eq!(cache.protected_len(), 1);
+    assert_eq!(cache.probation_len(), 1);
+    let order: Vec<_> = cache.iter().map(|(k, _)| *k).collect();
+    assert_eq!(order, vec!["A", "B"]);
+
+    cache.put("C", 3);
+    cache.put("D", 4);
+    assert_eq!(cache.get(&"A"), None);
+    assert_eq!(cache.get(&"B"), Some(&2));
+}
+
+#[test]
+fn test_two_q_conformance() {
+    common::conformance(TwoQCache::new);
+}
+
+#[test]
+fn test_two_q_scan_does_not_displace_main_queue() {
+    let mut cache = TwoQCache::new(8);
+    for key in 0..12 {
+        cache.put(key, key);
+    }
+    for key in 0..4 {
+        cache.put(key, key);
+    }
+    assert_eq!(cache.main_len(), 4);
+
+    for key in 100..200 {
+        cache.put(key, key);
+    }
+    for key in 0..4 {
+        assert_eq!(cache.get(&key), Some(&key));
+    }
+    assert_eq!(cache.main_len(), 4);
+    assert!(cache.ghost_len() <= 4);
+}
+
+#[test]
+fn test_two_q_second_sighting_within_ghost_window() {
+    let mut cache = TwoQCache::new(4);
+    for key in ["A", "B", "C", "D", "E"] {
+        cache.put(key, 0);
+    }
+    assert_eq!(cache.get(&"A"), None);
+    cache.put("A", 1);
+    assert_eq!(cache.main_len(), 1);
+    assert_eq!(cache.get(&"A"), Some(&1));
+
+    for key in ["F", "G", "H", "I"] {
+        cache.put(key, 0);
+    }
+    cache.put("B", 2);
+    assert_eq!(cache.main_len(), 1);
+    assert_eq!(cache.ghost_len(), 2);
+}
+
+#[test]
+fn test_arc_conformance() {
+    common::conformance(ArcCache::new);
+}
+
+#[test]
+fn test_arc_adapts_p_on_ghost_hits() {
+    let mut cache = ArcCache::new(2);
+    cache.put(1, 1);
+    cache.get(&1);
+    cache.put(2, 2);
+    cache.put(3, 3);
+    assert_eq!((cache.t1_len(), cache.t2_len(), cache.b1_len()), (1, 1, 1));
+    assert_eq!(cache.p(), 0);
+
+    cache.put(2, 2);
+    assert_eq!(cache.p(), 1);
+    assert_eq!(cache.b2_len(), 1);
+    assert_eq!(cache.get(&1), None);
+
+    cache.put(1, 1);
+    assert_eq!(cache.p(), 0);
+    assert_eq!(cache.t2_len(), 2);
+    assert_eq!(cache.get(&3), None);
+}
+
+#[test]
+fn test_arc_bounds_hold_under_mixed_workload() {
+    for capacity in 1..=5 {
+        let mut cache = ArcCache::new(capacity);
+        let trace = zipf_trace(4 * capacity, 2_000, 0.8, capacity as u64);
+        for (step, &key) in trace.iter().enumerate() {
+            match step % 7 {
+                0 => {
+                    cache.remove(&key);
+                }
+                1 | 2 => {
+                    cache.get(&key);
+                }
+                _ => cache.put(key, key),
+            }
+            assert!(cache.len() <= capacity);
+            assert!(cache.b1_len() <= capacity);
+            assert!(cache.b2_len() <= capacity);
+            assert!(cache.t1_len() + cache.b1_len() <= capacity);
+            assert!(cache.len() + cache.b1_len() + cache.b2_len() <= 2 * capacity);
+            assert!(cache.p() <= capacity);
+        }
+    }
+}
+
+#[test]
+fn test_clock_conformance() {
+    common::conformance(ClockCache::new);
+}
+
+#[test]
+fn test_clock_referenced_entry_survives_one_sweep() {
+    let mut cache = ClockCache::new(3);
+    cache.put("A", 1);
+    cache.put("B", 2);
+    cache.put("C", 3);
+    cache.get(&"A");
+
+    cache.put("D", 4);
+    assert_eq!(cache.get(&"B"), None);
+    assert_eq!(cache.is_referenced(&"A"), Some(false));
+
+    cache.put("E", 5);
+    assert_eq!(cache.get(&"C"), None);
+    cache.put("F", 6);
+    assert_eq!(cache.get(&"A"), None);
+    assert_eq!(cache.len(), 3);
+}
+
+#[test]
+fn test_clock_reuses_removed_slots() {
+    let mut cache = ClockCache::new(2);
+    cache.put(1, 1);
+    cache.put(2, 2);
+    assert_eq!(cache.remove(&1), Some(1));
+    cache.put(3, 3);
+    assert_eq!(cache.get(&2), Some(&2));
+    assert_eq!(cache.get(&3), Some(&3));
+    assert_eq!(cache.capacity(), 2);
+}
+
+// La suite de conformance exige qu'une clé insérée soit relue : le filtre
+// d'admission peut justement la rejeter
+#[test]
+fn test_tiny_lfu_basic_contract() {
+    let mut cache = TinyLfuCache::new(3);
+    assert!(cache.is_empty());
+    cache.put(1, 10);
+    cache.put(1, 11);
+    assert_eq!(cache.get(&1), Some(&11));
+    assert_eq!(cache.len(), 1);
+
+    for _ in 0..3 {
+        cache.get(&1);
+    }
+
+    for key in 2..10 {
+        cache.put(key, key * 10);
+        assert!(cache.len() <= 3);
+    }
+    assert_eq!(cache.len(), 3);
+    assert_eq!(cache.remove(&1), Some(11));
+}
+
+#[test]
+fn test_tiny_lfu_rejects_one_hit_wonders() {
+    let mut cache = TinyLfuCache::new(2);
+    cache.put("A", 1);
+    cache.put("B", 2);
+    cache.get(&"A");
+    cache.get(&"B");
+
+    for key in ["x", "y", "z"] {
+        cache.put(key, 0);
+        assert_eq!(cache.get_with(&key, GetOptions::passive()), None);
+    }
+    assert_eq!(cache.get(&"A"), Some(&1));
+    assert_eq!(cache.get(&"B"), Some(&2));
+}
+
+#[test]
+fn test_tiny_lfu_admits_candidate_more_frequent_than_victim() {
+    let mut cache = TinyLfuCache::new(1);
+    cache.put("old", 1);
+    for _ in 0..3 {
+        cache.get(&"new");
+    }
+    cache.put("new", 2);
+
+    assert_eq!(cache.get(&"old"), None);
+    assert_eq!(cache.get(&"new"), Some(&2));
+}
+
+#[test]
+fn test_zero_capacity_keeps_nothing_in_every_variant() {
+    let mut tiny = TinyLfuCache::with_sketch(0, 64, 4, 8);
+    let mut sampled = SampledLruCache::with_samples(0, 5, 7);
+    let mut ordered = OrderedCache::new(0);
+    for key in 1..=3 {
+        tiny.put(key, key);
+        sampled.put(key, key);
+        ordered.put(key, key);
+    }
+    assert_eq!((tiny.len(), sampled.len(), ordered.len()), (0, 0, 0));
+    assert_eq!(tiny.capacity(), 0);
+    assert_eq!(sampled.get(&3), None);
+}
+
+#[test]
+fn test_tiny_lfu_ages_sketch() {
+    let mut cache: TinyLfuCache<u32, u32> = TinyLfuCache::with_sketch(4, 64, 4, 8);
+    for _ in 0..6 {
+        cache.get(&1);
+    }
+    assert_eq!(cache.frequency(&1), 6);
+    cache.get(&2);
+    cache.get(&2);
+    assert_eq!(cache.frequency(&1), 3);
+    assert_eq!(cache.frequency(&2), 1);
+}
+
+#[test]
+fn test_tiny_lfu_hit_rate_beats_lru() {
+    let trace = zipf_trace(10_000, 100_000, 0.9, 42);
+    let lru = hit_rate(&mut Cache::new(200), &trace);
+    let tiny_lfu = hit_rate(&mut TinyLfuCache::new(200), &trace);
+    assert!(tiny_lfu > lru, "TinyLFU {:.3} contre LRU {:.3}", tiny_lfu, lru);
+}
+
+#[test]
+fn test_lru_k_conformance() {
+    common::conformance(|capacity| LruKCache::new(capacity, 2));
+    common::conformance(|capacity| LruKCache::new(capacity, 1));
+}
+
+#[test]
+fn test_lru_2_keeps_reused_keys_through_one_shot_stream() {
+    let run = |mut cache: Box<dyn CacheStorage<&'static str, u32>>| {
+        for key in ["index1", "index2"] {
+            cache.put(key, 0);
+            cache.get(&key);
+        }
+        for key in ["data1", "data2", "data3"] {
+            cache.put(key, 0);
+        }
+        (cache.get(&"index1").is_some(), cache.get(&"index2").is_some())
+    };
+
+    // LRU évince les pages d'index au profit de pages lues une seule fois
+    assert_eq!(run(Box::new(Cache::new(3))), (false, false));
+    assert_eq!(run(Box::new(LruKCache::new(3, 2))), (true, true));
+}
+
+#[test]
+fn test_lru_2_ranks_by_second_most_recent_access() {
+    let mut cache = LruKCache::new(2, 2);
+    cache.put("steady", 1);
+    cache.put("burst", 2);
+    cache.get(&"steady");
+    cache.get(&"burst");
+    cache.get(&"burst");
+
+    // burst est la plus récente, mais ses deux derniers accès sont plus
+    // récents que l'avant-dernier de steady : c'est steady qui part
+    cache.put("new", 3);
+    assert_eq!(cache.get_with(&"steady", GetOptions::passive()), None);
+    assert_eq!(cache.history_len(&"burst"), Some(2));
+}
+
+#[test]
+fn test_lru_k_short_histories_fall_back_to_lru() {
+    let mut cache = LruKCache::new(3, 3);
+    cache.put("A", 1);
+    cache.put("B", 2);
+    cache.put("C", 3);
+    cache.get(&"A");
+
+    cache.put("D", 4);
+    assert_eq!(cache.iter().map(|(k, _)| *k).collect::<Vec<_>>(), vec!["C", "A", "D"]);
+    assert_eq!(cache.remove(&"A"), Some(1));
+    assert_eq!(cache.len(), 2);
+}
+
+#[test]
+fn test_eviction_policy_conformance() {
+    common::conformance(|capacity| Cache::with_eviction_policy(capacity, Lru));
+    common::conformance(|capacity| Cache::with_eviction_policy(capacity, Mru));
+    common::conformance(|capacity| Cache::with_eviction_policy(capacity, Fifo::new()));
+}
+
+#[test]
+#[allow(deprecated)]
+fn test_eviction_policies_match_dedicated_variants() {
+    use lru_cache::cache::policy::Policy;
+
+    let trace = zipf_trace(64, 5_000, 1.0, 3);
+    let lru = hit_rate(&mut Cache::new(16), &trace);
+    assert_eq!(hit_rate(&mut Cache::with_eviction_policy(16, Lru), &trace), lru);
+
+    let mru = hit_rate(&mut Cache::with_policy(16, Policy::Mru), &trace);
+    assert_eq!(hit_rate(&mut Cache::with_eviction_policy(16, Mru), &trace), mru);
+
+    let two_choice = hit_rate(&mut Cache::with_policy(16, Policy::TwoChoice { seed: 5 }), &trace);
+    assert_eq!(hit_rate(&mut Cache::with_eviction_policy(16, TwoChoice::new(5)), &trace), two_choice);
+
+    let fifo = hit_rate(&mut FifoCache::new(16), &trace);
+    assert_eq!(hit_rate(&mut Cache::with_eviction_policy(16, Fifo::new()), &trace), fifo);
+}
+
+#[test]
+fn test_fifo_eviction_policy_ignores_reads_and_updates() {
+    let mut cache = Cache::with_eviction_policy(2, Fifo::new());
+    cache.put("A", 1);
+    cache.put("B", 2);
+    cache.get(&"A");
+    cache.put("A", 10);
+    cache.put("C", 3);
+
+    assert_eq!(cache.get(&"A"), None);
+    assert_eq!(cache.remove(&"B"), Some(2));
+    cache.put("D", 4);
+    cache.put("E", 5);
+    assert_eq!(cache.get(&"C"), None);
+}
+
+/// Politique d'exemple : évince la plus grande clé
+#[derive(Default)]
+struct LargestKey;
+
+impl EvictionPolicy<u32> for LargestKey {
+    fn choose_victim<'a>(&'a mut self, candidates: Candidates<'a, u32>) -> Option<&'a u32> {
+        candidates.max()
+    }
+}
+
+#[test]
+fn test_custom_eviction_policy() {
+    let mut cache = Cache::with_eviction_policy(3, LargestKey);
+    for key in [5, 1, 9, 3] {
+        cache.put(key, key * 10);
+    }
+    let mut keys: Vec<_> = cache.iter().map(|(k, _)| *k).collect();
+    keys.sort();
+    assert_eq!(keys, vec![1, 3, 5]);
+
+    cache.put(2, 20);
+    assert_eq!(cache.get(&5), None);
+    assert_eq!(cache.get(&2), Some(&20));
+}
+
+#[test]
+fn test_random_conformance() {
+    common::conformance(|capacity| RandomCache::with_seed(capacity, 7));
+    common::conformance(RandomCache::new);
+}
+
+#[test]
+fn test_random_eviction_sequence_is_fixed_by_seed() {
+    let mut cache = RandomCache::with_seed(3, 7);
+    let mut evicted = Vec::new();
+    for key in 0..8u32 {
+        let before: Vec<u32> = cache.iter().map(|(k, _)| *k).collect();
+        cache.put(key, key);
+        evicted.extend(before.into_iter().filter(|k| cache.get(k).is_none()));
+    }
+    assert_eq!(evicted, vec![0, 2, 3, 4, 6]);
+    assert_eq!(cache.len(), 3);
+    assert_eq!(cache.remove(&7), Some(7));
+    assert_eq!(cache.remove(&7), None);
+}
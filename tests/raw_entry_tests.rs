@@ -0,0 +1,94 @@
+use lru_cache::Cache;
+use lru_cache::cache::traits::{CacheIterate, CacheLen, CacheRemove, CacheStorage};
+use std::hash::{Hash, Hasher};
+
+/// Clé dont le hachage est interdit : les méthodes `_with_hash` ne
+/// doivent jamais l'appeler
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Digest([u8; 32]);
+
+impl Hash for Digest {
+    fn hash<H: Hasher>(&self, _state: &mut H) {
+        panic!("la clé ne doit pas être hachée");
+    }
+}
+
+impl Digest {
+    fn new(seed: u8) -> Self {
+        Digest([seed; 32])
+    }
+
+    fn prefix(&self) -> u64 {
+        u64::from_le_bytes(self.0[..8].try_into().unwrap())
+    }
+}
+
+fn get<'a>(cache: &'a mut Cache<Digest, u32>, key: &Digest) -> Option<&'a u32> {
+    cache.get_with_hash(key.prefix(), |candidate| candidate == key)
+}
+
+#[test]
+fn test_precomputed_hash_never_hashes_key() {
+    let mut cache = Cache::new(2);
+    let (a, b, c) = (Digest::new(1), Digest::new(2), Digest::new(3));
+    cache.put_with_hash(a.prefix(), a.clone(), 1);
+    cache.put_with_hash(b.prefix(), b.clone(), 2);
+    assert_eq!(get(&mut cache, &a), Some(&1));
+
+    cache.put_with_hash(c.prefix(), c.clone(), 3);
+    assert_eq!(get(&mut cache, &b), None);
+    assert_eq!(get(&mut cache, &a), Some(&1));
+    assert_eq!(get(&mut cache, &c), Some(&3));
+
+    cache.put_with_hash(a.prefix(), a.clone(), 10);
+    assert_eq!(get(&mut cache, &a), Some(&10));
+    assert_eq!(cache.len(), 2);
+    let stats = cache.stats();
+    assert_eq!((stats.hits, stats.misses), (4, 1));
+}
+
+#[test]
+fn test_precomputed_hash_collisions() {
+    let mut cache = Cache::new(3);
+    for seed in 0..3u32 {
+        cache.put_with_hash(42, seed, seed * 10);
+    }
+    for seed in 0..3u32 {
+        assert_eq!(cache.get_with_hash(42, |key| *key == seed), Some(&(seed * 10)));
+    }
+    assert_eq!(cache.get_with_hash(42, |key| *key == 3), None);
+    assert_eq!(cache.get_with_hash(43, |key| *key == 0), None);
+}
+
+#[test]
+fn test_precomputed_hash_matches_normal_api() {
+    let mut cache = Cache::new(2);
+    cache.put("a", 1);
+    let hash = cache.hash_key(&"b");
+    cache.put_with_hash(hash, "b", 2);
+
+    assert_eq!(cache.get(&"b"), Some(&2));
+    let hash = cache.hash_key(&"a");
+    assert_eq!(cache.get_with_hash(hash, |key| *key == "a"), Some(&1));
+    assert_eq!(cache.iter().map(|(key, _)| *key).collect::<Vec<_>>(), vec!["b", "a"]);
+    assert!(cache.check_invariants().is_ok());
+}
+
+#[test]
+fn test_mixed_api_keeps_one_entry_per_key() {
+    let mut cache = Cache::new(3);
+    let hash = cache.hash_key(&"a").wrapping_add(1);
+    cache.put_with_hash(hash, "a", 1);
+    cache.put("a", 2);
+    assert_eq!(cache.len(), 1);
+    assert_eq!(cache.get(&"a"), Some(&2));
+    assert_eq!(cache.get_with_hash(hash, |key| *key == "a"), Some(&2));
+
+    cache.put("b", 3);
+    cache.put_with_hash(7, "b", 4);
+    assert_eq!(cache.len(), 2);
+    assert_eq!(cache.get(&"b"), Some(&4));
+    assert_eq!(cache.remove(&"a"), Some(2));
+    assert_eq!(cache.len(), 1);
+    assert!(cache.check_invariants().is_ok());
+}
@@ -0,0 +1,67 @@
+mod common;
+
+use lru_cache::Cache;
+use lru_cache::cache::clock::MockClock;
+use lru_cache::cache::traits::{CacheIterate, CacheStorage, PersistentStorage};
+use lru_cache::cache::watched::WatchedCache;
+use lru_cache::storage::file::FileStorage;
+use std::fs;
+use std::num::NonZeroUsize;
+use std::time::Duration;
+
+fn entries(cache: &Cache<String, i32>) -> Vec<(String, i32)> {
+    cache.iter().map(|(key, value)| (key.clone(), *value)).collect()
+}
+
+#[test]
+fn test_reload_after_external_rewrite() {
+    let path = common::temp_path("reload_rewrite.txt");
+    let mut cache = Cache::new(3);
+    cache.put(String::from("a"), 1);
+    cache.put(String::from("b"), 2);
+    cache.save_to_file(&path).unwrap();
+    assert!(!cache.reload_if_changed(&path).unwrap());
+
+    FileStorage::save(&path, 3, &[("x", 10), ("y", 20), ("z", 30)]).unwrap();
+    assert!(cache.reload_if_changed(&path).unwrap());
+    assert_eq!(entries(&cache), [(String::from("x"), 10), (String::from("y"), 20), (String::from("z"), 30)]);
+    assert!(!cache.reload_if_changed(&path).unwrap());
+    fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn test_load_records_the_fingerprint() {
+    let path = common::temp_path("reload_load.txt");
+    FileStorage::save(&path, 2, &[("a", 1)]).unwrap();
+    let mut cache = Cache::<String, i32>::load_from_file(&path, NonZeroUsize::new(2).unwrap()).unwrap();
+    assert!(!cache.reload_if_changed(&path).unwrap());
+
+    let mut fresh: Cache<String, i32> = Cache::new(2);
+    assert!(fresh.reload_if_changed(&path).unwrap());
+    assert_eq!(entries(&fresh), [(String::from("a"), 1)]);
+
+    fs::remove_file(&path).unwrap();
+    assert!(cache.reload_if_changed(&path).is_err());
+    assert_eq!(entries(&cache), [(String::from("a"), 1)]);
+}
+
+#[test]
+fn test_watched_cache_checks_at_most_once_per_interval() {
+    let path = common::temp_path("reload_watched.txt");
+    FileStorage::save(&path, 2, &[("a", 1)]).unwrap();
+    let clock = MockClock::new();
+    let mut watched = WatchedCache::new(Cache::with_clock(2, clock.clone()), &path, Duration::from_secs(10));
+    assert_eq!(watched.get(&String::from("a")), Some(&1));
+
+    FileStorage::save(&path, 2, &[("a", 100), ("b", 2)]).unwrap();
+    clock.advance(Duration::from_secs(9));
+    assert_eq!(watched.get(&String::from("a")), Some(&1));
+    clock.advance(Duration::from_secs(1));
+    assert_eq!(watched.get(&String::from("a")), Some(&100));
+    assert_eq!(watched.get(&String::from("b")), Some(&2));
+
+    fs::remove_file(&path).unwrap();
+    clock.advance(Duration::from_secs(10));
+    assert_eq!(watched.get(&String::from("b")), Some(&2));
+    assert!(watched.check().is_err());
+}
@@ -0,0 +1,68 @@
+use lru_cache::Cache;
+use lru_cache::cache::testing::{replay, ReplayError};
+use lru_cache::cache::traits::{CacheRemove, CacheStorage};
+
+fn scripted(cache: &mut Cache<String, u32>) {
+    for (i, key) in ["A", "B", "C", "A", "D", "B", "E", "C"].iter().enumerate() {
+        if i % 3 == 0 {
+            cache.get(&key.to_string());
+        }
+        cache.put(key.to_string(), i as u32);
+    }
+    cache.remove(&String::from("D"));
+    cache.get(&String::from("Z"));
+}
+
+#[test]
+fn test_replay_reproduces_state() {
+    let mut cache = Cache::new(3);
+    cache.record_operations(100);
+    scripted(&mut cache);
+
+    let replayed = replay(&cache.export_replay(), 3).unwrap();
+
+    assert_eq!(replayed.cache.canonical_string(), cache.canonical_string());
+    assert_eq!(replayed.evictions, vec!["B", "C", "A", "D"]);
+}
+
+#[test]
+fn test_hashed_replay_reproduces_evictions() {
+    let mut keyed = Cache::new(3);
+    keyed.record_operations(100);
+    scripted(&mut keyed);
+    let mut hashed = Cache::new(3);
+    hashed.record_operations_hashed(100);
+    scripted(&mut hashed);
+
+    let export = hashed.export_replay();
+    assert!(!export.contains("\tA\t"));
+    let by_key = replay(&keyed.export_replay(), 3).unwrap();
+    let by_hash = replay(&export, 3).unwrap();
+
+    assert_eq!(by_hash.evictions.len(), by_key.evictions.len());
+    assert_eq!(by_hash.cache.canonical_string().lines().count(), 4);
+}
+
+#[test]
+fn test_replay_detects_divergence() {
+    let mut cache = Cache::new(3);
+    cache.record_operations(100);
+    scripted(&mut cache);
+
+    let result = replay(&cache.export_replay(), 2);
+
+    assert!(matches!(result, Err(ReplayError::Diverged { .. })));
+}
+
+#[test]
+fn test_operation_log_is_bounded() {
+    let mut cache = Cache::new(2);
+    cache.record_operations(2);
+    cache.put(1, 1);
+    cache.put(2, 2);
+    cache.put(3, 3);
+
+    let export = cache.export_replay();
+    assert!(export.starts_with("#replay v1 keys capacity=2 dropped=1\n"));
+    assert_eq!(export.lines().count(), 3);
+}
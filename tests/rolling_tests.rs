@@ -0,0 +1,86 @@
+mod common;
+
+use lru_cache::Cache;
+use lru_cache::cache::clock::MockClock;
+use lru_cache::cache::traits::CacheStorage;
+use lru_cache::storage::rolling::{load_as_of, load_latest, Date, RollingSaver};
+use std::fs;
+use std::path::PathBuf;
+use std::time::Duration;
+
+const DAY: Duration = Duration::from_secs(24 * 3600);
+
+fn scratch_dir(name: &str) -> PathBuf {
+    let dir = common::temp_path(name);
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+fn files(dir: &PathBuf) -> Vec<String> {
+    let mut names: Vec<_> = fs::read_dir(dir)
+        .unwrap()
+        .map(|entry| entry.unwrap().file_name().into_string().unwrap())
+        .collect();
+    names.sort();
+    names
+}
+
+#[test]
+fn test_date_conversions() {
+    assert_eq!(Date::from_system_time(std::time::UNIX_EPOCH).to_string(), "1970-01-01");
+    let leap = std::time::UNIX_EPOCH + Duration::from_secs(951_782_400);
+    assert_eq!(Date::from_system_time(leap), Date::new(2000, 2, 29).unwrap());
+    assert_eq!("2024-12-31".parse(), Ok(Date::new(2024, 12, 31).unwrap()));
+    assert!(Date::new(2023, 2, 29).is_none());
+    assert!("2024-1-31".parse::<Date>().is_err());
+}
+
+#[test]
+fn test_rolling_prunes_and_loads_as_of() {
+    let dir = scratch_dir("rolling");
+    let clock = MockClock::new();
+    let saver = RollingSaver::with_clock(&dir, "cache", 2, clock.clone());
+    let mut cache = Cache::new(3);
+    for day in 1..=3 {
+        cache.put(format!("day{}", day), day);
+        saver.save(&cache).unwrap();
+        clock.advance(DAY);
+    }
+
+    assert_eq!(files(&dir), vec!["cache-1970-01-02.txt", "cache-1970-01-03.txt"]);
+
+    let mut latest: Cache<String, i32> = load_latest(&dir, "cache", 3).unwrap();
+    assert_eq!(latest.get(&String::from("day3")), Some(&3));
+
+    let tuesday = Date::new(1970, 1, 2).unwrap();
+    let mut as_of: Cache<String, i32> = load_as_of(&dir, "cache", tuesday, 3).unwrap();
+    assert_eq!(as_of.get(&String::from("day2")), Some(&2));
+    assert_eq!(as_of.get(&String::from("day3")), None);
+
+    let later = Date::new(1971, 6, 1).unwrap();
+    let mut after: Cache<String, i32> = load_as_of(&dir, "cache", later, 3).unwrap();
+    assert_eq!(after.get(&String::from("day3")), Some(&3));
+
+    let before = Date::new(1970, 1, 1).unwrap();
+    let missing = load_as_of::<String, i32>(&dir, "cache", before, 3).unwrap_err();
+    assert_eq!(missing.kind(), std::io::ErrorKind::NotFound);
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn test_rolling_same_day_overwrites_and_ignores_other_files() {
+    let dir = scratch_dir("rolling_same_day");
+    fs::write(dir.join("other-1970-01-01.txt"), "1\nx;1\n").unwrap();
+    let saver = RollingSaver::with_clock(&dir, "cache", 1, MockClock::new());
+    let mut cache = Cache::new(2);
+    cache.put(String::from("a"), 1);
+    saver.save(&cache).unwrap();
+    cache.put(String::from("b"), 2);
+    saver.save(&cache).unwrap();
+
+    assert_eq!(files(&dir), vec!["cache-1970-01-01.txt", "other-1970-01-01.txt"]);
+    let mut latest: Cache<String, i32> = load_latest(&dir, "cache", 2).unwrap();
+    assert_eq!(latest.get(&String::from("b")), Some(&2));
+    fs::remove_dir_all(dir).unwrap();
+}
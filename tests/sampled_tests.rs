@@ -0,0 +1,89 @@
+use lru_cache::Cache;
+use lru_cache::cache::sampled::{SampledLruCache, DEFAULT_SAMPLES};
+use lru_cache::cache::traits::{CacheIterate, CacheLen, CacheRemove, CacheStorage, GetOptions};
+use std::collections::BTreeSet;
+
+#[test]
+fn test_basic_operations() {
+    let mut cache = SampledLruCache::with_seed(3, 7);
+    assert_eq!(cache.samples(), DEFAULT_SAMPLES);
+    cache.put("a", 1);
+    cache.put("b", 2);
+    cache.put("a", 10);
+    assert_eq!(cache.len(), 2);
+    assert_eq!(cache.get(&"a"), Some(&10));
+    assert_eq!(cache.peek(&"b"), Some(&2));
+    assert_eq!(cache.remove(&"a"), Some(10));
+    assert_eq!(cache.remove(&"a"), None);
+    assert_eq!(cache.iter().collect::<Vec<_>>(), vec![(&"b", &2)]);
+}
+
+#[test]
+fn test_touched_entry_survives_when_older_candidate_sampled() {
+    for seed in 0..200 {
+        let mut cache = SampledLruCache::with_samples(2, 64, seed);
+        cache.put("touched", 1);
+        cache.put("stale", 2);
+        cache.get(&"touched");
+        // « stale » est tirée au moins une fois sur 64 tirages : c'est elle
+        // qui est évincée, jamais « touched », utilisée plus récemment
+        cache.put("new", 3);
+        assert_eq!(cache.peek(&"touched"), Some(&1), "graine {seed}");
+        assert_eq!(cache.peek(&"stale"), None, "graine {seed}");
+    }
+}
+
+#[test]
+fn test_passive_get_does_not_protect_entry() {
+    for seed in 0..200 {
+        let mut cache = SampledLruCache::with_samples(2, 64, seed);
+        cache.put("read", 1);
+        cache.put("fresh", 2);
+        assert_eq!(cache.get_with(&"read", GetOptions::passive()), Some(&1));
+        cache.put("new", 3);
+        assert_eq!(cache.peek(&"read"), None, "graine {seed}");
+        assert_eq!(cache.peek(&"fresh"), Some(&2), "graine {seed}");
+    }
+}
+
+#[test]
+fn test_recent_entry_never_evicted_among_many() {
+    const CAPACITY: u32 = 100;
+    for seed in 0..50 {
+        let mut cache = SampledLruCache::with_seed(CAPACITY as usize, seed);
+        for key in 0..CAPACITY {
+            cache.put(key, key);
+        }
+        cache.get(&0);
+        for key in CAPACITY..CAPACITY + 10 {
+            cache.put(key, key);
+            assert_eq!(cache.peek(&0), Some(&0), "graine {seed}");
+        }
+        assert_eq!(cache.len(), CAPACITY as usize);
+    }
+}
+
+#[test]
+fn test_large_sample_matches_exact_lru() {
+    const CAPACITY: usize = 8;
+    let mut sampled = SampledLruCache::with_samples(CAPACITY, 512, 42);
+    let mut exact = Cache::new(CAPACITY);
+    for step in 0u64..500 {
+        let key = step.wrapping_mul(2654435761) % 13;
+        if step % 3 == 0 {
+            assert_eq!(sampled.get(&key).copied(), exact.get(&key).copied());
+        } else {
+            sampled.put(key, step);
+            exact.put(key, step);
+        }
+    }
+    let sampled_keys: BTreeSet<_> = sampled.iter().map(|(key, _)| *key).collect();
+    let exact_keys: BTreeSet<_> = exact.iter().map(|(key, _)| *key).collect();
+    assert_eq!(sampled_keys, exact_keys);
+}
+
+#[test]
+#[should_panic]
+fn test_zero_samples_panics() {
+    let _ = SampledLruCache::<u32, u32>::with_samples(4, 0, 1);
+}
@@ -0,0 +1,57 @@
+use lru_cache::Cache;
+use lru_cache::cache::listener::RemovalCause;
+use lru_cache::cache::traits::{CacheIterate, CacheLen};
+use std::sync::mpsc;
+
+fn keys(cache: &Cache<(&'static str, u32), u32>) -> Vec<(&'static str, u32)> {
+    cache.iter().map(|(key, _)| *key).collect()
+}
+
+#[test]
+fn test_namespaces_with_same_keys_do_not_interfere() {
+    let mut cache = Cache::new(4);
+    cache.scoped("users").put(1, 10);
+    cache.scoped("groups").put(1, 20);
+    assert_eq!(cache.len(), 2);
+    assert_eq!(cache.scoped("users").get(&1), Some(&10));
+    assert_eq!(cache.scoped("groups").peek(&1), Some(&20));
+
+    assert_eq!(cache.scoped("users").remove(&1), Some(10));
+    assert!(!cache.scoped("users").contains_key(&1));
+    assert!(cache.scoped("groups").contains_key(&1));
+    assert_eq!(cache.scoped("users").remove(&1), None);
+}
+
+#[test]
+fn test_clear_namespace_keeps_other_order() {
+    let (sender, removed) = mpsc::channel();
+    let mut cache = Cache::new(6);
+    cache.set_eviction_listener(move |key: (&str, u32), _, cause| sender.send((key, cause)).unwrap());
+    for key in 0..3 {
+        cache.scoped("a").put(key, key);
+        cache.scoped("b").put(key, key);
+    }
+    cache.scoped("b").get(&0);
+    assert_eq!(cache.scoped("a").len(), 3);
+
+    assert_eq!(cache.clear_namespace(&"a"), 3);
+    assert_eq!(keys(&cache), [("b", 1), ("b", 2), ("b", 0)]);
+    assert!(cache.scoped("a").is_empty());
+    assert_eq!(removed.try_iter().map(|(_, cause)| cause).collect::<Vec<_>>(), [RemovalCause::Explicit; 3]);
+    assert_eq!(cache.clear_namespace(&"a"), 0);
+}
+
+#[test]
+fn test_eviction_is_shared_across_namespaces() {
+    let mut cache = Cache::new(3);
+    cache.scoped("a").put(1, 1);
+    cache.scoped("b").put(1, 1);
+    cache.scoped("a").put(2, 2);
+    cache.scoped("b").put(2, 2);
+    assert_eq!(keys(&cache), [("b", 1), ("a", 2), ("b", 2)]);
+
+    cache.scoped("b").get(&1);
+    cache.scoped("b").put(3, 3);
+    assert_eq!(keys(&cache), [("b", 2), ("b", 1), ("b", 3)]);
+    assert_eq!(cache.scoped("b").iter().map(|(key, _)| *key).collect::<Vec<_>>(), [2, 1, 3]);
+}
@@ -0,0 +1,50 @@
+use lru_cache::cache::simulate::{simulate, simulate_lru};
+use lru_cache::cache::slru::SlruCache;
+use lru_cache::cache::testing::{run_trace, zipf_trace};
+use lru_cache::cache::two_q::TwoQCache;
+
+#[test]
+fn test_small_trace_hand_computed() {
+    // A B C A B D A B C D
+    let trace = ["A", "B", "C", "A", "B", "D", "A", "B", "C", "D"];
+    let results = simulate_lru(&[1, 2, 3, 4], trace);
+    let counts: Vec<_> = results
+        .iter()
+        .map(|result| (result.capacity, result.stats.hits, result.stats.misses, result.stats.evictions))
+        .collect();
+    assert_eq!(counts, vec![(1, 0, 10, 9), (2, 0, 10, 8), (3, 4, 6, 3), (4, 6, 4, 0)]);
+    assert_eq!(results[3].hit_rate(), 0.6);
+}
+
+#[test]
+fn test_zipf_hit_rate_is_monotone_in_capacity() {
+    let trace = zipf_trace(2_000, 20_000, 1.0, 7);
+    let capacities = [1, 10, 50, 100, 500, 1_000, 2_000];
+    let results = simulate_lru(&capacities, trace.iter().copied());
+    for pair in results.windows(2) {
+        assert!(pair[0].hit_rate() <= pair[1].hit_rate(), "{:?}", pair);
+    }
+    assert_eq!(results.last().unwrap().stats.evictions, 0);
+}
+
+#[test]
+fn test_simulation_matches_individual_runs() {
+    let trace = zipf_trace(200, 2_000, 0.8, 3);
+    let results = simulate(&[4, 16, 64], trace.iter().copied(), |capacity| SlruCache::new(capacity / 2, capacity / 2));
+    for result in &results {
+        let half = result.capacity / 2;
+        let alone = run_trace(&mut SlruCache::<u64, ()>::new(half, half), &trace);
+        assert_eq!(result.stats, alone);
+    }
+
+    let two_q = simulate(&[8], trace.iter().copied(), TwoQCache::<u64, ()>::new);
+    assert_eq!(two_q[0].stats, run_trace(&mut TwoQCache::<u64, ()>::new(8), &trace));
+}
+
+#[test]
+fn test_empty_inputs() {
+    assert!(simulate_lru::<u32>(&[], [1, 2, 3]).is_empty());
+    let results = simulate_lru::<u32>(&[3], []);
+    assert_eq!(results[0].stats.hits + results[0].stats.misses, 0);
+    assert_eq!(results[0].hit_rate(), 0.0);
+}
@@ -0,0 +1,78 @@
+use lru_cache::Cache;
+use lru_cache::cache::traits::{CacheIterate, CacheLen, CacheRemove, CacheStorage};
+use std::sync::mpsc;
+use std::thread;
+use std::time::Duration;
+
+/// Type sans `Display` ni `FromStr`, que `save_to_file` ne peut pas écrire
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct Key(u32, &'static str);
+
+fn victims(cache: &mut Cache<Key, Vec<u32>>) -> mpsc::Receiver<Key> {
+    let (sender, receiver) = mpsc::channel();
+    cache.set_eviction_listener(move |key, _, _| sender.send(key).unwrap());
+    receiver
+}
+
+#[test]
+fn test_restored_cache_evicts_like_the_original() {
+    let mut original = Cache::new(5);
+    for i in 0..7 {
+        original.put(Key(i, "k"), vec![i; i as usize]);
+    }
+    original.get(&Key(3, "k"));
+    original.pin(&Key(2, "k"));
+    original.put_with_ttl(Key(9, "ttl"), vec![9], Duration::from_secs(3600));
+
+    let snapshot = thread::spawn({
+        let snapshot = original.snapshot();
+        move || snapshot
+    })
+    .join()
+    .unwrap();
+    assert_eq!(snapshot.capacity(), 5);
+    assert_eq!(snapshot.len(), original.len());
+    let mut restored = Cache::restore(snapshot.clone());
+    let entries = |cache: &Cache<Key, Vec<u32>>| cache.iter().map(|(k, v)| (k.clone(), v.clone())).collect::<Vec<_>>();
+    assert_eq!(entries(&restored), entries(&original));
+    assert_eq!(snapshot.into_entries(), entries(&original));
+    assert_eq!(restored.pinned_len(), 1);
+    assert_eq!(restored.stats().insertions, 0);
+
+    let original_victims = victims(&mut original);
+    let restored_victims = victims(&mut restored);
+    for step in 0..20 {
+        match step % 4 {
+            0 | 1 => {
+                original.put(Key(100 + step, "new"), vec![step]);
+                restored.put(Key(100 + step, "new"), vec![step]);
+            }
+            2 => assert_eq!(restored.get(&Key(step / 2, "k")), original.get(&Key(step / 2, "k"))),
+            _ => assert_eq!(restored.remove(&Key(99 + step, "new")), original.remove(&Key(99 + step, "new"))),
+        }
+        assert_eq!(restored_victims.try_iter().collect::<Vec<_>>(), original_victims.try_iter().collect::<Vec<_>>());
+    }
+    assert_eq!(entries(&restored), entries(&original));
+    assert!(restored.contains_key(&Key(2, "k")));
+}
+
+#[test]
+fn test_restore_into_keeps_configuration_and_stats() {
+    let mut source = Cache::new(2);
+    source.put("a", 1);
+    source.put("b", 2);
+    source.get(&"a");
+
+    let mut target = Cache::new(10);
+    target.put("old", 0);
+    target.get(&"old");
+    let stats = target.stats();
+    target.restore_into(source.snapshot());
+    assert_eq!(target.stats(), stats);
+    assert_eq!(target.capacity(), 2);
+    assert!(!target.contains_key(&"old"));
+
+    target.put("c", 3);
+    assert_eq!(target.iter().map(|(k, _)| *k).collect::<Vec<_>>(), ["a", "c"]);
+    assert!(Cache::<&str, i32>::new(1).snapshot().is_empty());
+}
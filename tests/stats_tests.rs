@@ -0,0 +1,354 @@
+use lru_cache::Cache;
+use lru_cache::cache::clock::MockClock;
+use lru_cache::cache::stats::CacheStats;
+use lru_cache::cache::traits::{CacheIterate, CacheLen, CacheStorage, GetOptions};
+use std::time::Duration;
+
+#[test]
+fn test_stats_count_scripted_workload() {
+    let mut cache = Cache::new(2);
+    cache.put("A", 1);
+    cache.put("B", 2);
+    cache.get(&"A");
+    cache.get(&"C");
+    cache.put("A", 10);
+    cache.put("C", 3);
+    cache.get(&"B");
+    cache.get(&"C");
+
+    assert_eq!(
+        cache.stats(),
+        CacheStats {
+            hits: 2,
+            misses: 2,
+            insertions: 3,
+            updates: 1,
+            evictions: 1,
+            expirations: 0,
+            ghost_hits: 0,
+        }
+    );
+    assert_eq!(cache.stats().hit_ratio(), 0.5);
+}
+
+#[test]
+fn test_peek_and_contains_key_do_not_count() {
+    let mut cache = Cache::new(2);
+    cache.put("A", 1);
+    cache.peek(&"A");
+    cache.peek(&"B");
+    cache.contains_key(&"A");
+
+    let stats = cache.stats();
+    assert_eq!((stats.hits, stats.misses), (0, 0));
+    assert_eq!(stats.hit_ratio(), 0.0);
+}
+
+#[test]
+fn test_get_or_insert_with_counts_hits_and_misses() {
+    let mut cache = Cache::new(1);
+    assert_eq!(*cache.get_or_insert_with("A", || 1), 1);
+    assert_eq!(*cache.get_or_insert_with("A", || 2), 1);
+    assert_eq!(*cache.get_or_insert_with("B", || 3), 3);
+
+    let stats = cache.stats();
+    assert_eq!((stats.hits, stats.misses), (1, 2));
+    assert_eq!((stats.insertions, stats.evictions), (2, 1));
+}
+
+#[test]
+fn test_get_with_respects_record_stats() {
+    let mut cache = Cache::new(2);
+    cache.put("A", 1);
+    cache.get_with(&"A", GetOptions::passive());
+    cache.get_with(&"B", GetOptions::passive());
+    cache.get_with(
+        &"A",
+        GetOptions {
+            promote: false,
+            record_stats: true,
+        },
+    );
+
+    let stats = cache.stats();
+    assert_eq!((stats.hits, stats.misses), (1, 0));
+}
+
+#[test]
+fn test_expired_read_counts_miss_and_expiration() {
+    let clock = MockClock::new();
+    let mut cache = Cache::with_clock(2, clock.clone());
+    cache.put_with_ttl("A", 1, Duration::from_secs(10));
+    cache.put_with_ttl("B", 2, Duration::from_secs(10));
+    clock.advance(Duration::from_secs(10));
+    assert_eq!(cache.get(&"A"), None);
+    assert_eq!(cache.purge_expired(), 1);
+
+    let stats = cache.stats();
+    assert_eq!((stats.misses, stats.expirations, stats.evictions), (1, 2, 0));
+}
+
+#[test]
+fn test_evict_n_and_truncate_count_evictions() {
+    let mut cache = Cache::new(4);
+    for i in 0..4 {
+        cache.put(i, i);
+    }
+    cache.evict_n(1);
+    cache.truncate(1);
+    cache.invalidate(&3);
+    cache.clear();
+
+    assert_eq!(cache.stats().evictions, 3);
+}
+
+#[test]
+fn test_reset_stats() {
+    let mut cache = Cache::new(1);
+    cache.put("A", 1);
+    cache.get(&"A");
+    cache.reset_stats();
+    assert_eq!(cache.stats(), CacheStats::default());
+
+    cache.get(&"A");
+    assert_eq!(cache.stats().hits, 1);
+}
+
+fn parse_metrics(text: &str) -> Vec<(String, String)> {
+    text.lines()
+        .filter(|line| !line.starts_with('#'))
+        .map(|line| {
+            let (name, value) = line.rsplit_once(' ').unwrap();
+            (name.to_string(), value.to_string())
+        })
+        .collect()
+}
+
+#[test]
+fn test_metrics_text_after_workload() {
+    let mut cache = Cache::new(2);
+    cache.put("A", 1);
+    cache.put("B", 2);
+    cache.get(&"A");
+    cache.get(&"C");
+    cache.put("C", 3);
+    let text = cache.metrics_text("app_cache");
+
+    let expected = [
+        ("app_cache_hits_total", "1"),
+        ("app_cache_misses_total", "1"),
+        ("app_cache_insertions_total", "3"),
+        ("app_cache_updates_total", "0"),
+        ("app_cache_evictions_total", "1"),
+        ("app_cache_expirations_total", "0"),
+        ("app_cache_entries", "2"),
+        ("app_cache_capacity", "2"),
+        ("app_cache_total_weight", "2"),
+    ];
+    let samples = parse_metrics(&text);
+    let expected: Vec<_> = expected.iter().map(|(n, v)| (n.to_string(), v.to_string())).collect();
+    assert_eq!(samples, expected);
+
+    for (name, _) in &samples {
+        let kind = if name.ends_with("_total") { "counter" } else { "gauge" };
+        assert!(text.contains(&format!("# TYPE {} {}\n", name, kind)));
+        assert!(text.contains(&format!("# HELP {} ", name)));
+    }
+    assert_eq!(text, cache.metrics_text("app_cache"));
+}
+
+#[test]
+fn test_metrics_text_named_escapes_label() {
+    let cache = Cache::<&str, i32>::new(1);
+    let text = cache.metrics_text_named("c", "a\"b\\c");
+    assert!(text.contains("c_hits_total{cache=\"a\\\"b\\\\c\"} 0\n"));
+    assert_eq!(parse_metrics(&text).len(), 9);
+}
+
+#[test]
+fn test_warm_keeps_stats_and_given_order() {
+    let mut cache = Cache::new(3);
+    cache.put(0, 0);
+    cache.get(&0);
+    cache.get(&7);
+    let stats = cache.stats();
+
+    cache.warm((1..=5).map(|key| (key, key * 10)));
+    assert_eq!(cache.len(), 3);
+    assert_eq!(cache.iter().map(|(k, v)| (*k, *v)).collect::<Vec<_>>(), vec![(3, 30), (4, 40), (5, 50)]);
+    assert_eq!(cache.stats(), stats);
+
+    cache.put(6, 60);
+    assert_eq!(cache.peek(&3), None);
+    assert_eq!(cache.stats().evictions, stats.evictions + 1);
+}
+
+#[test]
+fn test_metadata_records_exact_timestamps() {
+    use lru_cache::cache::clock::Clock;
+    use lru_cache::cache::lru::EntryMetadata;
+
+    let clock = MockClock::new();
+    let start = clock.now();
+    let at = |secs| start + Duration::from_secs(secs);
+    let mut cache = Cache::with_clock(3, clock.clone());
+    cache.put("a", 1);
+    assert_eq!(cache.metadata(&"a"), None);
+    cache.track_metadata();
+
+    clock.advance(Duration::from_secs(1));
+    cache.put("b", 2);
+    clock.advance(Duration::from_secs(2));
+    cache.get(&"a");
+    cache.get(&"a");
+    clock.advance(Duration::from_secs(3));
+    cache.peek(&"b");
+    cache.put("b", 20);
+    cache.get_with(&"b", GetOptions::passive());
+    cache.get(&"missing");
+
+    assert_eq!(
+        cache.metadata(&"a"),
+        Some(EntryMetadata {
+            created_at: at(0),
+            last_accessed_at: at(3),
+            access_count: 2
+        })
+    );
+    assert_eq!(
+        cache.metadata(&"b"),
+        Some(EntryMetadata {
+            created_at: at(1),
+            last_accessed_at: at(1),
+            access_count: 0
+        })
+    );
+    let exported: Vec<_> = cache.iter_metadata().map(|(key, metadata)| (*key, metadata.created_at)).collect();
+    assert_eq!(exported, vec![("a", at(0)), ("b", at(1))]);
+
+    let mut plain = Cache::new(2);
+    plain.put("a", 1);
+    assert_eq!(plain.iter_metadata().count(), 0);
+    assert_eq!(Cache::<&str, i32>::with_metadata(2).metadata(&"a"), None);
+}
+
+#[test]
+fn test_access_counts_and_hottest() {
+    let mut cache = Cache::new(3);
+    cache.put("a", 1);
+    cache.put("b", 2);
+    cache.put("c", 3);
+    for key in ["a", "b", "a", "c", "a", "b", "missing"] {
+        cache.get(&key);
+    }
+    cache.peek(&"c");
+    cache.put("a", 10);
+    assert_eq!(cache.access_count(&"a"), Some(3));
+    assert_eq!(cache.access_count(&"b"), Some(2));
+    assert_eq!(cache.access_count(&"missing"), None);
+    assert_eq!(cache.hottest(2), vec![(&"a", 3), (&"b", 2)]);
+
+    cache.put("d", 4);
+    assert_eq!(cache.access_count(&"c"), None);
+    cache.put("c", 30);
+    assert_eq!(cache.access_count(&"c"), Some(0));
+    assert_eq!(cache.access_count(&"b"), None);
+    cache.get(&"d");
+    cache.get(&"c");
+    assert_eq!(cache.hottest(10), vec![(&"a", 3), (&"c", 1), (&"d", 1)]);
+
+    let restored = Cache::restore(cache.snapshot());
+    assert_eq!(restored.hottest(10), cache.hottest(10));
+}
+
+/// Cache dont les entrées 1 à `n` ont été lues il y a `n - i + 1` minutes
+fn aged_cache(n: u64) -> (Cache<u64, u64>, MockClock) {
+    let clock = MockClock::new();
+    let mut cache = Cache::with_clock(n as usize + 1, clock.clone());
+    cache.track_metadata();
+    for key in 1..=n {
+        cache.put(key, key);
+        clock.advance(Duration::from_secs(60));
+    }
+    (cache, clock)
+}
+
+#[test]
+fn test_age_histogram_counts() {
+    let (mut cache, clock) = aged_cache(5);
+    let minute = Duration::from_secs(60);
+    let buckets = [minute * 2, minute * 4];
+    // Inactivités : 5, 4, 3, 2 et 1 minutes
+    assert_eq!(cache.age_histogram(&buckets), vec![1, 2, 2]);
+    assert_eq!(cache.age_histogram(&[]), vec![5]);
+
+    cache.get(&1);
+    assert_eq!(cache.age_histogram(&buckets), vec![2, 2, 1]);
+    clock.advance(minute * 10);
+    assert_eq!(cache.age_histogram(&buckets), vec![0, 0, 5]);
+}
+
+#[test]
+fn test_age_queries_do_not_touch_recency() {
+    let (cache, _clock) = aged_cache(3);
+    cache.age_histogram(&[Duration::from_secs(90)]);
+    cache.percentile_idle(50.0);
+    assert_eq!(cache.iter().map(|(key, _)| *key).collect::<Vec<_>>(), vec![1, 2, 3]);
+    assert_eq!(cache.metadata(&1).unwrap().access_count, 0);
+}
+
+#[test]
+fn test_percentile_idle_nearest_rank() {
+    let (cache, _clock) = aged_cache(10);
+    let minutes = |n: u64| Some(Duration::from_secs(60 * n));
+    assert_eq!(cache.percentile_idle(0.0), minutes(1));
+    assert_eq!(cache.percentile_idle(10.0), minutes(1));
+    assert_eq!(cache.percentile_idle(15.0), minutes(2));
+    assert_eq!(cache.percentile_idle(50.0), minutes(5));
+    assert_eq!(cache.percentile_idle(90.0), minutes(9));
+    assert_eq!(cache.percentile_idle(100.0), minutes(10));
+}
+
+#[test]
+fn test_age_queries_edge_cases() {
+    let (empty, _clock) = aged_cache(0);
+    assert_eq!(empty.age_histogram(&[Duration::from_secs(1)]), vec![0, 0]);
+    assert_eq!(empty.percentile_idle(50.0), None);
+
+    let (single, _clock) = aged_cache(1);
+    assert_eq!(single.age_histogram(&[Duration::from_secs(60)]), vec![0, 1]);
+    for p in [0.0, 50.0, 100.0] {
+        assert_eq!(single.percentile_idle(p), Some(Duration::from_secs(60)));
+    }
+
+    let mut untracked = Cache::new(2);
+    untracked.put(1, 1);
+    assert_eq!(untracked.age_histogram(&[Duration::from_secs(1)]), vec![0, 0]);
+    assert_eq!(untracked.percentile_idle(50.0), None);
+}
+
+#[test]
+fn test_age_queries_skip_expired_entries() {
+    let clock = MockClock::new();
+    let mut cache = Cache::with_clock(3, clock.clone());
+    cache.track_metadata();
+    cache.put_with_ttl(1, 1, Duration::from_secs(30));
+    cache.put(2, 2);
+    clock.advance(Duration::from_secs(60));
+    assert_eq!(cache.age_histogram(&[]), vec![1]);
+    assert_eq!(cache.percentile_idle(100.0), Some(Duration::from_secs(60)));
+}
+
+#[test]
+#[should_panic]
+fn test_age_histogram_unsorted_buckets() {
+    let (cache, _clock) = aged_cache(1);
+    cache.age_histogram(&[Duration::from_secs(2), Duration::from_secs(1)]);
+}
+
+#[test]
+#[should_panic]
+fn test_percentile_idle_out_of_range() {
+    let (cache, _clock) = aged_cache(1);
+    cache.percentile_idle(101.0);
+}
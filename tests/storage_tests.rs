@@ -0,0 +1,777 @@
+mod common;
+
+use lru_cache::Cache;
+use lru_cache::cache::clock::MockClock;
+use lru_cache::cache::traits::{CacheIterate, CacheLen, CacheStorage, PersistentStorage};
+use lru_cache::storage::error::StorageError;
+use lru_cache::storage::file::{
+    FileStorage, LoadOptions, OversizedEntry, OversizedField, SaveOptions, VerifyOptions, DEFAULT_MAX_VALUE_LEN,
+};
+use std::env;
+use std::fs;
+use std::io::{self, Cursor, Read};
+use std::num::NonZeroUsize;
+use std::path::PathBuf;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+fn fixture(name: &str, content: &str) -> PathBuf {
+    let path = common::temp_path(name);
+    fs::write(&path, content).unwrap();
+    path
+}
+
+#[test]
+fn test_verify_good_file() {
+    let path = fixture("verify_good.txt", "3\na;1\nb;2\nc;3\n");
+    let report = Cache::<String, i32>::verify_file(path.to_str().unwrap()).unwrap();
+
+    assert!(report.is_ok());
+    assert_eq!(report.capacity, Some(3));
+    assert_eq!(report.entries, 3);
+    fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn test_verify_corrupt_file() {
+    let path = fixture("verify_corrupt.txt", "oops\na;1\nno separator\nb;x\n");
+    let report = FileStorage::verify::<String, i32>(path.to_str().unwrap(), VerifyOptions::default()).unwrap();
+
+    assert!(!report.is_ok());
+    assert_eq!(report.capacity, None);
+    assert_eq!(report.entries, 1);
+    assert_eq!(report.malformed_lines, 1);
+    assert_eq!(report.invalid_values, 1);
+    fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn test_verify_duplicate_keys() {
+    let path = fixture("verify_duplicates.txt", "5\na;1\nb;2\na;3\na;4\n");
+    for exact_duplicates in [false, true] {
+        let options = VerifyOptions { exact_duplicates };
+        let report = FileStorage::verify::<String, i32>(path.to_str().unwrap(), options).unwrap();
+
+        assert_eq!(report.entries, 2);
+        assert_eq!(report.duplicate_keys, 2);
+        assert!(!report.is_ok());
+    }
+    fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn test_verify_over_capacity() {
+    let path = fixture("verify_over_capacity.txt", "2\na;1\nb;2\nc;3\n");
+    let report = FileStorage::verify::<String, i32>(path.to_str().unwrap(), VerifyOptions::default()).unwrap();
+
+    assert!(report.over_capacity());
+    assert!(!report.is_ok());
+    fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn test_verify_missing_file() {
+    let result = FileStorage::verify::<String, i32>("does_not_exist.txt", VerifyOptions::default());
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_load_all_from_file_keeps_every_entry() {
+    let mut content = String::from("8\n");
+    for i in 0..10_000 {
+        content.push_str(&format!("key{};{}\n", i, i));
+    }
+    content.push_str("broken line\n");
+    let path = fixture("load_all.txt", &content);
+    let mut cache = Cache::<String, i32>::load_all_from_file(path.to_str().unwrap()).unwrap();
+
+    assert_eq!(cache.capacity(), 10_000);
+    assert_eq!(cache.len(), 10_000);
+    assert_eq!(cache.iter().last(), Some((&String::from("key9999"), &9999)));
+    assert_eq!(cache.get(&String::from("key0")), Some(&0));
+    fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn test_load_all_from_file_errors() {
+    let path = fixture("load_all_empty.txt", "0\n");
+    let cache = Cache::<String, i32>::load_all_from_file(path.to_str().unwrap()).unwrap();
+    assert_eq!(cache.capacity(), 1);
+    assert!(cache.is_empty());
+    fs::remove_file(path).unwrap();
+
+    let path = fixture("load_all_header.txt", "a;1\n");
+    let error = Cache::<String, i32>::load_all_from_file(path.to_str().unwrap()).unwrap_err();
+    assert!(matches!(error, StorageError::InvalidCapacity(ref line) if line == "a;1"));
+    fs::remove_file(path).unwrap();
+
+    let missing = output_missing();
+    assert!(matches!(Cache::<String, i32>::load_all_from_file(&missing), Err(StorageError::Io(_))));
+}
+
+fn output_missing() -> String {
+    common::temp_path("absent.txt").to_string_lossy().into_owned()
+}
+
+const SNAPSHOT: &str = "3\nalpha;1\nbeta;22\ngamma;333\n";
+
+#[test]
+fn test_load_lossy_reports_truncation_at_several_offsets() {
+    // (coupure, tronqué, entrées récupérées, octets propres)
+    let cases = [
+        (SNAPSHOT.len(), false, 3, SNAPSHOT.len() as u64),
+        (SNAPSHOT.len() - 1, true, 2, 18),
+        (SNAPSHOT.len() - 3, true, 2, 18),
+        (12, true, 1, 10),
+        (10, false, 1, 10),
+        (1, true, 0, 0),
+    ];
+    for (cut, truncated, entries, clean_len) in cases {
+        let path = fixture("truncated.txt", &SNAPSHOT[..cut]);
+        let loaded = FileStorage::load_lossy::<String, i32>(path.to_str().unwrap()).unwrap();
+
+        assert_eq!(loaded.report.truncated, truncated, "coupure {}", cut);
+        assert_eq!(loaded.report.clean_len, clean_len, "coupure {}", cut);
+        assert_eq!(loaded.data.len(), entries, "coupure {}", cut);
+        assert_eq!(loaded.report.entries, entries, "coupure {}", cut);
+        fs::remove_file(path).unwrap();
+    }
+}
+
+#[test]
+fn test_load_lossy_drops_partial_value() {
+    let path = fixture("truncated_value.txt", "2\na;1\nb;12");
+    let loaded = FileStorage::load_lossy::<String, i32>(path.to_str().unwrap()).unwrap();
+
+    assert_eq!(loaded.data, vec![(String::from("a"), 1)]);
+    assert_eq!(loaded.report.capacity, Some(2));
+    assert!(loaded.report.truncated);
+    fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn test_repair_writes_salvageable_prefix() {
+    let path = fixture("repair_in.txt", "3\nalpha;1\nno separator\nbeta;22\ngam");
+    let out = fixture("repair_out.txt", "");
+    let report = FileStorage::repair(path.to_str().unwrap(), out.to_str().unwrap()).unwrap();
+
+    assert!(report.truncated);
+    assert_eq!(report.entries, 2);
+    assert_eq!(report.skipped_lines, 1);
+    assert_eq!(fs::read_to_string(&out).unwrap(), "#lru_cache v4\n3\nalpha;1\nbeta;22\n#crc32 5909eba9\n");
+    let verified = FileStorage::verify::<String, i32>(out.to_str().unwrap(), VerifyOptions::default()).unwrap();
+    assert!(verified.is_ok());
+
+    fs::write(&path, "3\nalp").unwrap();
+    FileStorage::repair(path.to_str().unwrap(), out.to_str().unwrap()).unwrap();
+    assert_eq!(fs::read_to_string(&out).unwrap(), "#lru_cache v4\n3\n#crc32 689199d2\n");
+    fs::write(&path, "").unwrap();
+    let report = FileStorage::repair(path.to_str().unwrap(), out.to_str().unwrap()).unwrap();
+    assert_eq!(report.capacity, None);
+    assert_eq!(fs::read_to_string(&out).unwrap(), "#lru_cache v4\n0\n#crc32 43bcca11\n");
+    fs::remove_file(path).unwrap();
+    fs::remove_file(out).unwrap();
+}
+
+#[test]
+fn test_save_with_skips_entries_over_the_limits() {
+    let path = fixture("save_limits.txt", "");
+    let options = SaveOptions {
+        max_key_len: 4,
+        max_value_len: 6,
+        ..SaveOptions::default()
+    };
+    let data = vec![
+        (String::from("abcd"), String::from("123456")),
+        (String::from("abcde"), String::from("1")),
+        (String::from("k"), String::from("1234567")),
+    ];
+    let report = FileStorage::save_with(path.to_str().unwrap(), 3, &data, options).unwrap();
+
+    assert_eq!(report.entries, 1);
+    assert_eq!(
+        report.oversized,
+        vec![
+            OversizedEntry { position: 1, field: OversizedField::Key },
+            OversizedEntry { position: 2, field: OversizedField::Value },
+        ]
+    );
+    assert_eq!(fs::read_to_string(&path).unwrap(), "#lru_cache v4\n3\nabcd;123456\n#crc32 6d6ab738\n");
+    fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn test_load_with_limits_lossy_and_strict() {
+    let long_value = "9".repeat(100_000);
+    let content = format!("4\nabcd;123456\nabcde;1\nk;1234567\nz;{}\nlast;7\n", long_value);
+    let path = fixture("load_limits.txt", &content);
+    let mut options = LoadOptions {
+        max_key_len: 4,
+        max_value_len: 6,
+        strict: false,
+    };
+    let loaded = FileStorage::load_with::<String, String>(path.to_str().unwrap(), options).unwrap();
+
+    assert_eq!(loaded.capacity, 4);
+    assert_eq!(
+        loaded.data,
+        vec![
+            (String::from("abcd"), String::from("123456")),
+            (String::from("last"), String::from("7")),
+        ]
+    );
+    assert_eq!(
+        loaded.oversized,
+        vec![
+            OversizedEntry { position: 3, field: OversizedField::Key },
+            OversizedEntry { position: 4, field: OversizedField::Value },
+            OversizedEntry { position: 5, field: OversizedField::Value },
+        ]
+    );
+
+    options.strict = true;
+    let error = FileStorage::load_with::<String, String>(path.to_str().unwrap(), options).unwrap_err();
+    assert!(matches!(
+        error,
+        StorageError::Oversized(OversizedEntry { position: 3, field: OversizedField::Key })
+    ));
+    fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn test_default_load_skips_values_over_the_default_limit() {
+    let content = format!("2\nbig;{}\nsmall;{}\n", "x".repeat(DEFAULT_MAX_VALUE_LEN + 1), "y".repeat(DEFAULT_MAX_VALUE_LEN));
+    let path = fixture("load_default_limits.txt", &content);
+    let (_, data) = FileStorage::load::<String, String>(path.to_str().unwrap()).unwrap();
+
+    assert_eq!(data.len(), 1);
+    assert_eq!(data[0].0, "small");
+    fs::remove_file(path).unwrap();
+}
+
+fn dated_snapshot(name: &str, age: Duration) -> PathBuf {
+    let saved_at = (SystemTime::now() - age).duration_since(UNIX_EPOCH).unwrap().as_secs();
+    fixture(name, &format!("2;{}\na;1\nb;2\n", saved_at))
+}
+
+#[test]
+fn test_load_if_fresh_accepts_recent_snapshot() {
+    let path = dated_snapshot("fresh.txt", Duration::from_secs(60));
+    let mut cache = Cache::<String, i32>::load_from_file_if_fresh(path.to_str().unwrap(), NonZeroUsize::new(2).unwrap(), Duration::from_secs(3600))
+        .unwrap()
+        .unwrap();
+    assert_eq!(cache.get(&String::from("b")), Some(&2));
+    fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn test_load_if_fresh_rejects_stale_snapshot() {
+    let path = dated_snapshot("stale.txt", Duration::from_secs(2 * 3600));
+    let loaded = Cache::<String, i32>::load_from_file_if_fresh(path.to_str().unwrap(), NonZeroUsize::new(2).unwrap(), Duration::from_secs(3600));
+    assert!(loaded.unwrap().is_none());
+
+    let metadata = FileStorage::metadata(path.to_str().unwrap()).unwrap();
+    let age = SystemTime::now().duration_since(metadata.saved_at.unwrap()).unwrap();
+    assert!(age >= Duration::from_secs(2 * 3600));
+    fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn test_load_if_fresh_requires_timestamp() {
+    let path = fixture("undated.txt", "2\na;1\n");
+    let error = Cache::<String, i32>::load_from_file_if_fresh(path.to_str().unwrap(), NonZeroUsize::new(2).unwrap(), Duration::from_secs(3600))
+        .unwrap_err();
+    assert!(matches!(error, StorageError::MissingTimestamp));
+
+    let (capacity, data) = FileStorage::load::<String, i32>(path.to_str().unwrap()).unwrap();
+    assert_eq!((capacity, data.len()), (2, 1));
+    fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn test_save_to_file_records_save_time() {
+    let path = output_missing();
+    let mut cache = Cache::new(2);
+    cache.put(String::from("a"), 1);
+    let before = SystemTime::now() - Duration::from_secs(1);
+    cache.save_to_file(&path).unwrap();
+
+    let saved_at = FileStorage::metadata(&path).unwrap().saved_at.unwrap();
+    assert!(saved_at >= before && saved_at <= SystemTime::now());
+    fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn test_save_and_load_round_trip_separators_and_newlines() {
+    let path = common::temp_path("escaped.txt");
+    let path = path.to_str().unwrap();
+    let pieces = ["", "a;b", "x\ny", "\\", ";", "\r\n", "fin\\", "\\;", "é;ü\n"];
+    let mut data = Vec::new();
+    for key in pieces {
+        for value in pieces {
+            data.push((format!("{}|{}", key, value), format!("{}{}", value, key)));
+        }
+    }
+    FileStorage::save(path, data.len(), &data).unwrap();
+
+    let (capacity, loaded) = FileStorage::load::<String, String>(path).unwrap();
+    assert_eq!(capacity, data.len());
+    assert_eq!(loaded, data);
+    assert!(FileStorage::verify::<String, String>(path, VerifyOptions::default()).unwrap().is_ok());
+    fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn test_load_file_written_without_escapes() {
+    let path = fixture("unescaped.txt", "2\nC:\\temp;a;b\n;\n");
+    let (_, data) = FileStorage::load::<String, String>(path.to_str().unwrap()).unwrap();
+    assert_eq!(
+        data,
+        vec![
+            (String::from("C:\\temp"), String::from("a;b")),
+            (String::new(), String::new()),
+        ]
+    );
+    fs::remove_file(path).unwrap();
+}
+
+/// Valeur dont le formatage panique au deuxième appel, après la mesure de
+/// sa longueur, au milieu de l'écriture du fichier
+struct PanicOnWrite(std::cell::Cell<bool>);
+
+impl std::fmt::Display for PanicOnWrite {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.0.replace(true) {
+            panic!("écriture interrompue");
+        }
+        write!(f, "ok")
+    }
+}
+
+fn leftover_temp_files(path: &str) -> Vec<PathBuf> {
+    let prefix = format!("{}.tmp.", path);
+    fs::read_dir(env::temp_dir())
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .filter(|entry| entry.to_string_lossy().starts_with(&prefix))
+        .collect()
+}
+
+#[test]
+fn test_failed_save_keeps_previous_file() {
+    let path = common::temp_path("atomic.txt");
+    let path = path.to_str().unwrap();
+    FileStorage::save(path, 2, &[("a", 1), ("b", 2)]).unwrap();
+    let previous = fs::read_to_string(path).unwrap();
+
+    let data = [("x", PanicOnWrite(std::cell::Cell::new(false)))];
+    let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| FileStorage::save(path, 2, &data)));
+    assert!(result.is_err());
+
+    assert_eq!(fs::read_to_string(path).unwrap(), previous);
+    assert!(leftover_temp_files(path).is_empty());
+    fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn test_save_into_missing_directory_fails_cleanly() {
+    let dir = common::temp_path("no_such_dir");
+    let path = dir.join("cache.txt");
+    assert!(FileStorage::save(path.to_str().unwrap(), 1, &[("a", 1)]).is_err());
+    assert!(!dir.exists());
+}
+
+#[test]
+fn test_save_replaces_existing_file() {
+    let path = fixture("replace.txt", "9\nold;1\nstale;2\n");
+    let path = path.to_str().unwrap();
+    FileStorage::save(path, 1, &[("new", 3)]).unwrap();
+
+    assert_eq!(FileStorage::load::<String, i32>(path).unwrap(), (1, vec![(String::from("new"), 3)]));
+    assert!(leftover_temp_files(path).is_empty());
+    fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn test_load_rejects_corrupt_capacity() {
+    let cases = [("", None), ("\na;1\n", None), ("deux\na;1\n", Some("deux")), ("-1\n", Some("-1")), ("2;hier\n", Some("2;hier"))];
+    for (content, invalid) in cases {
+        let path = fixture("corrupt_capacity.txt", content);
+        let path = path.to_str().unwrap();
+        for error in [
+            FileStorage::load::<String, i32>(path).unwrap_err(),
+            FileStorage::load_strict::<String, i32>(path).unwrap_err(),
+        ] {
+            match invalid {
+                Some(line) => assert!(matches!(error, StorageError::InvalidCapacity(ref l) if l == line), "{:?}", error),
+                None => assert!(matches!(error, StorageError::MissingCapacity), "{:?}", error),
+            }
+        }
+        fs::remove_file(path).unwrap();
+    }
+}
+
+#[test]
+fn test_load_strict_rejects_line_without_separator() {
+    let path = fixture("strict_separator.txt", "3\na;1\nno separator\nb;2\n");
+    let path = path.to_str().unwrap();
+    let error = FileStorage::load_strict::<String, i32>(path).unwrap_err();
+    assert!(matches!(
+        error,
+        StorageError::InvalidEntry { line: 3, ref content } if content == "no separator"
+    ));
+
+    let (capacity, data) = FileStorage::load::<String, i32>(path).unwrap();
+    assert_eq!((capacity, data.len()), (3, 2));
+    fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn test_load_strict_rejects_value_failing_from_str() {
+    let path = fixture("strict_value.txt", "3\na;1\nb;2\nc;x\n");
+    let path = path.to_str().unwrap();
+    let error = FileStorage::load_strict::<String, i32>(path).unwrap_err();
+    assert!(matches!(
+        error,
+        StorageError::InvalidEntry { line: 4, ref content } if content == "c;x"
+    ));
+    assert_eq!(error.to_string(), "ligne 4 : entrée illisible \"c;x\"");
+
+    let (_, data) = FileStorage::load_strict::<String, String>(path).unwrap();
+    assert_eq!(data.len(), 3);
+    fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn test_load_lossy_lists_skipped_lines() {
+    let path = fixture("lossy_numbers.txt", "3\na;1\nbroken\nb;x\nc;3\n");
+    let loaded = FileStorage::load_lossy::<String, i32>(path.to_str().unwrap()).unwrap();
+    assert_eq!(loaded.data.len(), 2);
+    assert_eq!(loaded.report.skipped_lines, 2);
+    assert_eq!(loaded.report.skipped_line_numbers, vec![3, 4]);
+    fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn test_storage_error_converts_to_and_from_io() {
+    let missing = output_missing();
+    assert!(matches!(FileStorage::load::<String, i32>(&missing), Err(StorageError::Io(_))));
+
+    let path = fixture("io_conversion.txt", "oops\n");
+    let error = Cache::<String, i32>::load_from_file(path.to_str().unwrap(), NonZeroUsize::new(2).unwrap()).unwrap_err();
+    assert_eq!(error.kind(), std::io::ErrorKind::InvalidData);
+    let inner = error.get_ref().unwrap().downcast_ref::<StorageError>().unwrap();
+    assert!(matches!(inner, StorageError::InvalidCapacity(_)));
+    fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn test_load_from_file_auto_uses_stored_capacity() {
+    let path = output_missing();
+    let mut cache = Cache::new(5);
+    for i in 0..5 {
+        cache.put(format!("key{}", i), i);
+    }
+    cache.save_to_file(&path).unwrap();
+
+    let mut loaded = Cache::<String, i32>::load_from_file_auto(&path).unwrap();
+    assert_eq!(loaded.capacity(), 5);
+    assert_eq!(loaded.len(), 5);
+    assert_eq!(loaded.iter().collect::<Vec<_>>(), cache.iter().collect::<Vec<_>>());
+    assert_eq!(loaded.stats().evictions, 0);
+    for i in 0..5 {
+        assert_eq!(loaded.get(&format!("key{}", i)), Some(&i));
+    }
+
+    let mut smaller = Cache::<String, i32>::load_from_file(&path, NonZeroUsize::new(3).unwrap()).unwrap();
+    assert_eq!((smaller.capacity(), smaller.len()), (3, 3));
+    assert_eq!(smaller.stats().evictions, 2);
+    assert_eq!(smaller.get(&String::from("key1")), None);
+    assert_eq!(smaller.get(&String::from("key4")), Some(&4));
+    fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn test_load_from_file_auto_rejects_zero_or_missing_capacity() {
+    let path = fixture("auto_zero.txt", "0\na;1\n");
+    let error = Cache::<String, i32>::load_from_file_auto(path.to_str().unwrap()).unwrap_err();
+    assert!(matches!(error, StorageError::InvalidCapacity(ref line) if line == "0"));
+    fs::remove_file(path).unwrap();
+
+    let path = fixture("auto_missing.txt", "");
+    let error = Cache::<String, i32>::load_from_file_auto(path.to_str().unwrap()).unwrap_err();
+    assert!(matches!(error, StorageError::MissingCapacity));
+    fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn test_persistence_accepts_path_buf() {
+    let path: PathBuf = common::temp_path("path_buf.txt");
+    let mut cache = Cache::new(2);
+    cache.put(String::from("a"), 1);
+    cache.save_to_file(&path).unwrap();
+
+    let mut loaded = Cache::<String, i32>::load_from_file(&path, NonZeroUsize::new(2).unwrap()).unwrap();
+    assert_eq!(loaded.get(&String::from("a")), Some(&1));
+    assert_eq!(FileStorage::metadata(&path).unwrap().capacity, 2);
+    assert!(FileStorage::verify::<String, i32>(&path, VerifyOptions::default()).unwrap().is_ok());
+    fs::remove_file(path).unwrap();
+}
+
+#[cfg(unix)]
+#[test]
+fn test_persistence_accepts_non_utf8_path() {
+    use std::ffi::OsStr;
+    use std::os::unix::ffi::OsStrExt;
+
+    let name = OsStr::from_bytes(b"cache_\xff\xfe.txt");
+    let path = common::temp_path("non_utf8").join(name);
+    fs::create_dir_all(path.parent().unwrap()).unwrap();
+    assert!(path.to_str().is_none());
+
+    let mut cache = Cache::new(2);
+    cache.put(String::from("a"), 1);
+    cache.save_to_file(&path).unwrap();
+    let mut loaded = Cache::<String, i32>::load_from_file_auto(&path).unwrap();
+    assert_eq!(loaded.get(&String::from("a")), Some(&1));
+    fs::remove_dir_all(path.parent().unwrap()).unwrap();
+}
+
+#[test]
+fn test_load_v1_file_without_version_line() {
+    let path = fixture("version_v1.txt", "3;1700000000\na;1\nb\\;c;2\n");
+    assert_eq!(FileStorage::detect_version(&path).unwrap(), 1);
+
+    let (capacity, data) = FileStorage::load::<String, i32>(&path).unwrap();
+    assert_eq!(capacity, 3);
+    assert_eq!(data, vec![(String::from("a"), 1), (String::from("b;c"), 2)]);
+    assert_eq!(FileStorage::metadata(&path).unwrap().capacity, 3);
+    fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn test_load_v2_file() {
+    let path = fixture("version_v2.txt", "#lru_cache v2\n3;1700000000\na;1\nb\\;c;2\nbad\n");
+    assert_eq!(FileStorage::detect_version(&path).unwrap(), 2);
+
+    let (capacity, data) = FileStorage::load::<String, i32>(&path).unwrap();
+    assert_eq!(capacity, 3);
+    assert_eq!(data, vec![(String::from("a"), 1), (String::from("b;c"), 2)]);
+    let metadata = FileStorage::metadata(&path).unwrap();
+    assert_eq!(metadata.saved_at, Some(UNIX_EPOCH + Duration::from_secs(1_700_000_000)));
+
+    let error = FileStorage::load_strict::<String, i32>(&path).unwrap_err();
+    assert!(matches!(error, StorageError::InvalidEntry { line: 5, ref content } if content == "bad"));
+    let loaded = FileStorage::load_lossy::<String, i32>(&path).unwrap();
+    assert_eq!(loaded.report.capacity, Some(3));
+    assert_eq!(loaded.report.skipped_line_numbers, vec![5]);
+    fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn test_load_rejects_unknown_version() {
+    let path = fixture("version_v99.txt", "#lru_cache v99\n3\na;1\n");
+    assert_eq!(FileStorage::detect_version(&path).unwrap(), 99);
+
+    let error = FileStorage::load::<String, i32>(&path).unwrap_err();
+    assert!(matches!(error, StorageError::UnsupportedVersion(99)));
+    assert_eq!(error.to_string(), "version 99 du format non prise en charge");
+    assert!(matches!(FileStorage::metadata(&path), Err(StorageError::UnsupportedVersion(99))));
+    assert!(matches!(
+        Cache::<String, i32>::load_from_file_auto(&path),
+        Err(StorageError::UnsupportedVersion(99))
+    ));
+    let error = FileStorage::verify::<String, i32>(&path, VerifyOptions::default()).unwrap_err();
+    assert!(matches!(error.get_ref().and_then(|e| e.downcast_ref()), Some(StorageError::UnsupportedVersion(99))));
+
+    fs::write(&path, "#lru_cache vX\n3\n").unwrap();
+    assert!(matches!(FileStorage::detect_version(&path), Err(StorageError::InvalidCapacity(line)) if line == "#lru_cache vX"));
+    fs::remove_file(path).unwrap();
+}
+
+fn saved_checksum(path: &PathBuf) -> u32 {
+    let content = fs::read_to_string(path).unwrap();
+    let trailer = content.lines().last().unwrap();
+    u32::from_str_radix(trailer.strip_prefix("#crc32 ").unwrap(), 16).unwrap()
+}
+
+#[test]
+fn test_load_detects_corrupted_byte() {
+    let path = fixture("checksum_corrupt.txt", "");
+    let data: Vec<_> = (0..100).map(|i| (format!("key{}", i), i)).collect();
+    FileStorage::save(&path, 100, &data).unwrap();
+    let checked = FileStorage::load_checked::<String, i32>(&path).unwrap();
+    assert!(checked.verified);
+    assert_eq!(checked.data, data);
+
+    let expected = saved_checksum(&path);
+    let mut bytes = fs::read(&path).unwrap();
+    let middle = bytes.len() / 2;
+    let separator = (middle..).find(|&i| bytes[i] == b';').unwrap();
+    let digit = (separator..).find(|&i| bytes[i].is_ascii_digit()).unwrap();
+    bytes[digit] = if bytes[digit] == b'9' { b'8' } else { bytes[digit] + 1 };
+    fs::write(&path, &bytes).unwrap();
+
+    let error = FileStorage::load::<String, i32>(&path).unwrap_err();
+    assert!(matches!(error, StorageError::ChecksumMismatch { expected: e, found } if e == expected && found != expected));
+    assert!(matches!(
+        FileStorage::load_strict::<String, i32>(&path),
+        Err(StorageError::ChecksumMismatch { .. })
+    ));
+    assert!(matches!(
+        Cache::<String, i32>::load_from_file_auto(&path),
+        Err(StorageError::ChecksumMismatch { .. })
+    ));
+    let report = FileStorage::verify::<String, i32>(&path, VerifyOptions::default()).unwrap();
+    assert_eq!(report.checksum_ok, Some(false));
+    assert!(!report.is_ok());
+    assert_eq!(FileStorage::load_lossy::<String, i32>(&path).unwrap().data.len(), 100);
+    fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn test_load_detects_truncation() {
+    let path = fixture("checksum_truncated.txt", "");
+    FileStorage::save(&path, 3, &[("a", 1), ("b", 2), ("c", 3)]).unwrap();
+    let content = fs::read_to_string(&path).unwrap();
+
+    let without_trailer = content.rfind("#crc32").unwrap();
+    fs::write(&path, &content[..without_trailer]).unwrap();
+    assert!(matches!(
+        FileStorage::load::<String, i32>(&path),
+        Err(StorageError::MissingChecksum)
+    ));
+    let report = FileStorage::verify::<String, i32>(&path, VerifyOptions::default()).unwrap();
+    assert_eq!(report.checksum_ok, Some(false));
+
+    let after_first_entry = content.find("a;1\n").unwrap() + 4;
+    fs::write(&path, &content[..after_first_entry]).unwrap();
+    assert!(matches!(
+        FileStorage::load::<String, i32>(&path),
+        Err(StorageError::MissingChecksum)
+    ));
+
+    fs::write(&path, &content[..content.len() - 3]).unwrap();
+    assert!(matches!(
+        FileStorage::load::<String, i32>(&path),
+        Err(StorageError::MissingChecksum)
+    ));
+
+    // Des lignes ajoutées après la somme forment un segment sans somme
+    fs::write(&path, format!("{}d;4\n", content)).unwrap();
+    assert!(matches!(
+        FileStorage::load::<String, i32>(&path),
+        Err(StorageError::MissingChecksum)
+    ));
+    fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn test_old_files_load_unverified() {
+    let path = fixture("checksum_old.txt", "#lru_cache v2\n2\na;1\n");
+    let loaded = FileStorage::load_checked::<String, i32>(&path).unwrap();
+    assert_eq!(loaded.data, vec![(String::from("a"), 1)]);
+    assert!(!loaded.verified);
+    let report = FileStorage::verify::<String, i32>(&path, VerifyOptions::default()).unwrap();
+    assert_eq!(report.checksum_ok, None);
+    assert!(report.is_ok());
+
+    fs::write(&path, "2\na;1\n").unwrap();
+    assert!(!FileStorage::load_checked::<String, i32>(&path).unwrap().verified);
+    fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn test_checksum_with_load_limits_and_encoding() {
+    let path = fixture("checksum_limits.txt", "");
+    FileStorage::save(&path, 2, &[("k", 1), ("key", 12)]).unwrap();
+    let options = LoadOptions {
+        max_key_len: 1,
+        max_value_len: 1,
+        strict: false,
+    };
+    let loaded = FileStorage::load_with::<String, i32>(&path, options).unwrap();
+    assert_eq!(loaded.data, vec![(String::from("k"), 1)]);
+
+    FileStorage::save_encoded(&path, 2, &[(String::from("a;b"), 1.5)]).unwrap();
+    assert!(FileStorage::load_encoded::<String, f64>(&path).is_ok());
+    let content = fs::read_to_string(&path).unwrap().replace("1.5", "2.5");
+    fs::write(&path, content).unwrap();
+    let error = FileStorage::load_encoded::<String, f64>(&path).unwrap_err();
+    assert_eq!(error.kind(), std::io::ErrorKind::InvalidData);
+    assert!(matches!(
+        error.get_ref().and_then(|e| e.downcast_ref()),
+        Some(StorageError::ChecksumMismatch { .. })
+    ));
+    fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn test_writer_output_matches_file() {
+    let path = fixture("writer_matches.txt", "");
+    let clock = MockClock::new();
+    clock.advance(Duration::from_secs(1_700_000_000));
+    let mut cache = Cache::with_clock(3, clock);
+    cache.put(String::from("a;b"), String::from("line\nbreak\\"));
+    cache.put(String::from("c"), String::from("\r"));
+    cache.save_to_file(&path).unwrap();
+
+    let mut buffer = Vec::new();
+    cache.save_to_writer(&mut buffer).unwrap();
+    assert_eq!(buffer, fs::read(&path).unwrap());
+
+    let loaded = Cache::<String, String>::load_from_reader(Cursor::new(&buffer), NonZeroUsize::new(3).unwrap()).unwrap();
+    assert_eq!(loaded.iter().collect::<Vec<_>>(), cache.iter().collect::<Vec<_>>());
+    fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn test_writer_round_trip_empty_cache() {
+    let mut buffer = Vec::new();
+    FileStorage::save_to_writer(&mut buffer, 4, &[] as &[(String, i32)]).unwrap();
+    let (capacity, data) = FileStorage::load_from_reader::<String, i32>(Cursor::new(buffer)).unwrap();
+    assert_eq!(capacity, 4);
+    assert!(data.is_empty());
+
+    let loaded = Cache::<String, i32>::load_from_reader(io::empty(), NonZeroUsize::new(4).unwrap());
+    assert!(matches!(loaded, Err(StorageError::MissingCapacity)));
+}
+
+#[test]
+fn test_load_from_reader_reports_read_errors() {
+    struct FailingReader(usize);
+
+    impl Read for FailingReader {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            if self.0 == 0 {
+                return Err(io::Error::new(io::ErrorKind::ConnectionReset, "connexion perdue"));
+            }
+            self.0 -= 1;
+            let line = b"#lru_cache v3\n2\n";
+            buf[..line.len()].copy_from_slice(line);
+            Ok(line.len())
+        }
+    }
+
+    let error = FileStorage::load_from_reader::<String, i32>(FailingReader(1)).unwrap_err();
+    assert!(matches!(error, StorageError::Io(ref e) if e.kind() == io::ErrorKind::ConnectionReset));
+}
+
+#[test]
+fn test_save_and_load_million_entries() {
+    const ENTRIES: u32 = 1_000_000;
+    let path = fixture("million.txt", "");
+    let mut cache = Cache::new(ENTRIES as usize);
+    for i in 0..ENTRIES {
+        cache.put(i, i ^ 0x5555);
+    }
+
+    let start = std::time::Instant::now();
+    cache.save_to_file(&path).unwrap();
+    let mut loaded = Cache::<u32, u32>::load_from_file(&path, NonZeroUsize::new(ENTRIES as usize).unwrap()).unwrap();
+    assert!(start.elapsed() < Duration::from_secs(60), "{:?}", start.elapsed());
+
+    assert_eq!(loaded.len(), ENTRIES as usize);
+    assert_eq!(loaded.get(&0), Some(&0x5555));
+    assert_eq!(loaded.get(&(ENTRIES - 1)), Some(&((ENTRIES - 1) ^ 0x5555)));
+    fs::remove_file(path).unwrap();
+}
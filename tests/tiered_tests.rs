@@ -0,0 +1,113 @@
+mod common;
+
+use lru_cache::Cache;
+use lru_cache::cache::tiered::{TieredCache, TieredStats};
+use lru_cache::cache::traits::{CacheIterate, CacheLen, CacheRemove, CacheStorage, GetOptions};
+use lru_cache::storage::backend::MemoryBackend;
+use lru_cache::storage::file::FileStorage;
+use std::fs;
+
+fn keys(cache: &Cache<u32, String>) -> Vec<u32> {
+    cache.iter().map(|(key, _)| *key).collect()
+}
+
+#[test]
+fn test_working_set_fits_across_tiers() {
+    let path = common::temp_path("tiered_working_set.txt");
+    let mut cache = TieredCache::open(4, &path, 8).unwrap();
+    for key in 0..10 {
+        cache.put(key, format!("v{}", key));
+    }
+    assert_eq!((cache.l1().len(), cache.l2().len()), (4, 6));
+
+    for _ in 0..5 {
+        for key in 0..10 {
+            assert_eq!(cache.get(&key), Some(&format!("v{}", key)));
+        }
+    }
+    let stats = cache.stats();
+    assert_eq!(stats.misses, 0);
+    assert_eq!(stats.l1_hits + stats.l2_hits, 50);
+    assert_eq!(keys(cache.l1()), vec![6, 7, 8, 9]);
+    assert_eq!(keys(cache.l2()), vec![0, 1, 2, 3, 4, 5]);
+
+    cache.flush().unwrap();
+    let (capacity, data) = FileStorage::load::<u32, String>(&path).unwrap();
+    assert_eq!(capacity, 12);
+    let expected: Vec<_> = (0..10).map(|key| (key, format!("v{}", key))).collect();
+    assert_eq!(data, expected);
+
+    let reopened = TieredCache::<u32, String>::open(4, &path, 8).unwrap();
+    assert_eq!(keys(reopened.l1()), vec![6, 7, 8, 9]);
+    assert_eq!(keys(reopened.l2()), vec![0, 1, 2, 3, 4, 5]);
+    fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn test_passive_get_moves_nothing_and_counts_nothing() {
+    let mut cache = TieredCache::new(2, MemoryBackend::new(), 4);
+    for key in 0..4u32 {
+        cache.put(key, format!("v{}", key));
+    }
+    assert_eq!(keys(cache.l1()), vec![2, 3]);
+
+    assert_eq!(cache.get_with(&0, GetOptions::passive()), Some(&String::from("v0")));
+    assert_eq!(cache.get_with(&2, GetOptions::passive()), Some(&String::from("v2")));
+    assert_eq!(cache.get_with(&9, GetOptions::passive()), None);
+    assert_eq!(keys(cache.l1()), vec![2, 3]);
+    assert_eq!(keys(cache.l2()), vec![0, 1]);
+    assert_eq!(cache.stats(), TieredStats::default());
+
+    let counted = GetOptions {
+        promote: false,
+        record_stats: true,
+    };
+    assert_eq!(cache.get_with(&0, counted), Some(&String::from("v0")));
+    assert_eq!(keys(cache.l2()), vec![0, 1]);
+    assert_eq!(cache.stats().l2_hits, 1);
+}
+
+#[test]
+fn test_tiers_never_share_a_key() {
+    let mut cache = TieredCache::new(1, MemoryBackend::new(), 1);
+    cache.put("a", 1);
+    cache.put("b", 2);
+    cache.put("a", 10);
+    assert_eq!(cache.len(), 2);
+    assert_eq!(cache.l1().peek(&"a"), Some(&10));
+    assert_eq!(cache.l2().peek(&"b"), Some(&2));
+
+    cache.put("c", 3);
+    assert!(!cache.contains_key(&"b"));
+    assert_eq!(cache.get(&"b"), None);
+    assert_eq!(cache.get(&"a"), Some(&10));
+    assert_eq!(cache.remove(&"c"), Some(3));
+    assert_eq!(cache.remove(&"a"), Some(10));
+    assert_eq!(
+        cache.stats(),
+        TieredStats {
+            l1_hits: 0,
+            l2_hits: 1,
+            misses: 1
+        }
+    );
+    assert_eq!(cache.stats().hit_ratio(), 0.5);
+}
+
+#[test]
+fn test_put_evicting_returns_victims() {
+    let mut cache = Cache::new(2);
+    assert!(cache.put_evicting("a", 1).is_empty());
+    assert!(cache.put_evicting("b", 2).is_empty());
+    cache.get(&"a");
+    assert_eq!(cache.put_evicting("c", 3), vec![("b", 2)]);
+    assert!(cache.put_evicting("a", 10).is_empty());
+    assert_eq!(cache.stats().evictions, 1);
+
+    let mut weighted = Cache::with_weigher(3, |_: &&str, value: &usize| *value);
+    weighted.put("a", 1);
+    weighted.put("b", 1);
+    assert_eq!(weighted.put_evicting("c", 3), vec![("a", 1), ("b", 1)]);
+    assert!(weighted.put_evicting("d", 4).is_empty());
+    assert_eq!(weighted.len(), 1);
+}
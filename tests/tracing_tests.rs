@@ -0,0 +1,130 @@
+#![cfg(feature = "tracing")]
+
+mod common;
+
+use lru_cache::Cache;
+use lru_cache::cache::persistent::PersistentCache;
+use lru_cache::cache::traits::{CacheStorage, PersistentStorage};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::num::NonZeroUsize;
+use std::sync::{Arc, Mutex};
+use tracing::field::{Field, Visit};
+use tracing::span::{Attributes, Id, Record};
+use tracing::{Event, Level, Metadata, Subscriber};
+
+type Fields = BTreeMap<String, String>;
+
+/// Abonné qui conserve le niveau et les champs de chaque événement
+#[derive(Clone, Default)]
+struct Collector(Arc<Mutex<Vec<(Level, Fields)>>>);
+
+impl Collector {
+    fn events(&self, message: &str) -> Vec<(Level, Fields)> {
+        self.0
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|(_, fields)| fields.get("message").map(String::as_str) == Some(message))
+            .cloned()
+            .collect()
+    }
+}
+
+struct FieldVisitor<'a>(&'a mut Fields);
+
+impl Visit for FieldVisitor<'_> {
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        self.0.insert(field.name().to_string(), format!("{:?}", value));
+    }
+}
+
+impl Subscriber for Collector {
+    fn enabled(&self, _: &Metadata<'_>) -> bool {
+        true
+    }
+
+    fn new_span(&self, _: &Attributes<'_>) -> Id {
+        Id::from_u64(1)
+    }
+
+    fn record(&self, _: &Id, _: &Record<'_>) {}
+
+    fn record_follows_from(&self, _: &Id, _: &Id) {}
+
+    fn event(&self, event: &Event<'_>) {
+        let mut fields = Fields::new();
+        event.record(&mut FieldVisitor(&mut fields));
+        self.0.lock().unwrap().push((*event.metadata().level(), fields));
+    }
+
+    fn enter(&self, _: &Id) {}
+
+    fn exit(&self, _: &Id) {}
+}
+
+#[test]
+fn test_tracing_events() {
+    let collector = Collector::default();
+    tracing::subscriber::with_default(collector.clone(), || {
+        let mut cache = Cache::new(2);
+        cache.put(String::from("A"), 1);
+        cache.put(String::from("B"), 2);
+        cache.get(&String::from("A"));
+        cache.put(String::from("C"), 3);
+        cache.save_to_file("tracing_test.txt").unwrap();
+        Cache::<String, i32>::load_from_file("tracing_test.txt", NonZeroUsize::new(2).unwrap()).unwrap();
+        std::fs::remove_file("tracing_test.txt").unwrap();
+    });
+
+    let removed = collector.events("cache entry removed");
+    assert_eq!(removed.len(), 1);
+    let (level, fields) = &removed[0];
+    assert_eq!(*level, Level::DEBUG);
+    assert_eq!(fields["cause"], "Capacity");
+    assert_eq!(fields["len"], "1");
+    assert_eq!(fields["capacity"], "2");
+    assert_eq!(fields["key_hash"].len(), 16);
+
+    assert_eq!(collector.events("cache insert").len(), 5);
+    assert_eq!(collector.events("cache hit").len(), 1);
+    let saved = collector.events("cache file saved");
+    assert_eq!(saved.len(), 1);
+    assert_eq!(saved[0].1["entries"], "2");
+    assert_eq!(collector.events("cache file loaded").len(), 1);
+}
+
+#[test]
+fn test_capacity_mismatch_warning() {
+    let collector = Collector::default();
+    tracing::subscriber::with_default(collector.clone(), || {
+        let mut cache = Cache::new(3);
+        cache.put(String::from("A"), 1);
+        cache.save_to_file("tracing_mismatch.txt").unwrap();
+        Cache::<String, i32>::load_from_file("tracing_mismatch.txt", NonZeroUsize::new(3).unwrap()).unwrap();
+        Cache::<String, i32>::load_from_file("tracing_mismatch.txt", NonZeroUsize::new(1).unwrap()).unwrap();
+        std::fs::remove_file("tracing_mismatch.txt").unwrap();
+    });
+
+    let warnings = collector.events("cache file capacity mismatch");
+    assert_eq!(warnings.len(), 1);
+    assert_eq!(warnings[0].0, Level::WARN);
+    assert_eq!((warnings[0].1["stored"].as_str(), warnings[0].1["capacity"].as_str()), ("3", "1"));
+}
+
+#[test]
+fn test_failed_save_on_drop_is_a_warning() {
+    let blocker = common::temp_path("tracing_blocker");
+    std::fs::write(&blocker, "").unwrap();
+    let collector = Collector::default();
+    tracing::subscriber::with_default(collector.clone(), || {
+        let mut cache = PersistentCache::<String, i32>::open(blocker.join("cache.txt"), 1).unwrap();
+        cache.put(String::from("A"), 1);
+    });
+    std::fs::remove_file(&blocker).unwrap();
+
+    let warnings = collector.events("cache save on drop failed");
+    assert_eq!(warnings.len(), 1);
+    assert_eq!(warnings[0].0, Level::WARN);
+    assert!(warnings[0].1["path"].ends_with("cache.txt"));
+}
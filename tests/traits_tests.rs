@@ -0,0 +1,153 @@
+use lru_cache::Cache;
+use lru_cache::cache::arc_policy::ArcCache;
+use lru_cache::cache::clock::MockClock;
+use lru_cache::cache::clock_cache::ClockCache;
+use lru_cache::cache::concurrent::ConcurrentCache;
+use lru_cache::cache::lfu::LfuCache;
+use lru_cache::cache::slru::SlruCache;
+use lru_cache::cache::traits::{
+    CacheLen, CacheRemove, CacheStorage, CacheStorageExt, FullCache, GetOptions,
+};
+use lru_cache::cache::ttl::TtlMode;
+use lru_cache::cache::two_q::TwoQCache;
+use std::time::Duration;
+
+/// Implémentation tierce minimale : seulement `get` et `put`
+struct VecCache<K, V> {
+    entries: Vec<(K, V)>,
+}
+
+impl<K: PartialEq, V> CacheStorage<K, V> for VecCache<K, V> {
+    fn get(&mut self, key: &K) -> Option<&V> {
+        self.entries.iter().find(|(k, _)| k == key).map(|(_, v)| v)
+    }
+
+    fn put(&mut self, key: K, value: V) {
+        self.entries.retain(|(k, _)| *k != key);
+        self.entries.push((key, value));
+    }
+}
+
+fn fill<C: CacheStorage<u32, String>>(cache: &mut C) {
+    cache.put_all((0..3).map(|i| (i, format!("value_{}", i))));
+}
+
+fn drain_one<C: CacheStorage<u32, String> + CacheRemove<u32, String> + CacheLen>(cache: &mut C) -> usize {
+    cache.remove(&0);
+    cache.len()
+}
+
+fn keys<C: FullCache<u32, String>>(cache: &C) -> Vec<u32> {
+    cache.iter().map(|(k, _)| *k).collect()
+}
+
+#[test]
+fn test_minimal_implementor_works_with_generic_helpers() {
+    let mut cache = VecCache { entries: Vec::new() };
+    fill(&mut cache);
+
+    assert_eq!(cache.get_cloned(&1), Some(String::from("value_1")));
+    assert!(cache.contains(&2));
+    assert!(!cache.contains(&3));
+}
+
+#[test]
+fn test_cache_implements_optional_capabilities() {
+    let mut cache = Cache::new(3);
+    fill(&mut cache);
+    cache.get(&0);
+
+    assert_eq!(keys(&cache), vec![1, 2, 0]);
+    assert_eq!(drain_one(&mut cache), 2);
+    assert_eq!(keys(&cache), vec![1, 2]);
+    assert!(!cache.is_empty());
+}
+
+#[test]
+fn test_cache_storage_is_object_safe() {
+    let mut cache = Cache::new(2);
+    let storage: &mut dyn CacheStorage<&str, i32> = &mut cache;
+    storage.put("A", 1);
+    assert_eq!(storage.get_cloned(&"A"), Some(1));
+}
+
+/// Intergiciel générique : les lectures administratives passent par le même
+/// cache que le trafic réel
+fn middleware_read<C: CacheStorage<u32, u32>>(cache: &mut C, key: u32, admin: bool) -> Option<u32> {
+    let options = if admin { GetOptions::passive() } else { GetOptions::default() };
+    cache.get_with(&key, options).copied()
+}
+
+fn order<C: FullCache<u32, u32>>(cache: &C) -> Vec<u32> {
+    cache.iter().map(|(k, _)| *k).collect()
+}
+
+fn assert_admin_reads_keep_order<C: FullCache<u32, u32>>(mut cache: C) {
+    for key in 0..4 {
+        cache.put(key, key * 10);
+    }
+    let before = order(&cache);
+    for key in 0..4 {
+        assert_eq!(middleware_read(&mut cache, key, true), Some(key * 10));
+    }
+    assert_eq!(middleware_read(&mut cache, 99, true), None);
+    assert_eq!(order(&cache), before);
+}
+
+#[test]
+fn test_admin_reads_do_not_promote() {
+    assert_admin_reads_keep_order(Cache::new(4));
+    assert_admin_reads_keep_order(SlruCache::new(4, 2));
+    assert_admin_reads_keep_order(TwoQCache::new(4));
+    assert_admin_reads_keep_order(ArcCache::new(4));
+    assert_admin_reads_keep_order(ClockCache::new(4));
+    assert_admin_reads_keep_order(LfuCache::new(4));
+
+    let mut cache = Cache::new(2);
+    cache.put(1, 1);
+    cache.put(2, 2);
+    middleware_read(&mut cache, 1, false);
+    assert_eq!(order(&cache), vec![2, 1]);
+}
+
+#[test]
+fn test_admin_reads_leave_counters_untouched() {
+    let mut lfu = LfuCache::new(2);
+    lfu.put(1, 1);
+    middleware_read(&mut lfu, 1, true);
+    assert_eq!(lfu.frequency(&1), Some(1));
+
+    let mut clock = ClockCache::new(2);
+    clock.put(1, 1);
+    middleware_read(&mut clock, 1, true);
+    assert_eq!(clock.is_referenced(&1), Some(false));
+    middleware_read(&mut clock, 1, false);
+    assert_eq!(clock.is_referenced(&1), Some(true));
+}
+
+#[test]
+fn test_admin_reads_do_not_extend_ttl() {
+    let clock = MockClock::new();
+    let mut cache = Cache::with_ttl_and_clock(2, Duration::from_secs(10), TtlMode::AfterAccess, clock.clone());
+    cache.put(1, 1);
+    clock.advance(Duration::from_secs(6));
+    assert_eq!(middleware_read(&mut cache, 1, true), Some(1));
+    clock.advance(Duration::from_secs(6));
+    assert_eq!(middleware_read(&mut cache, 1, true), None);
+
+    cache.put(2, 2);
+    clock.advance(Duration::from_secs(6));
+    assert_eq!(middleware_read(&mut cache, 2, false), Some(2));
+    clock.advance(Duration::from_secs(6));
+    assert_eq!(middleware_read(&mut cache, 2, false), Some(2));
+}
+
+#[test]
+fn test_concurrent_admin_read() {
+    let cache = ConcurrentCache::new(2);
+    cache.put(1, 1);
+    cache.put(2, 2);
+    assert_eq!(cache.get_with(&1, GetOptions::passive()), Some(1));
+    cache.put(3, 3);
+    assert_eq!(cache.get(&1), None);
+}
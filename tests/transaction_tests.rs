@@ -0,0 +1,183 @@
+use lru_cache::Cache;
+use lru_cache::cache::clock::MockClock;
+use lru_cache::cache::listener::RemovalCause;
+use lru_cache::cache::traits::{CacheIterate, CacheLen, CacheStorage};
+use lru_cache::cache::ttl::TtlMode;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+type State = (Vec<(u32, String)>, usize, lru_cache::cache::stats::CacheStats);
+
+fn state(cache: &Cache<u32, String>) -> State {
+    let entries = cache.iter().map(|(key, value)| (*key, value.clone())).collect();
+    (entries, cache.total_weight(), cache.stats())
+}
+
+fn filled() -> Cache<u32, String> {
+    let mut cache = Cache::new(4);
+    for key in 1..=4 {
+        cache.put(key, key.to_string());
+    }
+    cache.get(&2);
+    cache.get(&9);
+    cache
+}
+
+/// Applique les mêmes opérations aux deux caches et compare les évictions
+fn assert_same_future(left: &mut Cache<u32, String>, right: &mut Cache<u32, String>) {
+    for key in [100, 3, 101, 102] {
+        left.put(key, String::new());
+        right.put(key, String::new());
+        assert_eq!(state(left), state(right));
+    }
+}
+
+#[test]
+fn test_rollback_restores_exact_state() {
+    let mut cache = filled();
+    let ids: Vec<_> = (1..=4).map(|key| cache.get_id(&key).unwrap()).collect();
+    let mut reference = filled();
+    for key in 1..=4 {
+        reference.get(&key);
+    }
+    let before = state(&cache);
+
+    let mut transaction = cache.transaction();
+    transaction.put(5, String::from("5"));
+    transaction.put(2, String::from("deux"));
+    assert!(transaction.remove(&4));
+    assert_eq!(transaction.get(&3).map(String::as_str), Some("3"));
+    transaction.put(7, String::from("7"));
+    transaction.put(8, String::from("8"));
+    assert_eq!(transaction.get(&1), None);
+    assert_eq!(transaction.peek(&2).map(String::as_str), Some("deux"));
+    transaction.rollback();
+
+    assert_eq!(state(&cache), before);
+    assert_eq!(state(&cache), state(&reference));
+    assert_eq!(cache.check_invariants(), Ok(()));
+    for id in ids {
+        assert!(cache.get_by_id(id).is_some());
+    }
+    reference.get(&1);
+    reference.get(&2);
+    reference.get(&3);
+    reference.get(&4);
+    assert_same_future(&mut cache, &mut reference);
+}
+
+#[test]
+fn test_drop_without_commit_rolls_back() {
+    let mut cache = filled();
+    let before = state(&cache);
+    {
+        let mut transaction = cache.transaction();
+        for key in 10..20 {
+            transaction.put(key, key.to_string());
+        }
+    }
+    assert_eq!(state(&cache), before);
+    assert_eq!(cache.check_invariants(), Ok(()));
+    assert_same_future(&mut cache, &mut filled());
+}
+
+#[test]
+fn test_commit_notifies_removals_once() {
+    let removed = Arc::new(Mutex::new(Vec::new()));
+    let mut cache = Cache::new(2);
+    let sink = Arc::clone(&removed);
+    cache.set_eviction_listener(move |key, value, cause| sink.lock().unwrap().push((key, value, cause)));
+    cache.put(1, String::from("1"));
+    cache.put(2, String::from("2"));
+
+    let mut transaction = cache.transaction();
+    transaction.put(3, String::from("3"));
+    transaction.put(2, String::from("deux"));
+    assert!(transaction.remove(&3));
+    assert!(removed.lock().unwrap().is_empty());
+    transaction.rollback();
+    assert!(removed.lock().unwrap().is_empty());
+
+    let mut transaction = cache.transaction();
+    transaction.put(3, String::from("3"));
+    transaction.put(2, String::from("deux"));
+    transaction.commit();
+    assert_eq!(
+        *removed.lock().unwrap(),
+        vec![
+            (1, String::from("1"), RemovalCause::Capacity),
+            (2, String::from("2"), RemovalCause::Replaced),
+        ]
+    );
+    assert_eq!(cache.len(), 2);
+    assert_eq!(cache.peek(&2).map(String::as_str), Some("deux"));
+}
+
+#[test]
+fn test_rollback_restores_deadlines_and_expired_entries() {
+    let clock = MockClock::new();
+    let mut cache = Cache::with_ttl_and_clock(3, Duration::from_secs(60), TtlMode::AfterAccess, clock.clone());
+    cache.put(1, String::from("1"));
+    clock.advance(Duration::from_secs(30));
+    cache.put(2, String::from("2"));
+    clock.advance(Duration::from_secs(30));
+    let before = state(&cache);
+    let remaining = cache.ttl_remaining(&2);
+
+    let mut transaction = cache.transaction();
+    assert_eq!(transaction.get(&1), None);
+    assert!(transaction.get(&2).is_some());
+    transaction.put(3, String::from("3"));
+    transaction.rollback();
+
+    assert_eq!(state(&cache), before);
+    assert_eq!(cache.ttl_remaining(&2), remaining);
+    assert_eq!(cache.expired_len(), 1);
+    assert_eq!(cache.check_invariants(), Ok(()));
+    clock.advance(Duration::from_secs(30));
+    assert_eq!(cache.purge_expired(), 2);
+}
+
+#[test]
+fn test_rollback_keeps_expired_entries_in_their_eviction_order() {
+    let evict_after_rollback = |rollback: bool| {
+        let clock = MockClock::new();
+        let mut cache = Cache::with_clock(5, clock.clone());
+        let removed = Arc::new(Mutex::new(Vec::new()));
+        let sink = Arc::clone(&removed);
+        cache.set_eviction_listener(move |key, _, _| sink.lock().unwrap().push(key));
+        for key in 1..=5u32 {
+            cache.put_with_ttl(key, key.to_string(), Duration::from_secs(key.into()));
+        }
+        clock.advance(Duration::from_secs(10));
+        if rollback {
+            let mut transaction = cache.transaction();
+            transaction.put(10, String::new());
+            transaction.put(11, String::new());
+            transaction.rollback();
+            assert_eq!(cache.check_invariants(), Ok(()));
+        }
+        for key in 20..25 {
+            cache.put(key, String::new());
+        }
+        drop(cache);
+        Arc::try_unwrap(removed).unwrap().into_inner().unwrap()
+    };
+    assert_eq!(evict_after_rollback(true), evict_after_rollback(false));
+}
+
+#[test]
+fn test_rollback_restores_weights() {
+    let mut cache = Cache::with_weigher(10, |_: &u32, value: &String| value.len());
+    cache.put(1, String::from("aaaa"));
+    cache.put(2, String::from("bbbb"));
+    let before = state(&cache);
+
+    let mut transaction = cache.transaction();
+    transaction.put(1, String::from("a"));
+    transaction.put(3, String::from("cccccccc"));
+    transaction.rollback();
+    assert_eq!(state(&cache), before);
+    assert_eq!(cache.total_weight(), 8);
+    assert_eq!(cache.check_invariants(), Ok(()));
+}
@@ -0,0 +1,358 @@
+use lru_cache::Cache;
+use lru_cache::cache::builder::CacheBuilder;
+use lru_cache::cache::clock::MockClock;
+use lru_cache::cache::listener::RemovalCause;
+use lru_cache::cache::traits::{CacheLen, CacheStorage};
+use lru_cache::cache::ttl::TtlMode;
+use std::num::NonZeroUsize;
+use std::time::Duration;
+
+const NANO: Duration = Duration::from_nanos(1);
+
+#[test]
+fn test_put_with_ttl_expires_entry() {
+    let clock = MockClock::new();
+    let mut cache = Cache::with_clock(3, clock.clone());
+    cache.put_with_ttl("A", 1, Duration::from_secs(20));
+    cache.put("B", 2);
+
+    assert_eq!(cache.get(&"A"), Some(&1));
+    clock.advance(Duration::from_secs(40));
+
+    assert_eq!(cache.peek(&"A"), None);
+    assert!(!cache.contains_key(&"A"));
+    assert_eq!(cache.len(), 2);
+    assert_eq!(cache.get(&"A"), None);
+    assert_eq!(cache.len(), 1);
+    assert_eq!(cache.get(&"B"), Some(&2));
+}
+
+#[test]
+fn test_entry_expires_exactly_at_deadline() {
+    let clock = MockClock::new();
+    let mut cache = Cache::with_clock(2, clock.clone());
+    cache.put_with_ttl("A", 1, Duration::from_secs(10));
+
+    clock.advance(Duration::from_secs(10) - NANO);
+    assert_eq!(cache.peek(&"A"), Some(&1));
+    clock.advance(NANO);
+    assert_eq!(cache.peek(&"A"), None);
+}
+
+#[test]
+fn test_overwrite_resets_deadline() {
+    let clock = MockClock::new();
+    let mut cache = Cache::with_clock(2, clock.clone());
+    cache.put_with_ttl("A", 1, Duration::from_secs(20));
+    clock.advance(Duration::from_secs(15));
+    cache.put_with_ttl("A", 2, Duration::from_secs(20));
+    clock.advance(Duration::from_secs(15));
+
+    assert_eq!(cache.get(&"A"), Some(&2));
+}
+
+#[test]
+fn test_plain_put_makes_entry_permanent() {
+    let clock = MockClock::new();
+    let mut cache = Cache::with_clock(2, clock.clone());
+    cache.put_with_ttl("A", 1, Duration::from_secs(20));
+    cache.put("A", 2);
+    clock.advance(Duration::from_secs(3600));
+
+    assert_eq!(cache.get(&"A"), Some(&2));
+}
+
+#[test]
+fn test_default_ttl_after_write() {
+    let clock = MockClock::new();
+    let mut cache = Cache::with_ttl_and_clock(3, Duration::from_secs(50), TtlMode::AfterWrite, clock.clone());
+    cache.put("A", 1);
+    clock.advance(Duration::from_secs(30));
+    assert_eq!(cache.get(&"A"), Some(&1));
+    clock.advance(Duration::from_secs(20));
+
+    assert_eq!(cache.get(&"A"), None);
+}
+
+#[test]
+fn test_default_ttl_after_access() {
+    let clock = MockClock::new();
+    let mut cache = Cache::with_ttl_and_clock(3, Duration::from_secs(60), TtlMode::AfterAccess, clock.clone());
+    cache.put("A", 1);
+    for _ in 0..3 {
+        clock.advance(Duration::from_secs(59));
+        assert_eq!(cache.get(&"A"), Some(&1));
+    }
+    clock.advance(Duration::from_secs(60));
+
+    assert_eq!(cache.peek(&"A"), None);
+    assert_eq!(cache.get(&"A"), None);
+}
+
+#[test]
+fn test_entry_expires_between_two_gets() {
+    let clock = MockClock::new();
+    let mut cache = Cache::with_ttl_and_clock(3, Duration::from_secs(10), TtlMode::AfterWrite, clock.clone());
+    cache.put("A", 1);
+
+    assert_eq!(cache.get(&"A"), Some(&1));
+    clock.advance(Duration::from_secs(10));
+    assert_eq!(cache.get(&"A"), None);
+}
+
+#[test]
+fn test_expired_entries_are_evicted_first() {
+    let clock = MockClock::new();
+    let mut cache = Cache::with_clock(3, clock.clone());
+    cache.put("A", 1);
+    cache.put_with_ttl("B", 2, Duration::from_secs(20));
+    cache.put("C", 3);
+    clock.advance(Duration::from_secs(40));
+
+    cache.put("D", 4);
+
+    assert_eq!(cache.get(&"A"), Some(&1));
+    assert_eq!(cache.get(&"C"), Some(&3));
+    assert_eq!(cache.get(&"D"), Some(&4));
+    assert_eq!(cache.len(), 3);
+}
+
+#[test]
+fn test_purge_expired_keeps_survivor_order() {
+    let clock = MockClock::new();
+    let mut cache = Cache::with_clock(6, clock.clone());
+    for i in 0..6 {
+        if i % 2 == 0 {
+            cache.put_with_ttl(i, i, Duration::from_secs(20));
+        } else {
+            cache.put(i, i);
+        }
+    }
+    clock.advance(Duration::from_secs(40));
+
+    assert_eq!(cache.expired_len(), 3);
+    assert_eq!(cache.purge_expired(), 3);
+    assert_eq!(cache.len(), 3);
+    assert_eq!(cache.expired_len(), 0);
+    assert_eq!(cache.purge_expired(), 0);
+
+    for i in 10..13 {
+        cache.put(i, i);
+    }
+    cache.put(13, 13);
+    assert_eq!(cache.peek(&1), None);
+    cache.put(14, 14);
+    assert_eq!(cache.peek(&3), None);
+    assert_eq!(cache.peek(&5), Some(&5));
+}
+
+#[test]
+fn test_clock_going_backwards_does_not_extend_lifetime() {
+    let clock = MockClock::new();
+    let mut cache = Cache::with_clock(3, clock.clone());
+    cache.put_with_ttl("A", 1, Duration::from_secs(10));
+    clock.advance(Duration::from_secs(5));
+    clock.rewind(Duration::from_secs(3600));
+
+    assert_eq!(cache.get(&"A"), Some(&1));
+    clock.advance(Duration::from_secs(10));
+    assert_eq!(cache.get(&"A"), None);
+}
+
+#[test]
+fn test_clock_going_backwards_does_not_panic() {
+    let clock = MockClock::new();
+    let mut cache = Cache::with_ttl_and_clock(4, Duration::from_secs(30), TtlMode::AfterAccess, clock.clone());
+    cache.put("A", 1);
+    cache.put_with_ttl("B", 2, Duration::from_secs(5));
+    clock.rewind(Duration::from_secs(120));
+    cache.put("C", 3);
+
+    assert_eq!(cache.get(&"A"), Some(&1));
+    assert_eq!(cache.expired_len(), 0);
+    assert_eq!(cache.purge_expired(), 0);
+    clock.advance(Duration::from_secs(6));
+    assert_eq!(cache.purge_expired(), 1);
+    assert_eq!(cache.peek(&"B"), None);
+    clock.advance(Duration::from_secs(30));
+    assert_eq!(cache.purge_expired(), 2);
+    assert_eq!(cache.len(), 0);
+}
+
+#[test]
+fn test_max_idle_expires_unread_entry() {
+    let clock = MockClock::new();
+    let mut cache = CacheBuilder::new()
+        .capacity(NonZeroUsize::new(3).unwrap())
+        .max_idle(Duration::from_secs(10))
+        .clock(clock.clone())
+        .build()
+        .unwrap();
+    cache.put("read", 1);
+    cache.put("idle", 2);
+
+    clock.advance(Duration::from_secs(10) - NANO);
+    assert_eq!(cache.get(&"read"), Some(&1));
+    assert_eq!(cache.peek(&"idle"), Some(&2));
+    clock.advance(NANO);
+    assert_eq!(cache.peek(&"idle"), None);
+    assert_eq!(cache.get(&"idle"), None);
+
+    clock.advance(Duration::from_secs(9));
+    assert_eq!(cache.get(&"read"), Some(&1));
+    clock.advance(Duration::from_secs(10));
+    assert_eq!(cache.get(&"read"), None);
+    assert!(cache.is_empty());
+}
+
+#[test]
+fn test_idle_entry_is_evicted_as_expired() {
+    let clock = MockClock::new();
+    let (sender, removed) = std::sync::mpsc::channel();
+    let mut cache = CacheBuilder::new()
+        .capacity(NonZeroUsize::new(2).unwrap())
+        .max_idle(Duration::from_secs(10))
+        .clock(clock.clone())
+        .eviction_listener(move |key, _, cause| sender.send((key, cause)).unwrap())
+        .build()
+        .unwrap();
+    cache.put("A", 1);
+    cache.put("B", 2);
+    clock.advance(Duration::from_secs(6));
+    cache.get(&"A");
+    clock.advance(Duration::from_secs(5));
+    cache.put("C", 3);
+    assert_eq!(removed.try_recv(), Ok(("B", RemovalCause::Expired)));
+    assert_eq!(cache.peek(&"A"), Some(&1));
+    assert_eq!(cache.expired_len(), 0);
+}
+
+#[test]
+fn test_ttl_and_max_idle_expire_at_earliest_deadline() {
+    let clock = MockClock::new();
+    let mut cache = CacheBuilder::new()
+        .capacity(NonZeroUsize::new(3).unwrap())
+        .time_to_live(Duration::from_secs(30))
+        .max_idle(Duration::from_secs(10))
+        .clock(clock.clone())
+        .build()
+        .unwrap();
+    cache.put("busy", 1);
+    cache.put("quiet", 2);
+    cache.put_with_ttl("short", 3, Duration::from_secs(5));
+
+    clock.advance(Duration::from_secs(5));
+    assert_eq!(cache.peek(&"short"), None);
+
+    // Lue juste avant son échéance d'inactivité (10 s), l'entrée survit
+    // au-delà, jusqu'à la fin de sa durée de vie (30 s)
+    clock.advance(Duration::from_secs(5) - NANO);
+    assert_eq!(cache.get(&"busy"), Some(&1));
+    assert_eq!(cache.peek(&"quiet"), Some(&2));
+    clock.advance(NANO);
+    assert_eq!(cache.peek(&"quiet"), None);
+    assert_eq!(cache.peek(&"busy"), Some(&1));
+    clock.advance(Duration::from_secs(8));
+    assert_eq!(cache.get(&"busy"), Some(&1));
+    clock.advance(Duration::from_secs(9));
+    assert_eq!(cache.get(&"busy"), Some(&1));
+    clock.advance(Duration::from_secs(3) - NANO);
+    assert_eq!(cache.peek(&"busy"), Some(&1));
+    clock.advance(NANO);
+    assert_eq!(cache.get(&"busy"), None);
+}
+
+#[test]
+fn test_set_and_extend_ttl_move_the_deadline() {
+    let clock = MockClock::new();
+    let mut cache = Cache::with_clock(3, clock.clone());
+    cache.put_with_ttl("A", 1, Duration::from_secs(10));
+    cache.put("B", 2);
+
+    clock.advance(Duration::from_secs(4));
+    assert_eq!(cache.ttl_remaining(&"A"), Some(Duration::from_secs(6)));
+    assert!(cache.extend_ttl(&"A", Duration::from_secs(5)));
+    assert_eq!(cache.ttl_remaining(&"A"), Some(Duration::from_secs(11)));
+    assert!(cache.set_ttl(&"A", Duration::from_secs(3)));
+    assert_eq!(cache.ttl_remaining(&"A"), Some(Duration::from_secs(3)));
+    clock.advance(Duration::from_secs(3) - NANO);
+    assert_eq!(cache.get(&"A"), Some(&1));
+    clock.advance(NANO);
+    assert_eq!(cache.get(&"A"), None);
+
+    assert!(cache.extend_ttl(&"B", Duration::from_secs(5)));
+    assert_eq!(cache.ttl_remaining(&"B"), None);
+    assert!(cache.set_ttl(&"B", Duration::from_secs(2)));
+    clock.advance(Duration::from_secs(2));
+    assert_eq!(cache.peek(&"B"), None);
+}
+
+#[test]
+fn test_extended_deadline_survives_later_reads() {
+    let clock = MockClock::new();
+    let mut cache = Cache::with_clock(2, clock.clone());
+    cache.put_with_ttl("A", 1, Duration::from_secs(10));
+    assert!(cache.extend_ttl(&"A", Duration::from_secs(20)));
+    clock.advance(Duration::from_secs(15));
+    assert_eq!(cache.get(&"A"), Some(&1));
+    assert_eq!(cache.ttl_remaining(&"A"), Some(Duration::from_secs(15)));
+}
+
+#[test]
+fn test_ttl_changes_do_not_resurrect_expired_entries() {
+    let clock = MockClock::new();
+    let mut cache = Cache::with_clock(2, clock.clone());
+    cache.put_with_ttl("A", 1, Duration::from_secs(10));
+    clock.advance(Duration::from_secs(10));
+    assert_eq!(cache.ttl_remaining(&"A"), None);
+    assert!(!cache.extend_ttl(&"A", Duration::from_secs(60)));
+    assert!(!cache.set_ttl(&"A", Duration::from_secs(60)));
+    assert_eq!(cache.len(), 0);
+    assert!(!cache.set_ttl(&"Z", Duration::from_secs(60)));
+    assert_eq!(cache.ttl_remaining(&"Z"), None);
+}
+
+#[test]
+fn test_iter_mut_does_not_refresh_idle_deadline() {
+    let clock = MockClock::new();
+    let mut cache = CacheBuilder::new()
+        .capacity(NonZeroUsize::new(3).unwrap())
+        .max_idle(Duration::from_secs(10))
+        .clock(clock.clone())
+        .build()
+        .unwrap();
+    cache.put("a", 1);
+
+    clock.advance(Duration::from_secs(5));
+    for (_, value) in cache.iter_mut() {
+        *value = 2;
+    }
+    assert_eq!(cache.peek(&"a"), Some(&2));
+    clock.advance(Duration::from_secs(5));
+    assert_eq!(cache.get(&"a"), None);
+}
+
+#[test]
+fn test_eviction_with_ttl_does_not_scan_entries() {
+    const CAPACITY: u32 = 20_000;
+    let clock = MockClock::new();
+    let ttl = Duration::from_secs(60);
+    let mut cache = Cache::with_ttl_and_clock(CAPACITY as usize, ttl, TtlMode::AfterWrite, clock.clone());
+    let started = std::time::Instant::now();
+
+    for key in 0..3 * CAPACITY {
+        cache.put(key, key);
+    }
+    clock.advance(Duration::from_secs(30));
+    cache.put_with_ttl(3 * CAPACITY, 0, Duration::from_secs(3600));
+    clock.advance(Duration::from_secs(30));
+    for key in 1..CAPACITY {
+        cache.put(4 * CAPACITY + key, key);
+    }
+
+    assert!(started.elapsed() < Duration::from_secs(5));
+    assert_eq!(cache.len(), CAPACITY as usize);
+    assert_eq!(cache.peek(&(3 * CAPACITY)), Some(&0));
+    assert_eq!(cache.expired_len(), 0);
+    assert_eq!(cache.check_invariants(), Ok(()));
+}
@@ -0,0 +1,77 @@
+use lru_cache::Cache;
+use lru_cache::cache::clock::MockClock;
+use lru_cache::cache::error::CasError;
+use lru_cache::cache::traits::{CacheRemove, CacheStorage};
+use std::time::Duration;
+
+#[test]
+fn test_versions_count_writes() {
+    let mut cache = Cache::new(2);
+    assert_eq!(cache.put_versioned("a", 1), 1);
+    assert_eq!(cache.put_versioned("a", 2), 2);
+    cache.put("a", 3);
+    assert_eq!(cache.get_versioned(&"a"), Some((&3, 3)));
+    assert_eq!(cache.get_versioned(&"b"), None);
+    assert_eq!(cache.stats().hits, 1);
+    assert_eq!(cache.stats().misses, 1);
+}
+
+#[test]
+fn test_successful_cas_chain() {
+    let mut cache = Cache::new(4);
+    let mut version = 0;
+    for total in 1..=5 {
+        version = cache.compare_and_put("sum", total, version).unwrap();
+        assert_eq!(version, total);
+    }
+    assert_eq!(cache.get_versioned(&"sum"), Some((&5, 5)));
+}
+
+#[test]
+fn test_cas_failure_leaves_entry_untouched() {
+    let mut cache = Cache::new(2);
+    cache.put("a", 1);
+    cache.put("b", 2);
+    let read = cache.get_versioned(&"a").map(|(_, version)| version).unwrap();
+    cache.put("a", 10);
+
+    assert_eq!(
+        cache.compare_and_put("a", 100, read),
+        Err(CasError {
+            value: 100,
+            current_version: 2,
+        })
+    );
+    assert_eq!(cache.compare_and_put("b", 20, 0).unwrap_err().current_version, 1);
+    assert_eq!(cache.compare_and_put("c", 3, 1).unwrap_err().current_version, 0);
+    assert_eq!(cache.peek(&"a"), Some(&10));
+    // L'échec n'a pas promu « b », qui reste la prochaine évincée
+    cache.put("c", 3);
+    assert_eq!(cache.peek(&"b"), None);
+    assert_eq!(cache.peek(&"a"), Some(&10));
+}
+
+#[test]
+fn test_reinsert_after_eviction_restarts_version() {
+    let clock = MockClock::new();
+    let mut cache = Cache::with_clock(1, clock.clone());
+    cache.put("a", 1);
+    assert_eq!(cache.put_versioned("a", 2), 2);
+    let stale = 2;
+
+    cache.put("b", 1);
+    assert_eq!(cache.put_versioned("a", 3), 1);
+    assert!(cache.compare_and_put("a", 4, stale).is_err());
+    assert_eq!(cache.compare_and_put("a", 4, 1), Ok(2));
+    // La version lue avant l'éviction est réatteinte : la comparaison ne
+    // voit pas l'éviction intermédiaire
+    assert_eq!(cache.compare_and_put("a", 5, stale), Ok(3));
+
+    cache.remove(&"a");
+    assert_eq!(cache.compare_and_put("a", 6, 0), Ok(1));
+
+    cache.put_with_ttl("a", 7, Duration::from_secs(1));
+    assert_eq!(cache.get_versioned(&"a"), Some((&7, 2)));
+    clock.advance(Duration::from_secs(1));
+    assert_eq!(cache.compare_and_put("a", 8, 0), Ok(1));
+}
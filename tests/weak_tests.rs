@@ -0,0 +1,68 @@
+use lru_cache::cache::traits::CacheLen;
+use lru_cache::cache::weak::WeakValueCache;
+use std::sync::Arc;
+
+#[test]
+fn test_dropped_values_miss() {
+    let mut cache = WeakValueCache::new(4);
+    let kept = Arc::new(String::from("kept"));
+    let dropped = Arc::new(String::from("dropped"));
+    cache.put(1, Arc::clone(&kept));
+    cache.put(2, Arc::clone(&dropped));
+    assert_eq!(cache.get(&2).as_deref().map(String::as_str), Some("dropped"));
+
+    drop(dropped);
+    assert_eq!(cache.len(), 2);
+    assert_eq!(cache.get(&2), None);
+    assert_eq!(cache.len(), 1);
+    assert!(Arc::ptr_eq(&cache.get(&1).unwrap(), &kept));
+    // Le cache ne garde pas la valeur en vie
+    assert_eq!(Arc::strong_count(&kept), 1);
+}
+
+#[test]
+fn test_purge_dead_counts_removed_entries() {
+    let mut cache = WeakValueCache::new(8);
+    let values: Vec<Arc<u32>> = (0..6).map(Arc::new).collect();
+    for (key, value) in values.iter().enumerate() {
+        cache.put(key, Arc::clone(value));
+    }
+    let kept: Vec<Arc<u32>> = values.into_iter().filter(|value| **value % 3 == 0).collect();
+    assert_eq!(cache.purge_dead(), 4);
+    assert_eq!(cache.len(), 2);
+    assert_eq!(cache.purge_dead(), 0);
+    assert_eq!(cache.get(&3).as_deref(), Some(&3));
+    assert_eq!(cache.remove(&0).as_deref(), Some(&0));
+    drop(kept);
+    assert_eq!(cache.remove(&3), None);
+    assert!(cache.is_empty());
+}
+
+#[test]
+fn test_full_cache_evicts_dead_entries_first() {
+    let mut cache = WeakValueCache::new(3);
+    let a = Arc::new('a');
+    let b = Arc::new('b');
+    let c = Arc::new('c');
+    cache.put("a", Arc::clone(&a));
+    cache.put("b", Arc::clone(&b));
+    cache.put("c", Arc::clone(&c));
+    drop(b);
+
+    // « b » est morte : elle part avant « a », la moins récemment utilisée
+    let d = Arc::new('d');
+    cache.put("d", Arc::clone(&d));
+    assert_eq!(cache.len(), 3);
+    assert!(cache.get(&"a").is_some());
+    assert!(cache.get(&"c").is_some());
+
+    // Sans entrée morte, l'éviction reste LRU : « d » est la plus ancienne
+    let e = Arc::new('e');
+    cache.put("e", Arc::clone(&e));
+    assert_eq!(cache.get(&"d"), None);
+    assert_eq!(cache.len(), 3);
+    assert!(cache.get(&"a").is_some());
+
+    cache.put("a", Arc::clone(&e));
+    assert_eq!(cache.get(&"a").as_deref(), Some(&'e'));
+}
@@ -0,0 +1,67 @@
+use lru_cache::Cache;
+use lru_cache::cache::traits::{CacheIterate, CacheStorage};
+
+fn by_len(max_total_weight: usize) -> Cache<&'static str, String> {
+    Cache::with_weigher(max_total_weight, |_: &&str, value: &String| value.len())
+}
+
+fn keys(cache: &Cache<&'static str, String>) -> Vec<&'static str> {
+    cache.iter().map(|(key, _)| *key).collect()
+}
+
+#[test]
+fn test_one_put_evicts_several_entries() {
+    let mut cache = by_len(10);
+    cache.put("a", "xxx".into());
+    cache.put("b", "xxx".into());
+    cache.put("c", "xxx".into());
+    assert_eq!(cache.total_weight(), 9);
+
+    cache.put("d", "xxxxxxx".into());
+    assert_eq!(keys(&cache), vec!["c", "d"]);
+    assert_eq!(cache.total_weight(), 10);
+}
+
+#[test]
+fn test_replacing_a_value_updates_the_weight() {
+    let mut cache = by_len(10);
+    cache.put("a", "xxx".into());
+    cache.put("b", "xxx".into());
+    cache.put("a", "x".into());
+    assert_eq!(cache.total_weight(), 4);
+
+    cache.put("b", "xxxxxxxx".into());
+    assert_eq!(keys(&cache), vec!["a", "b"]);
+    assert_eq!(cache.total_weight(), 9);
+
+    cache.put("a", "xxxx".into());
+    assert_eq!(keys(&cache), vec!["a"]);
+    assert_eq!(cache.total_weight(), 4);
+}
+
+#[test]
+fn test_entry_heavier_than_the_budget_is_rejected() {
+    let mut cache = by_len(5);
+    cache.put("a", "xx".into());
+    cache.put("b", "xx".into());
+
+    cache.put("c", "xxxxxx".into());
+    assert_eq!(keys(&cache), vec!["a", "b"]);
+    assert_eq!(cache.get(&"c"), None);
+
+    cache.put("a", "xxxxxx".into());
+    assert_eq!(keys(&cache), vec!["b"]);
+    assert_eq!(cache.total_weight(), 2);
+
+    cache.put("c", "xxxxx".into());
+    assert_eq!(keys(&cache), vec!["c"]);
+}
+
+#[test]
+fn test_unweighted_total_weight_is_len() {
+    let mut cache = Cache::new(2);
+    cache.put(1, 1);
+    cache.put(2, 2);
+    cache.put(3, 3);
+    assert_eq!(cache.total_weight(), 2);
+}
@@ -0,0 +1,210 @@
+use lru_cache::Cache;
+use lru_cache::cache::builder::CacheBuilder;
+use lru_cache::cache::clock::MockClock;
+use lru_cache::cache::error::CacheError;
+use lru_cache::cache::listener::RemovalCause;
+use lru_cache::cache::traits::{CacheLen, CacheRemove, CacheStorage};
+use lru_cache::cache::ttl::TtlMode;
+use std::collections::BTreeSet;
+use std::num::NonZeroUsize;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+type Purged = Arc<Mutex<Vec<u32>>>;
+
+/// Cache dont les entrées retirées à l'expiration sont relevées par
+/// l'écouteur
+fn watched_cache(capacity: usize, clock: &MockClock) -> (Cache<u32, u32>, Purged) {
+    let purged = Purged::default();
+    let mut cache = Cache::with_clock(capacity, clock.clone());
+    let sink = Arc::clone(&purged);
+    cache.set_eviction_listener(move |key, _, cause| {
+        if cause == RemovalCause::Expired {
+            sink.lock().unwrap().push(key);
+        }
+    });
+    (cache, purged)
+}
+
+fn take(purged: &Purged) -> BTreeSet<u32> {
+    purged.lock().unwrap().drain(..).collect()
+}
+
+/// Échéance en secondes de la clé `key`, répartie sur environ cinq heures
+fn deadline_secs(key: u32) -> u64 {
+    u64::from(key) * 7919 % 18_000 + 1
+}
+
+#[test]
+fn test_purge_expires_exactly_due_entries() {
+    const ENTRIES: u32 = 2_000;
+    let clock = MockClock::new();
+    let (mut cache, purged) = watched_cache(ENTRIES as usize, &clock);
+    for key in 0..ENTRIES {
+        cache.put_with_ttl(key, key, Duration::from_secs(deadline_secs(key)));
+    }
+
+    let mut elapsed = 0;
+    for jump in [0, 1, 59, 3_540, 1, 17, 600, 4_000, 2, 3_600, 7_200] {
+        elapsed += jump;
+        clock.advance(Duration::from_secs(jump));
+        let expected: BTreeSet<u32> = (0..ENTRIES)
+            .filter(|&key| (elapsed - jump + 1..=elapsed).contains(&deadline_secs(key)))
+            .collect();
+        assert_eq!(cache.purge_expired(), expected.len(), "après {} s", elapsed);
+        assert_eq!(take(&purged), expected, "après {} s", elapsed);
+        let alive = (0..ENTRIES).filter(|&key| deadline_secs(key) > elapsed).count();
+        assert_eq!(cache.len(), alive);
+        assert_eq!(cache.check_invariants(), Ok(()));
+    }
+    assert!(cache.is_empty());
+}
+
+#[test]
+fn test_purge_is_exact_within_a_tick() {
+    let clock = MockClock::new();
+    let mut cache = CacheBuilder::new()
+        .capacity(NonZeroUsize::new(4).unwrap())
+        .ttl_granularity(Duration::from_secs(60))
+        .clock(clock.clone())
+        .build()
+        .unwrap();
+    cache.put_with_ttl("a", 1, Duration::from_secs(10));
+    cache.put_with_ttl("b", 2, Duration::from_secs(20));
+    cache.put_with_ttl("c", 3, Duration::from_secs(90));
+
+    clock.advance(Duration::from_secs(10));
+    assert_eq!(cache.purge_expired(), 1);
+    assert_eq!(cache.peek(&"b"), Some(&2));
+    clock.advance(Duration::from_secs(9));
+    assert_eq!(cache.purge_expired(), 0);
+    clock.advance(Duration::from_secs(1));
+    assert_eq!(cache.purge_expired(), 1);
+    clock.advance(Duration::from_secs(69));
+    assert_eq!(cache.purge_expired(), 0);
+    clock.advance(Duration::from_secs(1));
+    assert_eq!(cache.purge_expired(), 1);
+    assert_eq!(cache.check_invariants(), Ok(()));
+}
+
+#[test]
+fn test_ttl_changes_reslot_entries() {
+    let clock = MockClock::new();
+    let (mut cache, purged) = watched_cache(4, &clock);
+    let hour = Duration::from_secs(3_600);
+    cache.put_with_ttl(1, 1, hour);
+    cache.put_with_ttl(2, 2, hour);
+    cache.put_with_ttl(3, 3, hour);
+    cache.put(4, 4);
+
+    assert!(cache.extend_ttl(&1, hour));
+    assert!(cache.set_ttl(&2, Duration::from_secs(60)));
+    assert!(cache.set_ttl(&4, hour * 3));
+
+    clock.advance(Duration::from_secs(60));
+    assert_eq!(cache.purge_expired(), 1);
+    assert_eq!(take(&purged), BTreeSet::from([2]));
+    clock.advance(hour);
+    assert_eq!(cache.purge_expired(), 1);
+    assert_eq!(take(&purged), BTreeSet::from([3]));
+    clock.advance(hour);
+    assert_eq!(cache.purge_expired(), 1);
+    assert_eq!(take(&purged), BTreeSet::from([1]));
+    clock.advance(hour);
+    assert_eq!(cache.purge_expired(), 1);
+    assert_eq!(take(&purged), BTreeSet::from([4]));
+    assert_eq!(cache.check_invariants(), Ok(()));
+}
+
+#[test]
+fn test_reads_reslot_after_access_entries() {
+    let clock = MockClock::new();
+    let mut cache = Cache::with_ttl_and_clock(3, Duration::from_secs(600), TtlMode::AfterAccess, clock.clone());
+    cache.put("read", 1);
+    cache.put("idle", 2);
+    for _ in 0..5 {
+        clock.advance(Duration::from_secs(300));
+        cache.get(&"read");
+        cache.purge_expired();
+    }
+    assert!(cache.contains_key(&"read"));
+    assert!(!cache.contains_key(&"idle"));
+    assert_eq!(cache.len(), 1);
+    clock.advance(Duration::from_secs(600));
+    assert_eq!(cache.purge_expired(), 1);
+    assert_eq!(cache.check_invariants(), Ok(()));
+}
+
+#[test]
+fn test_removed_and_replaced_entries_leave_the_wheel() {
+    let clock = MockClock::new();
+    let (mut cache, purged) = watched_cache(2, &clock);
+    cache.put_with_ttl(1, 1, Duration::from_secs(10));
+    cache.put_with_ttl(2, 2, Duration::from_secs(10));
+    cache.put(1, 10);
+    cache.put_with_ttl(3, 3, Duration::from_secs(20));
+    assert_eq!(cache.check_invariants(), Ok(()));
+
+    clock.advance(Duration::from_secs(30));
+    assert_eq!(cache.purge_expired(), 1);
+    assert_eq!(take(&purged), BTreeSet::from([3]));
+    assert_eq!(cache.peek(&1), Some(&10));
+    assert_eq!(cache.check_invariants(), Ok(()));
+}
+
+#[test]
+fn test_granularity_change_keeps_deadlines() {
+    let clock = MockClock::new();
+    let mut cache = Cache::with_clock(10, clock.clone());
+    for key in 1..=5u64 {
+        cache.put_with_ttl(key, key, Duration::from_secs(key * 100));
+    }
+    clock.advance(Duration::from_secs(150));
+    assert_eq!(cache.purge_expired(), 1);
+    cache.set_ttl_granularity(Duration::from_secs(7));
+    assert_eq!(cache.check_invariants(), Ok(()));
+    clock.advance(Duration::from_secs(50));
+    assert_eq!(cache.purge_expired(), 1);
+    clock.advance(Duration::from_secs(10_000));
+    assert_eq!(cache.purge_expired(), 3);
+
+    let built = CacheBuilder::<u32, u32>::new()
+        .capacity(NonZeroUsize::new(1).unwrap())
+        .ttl_granularity(Duration::ZERO)
+        .build();
+    assert_eq!(built.unwrap_err(), CacheError::ZeroGranularity);
+}
+
+#[test]
+fn test_immortal_and_far_deadlines() {
+    let clock = MockClock::new();
+    let mut cache = Cache::with_clock(3, clock.clone());
+    cache.put_with_ttl("forever", 1, Duration::MAX);
+    cache.put_with_ttl("decade", 2, Duration::from_secs(10 * 365 * 86_400));
+    cache.put_with_ttl("soon", 3, Duration::from_secs(1));
+    clock.advance(Duration::from_secs(9 * 365 * 86_400));
+    assert_eq!(cache.purge_expired(), 1);
+    clock.advance(Duration::from_secs(365 * 86_400));
+    assert_eq!(cache.purge_expired(), 1);
+    assert_eq!(cache.peek(&"forever"), Some(&1));
+    assert_eq!(cache.check_invariants(), Ok(()));
+}
+
+#[test]
+fn test_removing_due_entry_keeps_due_list_intact() {
+    let clock = MockClock::new();
+    let mut cache = Cache::with_clock(5, clock.clone());
+    for key in 1..=5 {
+        cache.put_with_ttl(key, key, Duration::from_secs(1));
+    }
+    clock.advance(Duration::from_secs(2));
+    cache.put(10, 10);
+    assert_eq!(cache.len(), 5);
+
+    cache.remove(&2);
+    assert_eq!(cache.check_invariants(), Ok(()));
+    assert_eq!(cache.purge_expired(), 3);
+    assert_eq!(cache.len(), 1);
+    assert_eq!(cache.peek(&10), Some(&10));
+    assert_eq!(cache.check_invariants(), Ok(()));
+}
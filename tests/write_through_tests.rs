@@ -0,0 +1,77 @@
+mod common;
+
+use lru_cache::cache::traits::{CacheIterate, CacheLen};
+use lru_cache::cache::write_through::WriteThroughCache;
+use lru_cache::storage::file::FileStorage;
+use std::fs;
+
+fn entries(cache: &WriteThroughCache<String, i32>) -> Vec<(String, i32)> {
+    cache.iter().map(|(key, value)| (key.clone(), *value)).collect()
+}
+
+fn mutate(cache: &mut WriteThroughCache<String, i32>) {
+    for (i, key) in ["a", "b", "c", "d"].iter().enumerate() {
+        cache.put(key.to_string(), i as i32).unwrap();
+    }
+    assert_eq!(cache.get(&String::from("b")), Some(&1));
+    assert_eq!(cache.remove(&String::from("c")).unwrap(), Some(2));
+    assert_eq!(cache.remove(&String::from("missing")).unwrap(), None);
+    cache.put(String::from("a"), 10).unwrap();
+    cache.put(String::from("e"), 4).unwrap();
+}
+
+#[test]
+fn test_snapshot_mode_persists_without_explicit_save() {
+    let path = common::temp_path("write_through_snapshot.txt");
+    let expected = {
+        let mut cache = WriteThroughCache::open(&path, 3).unwrap();
+        assert!(path.exists());
+        mutate(&mut cache);
+        entries(&cache)
+    };
+    assert_eq!(expected.len(), 3);
+
+    let cache = WriteThroughCache::<String, i32>::open(&path, 3).unwrap();
+    assert_eq!(entries(&cache), expected);
+    assert_eq!(fs::read_to_string(&path).unwrap().matches("#crc32").count(), 1);
+    fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn test_append_mode_persists_without_explicit_save() {
+    let path = common::temp_path("write_through_append.txt");
+    let expected = {
+        let mut cache = WriteThroughCache::open_appending(&path, 3).unwrap();
+        mutate(&mut cache);
+        entries(&cache)
+    };
+    assert!(fs::read_to_string(&path).unwrap().contains("#del c\n"));
+
+    let mut cache = WriteThroughCache::<String, i32>::open_appending(&path, 3).unwrap();
+    assert_eq!(entries(&cache), expected);
+    cache.clear().unwrap();
+    cache.put(String::from("f"), 5).unwrap();
+    cache.flush().unwrap();
+    let content = fs::read_to_string(&path).unwrap();
+    assert_eq!(content.matches("#crc32").count(), 1);
+    drop(cache);
+
+    let (_, data) = FileStorage::load::<String, i32>(&path).unwrap();
+    assert_eq!(data, vec![(String::from("f"), 5)]);
+    fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn test_write_failure_is_reported() {
+    let dir = common::temp_path("write_through_dir");
+    let path = dir.join("cache.txt");
+    let mut cache = WriteThroughCache::<String, i32>::open(&path, 2).unwrap();
+    fs::remove_file(&path).unwrap();
+    fs::remove_dir(&dir).unwrap();
+    fs::write(&dir, "pas un répertoire").unwrap();
+
+    assert!(cache.put(String::from("a"), 1).is_err());
+    assert_eq!(cache.len(), 1);
+    assert!(cache.flush().is_err());
+    fs::remove_file(dir).unwrap();
+}